@@ -0,0 +1,13 @@
+-- Create spending limits table
+CREATE TABLE IF NOT EXISTS spending_limits (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category VARCHAR(100) NOT NULL,
+    monthly_limit DECIMAL(30,2) NOT NULL CHECK (monthly_limit > 0),
+    enforcement VARCHAR(10) NOT NULL DEFAULT 'soft' CHECK (enforcement IN ('hard', 'soft')),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- One limit per category per user
+CREATE UNIQUE INDEX IF NOT EXISTS idx_spending_limits_user_category ON spending_limits(user_id, category);
\ No newline at end of file
@@ -0,0 +1,12 @@
+-- Pending email address changes awaiting verification of the new address
+CREATE TABLE IF NOT EXISTS email_change_requests (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    new_email VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_change_requests_user_id ON email_change_requests(user_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_change_requests_token_hash ON email_change_requests(token_hash);
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Account deletion requests are honoured after a grace period
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deletion_scheduled_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_users_deletion_scheduled_at ON users(deletion_scheduled_at)
+WHERE deletion_scheduled_at IS NOT NULL;
\ No newline at end of file
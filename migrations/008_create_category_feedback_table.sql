@@ -0,0 +1,11 @@
+-- Corrections to suggested categories, used as training examples for categorization
+CREATE TABLE IF NOT EXISTS category_feedback (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    description TEXT NOT NULL,
+    suggested_category VARCHAR(100),
+    chosen_category VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_category_feedback_user_id ON category_feedback(user_id);
\ No newline at end of file
@@ -0,0 +1,12 @@
+-- Month-end pocket balances, seeded by imports so migrated users keep their history
+CREATE TABLE IF NOT EXISTS pocket_balance_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    pocket_id UUID NOT NULL REFERENCES pockets(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    snapshot_month DATE NOT NULL,
+    balance DECIMAL(15,2) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (pocket_id, snapshot_month)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pocket_balance_snapshots_user_id ON pocket_balance_snapshots(user_id);
\ No newline at end of file
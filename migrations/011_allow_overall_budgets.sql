@@ -0,0 +1,7 @@
+-- A budget with no category caps total spending for its period
+ALTER TABLE budgets ALTER COLUMN category DROP NOT NULL;
+
+-- NULL categories never collide in idx_budgets_unique_active, so overall budgets need their own index
+CREATE UNIQUE INDEX IF NOT EXISTS idx_budgets_unique_active_overall
+ON budgets(user_id, period_start, period_end)
+WHERE is_active = true AND category IS NULL;
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Archived pockets keep their transaction history but drop out of everyday views
+ALTER TABLE pockets ADD COLUMN IF NOT EXISTS archived BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX IF NOT EXISTS idx_pockets_user_archived ON pockets(user_id, archived);
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- User-arranged pocket layout: position within the list and an optional group heading
+ALTER TABLE pockets ADD COLUMN IF NOT EXISTS sort_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE pockets ADD COLUMN IF NOT EXISTS pocket_group VARCHAR(50);
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Adjustments record manual corrections to a pocket balance; their amount keeps its sign
+ALTER TABLE transactions DROP CONSTRAINT IF EXISTS transactions_transaction_type_check;
+ALTER TABLE transactions ADD CONSTRAINT transactions_transaction_type_check
+    CHECK (transaction_type IN ('income', 'expense', 'adjustment'));
\ No newline at end of file
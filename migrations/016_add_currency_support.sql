@@ -0,0 +1,20 @@
+-- Users report in a base currency; each pocket keeps the currency its amounts are recorded in
+ALTER TABLE users ADD COLUMN IF NOT EXISTS base_currency VARCHAR(3) NOT NULL DEFAULT 'IDR';
+ALTER TABLE pockets ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'IDR';
+
+-- Historical rates quoted as units of `currency` per 1 USD, loaded by an external feed
+CREATE TABLE IF NOT EXISTS exchange_rates (
+    currency VARCHAR(3) NOT NULL,
+    rate_date DATE NOT NULL,
+    usd_rate DECIMAL(20,10) NOT NULL CHECK (usd_rate > 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (currency, rate_date)
+);
+
+-- Snapshot balances restated into the owner's base currency at the month-end rate
+ALTER TABLE pocket_balance_snapshots ADD COLUMN IF NOT EXISTS converted_balance DECIMAL(15,2);
+ALTER TABLE pocket_balance_snapshots ADD COLUMN IF NOT EXISTS converted_currency VARCHAR(3);
+
+-- Restatement jobs report how far along they are
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS processed_items INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS total_items INTEGER;
\ No newline at end of file
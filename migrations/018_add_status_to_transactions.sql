@@ -0,0 +1,5 @@
+-- Future-dated transactions can wait as pending; only posted ones move balances and analytics
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'posted'
+    CHECK (status IN ('pending', 'posted', 'cancelled'));
+
+CREATE INDEX IF NOT EXISTS idx_transactions_pending_date ON transactions(transaction_date) WHERE status = 'pending';
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- Free-form annotations and integration data kept apart from the description
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS notes TEXT;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS metadata JSONB;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Opt-in monthly summary email; the last sent month keeps the job from mailing twice
+ALTER TABLE users ADD COLUMN IF NOT EXISTS monthly_digest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS last_digest_month DATE;
+
+CREATE INDEX IF NOT EXISTS idx_users_monthly_digest ON users(last_digest_month) WHERE monthly_digest;
\ No newline at end of file
@@ -0,0 +1,14 @@
+-- Read-only links exposing a single pocket's balance and trend without signing in
+CREATE TABLE IF NOT EXISTS pocket_share_tokens (
+    id UUID PRIMARY KEY,
+    pocket_id UUID NOT NULL REFERENCES pockets(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    label VARCHAR(100),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE,
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pocket_share_tokens_pocket_id ON pocket_share_tokens(pocket_id);
\ No newline at end of file
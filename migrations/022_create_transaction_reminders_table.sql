@@ -0,0 +1,15 @@
+-- Follow-ups attached to a transaction, such as an expected refund or an open dispute
+CREATE TABLE IF NOT EXISTS transaction_reminders (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    transaction_id BIGINT NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    note VARCHAR(500) NOT NULL,
+    remind_on DATE NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'done', 'dismissed')),
+    notified_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_reminders_transaction_id ON transaction_reminders(transaction_id);
+CREATE INDEX IF NOT EXISTS idx_transaction_reminders_due ON transaction_reminders(remind_on) WHERE status = 'open';
\ No newline at end of file
@@ -0,0 +1,17 @@
+-- Refunds claimed against an expense; once received, the credit is linked to the income transaction
+CREATE TABLE IF NOT EXISTS transaction_refunds (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    original_transaction_id BIGINT NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    refund_transaction_id BIGINT UNIQUE REFERENCES transactions(id) ON DELETE SET NULL,
+    requested_amount DECIMAL(15,2) NOT NULL CHECK (requested_amount > 0),
+    received_amount DECIMAL(15,2),
+    status VARCHAR(20) NOT NULL DEFAULT 'requested' CHECK (status IN ('requested', 'received', 'written_off')),
+    note VARCHAR(500),
+    resolved_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_refunds_original ON transaction_refunds(original_transaction_id);
+CREATE INDEX IF NOT EXISTS idx_transaction_refunds_user_status ON transaction_refunds(user_id, status);
\ No newline at end of file
@@ -0,0 +1,10 @@
+-- Display preferences; users without a row get the defaults applied by the API
+CREATE TABLE IF NOT EXISTS user_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    currency VARCHAR(3) NOT NULL,
+    locale VARCHAR(16) NOT NULL,
+    week_start VARCHAR(10) NOT NULL CHECK (week_start IN ('monday', 'sunday', 'saturday')),
+    timezone VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
\ No newline at end of file
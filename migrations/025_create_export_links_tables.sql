@@ -0,0 +1,27 @@
+-- Time-boxed, read-only links that hand a date range of transactions and reports to someone without an account
+CREATE TABLE IF NOT EXISTS export_links (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    label VARCHAR(100),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    from_date DATE NOT NULL,
+    to_date DATE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    CHECK (from_date <= to_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_links_user_id ON export_links(user_id);
+
+CREATE TABLE IF NOT EXISTS export_link_access_logs (
+    id BIGSERIAL PRIMARY KEY,
+    export_link_id UUID NOT NULL REFERENCES export_links(id) ON DELETE CASCADE,
+    resource VARCHAR(20) NOT NULL,
+    ip_address VARCHAR(45),
+    user_agent VARCHAR(512),
+    accessed_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_link_access_logs_link ON export_link_access_logs(export_link_id, accessed_at DESC);
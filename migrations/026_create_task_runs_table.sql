@@ -0,0 +1,16 @@
+-- One row per execution of a scheduled background task, scheduled or triggered by an admin
+CREATE TABLE IF NOT EXISTS task_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    task_name VARCHAR(50) NOT NULL,
+    trigger VARCHAR(20) NOT NULL CHECK (trigger IN ('schedule', 'manual')),
+    status VARCHAR(20) NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'succeeded', 'failed')),
+    items_processed INTEGER,
+    error TEXT,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_runs_task_name ON task_runs(task_name, started_at DESC);
+
+-- At most one run of a task is in flight, across every instance of the app
+CREATE UNIQUE INDEX IF NOT EXISTS idx_task_runs_one_running ON task_runs(task_name) WHERE status = 'running';
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Read-only analytics feeds polled by external dashboards with a per-feed token
+CREATE TABLE IF NOT EXISTS analytics_feeds (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    feed_type VARCHAR(40) NOT NULL CHECK (feed_type IN ('monthly_category_summary', 'monthly_cashflow')),
+    label VARCHAR(100),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_feeds_user_id ON analytics_feeds(user_id, created_at DESC);
\ No newline at end of file
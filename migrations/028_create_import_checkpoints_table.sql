@@ -0,0 +1,15 @@
+-- Progress of a transaction import committed in batches, so a retried upload resumes after the last committed batch
+CREATE TABLE IF NOT EXISTS import_checkpoints (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fingerprint VARCHAR(64) NOT NULL,
+    total_rows INTEGER NOT NULL,
+    rows_committed INTEGER NOT NULL DEFAULT 0,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Only unfinished imports are resumable; a finished file can be imported again on purpose
+CREATE UNIQUE INDEX IF NOT EXISTS idx_import_checkpoints_in_progress
+    ON import_checkpoints(user_id, fingerprint) WHERE completed_at IS NULL;
\ No newline at end of file
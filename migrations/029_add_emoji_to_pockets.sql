@@ -0,0 +1,2 @@
+-- The pocket code has always stored an emoji, but no migration ever created the column
+ALTER TABLE pockets ADD COLUMN IF NOT EXISTS emoji VARCHAR(10) NOT NULL DEFAULT '💰';
\ No newline at end of file
@@ -0,0 +1,14 @@
+-- Loans and credit cards the user is paying down; principal is the current outstanding balance
+CREATE TABLE IF NOT EXISTS debts (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    debt_type VARCHAR(20) NOT NULL CHECK (debt_type IN ('loan', 'credit_card')),
+    principal DECIMAL(15,2) NOT NULL CHECK (principal >= 0),
+    apr DECIMAL(6,3) NOT NULL CHECK (apr >= 0 AND apr <= 100),
+    minimum_payment DECIMAL(15,2) NOT NULL CHECK (minimum_payment > 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_debts_user_id ON debts(user_id);
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Closed periods of a budget, written when the budget rolls over to its next period
+CREATE TABLE IF NOT EXISTS budget_periods (
+    id BIGSERIAL PRIMARY KEY,
+    budget_id BIGINT NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    target_amount DECIMAL(15,2) NOT NULL,
+    actual_amount DECIMAL(15,2) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (budget_id, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_budgets_active_period_end ON budgets(period_end) WHERE is_active = true;
\ No newline at end of file
@@ -0,0 +1,12 @@
+-- Percentages of the target at which the owner is alerted; an empty list turns alerts off
+ALTER TABLE budgets ADD COLUMN IF NOT EXISTS alert_thresholds INTEGER[] NOT NULL DEFAULT '{50,80,100}';
+
+-- Thresholds already alerted in a budget period, so each one fires once per period
+CREATE TABLE IF NOT EXISTS budget_alerts (
+    budget_id BIGINT NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    period_start DATE NOT NULL,
+    threshold INTEGER NOT NULL,
+    spent_amount DECIMAL(15,2) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (budget_id, period_start, threshold)
+);
\ No newline at end of file
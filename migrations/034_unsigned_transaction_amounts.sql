@@ -0,0 +1,7 @@
+-- Income and expense amounts are unsigned, transaction_type gives their direction;
+-- only adjustments keep a sign
+UPDATE transactions SET amount = ABS(amount) WHERE transaction_type IN ('income', 'expense') AND amount < 0;
+
+ALTER TABLE transactions DROP CONSTRAINT IF EXISTS transactions_amount_sign_check;
+ALTER TABLE transactions ADD CONSTRAINT transactions_amount_sign_check
+    CHECK (transaction_type = 'adjustment' OR amount >= 0);
\ No newline at end of file
@@ -0,0 +1,27 @@
+-- Posted income and expense per user, calendar month (first day) and category, with received
+-- refunds netted against their original expense the way analytics nets them. Category summaries
+-- and monthly trends read these rows instead of scanning a user's whole history.
+CREATE TABLE IF NOT EXISTS monthly_category_aggregates (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    transaction_type VARCHAR(20) NOT NULL,
+    month DATE NOT NULL,
+    category VARCHAR(100) NOT NULL,
+    total_amount DECIMAL(30,2) NOT NULL,
+    transaction_count BIGINT NOT NULL,
+    PRIMARY KEY (user_id, transaction_type, month, category)
+);
+
+-- Months whose aggregates a write made stale. Writers mark them in the same database transaction
+-- as the write, and readers refresh a user's marked months before reading.
+CREATE TABLE IF NOT EXISTS monthly_aggregate_refreshes (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    month DATE NOT NULL,
+    marked_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, month)
+);
+
+-- Existing history is aggregated on each user's first read
+INSERT INTO monthly_aggregate_refreshes (user_id, month)
+SELECT DISTINCT user_id, date_trunc('month', transaction_date)::date
+FROM transactions
+ON CONFLICT DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,25 @@
+-- Cold storage for transactions older than TRANSACTION_ARCHIVE_AFTER_YEARS, partitioned by calendar
+-- month so reads bounded by transaction_date only scan the months they need. The archive job
+-- creates each month's partition before moving rows into it. The hot table stays unpartitioned:
+-- refunds and reminders reference transactions(id), and a unique key on a partitioned table has
+-- to include the partition column.
+CREATE TABLE IF NOT EXISTS transactions_archive (
+    id BIGINT NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id UUID,
+    description TEXT NOT NULL,
+    amount DECIMAL(30,2) NOT NULL,
+    category VARCHAR(100) NOT NULL,
+    transaction_type VARCHAR(20) NOT NULL,
+    transaction_date DATE NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    notes TEXT,
+    metadata JSONB,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    archived_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id, transaction_date)
+) PARTITION BY RANGE (transaction_date);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_archive_user_date ON transactions_archive(user_id, transaction_date);
+CREATE INDEX IF NOT EXISTS idx_transactions_archive_account ON transactions_archive(account_id);
\ No newline at end of file
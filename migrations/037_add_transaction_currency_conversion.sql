@@ -0,0 +1,9 @@
+-- Transactions recorded in another currency than their pocket's keep the amount as entered and
+-- the rate it was converted at; amount itself stays in the pocket's currency
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS original_amount DECIMAL(30,2);
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS original_currency VARCHAR(3);
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS exchange_rate DECIMAL(20,10);
+
+ALTER TABLE transactions_archive ADD COLUMN IF NOT EXISTS original_amount DECIMAL(30,2);
+ALTER TABLE transactions_archive ADD COLUMN IF NOT EXISTS original_currency VARCHAR(3);
+ALTER TABLE transactions_archive ADD COLUMN IF NOT EXISTS exchange_rate DECIMAL(20,10);
\ No newline at end of file
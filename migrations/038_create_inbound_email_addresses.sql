@@ -0,0 +1,11 @@
+-- One forwarding address per user for emailed receipts; rotating it replaces the row
+CREATE TABLE IF NOT EXISTS inbound_email_addresses (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    local_part VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Providers redeliver on timeouts, so each message creates at most one transaction per owner
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_email_message_id
+    ON transactions (user_id, (metadata->>'email_message_id'))
+    WHERE metadata ? 'email_message_id';
\ No newline at end of file
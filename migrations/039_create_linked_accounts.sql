@@ -0,0 +1,21 @@
+-- Accounts on other services that act for a user, such as a Telegram or Slack chat user
+CREATE TABLE IF NOT EXISTS linked_accounts (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(20) NOT NULL,
+    external_id VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (provider, external_id),
+    UNIQUE (user_id, provider)
+);
+
+-- One-time codes a user sends from the other service to prove they control that account
+CREATE TABLE IF NOT EXISTS account_link_codes (
+    code_hash VARCHAR(64) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(20) NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_link_codes_user_id ON account_link_codes(user_id);
\ No newline at end of file
@@ -0,0 +1,11 @@
+-- Hot and archived transactions together, for reads that span a user's history: analytics,
+-- digests, budgets and exports. Filters on the view reach both branches of the UNION ALL, so a
+-- transaction_date bound still prunes archive partitions.
+CREATE OR REPLACE VIEW all_transactions AS
+    SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata,
+           original_amount, original_currency, exchange_rate, created_at, updated_at
+    FROM transactions
+    UNION ALL
+    SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata,
+           original_amount, original_currency, exchange_rate, created_at, updated_at
+    FROM transactions_archive;
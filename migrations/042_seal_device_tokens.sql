@@ -0,0 +1,8 @@
+-- Push tokens are credentials for sending to a phone, so they are stored sealed with
+-- FIELD_ENCRYPTION_KEY and found by their SHA-256 instead. SQL can't seal the existing
+-- tokens; they are dropped, and apps register them again on their next launch.
+DELETE FROM devices;
+
+ALTER TABLE devices DROP COLUMN IF EXISTS token;
+ALTER TABLE devices ADD COLUMN IF NOT EXISTS token_hash VARCHAR(64) NOT NULL UNIQUE;
+ALTER TABLE devices ADD COLUMN IF NOT EXISTS sealed_token BYTEA NOT NULL;
@@ -0,0 +1,12 @@
+-- Shared pockets and budgets belong to their organization, so purging whoever created them
+-- only clears user_id. Personal rows must still have an owner; the account purge removes them
+-- before the user row, since one foreign key can't both cascade and set null.
+ALTER TABLE pockets ALTER COLUMN user_id DROP NOT NULL;
+ALTER TABLE pockets DROP CONSTRAINT IF EXISTS pockets_user_id_fkey;
+ALTER TABLE pockets ADD CONSTRAINT pockets_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE pockets ADD CONSTRAINT pockets_owner_check CHECK (organization_id IS NOT NULL OR user_id IS NOT NULL);
+
+ALTER TABLE budgets ALTER COLUMN user_id DROP NOT NULL;
+ALTER TABLE budgets DROP CONSTRAINT IF EXISTS budgets_user_id_fkey;
+ALTER TABLE budgets ADD CONSTRAINT budgets_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE budgets ADD CONSTRAINT budgets_owner_check CHECK (organization_id IS NOT NULL OR user_id IS NOT NULL);
@@ -0,0 +1,3 @@
+-- Tokens issued before this moment are rejected; the auth middleware reads it when the
+-- Redis copy of the marker is missing
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tokens_valid_after TIMESTAMP WITH TIME ZONE;
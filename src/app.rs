@@ -0,0 +1,331 @@
+use axum::{
+    extract::{DefaultBodyLimit, Extension},
+    Router,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+#[cfg(feature = "chaos")]
+use tracing::warn;
+
+use crate::categorization::model_from_name;
+use crate::config::{AppConfig, JwtConfig};
+use crate::middleware::{cors_layer, logging_layer, query_budget_middleware, request_timeout_middleware, security_layers};
+use crate::repositories::{PostgresAuthRepository, PostgresPocketRepository, PostgresUserRepository, PostgresTransactionRepository, PostgresBudgetRepository, PostgresSpendingLimitRepository, PostgresSessionRepository, PostgresCategorizationRepository, PostgresJobRepository, PostgresCurrencyRepository, PostgresAuditRepository, PostgresDigestRepository, PostgresShareTokenRepository, PostgresReminderRepository, PostgresRefundRepository, PostgresPreferenceRepository, PostgresExportLinkRepository, PostgresAnalyticsFeedRepository, PostgresTaskRunRepository, PostgresImportCheckpointRepository, PostgresInboundEmailRepository, PostgresLinkedAccountRepository, PostgresDeviceRepository, PostgresOrganizationRepository, PostgresDebtRepository, PostgresAnomalyRepository, PostgresMonthlyAggregateRepository, UnitOfWork};
+use crate::routes::{metrics_routes, auth_routes, pocket_routes, user_routes, transaction_routes, budget_routes, account_summary_routes, expense_analytics_routes, income_analytics_routes, spending_limit_routes, export_routes, session_routes, status_routes, categorization_routes, pocket_import_routes, job_routes, import_routes, pocket_adjustment_routes, currency_routes, audit_routes, share_token_routes, report_routes, reminder_routes, refund_routes, preference_routes, export_link_routes, analytics_feed_routes, email_template_routes, live_routes, task_routes, repair_routes, notification_routes, route_table_routes, organization_routes, debt_routes, subscription_analytics_routes, anomaly_routes, financial_health_routes, inbound_email_routes, chat_bot_routes};
+use crate::state::AppState;
+use crate::services::{AuthService, PocketService, UserService, TransactionService, BudgetService, OrganizationService, DebtService, SubscriptionAnalyticsService, AnomalyService, FinancialHealthService, AccountSummaryService, ExpenseAnalyticsService, IncomeAnalyticsService, SpendingLimitService, ExportService, SessionService, StatusService, CategorizationService, PocketImportService, PocketAdjustmentService, ImportService, InboundEmailService, ChatBotService, PushNotificationService, JobService, ReaggregationService, CurrencyService, AuditService, DigestService, ShareTokenService, ReportService, ReminderService, RefundService, PreferenceService, ExportLinkService, AnalyticsFeedService, SchedulerService, ScheduledTask, AccountPurgeWorker, PendingTransactionWorker, TransactionArchiveWorker, MonthlyDigestWorker, ReminderWorker, BudgetRolloverWorker, BudgetAlertWorker};
+use crate::utils::{CacheService, DistributedLock, EmailTemplates, EventBus, FieldCipher, Mailer, PoolMetrics, StatementMetrics, PasswordHasher, Argon2PasswordHasher, push_senders};
+
+// The fully wired application. The scheduler is handed back unstarted so the
+// binary can run background work while tests drive only the router.
+pub struct App {
+    pub router: Router,
+    pub scheduler: SchedulerService<PostgresTaskRunRepository>,
+    // Change notifications, for forwarding beyond this process
+    pub events: EventBus,
+    // Filled in by the connection monitor the binary starts, served on /metrics
+    pub pool_metrics: PoolMetrics,
+    // Turns budget alerts and anomaly events into phone notifications once the binary starts it
+    pub push_notifications: PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>,
+}
+
+// Builds every repository, service and route on top of already-connected pools
+pub fn build_app(config: &AppConfig, pool: PgPool, replica_pool: Option<PgPool>, cache_service: CacheService) -> Result<App, Box<dyn std::error::Error>> {
+    // Create mailer
+    let email_templates = EmailTemplates::new()?;
+    let mailer = Mailer::new(&config.email, email_templates.clone());
+
+    // Per-route statement counts, filled in by the statement budget middleware
+    let statement_metrics = StatementMetrics::new();
+    let pool_metrics = PoolMetrics::new();
+
+    // Create JWT config
+    let jwt_config = JwtConfig::new(&config.jwt_secret, &config.jwt)?;
+
+    // Seals the columns that hold credentials, such as device push tokens
+    let field_cipher = FieldCipher::new(&config.encryption)?;
+
+    // Create repositories
+    let auth_repository = PostgresAuthRepository::new(pool.clone());
+    let user_repository = PostgresUserRepository::new(pool.clone());
+    let pocket_repository = PostgresPocketRepository::new(pool.clone());
+    let transaction_repository = PostgresTransactionRepository::new(pool.clone()).with_replica(replica_pool.clone());
+    let budget_repository = PostgresBudgetRepository::new(pool.clone());
+    let spending_limit_repository = PostgresSpendingLimitRepository::new(pool.clone());
+    let session_repository = PostgresSessionRepository::new(pool.clone());
+    let categorization_repository = PostgresCategorizationRepository::new(pool.clone());
+    let job_repository = PostgresJobRepository::new(pool.clone());
+    let currency_repository = PostgresCurrencyRepository::new(pool.clone());
+    let audit_repository = PostgresAuditRepository::new(pool.clone()).with_replica(replica_pool);
+    let digest_repository = PostgresDigestRepository::new(pool.clone());
+    let share_token_repository = PostgresShareTokenRepository::new(pool.clone());
+    let reminder_repository = PostgresReminderRepository::new(pool.clone());
+    let refund_repository = PostgresRefundRepository::new(pool.clone());
+    let preference_repository = PostgresPreferenceRepository::new(pool.clone());
+    let export_link_repository = PostgresExportLinkRepository::new(pool.clone());
+    let analytics_feed_repository = PostgresAnalyticsFeedRepository::new(pool.clone());
+    let task_run_repository = PostgresTaskRunRepository::new(pool.clone());
+    let import_checkpoint_repository = PostgresImportCheckpointRepository::new(pool.clone());
+    let organization_repository = PostgresOrganizationRepository::new(pool.clone());
+    let debt_repository = PostgresDebtRepository::new(pool.clone());
+    let anomaly_repository = PostgresAnomalyRepository::new(pool.clone());
+    let monthly_aggregate_repository = PostgresMonthlyAggregateRepository::new(pool.clone());
+
+    // Create services
+    let event_bus = EventBus::new();
+    let session_service = SessionService::new(session_repository, jwt_config.token_ttl);
+    let password_hasher: Arc<dyn PasswordHasher> = Arc::new(Argon2PasswordHasher::default());
+    let auth_service = AuthService::new(auth_repository, jwt_config.clone(), session_service.clone(), mailer.clone(), cache_service.clone(), password_hasher.clone(), config.password_policy.clone());
+    let user_service = UserService::new(user_repository.clone(), mailer.clone(), password_hasher, config.password_policy.clone(), config.account_deletion_grace_days);
+    let pocket_service = PocketService::new(pocket_repository.clone(), event_bus.clone());
+    let share_token_service = ShareTokenService::new(share_token_repository, pocket_repository.clone());
+    let spending_limit_service = SpendingLimitService::new(spending_limit_repository);
+    let debt_service = DebtService::new(debt_repository);
+    let transaction_service = TransactionService::new(
+        transaction_repository.clone(),
+        pocket_repository.clone(),
+        currency_repository.clone(),
+        spending_limit_service.clone(),
+        UnitOfWork::new(pool.clone()),
+        event_bus.clone(),
+    );
+    let budget_service = BudgetService::new(budget_repository.clone(), event_bus.clone());
+    let organization_service = OrganizationService::new(organization_repository, user_repository.clone());
+    let account_summary_service = AccountSummaryService::new(pocket_repository.clone(), transaction_repository.clone());
+    let expense_analytics_service = ExpenseAnalyticsService::new(transaction_repository.clone(), monthly_aggregate_repository.clone());
+    let income_analytics_service = IncomeAnalyticsService::new(transaction_repository.clone(), monthly_aggregate_repository);
+    let subscription_analytics_service = SubscriptionAnalyticsService::new(transaction_repository.clone());
+    let anomaly_service = AnomalyService::new(anomaly_repository, event_bus.clone(), config.anomaly_alerts);
+    let financial_health_service = FinancialHealthService::new(pocket_repository.clone(), transaction_repository.clone(), budget_repository, currency_repository.clone());
+    let export_service = ExportService::new(transaction_repository.clone(), pocket_repository.clone(), currency_repository.clone());
+    let report_service = ReportService::new(transaction_repository.clone(), user_repository.clone());
+    let export_link_service = ExportLinkService::new(
+        export_link_repository,
+        user_repository.clone(),
+        export_service.clone(),
+        report_service.clone(),
+    );
+    let analytics_feed_service = AnalyticsFeedService::new(
+        analytics_feed_repository,
+        transaction_repository.clone(),
+        user_repository.clone(),
+    );
+    let refund_service = RefundService::new(refund_repository, transaction_repository.clone());
+    let pocket_import_service = PocketImportService::new(
+        pocket_repository.clone(),
+        transaction_repository.clone(),
+        UnitOfWork::new(pool.clone()),
+    );
+    let pocket_adjustment_service = PocketAdjustmentService::new(
+        pocket_repository.clone(),
+        transaction_repository.clone(),
+        UnitOfWork::new(pool.clone()),
+        DistributedLock::new(cache_service.clone()),
+    );
+    let import_service = ImportService::new(
+        transaction_repository,
+        pocket_repository.clone(),
+        import_checkpoint_repository,
+        UnitOfWork::new(pool.clone()),
+    );
+    let status_service = StatusService::new(pool.clone(), cache_service.clone());
+    let categorization_service = CategorizationService::new(
+        categorization_repository,
+        model_from_name(&config.categorization_provider),
+    );
+    let inbound_email_service = InboundEmailService::new(
+        PostgresInboundEmailRepository::new(pool.clone()),
+        categorization_service.clone(),
+        config.inbound_email.clone(),
+    );
+    let chat_bot_service = ChatBotService::new(
+        PostgresLinkedAccountRepository::new(pool.clone()),
+        categorization_service.clone(),
+        config.chat_bot.clone(),
+    );
+    let push_notification_service = PushNotificationService::new(
+        PostgresDeviceRepository::new(pool.clone(), field_cipher),
+        preference_repository.clone(),
+        push_senders(&config.push)?,
+    );
+    let audit_service = AuditService::new(audit_repository);
+    let digest_service = DigestService::new(digest_repository, mailer.clone());
+    let reminder_service = ReminderService::new(reminder_repository, mailer.clone());
+    let job_service = JobService::new(job_repository.clone());
+    let preference_service = PreferenceService::new(preference_repository, currency_repository.clone());
+    let currency_service = CurrencyService::new(
+        user_repository,
+        currency_repository,
+        job_repository.clone(),
+        cache_service.clone(),
+    );
+    let reaggregation_service = ReaggregationService::new(job_repository, pocket_repository.clone(), cache_service.clone());
+
+    // Periodic background work; every run is recorded and visible under /admin/tasks
+    let mut scheduled_tasks: Vec<Arc<dyn ScheduledTask>> = vec![
+        // Post pending transactions once their date arrives, every 15 minutes
+        Arc::new(PendingTransactionWorker::new(transaction_service.clone(), cache_service.clone(), 900)),
+        // Notify users about reminders that have come due, every 15 minutes
+        Arc::new(ReminderWorker::new(reminder_service.clone(), 900)),
+        // Mail last month's summary to users who opted in, checked hourly
+        Arc::new(MonthlyDigestWorker::new(digest_service, 3600)),
+        // Purge accounts whose deletion grace period has elapsed, checked hourly
+        Arc::new(AccountPurgeWorker::new(user_service.clone(), cache_service.clone(), 3600)),
+        // Close ended budget periods into their history and start the next period, checked hourly
+        Arc::new(BudgetRolloverWorker::new(budget_service.clone(), cache_service.clone(), 3600)),
+        // Alert owners whose spending reached one of a budget's thresholds, every 15 minutes
+        Arc::new(BudgetAlertWorker::new(budget_service.clone(), 900)),
+    ];
+    // Move settled transactions past the retention window to the archive, checked daily
+    if let Some(years) = config.transaction_archive_after_years {
+        scheduled_tasks.push(Arc::new(TransactionArchiveWorker::new(transaction_service.clone(), cache_service.clone(), years, 86400)));
+    }
+    let scheduler_service = SchedulerService::new(task_run_repository, cache_service.clone(), scheduled_tasks);
+
+    let state = AppState {
+        pool,
+        cache: cache_service.clone(),
+        jwt: jwt_config.clone(),
+        http: config.http.clone(),
+        metrics_config: config.metrics.clone(),
+        statement_metrics: statement_metrics.clone(),
+        pool_metrics: pool_metrics.clone(),
+        email_templates,
+        event_bus: event_bus.clone(),
+        #[cfg(feature = "graphql")]
+        graphql: crate::graphql::GraphqlService::new(
+            user_service.clone(),
+            pocket_service.clone(),
+            transaction_service.clone(),
+            budget_service.clone(),
+            account_summary_service.clone(),
+            expense_analytics_service.clone(),
+            income_analytics_service.clone(),
+        ),
+        auth: auth_service,
+        sessions: session_service.clone(),
+        users: user_service.clone(),
+        currency: currency_service,
+        preferences: preference_service,
+        pockets: pocket_service,
+        pocket_import: pocket_import_service,
+        pocket_adjustments: pocket_adjustment_service,
+        share_tokens: share_token_service,
+        transactions: transaction_service,
+        reminders: reminder_service,
+        refunds: refund_service,
+        budgets: budget_service,
+        organizations: organization_service,
+        spending_limits: spending_limit_service,
+        debts: debt_service,
+        account_summary: account_summary_service,
+        expense_analytics: expense_analytics_service,
+        income_analytics: income_analytics_service,
+        subscription_analytics: subscription_analytics_service,
+        anomalies: anomaly_service,
+        financial_health: financial_health_service,
+        exports: export_service,
+        reports: report_service,
+        export_links: export_link_service,
+        analytics_feeds: analytics_feed_service,
+        imports: import_service,
+        categorization: categorization_service,
+        inbound_email: inbound_email_service,
+        chat_bot: chat_bot_service,
+        push_notifications: push_notification_service.clone(),
+        jobs: job_service,
+        reaggregation: reaggregation_service,
+        audit: audit_service,
+        status: status_service,
+        scheduler: scheduler_service.clone(),
+    };
+
+    // Build application routes
+    let app = Router::new()
+        .merge(status_routes())
+        .merge(metrics_routes())
+        .merge(auth_routes())
+        .merge(session_routes())
+        .merge(user_routes())
+        .merge(currency_routes())
+        .merge(pocket_routes())
+        .merge(pocket_adjustment_routes())
+        .merge(share_token_routes())
+        .merge(transaction_routes())
+        .merge(reminder_routes())
+        .merge(refund_routes())
+        .merge(preference_routes())
+        .merge(export_link_routes())
+        .merge(analytics_feed_routes())
+        .merge(email_template_routes())
+        .merge(live_routes())
+        .merge(notification_routes())
+        .merge(task_routes())
+        .merge(repair_routes())
+        .merge(budget_routes())
+        .merge(organization_routes())
+        .merge(account_summary_routes())
+        .merge(expense_analytics_routes())
+        .merge(income_analytics_routes())
+        .merge(subscription_analytics_routes())
+        .merge(anomaly_routes())
+        .merge(financial_health_routes())
+        .merge(spending_limit_routes())
+        .merge(debt_routes())
+        .merge(export_routes())
+        .merge(report_routes())
+        .merge(categorization_routes())
+        .merge(chat_bot_routes())
+        .merge(job_routes())
+        .merge(audit_routes())
+        .merge(route_table_routes())
+        // Imports and forwarded emails carry whole files, so they may use the full upload allowance
+        .merge(
+            import_routes()
+                .merge(pocket_import_routes())
+                .merge(inbound_email_routes())
+                .layer(DefaultBodyLimit::max(config.http.max_upload_bytes)),
+        );
+
+    #[cfg(feature = "graphql")]
+    let app = app.merge(crate::routes::graphql_routes());
+
+    let app = app.with_state(state);
+
+    // Fault injection wraps every route, so an injected cache outage reaches middleware and handlers alike
+    #[cfg(feature = "chaos")]
+    let app = if std::env::var("APP_ENV").map(|env| env == "production").unwrap_or(false) {
+        warn!("Chaos feature compiled in but disabled because APP_ENV=production");
+        app
+    } else {
+        warn!("Chaos fault-injection middleware is enabled");
+        app.layer(axum::middleware::from_fn(crate::middleware::chaos_middleware))
+    };
+
+    let app = app
+        .layer(axum::middleware::from_fn(request_timeout_middleware))
+        .layer(axum::middleware::from_fn(query_budget_middleware))
+        .layer(cors_layer())
+        .layer(logging_layer())
+        // Middleware runs without state and reads what it needs from request extensions
+        .layer(Extension(jwt_config))
+        .layer(Extension(session_service))
+        .layer(Extension(user_service))
+        .layer(Extension(config.balance_visibility.clone()))
+        .layer(Extension(config.metrics.clone()))
+        .layer(Extension(config.admin.clone()))
+        .layer(Extension(config.http.clone()))
+        .layer(Extension(statement_metrics))
+        .layer(Extension(cache_service));
+
+    let app = security_layers(app, &config.http);
+
+    Ok(App {
+        router: app,
+        scheduler: scheduler_service,
+        events: event_bus,
+        pool_metrics,
+        push_notifications: push_notification_service,
+    })
+
+}
\ No newline at end of file
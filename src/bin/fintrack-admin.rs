@@ -0,0 +1,244 @@
+use std::collections::HashSet;
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use serde::Serialize;
+use sqlx::PgPool;
+use tracing_subscriber::filter::LevelFilter;
+use uuid::Uuid;
+
+use rust_fintrack_backend::{
+    config::{create_pool, AppConfig, JwtConfig},
+    handlers::{mark_sessions_revoked, revoke_tokens_issued_before},
+    models::RegisterRequest,
+    repositories::{PostgresAuthRepository, PostgresPocketRepository, PostgresSessionRepository, PostgresUserRepository},
+    services::{AuthService, PocketService, SessionService, UserService},
+    utils::{
+        user_cache_key, validate_data,
+        Argon2PasswordHasher, CacheService, EmailTemplates, EventBus, Mailer, PasswordHasher,
+    },
+};
+
+const USAGE: &str = "Usage: fintrack-admin <command> [args]
+
+Commands:
+  create-user <email> <name>         Create an account; the password is read from stdin
+  reset-password <email>             Set a new password from stdin and sign out every session
+  migrate                            Apply pending database migrations
+  flush-cache <email>                Drop the user's cached profile and derived views
+  repair-balances [--dry-run] [email]
+                                     Replay posted transactions and correct drifted pocket
+                                     balances for one user or everyone
+  export-user <email> [file]         Write the user's data export as JSON to a file or stdout";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(command) = args.first() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    // Logs go to stderr so an export on stdout stays clean JSON
+    tracing_subscriber::fmt()
+        .with_max_level(LevelFilter::WARN)
+        .with_writer(io::stderr)
+        .init();
+
+    match run(command, &args[1..]).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: &str, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    match (command, args) {
+        ("create-user", [email, name]) => {
+            let admin = Admin::connect().await?;
+            let password = read_password("Password: ")?;
+            let request = RegisterRequest { name: name.clone(), email: email.clone(), password };
+            validate_data(&request)?;
+
+            let user = admin.auth_service().create_account(&request).await?;
+            println!("Created user {} ({})", user.email, user.id);
+        }
+        ("reset-password", [email]) => {
+            let admin = Admin::connect().await?;
+            let user = admin.user_service().get_user_by_email(email).await?;
+            let password = read_password("New password: ")?;
+            admin.user_service().reset_password(user.id, &password).await?;
+            let revoked = admin.sign_out_everywhere(user.id).await?;
+            println!("Reset password for {} and revoked {} sessions", user.email, revoked);
+        }
+        ("migrate", []) => {
+            // Loads .env and CONFIG_FILE so create_pool finds DATABASE_URL
+            AppConfig::from_env()?;
+            let pool = create_pool().await?;
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            println!("Migrations are up to date");
+        }
+        ("flush-cache", [email]) => {
+            let admin = Admin::connect().await?;
+            let user = admin.user_service().get_user_by_email(email).await?;
+            admin.cache.delete(&user_cache_key(&user.id)).await;
+            let flushed = admin.cache.invalidate_user_derived(&user.id).await;
+            // Each server's in-process tier still serves its copy until the local TTL runs out
+            println!("Flushed cached profile and {} derived entries for {}", flushed, user.email);
+        }
+        ("repair-balances", args) if args.len() <= 2 => {
+            let dry_run = args.iter().any(|arg| arg == "--dry-run");
+            let emails: Vec<&String> = args.iter().filter(|arg| *arg != "--dry-run").collect();
+            if emails.len() > 1 {
+                return Err(USAGE.into());
+            }
+
+            let admin = Admin::connect().await?;
+            let user_id = match emails.first() {
+                Some(email) => Some(admin.user_service().get_user_by_email(email).await?.id),
+                None => None,
+            };
+            let report = admin.pocket_service().repair_balances(user_id, dry_run).await?;
+            if !dry_run {
+                let users: HashSet<Uuid> = report.corrections.iter().filter_map(|correction| correction.owner()).collect();
+                for user_id in &users {
+                    admin.cache.invalidate_user_derived(user_id).await;
+                }
+            }
+
+            for correction in &report.corrections {
+                let holder = match (correction.organization_id, correction.user_id) {
+                    (Some(organization_id), _) => format!("organization {}", organization_id),
+                    (None, Some(user_id)) => format!("user {}", user_id),
+                    (None, None) => "no owner".to_string(),
+                };
+                println!(
+                    "{} ({}, {}): stored {}, replayed {}, drift {}",
+                    correction.name,
+                    correction.pocket_id,
+                    holder,
+                    correction.previous_balance,
+                    correction.balance,
+                    correction.drift
+                );
+            }
+            println!(
+                "{} of {} pockets drifted{}",
+                report.corrections.len(),
+                report.pockets_checked,
+                match (dry_run, report.corrections.is_empty()) {
+                    (true, _) => "; nothing was changed (dry run)",
+                    (false, false) => " and were corrected",
+                    (false, true) => "",
+                }
+            );
+        }
+        ("export-user", [email, rest @ ..]) if rest.len() <= 1 => {
+            let admin = Admin::connect().await?;
+            let user = admin.user_service().get_user_by_email(email).await?;
+            let export = admin.user_service().export_user_data(user.id).await?;
+            match rest.first() {
+                Some(path) => {
+                    write_json(std::fs::File::create(path)?, &export)?;
+                    eprintln!("Wrote export for {} to {}", user.email, path);
+                }
+                None => write_json(io::stdout().lock(), &export)?,
+            }
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+
+// The same wiring build_app does, limited to what the commands need
+struct Admin {
+    config: AppConfig,
+    pool: PgPool,
+    cache: CacheService,
+    jwt_config: JwtConfig,
+    mailer: Mailer,
+    hasher: Arc<dyn PasswordHasher>,
+}
+
+impl Admin {
+    async fn connect() -> Result<Self, Box<dyn std::error::Error>> {
+        let config = AppConfig::from_env()?;
+
+        let pool = create_pool().await?;
+        let cache = CacheService::new(&config.redis).await;
+        let jwt_config = JwtConfig::new(&config.jwt_secret, &config.jwt)?;
+        let mailer = Mailer::new(&config.email, EmailTemplates::new()?);
+
+        Ok(Self {
+            config,
+            pool,
+            cache,
+            jwt_config,
+            mailer,
+            hasher: Arc::new(Argon2PasswordHasher::default()),
+        })
+    }
+
+    fn session_service(&self) -> SessionService<PostgresSessionRepository> {
+        SessionService::new(PostgresSessionRepository::new(self.pool.clone()), self.jwt_config.token_ttl)
+    }
+
+    fn auth_service(&self) -> AuthService<PostgresAuthRepository, PostgresSessionRepository> {
+        AuthService::new(
+            PostgresAuthRepository::new(self.pool.clone()),
+            self.jwt_config.clone(),
+            self.session_service(),
+            self.mailer.clone(),
+            self.cache.clone(),
+            self.hasher.clone(),
+            self.config.password_policy.clone(),
+        )
+    }
+
+    fn user_service(&self) -> UserService<PostgresUserRepository> {
+        UserService::new(
+            PostgresUserRepository::new(self.pool.clone()),
+            self.mailer.clone(),
+            self.hasher.clone(),
+            self.config.password_policy.clone(),
+            self.config.account_deletion_grace_days,
+        )
+    }
+
+    fn pocket_service(&self) -> PocketService<PostgresPocketRepository> {
+        PocketService::new(PostgresPocketRepository::new(self.pool.clone()), EventBus::new())
+    }
+
+    // Mirrors the change-password handler: revoke every session and reject older tokens
+    async fn sign_out_everywhere(&self, user_id: Uuid) -> Result<usize, Box<dyn std::error::Error>> {
+        let revoked = self.session_service().revoke_all_sessions(user_id).await?;
+        mark_sessions_revoked(&self.cache, &self.jwt_config, &revoked).await;
+
+        let revoked_before = chrono::Utc::now().timestamp();
+        revoke_tokens_issued_before(&self.session_service(), &self.cache, &self.jwt_config, user_id, revoked_before).await?;
+        self.cache.delete(&user_cache_key(&user_id)).await;
+
+        Ok(revoked.len())
+    }
+}
+
+// First line of stdin, so the password never lands in shell history or `ps`
+fn read_password(prompt: &str) -> io::Result<String> {
+    eprint!("{}", prompt);
+    io::stderr().flush()?;
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn write_json<W: Write, T: Serialize>(mut writer: W, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    serde_json::to_writer_pretty(&mut writer, value)?;
+    writeln!(writer)?;
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use super::{normalize_description, CategoryModel};
+use crate::models::{CategorySuggestion, LabeledExample};
+
+const EMBEDDING_DIMENSIONS: usize = 256;
+const NEIGHBOURS: usize = 5;
+// Explicit corrections say more about the user's intent than old transactions
+const FEEDBACK_WEIGHT: f64 = 2.0;
+
+// Local nearest-neighbour model over hashed word and character-trigram
+// embeddings. It needs no training step: the user's own labelled history is
+// the model, so feedback takes effect on the next suggestion.
+pub struct EmbeddingModel {
+    min_similarity: f64,
+}
+
+impl Default for EmbeddingModel {
+    fn default() -> Self {
+        Self { min_similarity: 0.3 }
+    }
+}
+
+#[async_trait::async_trait]
+impl CategoryModel for EmbeddingModel {
+    fn name(&self) -> &'static str {
+        "embedding"
+    }
+
+    async fn suggest(&self, description: &str, examples: &[LabeledExample]) -> Option<CategorySuggestion> {
+        let query = embed(description)?;
+
+        let mut neighbours: Vec<(f64, &LabeledExample)> = examples
+            .iter()
+            .filter_map(|example| {
+                let similarity = cosine(&query, &embed(&example.description)?);
+                (similarity >= self.min_similarity).then_some((similarity, example))
+            })
+            .collect();
+        neighbours.sort_by(|a, b| b.0.total_cmp(&a.0));
+        neighbours.truncate(NEIGHBOURS);
+
+        if neighbours.is_empty() {
+            return None;
+        }
+
+        let mut votes: HashMap<&str, (f64, f64)> = HashMap::new();
+        let mut total_weight = 0.0;
+        for (similarity, example) in &neighbours {
+            let weight = similarity * if example.from_feedback { FEEDBACK_WEIGHT } else { 1.0 };
+            let entry = votes.entry(example.category.as_str()).or_insert((0.0, 0.0));
+            entry.0 += weight;
+            entry.1 = entry.1.max(*similarity);
+            total_weight += weight;
+        }
+
+        let (category, (weight, best_similarity)) = votes
+            .into_iter()
+            .max_by(|a, b| a.1.0.total_cmp(&b.1.0))?;
+
+        // Agreement among neighbours scaled by how close the best match is
+        let confidence = (weight / total_weight) * best_similarity;
+
+        Some(CategorySuggestion {
+            category: category.to_string(),
+            confidence: (confidence * 100.0).round() / 100.0,
+            source: self.name().to_string(),
+        })
+    }
+}
+
+fn embed(description: &str) -> Option<Vec<f64>> {
+    let normalized = normalize_description(description);
+    if normalized.is_empty() {
+        return None;
+    }
+
+    let mut vector = vec![0.0; EMBEDDING_DIMENSIONS];
+    for word in normalized.split(' ') {
+        vector[bucket(word)] += 1.0;
+
+        let padded: Vec<char> = format!("#{}#", word).chars().collect();
+        for trigram in padded.windows(3) {
+            vector[bucket(&trigram.iter().collect::<String>())] += 0.5;
+        }
+    }
+
+    let norm = vector.iter().map(|value| value * value).sum::<f64>().sqrt();
+    if norm == 0.0 {
+        return None;
+    }
+    Some(vector.into_iter().map(|value| value / norm).collect())
+}
+
+fn cosine(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// FNV-1a, stable across runs unlike the std hasher
+fn bucket(token: &str) -> usize {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in token.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    (hash % EMBEDDING_DIMENSIONS as u64) as usize
+}
\ No newline at end of file
@@ -0,0 +1,31 @@
+pub mod embedding;
+pub mod rules;
+
+pub use embedding::*;
+pub use rules::*;
+
+use crate::models::{CategorySuggestion, LabeledExample};
+
+// A pluggable source of category suggestions. Implementations can be local
+// models or adapters for an external service.
+#[async_trait::async_trait]
+pub trait CategoryModel: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn suggest(&self, description: &str, examples: &[LabeledExample]) -> Option<CategorySuggestion>;
+}
+
+pub fn model_from_name(name: &str) -> Option<Box<dyn CategoryModel>> {
+    match name.to_lowercase().as_str() {
+        "embedding" => Some(Box::new(EmbeddingModel::default())),
+        _ => None,
+    }
+}
+
+pub(crate) fn normalize_description(description: &str) -> String {
+    description
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use super::normalize_description;
+use crate::models::{CategorySuggestion, LabeledExample};
+
+// Keyword rules for common merchants and bill types
+const EXPENSE_RULES: &[(&str, &[&str])] = &[
+    ("Food", &["restaurant", "cafe", "coffee", "starbucks", "lunch", "dinner", "breakfast", "pizza", "burger", "mcdonald", "kfc", "gofood", "grabfood", "bakery", "snack"]),
+    ("Groceries", &["grocery", "groceries", "supermarket", "market", "indomaret", "alfamart", "walmart"]),
+    ("Transport", &["uber", "grab", "gojek", "taxi", "fuel", "gas", "petrol", "parking", "toll", "train", "bus", "mrt", "krl"]),
+    ("Bills", &["electricity", "electric", "pln", "water", "internet", "wifi", "phone", "pulsa", "insurance", "rent"]),
+    ("Entertainment", &["netflix", "spotify", "cinema", "movie", "concert", "game", "steam", "youtube"]),
+    ("Shopping", &["amazon", "tokopedia", "shopee", "lazada", "mall", "clothes", "shoes"]),
+    ("Health", &["pharmacy", "apotek", "doctor", "hospital", "clinic", "dentist", "medicine"]),
+];
+
+const INCOME_RULES: &[(&str, &[&str])] = &[
+    ("Salary", &["salary", "payroll", "gaji", "wage"]),
+    ("Bonus", &["bonus", "thr", "incentive"]),
+    ("Investment", &["dividend", "interest", "bunga", "coupon"]),
+    ("Refund", &["refund", "cashback", "reimbursement"]),
+];
+
+const HISTORY_MATCH_CONFIDENCE: f64 = 0.9;
+const KEYWORD_MATCH_CONFIDENCE: f64 = 0.6;
+
+// Deterministic fallback used when no model is configured or it is unsure
+#[derive(Clone, Default)]
+pub struct RulesEngine;
+
+impl RulesEngine {
+    pub fn suggest(&self, description: &str, transaction_type: Option<&str>, examples: &[LabeledExample]) -> Option<CategorySuggestion> {
+        let normalized = normalize_description(description);
+        if normalized.is_empty() {
+            return None;
+        }
+
+        // The same description was categorized before
+        if let Some(example) = examples
+            .iter()
+            .find(|example| normalize_description(&example.description) == normalized)
+        {
+            return Some(suggestion(&example.category, HISTORY_MATCH_CONFIDENCE));
+        }
+
+        let rules = match transaction_type {
+            Some("income") => INCOME_RULES,
+            _ => EXPENSE_RULES,
+        };
+
+        let words: Vec<&str> = normalized.split(' ').collect();
+        let (category, _) = rules
+            .iter()
+            .find(|(_, keywords)| words.iter().any(|word| keywords.contains(word)))?;
+
+        // Prefer the user's own spelling of the category if they already use it
+        let category = examples
+            .iter()
+            .find(|example| example.category.eq_ignore_ascii_case(category))
+            .map(|example| example.category.as_str())
+            .unwrap_or(category);
+
+        Some(suggestion(category, KEYWORD_MATCH_CONFIDENCE))
+    }
+}
+
+fn suggestion(category: &str, confidence: f64) -> CategorySuggestion {
+    CategorySuggestion {
+        category: category.to_string(),
+        confidence,
+        source: "rules".to_string(),
+    }
+}
\ No newline at end of file
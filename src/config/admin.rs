@@ -0,0 +1,24 @@
+use crate::config::config_var;
+
+#[derive(Debug, Clone)]
+pub struct AdminConfig {
+    // Accounts allowed to reach /admin endpoints, read from the comma-separated ADMIN_EMAILS
+    pub emails: Vec<String>,
+}
+
+impl AdminConfig {
+    pub fn from_env() -> Self {
+        let emails = config_var("ADMIN_EMAILS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|email| email.trim().to_lowercase())
+            .filter(|email| !email.is_empty())
+            .collect();
+
+        Self { emails }
+    }
+
+    pub fn is_admin(&self, email: &str) -> bool {
+        self.emails.iter().any(|admin| admin.eq_ignore_ascii_case(email))
+    }
+}
\ No newline at end of file
@@ -1,32 +1,232 @@
-use std::env;
-use crate::config::RedisConfig;
+use lettre::message::Mailbox;
+use std::fmt;
+use std::path::Path;
 
-#[derive(Debug, Clone)]
+use crate::config::{config_var, load_config_file, required_config_var, AdminConfig, BalanceVisibilityConfig, ChatBotConfig, EmailConfig, EncryptionConfig, EventStreamConfig, HttpConfig, InboundEmailConfig, JwtSettings, MetricsConfig, PasswordPolicyConfig, PoolMonitorConfig, PushConfig, RedisConfig};
+use crate::config::redact::{redact_url, REDACTED};
+
+// RFC 7518 asks for an HS256 key at least as long as the hash output
+const MIN_HS256_SECRET_BYTES: usize = 32;
+
+#[derive(Clone)]
 pub struct AppConfig {
     pub database_url: String,
     pub jwt_secret: String,
+    pub jwt: JwtSettings,
     pub port: u16,
     pub host: String,
     pub redis: RedisConfig,
+    pub email: EmailConfig,
+    pub inbound_email: InboundEmailConfig,
+    pub chat_bot: ChatBotConfig,
+    pub push: PushConfig,
+    pub balance_visibility: BalanceVisibilityConfig,
+    pub metrics: MetricsConfig,
+    pub admin: AdminConfig,
+    pub password_policy: PasswordPolicyConfig,
+    pub event_stream: EventStreamConfig,
+    pub http: HttpConfig,
+    pub pool_monitor: PoolMonitorConfig,
+    pub encryption: EncryptionConfig,
+    // Push an anomaly event when a new expense is far above its category's usual amount
+    pub anomaly_alerts: bool,
+    pub account_deletion_grace_days: i64,
+    pub categorization_provider: String,
+    // Move settled transactions older than this many years to the archive; unset keeps them all hot
+    pub transaction_archive_after_years: Option<u32>,
 }
 
 impl AppConfig {
     pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
         dotenv::dotenv().ok();
+        load_config_file()?;
 
-        Ok(Self {
-            database_url: env::var("DATABASE_URL")?,
-            jwt_secret: env::var("JWT_SECRET")?,
-            port: env::var("PORT")
+        let config = Self {
+            database_url: required_config_var("DATABASE_URL")?,
+            jwt_secret: required_config_var("JWT_SECRET")?,
+            jwt: JwtSettings::from_env(),
+            port: config_var("PORT")
                 .unwrap_or_else(|_| "3000".to_string())
                 .parse()?,
-            host: env::var("HOST")
+            host: config_var("HOST")
                 .unwrap_or_else(|_| "0.0.0.0".to_string()),
             redis: RedisConfig::from_env(),
-        })
+            email: EmailConfig::from_env(),
+            inbound_email: InboundEmailConfig::from_env(),
+            chat_bot: ChatBotConfig::from_env(),
+            push: PushConfig::from_env(),
+            balance_visibility: BalanceVisibilityConfig::from_env(),
+            metrics: MetricsConfig::from_env(),
+            admin: AdminConfig::from_env(),
+            password_policy: PasswordPolicyConfig::from_env(),
+            event_stream: EventStreamConfig::from_env(),
+            http: HttpConfig::from_env(),
+            pool_monitor: PoolMonitorConfig::from_env(),
+            encryption: EncryptionConfig::from_env(),
+            anomaly_alerts: config_var("ANOMALY_ALERTS_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            account_deletion_grace_days: config_var("ACCOUNT_DELETION_GRACE_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()?,
+            categorization_provider: config_var("CATEGORIZATION_PROVIDER")
+                .unwrap_or_else(|_| "embedding".to_string()),
+            transaction_archive_after_years: match config_var("TRANSACTION_ARCHIVE_AFTER_YEARS") {
+                Ok(years) if !years.trim().is_empty() => Some(years.trim().parse()?),
+                _ => None,
+            },
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Reports every problem at once so a broken deploy is fixed in one round
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+
+        if !(self.database_url.starts_with("postgres://") || self.database_url.starts_with("postgresql://")) {
+            problems.push("DATABASE_URL must be a postgres:// or postgresql:// URL".to_string());
+        }
+
+        problems.extend(self.jwt.invalid.iter().cloned());
+        if self.jwt.token_ttl_hours <= 0 {
+            problems.push("JWT_TTL_HOURS must be greater than 0".to_string());
+        }
+
+        match self.jwt.algorithm.as_str() {
+            "HS256" => {
+                if self.jwt_secret.len() < MIN_HS256_SECRET_BYTES {
+                    problems.push(format!("JWT_SECRET must be at least {} bytes for HS256", MIN_HS256_SECRET_BYTES));
+                }
+            }
+            "RS256" | "EdDSA" => {
+                for (name, path) in [
+                    ("JWT_PRIVATE_KEY_PATH", &self.jwt.private_key_path),
+                    ("JWT_PUBLIC_KEY_PATH", &self.jwt.public_key_path),
+                ] {
+                    match path {
+                        Some(path) if !Path::new(path).is_file() => {
+                            problems.push(format!("{} '{}' does not exist", name, path));
+                        }
+                        Some(_) => {}
+                        None => problems.push(format!("JWT_ALGORITHM={} needs {}", self.jwt.algorithm, name)),
+                    }
+                }
+            }
+            other => problems.push(format!("Unsupported JWT_ALGORITHM '{}', expected HS256, RS256 or EdDSA", other)),
+        }
+
+        if self.redis.enabled && self.redis.addr.trim().is_empty() {
+            problems.push("REDIS_ADDR must be set while REDIS_ENABLED is true".to_string());
+        }
+
+        if self.email.enabled {
+            if self.email.smtp_host.trim().is_empty() {
+                problems.push("SMTP_HOST must be set while EMAIL_ENABLED is true".to_string());
+            }
+            if self.email.from_address.parse::<Mailbox>().is_err() {
+                problems.push(format!("EMAIL_FROM '{}' is not a valid address", self.email.from_address));
+            }
+        }
+
+        if self.inbound_email.is_enabled() && self.inbound_email.mailgun_signing_key.is_none() && self.inbound_email.ses_secret.is_none() {
+            problems.push("INBOUND_EMAIL_DOMAIN needs MAILGUN_WEBHOOK_SIGNING_KEY or SES_WEBHOOK_SECRET".to_string());
+        }
+
+        problems.extend(self.push.invalid.iter().cloned());
+        for (name, path) in [
+            ("FCM_SERVICE_ACCOUNT_PATH", &self.push.fcm_service_account_path),
+            ("APNS_KEY_PATH", &self.push.apns_key_path),
+        ] {
+            if let Some(path) = path.as_ref().filter(|path| !Path::new(path).is_file()) {
+                problems.push(format!("{} '{}' does not exist", name, path));
+            }
+        }
+        if self.push.apns_enabled() {
+            for (name, value) in [
+                ("APNS_KEY_ID", &self.push.apns_key_id),
+                ("APNS_TEAM_ID", &self.push.apns_team_id),
+                ("APNS_TOPIC", &self.push.apns_topic),
+            ] {
+                if value.is_none() {
+                    problems.push(format!("APNS_KEY_PATH needs {}", name));
+                }
+            }
+        }
+
+        if self.http.max_body_bytes == 0 {
+            problems.push("HTTP_MAX_BODY_BYTES must be greater than 0".to_string());
+        }
+        if self.http.request_timeout_seconds == 0 {
+            problems.push("HTTP_REQUEST_TIMEOUT_SECONDS must be greater than 0".to_string());
+        }
+        for proxy in &self.http.trusted_proxies {
+            if proxy.parse::<std::net::IpAddr>().is_err() {
+                problems.push(format!("TRUSTED_PROXIES entry '{}' is not an IP address", proxy));
+            }
+        }
+
+        if self.push.enabled() && self.encryption.key.is_none() {
+            problems.push("FIELD_ENCRYPTION_KEY must be set while push notifications are enabled, device push tokens are stored sealed with it".to_string());
+        }
+        if let Err(problem) = self.encryption.decoded_keys() {
+            problems.push(problem);
+        }
+
+        if self.account_deletion_grace_days < 0 {
+            problems.push("ACCOUNT_DELETION_GRACE_DAYS must not be negative".to_string());
+        }
+
+        if self.transaction_archive_after_years == Some(0) {
+            problems.push("TRANSACTION_ARCHIVE_AFTER_YEARS must be at least 1".to_string());
+        }
+
+        // model_from_name treats anything unknown as "no model", which would hide a typo
+        if !matches!(self.categorization_provider.to_lowercase().as_str(), "embedding" | "none") {
+            problems.push(format!(
+                "Unknown CATEGORIZATION_PROVIDER '{}', expected embedding or none",
+                self.categorization_provider
+            ));
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(format!("Invalid configuration:\n  - {}", problems.join("\n  - ")))
     }
 
     pub fn server_address(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+}
+
+impl fmt::Debug for AppConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppConfig")
+            .field("database_url", &redact_url(&self.database_url))
+            .field("jwt_secret", &REDACTED)
+            .field("jwt", &self.jwt)
+            .field("port", &self.port)
+            .field("host", &self.host)
+            .field("redis", &self.redis)
+            .field("email", &self.email)
+            .field("inbound_email", &self.inbound_email)
+            .field("chat_bot", &self.chat_bot)
+            .field("push", &self.push)
+            .field("balance_visibility", &self.balance_visibility)
+            .field("metrics", &self.metrics)
+            .field("admin", &self.admin)
+            .field("password_policy", &self.password_policy)
+            .field("event_stream", &self.event_stream)
+            .field("http", &self.http)
+            .field("pool_monitor", &self.pool_monitor)
+            .field("encryption", &self.encryption)
+            .field("anomaly_alerts", &self.anomaly_alerts)
+            .field("account_deletion_grace_days", &self.account_deletion_grace_days)
+            .field("categorization_provider", &self.categorization_provider)
+            .field("transaction_archive_after_years", &self.transaction_archive_after_years)
+            .finish()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,35 @@
+use crate::config::config_var;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HiddenBalanceMode {
+    Mask,
+    Omit,
+}
+
+#[derive(Debug, Clone)]
+pub struct BalanceVisibilityConfig {
+    pub mode: HiddenBalanceMode,
+    pub reveal_max_auth_age_secs: i64,
+}
+
+impl BalanceVisibilityConfig {
+    pub fn from_env() -> Self {
+        let mode = match config_var("HIDDEN_BALANCE_MODE")
+            .unwrap_or_else(|_| "mask".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "omit" => HiddenBalanceMode::Omit,
+            _ => HiddenBalanceMode::Mask,
+        };
+        let reveal_max_auth_age_secs = config_var("BALANCE_REVEAL_MAX_AUTH_AGE_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .unwrap_or(300);
+
+        Self {
+            mode,
+            reveal_max_auth_age_secs,
+        }
+    }
+}
\ No newline at end of file
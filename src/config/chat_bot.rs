@@ -0,0 +1,31 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_secret};
+
+// Chat bots that log expenses for linked users. Each bot's webhook is only served while its
+// secret is set.
+#[derive(Clone)]
+pub struct ChatBotConfig {
+    // The secret_token given to Telegram's setWebhook, echoed back on every update
+    pub telegram_secret_token: Option<String>,
+    // The Slack app's signing secret, checked against X-Slack-Signature
+    pub slack_signing_secret: Option<String>,
+}
+
+impl ChatBotConfig {
+    pub fn from_env() -> Self {
+        Self {
+            telegram_secret_token: config_var("TELEGRAM_WEBHOOK_SECRET").ok().filter(|secret| !secret.is_empty()),
+            slack_signing_secret: config_var("SLACK_SIGNING_SECRET").ok().filter(|secret| !secret.is_empty()),
+        }
+    }
+}
+
+impl fmt::Debug for ChatBotConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatBotConfig")
+            .field("telegram_secret_token", &redact_secret(&self.telegram_secret_token))
+            .field("slack_signing_secret", &redact_secret(&self.slack_signing_secret))
+            .finish()
+    }
+}
\ No newline at end of file
@@ -1,46 +1,64 @@
+use log::LevelFilter;
 use sqlx::{PgPool, postgres::{PgPoolOptions, PgConnectOptions}, ConnectOptions};
-use std::{env, time::Duration, str::FromStr};
+use std::{time::Duration, str::FromStr};
+
+use crate::config::config_var;
 use tracing;
 
 pub async fn create_pool() -> Result<PgPool, sqlx::Error> {
-    let database_url = env::var("DATABASE_URL")
+    let database_url = config_var("DATABASE_URL")
         .expect("DATABASE_URL must be set");
 
+    connect(&database_url, "primary").await
+}
+
+// Optional read-only replica from DATABASE_REPLICA_URL, sized by the same DB_* settings
+pub async fn create_replica_pool() -> Result<Option<PgPool>, sqlx::Error> {
+    match config_var("DATABASE_REPLICA_URL").ok().filter(|url| !url.is_empty()) {
+        Some(replica_url) => Ok(Some(connect(&replica_url, "replica").await?)),
+        None => Ok(None),
+    }
+}
+
+async fn connect(database_url: &str, role: &str) -> Result<PgPool, sqlx::Error> {
     // Optimized pool configuration for low-resource environment (2CPU 2GB RAM)
-    let max_connections: u32 = env::var("DB_MAX_CONNECTIONS")
+    let max_connections: u32 = config_var("DB_MAX_CONNECTIONS")
         .ok()
         .and_then(|v| v.parse().ok())
         .unwrap_or(8); // Drastically reduced for 2GB RAM
-    let min_connections: u32 = env::var("DB_MIN_CONNECTIONS")
+    let min_connections: u32 = config_var("DB_MIN_CONNECTIONS")
         .ok()
         .and_then(|v| v.parse().ok())
         .unwrap_or(2); // Minimal connections to reduce memory usage
     let acquire_timeout = Duration::from_secs(
-        env::var("DB_ACQUIRE_TIMEOUT_SECS")
+        config_var("DB_ACQUIRE_TIMEOUT_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(5), // Faster timeout for quick failure
     );
     let idle_timeout = Duration::from_secs(
-        env::var("DB_IDLE_TIMEOUT_SECS")
+        config_var("DB_IDLE_TIMEOUT_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(30), // Shorter idle timeout to free connections
     );
     let max_lifetime = Duration::from_secs(
-        env::var("DB_MAX_LIFETIME_SECS")
+        config_var("DB_MAX_LIFETIME_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(1800), // 30 minutes max lifetime
     );
 
     // Parse connection options to enable statement caching
-    let mut connect_options = PgConnectOptions::from_str(&database_url)?;
+    let mut connect_options = PgConnectOptions::from_str(database_url)?;
     
     // Enable statement caching for better performance
     connect_options = connect_options
         .statement_cache_capacity(64) // Further reduced cache size for memory efficiency
-        .disable_statement_logging(); // Reduce logging overhead
+        // Statements are reported at TRACE so the per-request statement budget can count
+        // them; the log output itself stays at INFO and never prints them
+        .log_statements(LevelFilter::Trace)
+        .log_slow_statements(LevelFilter::Trace, Duration::from_secs(1));
 
     let pool = PgPoolOptions::new()
         .max_connections(max_connections)
@@ -54,8 +72,8 @@ pub async fn create_pool() -> Result<PgPool, sqlx::Error> {
 
     // Log pool configuration for debugging
     tracing::info!(
-        "Database pool created with max_connections={}, min_connections={}, acquire_timeout={}s",
-        max_connections, min_connections, acquire_timeout.as_secs()
+        "Database {} pool created with max_connections={}, min_connections={}, acquire_timeout={}s",
+        role, max_connections, min_connections, acquire_timeout.as_secs()
     );
 
     Ok(pool)
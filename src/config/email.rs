@@ -0,0 +1,53 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_secret};
+
+#[derive(Clone)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub from_address: String,
+    pub enabled: bool,
+}
+
+impl EmailConfig {
+    pub fn from_env() -> Self {
+        let smtp_host = config_var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_string());
+        let smtp_port = config_var("SMTP_PORT")
+            .unwrap_or_else(|_| "587".to_string())
+            .parse()
+            .unwrap_or(587);
+        let smtp_username = config_var("SMTP_USERNAME").ok().filter(|u| !u.is_empty());
+        let smtp_password = config_var("SMTP_PASSWORD").ok().filter(|p| !p.is_empty());
+        let from_address = config_var("EMAIL_FROM")
+            .unwrap_or_else(|_| "Fintrack <no-reply@fintrack.local>".to_string());
+        let enabled = config_var("EMAIL_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
+
+        Self {
+            smtp_host,
+            smtp_port,
+            smtp_username,
+            smtp_password,
+            from_address,
+            enabled,
+        }
+    }
+}
+
+impl fmt::Debug for EmailConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailConfig")
+            .field("smtp_host", &self.smtp_host)
+            .field("smtp_port", &self.smtp_port)
+            .field("smtp_username", &self.smtp_username)
+            .field("smtp_password", &redact_secret(&self.smtp_password))
+            .field("from_address", &self.from_address)
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_secret};
+
+// AES-256-GCM takes a 256-bit key
+const FIELD_KEY_BYTES: usize = 32;
+
+#[derive(Clone, Default)]
+pub struct EncryptionConfig {
+    // Hex-encoded key that seals new values. A KMS-managed data key is decrypted by the
+    // deploy tooling and handed over here like any other secret
+    pub key: Option<String>,
+    // Retired keys, still tried when opening values so a rotation doesn't strand old rows
+    pub previous_keys: Vec<String>,
+}
+
+impl EncryptionConfig {
+    pub fn from_env() -> Self {
+        let key = config_var("FIELD_ENCRYPTION_KEY").ok().filter(|key| !key.trim().is_empty());
+        let previous_keys = config_var("FIELD_ENCRYPTION_PREVIOUS_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect();
+
+        Self { key, previous_keys }
+    }
+
+    // Current key first, then the retired ones
+    pub fn decoded_keys(&self) -> Result<Vec<[u8; FIELD_KEY_BYTES]>, String> {
+        let current = self.key.iter().map(|key| ("FIELD_ENCRYPTION_KEY", key));
+        let previous = self.previous_keys.iter().map(|key| ("FIELD_ENCRYPTION_PREVIOUS_KEYS", key));
+
+        current
+            .chain(previous)
+            .map(|(name, key)| {
+                decode_hex_key(key.trim()).ok_or_else(|| format!("{} must be {} hex-encoded bytes", name, FIELD_KEY_BYTES))
+            })
+            .collect()
+    }
+}
+
+fn decode_hex_key(hex: &str) -> Option<[u8; FIELD_KEY_BYTES]> {
+    if hex.len() != FIELD_KEY_BYTES * 2 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut key = [0u8; FIELD_KEY_BYTES];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+impl fmt::Debug for EncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptionConfig")
+            .field("key", &redact_secret(&self.key))
+            .field("previous_keys", &self.previous_keys.len())
+            .finish()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_url};
+
+#[derive(Clone)]
+pub struct EventStreamConfig {
+    // nats://[user:pass@]host:port of the server change events are published to, off when unset
+    pub nats_url: Option<String>,
+    // Events go to <prefix>.<resource>.<action>, e.g. fintrack.transaction.created
+    pub subject_prefix: String,
+}
+
+impl EventStreamConfig {
+    pub fn from_env() -> Self {
+        let nats_url = config_var("EVENT_STREAM_NATS_URL").ok().filter(|url| !url.is_empty());
+        let subject_prefix = config_var("EVENT_STREAM_SUBJECT_PREFIX")
+            .ok()
+            .filter(|prefix| !prefix.is_empty())
+            .unwrap_or_else(|| "fintrack".to_string());
+
+        Self {
+            nats_url,
+            subject_prefix,
+        }
+    }
+}
+
+impl fmt::Debug for EventStreamConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStreamConfig")
+            .field("nats_url", &self.nats_url.as_deref().map(redact_url))
+            .field("subject_prefix", &self.subject_prefix)
+            .finish()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::net::IpAddr;
+
+use crate::config::config_var;
+
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    // Largest body a JSON endpoint reads
+    pub max_body_bytes: usize,
+    // Largest body any request may send, used by the import endpoints; anything bigger is refused unread
+    pub max_upload_bytes: usize,
+    pub request_timeout_seconds: u64,
+    // Budget for routes marked long running in the route table
+    pub long_request_timeout_seconds: u64,
+    // Strict-Transport-Security max-age, 0 leaves the header off (e.g. behind plain HTTP in development)
+    pub hsts_max_age_seconds: u64,
+    // Peers whose X-Forwarded-For and X-Real-IP are believed, read from the comma-separated
+    // TRUSTED_PROXIES. Requests from anyone else are attributed to the socket address.
+    pub trusted_proxies: Vec<String>,
+}
+
+impl HttpConfig {
+    pub fn from_env() -> Self {
+        let max_body_bytes = config_var("HTTP_MAX_BODY_BYTES")
+            .unwrap_or_else(|_| "1048576".to_string())
+            .parse()
+            .unwrap_or(1024 * 1024);
+        let max_upload_bytes = config_var("HTTP_MAX_UPLOAD_BYTES")
+            .unwrap_or_else(|_| "10485760".to_string())
+            .parse()
+            .unwrap_or(10 * 1024 * 1024);
+        let request_timeout_seconds = config_var("HTTP_REQUEST_TIMEOUT_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+        let long_request_timeout_seconds = config_var("HTTP_LONG_REQUEST_TIMEOUT_SECONDS")
+            .unwrap_or_else(|_| "120".to_string())
+            .parse()
+            .unwrap_or(120);
+        let hsts_max_age_seconds = config_var("HSTS_MAX_AGE_SECONDS")
+            .unwrap_or_else(|_| "31536000".to_string())
+            .parse()
+            .unwrap_or(31_536_000);
+        let trusted_proxies = config_var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(|proxy| proxy.trim().to_string())
+            .filter(|proxy| !proxy.is_empty())
+            .collect();
+
+        Self {
+            max_body_bytes,
+            // An upload limit below the JSON one would silently lower it
+            max_upload_bytes: max_upload_bytes.max(max_body_bytes),
+            request_timeout_seconds,
+            long_request_timeout_seconds: long_request_timeout_seconds.max(request_timeout_seconds),
+            hsts_max_age_seconds,
+            trusted_proxies,
+        }
+    }
+
+    pub fn trusts(&self, peer: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|proxy| proxy.parse::<IpAddr>() == Ok(peer))
+    }
+}
\ No newline at end of file
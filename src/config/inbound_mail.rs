@@ -0,0 +1,45 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_secret};
+
+// Receipts forwarded to a per-user address on `domain` become pending transactions.
+// Each provider's webhook is only served while its credential is set.
+#[derive(Clone)]
+pub struct InboundEmailConfig {
+    pub domain: Option<String>,
+    // Mailgun's webhook signing key, checked against the signature on every post
+    pub mailgun_signing_key: Option<String>,
+    // Password in the basic auth credentials of the SNS subscription URL SES publishes to
+    pub ses_secret: Option<String>,
+}
+
+impl InboundEmailConfig {
+    pub fn from_env() -> Self {
+        let domain = config_var("INBOUND_EMAIL_DOMAIN")
+            .ok()
+            .map(|domain| domain.trim().to_lowercase())
+            .filter(|domain| !domain.is_empty());
+        let mailgun_signing_key = config_var("MAILGUN_WEBHOOK_SIGNING_KEY").ok().filter(|key| !key.is_empty());
+        let ses_secret = config_var("SES_WEBHOOK_SECRET").ok().filter(|secret| !secret.is_empty());
+
+        Self {
+            domain,
+            mailgun_signing_key,
+            ses_secret,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.domain.is_some()
+    }
+}
+
+impl fmt::Debug for InboundEmailConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InboundEmailConfig")
+            .field("domain", &self.domain)
+            .field("mailgun_signing_key", &redact_secret(&self.mailgun_signing_key))
+            .field("ses_secret", &redact_secret(&self.ses_secret))
+            .finish()
+    }
+}
\ No newline at end of file
@@ -1,34 +1,154 @@
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse,
+};
 use uuid::Uuid;
 
+use crate::config::{config_var, parsed_config_var};
 use crate::models::Claims;
-use crate::utils::AppError;
+use crate::utils::{AppError, base64url};
+
+// Sessions share this lifetime so they expire together with their token
+pub const DEFAULT_TOKEN_TTL_HOURS: i64 = 24;
+
+// Refresh tokens slide forward on every rotation
+pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Debug, Clone)]
+pub struct JwtSettings {
+    pub token_ttl_hours: i64,
+    pub issuer: String,
+    pub audience: String,
+    // Tolerated clock drift between us and whoever checks exp/iat
+    pub leeway_secs: u64,
+    // HS256 signs with JWT_SECRET; RS256 and EdDSA sign with the PEM key pair below
+    pub algorithm: String,
+    pub private_key_path: Option<String>,
+    pub public_key_path: Option<String>,
+    pub key_id: String,
+    // Settings above that were set but didn't parse, reported by AppConfig::validate
+    pub invalid: Vec<String>,
+}
+
+impl JwtSettings {
+    pub fn from_env() -> Self {
+        let mut invalid = Vec::new();
+
+        Self {
+            token_ttl_hours: parsed_config_var("JWT_TTL_HOURS", DEFAULT_TOKEN_TTL_HOURS, &mut invalid),
+            issuer: config_var("JWT_ISSUER")
+                .unwrap_or_else(|_| "fintrack".to_string()),
+            audience: config_var("JWT_AUDIENCE")
+                .unwrap_or_else(|_| "fintrack-api".to_string()),
+            leeway_secs: parsed_config_var("JWT_LEEWAY_SECS", 60, &mut invalid),
+            algorithm: config_var("JWT_ALGORITHM")
+                .unwrap_or_else(|_| "HS256".to_string()),
+            private_key_path: config_var("JWT_PRIVATE_KEY_PATH").ok().filter(|path| !path.is_empty()),
+            public_key_path: config_var("JWT_PUBLIC_KEY_PATH").ok().filter(|path| !path.is_empty()),
+            key_id: config_var("JWT_KEY_ID")
+                .unwrap_or_else(|_| "fintrack-1".to_string()),
+            invalid,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct JwtConfig {
     pub encoding_key: EncodingKey,
     pub decoding_key: DecodingKey,
     pub validation: Validation,
+    pub header: Header,
+    // Public half of the signing key, served at /.well-known/jwks.json. Empty for HS256.
+    pub jwks: JwkSet,
+    pub token_ttl: chrono::Duration,
+    pub issuer: String,
+    pub audience: String,
 }
 
 impl JwtConfig {
-    pub fn new(secret: &str) -> Self {
-        Self {
-            encoding_key: EncodingKey::from_secret(secret.as_ref()),
-            decoding_key: DecodingKey::from_secret(secret.as_ref()),
-            validation: Validation::default(),
+    pub fn new(secret: &str, settings: &JwtSettings) -> Result<Self, Box<dyn std::error::Error>> {
+        let algorithm: Algorithm = settings.algorithm.parse()?;
+
+        let (encoding_key, decoding_key, keys) = match algorithm {
+            Algorithm::HS256 => (
+                EncodingKey::from_secret(secret.as_ref()),
+                DecodingKey::from_secret(secret.as_ref()),
+                Vec::new(),
+            ),
+            Algorithm::RS256 => {
+                let (private_pem, public_pem) = read_key_pair(settings)?;
+                let encoding_key = EncodingKey::from_rsa_pem(&private_pem)?;
+                let decoding_key = DecodingKey::from_rsa_pem(&public_pem)?;
+                let jwk = Jwk::from_encoding_key(&encoding_key, algorithm)?;
+                (encoding_key, decoding_key, vec![jwk])
+            }
+            Algorithm::EdDSA => {
+                let (private_pem, public_pem) = read_key_pair(settings)?;
+                let encoding_key = EncodingKey::from_ed_pem(&private_pem)?;
+                let decoding_key = DecodingKey::from_ed_pem(&public_pem)?;
+                // jsonwebtoken can't derive an OKP jwk from the signing key, so build it from the raw public key
+                let jwk = Jwk {
+                    common: CommonParameters {
+                        key_algorithm: Some(KeyAlgorithm::EdDSA),
+                        ..Default::default()
+                    },
+                    algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                        key_type: OctetKeyPairType::OctetKeyPair,
+                        curve: EllipticCurve::Ed25519,
+                        x: base64url(&ed25519_public_key(&public_pem)?),
+                    }),
+                };
+                (encoding_key, decoding_key, vec![jwk])
+            }
+            other => return Err(format!("Unsupported JWT_ALGORITHM {:?}, expected HS256, RS256 or EdDSA", other).into()),
+        };
+
+        let mut header = Header::new(algorithm);
+        let keys = keys
+            .into_iter()
+            .map(|mut jwk| {
+                jwk.common.key_id = Some(settings.key_id.clone());
+                jwk.common.public_key_use = Some(PublicKeyUse::Signature);
+                jwk
+            })
+            .collect::<Vec<_>>();
+        if !keys.is_empty() {
+            header.kid = Some(settings.key_id.clone());
         }
+
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = settings.leeway_secs;
+        validation.set_issuer(&[&settings.issuer]);
+        validation.set_audience(&[&settings.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+        Ok(Self {
+            encoding_key,
+            decoding_key,
+            validation,
+            header,
+            jwks: JwkSet { keys },
+            token_ttl: chrono::Duration::hours(settings.token_ttl_hours),
+            issuer: settings.issuer.clone(),
+            audience: settings.audience.clone(),
+        })
+    }
+
+    // Anything keyed to a token (revocation markers, sessions) must live at least this long
+    pub fn token_ttl_secs(&self) -> u64 {
+        self.token_ttl.num_seconds().max(0) as u64
     }
 
-    pub fn create_token(&self, user_id: Uuid, email: String) -> Result<String, AppError> {
+    pub fn create_token(&self, user_id: Uuid, email: String, session_id: Uuid) -> Result<String, AppError> {
         let exp = chrono::Utc::now()
-            .checked_add_signed(chrono::Duration::hours(24))
+            .checked_add_signed(self.token_ttl)
             .expect("valid timestamp")
             .timestamp() as usize;
 
-        let claims = Claims::new(user_id, email, exp);
+        let claims = Claims::new(user_id, email, session_id, exp, self.issuer.clone(), self.audience.clone());
 
-        encode(&Header::default(), &claims, &self.encoding_key)
+        encode(&self.header, &claims, &self.encoding_key)
             .map_err(|e| AppError::InternalServerError(format!("Token creation failed: {}", e)))
     }
 
@@ -37,4 +157,24 @@ impl JwtConfig {
             .map(|data| data.claims)
             .map_err(|_| AppError::Unauthorized("Invalid token".to_string()))
     }
+}
+
+// An Ed25519 SubjectPublicKeyInfo is a fixed 12-byte DER prefix followed by the 32-byte key
+fn ed25519_public_key(pem: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let body = std::str::from_utf8(pem)?
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+    let der = STANDARD.decode(body.trim())?;
+    if der.len() != 44 {
+        return Err("JWT_PUBLIC_KEY_PATH does not hold an Ed25519 public key".into());
+    }
+    Ok(der[12..].to_vec())
+}
+
+fn read_key_pair(settings: &JwtSettings) -> Result<(Vec<u8>, Vec<u8>), Box<dyn std::error::Error>> {
+    let (Some(private_path), Some(public_path)) = (&settings.private_key_path, &settings.public_key_path) else {
+        return Err(format!("JWT_ALGORITHM={} needs JWT_PRIVATE_KEY_PATH and JWT_PUBLIC_KEY_PATH", settings.algorithm).into());
+    };
+    Ok((std::fs::read(private_path)?, std::fs::read(public_path)?))
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_secret};
+
+#[derive(Clone)]
+pub struct MetricsConfig {
+    // Statements a single request may issue before it is flagged, 0 turns the check off
+    pub statement_budget: usize,
+    // Bearer token for GET /metrics, the endpoint is disabled when unset
+    pub token: Option<String>,
+    // Statements slower than this are logged with their route and SQL, 0 turns the log off
+    pub slow_query_threshold_ms: u64,
+}
+
+impl MetricsConfig {
+    pub fn from_env() -> Self {
+        let statement_budget = config_var("DB_STATEMENT_BUDGET")
+            .unwrap_or_else(|_| "25".to_string())
+            .parse()
+            .unwrap_or(25);
+        let token = config_var("METRICS_TOKEN").ok().filter(|token| !token.is_empty());
+        let slow_query_threshold_ms = config_var("DB_SLOW_QUERY_MS")
+            .unwrap_or_else(|_| "500".to_string())
+            .parse()
+            .unwrap_or(500);
+
+        Self {
+            statement_budget,
+            token,
+            slow_query_threshold_ms,
+        }
+    }
+}
+
+impl fmt::Debug for MetricsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsConfig")
+            .field("statement_budget", &self.statement_budget)
+            .field("token", &redact_secret(&self.token))
+            .field("slow_query_threshold_ms", &self.slow_query_threshold_ms)
+            .finish()
+    }
+}
\ No newline at end of file
@@ -2,8 +2,35 @@ pub mod database;
 pub mod jwt;
 pub mod app;
 pub mod redis;
+pub mod email;
+pub mod inbound_mail;
+pub mod chat_bot;
+pub mod push_notification;
+pub mod balance_visibility;
+pub mod metrics;
+pub mod admin;
+pub mod password_policy;
+pub mod event_stream;
+pub mod http;
+pub mod pool_monitor;
+pub mod encryption;
+pub mod redact;
+pub mod source;
 
 pub use database::*;
 pub use jwt::*;
 pub use app::*;
-pub use redis::*;
\ No newline at end of file
+pub use redis::*;
+pub use email::*;
+pub use inbound_mail::*;
+pub use chat_bot::*;
+pub use push_notification::*;
+pub use balance_visibility::*;
+pub use metrics::*;
+pub use admin::*;
+pub use password_policy::*;
+pub use event_stream::*;
+pub use http::*;
+pub use pool_monitor::*;
+pub use encryption::*;
+pub use source::*;
\ No newline at end of file
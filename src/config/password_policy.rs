@@ -0,0 +1,70 @@
+use crate::config::config_var;
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicyConfig {
+    pub min_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    // Look new passwords up in the HaveIBeenPwned range API; only a 5 character hash prefix leaves the server
+    pub breach_check: bool,
+}
+
+impl Default for PasswordPolicyConfig {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+            breach_check: false,
+        }
+    }
+}
+
+impl PasswordPolicyConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let flag = |name: &str, default: bool| {
+            config_var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            min_length: config_var("PASSWORD_MIN_LENGTH")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.min_length),
+            require_uppercase: flag("PASSWORD_REQUIRE_UPPERCASE", defaults.require_uppercase),
+            require_lowercase: flag("PASSWORD_REQUIRE_LOWERCASE", defaults.require_lowercase),
+            require_digit: flag("PASSWORD_REQUIRE_DIGIT", defaults.require_digit),
+            require_symbol: flag("PASSWORD_REQUIRE_SYMBOL", defaults.require_symbol),
+            breach_check: flag("PASSWORD_BREACH_CHECK", defaults.breach_check),
+        }
+    }
+
+    // Human-readable list of what the password is missing, empty when it passes
+    pub fn violations(&self, password: &str) -> Vec<String> {
+        let mut missing = Vec::new();
+        if password.chars().count() < self.min_length {
+            missing.push(format!("at least {} characters", self.min_length));
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            missing.push("an uppercase letter".to_string());
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            missing.push("a lowercase letter".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            missing.push("a digit".to_string());
+        }
+        if self.require_symbol && !password.chars().any(|c| !c.is_alphanumeric() && !c.is_whitespace()) {
+            missing.push("a symbol".to_string());
+        }
+        missing
+    }
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::config::{config_var, redact::redact_url_path};
+
+#[derive(Clone)]
+pub struct PoolMonitorConfig {
+    pub check_interval_seconds: u64,
+    // Consecutive exhausted checks before an alert goes out, so a momentary spike stays quiet
+    pub alert_after_checks: u32,
+    // Receives a JSON POST when the pool becomes exhausted and again once it recovers
+    pub alert_webhook_url: Option<String>,
+}
+
+impl PoolMonitorConfig {
+    pub fn from_env() -> Self {
+        let check_interval_seconds = config_var("DB_MONITOR_INTERVAL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+        let alert_after_checks = config_var("DB_POOL_ALERT_AFTER_CHECKS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()
+            .unwrap_or(3);
+        let alert_webhook_url = config_var("DB_POOL_ALERT_WEBHOOK_URL").ok().filter(|url| !url.is_empty());
+
+        Self {
+            check_interval_seconds: check_interval_seconds.max(1),
+            alert_after_checks: alert_after_checks.max(1),
+            alert_webhook_url,
+        }
+    }
+}
+
+impl fmt::Debug for PoolMonitorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolMonitorConfig")
+            .field("check_interval_seconds", &self.check_interval_seconds)
+            .field("alert_after_checks", &self.alert_after_checks)
+            .field("alert_webhook_url", &self.alert_webhook_url.as_deref().map(redact_url_path))
+            .finish()
+    }
+}
\ No newline at end of file
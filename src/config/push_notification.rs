@@ -0,0 +1,44 @@
+use crate::config::{config_var, parsed_config_var};
+
+// Delivery of budget alerts and large-transaction warnings to registered phones. Each service is
+// only used while its credentials are set; devices on the other one are kept but not notified.
+#[derive(Debug, Clone)]
+pub struct PushConfig {
+    // Service account JSON downloaded from the Firebase console
+    pub fcm_service_account_path: Option<String>,
+    // The .p8 token signing key from the Apple developer account, with its key and team IDs
+    pub apns_key_path: Option<String>,
+    pub apns_key_id: Option<String>,
+    pub apns_team_id: Option<String>,
+    // The iOS app's bundle ID
+    pub apns_topic: Option<String>,
+    // Development builds of the app get their tokens from the sandbox environment
+    pub apns_sandbox: bool,
+    // Settings above that were set but didn't parse, reported by AppConfig::validate
+    pub invalid: Vec<String>,
+}
+
+impl PushConfig {
+    pub fn from_env() -> Self {
+        let optional = |name: &str| config_var(name).ok().filter(|value| !value.is_empty());
+        let mut invalid = Vec::new();
+
+        Self {
+            fcm_service_account_path: optional("FCM_SERVICE_ACCOUNT_PATH"),
+            apns_key_path: optional("APNS_KEY_PATH"),
+            apns_key_id: optional("APNS_KEY_ID"),
+            apns_team_id: optional("APNS_TEAM_ID"),
+            apns_topic: optional("APNS_TOPIC"),
+            apns_sandbox: parsed_config_var("APNS_SANDBOX", false, &mut invalid),
+            invalid,
+        }
+    }
+
+    pub fn apns_enabled(&self) -> bool {
+        self.apns_key_path.is_some()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.fcm_service_account_path.is_some() || self.apns_enabled()
+    }
+}
\ No newline at end of file
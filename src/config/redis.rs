@@ -1,6 +1,8 @@
-use std::env;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+use crate::config::{config_var, redact::redact_secret};
+
+#[derive(Clone)]
 pub struct RedisConfig {
     pub addr: String,
     pub password: Option<String>,
@@ -8,28 +10,60 @@ pub struct RedisConfig {
     pub max_connections: u32,
     pub connection_timeout: u64,
     pub enabled: bool,
+    // File hot entries are dumped to on shutdown and preloaded from on startup, unset disables it
+    pub snapshot_path: Option<String>,
+    pub snapshot_max_entries: usize,
+    // Snapshots older than this are discarded instead of preloaded
+    pub snapshot_max_age_seconds: i64,
+    // In-process tier in front of Redis, 0 disables it
+    pub local_cache_capacity: u64,
+    // Upper bound on how long a local copy lives, whatever the Redis TTL
+    pub local_cache_ttl_seconds: u64,
+    // How often to retry Redis when it was unreachable at startup
+    pub reconnect_interval_seconds: u64,
 }
 
 impl RedisConfig {
     pub fn from_env() -> Self {
-        let addr = env::var("REDIS_ADDR").unwrap_or_else(|_| "localhost:6379".to_string());
-        let password = env::var("REDIS_PASSWORD").ok().filter(|p| !p.is_empty());
-        let db = env::var("REDIS_DB")
+        let addr = config_var("REDIS_ADDR").unwrap_or_else(|_| "localhost:6379".to_string());
+        let password = config_var("REDIS_PASSWORD").ok().filter(|p| !p.is_empty());
+        let db = config_var("REDIS_DB")
             .unwrap_or_else(|_| "0".to_string())
             .parse()
             .unwrap_or(0);
-        let max_connections = env::var("REDIS_MAX_CONNECTIONS")
+        let max_connections = config_var("REDIS_MAX_CONNECTIONS")
             .unwrap_or_else(|_| "10".to_string())
             .parse()
             .unwrap_or(10);
-        let connection_timeout = env::var("REDIS_CONNECTION_TIMEOUT")
+        let connection_timeout = config_var("REDIS_CONNECTION_TIMEOUT")
             .unwrap_or_else(|_| "5".to_string())
             .parse()
             .unwrap_or(5);
-        let enabled = env::var("REDIS_ENABLED")
+        let enabled = config_var("REDIS_ENABLED")
             .unwrap_or_else(|_| "true".to_string())
             .parse()
             .unwrap_or(true);
+        let snapshot_path = config_var("CACHE_SNAPSHOT_PATH").ok().filter(|path| !path.is_empty());
+        let snapshot_max_entries = config_var("CACHE_SNAPSHOT_MAX_ENTRIES")
+            .unwrap_or_else(|_| "10000".to_string())
+            .parse()
+            .unwrap_or(10000);
+        let snapshot_max_age_seconds = config_var("CACHE_SNAPSHOT_MAX_AGE_SECONDS")
+            .unwrap_or_else(|_| "600".to_string())
+            .parse()
+            .unwrap_or(600);
+        let local_cache_capacity = config_var("CACHE_LOCAL_CAPACITY")
+            .unwrap_or_else(|_| "10000".to_string())
+            .parse()
+            .unwrap_or(10000);
+        let local_cache_ttl_seconds = config_var("CACHE_LOCAL_TTL_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+        let reconnect_interval_seconds = config_var("REDIS_RECONNECT_INTERVAL_SECONDS")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse()
+            .unwrap_or(15);
 
         Self {
             addr,
@@ -38,6 +72,12 @@ impl RedisConfig {
             max_connections,
             connection_timeout,
             enabled,
+            snapshot_path,
+            snapshot_max_entries,
+            snapshot_max_age_seconds,
+            local_cache_capacity,
+            local_cache_ttl_seconds,
+            reconnect_interval_seconds,
         }
     }
 
@@ -47,4 +87,23 @@ impl RedisConfig {
             None => format!("redis://{}/{}", self.addr, self.db),
         }
     }
+}
+
+impl fmt::Debug for RedisConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisConfig")
+            .field("addr", &self.addr)
+            .field("password", &redact_secret(&self.password))
+            .field("db", &self.db)
+            .field("max_connections", &self.max_connections)
+            .field("connection_timeout", &self.connection_timeout)
+            .field("enabled", &self.enabled)
+            .field("snapshot_path", &self.snapshot_path)
+            .field("snapshot_max_entries", &self.snapshot_max_entries)
+            .field("snapshot_max_age_seconds", &self.snapshot_max_age_seconds)
+            .field("local_cache_capacity", &self.local_cache_capacity)
+            .field("local_cache_ttl_seconds", &self.local_cache_ttl_seconds)
+            .field("reconnect_interval_seconds", &self.reconnect_interval_seconds)
+            .finish()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::env::{self, VarError};
+use std::fs;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+static FILE_VALUES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+// Reads the TOML file named by CONFIG_FILE, if any. Keys map onto the environment variable
+// names, with tables joined by an underscore, so `[db] max_connections = 8` supplies
+// DB_MAX_CONNECTIONS. Arrays become comma-separated lists. The environment always wins.
+pub fn load_config_file() -> Result<(), String> {
+    let Ok(path) = env::var("CONFIG_FILE") else {
+        return Ok(());
+    };
+    if path.is_empty() || FILE_VALUES.get().is_some() {
+        return Ok(());
+    }
+
+    let contents = fs::read_to_string(&path).map_err(|e| format!("Cannot read CONFIG_FILE '{}': {}", path, e))?;
+    let document: DocumentMut = contents
+        .parse()
+        .map_err(|e| format!("CONFIG_FILE '{}' is not valid TOML: {}", path, e))?;
+
+    let mut values = HashMap::new();
+    flatten_table("", document.as_table(), &mut values)
+        .map_err(|e| format!("CONFIG_FILE '{}': {}", path, e))?;
+    let _ = FILE_VALUES.set(values);
+    Ok(())
+}
+
+// Every config module reads its settings through this instead of env::var
+pub fn config_var(name: &str) -> Result<String, VarError> {
+    match env::var(name) {
+        Err(VarError::NotPresent) => FILE_VALUES
+            .get()
+            .and_then(|values| values.get(name))
+            .cloned()
+            .ok_or(VarError::NotPresent),
+        result => result,
+    }
+}
+
+// For settings without a sensible default; an empty value counts as missing
+pub fn required_config_var(name: &str) -> Result<String, String> {
+    config_var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| format!("{} must be set in the environment or CONFIG_FILE", name))
+}
+
+// For optional settings with a default. A value that doesn't parse is added to `invalid` for
+// AppConfig::validate to report, and the default stands in until then.
+pub fn parsed_config_var<T: FromStr>(name: &str, default: T, invalid: &mut Vec<String>) -> T {
+    match config_var(name) {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse().unwrap_or_else(|_| {
+            invalid.push(format!("{} '{}' is not a valid value", name, value));
+            default
+        }),
+        _ => default,
+    }
+}
+
+fn flatten_table(prefix: &str, table: &Table, values: &mut HashMap<String, String>) -> Result<(), String> {
+    for (key, item) in table.iter() {
+        let name = if prefix.is_empty() {
+            key.to_uppercase()
+        } else {
+            format!("{}_{}", prefix, key.to_uppercase())
+        };
+
+        match item {
+            Item::Table(table) => flatten_table(&name, table, values)?,
+            Item::Value(Value::InlineTable(table)) => flatten_table(&name, &table.clone().into_table(), values)?,
+            Item::Value(value) => {
+                values.insert(name.clone(), scalar(value).ok_or_else(|| format!("unsupported value for {}", name))?);
+            }
+            Item::None | Item::ArrayOfTables(_) => return Err(format!("unsupported value for {}", name)),
+        }
+    }
+    Ok(())
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.value().clone()),
+        Value::Integer(value) => Some(value.value().to_string()),
+        Value::Float(value) => Some(value.value().to_string()),
+        Value::Boolean(value) => Some(value.value().to_string()),
+        Value::Array(items) => items
+            .iter()
+            .map(scalar)
+            .collect::<Option<Vec<_>>>()
+            .map(|items| items.join(",")),
+        Value::Datetime(_) | Value::InlineTable(_) => None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::{Pocket, Transaction};
+
+const UNASSIGNED_ACCOUNT: &str = "Assets:Unassigned";
+const UNCATEGORIZED: &str = "Uncategorized";
+const ADJUSTMENT_ACCOUNT: &str = "Equity:Adjustments";
+
+// Maps pockets onto asset accounts and categories onto income/expense accounts,
+// using the colon-separated hierarchy GnuCash and ledger-cli both understand
+pub struct AccountMap {
+    pockets: HashMap<Uuid, Pocket>,
+}
+
+impl AccountMap {
+    pub fn new(pockets: Vec<Pocket>) -> Self {
+        Self {
+            pockets: pockets.into_iter().map(|pocket| (pocket.id, pocket)).collect(),
+        }
+    }
+
+    pub fn pocket(&self, account_id: Option<Uuid>) -> Option<&Pocket> {
+        account_id.and_then(|id| self.pockets.get(&id))
+    }
+
+    pub fn asset_account(&self, account_id: Option<Uuid>) -> String {
+        match self.pocket(account_id) {
+            Some(pocket) => format!("Assets:Pockets:{}", sanitize_account_segment(&pocket.name)),
+            None => UNASSIGNED_ACCOUNT.to_string(),
+        }
+    }
+
+    pub fn category_account(&self, transaction: &Transaction) -> String {
+        let root = match transaction.transaction_type.as_str() {
+            "income" => "Income",
+            "adjustment" => return ADJUSTMENT_ACCOUNT.to_string(),
+            _ => "Expenses",
+        };
+        let category = transaction
+            .category
+            .as_deref()
+            .filter(|category| !category.trim().is_empty())
+            .unwrap_or(UNCATEGORIZED);
+
+        format!("{}:{}", root, sanitize_account_segment(category))
+    }
+}
+
+// Colons would split the hierarchy and ledger-cli treats double spaces as the
+// end of the account name, so both are normalized away
+pub fn sanitize_account_segment(name: &str) -> String {
+    name.replace(':', "-")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use super::{signed_amount, ExportData, Exporter};
+
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn content_type(&self) -> &'static str {
+        "text/csv; charset=utf-8"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn export(&self, data: &ExportData) -> String {
+        let mut output = String::from("id,date,description,amount,type,category,account\n");
+
+        for transaction in &data.transactions {
+            let fields = [
+                transaction.id.to_string(),
+                transaction.transaction_date.format("%Y-%m-%d").to_string(),
+                transaction.description.clone(),
+                signed_amount(transaction).to_string(),
+                transaction.transaction_type.clone(),
+                transaction.category.clone().unwrap_or_default(),
+                data.accounts.asset_account(transaction.account_id),
+            ];
+
+            let line: Vec<String> = fields.iter().map(|field| escape_csv_field(field)).collect();
+            output.push_str(&line.join(","));
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,54 @@
+use super::{signed_amount, ExportData, Exporter};
+
+pub struct LedgerExporter;
+
+impl Exporter for LedgerExporter {
+    fn content_type(&self) -> &'static str {
+        "text/plain; charset=utf-8"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ledger"
+    }
+
+    fn export(&self, data: &ExportData) -> String {
+        let mut output = String::new();
+
+        for transaction in &data.transactions {
+            let asset_account = data.accounts.asset_account(transaction.account_id);
+            let category_account = data.accounts.category_account(transaction);
+            let amount = signed_amount(transaction);
+            let currency = data.currency(transaction.account_id);
+
+            // Balanced double-entry: the category leg mirrors the pocket leg
+            output.push_str(&format!(
+                "{} * {}\n",
+                transaction.transaction_date.format("%Y/%m/%d"),
+                transaction.description.replace(['\r', '\n'], " ")
+            ));
+            output.push_str(&format!("    ; id: {}\n", transaction.id));
+            output.push_str(&format!("    {}  {} {}\n", category_account, -amount, currency));
+            output.push_str(&format!("    {}  {} {}\n", asset_account, amount, currency));
+            output.push('\n');
+        }
+
+        output
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::fixtures::export_data;
+
+    #[test]
+    fn postings_balance_in_the_pocket_currency() {
+        let output = LedgerExporter.export(&export_data());
+
+        assert_eq!(
+            output,
+            "2024/03/05 * Transaction 1\n    ; id: 1\n    Expenses:Food  42.50 EUR\n    Assets:Pockets:Travel- Wallet  -42.50 EUR\n\n\
+             2024/03/01 * Transaction 2\n    ; id: 2\n    Income:Salary  -1000.00 EUR\n    Assets:Pockets:Travel- Wallet  1000.00 EUR\n\n\
+             2024/03/07 * Transaction 3\n    ; id: 3\n    Expenses:Transport  15000 IDR\n    Assets:Unassigned  -15000 IDR\n\n"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+pub mod accounts;
+pub mod csv;
+pub mod ledger;
+pub mod ofx;
+pub mod pdf;
+pub mod qif;
+
+pub use accounts::*;
+pub use csv::*;
+pub use ledger::*;
+pub use ofx::*;
+pub use pdf::*;
+pub use qif::*;
+
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::{Pocket, Transaction};
+use crate::utils::AppError;
+
+// Everything an exporter needs to render a user's ledger
+pub struct ExportData {
+    pub transactions: Vec<Transaction>,
+    pub accounts: AccountMap,
+    // What transactions outside a pocket are recorded in
+    pub base_currency: String,
+}
+
+impl ExportData {
+    pub fn new(transactions: Vec<Transaction>, pockets: Vec<Pocket>, base_currency: String) -> Self {
+        Self {
+            transactions,
+            accounts: AccountMap::new(pockets),
+            base_currency,
+        }
+    }
+
+    pub fn currency(&self, account_id: Option<Uuid>) -> &str {
+        self.accounts
+            .pocket(account_id)
+            .map_or(self.base_currency.as_str(), |pocket| pocket.currency.as_str())
+    }
+}
+
+pub trait Exporter: Send + Sync {
+    fn content_type(&self) -> &'static str;
+    fn file_extension(&self) -> &'static str;
+    fn export(&self, data: &ExportData) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Qif,
+    Ofx,
+    Ledger,
+}
+
+impl ExportFormat {
+    pub fn exporter(&self) -> Box<dyn Exporter> {
+        match self {
+            ExportFormat::Csv => Box::new(CsvExporter),
+            ExportFormat::Qif => Box::new(QifExporter),
+            ExportFormat::Ofx => Box::new(OfxExporter),
+            ExportFormat::Ledger => Box::new(LedgerExporter),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "qif" => Ok(ExportFormat::Qif),
+            "ofx" => Ok(ExportFormat::Ofx),
+            "ledger" => Ok(ExportFormat::Ledger),
+            _ => Err(AppError::ValidationError(
+                "Format must be one of 'csv', 'qif', 'ofx' or 'ledger'".to_string(),
+            )),
+        }
+    }
+}
+
+pub fn signed_amount(transaction: &Transaction) -> Decimal {
+    transaction.signed_amount()
+}
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use chrono::{NaiveDate, Utc};
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    use super::ExportData;
+    use crate::models::{Pocket, Transaction, TRANSACTION_STATUS_POSTED};
+
+    pub fn pocket(name: &str, currency: &str, balance: Decimal) -> Pocket {
+        Pocket {
+            id: Uuid::new_v4(),
+            user_id: None,
+            organization_id: None,
+            name: name.to_string(),
+            emoji: String::new(),
+            balance,
+            archived: false,
+            sort_order: 0,
+            group: None,
+            currency: currency.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn transaction(id: i64, account_id: Option<Uuid>, transaction_type: &str, amount: Decimal, category: &str, date: &str) -> Transaction {
+        Transaction {
+            id,
+            user_id: Uuid::nil(),
+            account_id,
+            description: format!("Transaction {}", id),
+            amount,
+            category: Some(category.to_string()),
+            transaction_type: transaction_type.to_string(),
+            transaction_date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            status: TRANSACTION_STATUS_POSTED.to_string(),
+            notes: None,
+            metadata: None,
+            original_amount: None,
+            original_currency: None,
+            exchange_rate: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    // A EUR pocket holding 1250.00 with one expense and one income, plus an expense outside any pocket
+    pub fn export_data() -> ExportData {
+        let wallet = pocket("Travel: Wallet", "EUR", Decimal::new(125000, 2));
+        let wallet_id = Some(wallet.id);
+        ExportData::new(
+            vec![
+                transaction(1, wallet_id, "expense", Decimal::new(4250, 2), "Food", "2024-03-05"),
+                transaction(2, wallet_id, "income", Decimal::new(100000, 2), "Salary", "2024-03-01"),
+                transaction(3, None, "expense", Decimal::new(15000, 0), "Transport", "2024-03-07"),
+            ],
+            vec![wallet],
+            "IDR".to_string(),
+        )
+    }
+}
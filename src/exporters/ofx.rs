@@ -0,0 +1,158 @@
+use chrono::{NaiveDate, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::{signed_amount, ExportData, Exporter};
+use crate::models::Transaction;
+
+const OFX_BANK_ID: &str = "FINTRACK";
+
+pub struct OfxExporter;
+
+impl Exporter for OfxExporter {
+    fn content_type(&self) -> &'static str {
+        "application/x-ofx"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ofx"
+    }
+
+    fn export(&self, data: &ExportData) -> String {
+        let now = Utc::now().format("%Y%m%d%H%M%S").to_string();
+
+        // One statement per pocket, keyed by the pocket id so re-imports match up
+        let mut by_account: BTreeMap<Option<Uuid>, Vec<&Transaction>> = BTreeMap::new();
+        for transaction in &data.transactions {
+            let account_id = data.accounts.pocket(transaction.account_id).map(|pocket| pocket.id);
+            by_account.entry(account_id).or_default().push(transaction);
+        }
+
+        let mut output = String::new();
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+        output.push_str("<?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>\n");
+        output.push_str("<OFX>\n");
+        output.push_str("<SIGNONMSGSRSV1><SONRS>\n");
+        output.push_str("<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>\n");
+        output.push_str(&format!("<DTSERVER>{}</DTSERVER>\n", now));
+        output.push_str("<LANGUAGE>ENG</LANGUAGE>\n");
+        output.push_str("</SONRS></SIGNONMSGSRSV1>\n");
+        output.push_str("<BANKMSGSRSV1>\n");
+
+        for (index, (account_id, transactions)) in by_account.into_iter().enumerate() {
+            let account_ref = account_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "UNASSIGNED".to_string());
+            let start = transactions.iter().map(|t| t.transaction_date).min();
+            let end = transactions.iter().map(|t| t.transaction_date).max();
+            let pocket = data.accounts.pocket(account_id);
+
+            output.push_str("<STMTTRNRS>\n");
+            output.push_str(&format!("<TRNUID>{}</TRNUID>\n", index + 1));
+            output.push_str("<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>\n");
+            output.push_str("<STMTRS>\n");
+            output.push_str(&format!("<CURDEF>{}</CURDEF>\n", data.currency(account_id)));
+            output.push_str("<BANKACCTFROM>\n");
+            output.push_str(&format!("<BANKID>{}</BANKID>\n", OFX_BANK_ID));
+            output.push_str(&format!("<ACCTID>{}</ACCTID>\n", account_ref));
+            output.push_str("<ACCTTYPE>CHECKING</ACCTTYPE>\n");
+            output.push_str("</BANKACCTFROM>\n");
+            output.push_str("<BANKTRANLIST>\n");
+            output.push_str(&format!("<DTSTART>{}</DTSTART>\n", ofx_date(start)));
+            output.push_str(&format!("<DTEND>{}</DTEND>\n", ofx_date(end)));
+
+            for transaction in transactions {
+                let trn_type = if signed_amount(transaction).is_sign_negative() { "DEBIT" } else { "CREDIT" };
+                output.push_str("<STMTTRN>\n");
+                output.push_str(&format!("<TRNTYPE>{}</TRNTYPE>\n", trn_type));
+                output.push_str(&format!("<DTPOSTED>{}</DTPOSTED>\n", transaction.transaction_date.format("%Y%m%d")));
+                output.push_str(&format!("<TRNAMT>{}</TRNAMT>\n", signed_amount(transaction)));
+                output.push_str(&format!("<FITID>{}</FITID>\n", transaction.id));
+                output.push_str(&format!("<NAME>{}</NAME>\n", escape_xml(&truncate(&transaction.description, 32))));
+                output.push_str(&format!("<MEMO>{}</MEMO>\n", escape_xml(&data.accounts.category_account(transaction))));
+                output.push_str("</STMTTRN>\n");
+            }
+
+            output.push_str("</BANKTRANLIST>\n");
+            // The pocket balance is as of now whatever the exported range; transactions
+            // outside a pocket have no balance to report
+            if let Some(pocket) = pocket {
+                output.push_str("<LEDGERBAL>\n");
+                output.push_str(&format!("<BALAMT>{}</BALAMT>\n", pocket.balance));
+                output.push_str(&format!("<DTASOF>{}</DTASOF>\n", now));
+                output.push_str("</LEDGERBAL>\n");
+            }
+            output.push_str("</STMTRS>\n");
+            output.push_str("</STMTTRNRS>\n");
+        }
+
+        output.push_str("</BANKMSGSRSV1>\n");
+        output.push_str("</OFX>\n");
+        output
+    }
+}
+
+fn ofx_date(date: Option<NaiveDate>) -> String {
+    date.unwrap_or_else(|| Utc::now().date_naive())
+        .format("%Y%m%d")
+        .to_string()
+}
+
+// OFX caps NAME at 32 characters
+fn truncate(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::fixtures::export_data;
+
+    fn statements(output: &str) -> Vec<&str> {
+        output.split("<STMTTRNRS>").skip(1).collect()
+    }
+
+    #[test]
+    fn statements_use_the_pocket_currency_and_balance() {
+        let data = export_data();
+        let output = OfxExporter.export(&data);
+        let pocket_id = data.transactions[0].account_id.unwrap();
+
+        let pocket = statements(&output)
+            .into_iter()
+            .find(|statement| statement.contains(&format!("<ACCTID>{}</ACCTID>", pocket_id)))
+            .expect("pocket statement");
+        assert!(pocket.contains("<CURDEF>EUR</CURDEF>"));
+        assert!(pocket.contains("<DTSTART>20240301</DTSTART>\n<DTEND>20240305</DTEND>"));
+        assert!(pocket.contains("<TRNTYPE>DEBIT</TRNTYPE>\n<DTPOSTED>20240305</DTPOSTED>\n<TRNAMT>-42.50</TRNAMT>"));
+        assert!(pocket.contains("<BALAMT>1250.00</BALAMT>"));
+    }
+
+    #[test]
+    fn unassigned_transactions_have_no_ledger_balance() {
+        let output = OfxExporter.export(&export_data());
+
+        let unassigned = statements(&output)
+            .into_iter()
+            .find(|statement| statement.contains("<ACCTID>UNASSIGNED</ACCTID>"))
+            .expect("unassigned statement");
+        assert!(unassigned.contains("<CURDEF>IDR</CURDEF>"));
+        assert!(!unassigned.contains("<LEDGERBAL>"));
+    }
+
+    #[test]
+    fn names_are_truncated_and_escaped() {
+        let mut data = export_data();
+        data.transactions[0].description = "Fish & Chips <to go> at the harbour market".to_string();
+
+        assert!(OfxExporter.export(&data).contains("<NAME>Fish &amp; Chips &lt;to go&gt; at the harb</NAME>"));
+    }
+}
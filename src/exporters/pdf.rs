@@ -0,0 +1,262 @@
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+use rust_decimal::Decimal;
+
+use crate::models::MonthlyReport;
+
+// A4 in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const BOTTOM: f32 = 60.0;
+
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+
+// Column x positions for the transaction list, amounts are right aligned at the margin
+const COL_DATE: f32 = MARGIN;
+const COL_DESCRIPTION: f32 = MARGIN + 70.0;
+const COL_CATEGORY: f32 = MARGIN + 300.0;
+const COL_RIGHT: f32 = PAGE_WIDTH - MARGIN;
+
+// Renders a statement-style monthly report: summary, category table and transaction list
+pub fn render_monthly_report(report: &MonthlyReport) -> Vec<u8> {
+    let mut writer = PageWriter::new();
+    let currency = report.currency.as_str();
+
+    writer.text(BOLD, 18.0, MARGIN, &format!("Monthly Statement - {}", report.month.format("%B %Y")));
+    writer.advance(22.0);
+    writer.text(REGULAR, 10.0, MARGIN, &format!("{} | {} to {} | Amounts in {}", report.owner_name, report.month, report.period_end, currency));
+    writer.advance(30.0);
+
+    writer.heading("Summary");
+    for (label, amount) in [
+        ("Income", report.income),
+        ("Expenses", -report.expenses),
+        ("Adjustments", report.adjustments),
+        ("Net", report.net),
+    ] {
+        writer.ensure_space(16.0);
+        writer.text(REGULAR, 10.0, MARGIN, label);
+        writer.text_right(if label == "Net" { BOLD } else { REGULAR }, 10.0, COL_RIGHT, &format_amount(amount));
+        writer.advance(16.0);
+    }
+    writer.advance(14.0);
+
+    writer.heading("By category");
+    if report.categories.is_empty() {
+        writer.text(REGULAR, 10.0, MARGIN, "No posted transactions this month.");
+        writer.advance(16.0);
+    } else {
+        writer.table_header(&[(MARGIN, "Category"), (COL_CATEGORY, "Type"), (COL_CATEGORY + 80.0, "Count")], "Amount");
+        for category in &report.categories {
+            writer.ensure_space(15.0);
+            writer.text(REGULAR, 9.0, MARGIN, &truncate(&category.category, 50));
+            writer.text(REGULAR, 9.0, COL_CATEGORY, &category.transaction_type);
+            writer.text(REGULAR, 9.0, COL_CATEGORY + 80.0, &category.transaction_count.to_string());
+            writer.text_right(REGULAR, 9.0, COL_RIGHT, &format_amount(category.amount));
+            writer.advance(15.0);
+        }
+    }
+    writer.advance(14.0);
+
+    writer.heading("Transactions");
+    if report.transactions.is_empty() {
+        writer.text(REGULAR, 10.0, MARGIN, "No posted transactions this month.");
+        writer.advance(16.0);
+    } else {
+        let columns = [(COL_DATE, "Date"), (COL_DESCRIPTION, "Description"), (COL_CATEGORY, "Category")];
+        writer.table_header(&columns, "Amount");
+        for transaction in &report.transactions {
+            // Repeat the column headers at the top of every continuation page
+            if writer.ensure_space(15.0) {
+                writer.table_header(&columns, "Amount");
+            }
+            let amount = transaction.amount.parse::<Decimal>().unwrap_or_default();
+            let signed = if transaction.transaction_type == "expense" { -amount.abs() } else { amount };
+            writer.text(REGULAR, 9.0, COL_DATE, &transaction.transaction_date.to_string());
+            writer.text(REGULAR, 9.0, COL_DESCRIPTION, &truncate(&transaction.description, 42));
+            writer.text(REGULAR, 9.0, COL_CATEGORY, &truncate(transaction.category.as_deref().unwrap_or("-"), 20));
+            writer.text_right(REGULAR, 9.0, COL_RIGHT, &format_amount(signed));
+            writer.advance(15.0);
+        }
+    }
+
+    writer.finish(&format!("Generated {}", report.generated_at.format("%Y-%m-%d %H:%M UTC")))
+}
+
+struct PageWriter {
+    pages: Vec<Content>,
+    current: Content,
+    y: f32,
+}
+
+impl PageWriter {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            current: Content::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn advance(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    // Starts a new page when the next row wouldn't fit, returning whether it did
+    fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y - height >= BOTTOM {
+            return false;
+        }
+        let page = std::mem::replace(&mut self.current, Content::new());
+        self.pages.push(page);
+        self.y = PAGE_HEIGHT - MARGIN;
+        true
+    }
+
+    fn heading(&mut self, title: &str) {
+        self.ensure_space(40.0);
+        self.text(BOLD, 12.0, MARGIN, title);
+        self.advance(18.0);
+    }
+
+    fn table_header(&mut self, columns: &[(f32, &str)], amount_label: &str) {
+        self.ensure_space(20.0);
+        for (x, label) in columns {
+            self.text(BOLD, 9.0, *x, label);
+        }
+        self.text_right(BOLD, 9.0, COL_RIGHT, amount_label);
+        self.current
+            .set_line_width(0.5)
+            .move_to(MARGIN, self.y - 4.0)
+            .line_to(COL_RIGHT, self.y - 4.0)
+            .stroke();
+        self.advance(16.0);
+    }
+
+    fn text(&mut self, font: Name, size: f32, x: f32, text: &str) {
+        self.current
+            .begin_text()
+            .set_font(font, size)
+            .next_line(x, self.y)
+            .show(Str(&encode(text)))
+            .end_text();
+    }
+
+    fn text_right(&mut self, font: Name, size: f32, right: f32, text: &str) {
+        let x = right - text_width(text, size);
+        self.text(font, size, x, text);
+    }
+
+    fn finish(mut self, footer: &str) -> Vec<u8> {
+        self.pages.push(self.current);
+
+        let mut pdf = Pdf::new();
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let regular_id = Ref::new(3);
+        let bold_id = Ref::new(4);
+        let page_ids: Vec<Ref> = (0..self.pages.len()).map(|i| Ref::new(5 + 2 * i as i32)).collect();
+
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id).kids(page_ids.iter().copied()).count(page_ids.len() as i32);
+        // Base-14 fonts need no embedding; WinAnsi covers Latin-1 text
+        pdf.type1_font(regular_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.type1_font(bold_id)
+            .base_font(Name(b"Helvetica-Bold"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+        let total = self.pages.len();
+        for (index, mut content) in self.pages.into_iter().enumerate() {
+            let page_id = page_ids[index];
+            let content_id = Ref::new(page_id.get() + 1);
+
+            let page_label = format!("Page {} of {}", index + 1, total);
+            content
+                .begin_text()
+                .set_font(REGULAR, 8.0)
+                .next_line(MARGIN, BOTTOM - 25.0)
+                .show(Str(&encode(footer)))
+                .end_text();
+            content
+                .begin_text()
+                .set_font(REGULAR, 8.0)
+                .next_line(COL_RIGHT - text_width(&page_label, 8.0), BOTTOM - 25.0)
+                .show(Str(&encode(&page_label)))
+                .end_text();
+
+            let mut page = pdf.page(page_id);
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+            page.parent(page_tree_id);
+            page.contents(content_id);
+            page.resources().fonts().pair(REGULAR, regular_id).pair(BOLD, bold_id);
+            page.finish();
+
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+fn format_amount(amount: Decimal) -> String {
+    let rounded = amount.round_dp(2);
+    let sign = if rounded.is_sign_negative() && !rounded.is_zero() { "-" } else { "" };
+    let fixed = format!("{:.2}", rounded.abs());
+    let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, "00"));
+
+    // Group thousands so large balances stay readable
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    format!("{}{}.{}", sign, grouped, fraction)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+// WinAnsi matches Latin-1 for printable characters plus a few typographic extras; anything else is replaced
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7E}' | '\u{A0}'..='\u{FF}' => c as u8,
+            '€' => 0x80,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+// Approximate Helvetica advance widths, enough to right-align figures
+fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => 556,
+            '.' | ',' | ' ' => 278,
+            '-' => 333,
+            'A'..='Z' => 667,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use super::{signed_amount, ExportData, Exporter};
+use crate::models::Transaction;
+
+pub struct QifExporter;
+
+impl Exporter for QifExporter {
+    fn content_type(&self) -> &'static str {
+        "application/qif"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "qif"
+    }
+
+    fn export(&self, data: &ExportData) -> String {
+        // QIF has no per-transaction account, so transactions are grouped
+        // under an !Account header for each pocket
+        let mut by_account: BTreeMap<String, Vec<&Transaction>> = BTreeMap::new();
+        for transaction in &data.transactions {
+            by_account
+                .entry(data.accounts.asset_account(transaction.account_id))
+                .or_default()
+                .push(transaction);
+        }
+
+        let mut output = String::new();
+        for (account, transactions) in by_account {
+            output.push_str("!Account\n");
+            output.push_str(&format!("N{}\n", account));
+            output.push_str("TBank\n");
+            output.push_str("^\n");
+            output.push_str("!Type:Bank\n");
+
+            for transaction in transactions {
+                output.push_str(&format!("D{}\n", transaction.transaction_date.format("%m/%d/%Y")));
+                output.push_str(&format!("T{}\n", signed_amount(transaction)));
+                output.push_str(&format!("P{}\n", single_line(&transaction.description)));
+                output.push_str(&format!("L{}\n", data.accounts.category_account(transaction)));
+                output.push_str(&format!("N{}\n", transaction.id));
+                output.push_str("^\n");
+            }
+        }
+
+        output
+    }
+}
+
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::fixtures::export_data;
+
+    #[test]
+    fn transactions_are_grouped_under_their_pocket_account() {
+        let output = QifExporter.export(&export_data());
+
+        assert_eq!(
+            output,
+            "!Account\nNAssets:Pockets:Travel- Wallet\nTBank\n^\n!Type:Bank\n\
+             D03/05/2024\nT-42.50\nPTransaction 1\nLExpenses:Food\nN1\n^\n\
+             D03/01/2024\nT1000.00\nPTransaction 2\nLIncome:Salary\nN2\n^\n\
+             !Account\nNAssets:Unassigned\nTBank\n^\n!Type:Bank\n\
+             D03/07/2024\nT-15000\nPTransaction 3\nLExpenses:Transport\nN3\n^\n"
+        );
+    }
+
+    #[test]
+    fn descriptions_stay_on_one_line() {
+        let mut data = export_data();
+        data.transactions[0].description = "Dinner\r\nwith friends".to_string();
+
+        assert!(QifExporter.export(&data).contains("PDinner  with friends\n"));
+    }
+}
@@ -0,0 +1,32 @@
+use async_graphql::dataloader::Loader;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::models::PocketResponse;
+use crate::repositories::PocketRepository;
+use crate::services::PocketService;
+use crate::utils::AppError;
+
+// Resolves the pocket of every transaction in a page with one query. A loader is
+// built per request and scoped to the caller, so it never returns someone else's pocket.
+pub struct PocketLoader<P: PocketRepository> {
+    pocket_service: PocketService<P>,
+    user_id: Uuid,
+}
+
+impl<P: PocketRepository> PocketLoader<P> {
+    pub fn new(pocket_service: PocketService<P>, user_id: Uuid) -> Self {
+        Self { pocket_service, user_id }
+    }
+}
+
+impl<P: PocketRepository + 'static> Loader<Uuid> for PocketLoader<P> {
+    type Value = PocketResponse;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let pockets = self.pocket_service.get_pockets_by_ids(self.user_id, keys).await.map_err(Arc::new)?;
+        Ok(pockets.into_iter().map(|pocket| (pocket.id, pocket)).collect())
+    }
+}
\ No newline at end of file
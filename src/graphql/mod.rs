@@ -0,0 +1,5 @@
+pub mod loaders;
+pub mod schema;
+
+pub use loaders::*;
+pub use schema::*;
\ No newline at end of file
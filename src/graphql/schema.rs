@@ -0,0 +1,144 @@
+use async_graphql::{
+    dataloader::DataLoader, ComplexObject, Context, EmptyMutation, EmptySubscription, Json, Object, Result, Schema,
+};
+use uuid::Uuid;
+
+use crate::graphql::PocketLoader;
+use crate::middleware::AuthUser;
+use crate::models::{
+    AccountSummaryResponse, BudgetSummaryResponse, DateRangeQuery, ExpenseSummaryResponse, IncomeDateRangeQuery,
+    IncomeSummaryResponse, ListBudgetsQuery, ListBudgetsResponse, ListTransactionsQuery, ListTransactionsResponse,
+    PocketResponse, TransactionResponse, UserResponse,
+};
+use crate::repositories::{
+    PostgresBudgetRepository, PostgresCurrencyRepository, PostgresMonthlyAggregateRepository, PostgresPocketRepository, PostgresSpendingLimitRepository,
+    PostgresTransactionRepository, PostgresUserRepository,
+};
+use crate::services::{
+    AccountSummaryService, BudgetService, ExpenseAnalyticsService, IncomeAnalyticsService, PocketService,
+    TransactionService, UserService,
+};
+use crate::utils::validate_data;
+
+// Keeps a single dashboard query from fanning out without bound
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 500;
+
+// Read-only view over the REST services. Field names stay snake_case so responses
+// match the REST payloads and balance masking recognises the same keys.
+pub struct QueryRoot;
+
+#[Object(rename_fields = "snake_case", rename_args = "snake_case")]
+impl QueryRoot {
+    async fn me(&self, ctx: &Context<'_>) -> Result<UserResponse> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<UserService<PostgresUserRepository>>()?;
+        Ok(service.get_user_by_id(auth_user.id).await?)
+    }
+
+    async fn pockets(&self, ctx: &Context<'_>, include_archived: Option<bool>) -> Result<Vec<PocketResponse>> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<PocketService<PostgresPocketRepository>>()?;
+        Ok(service.get_user_pockets(auth_user.id, include_archived.unwrap_or(false)).await?)
+    }
+
+    async fn pocket(&self, ctx: &Context<'_>, id: Uuid) -> Result<PocketResponse> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<PocketService<PostgresPocketRepository>>()?;
+        Ok(service.get_pocket_by_id(id, auth_user.id).await?)
+    }
+
+    async fn transactions(&self, ctx: &Context<'_>, filter: Option<ListTransactionsQuery>) -> Result<ListTransactionsResponse> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>()?;
+        let filter = filter.unwrap_or_default();
+        validate_data(&filter)?;
+        Ok(service.list_transactions(auth_user.id, filter).await?)
+    }
+
+    async fn budgets(&self, ctx: &Context<'_>, filter: Option<ListBudgetsQuery>) -> Result<ListBudgetsResponse> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<BudgetService<PostgresBudgetRepository>>()?;
+        let filter = filter.unwrap_or_default();
+        validate_data(&filter)?;
+        Ok(service.list_budgets(auth_user.id, filter).await?)
+    }
+
+    async fn budget_summary(&self, ctx: &Context<'_>) -> Result<Json<BudgetSummaryResponse>> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<BudgetService<PostgresBudgetRepository>>()?;
+        Ok(Json(service.get_budget_summary(auth_user.id).await?))
+    }
+
+    async fn account_summary(&self, ctx: &Context<'_>, include_archived: Option<bool>) -> Result<Json<AccountSummaryResponse>> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<AccountSummaryService<PostgresPocketRepository, PostgresTransactionRepository>>()?;
+        Ok(Json(service.get_account_summary(auth_user.id, include_archived.unwrap_or(false)).await?))
+    }
+
+    async fn expense_summary(&self, ctx: &Context<'_>, from_date: String, to_date: String) -> Result<Json<ExpenseSummaryResponse>> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>()?;
+        let query = DateRangeQuery { from_date, to_date };
+        Ok(Json(service.get_expense_summary(auth_user.id, query).await?))
+    }
+
+    async fn income_summary(&self, ctx: &Context<'_>, from_date: String, to_date: String) -> Result<Json<IncomeSummaryResponse>> {
+        let auth_user = ctx.data::<AuthUser>()?;
+        let service = ctx.data::<IncomeAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>()?;
+        let query = IncomeDateRangeQuery { from_date, to_date };
+        Ok(Json(service.get_income_summary(auth_user.id, query).await?))
+    }
+}
+
+#[ComplexObject(rename_fields = "snake_case")]
+impl TransactionResponse {
+    async fn pocket(&self, ctx: &Context<'_>) -> Result<Option<PocketResponse>> {
+        let Some(pocket_id) = self.account_id else {
+            return Ok(None);
+        };
+
+        let loader = ctx.data::<DataLoader<PocketLoader<PostgresPocketRepository>>>()?;
+        Ok(loader.load_one(pocket_id).await?)
+    }
+}
+
+#[derive(Clone)]
+pub struct GraphqlService {
+    schema: Schema<QueryRoot, EmptyMutation, EmptySubscription>,
+    pocket_service: PocketService<PostgresPocketRepository>,
+}
+
+impl GraphqlService {
+    pub fn new(
+        user_service: UserService<PostgresUserRepository>,
+        pocket_service: PocketService<PostgresPocketRepository>,
+        transaction_service: TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>,
+        budget_service: BudgetService<PostgresBudgetRepository>,
+        account_summary_service: AccountSummaryService<PostgresPocketRepository, PostgresTransactionRepository>,
+        expense_analytics_service: ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>,
+        income_analytics_service: IncomeAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>,
+    ) -> Self {
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .data(user_service)
+            .data(pocket_service.clone())
+            .data(transaction_service)
+            .data(budget_service)
+            .data(account_summary_service)
+            .data(expense_analytics_service)
+            .data(income_analytics_service)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .finish();
+
+        Self {
+            schema,
+            pocket_service,
+        }
+    }
+
+    pub async fn execute(&self, auth_user: AuthUser, request: async_graphql::Request) -> async_graphql::Response {
+        let loader = DataLoader::new(PocketLoader::new(self.pocket_service.clone(), auth_user.id), tokio::spawn);
+        self.schema.execute(request.data(auth_user).data(loader)).await
+    }
+}
\ No newline at end of file
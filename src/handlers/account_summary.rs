@@ -4,25 +4,32 @@ use axum::{
 };
 
 use crate::middleware::AuthUser;
-use crate::services::AccountSummaryService;
-use crate::repositories::{PostgresPocketRepository, PostgresTransactionRepository};
-use crate::utils::{AppError, success_response, CacheService};
+use crate::models::{AccountSummaryQuery, AccountSummaryResponse, FormatAmounts};
+use crate::services::{AccountSummaryService, PreferenceService};
+use crate::repositories::{PostgresCurrencyRepository, PostgresPocketRepository, PostgresPreferenceRepository, PostgresTransactionRepository};
+use crate::utils::{AppError, ValidatedQuery, success_response, CacheService, ACCOUNT_SUMMARY};
 
 pub async fn get_account_summary(
     State(service): State<AccountSummaryService<PostgresPocketRepository, PostgresTransactionRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
+    ValidatedQuery(query): ValidatedQuery<AccountSummaryQuery>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let cache_key = format!("account_summary:{}", auth_user.id);
+    let formatter = preferences.money_formatter(auth_user.id).await?;
+    let include_archived = query.include_archived.unwrap_or(false);
+    let cache = cache.typed::<AccountSummaryResponse>(ACCOUNT_SUMMARY);
+    let cache_key = cache.key(&auth_user.id, &[&include_archived]);
 
-    if let Some(cached_response) = cache.get::<crate::models::AccountSummaryResponse>(&cache_key).await {
+    if let Some(mut cached_response) = cache.get(&cache_key).await {
+        cached_response.format_amounts(&formatter);
         return Ok(success_response(cached_response));
     }
 
-    let response = service.get_account_summary(auth_user.id).await?;
+    let mut response = service.get_account_summary(auth_user.id, include_archived).await?;
 
-    // Cache the response for 5 minutes
-    let _ = cache.set(&cache_key, &response, Some(300)).await;
+    let _ = cache.set(&cache_key, &response).await;
 
+    response.format_amounts(&formatter);
     Ok(success_response(response))
 }
\ No newline at end of file
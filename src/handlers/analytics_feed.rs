@@ -0,0 +1,92 @@
+use axum::{
+    extract::{Path, State, Extension},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{AnalyticsFeedDocument, AnalyticsFeedQuery, CreateAnalyticsFeedRequest};
+use crate::services::{AnalyticsFeedService, feed_months};
+use crate::repositories::{PostgresAnalyticsFeedRepository, PostgresTransactionRepository, PostgresUserRepository};
+use crate::utils::{AppError, CacheService, ValidatedJson, ValidatedQuery, hash_token, success_response, created_response, ANALYTICS_FEED};
+
+pub async fn create_analytics_feed(
+    State(service): State<AnalyticsFeedService<PostgresAnalyticsFeedRepository, PostgresTransactionRepository, PostgresUserRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<CreateAnalyticsFeedRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create_feed(auth_user.id, request).await?;
+    Ok(created_response(response))
+}
+
+pub async fn list_analytics_feeds(
+    State(service): State<AnalyticsFeedService<PostgresAnalyticsFeedRepository, PostgresTransactionRepository, PostgresUserRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let feeds = service.list_feeds(auth_user.id).await?;
+    Ok(success_response(feeds))
+}
+
+pub async fn revoke_analytics_feed(
+    State(service): State<AnalyticsFeedService<PostgresAnalyticsFeedRepository, PostgresTransactionRepository, PostgresUserRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let feed = service.revoke_feed(auth_user.id, id).await?;
+    Ok(success_response(feed))
+}
+
+// The bare document is returned rather than the usual envelope so dashboards can read `rows` directly.
+// The token is checked on every request; only the computed document is cached.
+pub async fn get_analytics_feed(
+    State(service): State<AnalyticsFeedService<PostgresAnalyticsFeedRepository, PostgresTransactionRepository, PostgresUserRepository>>,
+    State(cache): State<CacheService>,
+    Path(token): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<AnalyticsFeedQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let feed = service.resolve(&token).await?;
+    let months = feed_months(query.months);
+
+    let cache = cache.typed::<AnalyticsFeedDocument>(ANALYTICS_FEED);
+    let cache_key = cache.key(&feed.user_id, &[&feed.id, &months]);
+    let document = match cache.get(&cache_key).await {
+        Some(document) => document,
+        None => {
+            let document = service.build_document(&feed, months).await?;
+            if !cache.set(&cache_key, &document).await {
+                error!("Failed to cache analytics feed {}", feed.id);
+            }
+            document
+        }
+    };
+
+    let body = serde_json::to_string(&document)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to serialize feed: {}", e)))?;
+    let etag = format!("\"{}\"", &hash_token(&body)[..32]);
+    let cache_control = format!("private, max-age={}", ANALYTICS_FEED.ttl_seconds);
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+    if not_modified {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        body,
+    )
+        .into_response())
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Extension,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::AnomalyQuery;
+use crate::services::AnomalyService;
+use crate::repositories::PostgresAnomalyRepository;
+use crate::utils::{AppError, ValidatedQuery, success_response};
+
+pub async fn get_anomalies(
+    State(service): State<AnomalyService<PostgresAnomalyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<AnomalyQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_anomalies(auth_user.id, query).await?;
+    Ok(success_response(response))
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{State, Extension},
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::AuditLogQuery;
+use crate::services::AuditService;
+use crate::repositories::PostgresAuditRepository;
+use crate::utils::{AppError, ValidatedQuery, success_response};
+
+pub async fn get_audit_log(
+    State(service): State<AuditService<PostgresAuditRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<AuditLogQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.list_entries(auth_user.id, query).await?;
+    Ok(success_response(response))
+}
+
+pub async fn export_audit_log(
+    State(service): State<AuditService<PostgresAuditRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<AuditLogQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let file = service.export_entries(auth_user.id, query).await?;
+
+    let disposition = format!("attachment; filename=\"{}\"", file.filename);
+    Ok((
+        [
+            (header::CONTENT_TYPE, file.content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        file.content,
+    ))
+}
\ No newline at end of file
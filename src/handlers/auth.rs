@@ -1,22 +1,62 @@
-use axum::{extract::State, response::IntoResponse};
+use axum::{extract::State, http::header, response::IntoResponse, Json};
 
-use crate::models::{LoginRequest, RegisterRequest};
-use crate::services::AuthService;
-use crate::repositories::PostgresAuthRepository;
-use crate::utils::{AppError, ValidatedJson, success_response, created_response};
+use crate::config::JwtConfig;
+use crate::handlers::session::mark_sessions_revoked;
+use crate::middleware::AuthUser;
+use crate::models::{LoginRequest, RegisterRequest, StepUpRequest, RefreshTokenRequest, ClientInfo};
+use crate::services::{AuthService, RefreshOutcome};
+use crate::repositories::{PostgresAuthRepository, PostgresSessionRepository};
+use crate::utils::{AppError, codes, CacheService, ValidatedJson, success_response, created_response};
+
+const JWKS_CACHE_SECONDS: u64 = 300;
 
 pub async fn register(
-    State(auth_service): State<AuthService<PostgresAuthRepository>>,
+    State(auth_service): State<AuthService<PostgresAuthRepository, PostgresSessionRepository>>,
+    client: ClientInfo,
     ValidatedJson(request): ValidatedJson<RegisterRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = auth_service.register(request).await?;
+    let response = auth_service.register(request, client).await?;
     Ok(created_response(response))
 }
 
 pub async fn login(
-    State(auth_service): State<AuthService<PostgresAuthRepository>>,
+    State(auth_service): State<AuthService<PostgresAuthRepository, PostgresSessionRepository>>,
+    client: ClientInfo,
     ValidatedJson(request): ValidatedJson<LoginRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = auth_service.login(request).await?;
+    let response = auth_service.login(request, client).await?;
+    Ok(success_response(response))
+}
+
+pub async fn step_up(
+    State(auth_service): State<AuthService<PostgresAuthRepository, PostgresSessionRepository>>,
+    auth_user: AuthUser,
+    client: ClientInfo,
+    ValidatedJson(request): ValidatedJson<StepUpRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = auth_service.step_up(auth_user.id, auth_user.session_id, request, client).await?;
     Ok(success_response(response))
+}
+
+pub async fn refresh(
+    State(auth_service): State<AuthService<PostgresAuthRepository, PostgresSessionRepository>>,
+    State(cache_service): State<CacheService>,
+    State(jwt_config): State<JwtConfig>,
+    client: ClientInfo,
+    ValidatedJson(request): ValidatedJson<RefreshTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    match auth_service.refresh(request, client).await? {
+        RefreshOutcome::Refreshed(response) => Ok(success_response(response)),
+        RefreshOutcome::FamilyRevoked(session_id) => {
+            // Access tokens from the revoked session must stop working immediately
+            mark_sessions_revoked(&cache_service, &jwt_config, &[session_id]).await;
+            Err(AppError::Unauthorized("Refresh token reuse detected, please sign in again".to_string()).with_code(codes::TOKEN_REVOKED))
+        }
+    }
+}
+
+// Lets other services verify our access tokens without holding the signing secret
+pub async fn jwks(State(jwt_config): State<JwtConfig>) -> impl IntoResponse {
+    let cache_control = format!("public, max-age={}", JWKS_CACHE_SECONDS);
+    ([(header::CACHE_CONTROL, cache_control)], Json(jwt_config.jwks))
 }
\ No newline at end of file
@@ -1,40 +1,53 @@
 use axum::{
-    extract::{Path, Query, State, Extension},
+    extract::{Path, State, Extension},
     response::IntoResponse,
 };
 
 use crate::middleware::AuthUser;
-use crate::models::{CreateBudgetRequest, UpdateBudgetRequest, ListBudgetsQuery};
-use crate::services::BudgetService;
-use crate::repositories::PostgresBudgetRepository;
-use crate::utils::{AppError, ValidatedJson, success_response, created_response, no_content_response, CacheService};
+use crate::models::{CreateBudgetRequest, UpdateBudgetRequest, IfUnmodifiedSince, ListBudgetsQuery, ListBudgetsResponse, BudgetHistoryQuery, FormatAmounts};
+use crate::services::{BudgetService, PreferenceService};
+use crate::repositories::{PostgresBudgetRepository, PostgresCurrencyRepository, PostgresPreferenceRepository};
+use crate::utils::{
+    AppError, ValidatedJson, ValidatedQuery, success_response, created_response, no_content_response, CacheService, BUDGETS,
+    BUDGET_SUMMARY, BUDGET_PERFORMANCE, BUDGET_DETAIL_PERFORMANCE, BUDGET_CATEGORIES, BUDGET_SUGGESTIONS, BUDGET_VIEWS,
+};
 
 pub async fn get_budgets(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Query(query): Query<ListBudgetsQuery>,
-    Extension(cache): Extension<CacheService>,
+    ValidatedQuery(query): ValidatedQuery<ListBudgetsQuery>,
+    State(cache): State<CacheService>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
 ) -> Result<impl IntoResponse, AppError> {
+    let formatter = preferences.money_formatter(auth_user.id).await?;
+
     // Try to get from cache first
-    let cache_key = format!(
-        "budgets:{}:page:{}:limit:{}:category:{}:period_type:{}:active:{}",
-        auth_user.id,
+    let cache = cache.typed::<ListBudgetsResponse>(BUDGETS);
+    let filters = format!(
+        "page:{}:limit:{}:category:{}:categories:{}:period_type:{}:active:{}:min:{}:max:{}:sort:{}:order:{}",
         query.page.unwrap_or(1),
         query.limit.unwrap_or(20),
         query.category.as_deref().unwrap_or(""),
+        query.categories.as_deref().unwrap_or(""),
         query.period_type.as_deref().unwrap_or(""),
-        query.is_active.map(|b| b.to_string()).unwrap_or_default()
+        query.is_active.map(|b| b.to_string()).unwrap_or_default(),
+        query.min_amount.as_deref().unwrap_or(""),
+        query.max_amount.as_deref().unwrap_or(""),
+        query.sort_by.as_deref().unwrap_or(""),
+        query.order.as_deref().unwrap_or("")
     );
+    let cache_key = cache.key(&auth_user.id, &[&filters]);
 
-    if let Some(cached_response) = cache.get::<crate::models::ListBudgetsResponse>(&cache_key).await {
+    if let Some(mut cached_response) = cache.get(&cache_key).await {
+        cached_response.format_amounts(&formatter);
         return Ok(success_response(cached_response));
     }
 
-    let response = service.list_budgets(auth_user.id, query).await?;
+    let mut response = service.list_budgets(auth_user.id, query).await?;
 
-    // Cache the response for 5 minutes
-    let _ = cache.set(&cache_key, &response, Some(300)).await;
+    let _ = cache.set(&cache_key, &response).await;
 
+    response.format_amounts(&formatter);
     Ok(success_response(response))
 }
 
@@ -42,50 +55,57 @@ pub async fn get_budget_by_id(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<i64>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = service.get_budget_by_id(id, auth_user.id).await?;
+    let mut response = service.get_budget_by_id(id, auth_user.id).await?;
+    response.format_amounts(&preferences.money_formatter(auth_user.id).await?);
     Ok(success_response(response))
 }
 
 pub async fn create_budget(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
     ValidatedJson(request): ValidatedJson<CreateBudgetRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = service.create_budget(auth_user.id, request).await?;
+    let mut response = service.create_budget(auth_user.id, request).await?;
 
-    // Invalidate budget cache
-    let _ = cache.delete(&format!("budgets:{}:*", auth_user.id)).await;
+    // Drop every cached view of the user's budgets
+    cache.invalidate_group(BUDGET_VIEWS, &auth_user.id).await;
 
+    response.format_amounts(&preferences.money_formatter(auth_user.id).await?);
     Ok(created_response(response))
 }
 
 pub async fn update_budget(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
     Path(id): Path<i64>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
+    unmodified_since: IfUnmodifiedSince,
     ValidatedJson(request): ValidatedJson<UpdateBudgetRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = service.update_budget(id, auth_user.id, request).await?;
+    let mut response = service.update_budget(id, auth_user.id, request, unmodified_since).await?;
 
-    // Invalidate budget cache
-    let _ = cache.delete(&format!("budgets:{}:*", auth_user.id)).await;
+    // Drop every cached view of the user's budgets
+    cache.invalidate_group(BUDGET_VIEWS, &auth_user.id).await;
 
+    response.format_amounts(&preferences.money_formatter(auth_user.id).await?);
     Ok(success_response(response))
 }
 
 pub async fn delete_budget(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
     Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, AppError> {
     service.delete_budget(id, auth_user.id).await?;
 
-    // Invalidate budget cache
-    let _ = cache.delete(&format!("budgets:{}:*", auth_user.id)).await;
+    // Drop every cached view of the user's budgets
+    cache.invalidate_group(BUDGET_VIEWS, &auth_user.id).await;
 
     Ok(no_content_response())
 }
@@ -93,18 +113,11 @@ pub async fn delete_budget(
 pub async fn get_budget_summary(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
 ) -> Result<impl IntoResponse, AppError> {
-    let cache_key = format!("budget_summary:{}", auth_user.id);
-
-    if let Some(cached_response) = cache.get::<crate::models::BudgetSummaryResponse>(&cache_key).await {
-        return Ok(success_response(cached_response));
-    }
-
-    let response = service.get_budget_summary(auth_user.id).await?;
-
-    // Cache the response for 10 minutes
-    let _ = cache.set(&cache_key, &response, Some(600)).await;
+    let cache = cache.typed(BUDGET_SUMMARY);
+    let cache_key = cache.key(&auth_user.id, &[]);
+    let response = cache.get_or_compute(&cache_key, || service.get_budget_summary(auth_user.id)).await?;
 
     Ok(success_response(response))
 }
@@ -112,37 +125,46 @@ pub async fn get_budget_summary(
 pub async fn get_budget_performance(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
 ) -> Result<impl IntoResponse, AppError> {
-    let cache_key = format!("budget_performance:{}", auth_user.id);
+    let cache = cache.typed(BUDGET_PERFORMANCE);
+    let cache_key = cache.key(&auth_user.id, &[]);
+    let response = cache.get_or_compute(&cache_key, || service.get_budget_performance(auth_user.id)).await?;
 
-    if let Some(cached_response) = cache.get::<crate::models::BudgetPerformanceResponse>(&cache_key).await {
-        return Ok(success_response(cached_response));
-    }
+    Ok(success_response(response))
+}
 
-    let response = service.get_budget_performance(auth_user.id).await?;
+pub async fn get_budget_detail_performance(
+    State(service): State<BudgetService<PostgresBudgetRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    State(cache): State<CacheService>,
+) -> Result<impl IntoResponse, AppError> {
+    let cache = cache.typed(BUDGET_DETAIL_PERFORMANCE);
+    let cache_key = cache.key(&auth_user.id, &[&id]);
+    let response = cache.get_or_compute(&cache_key, || service.get_budget_detail_performance(id, auth_user.id)).await?;
 
-    // Cache the response for 5 minutes (performance data changes more frequently)
-    let _ = cache.set(&cache_key, &response, Some(300)).await;
+    Ok(success_response(response))
+}
 
+pub async fn get_budget_history(
+    State(service): State<BudgetService<PostgresBudgetRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    ValidatedQuery(query): ValidatedQuery<BudgetHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_budget_history(id, auth_user.id, query).await?;
     Ok(success_response(response))
 }
 
 pub async fn get_budget_categories(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
 ) -> Result<impl IntoResponse, AppError> {
-    let cache_key = format!("budget_categories:{}", auth_user.id);
-
-    if let Some(cached_response) = cache.get::<Vec<String>>(&cache_key).await {
-        return Ok(success_response(cached_response));
-    }
-
-    let response = service.get_budget_categories(auth_user.id).await?;
-
-    // Cache the response for 15 minutes
-    let _ = cache.set(&cache_key, &response, Some(900)).await;
+    let cache = cache.typed(BUDGET_CATEGORIES);
+    let cache_key = cache.key(&auth_user.id, &[]);
+    let response = cache.get_or_compute(&cache_key, || service.get_budget_categories(auth_user.id)).await?;
 
     Ok(success_response(response))
 }
@@ -150,18 +172,11 @@ pub async fn get_budget_categories(
 pub async fn get_budget_suggestions(
     State(service): State<BudgetService<PostgresBudgetRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
 ) -> Result<impl IntoResponse, AppError> {
-    let cache_key = format!("budget_suggestions:{}", auth_user.id);
-
-    if let Some(cached_response) = cache.get::<crate::models::BudgetSuggestionsResponse>(&cache_key).await {
-        return Ok(success_response(cached_response));
-    }
-
-    let response = service.get_budget_suggestions(auth_user.id).await?;
-
-    // Cache the response for 30 minutes (suggestions don't change frequently)
-    let _ = cache.set(&cache_key, &response, Some(1800)).await;
+    let cache = cache.typed(BUDGET_SUGGESTIONS);
+    let cache_key = cache.key(&auth_user.id, &[]);
+    let response = cache.get_or_compute(&cache_key, || service.get_budget_suggestions(auth_user.id)).await?;
 
     Ok(success_response(response))
 }
\ No newline at end of file
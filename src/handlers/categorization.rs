@@ -0,0 +1,28 @@
+use axum::{
+    extract::{State, Extension},
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::{CategoryFeedbackRequest, SuggestCategoryRequest};
+use crate::services::CategorizationService;
+use crate::repositories::PostgresCategorizationRepository;
+use crate::utils::{AppError, ValidatedJson, success_response, created_response};
+
+pub async fn suggest_category(
+    State(service): State<CategorizationService<PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<SuggestCategoryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.suggest_category(auth_user.id, request).await?;
+    Ok(success_response(response))
+}
+
+pub async fn submit_category_feedback(
+    State(service): State<CategorizationService<PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<CategoryFeedbackRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.record_feedback(auth_user.id, request).await?;
+    Ok(created_response(response))
+}
\ No newline at end of file
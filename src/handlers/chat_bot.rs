@@ -0,0 +1,118 @@
+use axum::{
+    extract::{Path, State, Extension},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use tracing::warn;
+
+use crate::handlers::transaction::after_transaction_created;
+use crate::middleware::AuthUser;
+use crate::models::{ChatAction, ChatMessage, CreateLinkCodeRequest, AUDIT_ACTION_CREATE};
+use crate::services::{ChatBotService, TransactionService, AuditService, AnomalyService, expense_logged_reply};
+use crate::repositories::{
+    PostgresLinkedAccountRepository, PostgresCategorizationRepository, PostgresTransactionRepository, PostgresSpendingLimitRepository,
+    PostgresPocketRepository, PostgresCurrencyRepository, PostgresAuditRepository, PostgresAnomalyRepository,
+};
+use crate::utils::{AppError, ValidatedJson, success_response, created_response, no_content_response, CacheService};
+
+pub async fn create_link_code(
+    State(service): State<ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<CreateLinkCodeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create_link_code(auth_user.id, request).await?;
+    Ok(created_response(response))
+}
+
+pub async fn list_linked_accounts(
+    State(service): State<ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let accounts = service.list_links(auth_user.id).await?;
+    Ok(success_response(accounts))
+}
+
+pub async fn unlink_account(
+    State(service): State<ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(provider): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    service.unlink(auth_user.id, &provider).await?;
+    Ok(no_content_response())
+}
+
+// Telegram takes a method call in the webhook response, so the reply needs no outbound request
+pub async fn receive_telegram_update(
+    State(service): State<ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>>,
+    State(transactions): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    State(anomalies): State<AnomalyService<PostgresAnomalyRepository>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    let secret_token = headers.get("x-telegram-bot-api-secret-token").and_then(|value| value.to_str().ok());
+    let Some((chat_id, message)) = service.telegram_message(secret_token, &body)? else {
+        return Ok(StatusCode::OK.into_response());
+    };
+
+    let reply = respond(&service, &transactions, &cache, &audit, &anomalies, message).await?;
+    Ok(Json(json!({ "method": "sendMessage", "chat_id": chat_id, "text": reply })).into_response())
+}
+
+pub async fn receive_slack_command(
+    State(service): State<ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>>,
+    State(transactions): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    State(anomalies): State<AnomalyService<PostgresAnomalyRepository>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, AppError> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let message = service.slack_message(header("x-slack-request-timestamp"), header("x-slack-signature"), &body)?;
+
+    let reply = respond(&service, &transactions, &cache, &audit, &anomalies, message).await?;
+    Ok(Json(json!({ "response_type": "ephemeral", "text": reply })).into_response())
+}
+
+async fn respond(
+    service: &ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>,
+    transactions: &TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>,
+    cache: &CacheService,
+    audit: &AuditService<PostgresAuditRepository>,
+    anomalies: &AnomalyService<PostgresAnomalyRepository>,
+    message: ChatMessage,
+) -> Result<String, AppError> {
+    let provider = message.provider;
+    let (user_id, request) = match service.handle(message).await? {
+        ChatAction::Reply(reply) => return Ok(reply),
+        ChatAction::LogExpense { user_id, request } => (user_id, request),
+    };
+
+    let (response, warning) = match transactions.create_transaction(user_id, *request).await {
+        Ok(created) => created,
+        Err(error) => return Ok(failure_reply(&error)),
+    };
+
+    let summary = format!("Created expense of {} from {}", response.amount, provider);
+    after_transaction_created(user_id, &response, AUDIT_ACTION_CREATE, &summary, cache, audit, anomalies).await;
+
+    Ok(expense_logged_reply(&response, warning.as_deref()))
+}
+
+// Chat services retry failed deliveries, so even a refused expense is answered with 200
+fn failure_reply(error: &AppError) -> String {
+    match error {
+        AppError::Detailed { inner, .. } => failure_reply(inner),
+        AppError::Conflict(message) | AppError::ValidationError(message) | AppError::BadRequest(message) => {
+            format!("Couldn't log that expense: {}", message)
+        }
+        other => {
+            warn!("Chat expense could not be created: {}", other);
+            "Couldn't log that expense right now. Please try again later.".to_string()
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::UpdateBaseCurrencyRequest;
+use crate::services::CurrencyService;
+use crate::repositories::{PostgresUserRepository, PostgresCurrencyRepository, PostgresJobRepository};
+use crate::utils::{AppError, ValidatedJson, success_response, CacheService, user_cache_key};
+
+pub async fn update_base_currency(
+    auth_user: AuthUser,
+    State(service): State<CurrencyService<PostgresUserRepository, PostgresCurrencyRepository, PostgresJobRepository>>,
+    State(cache_service): State<CacheService>,
+    ValidatedJson(request): ValidatedJson<UpdateBaseCurrencyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.change_base_currency(auth_user.id, request).await?;
+
+    // Invalidate user cache after update
+    cache_service.delete(&user_cache_key(&auth_user.id)).await;
+
+    Ok(success_response(response))
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::{CreateDebtRequest, UpdateDebtRequest, PayoffPlanQuery};
+use crate::services::DebtService;
+use crate::repositories::PostgresDebtRepository;
+use crate::utils::{AppError, ValidatedJson, ValidatedQuery, success_response, created_response, no_content_response};
+
+pub async fn get_debts(
+    State(service): State<DebtService<PostgresDebtRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.list_debts(auth_user.id).await?;
+    Ok(success_response(response))
+}
+
+pub async fn get_debt_by_id(
+    State(service): State<DebtService<PostgresDebtRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_debt_by_id(id, auth_user.id).await?;
+    Ok(success_response(response))
+}
+
+pub async fn create_debt(
+    State(service): State<DebtService<PostgresDebtRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<CreateDebtRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create_debt(auth_user.id, request).await?;
+    Ok(created_response(response))
+}
+
+pub async fn update_debt(
+    State(service): State<DebtService<PostgresDebtRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<UpdateDebtRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.update_debt(id, auth_user.id, request).await?;
+    Ok(success_response(response))
+}
+
+pub async fn delete_debt(
+    State(service): State<DebtService<PostgresDebtRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete_debt(id, auth_user.id).await?;
+    Ok(no_content_response())
+}
+
+pub async fn get_debt_payoff_plan(
+    State(service): State<DebtService<PostgresDebtRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    ValidatedQuery(query): ValidatedQuery<PayoffPlanQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_payoff_plan(id, auth_user.id, query).await?;
+    Ok(success_response(response))
+}
\ No newline at end of file
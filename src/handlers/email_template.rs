@@ -0,0 +1,23 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+
+use crate::models::EmailTemplatePreviewQuery;
+use crate::utils::{AppError, ValidatedQuery, DEFAULT_EMAIL_LOCALE, EmailTemplates, success_response};
+
+pub async fn list_email_templates(
+    State(templates): State<EmailTemplates>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(success_response(templates.list()))
+}
+
+pub async fn preview_email_template(
+    State(templates): State<EmailTemplates>,
+    Path(name): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<EmailTemplatePreviewQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let locale = query.locale.as_deref().unwrap_or(DEFAULT_EMAIL_LOCALE);
+    let preview = templates.preview(&name, locale)?;
+    Ok(success_response(preview))
+}
\ No newline at end of file
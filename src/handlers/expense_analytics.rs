@@ -1,181 +1,112 @@
 use axum::{
-    extract::{Query, State},
-    response::Json,
+    extract::State,
+    response::IntoResponse,
     Extension,
 };
-use tracing::{error, info};
-use validator::Validate;
+use tracing::info;
 
-use crate::models::{
-    DateRangeQuery, RecentTransactionsQuery, ExpenseSummaryResponse,
-    CategorySummaryResponse, TrendResponse, RecentTransactionsResponse,
-};
+use crate::middleware::AuthUser;
+use crate::models::{DateRangeQuery, RecentTransactionsQuery, HeatmapQuery};
 use crate::services::ExpenseAnalyticsService;
-use crate::repositories::PostgresTransactionRepository;
-use crate::utils::{AppError, CacheService};
+use crate::repositories::{PostgresMonthlyAggregateRepository, PostgresTransactionRepository};
+use crate::utils::{
+    AppError, ValidatedQuery, CacheService, success_response, EXPENSE_SUMMARY, EXPENSE_CATEGORY_SUMMARY, EXPENSE_MONTHLY_TREND,
+    EXPENSE_DAILY_TREND, EXPENSE_HEATMAP, RECENT_EXPENSE_TRANSACTIONS,
+};
 
 pub async fn get_expense_summary(
-    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository>>,
-    Extension(cache): Extension<CacheService>,
-    Extension(user_id): Extension<uuid::Uuid>,
-    Query(query): Query<DateRangeQuery>,
-) -> Result<Json<ExpenseSummaryResponse>, AppError> {
-    // Validate query parameters
-    query.validate().map_err(|e| {
-        AppError::BadRequest(format!("Validation error: {}", e))
-    })?;
-
+    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<DateRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_user.id;
     info!("Getting expense summary for user {}", user_id);
 
-    // Create cache key
-    let cache_key = format!("expense_summary:{}:{}:{}", user_id, query.from_date, query.to_date);
-
-    // Try to get from cache first
-    if let Some(cached_response) = cache.get::<ExpenseSummaryResponse>(&cache_key).await {
-        info!("Returning cached expense summary for user {}", user_id);
-        return Ok(Json(cached_response));
-    }
-
-    // Get from service
-    let response = service.get_expense_summary(user_id, query).await?;
+    let cache = cache.typed(EXPENSE_SUMMARY);
+    let cache_key = cache.key(&user_id, &[&query.from_date, &query.to_date]);
+    let response = cache.get_or_compute(&cache_key, || service.get_expense_summary(user_id, query)).await?;
 
-    // Cache the response for 15 minutes
-    if !cache.set(&cache_key, &response, Some(900)).await {
-        error!("Failed to cache expense summary");
-    }
-
-    Ok(Json(response))
+    Ok(success_response(response))
 }
 
 pub async fn get_expense_category_summary(
-    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository>>,
-    Extension(cache): Extension<CacheService>,
-    Extension(user_id): Extension<uuid::Uuid>,
-    Query(query): Query<DateRangeQuery>,
-) -> Result<Json<CategorySummaryResponse>, AppError> {
-    // Validate query parameters
-    query.validate().map_err(|e| {
-        AppError::BadRequest(format!("Validation error: {}", e))
-    })?;
-
+    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<DateRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_user.id;
     info!("Getting expense category summary for user {}", user_id);
 
-    // Create cache key
-    let cache_key = format!("expense_category_summary:{}:{}:{}", user_id, query.from_date, query.to_date);
-
-    // Try to get from cache first
-    if let Some(cached_response) = cache.get::<CategorySummaryResponse>(&cache_key).await {
-        info!("Returning cached expense category summary for user {}", user_id);
-        return Ok(Json(cached_response));
-    }
-
-    // Get from service
-    let response = service.get_category_summary(user_id, query).await?;
-
-    // Cache the response for 15 minutes
-    if !cache.set(&cache_key, &response, Some(900)).await {
-        error!("Failed to cache expense category summary");
-    }
+    let cache = cache.typed(EXPENSE_CATEGORY_SUMMARY);
+    let cache_key = cache.key(&user_id, &[&query.from_date, &query.to_date]);
+    let response = cache.get_or_compute(&cache_key, || service.get_category_summary(user_id, query)).await?;
 
-    Ok(Json(response))
+    Ok(success_response(response))
 }
 
 pub async fn get_expense_monthly_trend(
-    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository>>,
-    Extension(cache): Extension<CacheService>,
-    Extension(user_id): Extension<uuid::Uuid>,
-    Query(query): Query<DateRangeQuery>,
-) -> Result<Json<TrendResponse>, AppError> {
-    // Validate query parameters
-    query.validate().map_err(|e| {
-        AppError::BadRequest(format!("Validation error: {}", e))
-    })?;
-
+    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<DateRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_user.id;
     info!("Getting expense monthly trend for user {}", user_id);
 
-    // Create cache key
-    let cache_key = format!("expense_monthly_trend:{}:{}:{}", user_id, query.from_date, query.to_date);
-
-    // Try to get from cache first
-    if let Some(cached_response) = cache.get::<TrendResponse>(&cache_key).await {
-        info!("Returning cached expense monthly trend for user {}", user_id);
-        return Ok(Json(cached_response));
-    }
-
-    // Get from service
-    let response = service.get_monthly_trend(user_id, query).await?;
+    let cache = cache.typed(EXPENSE_MONTHLY_TREND);
+    let cache_key = cache.key(&user_id, &[&query.from_date, &query.to_date]);
+    let response = cache.get_or_compute(&cache_key, || service.get_monthly_trend(user_id, query)).await?;
 
-    // Cache the response for 15 minutes
-    if !cache.set(&cache_key, &response, Some(900)).await {
-        error!("Failed to cache expense monthly trend");
-    }
-
-    Ok(Json(response))
+    Ok(success_response(response))
 }
 
 pub async fn get_expense_daily_trend(
-    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository>>,
-    Extension(cache): Extension<CacheService>,
-    Extension(user_id): Extension<uuid::Uuid>,
-    Query(query): Query<DateRangeQuery>,
-) -> Result<Json<TrendResponse>, AppError> {
-    // Validate query parameters
-    query.validate().map_err(|e| {
-        AppError::BadRequest(format!("Validation error: {}", e))
-    })?;
-
+    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<DateRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_user.id;
     info!("Getting expense daily trend for user {}", user_id);
 
-    // Create cache key
-    let cache_key = format!("expense_daily_trend:{}:{}:{}", user_id, query.from_date, query.to_date);
+    let cache = cache.typed(EXPENSE_DAILY_TREND);
+    let cache_key = cache.key(&user_id, &[&query.from_date, &query.to_date]);
+    let response = cache.get_or_compute(&cache_key, || service.get_daily_trend(user_id, query)).await?;
 
-    // Try to get from cache first
-    if let Some(cached_response) = cache.get::<TrendResponse>(&cache_key).await {
-        info!("Returning cached expense daily trend for user {}", user_id);
-        return Ok(Json(cached_response));
-    }
+    Ok(success_response(response))
+}
 
-    // Get from service
-    let response = service.get_daily_trend(user_id, query).await?;
+pub async fn get_expense_heatmap(
+    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<HeatmapQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_user.id;
+    info!("Getting expense heatmap for user {}", user_id);
 
-    // Cache the response for 15 minutes
-    if !cache.set(&cache_key, &response, Some(900)).await {
-        error!("Failed to cache expense daily trend");
-    }
+    let cache = cache.typed(EXPENSE_HEATMAP);
+    let cache_key = cache.key(&user_id, &[&query.year.map(|year| year.to_string()).unwrap_or_default()]);
+    let response = cache.get_or_compute(&cache_key, || service.get_heatmap(user_id, query)).await?;
 
-    Ok(Json(response))
+    Ok(success_response(response))
 }
 
 pub async fn get_recent_expense_transactions(
-    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository>>,
-    Extension(cache): Extension<CacheService>,
-    Extension(user_id): Extension<uuid::Uuid>,
-    Query(query): Query<RecentTransactionsQuery>,
-) -> Result<Json<RecentTransactionsResponse>, AppError> {
-    // Validate query parameters
-    query.validate().map_err(|e| {
-        AppError::BadRequest(format!("Validation error: {}", e))
-    })?;
-
+    State(service): State<ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<RecentTransactionsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_user.id;
     let limit = query.limit.unwrap_or(10);
     info!("Getting {} recent expense transactions for user {}", limit, user_id);
 
-    // Create cache key
-    let cache_key = format!("recent_expense_transactions:{}:{}", user_id, limit);
-
-    // Try to get from cache first (shorter cache time for recent data)
-    if let Some(cached_response) = cache.get::<RecentTransactionsResponse>(&cache_key).await {
-        info!("Returning cached recent expense transactions for user {}", user_id);
-        return Ok(Json(cached_response));
-    }
-
-    // Get from service
-    let response = service.get_recent_transactions(user_id, query).await?;
-
-    // Cache the response for 5 minutes (shorter for recent data)
-    if !cache.set(&cache_key, &response, Some(300)).await {
-        error!("Failed to cache recent expense transactions");
-    }
+    let cache = cache.typed(RECENT_EXPENSE_TRANSACTIONS);
+    let cache_key = cache.key(&user_id, &[&limit]);
+    let response = cache.get_or_compute(&cache_key, || service.get_recent_transactions(user_id, query)).await?;
 
-    Ok(Json(response))
+    Ok(success_response(response))
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+use axum::{
+    extract::{State, Extension},
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::ExportTransactionsQuery;
+use crate::services::ExportService;
+use crate::repositories::{PostgresTransactionRepository, PostgresPocketRepository, PostgresCurrencyRepository};
+use crate::utils::{AppError, ValidatedQuery};
+
+pub async fn export_transactions(
+    State(service): State<ExportService<PostgresTransactionRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<ExportTransactionsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let file = service.export_transactions(auth_user.id, query).await?;
+
+    let disposition = format!("attachment; filename=\"{}\"", file.filename);
+    Ok((
+        [
+            (header::CONTENT_TYPE, file.content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        file.content,
+    ))
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use axum::{
+    extract::{Path, State, Extension},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{ClientInfo, CreateExportLinkRequest, ExportTransactionsQuery, MonthlyReportQuery, ReportFormat};
+use crate::services::ExportLinkService;
+use crate::repositories::{PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository};
+use crate::utils::{AppError, ValidatedJson, ValidatedQuery, success_response, created_response};
+
+pub async fn create_export_link(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<CreateExportLinkRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create_link(auth_user.id, request).await?;
+    Ok(created_response(response))
+}
+
+pub async fn list_export_links(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let links = service.list_links(auth_user.id).await?;
+    Ok(success_response(links))
+}
+
+pub async fn revoke_export_link(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let link = service.revoke_link(auth_user.id, id).await?;
+    Ok(success_response(link))
+}
+
+pub async fn get_export_link_access_log(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let entries = service.access_log(auth_user.id, id).await?;
+    Ok(success_response(entries))
+}
+
+// Revoking a link has to take effect immediately, so none of the shared responses may be stored
+pub async fn get_shared_export(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    client: ClientInfo,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let summary = service.shared_summary(&token, &client).await?;
+    Ok(([(header::CACHE_CONTROL, "no-store")], success_response(summary)))
+}
+
+pub async fn get_shared_export_transactions(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    client: ClientInfo,
+    Path(token): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<ExportTransactionsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let file = service.shared_transactions(&token, query, &client).await?;
+
+    let disposition = format!("attachment; filename=\"{}\"", file.filename);
+    Ok((
+        [
+            (header::CONTENT_TYPE, file.content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        file.content,
+    ))
+}
+
+pub async fn get_shared_export_report(
+    State(service): State<ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>>,
+    client: ClientInfo,
+    Path(token): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<MonthlyReportQuery>,
+) -> Result<Response, AppError> {
+    let format = match query.format.as_deref() {
+        Some(format) => ReportFormat::from_str(format)?,
+        None => ReportFormat::Pdf,
+    };
+
+    if format == ReportFormat::Json {
+        let report = service.shared_report(&token, &query.month, &client).await?;
+        return Ok(([(header::CACHE_CONTROL, "no-store")], success_response(report)).into_response());
+    }
+
+    let file = service.shared_report_pdf(&token, &query.month, &client).await?;
+
+    let disposition = format!("attachment; filename=\"{}\"", file.filename);
+    Ok((
+        [
+            (header::CONTENT_TYPE, file.content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        file.content,
+    )
+        .into_response())
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Extension,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::FinancialHealthQuery;
+use crate::services::FinancialHealthService;
+use crate::repositories::{PostgresBudgetRepository, PostgresCurrencyRepository, PostgresPocketRepository, PostgresTransactionRepository};
+use crate::utils::{AppError, CacheService, ValidatedQuery, success_response, FINANCIAL_HEALTH};
+
+pub async fn get_financial_health(
+    State(service): State<FinancialHealthService<PostgresPocketRepository, PostgresTransactionRepository, PostgresBudgetRepository, PostgresCurrencyRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<FinancialHealthQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let cache = cache.typed(FINANCIAL_HEALTH);
+    let cache_key = cache.key(&auth_user.id, &[&query.months.map(|months| months.to_string()).unwrap_or_default()]);
+    let response = cache.get_or_compute(&cache_key, || service.get_health(auth_user.id, query)).await?;
+
+    Ok(success_response(response))
+}
\ No newline at end of file
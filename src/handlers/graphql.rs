@@ -0,0 +1,16 @@
+use axum::{
+    extract::State,
+    Json,
+};
+
+use crate::graphql::GraphqlService;
+use crate::middleware::AuthUser;
+
+// GraphQL reports errors inside the response body, so this always answers 200
+pub async fn graphql(
+    auth_user: AuthUser,
+    State(service): State<GraphqlService>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(service.execute(auth_user, request).await)
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use axum::{
+    extract::{State, Extension},
+    response::IntoResponse,
+};
+use tracing::warn;
+
+use crate::middleware::AuthUser;
+use crate::models::ImportTransactionsQuery;
+use crate::services::{ImportService, ReaggregationService};
+use crate::repositories::{PostgresImportCheckpointRepository, PostgresJobRepository, PostgresPocketRepository, PostgresTransactionRepository};
+use crate::utils::{AppError, ValidatedQuery, created_response, CacheService, TRANSACTION_VIEWS};
+
+// Takes the raw CSV file as the request body
+pub async fn import_transactions(
+    State(service): State<ImportService<PostgresTransactionRepository, PostgresPocketRepository, PostgresImportCheckpointRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache): State<CacheService>,
+    State(reaggregation): State<ReaggregationService<PostgresJobRepository, PostgresPocketRepository>>,
+    ValidatedQuery(query): ValidatedQuery<ImportTransactionsQuery>,
+    body: String,
+) -> Result<impl IntoResponse, AppError> {
+    let mut response = service.import_transactions(auth_user.id, query, body).await?;
+
+    // The import is already committed, so a failed enqueue only loses the follow-up job
+    match reaggregation.enqueue(auth_user.id).await {
+        Ok(job) => response.reaggregation_job = Some(job),
+        Err(e) => warn!("Failed to enqueue reaggregation for user {}: {}", auth_user.id, e),
+    }
+
+    cache.invalidate_pockets(&auth_user.id).await;
+    cache.invalidate_group(TRANSACTION_VIEWS, &auth_user.id).await;
+
+    Ok(created_response(response))
+}
\ No newline at end of file
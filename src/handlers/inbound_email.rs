@@ -0,0 +1,108 @@
+use axum::{
+    extract::{State, Extension},
+    http::{header, HeaderMap},
+    response::IntoResponse,
+    Form,
+};
+use tracing::info;
+
+use crate::handlers::transaction::after_transaction_created;
+use crate::middleware::AuthUser;
+use crate::models::{InboundEmailAction, InboundEmailOutcome, MailgunInboundEmail, AUDIT_ACTION_CREATE, INBOUND_EMAIL_CREATED};
+use crate::services::{InboundEmailService, TransactionService, AuditService, AnomalyService};
+use crate::repositories::{
+    PostgresInboundEmailRepository, PostgresCategorizationRepository, PostgresTransactionRepository, PostgresSpendingLimitRepository,
+    PostgresPocketRepository, PostgresCurrencyRepository, PostgresAuditRepository, PostgresAnomalyRepository,
+};
+use crate::utils::{AppError, success_response, created_response, CacheService};
+
+pub async fn get_inbound_address(
+    State(service): State<InboundEmailService<PostgresInboundEmailRepository, PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_address(auth_user.id).await?;
+    Ok(success_response(response))
+}
+
+pub async fn generate_inbound_address(
+    State(service): State<InboundEmailService<PostgresInboundEmailRepository, PostgresCategorizationRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.generate_address(auth_user.id).await?;
+    Ok(created_response(response))
+}
+
+pub async fn receive_mailgun_email(
+    State(service): State<InboundEmailService<PostgresInboundEmailRepository, PostgresCategorizationRepository>>,
+    State(transactions): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    State(anomalies): State<AnomalyService<PostgresAnomalyRepository>>,
+    Form(email): Form<MailgunInboundEmail>,
+) -> Result<impl IntoResponse, AppError> {
+    let action = service.receive_mailgun(email).await?;
+    let outcome = record(action, &transactions, &cache, &audit, &anomalies).await?;
+    Ok(success_response(outcome))
+}
+
+// SNS posts JSON as text/plain, so the body is read as a string
+pub async fn receive_ses_email(
+    State(service): State<InboundEmailService<PostgresInboundEmailRepository, PostgresCategorizationRepository>>,
+    State(transactions): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    State(anomalies): State<AnomalyService<PostgresAnomalyRepository>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<impl IntoResponse, AppError> {
+    let authorization = headers.get(header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+    let action = service.receive_ses(authorization, &body).await?;
+    let outcome = record(action, &transactions, &cache, &audit, &anomalies).await?;
+    Ok(success_response(outcome))
+}
+
+async fn record(
+    action: InboundEmailAction,
+    transactions: &TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>,
+    cache: &CacheService,
+    audit: &AuditService<PostgresAuditRepository>,
+    anomalies: &AnomalyService<PostgresAnomalyRepository>,
+) -> Result<InboundEmailOutcome, AppError> {
+    let (user_id, request) = match action {
+        InboundEmailAction::Done(outcome) => {
+            if let Some(reason) = &outcome.reason {
+                info!("Inbound email not ingested: {}", reason);
+            }
+            return Ok(outcome);
+        }
+        InboundEmailAction::RecordExpense { user_id, request } => (user_id, request),
+    };
+
+    let (response, _) = match transactions.create_transaction(user_id, *request).await {
+        Ok(created) => created,
+        Err(error) => {
+            let reason = refusal(error)?;
+            info!("Inbound email not ingested: {}", reason);
+            return Ok(InboundEmailOutcome::rejected(reason));
+        }
+    };
+
+    after_transaction_created(user_id, &response, AUDIT_ACTION_CREATE, "Created pending expense from an emailed receipt", cache, audit, anomalies).await;
+
+    Ok(InboundEmailOutcome {
+        status: INBOUND_EMAIL_CREATED,
+        transaction_id: Some(response.id),
+        reason: None,
+        user_id: Some(user_id),
+    })
+}
+
+// A receipt the transaction rules refuse is answered as rejected, since providers retry anything
+// but 200. Other failures are passed on so the provider delivers the message again.
+fn refusal(error: AppError) -> Result<String, AppError> {
+    match error {
+        AppError::Detailed { inner, .. } => refusal(*inner),
+        AppError::Conflict(message) | AppError::ValidationError(message) | AppError::BadRequest(message) => Ok(message),
+        other => Err(other),
+    }
+}
\ No newline at end of file
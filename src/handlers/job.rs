@@ -0,0 +1,27 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::services::JobService;
+use crate::repositories::PostgresJobRepository;
+use crate::utils::{AppError, success_response};
+
+pub async fn get_jobs(
+    State(service): State<JobService<PostgresJobRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let jobs = service.list_jobs(auth_user.id).await?;
+    Ok(success_response(jobs))
+}
+
+pub async fn get_job_by_id(
+    State(service): State<JobService<PostgresJobRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let job = service.get_job(id, auth_user.id).await?;
+    Ok(success_response(job))
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::IntoResponse,
+};
+use serde_json::json;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::utils::{EventBus, LiveEvent};
+
+pub async fn live_updates(
+    auth_user: AuthUser,
+    State(events): State<EventBus>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    // Subscribe before the upgrade completes so nothing published in between is lost
+    let receiver = events.subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, receiver, auth_user.id))
+}
+
+async fn stream_events(mut socket: WebSocket, mut receiver: Receiver<LiveEvent>, user_id: Uuid) {
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let payload = match event {
+                    Ok(event) if event.user_id == user_id => json!(event),
+                    Ok(_) => continue,
+                    // The client fell behind and missed events, it has to refetch everything
+                    Err(RecvError::Lagged(skipped)) => json!({ "action": "resync", "skipped": skipped }),
+                    Err(RecvError::Closed) => break,
+                };
+
+                if socket.send(Message::Text(payload.to_string().into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                // Pings are answered by axum, anything else from the client is ignored
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    debug!("Live update connection closed for user {}", user_id);
+}
\ No newline at end of file
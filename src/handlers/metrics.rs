@@ -0,0 +1,35 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderMap},
+    response::IntoResponse,
+};
+
+use crate::config::MetricsConfig;
+use crate::utils::{AppError, PoolMetrics, StatementMetrics, hash_token};
+
+pub async fn get_metrics(
+    State(metrics): State<StatementMetrics>,
+    State(pool_metrics): State<PoolMetrics>,
+    State(config): State<MetricsConfig>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(expected) = config.token.as_deref() else {
+        return Err(AppError::NotFound("Not found".to_string()));
+    };
+
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| AppError::Unauthorized("Missing metrics token".to_string()))?;
+
+    // Compare digests so the check doesn't leak how much of the token matched
+    if hash_token(provided) != hash_token(expected) {
+        return Err(AppError::Unauthorized("Invalid metrics token".to_string()));
+    }
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics.render() + &pool_metrics.render(),
+    ))
+}
\ No newline at end of file
@@ -6,6 +6,40 @@ pub mod budget;
 pub mod account_summary;
 pub mod expense_analytics;
 pub mod income_analytics;
+pub mod spending_limit;
+pub mod export;
+pub mod session;
+pub mod status;
+pub mod categorization;
+pub mod pocket_import;
+pub mod job;
+pub mod import;
+pub mod pocket_adjustment;
+pub mod currency;
+pub mod audit;
+pub mod share_token;
+pub mod report;
+pub mod reminder;
+pub mod refund;
+pub mod preference;
+pub mod export_link;
+pub mod analytics_feed;
+pub mod email_template;
+pub mod live;
+pub mod task;
+pub mod repair;
+pub mod notification;
+pub mod organization;
+pub mod debt;
+pub mod subscription_analytics;
+pub mod anomaly;
+pub mod financial_health;
+pub mod inbound_email;
+pub mod chat_bot;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod metrics;
+pub mod route_table;
 
 pub use auth::*;
 pub use pocket::*;
@@ -14,4 +48,38 @@ pub use transaction::*;
 pub use budget::*;
 pub use account_summary::*;
 pub use expense_analytics::*;
-pub use income_analytics::*;
\ No newline at end of file
+pub use income_analytics::*;
+pub use spending_limit::*;
+pub use export::*;
+pub use session::*;
+pub use status::*;
+pub use categorization::*;
+pub use pocket_import::*;
+pub use job::*;
+pub use import::*;
+pub use pocket_adjustment::*;
+pub use currency::*;
+pub use audit::*;
+pub use share_token::*;
+pub use report::*;
+pub use reminder::*;
+pub use refund::*;
+pub use preference::*;
+pub use export_link::*;
+pub use analytics_feed::*;
+pub use email_template::*;
+pub use live::*;
+pub use task::*;
+pub use repair::*;
+pub use notification::*;
+pub use organization::*;
+pub use debt::*;
+pub use subscription_analytics::*;
+pub use anomaly::*;
+pub use financial_health::*;
+pub use inbound_email::*;
+pub use chat_bot::*;
+#[cfg(feature = "graphql")]
+pub use graphql::*;
+pub use metrics::*;
+pub use route_table::*;
\ No newline at end of file
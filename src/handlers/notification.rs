@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use std::convert::Infallible;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    StreamExt,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::{RegisterDeviceRequest, UpdateNotificationPreferencesRequest};
+use crate::repositories::{PostgresDeviceRepository, PostgresPreferenceRepository};
+use crate::services::PushNotificationService;
+use crate::utils::{AppError, EventBus, ValidatedJson, success_response, created_response, no_content_response};
+
+// Same events as /ws, for clients whose proxies do not let WebSocket upgrades through.
+// There is no replay, so a reconnecting client should refetch what it shows.
+pub async fn notification_stream(
+    auth_user: AuthUser,
+    State(events): State<EventBus>,
+) -> impl IntoResponse {
+    let user_id = auth_user.id;
+    let stream = BroadcastStream::new(events.subscribe()).filter_map(move |event| {
+        let event = match event {
+            Ok(event) if event.user_id == user_id => Event::default().event("change").json_data(&event).ok()?,
+            Ok(_) => return None,
+            // The client fell behind and missed events, it has to refetch everything
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => Event::default().event("resync").data(skipped.to_string()),
+        };
+        Some(Ok::<_, Infallible>(event))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+pub async fn register_device(
+    State(service): State<PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<RegisterDeviceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let device = service.register_device(auth_user.id, request).await?;
+    Ok(created_response(device))
+}
+
+pub async fn list_devices(
+    State(service): State<PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let devices = service.list_devices(auth_user.id).await?;
+    Ok(success_response(devices))
+}
+
+pub async fn delete_device(
+    State(service): State<PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete_device(auth_user.id, id).await?;
+    Ok(no_content_response())
+}
+
+pub async fn get_notification_preferences(
+    State(service): State<PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = service.get_preferences(auth_user.id).await?;
+    Ok(success_response(preferences))
+}
+
+pub async fn update_notification_preferences(
+    State(service): State<PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<UpdateNotificationPreferencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = service.update_preferences(auth_user.id, request).await?;
+    Ok(success_response(preferences))
+}
\ No newline at end of file
@@ -0,0 +1,185 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{
+    AddOrganizationMemberRequest, CreateBudgetRequest, CreateOrganizationRequest, CreatePocketRequest, IfUnmodifiedSince,
+    ListBudgetsQuery, ListPocketsQuery, UpdateBudgetRequest, UpdateOrganizationMemberRequest, UpdateOrganizationRequest,
+    UpdatePocketRequest, ORG_ROLE_ADMIN, ORG_ROLE_MEMBER,
+};
+use crate::repositories::{PostgresBudgetRepository, PostgresOrganizationRepository, PostgresPocketRepository, PostgresUserRepository};
+use crate::services::{BudgetService, OrganizationService, PocketService};
+use crate::utils::{AppError, ValidatedJson, ValidatedQuery, success_response, created_response, no_content_response};
+
+pub async fn list_organizations(
+    auth_user: AuthUser,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let organizations = service.list_organizations(auth_user.id).await?;
+    Ok(success_response(organizations))
+}
+
+pub async fn create_organization(
+    auth_user: AuthUser,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    ValidatedJson(request): ValidatedJson<CreateOrganizationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = service.create_organization(auth_user.id, request).await?;
+    Ok(created_response(organization))
+}
+
+pub async fn get_organization(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = service.get_organization(id, auth_user.id).await?;
+    Ok(success_response(organization))
+}
+
+pub async fn update_organization(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    ValidatedJson(request): ValidatedJson<UpdateOrganizationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let organization = service.update_organization(id, auth_user.id, request).await?;
+    Ok(success_response(organization))
+}
+
+pub async fn delete_organization(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete_organization(id, auth_user.id).await?;
+    Ok(no_content_response())
+}
+
+pub async fn list_organization_members(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let members = service.list_members(id, auth_user.id).await?;
+    Ok(success_response(members))
+}
+
+pub async fn add_organization_member(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    ValidatedJson(request): ValidatedJson<AddOrganizationMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = service.add_member(id, auth_user.id, request).await?;
+    Ok(created_response(member))
+}
+
+pub async fn update_organization_member(
+    auth_user: AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    ValidatedJson(request): ValidatedJson<UpdateOrganizationMemberRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let member = service.update_member(id, auth_user.id, user_id, request).await?;
+    Ok(success_response(member))
+}
+
+pub async fn remove_organization_member(
+    auth_user: AuthUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    service.remove_member(id, auth_user.id, user_id).await?;
+    Ok(no_content_response())
+}
+
+// Every member sees shared pockets and budgets; creating them takes an admin, and changing
+// them is decided by the role policy
+pub async fn get_organization_pockets(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    ValidatedQuery(query): ValidatedQuery<ListPocketsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    service.require_role(id, auth_user.id, ORG_ROLE_MEMBER).await?;
+    let pockets = pocket_service
+        .get_organization_pockets(id, query.include_archived.unwrap_or(false))
+        .await?;
+    Ok(success_response(pockets))
+}
+
+pub async fn create_organization_pocket(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    ValidatedJson(request): ValidatedJson<CreatePocketRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    service.require_role(id, auth_user.id, ORG_ROLE_ADMIN).await?;
+    let pocket = pocket_service.create_organization_pocket(id, auth_user.id, request).await?;
+    Ok(created_response(pocket))
+}
+
+pub async fn update_organization_pocket(
+    auth_user: AuthUser,
+    Path((id, pocket_id)): Path<(Uuid, Uuid)>,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    unmodified_since: IfUnmodifiedSince,
+    ValidatedJson(request): ValidatedJson<UpdatePocketRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let pocket = pocket_service
+        .update_organization_pocket(id, pocket_id, auth_user.id, request, unmodified_since)
+        .await?;
+    Ok(success_response(pocket))
+}
+
+pub async fn get_organization_budgets(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    State(budget_service): State<BudgetService<PostgresBudgetRepository>>,
+    ValidatedQuery(query): ValidatedQuery<ListBudgetsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    service.require_role(id, auth_user.id, ORG_ROLE_MEMBER).await?;
+    let budgets = budget_service.list_organization_budgets(id, query).await?;
+    Ok(success_response(budgets))
+}
+
+pub async fn create_organization_budget(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(service): State<OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>>,
+    State(budget_service): State<BudgetService<PostgresBudgetRepository>>,
+    ValidatedJson(request): ValidatedJson<CreateBudgetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    service.require_role(id, auth_user.id, ORG_ROLE_ADMIN).await?;
+    let budget = budget_service.create_organization_budget(id, auth_user.id, request).await?;
+    Ok(created_response(budget))
+}
+
+pub async fn update_organization_budget(
+    auth_user: AuthUser,
+    Path((id, budget_id)): Path<(Uuid, i64)>,
+    State(budget_service): State<BudgetService<PostgresBudgetRepository>>,
+    unmodified_since: IfUnmodifiedSince,
+    ValidatedJson(request): ValidatedJson<UpdateBudgetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let budget = budget_service
+        .update_organization_budget(id, budget_id, auth_user.id, request, unmodified_since)
+        .await?;
+    Ok(success_response(budget))
+}
+
+pub async fn delete_organization_budget(
+    auth_user: AuthUser,
+    Path((id, budget_id)): Path<(Uuid, i64)>,
+    State(budget_service): State<BudgetService<PostgresBudgetRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    budget_service.delete_organization_budget(id, budget_id, auth_user.id).await?;
+    Ok(no_content_response())
+}
\ No newline at end of file
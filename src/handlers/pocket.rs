@@ -1,20 +1,33 @@
 use axum::{
-    extract::{Path, State, Extension},
+    extract::{Path, State},
     response::IntoResponse,
 };
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
-use crate::models::{CreatePocketRequest, UpdatePocketRequest};
-use crate::services::PocketService;
-use crate::repositories::PostgresPocketRepository;
-use crate::utils::{AppError, ValidatedJson, success_response, created_response, no_content_response, CacheService, user_pockets_cache_key};
+use crate::models::{
+    CreatePocketRequest, UpdatePocketRequest, IfUnmodifiedSince, PocketQuickBalance, ListPocketsQuery, DeletePocketQuery, ReorderPocketsRequest,
+    AUDIT_ENTITY_POCKET, AUDIT_ACTION_CREATE, AUDIT_ACTION_UPDATE, AUDIT_ACTION_DELETE, AUDIT_ACTION_ARCHIVE, AUDIT_ACTION_UNARCHIVE,
+};
+use crate::services::{PocketService, AuditService};
+use crate::repositories::{PostgresPocketRepository, PostgresAuditRepository};
+use crate::utils::{AppError, ValidatedJson, ValidatedQuery, success_response, created_response, no_content_response, CacheService, user_pockets_cache_key, user_balances_cache_key, POCKET_VIEWS, TRANSACTION_VIEWS};
+
+// Every balance change drops the hash, so the TTL only bounds memory for idle users
+const BALANCES_CACHE_TTL_SECS: u64 = 60 * 60;
 
 pub async fn get_pockets(
     auth_user: AuthUser,
     State(pocket_service): State<PocketService<PostgresPocketRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
+    ValidatedQuery(query): ValidatedQuery<ListPocketsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
+    // Only the default list is cached, archived pockets are an occasional lookup
+    if query.include_archived.unwrap_or(false) {
+        let pockets = pocket_service.get_user_pockets(auth_user.id, true).await?;
+        return Ok(success_response(pockets));
+    }
+
     let cache_key = user_pockets_cache_key(&auth_user.id);
     
     // Try to get from cache first
@@ -23,7 +36,7 @@ pub async fn get_pockets(
     }
     
     // If not in cache, get from database
-    let pockets = pocket_service.get_user_pockets(auth_user.id).await?;
+    let pockets = pocket_service.get_user_pockets(auth_user.id, false).await?;
     
     // Cache the result for 3 minutes
     cache_service.set(&cache_key, &pockets, Some(180)).await;
@@ -31,6 +44,33 @@ pub async fn get_pockets(
     Ok(success_response(pockets))
 }
 
+// Polled by widgets, so reads come from a Redis hash that is dropped whenever a balance moves
+pub async fn get_pocket_balances(
+    auth_user: AuthUser,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    State(cache_service): State<CacheService>,
+) -> Result<impl IntoResponse, AppError> {
+    let cache_key = user_balances_cache_key(&auth_user.id);
+
+    // Fields carry their list position since hash order is arbitrary
+    if let Some(mut cached) = cache_service.get_hash::<(usize, PocketQuickBalance)>(&cache_key).await {
+        cached.sort_by_key(|(position, _)| *position);
+        let balances: Vec<PocketQuickBalance> = cached.into_iter().map(|(_, balance)| balance).collect();
+        return Ok(success_response(balances));
+    }
+
+    let balances = pocket_service.get_quick_balances(auth_user.id).await?;
+
+    let fields: Vec<(String, (usize, &PocketQuickBalance))> = balances
+        .iter()
+        .enumerate()
+        .map(|(position, balance)| (balance.id.to_string(), (position, balance)))
+        .collect();
+    cache_service.set_hash(&cache_key, &fields, BALANCES_CACHE_TTL_SECS).await;
+
+    Ok(success_response(balances))
+}
+
 pub async fn get_pocket_by_id(
     auth_user: AuthUser,
     Path(id): Path<Uuid>,
@@ -43,14 +83,26 @@ pub async fn get_pocket_by_id(
 pub async fn create_pocket(
     auth_user: AuthUser,
     State(pocket_service): State<PocketService<PostgresPocketRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
     ValidatedJson(create_request): ValidatedJson<CreatePocketRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let pocket = pocket_service.create_pocket(auth_user.id, create_request).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &pocket.id.to_string(),
+            AUDIT_ACTION_CREATE,
+            &format!("Created pocket {}", pocket.name),
+            serde_json::to_value(&pocket).ok(),
+        )
+        .await;
     
     // Invalidate user pockets cache after creation
-    let cache_key = user_pockets_cache_key(&auth_user.id);
-    cache_service.delete(&cache_key).await;
+    cache_service.invalidate_pockets(&auth_user.id).await;
+    cache_service.invalidate_group(POCKET_VIEWS, &auth_user.id).await;
     
     Ok(created_response(pocket))
 }
@@ -59,29 +111,124 @@ pub async fn update_pocket(
     auth_user: AuthUser,
     Path(id): Path<Uuid>,
     State(pocket_service): State<PocketService<PostgresPocketRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    unmodified_since: IfUnmodifiedSince,
     ValidatedJson(update_request): ValidatedJson<UpdatePocketRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let pocket = pocket_service.update_pocket(id, auth_user.id, update_request).await?;
+    let previous = pocket_service.get_pocket_by_id(id, auth_user.id).await?;
+    let pocket = pocket_service.update_pocket(id, auth_user.id, update_request, unmodified_since).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &pocket.id.to_string(),
+            AUDIT_ACTION_UPDATE,
+            &format!("Updated pocket {}", pocket.name),
+            Some(serde_json::json!({ "before": previous, "after": pocket })),
+        )
+        .await;
     
     // Invalidate user pockets cache after update
-    let cache_key = user_pockets_cache_key(&auth_user.id);
-    cache_service.delete(&cache_key).await;
+    cache_service.invalidate_pockets(&auth_user.id).await;
+    cache_service.invalidate_group(POCKET_VIEWS, &auth_user.id).await;
     
     Ok(success_response(pocket))
 }
 
+pub async fn reorder_pockets(
+    auth_user: AuthUser,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    State(cache_service): State<CacheService>,
+    ValidatedJson(reorder_request): ValidatedJson<ReorderPocketsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let pockets = pocket_service.reorder_pockets(auth_user.id, reorder_request).await?;
+
+    cache_service.invalidate_pockets(&auth_user.id).await;
+    cache_service.invalidate_group(POCKET_VIEWS, &auth_user.id).await;
+
+    Ok(success_response(pockets))
+}
+
+pub async fn archive_pocket(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    State(cache_service): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let pocket = pocket_service.set_pocket_archived(id, auth_user.id, true).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &pocket.id.to_string(),
+            AUDIT_ACTION_ARCHIVE,
+            &format!("Archived pocket {}", pocket.name),
+            None,
+        )
+        .await;
+
+    // Archiving changes what the default list and summaries show
+    cache_service.invalidate_pockets(&auth_user.id).await;
+    cache_service.invalidate_group(POCKET_VIEWS, &auth_user.id).await;
+
+    Ok(success_response(pocket))
+}
+
+pub async fn unarchive_pocket(
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    State(cache_service): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let pocket = pocket_service.set_pocket_archived(id, auth_user.id, false).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &pocket.id.to_string(),
+            AUDIT_ACTION_UNARCHIVE,
+            &format!("Unarchived pocket {}", pocket.name),
+            None,
+        )
+        .await;
+
+    cache_service.invalidate_pockets(&auth_user.id).await;
+    cache_service.invalidate_group(POCKET_VIEWS, &auth_user.id).await;
+
+    Ok(success_response(pocket))
+}
+
 pub async fn delete_pocket(
     auth_user: AuthUser,
     Path(id): Path<Uuid>,
     State(pocket_service): State<PocketService<PostgresPocketRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    ValidatedQuery(query): ValidatedQuery<DeletePocketQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    pocket_service.delete_pocket(id, auth_user.id).await?;
+    let previous = pocket_service.get_pocket_by_id(id, auth_user.id).await?;
+    pocket_service.delete_pocket(id, auth_user.id, query.reassign_to).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &id.to_string(),
+            AUDIT_ACTION_DELETE,
+            &format!("Deleted pocket {}", previous.name),
+            Some(serde_json::json!({ "pocket": previous, "reassign_to": query.reassign_to })),
+        )
+        .await;
     
-    // Invalidate user pockets cache after deletion
-    let cache_key = user_pockets_cache_key(&auth_user.id);
-    cache_service.delete(&cache_key).await;
+    // The pocket's transactions go with it, so every view computed from them is stale
+    cache_service.invalidate_pockets(&auth_user.id).await;
+    cache_service.invalidate_group(TRANSACTION_VIEWS, &auth_user.id).await;
     
     Ok(no_content_response())
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::handlers::transaction::after_transaction_created;
+use crate::middleware::AuthUser;
+use crate::models::{CreatePocketAdjustmentRequest, AUDIT_ACTION_ADJUST};
+use crate::services::{PocketAdjustmentService, AuditService, AnomalyService};
+use crate::repositories::{PostgresPocketRepository, PostgresTransactionRepository, PostgresAuditRepository, PostgresAnomalyRepository};
+use crate::utils::{AppError, ValidatedJson, created_response, CacheService};
+
+pub async fn create_pocket_adjustment(
+    State(service): State<PocketAdjustmentService<PostgresPocketRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    State(anomalies): State<AnomalyService<PostgresAnomalyRepository>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<CreatePocketAdjustmentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.adjust_balance(id, auth_user.id, request).await?;
+
+    let summary = format!(
+        "Adjusted {} balance by {}: {}",
+        response.pocket.name, response.adjustment.amount, response.adjustment.description
+    );
+    after_transaction_created(auth_user.id, &response.adjustment, AUDIT_ACTION_ADJUST, &summary, &cache, &audit, &anomalies).await;
+
+    Ok(created_response(response))
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use axum::{
+    extract::{State, Extension},
+    response::IntoResponse,
+};
+use tracing::warn;
+
+use crate::middleware::AuthUser;
+use crate::models::ImportPocketsRequest;
+use crate::services::{PocketImportService, ReaggregationService};
+use crate::repositories::{PostgresJobRepository, PostgresPocketRepository, PostgresTransactionRepository};
+use crate::utils::{AppError, ValidatedJson, created_response, CacheService, TRANSACTION_VIEWS};
+
+pub async fn import_pockets(
+    State(service): State<PocketImportService<PostgresPocketRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache): State<CacheService>,
+    State(reaggregation): State<ReaggregationService<PostgresJobRepository, PostgresPocketRepository>>,
+    ValidatedJson(request): ValidatedJson<ImportPocketsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut response = service.import_pockets(auth_user.id, request).await?;
+
+    // The import is already committed, so a failed enqueue only loses the follow-up job
+    match reaggregation.enqueue(auth_user.id).await {
+        Ok(job) => response.reaggregation_job = Some(job),
+        Err(e) => warn!("Failed to enqueue reaggregation for user {}: {}", auth_user.id, e),
+    }
+
+    // Invalidate pocket and transaction caches, the import touches both
+    cache.invalidate_pockets(&auth_user.id).await;
+    cache.invalidate_group(TRANSACTION_VIEWS, &auth_user.id).await;
+
+    Ok(created_response(response))
+}
\ No newline at end of file
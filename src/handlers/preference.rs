@@ -0,0 +1,27 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::UpdatePreferencesRequest;
+use crate::services::PreferenceService;
+use crate::repositories::{PostgresPreferenceRepository, PostgresCurrencyRepository};
+use crate::utils::{AppError, ValidatedJson, success_response};
+
+pub async fn get_preferences(
+    auth_user: AuthUser,
+    State(service): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = service.get_preferences(auth_user.id).await?;
+    Ok(success_response(preferences))
+}
+
+pub async fn update_preferences(
+    auth_user: AuthUser,
+    State(service): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
+    ValidatedJson(request): ValidatedJson<UpdatePreferencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = service.update_preferences(auth_user.id, request).await?;
+    Ok(success_response(preferences))
+}
\ No newline at end of file
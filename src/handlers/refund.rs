@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::{CreateRefundRequest, ListRefundsQuery, ReceiveRefundRequest};
+use crate::services::RefundService;
+use crate::repositories::{PostgresRefundRepository, PostgresTransactionRepository};
+use crate::utils::{AppError, ValidatedJson, ValidatedQuery, success_response, created_response, CacheService};
+
+pub async fn create_refund(
+    State(service): State<RefundService<PostgresRefundRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(transaction_id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<CreateRefundRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let refund = service.request_refund(auth_user.id, transaction_id, request).await?;
+    Ok(created_response(refund))
+}
+
+pub async fn get_transaction_refunds(
+    State(service): State<RefundService<PostgresRefundRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(transaction_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let refunds = service.list_for_transaction(auth_user.id, transaction_id).await?;
+    Ok(success_response(refunds))
+}
+
+pub async fn get_refunds(
+    State(service): State<RefundService<PostgresRefundRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<ListRefundsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let refunds = service.list_refunds(auth_user.id, query).await?;
+    Ok(success_response(refunds))
+}
+
+pub async fn receive_refund(
+    State(service): State<RefundService<PostgresRefundRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache): State<CacheService>,
+    Path(id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<ReceiveRefundRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let refund = service.receive_refund(auth_user.id, id, request).await?;
+
+    // Analytics net received refunds, so cached figures are stale now
+    cache.invalidate_user_derived(&auth_user.id).await;
+
+    Ok(success_response(refund))
+}
+
+pub async fn write_off_refund(
+    State(service): State<RefundService<PostgresRefundRepository, PostgresTransactionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let refund = service.write_off_refund(auth_user.id, id).await?;
+    Ok(success_response(refund))
+}
\ No newline at end of file
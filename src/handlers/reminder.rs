@@ -0,0 +1,56 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::{CreateReminderRequest, UpdateReminderRequest};
+use crate::services::ReminderService;
+use crate::repositories::PostgresReminderRepository;
+use crate::utils::{AppError, ValidatedJson, success_response, created_response, no_content_response};
+
+pub async fn create_reminder(
+    State(service): State<ReminderService<PostgresReminderRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(transaction_id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<CreateReminderRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let reminder = service.create_reminder(auth_user.id, transaction_id, request).await?;
+    Ok(created_response(reminder))
+}
+
+pub async fn get_transaction_reminders(
+    State(service): State<ReminderService<PostgresReminderRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(transaction_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let reminders = service.list_for_transaction(auth_user.id, transaction_id).await?;
+    Ok(success_response(reminders))
+}
+
+pub async fn get_due_reminders(
+    State(service): State<ReminderService<PostgresReminderRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let reminders = service.due_reminders(auth_user.id).await?;
+    Ok(success_response(reminders))
+}
+
+pub async fn update_reminder(
+    State(service): State<ReminderService<PostgresReminderRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<UpdateReminderRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let reminder = service.update_reminder(id, auth_user.id, request).await?;
+    Ok(success_response(reminder))
+}
+
+pub async fn delete_reminder(
+    State(service): State<ReminderService<PostgresReminderRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete_reminder(id, auth_user.id).await?;
+    Ok(no_content_response())
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use tracing::info;
+
+use crate::middleware::AuthUser;
+use crate::models::RepairBalancesQuery;
+use crate::repositories::PostgresPocketRepository;
+use crate::services::PocketService;
+use crate::utils::{AppError, CacheService, ValidatedQuery, success_response};
+
+pub async fn repair_balances(
+    auth_user: AuthUser,
+    State(pocket_service): State<PocketService<PostgresPocketRepository>>,
+    State(cache_service): State<CacheService>,
+    ValidatedQuery(query): ValidatedQuery<RepairBalancesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let dry_run = query.dry_run.unwrap_or(false);
+    let report = pocket_service.repair_balances(query.user_id, dry_run).await?;
+
+    if !dry_run {
+        let users: HashSet<_> = report.corrections.iter().filter_map(|correction| correction.owner()).collect();
+        for user_id in &users {
+            cache_service.invalidate_user_derived(user_id).await;
+        }
+    }
+
+    info!(
+        "Balance repair by {}: {} pockets checked, {} drifted{}",
+        auth_user.email,
+        report.pockets_checked,
+        report.corrections.len(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+    Ok(success_response(report))
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use axum::{
+    extract::{State, Extension},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use std::str::FromStr;
+
+use crate::middleware::AuthUser;
+use crate::models::{MonthlyReportQuery, ReportFormat};
+use crate::services::ReportService;
+use crate::repositories::{PostgresTransactionRepository, PostgresUserRepository};
+use crate::utils::{AppError, ValidatedQuery, success_response};
+
+pub async fn get_monthly_report(
+    State(service): State<ReportService<PostgresTransactionRepository, PostgresUserRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<MonthlyReportQuery>,
+) -> Result<Response, AppError> {
+    let format = match query.format.as_deref() {
+        Some(format) => ReportFormat::from_str(format)?,
+        None => ReportFormat::Pdf,
+    };
+
+    if format == ReportFormat::Json {
+        let report = service.monthly_report(auth_user.id, &query.month).await?;
+        return Ok(success_response(report).into_response());
+    }
+
+    let file = service.monthly_report_pdf(auth_user.id, &query.month).await?;
+
+    let disposition = format!("attachment; filename=\"{}\"", file.filename);
+    Ok((
+        [
+            (header::CONTENT_TYPE, file.content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        file.content,
+    )
+        .into_response())
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+use axum::response::IntoResponse;
+
+use crate::routes::ROUTE_TABLE;
+use crate::utils::{AppError, success_response};
+
+pub async fn list_routes() -> Result<impl IntoResponse, AppError> {
+    Ok(success_response(ROUTE_TABLE))
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::config::JwtConfig;
+use crate::middleware::AuthUser;
+use crate::services::SessionService;
+use crate::repositories::PostgresSessionRepository;
+use crate::utils::{AppError, CacheService, success_response, no_content_response, session_cache_key, user_tokens_valid_after_key};
+
+// Revocation markers only need to outlive the tokens they block
+pub async fn mark_sessions_revoked(cache_service: &CacheService, jwt_config: &JwtConfig, session_ids: &[Uuid]) {
+    for session_id in session_ids {
+        cache_service
+            .set(&session_cache_key(session_id), &false, Some(jwt_config.token_ttl_secs()))
+            .await;
+    }
+}
+
+// Persists the cutoff before caching it, so a Redis outage or eviction can't bring older tokens back
+pub async fn revoke_tokens_issued_before(
+    session_service: &SessionService<PostgresSessionRepository>,
+    cache_service: &CacheService,
+    jwt_config: &JwtConfig,
+    user_id: Uuid,
+    revoked_before: i64,
+) -> Result<(), AppError> {
+    session_service.revoke_tokens_issued_before(user_id, revoked_before).await?;
+    cache_service
+        .set(&user_tokens_valid_after_key(&user_id), &revoked_before, Some(jwt_config.token_ttl_secs()))
+        .await;
+    Ok(())
+}
+
+pub async fn get_sessions(
+    State(session_service): State<SessionService<PostgresSessionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let sessions = session_service.list_sessions(auth_user.id, auth_user.session_id).await?;
+    Ok(success_response(sessions))
+}
+
+pub async fn revoke_session(
+    State(session_service): State<SessionService<PostgresSessionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache_service): State<CacheService>,
+    State(jwt_config): State<JwtConfig>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    session_service.revoke_session(id, auth_user.id).await?;
+    mark_sessions_revoked(&cache_service, &jwt_config, &[id]).await;
+    Ok(no_content_response())
+}
+
+pub async fn revoke_other_sessions(
+    State(session_service): State<SessionService<PostgresSessionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache_service): State<CacheService>,
+    State(jwt_config): State<JwtConfig>,
+) -> Result<impl IntoResponse, AppError> {
+    let revoked = session_service.revoke_other_sessions(auth_user.id, auth_user.session_id).await?;
+    mark_sessions_revoked(&cache_service, &jwt_config, &revoked).await;
+    Ok(no_content_response())
+}
+
+pub async fn logout(
+    State(session_service): State<SessionService<PostgresSessionRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache_service): State<CacheService>,
+    State(jwt_config): State<JwtConfig>,
+) -> Result<impl IntoResponse, AppError> {
+    session_service.revoke_session(auth_user.session_id, auth_user.id).await?;
+    mark_sessions_revoked(&cache_service, &jwt_config, &[auth_user.session_id]).await;
+    Ok(no_content_response())
+}
\ No newline at end of file
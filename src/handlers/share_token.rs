@@ -0,0 +1,76 @@
+use axum::{
+    extract::{Path, State, Extension},
+    http::header,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{CreateShareTokenRequest, AUDIT_ENTITY_POCKET, AUDIT_ACTION_SHARE, AUDIT_ACTION_UNSHARE};
+use crate::services::{ShareTokenService, AuditService};
+use crate::repositories::{PostgresShareTokenRepository, PostgresPocketRepository, PostgresAuditRepository};
+use crate::utils::{AppError, ValidatedJson, success_response, created_response};
+
+pub async fn create_share_token(
+    State(service): State<ShareTokenService<PostgresShareTokenRepository, PostgresPocketRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<CreateShareTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create_token(id, auth_user.id, request).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &id.to_string(),
+            AUDIT_ACTION_SHARE,
+            &format!("Created share link {}", response.share_token.id),
+            serde_json::to_value(&response.share_token).ok(),
+        )
+        .await;
+
+    Ok(created_response(response))
+}
+
+pub async fn list_share_tokens(
+    State(service): State<ShareTokenService<PostgresShareTokenRepository, PostgresPocketRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let tokens = service.list_tokens(id, auth_user.id).await?;
+    Ok(success_response(tokens))
+}
+
+pub async fn revoke_share_token(
+    State(service): State<ShareTokenService<PostgresShareTokenRepository, PostgresPocketRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    Path((id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let token = service.revoke_token(id, token_id, auth_user.id).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_POCKET,
+            &id.to_string(),
+            AUDIT_ACTION_UNSHARE,
+            &format!("Revoked share link {}", token.id),
+            None,
+        )
+        .await;
+
+    Ok(success_response(token))
+}
+
+pub async fn get_shared_summary(
+    State(service): State<ShareTokenService<PostgresShareTokenRepository, PostgresPocketRepository>>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let summary = service.shared_summary(&token).await?;
+
+    // Revoking a link has to take effect immediately, so intermediaries must not keep a copy
+    Ok(([(header::CACHE_CONTROL, "no-store")], success_response(summary)))
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Path, State, Extension},
+    response::IntoResponse,
+};
+
+use crate::middleware::AuthUser;
+use crate::models::{CreateSpendingLimitRequest, UpdateSpendingLimitRequest};
+use crate::services::SpendingLimitService;
+use crate::repositories::PostgresSpendingLimitRepository;
+use crate::utils::{AppError, ValidatedJson, success_response, created_response, no_content_response};
+
+pub async fn get_spending_limits(
+    State(service): State<SpendingLimitService<PostgresSpendingLimitRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.list_spending_limits(auth_user.id).await?;
+    Ok(success_response(response))
+}
+
+pub async fn get_spending_limit_by_id(
+    State(service): State<SpendingLimitService<PostgresSpendingLimitRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_spending_limit_by_id(id, auth_user.id).await?;
+    Ok(success_response(response))
+}
+
+pub async fn create_spending_limit(
+    State(service): State<SpendingLimitService<PostgresSpendingLimitRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(request): ValidatedJson<CreateSpendingLimitRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create_spending_limit(auth_user.id, request).await?;
+    Ok(created_response(response))
+}
+
+pub async fn update_spending_limit(
+    State(service): State<SpendingLimitService<PostgresSpendingLimitRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+    ValidatedJson(request): ValidatedJson<UpdateSpendingLimitRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.update_spending_limit(id, auth_user.id, request).await?;
+    Ok(success_response(response))
+}
+
+pub async fn delete_spending_limit(
+    State(service): State<SpendingLimitService<PostgresSpendingLimitRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete_spending_limit(id, auth_user.id).await?;
+    Ok(no_content_response())
+}
\ No newline at end of file
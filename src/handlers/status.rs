@@ -0,0 +1,32 @@
+use axum::{extract::State, http::StatusCode, response::{IntoResponse, Json}};
+use serde_json::json;
+
+use crate::models::ClientInfo;
+use crate::services::StatusService;
+use crate::utils::{AppError, success_response};
+
+pub async fn get_status(
+    State(status_service): State<StatusService>,
+    client: ClientInfo,
+) -> Result<impl IntoResponse, AppError> {
+    let client_key = client.ip_address.unwrap_or_else(|| "unknown".to_string());
+    status_service.check_rate_limit(&client_key).await?;
+
+    let status = status_service.get_status().await;
+    Ok(success_response(status))
+}
+
+// The process is up and serving; deliberately touches no dependency so a database
+// outage never gets healthy instances restarted
+pub async fn liveness() -> impl IntoResponse {
+    Json(json!({
+        "status": "ok",
+        "message": "Rust Fintrack Backend is running"
+    }))
+}
+
+pub async fn readiness(State(status_service): State<StatusService>) -> impl IntoResponse {
+    let readiness = status_service.readiness().await;
+    let status = if readiness.status == "ready" { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(readiness))
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Extension,
+};
+
+use crate::middleware::AuthUser;
+use crate::services::SubscriptionAnalyticsService;
+use crate::repositories::PostgresTransactionRepository;
+use crate::utils::{AppError, CacheService, success_response, SUBSCRIPTIONS};
+
+pub async fn get_subscriptions(
+    State(service): State<SubscriptionAnalyticsService<PostgresTransactionRepository>>,
+    State(cache): State<CacheService>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let cache = cache.typed(SUBSCRIPTIONS);
+    let cache_key = cache.key(&auth_user.id, &[]);
+    let response = cache.get_or_compute(&cache_key, || service.get_subscriptions(auth_user.id)).await?;
+
+    Ok(success_response(response))
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+    response::IntoResponse,
+};
+use tracing::info;
+
+use crate::middleware::AuthUser;
+use crate::models::ListTaskRunsQuery;
+use crate::repositories::PostgresTaskRunRepository;
+use crate::services::SchedulerService;
+use crate::utils::{AppError, ValidatedQuery, ApiResponse, success_response};
+
+pub async fn list_tasks(
+    State(service): State<SchedulerService<PostgresTaskRunRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let tasks = service.list_tasks().await?;
+    Ok(success_response(tasks))
+}
+
+pub async fn list_task_runs(
+    State(service): State<SchedulerService<PostgresTaskRunRepository>>,
+    Path(name): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<ListTaskRunsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let runs = service.list_runs(&name, query).await?;
+    Ok(success_response(runs))
+}
+
+pub async fn trigger_task(
+    auth_user: AuthUser,
+    State(service): State<SchedulerService<PostgresTaskRunRepository>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let run = service.trigger(&name).await?;
+    info!("Task {} triggered manually by {}", name, auth_user.email);
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(run))))
+}
\ No newline at end of file
@@ -1,95 +1,286 @@
 use axum::{
-    extract::{Path, Query, State, Extension},
+    extract::{Path, State, Extension},
+    http::StatusCode,
     response::IntoResponse,
+    Json,
 };
+use uuid::Uuid;
 
 use crate::middleware::AuthUser;
-use crate::models::{CreateTransactionRequest, UpdateTransactionRequest, ListTransactionsQuery};
-use crate::services::TransactionService;
-use crate::repositories::PostgresTransactionRepository;
-use crate::utils::{AppError, ValidatedJson, success_response, created_response, no_content_response, CacheService};
+use crate::models::{
+    CreateTransactionRequest, UpdateTransactionRequest, IfUnmodifiedSince, ListTransactionsQuery, ListTransactionsResponse, TransactionResponse, FormatAmounts,
+    AUDIT_ENTITY_TRANSACTION, AUDIT_ACTION_CREATE, AUDIT_ACTION_UPDATE, AUDIT_ACTION_DELETE, AUDIT_ACTION_POST, AUDIT_ACTION_CANCEL,
+};
+use crate::services::{TransactionService, AuditService, AnomalyService, PreferenceService};
+use crate::repositories::{
+    PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresAuditRepository, PostgresAnomalyRepository,
+    PostgresPreferenceRepository, PostgresCurrencyRepository,
+};
+use crate::utils::{AppError, ApiResponse, ValidatedJson, ValidatedQuery, success_response, no_content_response, CacheService, user_cache_key, TRANSACTIONS, TRANSACTION_VIEWS};
 
-pub async fn get_transactions(
-    State(service): State<TransactionService<PostgresTransactionRepository>>,
-    Extension(auth_user): Extension<AuthUser>,
-    Query(query): Query<ListTransactionsQuery>,
-    Extension(cache): Extension<CacheService>,
-) -> Result<impl IntoResponse, AppError> {
-    // Try to get from cache first
-    let cache_key = format!(
-        "transactions:{}:page:{}:limit:{}:category:{}:from:{}:to:{}:type:{}",
-        auth_user.id,
+// Every filter that changes the listing, as one key part
+fn transactions_filter_key(query: &ListTransactionsQuery) -> String {
+    format!(
+        "page:{}:limit:{}:category:{}:categories:{}:from:{}:to:{}:type:{}:account:{}:min:{}:max:{}:sort:{}:order:{}:status:{}",
         query.page.unwrap_or(1),
         query.limit.unwrap_or(20),
         query.category.as_deref().unwrap_or(""),
+        query.categories.as_deref().unwrap_or(""),
         query.from_date.as_deref().unwrap_or(""),
         query.to_date.as_deref().unwrap_or(""),
-        query.transaction_type.as_deref().unwrap_or("")
-    );
+        query.transaction_type.as_deref().unwrap_or(""),
+        query.account_id.map(|id| id.to_string()).unwrap_or_default(),
+        query.min_amount.as_deref().unwrap_or(""),
+        query.max_amount.as_deref().unwrap_or(""),
+        query.sort_by.as_deref().unwrap_or(""),
+        query.order.as_deref().unwrap_or(""),
+        query.status.as_deref().unwrap_or("")
+    )
+}
+
+fn audit_summary(verb: &str, transaction: &TransactionResponse) -> String {
+    format!(
+        "{} {} of {} ({})",
+        verb,
+        transaction.transaction_type,
+        transaction.amount,
+        transaction.category.as_deref().unwrap_or("uncategorized")
+    )
+}
+
+// Whatever path a transaction is created through, unusual expenses are flagged, the creation is
+// audited under `action` and every cached view of the user's money is dropped
+pub async fn after_transaction_created(
+    user_id: Uuid,
+    response: &TransactionResponse,
+    action: &str,
+    summary: &str,
+    cache: &CacheService,
+    audit: &AuditService<PostgresAuditRepository>,
+    anomalies: &AnomalyService<PostgresAnomalyRepository>,
+) {
+    if response.transaction_type == "expense" {
+        anomalies.alert_if_unusual(user_id, response.id).await;
+    }
+
+    audit
+        .record(
+            user_id,
+            AUDIT_ENTITY_TRANSACTION,
+            &response.id.to_string(),
+            action,
+            summary,
+            serde_json::to_value(response).ok(),
+        )
+        .await;
+
+    invalidate_transaction_caches(cache, &user_id).await;
+}
 
-    if let Some(cached_response) = cache.get::<crate::models::ListTransactionsResponse>(&cache_key).await {
+// Any transaction write changes the user's balance, their pockets and every view computed
+// from transactions
+async fn invalidate_transaction_caches(cache: &CacheService, user_id: &Uuid) {
+    let _ = cache.delete(&user_cache_key(user_id)).await;
+    cache.invalidate_pockets(user_id).await;
+    cache.invalidate_group(TRANSACTION_VIEWS, user_id).await;
+}
+
+pub async fn get_transactions(
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedQuery(query): ValidatedQuery<ListTransactionsQuery>,
+    State(cache): State<CacheService>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let formatter = preferences.money_formatter(auth_user.id).await?;
+
+    // Try to get from cache first
+    let cache = cache.typed::<ListTransactionsResponse>(TRANSACTIONS);
+    let cache_key = cache.key(&auth_user.id, &[&transactions_filter_key(&query)]);
+
+    if let Some(mut cached_response) = cache.get(&cache_key).await {
+        cached_response.format_amounts(&formatter);
         return Ok(success_response(cached_response));
     }
 
-    let response = service.list_transactions(auth_user.id, query).await?;
+    let mut response = service.list_transactions(auth_user.id, query).await?;
+
+    let _ = cache.set(&cache_key, &response).await;
+
+    response.format_amounts(&formatter);
+    Ok(success_response(response))
+}
+
+// Transactions of a single pocket, for the pocket detail screen
+pub async fn get_pocket_transactions(
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(pocket_id): Path<Uuid>,
+    ValidatedQuery(mut query): ValidatedQuery<ListTransactionsQuery>,
+    State(cache): State<CacheService>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    query.account_id = Some(pocket_id);
+    let formatter = preferences.money_formatter(auth_user.id).await?;
+
+    let cache = cache.typed::<ListTransactionsResponse>(TRANSACTIONS);
+    let cache_key = cache.key(&auth_user.id, &[&transactions_filter_key(&query)]);
+    if let Some(mut cached_response) = cache.get(&cache_key).await {
+        cached_response.format_amounts(&formatter);
+        return Ok(success_response(cached_response));
+    }
 
-    // Cache the response for 5 minutes
-    let _ = cache.set(&cache_key, &response, Some(300)).await;
+    let mut response = service.list_transactions(auth_user.id, query).await?;
+    let _ = cache.set(&cache_key, &response).await;
 
+    response.format_amounts(&formatter);
     Ok(success_response(response))
 }
 
 pub async fn get_transaction_by_id(
-    State(service): State<TransactionService<PostgresTransactionRepository>>,
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
     Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<i64>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = service.get_transaction_by_id(id, auth_user.id).await?;
+    let mut response = service.get_transaction_by_id(id, auth_user.id).await?;
+    response.format_amounts(&preferences.money_formatter(auth_user.id).await?);
     Ok(success_response(response))
 }
 
 pub async fn create_transaction(
-    State(service): State<TransactionService<PostgresTransactionRepository>>,
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    State(anomalies): State<AnomalyService<PostgresAnomalyRepository>>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
     ValidatedJson(request): ValidatedJson<CreateTransactionRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = service.create_transaction(auth_user.id, request).await?;
+    let (mut response, warning) = service.create_transaction(auth_user.id, request).await?;
+    after_transaction_created(auth_user.id, &response, AUDIT_ACTION_CREATE, &audit_summary("Created", &response), &cache, &audit, &anomalies).await;
 
-    // Invalidate user cache
-    let _ = cache.delete(&format!("user:{}", auth_user.id)).await;
-    let _ = cache.delete(&format!("user:{}:pockets", auth_user.id)).await;
+    response.format_amounts(&preferences.money_formatter(auth_user.id).await?);
 
-    Ok(created_response(response))
+    // Soft spending limit violations are reported alongside the created transaction
+    let body = match warning {
+        Some(message) => ApiResponse::success_with_message(response, message),
+        None => ApiResponse::success(response),
+    };
+
+    Ok((StatusCode::CREATED, Json(body)))
 }
 
+// Axum hands each extractor over as its own argument
+#[allow(clippy::too_many_arguments)]
 pub async fn update_transaction(
-    State(service): State<TransactionService<PostgresTransactionRepository>>,
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
     Path(id): Path<i64>,
+    State(preferences): State<PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>>,
+    unmodified_since: IfUnmodifiedSince,
     ValidatedJson(request): ValidatedJson<UpdateTransactionRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let response = service.update_transaction(id, auth_user.id, request).await?;
+    let previous = service.get_transaction_by_id(id, auth_user.id).await?;
+    let (mut response, warning) = service.update_transaction(id, auth_user.id, request, unmodified_since).await?;
 
-    // Invalidate user cache
-    let _ = cache.delete(&format!("user:{}", auth_user.id)).await;
-    let _ = cache.delete(&format!("user:{}:pockets", auth_user.id)).await;
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_TRANSACTION,
+            &response.id.to_string(),
+            AUDIT_ACTION_UPDATE,
+            &audit_summary("Updated", &response),
+            Some(serde_json::json!({ "before": previous, "after": response })),
+        )
+        .await;
 
-    Ok(success_response(response))
+    invalidate_transaction_caches(&cache, &auth_user.id).await;
+
+    response.format_amounts(&preferences.money_formatter(auth_user.id).await?);
+
+    let body = match warning {
+        Some(message) => ApiResponse::success_with_message(response, message),
+        None => ApiResponse::success(response),
+    };
+
+    Ok(Json(body))
 }
 
 pub async fn delete_transaction(
-    State(service): State<TransactionService<PostgresTransactionRepository>>,
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
     Extension(auth_user): Extension<AuthUser>,
-    Extension(cache): Extension<CacheService>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
     Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, AppError> {
+    let previous = service.get_transaction_by_id(id, auth_user.id).await?;
     service.delete_transaction(id, auth_user.id).await?;
 
-    // Invalidate user cache
-    let _ = cache.delete(&format!("user:{}", auth_user.id)).await;
-    let _ = cache.delete(&format!("user:{}:pockets", auth_user.id)).await;
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_TRANSACTION,
+            &id.to_string(),
+            AUDIT_ACTION_DELETE,
+            &audit_summary("Deleted", &previous),
+            serde_json::to_value(&previous).ok(),
+        )
+        .await;
+
+    invalidate_transaction_caches(&cache, &auth_user.id).await;
 
     Ok(no_content_response())
+}
+
+pub async fn post_transaction(
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.post_transaction(id, auth_user.id).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_TRANSACTION,
+            &response.id.to_string(),
+            AUDIT_ACTION_POST,
+            &audit_summary("Posted", &response),
+            serde_json::to_value(&response).ok(),
+        )
+        .await;
+
+    invalidate_transaction_caches(&cache, &auth_user.id).await;
+
+    Ok(success_response(response))
+}
+
+pub async fn cancel_transaction(
+    State(service): State<TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>>,
+    Extension(auth_user): Extension<AuthUser>,
+    State(cache): State<CacheService>,
+    State(audit): State<AuditService<PostgresAuditRepository>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.cancel_transaction(id, auth_user.id).await?;
+
+    audit
+        .record(
+            auth_user.id,
+            AUDIT_ENTITY_TRANSACTION,
+            &response.id.to_string(),
+            AUDIT_ACTION_CANCEL,
+            &audit_summary("Cancelled", &response),
+            serde_json::to_value(&response).ok(),
+        )
+        .await;
+
+    invalidate_transaction_caches(&cache, &auth_user.id).await;
+
+    Ok(success_response(response))
 }
\ No newline at end of file
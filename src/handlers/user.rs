@@ -1,15 +1,25 @@
-use axum::{extract::{State, Extension}, Json, response::IntoResponse};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    Json,
+    response::IntoResponse,
+};
 
+use crate::config::JwtConfig;
 use crate::middleware::AuthUser;
-use crate::models::{UpdateUserNameRequest, UpdateHideBalanceRequest};
-use crate::services::UserService;
-use crate::repositories::PostgresUserRepository;
-use crate::utils::{AppError, ValidatedJson, success_response, CacheService, user_cache_key};
+use crate::models::{
+    AuthResponse, ListUsersQuery, UpdateUserNameRequest, UpdateHideBalanceRequest, UpdateMonthlyDigestRequest,
+    ChangePasswordRequest, ChangeEmailRequest, VerifyEmailChangeRequest, DeleteAccountRequest
+};
+use crate::handlers::session::{mark_sessions_revoked, revoke_tokens_issued_before};
+use crate::services::{UserService, SessionService};
+use crate::repositories::{PostgresUserRepository, PostgresSessionRepository};
+use crate::utils::{AppError, ApiResponse, ValidatedJson, ValidatedQuery, success_response, CacheService, user_cache_key};
 
 pub async fn get_me(
     auth_user: AuthUser,
     State(user_service): State<UserService<PostgresUserRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
 ) -> Result<impl IntoResponse, AppError> {
     let cache_key = user_cache_key(&auth_user.id);
     
@@ -30,7 +40,7 @@ pub async fn get_me(
 pub async fn update_name(
     auth_user: AuthUser,
     State(user_service): State<UserService<PostgresUserRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
     ValidatedJson(update_request): ValidatedJson<UpdateUserNameRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user = user_service.update_user_name(auth_user.id, update_request).await?;
@@ -45,7 +55,7 @@ pub async fn update_name(
 pub async fn update_hide_balance(
     auth_user: AuthUser,
     State(user_service): State<UserService<PostgresUserRepository>>,
-    Extension(cache_service): Extension<CacheService>,
+    State(cache_service): State<CacheService>,
     Json(update_request): Json<UpdateHideBalanceRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let user = user_service.update_hide_balance(auth_user.id, update_request).await?;
@@ -57,9 +67,103 @@ pub async fn update_hide_balance(
     Ok(success_response(user))
 }
 
+pub async fn update_monthly_digest(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+    State(cache_service): State<CacheService>,
+    Json(update_request): Json<UpdateMonthlyDigestRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = user_service.update_monthly_digest(auth_user.id, update_request).await?;
+    
+    // Invalidate user cache after update
+    let cache_key = user_cache_key(&auth_user.id);
+    cache_service.delete(&cache_key).await;
+    
+    Ok(success_response(user))
+}
+
 pub async fn list_users(
     State(user_service): State<UserService<PostgresUserRepository>>,
+    ValidatedQuery(query): ValidatedQuery<ListUsersQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let users = user_service.list_users().await?;
+    let users = user_service.list_users(query).await?;
     Ok(success_response(users))
+}
+
+pub async fn change_password(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+    State(cache_service): State<CacheService>,
+    State(jwt_config): State<JwtConfig>,
+    State(session_service): State<SessionService<PostgresSessionRepository>>,
+    ValidatedJson(request): ValidatedJson<ChangePasswordRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = user_service.change_password(auth_user.id, request).await?;
+
+    // Sign out every other device
+    let revoked = session_service.revoke_other_sessions(auth_user.id, auth_user.session_id).await?;
+    mark_sessions_revoked(&cache_service, &jwt_config, &revoked).await;
+
+    // Every token issued before this moment stops being accepted
+    let revoked_before = chrono::Utc::now().timestamp();
+    revoke_tokens_issued_before(&session_service, &cache_service, &jwt_config, auth_user.id, revoked_before).await?;
+    cache_service.delete(&user_cache_key(&auth_user.id)).await;
+
+    // Hand the current client a fresh token so it stays signed in
+    let token = jwt_config.create_token(user.id, user.email.clone(), auth_user.session_id)?;
+
+    Ok(success_response(AuthResponse { token, refresh_token: None, user }))
+}
+
+pub async fn request_email_change(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+    ValidatedJson(request): ValidatedJson<ChangeEmailRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    user_service.request_email_change(auth_user.id, request).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::<()>::message("Verification code sent to the new email address".to_string())),
+    ))
+}
+
+pub async fn verify_email_change(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+    State(cache_service): State<CacheService>,
+    ValidatedJson(request): ValidatedJson<VerifyEmailChangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = user_service.confirm_email_change(auth_user.id, request).await?;
+
+    cache_service.delete(&user_cache_key(&auth_user.id)).await;
+
+    Ok(success_response(user))
+}
+
+pub async fn delete_me(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+    ValidatedJson(request): ValidatedJson<DeleteAccountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = user_service.schedule_account_deletion(auth_user.id, request).await?;
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse::success(response))))
+}
+
+pub async fn cancel_deletion(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    user_service.cancel_account_deletion(auth_user.id).await?;
+    Ok(Json(ApiResponse::<()>::message("Account deletion cancelled".to_string())))
+}
+
+pub async fn export_me(
+    auth_user: AuthUser,
+    State(user_service): State<UserService<PostgresUserRepository>>,
+) -> Result<impl IntoResponse, AppError> {
+    let export = user_service.export_user_data(auth_user.id).await?;
+
+    let disposition = format!("attachment; filename=\"fintrack-export-{}.json\"", auth_user.id);
+    Ok(([(header::CONTENT_DISPOSITION, disposition)], Json(export)))
 }
\ No newline at end of file
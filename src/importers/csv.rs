@@ -0,0 +1,104 @@
+use crate::utils::AppError;
+
+const DELIMITERS: [char; 3] = [',', ';', '\t'];
+
+// Picks whichever delimiter splits the header line into the most columns
+pub fn detect_delimiter(content: &str) -> char {
+    let header = content.lines().next().unwrap_or_default();
+    DELIMITERS
+        .iter()
+        .copied()
+        .max_by_key(|delimiter| header.matches(*delimiter).count())
+        .unwrap_or(',')
+}
+
+// Splits RFC 4180 style CSV into records, honouring quoted fields that
+// contain delimiters, escaped quotes or line breaks. Blank lines are skipped.
+pub fn parse_records(content: &str, delimiter: char) -> Result<Vec<Vec<String>>, AppError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+        } else if c == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            if record.iter().any(|f| !f.trim().is_empty()) {
+                records.push(std::mem::take(&mut record));
+            }
+            record.clear();
+        } else {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(AppError::ValidationError("CSV ends inside a quoted field".to_string()));
+    }
+
+    record.push(field);
+    if record.iter().any(|f| !f.trim().is_empty()) {
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delimiter_is_the_one_that_splits_the_header_most() {
+        assert_eq!(detect_delimiter("date;description;amount\n2024-01-05;Coffee;1,50"), ';');
+        assert_eq!(detect_delimiter("date\tdescription\tamount"), '\t');
+        assert_eq!(detect_delimiter("date,description,amount"), ',');
+    }
+
+    #[test]
+    fn quoted_fields_keep_delimiters_quotes_and_line_breaks() {
+        let records = parse_records("date,description,amount\r\n2024-01-05,\"Coffee, \"\"large\"\"\nto go\",\"1,500\"\r\n", ',')
+            .expect("valid CSV");
+
+        assert_eq!(
+            records,
+            vec![
+                vec!["date".to_string(), "description".to_string(), "amount".to_string()],
+                vec!["2024-01-05".to_string(), "Coffee, \"large\"\nto go".to_string(), "1,500".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn byte_order_mark_and_blank_lines_are_skipped() {
+        let records = parse_records("\u{feff}a;b\n\n;\n1;2", ';').expect("valid CSV");
+
+        assert_eq!(records, vec![vec!["a".to_string(), "b".to_string()], vec!["1".to_string(), "2".to_string()]]);
+    }
+
+    #[test]
+    fn unterminated_quote_is_rejected() {
+        match parse_records("a,b\n1,\"2\n", ',') {
+            Err(AppError::ValidationError(message)) => assert_eq!(message, "CSV ends inside a quoted field"),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::str::FromStr;
+
+use crate::utils::AppError;
+
+// Date layouts seen in bank exports, tried in this order when auto-detecting.
+// Day-first comes before month-first since that is what Indonesian banks use.
+pub const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%d/%m/%Y", "%d-%m-%Y", "%d.%m.%Y", "%Y/%m/%d", "%m/%d/%Y"];
+
+// How numbers and dates are written in one import file
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportLocale {
+    pub decimal_separator: char,
+    pub thousands_separator: char,
+    pub date_format: String,
+}
+
+impl ImportLocale {
+    pub fn parse_amount(&self, raw: &str) -> Option<Decimal> {
+        let mut value = raw.trim();
+        let mut negative = false;
+
+        if let Some(inner) = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+            negative = true;
+            value = inner;
+        }
+        if let Some(rest) = value.strip_suffix('-') {
+            negative = !negative;
+            value = rest;
+        }
+
+        let mut normalized = String::with_capacity(value.len());
+        for c in value.chars() {
+            if c.is_ascii_digit() {
+                normalized.push(c);
+            } else if c == self.decimal_separator {
+                normalized.push('.');
+            } else if c == '-' && normalized.is_empty() {
+                negative = !negative;
+            } else if c == self.thousands_separator || c.is_whitespace() || c.is_alphabetic() || c == '$' {
+                // Grouping, currency symbols and codes such as "Rp" or "IDR"
+            } else {
+                return None;
+            }
+        }
+
+        let amount = Decimal::from_str(&normalized).ok()?;
+        Some(if negative { -amount } else { amount })
+    }
+
+    pub fn parse_date(&self, raw: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(raw.trim(), &self.date_format).ok()
+    }
+}
+
+// Accepts either a chrono format or the DD/MM/YYYY style users type
+pub fn date_format_from_pattern(pattern: &str) -> Result<String, AppError> {
+    let format = pattern
+        .replace("YYYY", "%Y")
+        .replace("yyyy", "%Y")
+        .replace("MM", "%m")
+        .replace("mm", "%m")
+        .replace("DD", "%d")
+        .replace("dd", "%d");
+
+    if !(format.contains("%Y") && format.contains("%m") && format.contains("%d")) {
+        return Err(AppError::ValidationError(
+            "date_format must contain a year, month and day, e.g. DD/MM/YYYY".to_string(),
+        ));
+    }
+
+    Ok(format)
+}
+
+// Votes across every amount in the file; a lone separator followed by exactly
+// three digits ("1.500") is ambiguous and does not count either way
+pub fn detect_decimal_separator<'a>(values: impl Iterator<Item = &'a str>) -> char {
+    let (mut dot, mut comma) = (0, 0);
+
+    for value in values {
+        let digits: String = value.chars().filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',').collect();
+        let last_dot = digits.rfind('.');
+        let last_comma = digits.rfind(',');
+
+        let decimal = match (last_dot, last_comma) {
+            (Some(d), Some(c)) => Some(if d > c { '.' } else { ',' }),
+            (Some(i), None) | (None, Some(i)) => {
+                let separator = if last_dot.is_some() { '.' } else { ',' };
+                if digits.matches(separator).count() > 1 {
+                    Some(if separator == '.' { ',' } else { '.' })
+                } else if digits.len() - i - 1 != 3 {
+                    Some(separator)
+                } else {
+                    None
+                }
+            }
+            (None, None) => None,
+        };
+
+        match decimal {
+            Some('.') => dot += 1,
+            Some(_) => comma += 1,
+            None => {}
+        }
+    }
+
+    if comma > dot { ',' } else { '.' }
+}
+
+pub fn detect_date_format<'a>(values: impl Iterator<Item = &'a str> + Clone) -> Option<&'static str> {
+    DATE_FORMATS.iter().copied().find(|format| {
+        values
+            .clone()
+            .all(|value| NaiveDate::parse_from_str(value.trim(), format).is_ok())
+    })
+}
+
+pub fn thousands_separator_for(decimal_separator: char) -> char {
+    if decimal_separator == ',' { '.' } else { ',' }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(decimal_separator: char, date_format: &str) -> ImportLocale {
+        ImportLocale {
+            decimal_separator,
+            thousands_separator: thousands_separator_for(decimal_separator),
+            date_format: date_format.to_string(),
+        }
+    }
+
+    #[test]
+    fn amounts_follow_the_locale_separators() {
+        assert_eq!(locale(',', "%d/%m/%Y").parse_amount("Rp 1.500.000,50"), Some(Decimal::new(150000050, 2)));
+        assert_eq!(locale('.', "%Y-%m-%d").parse_amount("$1,234.56"), Some(Decimal::new(123456, 2)));
+        assert_eq!(locale('.', "%Y-%m-%d").parse_amount("IDR 45000"), Some(Decimal::new(45000, 0)));
+    }
+
+    #[test]
+    fn negative_amounts_in_any_bank_notation() {
+        let locale = locale('.', "%Y-%m-%d");
+
+        assert_eq!(locale.parse_amount("-250.00"), Some(Decimal::new(-25000, 2)));
+        assert_eq!(locale.parse_amount("(250.00)"), Some(Decimal::new(-25000, 2)));
+        assert_eq!(locale.parse_amount("250.00-"), Some(Decimal::new(-25000, 2)));
+    }
+
+    #[test]
+    fn malformed_amounts_do_not_parse() {
+        let locale = locale('.', "%Y-%m-%d");
+
+        assert_eq!(locale.parse_amount("12#4"), None);
+        assert_eq!(locale.parse_amount("n/a"), None);
+        assert_eq!(locale.parse_amount(""), None);
+    }
+
+    #[test]
+    fn dates_use_the_configured_format() {
+        let locale = locale(',', "%d/%m/%Y");
+
+        assert_eq!(locale.parse_date(" 05/01/2024 "), NaiveDate::from_ymd_opt(2024, 1, 5));
+        assert_eq!(locale.parse_date("2024-01-05"), None);
+    }
+
+    #[test]
+    fn date_patterns_become_chrono_formats() {
+        assert_eq!(date_format_from_pattern("DD/MM/YYYY").expect("valid pattern"), "%d/%m/%Y");
+        assert_eq!(date_format_from_pattern("%Y-%m-%d").expect("valid pattern"), "%Y-%m-%d");
+        assert!(date_format_from_pattern("MM/YYYY").is_err());
+    }
+
+    #[test]
+    fn decimal_separator_is_voted_across_the_file() {
+        assert_eq!(detect_decimal_separator(["1.500,25", "20,00"].into_iter()), ',');
+        assert_eq!(detect_decimal_separator(["1,234.56", "12.50"].into_iter()), '.');
+        // Repeated separators can only be grouping
+        assert_eq!(detect_decimal_separator(["1.500.000", "2.000"].into_iter()), ',');
+    }
+
+    #[test]
+    fn three_digit_groups_alone_fall_back_to_a_dot() {
+        assert_eq!(detect_decimal_separator(["1.500", "2,000"].into_iter()), '.');
+    }
+
+    #[test]
+    fn date_format_is_the_first_that_fits_every_value() {
+        assert_eq!(detect_date_format(["2024-01-31", "2024-02-01"].iter().copied()), Some("%Y-%m-%d"));
+        assert_eq!(detect_date_format(["31/01/2024", "05/02/2024"].iter().copied()), Some("%d/%m/%Y"));
+        // Day-first is preferred until a value rules it out
+        assert_eq!(detect_date_format(["05/02/2024"].iter().copied()), Some("%d/%m/%Y"));
+        assert_eq!(detect_date_format(["05/02/2024", "01/31/2024"].iter().copied()), Some("%m/%d/%Y"));
+        assert_eq!(detect_date_format(["yesterday"].iter().copied()), None);
+    }
+}
@@ -0,0 +1,284 @@
+pub mod csv;
+pub mod locale;
+pub mod receipt;
+pub mod quick_entry;
+
+pub use csv::*;
+pub use locale::*;
+pub use receipt::*;
+pub use quick_entry::*;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::utils::AppError;
+
+pub const DEFAULT_CATEGORY: &str = "Uncategorized";
+
+// Header names recognised for each column, including common Indonesian bank labels
+const DATE_HEADERS: &[&str] = &["date", "transaction_date", "tanggal", "tgl"];
+const DESCRIPTION_HEADERS: &[&str] = &["description", "memo", "keterangan", "deskripsi", "uraian"];
+const AMOUNT_HEADERS: &[&str] = &["amount", "jumlah", "nominal", "mutasi"];
+const DEBIT_HEADERS: &[&str] = &["debit", "debet", "withdrawal"];
+const CREDIT_HEADERS: &[&str] = &["credit", "kredit", "deposit"];
+const TYPE_HEADERS: &[&str] = &["type", "transaction_type", "jenis"];
+const CATEGORY_HEADERS: &[&str] = &["category", "kategori"];
+
+// Per-import overrides; anything left unset is detected from the file
+#[derive(Debug, Default)]
+pub struct ImportOptions {
+    pub delimiter: Option<char>,
+    pub decimal_separator: Option<char>,
+    pub thousands_separator: Option<char>,
+    pub date_format: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ImportedRow {
+    pub line: usize,
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: Decimal,
+    pub transaction_type: String,
+    pub category: String,
+}
+
+#[derive(Debug)]
+pub struct ParsedImport {
+    pub delimiter: char,
+    pub locale: ImportLocale,
+    pub rows: Vec<ImportedRow>,
+}
+
+struct Columns {
+    date: usize,
+    description: usize,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+    transaction_type: Option<usize>,
+    category: Option<usize>,
+}
+
+impl Columns {
+    fn from_header(header: &[String]) -> Result<Self, AppError> {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.contains(&h.trim().to_lowercase().as_str()))
+        };
+
+        let columns = Self {
+            date: find(DATE_HEADERS)
+                .ok_or_else(|| AppError::ValidationError("CSV header is missing a date column".to_string()))?,
+            description: find(DESCRIPTION_HEADERS)
+                .ok_or_else(|| AppError::ValidationError("CSV header is missing a description column".to_string()))?,
+            amount: find(AMOUNT_HEADERS),
+            debit: find(DEBIT_HEADERS),
+            credit: find(CREDIT_HEADERS),
+            transaction_type: find(TYPE_HEADERS),
+            category: find(CATEGORY_HEADERS),
+        };
+
+        if columns.amount.is_none() && columns.debit.is_none() && columns.credit.is_none() {
+            return Err(AppError::ValidationError(
+                "CSV header needs an amount column or debit/credit columns".to_string(),
+            ));
+        }
+
+        Ok(columns)
+    }
+
+    fn amount_values<'a>(&self, record: &'a [String]) -> impl Iterator<Item = &'a str> + use<'a> {
+        [self.amount, self.debit, self.credit]
+            .into_iter()
+            .flatten()
+            .filter_map(move |i| record.get(i).map(|v| v.as_str()))
+            .filter(|v| !v.trim().is_empty())
+    }
+}
+
+// Parses a bank or app CSV export into rows ready to become transactions.
+// Fails on the first bad row so a partial file is never imported.
+pub fn parse_transactions(content: &str, options: &ImportOptions) -> Result<ParsedImport, AppError> {
+    let delimiter = options.delimiter.unwrap_or_else(|| detect_delimiter(content));
+    let records = parse_records(content, delimiter)?;
+    let (header, body) = records
+        .split_first()
+        .ok_or_else(|| AppError::ValidationError("CSV file is empty".to_string()))?;
+    let columns = Columns::from_header(header)?;
+
+    let decimal_separator = options
+        .decimal_separator
+        .unwrap_or_else(|| detect_decimal_separator(body.iter().flat_map(|r| columns.amount_values(r))));
+    let date_format = match &options.date_format {
+        Some(format) => format.clone(),
+        None => detect_date_format(body.iter().filter_map(|r| r.get(columns.date).map(|v| v.as_str())))
+            .ok_or_else(|| AppError::ValidationError(
+                "Could not detect the date format, pass date_format explicitly".to_string(),
+            ))?
+            .to_string(),
+    };
+    let locale = ImportLocale {
+        decimal_separator,
+        thousands_separator: options
+            .thousands_separator
+            .unwrap_or_else(|| thousands_separator_for(decimal_separator)),
+        date_format,
+    };
+
+    let rows = body
+        .iter()
+        .enumerate()
+        .map(|(index, record)| parse_row(index + 2, record, &columns, &locale))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ParsedImport { delimiter, locale, rows })
+}
+
+fn parse_row(line: usize, record: &[String], columns: &Columns, locale: &ImportLocale) -> Result<ImportedRow, AppError> {
+    let field = |i: usize| record.get(i).map(|v| v.trim()).unwrap_or_default();
+    let optional = |i: Option<usize>| i.map(field).filter(|v| !v.is_empty());
+    let row_error = |message: &str| AppError::ValidationError(format!("Row {}: {}", line, message));
+
+    let date = locale
+        .parse_date(field(columns.date))
+        .ok_or_else(|| row_error("invalid date"))?;
+
+    let description = field(columns.description);
+    if description.is_empty() {
+        return Err(row_error("description is required"));
+    }
+
+    let parse = |raw: &str| locale.parse_amount(raw).ok_or_else(|| row_error("invalid amount"));
+    let amount = match optional(columns.amount) {
+        Some(raw) => parse(raw)?,
+        None => {
+            let credit = optional(columns.credit).map(parse).transpose()?.unwrap_or_default();
+            let debit = optional(columns.debit).map(parse).transpose()?.unwrap_or_default();
+            credit - debit.abs()
+        }
+    };
+
+    let transaction_type = match optional(columns.transaction_type) {
+        Some(raw) => normalize_type(raw).ok_or_else(|| row_error("unknown transaction type"))?,
+        None if amount.is_sign_negative() => "expense",
+        None => "income",
+    };
+
+    if amount.is_zero() {
+        return Err(row_error("amount must not be zero"));
+    }
+
+    Ok(ImportedRow {
+        line,
+        date,
+        description: description.to_string(),
+        amount: amount.abs(),
+        transaction_type: transaction_type.to_string(),
+        category: optional(columns.category).unwrap_or(DEFAULT_CATEGORY).to_string(),
+    })
+}
+
+fn normalize_type(raw: &str) -> Option<&'static str> {
+    match raw.to_lowercase().as_str() {
+        "income" | "credit" | "cr" | "kredit" | "masuk" => Some("income"),
+        "expense" | "debit" | "db" | "debet" | "keluar" => Some("expense"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Result<ParsedImport, AppError> {
+        parse_transactions(content, &ImportOptions::default())
+    }
+
+    fn validation_message(result: Result<ParsedImport, AppError>) -> String {
+        match result {
+            Err(AppError::ValidationError(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn indonesian_bank_export_maps_debit_and_credit_columns() {
+        let import = parse("Tanggal;Keterangan;Debet;Kredit\n05/01/2024;Gaji;;5.000.000,00\n06/01/2024;Makan siang;45.000,00;\n")
+            .expect("valid export");
+
+        assert_eq!(import.delimiter, ';');
+        assert_eq!(import.locale.decimal_separator, ',');
+        assert_eq!(import.locale.date_format, "%d/%m/%Y");
+
+        let salary = &import.rows[0];
+        assert_eq!((salary.line, salary.date), (2, NaiveDate::from_ymd_opt(2024, 1, 5).unwrap()));
+        assert_eq!((salary.transaction_type.as_str(), salary.amount), ("income", Decimal::new(5_000_000, 0)));
+        assert_eq!(salary.category, DEFAULT_CATEGORY);
+
+        let lunch = &import.rows[1];
+        assert_eq!((lunch.line, lunch.description.as_str()), (3, "Makan siang"));
+        assert_eq!((lunch.transaction_type.as_str(), lunch.amount), ("expense", Decimal::new(45_000, 0)));
+    }
+
+    #[test]
+    fn amount_column_takes_its_type_and_category_from_the_row() {
+        let import = parse("Date,Memo,Amount,Type,Category\n2024-01-05,Coffee,\"1,500.00\",debit,Food\n2024-01-06,Refund,-20.00,,\n")
+            .expect("valid export");
+
+        let coffee = &import.rows[0];
+        assert_eq!((coffee.transaction_type.as_str(), coffee.amount), ("expense", Decimal::new(1500, 0)));
+        assert_eq!(coffee.category, "Food");
+
+        // Without a type the sign decides
+        assert_eq!(import.rows[1].transaction_type, "expense");
+        assert_eq!(import.rows[1].amount, Decimal::new(20, 0));
+    }
+
+    #[test]
+    fn options_override_detection() {
+        let content = "date;description;amount\n2024-01-05;Kopi;1.500\n";
+        let options = ImportOptions { decimal_separator: Some(','), ..Default::default() };
+
+        // "1.500" alone is ambiguous and would be read as 1.5
+        assert_eq!(parse(content).expect("valid export").rows[0].amount, Decimal::new(15, 1));
+        assert_eq!(parse_transactions(content, &options).expect("valid export").rows[0].amount, Decimal::new(1500, 0));
+    }
+
+    #[test]
+    fn header_without_required_columns_is_rejected() {
+        assert_eq!(validation_message(parse("")), "CSV file is empty");
+        assert_eq!(validation_message(parse("description,amount\nCoffee,10\n")), "CSV header is missing a date column");
+        assert_eq!(validation_message(parse("date,amount\n2024-01-05,10\n")), "CSV header is missing a description column");
+        assert_eq!(
+            validation_message(parse("date,description\n2024-01-05,Coffee\n")),
+            "CSV header needs an amount column or debit/credit columns"
+        );
+    }
+
+    #[test]
+    fn undetectable_dates_ask_for_a_format() {
+        assert_eq!(
+            validation_message(parse("date,description,amount\nsoon,Coffee,10\n")),
+            "Could not detect the date format, pass date_format explicitly"
+        );
+    }
+
+    #[test]
+    fn malformed_rows_name_their_line() {
+        let header = "date,description,amount,type\n2024-01-05,Coffee,10,\n";
+        let with_row = |row: &str| parse(&format!("{}{}\n", header, row));
+
+        assert_eq!(validation_message(with_row("2024-01-06,,10,")), "Row 3: description is required");
+        assert_eq!(validation_message(with_row("2024-01-06,Tea,12#4,")), "Row 3: invalid amount");
+        assert_eq!(validation_message(with_row("2024-01-06,Tea,0,")), "Row 3: amount must not be zero");
+        assert_eq!(validation_message(with_row("2024-01-06,Tea,10,transfer")), "Row 3: unknown transaction type");
+
+        let options = ImportOptions { date_format: Some("%Y-%m-%d".to_string()), ..Default::default() };
+        assert_eq!(
+            validation_message(parse_transactions(&format!("{}2024-13-06,Tea,10,\n", header), &options)),
+            "Row 3: invalid date"
+        );
+    }
+}
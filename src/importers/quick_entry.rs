@@ -0,0 +1,29 @@
+use rust_decimal::Decimal;
+
+use crate::importers::parse_written_amount;
+
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+// "coffee 4.50" or "4.50 coffee": the amount is the last word, or failing that the first, and
+// the remaining words describe the expense
+pub fn parse_quick_entry(text: &str) -> Option<(String, Decimal)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (amount, description) = match words.last().and_then(|word| amount_word(word)) {
+        Some(amount) => (amount, &words[..words.len() - 1]),
+        None => (amount_word(words.first()?)?, &words[1..]),
+    };
+
+    if description.is_empty() {
+        return None;
+    }
+    Some((description.join(" ").chars().take(MAX_DESCRIPTION_CHARS).collect(), amount))
+}
+
+// Symbols around the number are dropped, as in "$4.50" or "4,50€"
+fn amount_word(word: &str) -> Option<Decimal> {
+    let number = word.trim_matches(|c: char| !c.is_alphanumeric());
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+        return None;
+    }
+    parse_written_amount(number)
+}
\ No newline at end of file
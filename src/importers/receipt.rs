@@ -0,0 +1,188 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rust_decimal::Decimal;
+
+use crate::importers::{thousands_separator_for, ImportLocale};
+
+// Lines naming the amount actually charged, most specific first
+const TOTAL_LABELS: &[&str] = &["grand total", "amount paid", "total paid", "total bayar", "amount due", "total"];
+const FORWARD_PREFIXES: &[&str] = &["fwd:", "fw:", "tr:", "wg:"];
+const DEFAULT_DESCRIPTION: &str = "Emailed receipt";
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+// The charged total: the last amount on the line with the most specific total label
+pub fn parse_receipt_total(text: &str) -> Option<Decimal> {
+    TOTAL_LABELS.iter().find_map(|label| {
+        text.lines()
+            .map(|line| line.to_lowercase())
+            .filter(|line| !(*label == "total" && line.contains("subtotal")))
+            .find_map(|line| {
+                let (_, after) = line.split_once(label)?;
+                parse_written_amount(last_number(after)?)
+            })
+    })
+}
+
+// The subject without forwarding prefixes, which usually names the merchant
+pub fn receipt_description(subject: &str) -> String {
+    let mut description = subject.trim();
+    while let Some(prefix) = FORWARD_PREFIXES
+        .iter()
+        .find(|prefix| description.get(..prefix.len()).is_some_and(|head| head.eq_ignore_ascii_case(prefix)))
+    {
+        description = description[prefix.len()..].trim_start();
+    }
+
+    if description.is_empty() {
+        return DEFAULT_DESCRIPTION.to_string();
+    }
+    description.chars().take(MAX_DESCRIPTION_CHARS).collect()
+}
+
+fn last_number(text: &str) -> Option<&str> {
+    let end = text.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let start = text[..end]
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .map_or(0, |i| i + 1);
+    Some(text[start..end].trim_start_matches(['.', ',']))
+}
+
+// Amounts as people write them rarely show three decimals, so a lone separator before three
+// digits groups thousands ("Rp 25.000"); otherwise the last separator is the decimal point
+pub fn parse_written_amount(raw: &str) -> Option<Decimal> {
+    let decimal_separator = match raw.rfind(['.', ',']) {
+        Some(i) if raw.len() - i - 1 == 3 => thousands_separator_for(raw.as_bytes()[i] as char),
+        Some(i) => raw.as_bytes()[i] as char,
+        None => '.',
+    };
+    let locale = ImportLocale {
+        decimal_separator,
+        thousands_separator: thousands_separator_for(decimal_separator),
+        date_format: String::new(),
+    };
+
+    locale.parse_amount(raw).filter(|amount| *amount > Decimal::ZERO)
+}
+
+// The readable text of a raw MIME message: its first text/plain part, or the first text/html
+// part with the markup dropped
+pub fn plain_text_from_mime(raw: &str) -> String {
+    let mut html = None;
+    if let Some(text) = find_text_part(raw, &mut html) {
+        return text;
+    }
+    html.map(|html| strip_tags(&html)).unwrap_or_default()
+}
+
+fn find_text_part(part: &str, html: &mut Option<String>) -> Option<String> {
+    let (headers, body) = split_headers(part);
+    let content_type = header_value(&headers, "content-type").unwrap_or_else(|| "text/plain".to_string());
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+
+    if media_type.starts_with("multipart/") {
+        let boundary = header_param(&content_type, "boundary")?;
+        let delimiter = format!("--{}", boundary);
+        return body
+            .split(delimiter.as_str())
+            .skip(1)
+            .take_while(|section| !section.starts_with("--"))
+            .find_map(|section| find_text_part(section.trim_start_matches(['\r', '\n']), html));
+    }
+
+    let encoding = header_value(&headers, "content-transfer-encoding").unwrap_or_default().to_lowercase();
+    let decoded = match encoding.as_str() {
+        "base64" => {
+            let compact: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+            String::from_utf8_lossy(&STANDARD.decode(compact).ok()?).into_owned()
+        }
+        "quoted-printable" => decode_quoted_printable(body),
+        _ => body.to_string(),
+    };
+
+    match media_type.as_str() {
+        "text/plain" => Some(decoded),
+        "text/html" => {
+            html.get_or_insert(decoded);
+            None
+        }
+        _ => None,
+    }
+}
+
+// Headers unfolded one per line, and the body after the first blank line
+fn split_headers(part: &str) -> (Vec<String>, &str) {
+    let (head, body) = part
+        .split_once("\r\n\r\n")
+        .or_else(|| part.split_once("\n\n"))
+        .unwrap_or((part, ""));
+
+    let mut headers: Vec<String> = Vec::new();
+    for line in head.lines() {
+        match headers.last_mut() {
+            Some(last) if line.starts_with([' ', '\t']) => last.push_str(line),
+            _ => headers.push(line.to_string()),
+        }
+    }
+    (headers, body)
+}
+
+fn header_value(headers: &[String], name: &str) -> Option<String> {
+    headers.iter().find_map(|header| {
+        let (key, value) = header.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim().to_string())
+    })
+}
+
+fn header_param(value: &str, name: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn decode_quoted_printable(body: &str) -> String {
+    let bytes = body.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'=' {
+            decoded.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+
+        let rest = &bytes[i + 1..];
+        if rest.starts_with(b"\r\n") {
+            i += 3;
+        } else if rest.starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = rest
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(b'=');
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push('\n');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
\ No newline at end of file
@@ -0,0 +1,295 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+// Five years of minutes; an expression with no match in that span (e.g. Feb 31) never fires
+const MAX_SEARCH_MINUTES: i64 = 5 * 366 * 24 * 60;
+
+// A standard five-field cron expression (minute hour day-of-month month day-of-week),
+// evaluated in UTC. Fields accept `*`, numbers, ranges `a-b`, lists `a,b` and steps `*/n` or `a-b/n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    // Cron matches either day field when both are restricted, and only the other when one is `*`
+    day_of_month_any: bool,
+    day_of_week_any: bool,
+}
+
+impl CronExpr {
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut candidate = start;
+
+        while (candidate - start).num_minutes() <= MAX_SEARCH_MINUTES {
+            if !bit(self.months, candidate.month()) {
+                candidate = first_of_next_month(candidate)?;
+                continue;
+            }
+            if !self.day_matches(candidate) {
+                candidate = start_of_day(candidate)? + Duration::days(1);
+                continue;
+            }
+            if !bit(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+            return Some(candidate);
+        }
+
+        None
+    }
+
+    fn day_matches(&self, at: DateTime<Utc>) -> bool {
+        let by_month = bit(self.days_of_month, at.day());
+        let by_week = bit(self.days_of_week, at.weekday().num_days_from_sunday());
+
+        match (self.day_of_month_any, self.day_of_week_any) {
+            (true, true) => true,
+            (true, false) => by_week,
+            (false, true) => by_month,
+            (false, false) => by_month || by_week,
+        }
+    }
+}
+
+impl FromStr for CronExpr {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = source.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(format!("Cron expression '{}' must have 5 fields", source));
+        };
+
+        // Sunday may be written as 0 or 7
+        let mut days_of_week = parse_field(day_of_week, 0, 7)?;
+        if bit(days_of_week, 7) {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            source: fields.join(" "),
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(day_of_month, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week,
+            day_of_month_any: *day_of_month == "*",
+            day_of_week_any: *day_of_week == "*",
+        })
+    }
+}
+
+impl fmt::Display for CronExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("Invalid step in cron field '{}'", field))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, field)?, parse_value(end, field)?)
+        } else {
+            let value = parse_value(range, field)?;
+            // `5/15` means every 15 starting at 5
+            (value, if step > 1 { max } else { value })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(format!("Cron field '{}' must stay within {}-{}", field, min, max));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_value(value: &str, field: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{}' in cron field '{}'", value, field))
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn start_of_day(at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(at.year(), at.month(), at.day(), 0, 0, 0).single()
+}
+
+fn first_of_next_month(at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (year, month) = if at.month() == 12 { (at.year() + 1, 1) } else { (at.year(), at.month() + 1) };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, second).single().expect("valid time")
+    }
+
+    fn next(expression: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        expression.parse::<CronExpr>().expect("valid expression").next_after(after)
+    }
+
+    #[test]
+    fn steps_fire_on_the_next_matching_minute() {
+        assert_eq!(next("*/5 * * * *", at(2024, 1, 1, 10, 3, 27)), Some(at(2024, 1, 1, 10, 5, 0)));
+    }
+
+    #[test]
+    fn next_run_is_strictly_after_the_given_time() {
+        assert_eq!(next("*/5 * * * *", at(2024, 1, 1, 10, 5, 0)), Some(at(2024, 1, 1, 10, 10, 0)));
+    }
+
+    #[test]
+    fn hourly_runs_roll_over_into_the_next_hour() {
+        assert_eq!(next("0 * * * *", at(2024, 1, 1, 10, 59, 30)), Some(at(2024, 1, 1, 11, 0, 0)));
+    }
+
+    #[test]
+    fn daily_runs_roll_over_into_the_next_month() {
+        assert_eq!(next("30 2 * * *", at(2024, 1, 31, 3, 0, 0)), Some(at(2024, 2, 1, 2, 30, 0)));
+    }
+
+    #[test]
+    fn yearly_runs_roll_over_into_the_next_year() {
+        assert_eq!(next("0 0 1 1 *", at(2024, 6, 15, 12, 0, 0)), Some(at(2025, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn day_of_week_picks_the_next_matching_weekday() {
+        // 2024-01-02 is a Tuesday
+        assert_eq!(next("0 9 * * 1", at(2024, 1, 2, 0, 0, 0)), Some(at(2024, 1, 8, 9, 0, 0)));
+    }
+
+    #[test]
+    fn sunday_can_be_written_as_seven() {
+        let zero = "0 9 * * 0".parse::<CronExpr>().expect("valid expression");
+        let seven = "0 9 * * 7".parse::<CronExpr>().expect("valid expression");
+
+        assert_eq!(seven.next_after(at(2024, 1, 2, 0, 0, 0)), Some(at(2024, 1, 7, 9, 0, 0)));
+        assert_eq!(zero.next_after(at(2024, 1, 2, 0, 0, 0)), seven.next_after(at(2024, 1, 2, 0, 0, 0)));
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either_day() {
+        // The 13th or any Monday; 2024-01-08 is a Monday and 2024-01-13 a Saturday
+        assert_eq!(next("0 0 13 * 1", at(2024, 1, 2, 0, 0, 0)), Some(at(2024, 1, 8, 0, 0, 0)));
+        assert_eq!(next("0 0 13 * 1", at(2024, 1, 8, 0, 0, 0)), Some(at(2024, 1, 13, 0, 0, 0)));
+    }
+
+    #[test]
+    fn a_step_from_a_value_repeats_until_the_end_of_the_field() {
+        assert_eq!(next("5/15 * * * *", at(2024, 1, 1, 10, 0, 0)), Some(at(2024, 1, 1, 10, 5, 0)));
+        assert_eq!(next("5/15 * * * *", at(2024, 1, 1, 10, 5, 0)), Some(at(2024, 1, 1, 10, 20, 0)));
+        assert_eq!(next("5/15 * * * *", at(2024, 1, 1, 10, 50, 0)), Some(at(2024, 1, 1, 11, 5, 0)));
+    }
+
+    #[test]
+    fn ranges_and_lists_combine() {
+        let expression = "0 9-17/4,20 * * 1-5";
+
+        assert_eq!(next(expression, at(2024, 1, 1, 9, 0, 0)), Some(at(2024, 1, 1, 13, 0, 0)));
+        assert_eq!(next(expression, at(2024, 1, 1, 17, 0, 0)), Some(at(2024, 1, 1, 20, 0, 0)));
+        // Friday evening skips the weekend
+        assert_eq!(next(expression, at(2024, 1, 5, 20, 0, 0)), Some(at(2024, 1, 8, 9, 0, 0)));
+    }
+
+    #[test]
+    fn leap_days_wait_for_the_next_leap_year() {
+        assert_eq!(next("0 0 29 2 *", at(2024, 3, 1, 0, 0, 0)), Some(at(2028, 2, 29, 0, 0, 0)));
+    }
+
+    #[test]
+    fn impossible_dates_never_fire() {
+        assert_eq!(next("0 0 31 2 *", at(2024, 1, 1, 0, 0, 0)), None);
+    }
+
+    #[test]
+    fn display_normalizes_whitespace() {
+        let expression = "  0   9 * *\t1 ".parse::<CronExpr>().expect("valid expression");
+
+        assert_eq!(expression.to_string(), "0 9 * * 1");
+    }
+
+    #[test]
+    fn expressions_need_five_fields() {
+        assert_eq!(
+            "* * * *".parse::<CronExpr>(),
+            Err("Cron expression '* * * *' must have 5 fields".to_string())
+        );
+        assert!("0 * * * * *".parse::<CronExpr>().is_err());
+        assert!("".parse::<CronExpr>().is_err());
+    }
+
+    #[test]
+    fn values_outside_the_field_range_are_rejected() {
+        assert_eq!(
+            "60 * * * *".parse::<CronExpr>(),
+            Err("Cron field '60' must stay within 0-59".to_string())
+        );
+        assert!("* 24 * * *".parse::<CronExpr>().is_err());
+        assert!("* * 0 * *".parse::<CronExpr>().is_err());
+        assert!("* * * 13 *".parse::<CronExpr>().is_err());
+        assert!("* * * * 8".parse::<CronExpr>().is_err());
+    }
+
+    #[test]
+    fn reversed_ranges_are_rejected() {
+        assert_eq!(
+            "30-10 * * * *".parse::<CronExpr>(),
+            Err("Cron field '30-10' must stay within 0-59".to_string())
+        );
+    }
+
+    #[test]
+    fn zero_and_non_numeric_steps_are_rejected() {
+        assert_eq!(
+            "*/0 * * * *".parse::<CronExpr>(),
+            Err("Invalid step in cron field '*/0'".to_string())
+        );
+        assert!("*/x * * * *".parse::<CronExpr>().is_err());
+    }
+
+    #[test]
+    fn non_numeric_values_are_rejected() {
+        assert_eq!(
+            "* * * JAN *".parse::<CronExpr>(),
+            Err("Invalid value 'JAN' in cron field 'JAN'".to_string())
+        );
+        assert!("1-x * * * *".parse::<CronExpr>().is_err());
+        assert!("1,,2 * * * *".parse::<CronExpr>().is_err());
+    }
+}
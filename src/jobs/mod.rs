@@ -0,0 +1,84 @@
+pub mod cron;
+
+pub use cron::*;
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+// When a scheduled task fires. Firing times are derived from the wall clock rather
+// than from process start, so every instance computes the same slots.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    // Every n seconds, aligned to the Unix epoch
+    Every(u64),
+    Cron(CronExpr),
+}
+
+impl Schedule {
+    pub fn every(secs: u64) -> Self {
+        Self::Every(secs.max(1))
+    }
+
+    pub fn cron(expression: &str) -> Result<Self, String> {
+        expression.parse().map(Self::Cron)
+    }
+
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(secs) => {
+                let secs = *secs as i64;
+                let next = (after.timestamp().div_euclid(secs) + 1) * secs;
+                DateTime::from_timestamp(next, 0)
+            }
+            Self::Cron(expression) => expression.next_after(after),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Every(secs) => write!(f, "every {}s", secs),
+            Self::Cron(expression) => write!(f, "cron {}", expression),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, hour, minute, second).single().expect("valid time")
+    }
+
+    #[test]
+    fn intervals_are_aligned_to_the_epoch() {
+        let schedule = Schedule::every(300);
+
+        assert_eq!(schedule.next_after(at(10, 3, 27)), Some(at(10, 5, 0)));
+        assert_eq!(schedule.next_after(at(10, 5, 0)), Some(at(10, 10, 0)));
+    }
+
+    #[test]
+    fn zero_second_intervals_run_every_second() {
+        let schedule = Schedule::every(0);
+
+        assert_eq!(schedule.to_string(), "every 1s");
+        assert_eq!(schedule.next_after(at(10, 0, 0)), Some(at(10, 0, 1)));
+    }
+
+    #[test]
+    fn cron_schedules_use_the_expression() {
+        let schedule = Schedule::cron("0 */6 * * *").expect("valid expression");
+
+        assert_eq!(schedule.to_string(), "cron 0 */6 * * *");
+        assert_eq!(schedule.next_after(at(10, 0, 0)), Some(at(12, 0, 0)));
+    }
+
+    #[test]
+    fn invalid_cron_schedules_are_rejected() {
+        assert!(Schedule::cron("every hour").is_err());
+        assert!(Schedule::cron("0 25 * * *").is_err());
+    }
+}
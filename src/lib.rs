@@ -1,18 +1,30 @@
+pub mod app;
+pub mod categorization;
 pub mod config;
+pub mod exporters;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod importers;
+pub mod jobs;
 pub mod handlers;
 pub mod middleware;
 pub mod models;
+pub mod policy;
 pub mod repositories;
 pub mod routes;
 pub mod services;
+pub mod state;
 pub mod utils;
 
+// App exports
+pub use app::{build_app, App};
+
 // Config exports
 pub use config::*;
 
 // Handler exports
 pub use handlers::auth::{register, login};
-pub use handlers::user::{get_me, update_name, update_hide_balance, list_users};
+pub use handlers::user::{get_me, update_name, update_hide_balance, update_monthly_digest, list_users};
 pub use handlers::pocket::{get_pockets, get_pocket_by_id, create_pocket, update_pocket, delete_pocket};
 
 // Middleware exports
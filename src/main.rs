@@ -1,90 +1,75 @@
-use axum::{
-    extract::Extension,
-    http::StatusCode,
-    response::Json,
-    routing::get,
-    Router,
-};
-use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::signal;
-use tracing::{info, warn, Level};
-use tracing_subscriber;
+use tracing::{info, warn};
+use tracing_subscriber::{filter::{filter_fn, LevelFilter}, prelude::*};
 
 use rust_fintrack_backend::{
-    config::{create_pool, AppConfig, JwtConfig},
-    middleware::{cors_layer, logging_layer},
-    repositories::{PostgresAuthRepository, PostgresPocketRepository, PostgresUserRepository, PostgresTransactionRepository, PostgresBudgetRepository},
-    routes::{auth_routes, pocket_routes, user_routes, transaction_routes, budget_routes, account_summary_routes, expense_analytics_routes, income_analytics_routes},
-    services::{AuthService, PocketService, UserService, TransactionService, BudgetService, AccountSummaryService, ExpenseAnalyticsService, IncomeAnalyticsService},
-    utils::{CacheService, start_connection_monitoring},
+    app::{build_app, App},
+    config::{create_pool, create_replica_pool, AppConfig},
+    services::start_push_delivery,
+    middleware::{is_statement_event, SlowQueryLayer, StatementCountLayer},
+    utils::{CacheService, start_connection_monitoring, load_cache_snapshot, save_cache_snapshot},
 };
 
+// How long shutdown waits for in-flight background task runs
+const SCHEDULER_SHUTDOWN_GRACE_SECS: u64 = 30;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
-
     // Load configuration
     let config = AppConfig::from_env()?;
+
+    // Initialize tracing; statement events only feed the per-request statement counter
+    // and the slow statement log
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO))
+        .with(StatementCountLayer.with_filter(filter_fn(is_statement_event)))
+        .with(SlowQueryLayer::new(config.metrics.slow_query_threshold_ms).with_filter(filter_fn(is_statement_event)))
+        .init();
+
     info!("Starting server with config: {:?}", config);
 
     // Create database connection pool
     let pool = create_pool().await?;
     info!("Database connection pool created");
 
-    // Start connection monitoring
-    start_connection_monitoring(pool.clone()).await;
-    info!("Connection monitoring started");
+    // Analytics and list reads go here when a replica is configured
+    let replica_pool = create_replica_pool().await?;
 
     // Create Redis cache service
     let cache_service = CacheService::new(&config.redis).await;
 
-    // Create JWT config
-    let jwt_config = JwtConfig::new(&config.jwt_secret);
-
-    // Create repositories
-    let auth_repository = PostgresAuthRepository::new(pool.clone());
-    let user_repository = PostgresUserRepository::new(pool.clone());
-    let pocket_repository = PostgresPocketRepository::new(pool.clone());
-    let transaction_repository = PostgresTransactionRepository::new(pool.clone());
-    let budget_repository = PostgresBudgetRepository::new(pool.clone());
-
-    // Create services
-    let auth_service = AuthService::new(auth_repository, jwt_config.clone());
-    let user_service = UserService::new(user_repository);
-    let pocket_service = PocketService::new(pocket_repository.clone());
-    let transaction_service = TransactionService::new(transaction_repository.clone());
-    let budget_service = BudgetService::new(budget_repository);
-    let account_summary_service = AccountSummaryService::new(pocket_repository.clone(), transaction_repository.clone());
-    let expense_analytics_service = ExpenseAnalyticsService::new(transaction_repository.clone());
-    let income_analytics_service = IncomeAnalyticsService::new(transaction_repository);
-
-    // Build application routes
-    let app = Router::new()
-        .route("/health", get(health_check))
-        .nest("/auth", auth_routes().with_state(auth_service))
-        .nest("/users", user_routes().with_state(user_service))
-        .nest("/pockets", pocket_routes().with_state(pocket_service))
-        .merge(transaction_routes().with_state(transaction_service))
-        .merge(budget_routes().with_state(budget_service))
-        .merge(account_summary_routes().with_state(account_summary_service))
-        .merge(expense_analytics_routes().with_state(expense_analytics_service))
-        .merge(income_analytics_routes().with_state(income_analytics_service))
-        .layer(cors_layer())
-        .layer(logging_layer())
-        .layer(Extension(pool.clone()))
-        .layer(Extension(jwt_config))
-        .layer(Extension(cache_service));
+    // Warm the cache from the previous shutdown's snapshot
+    load_cache_snapshot(&cache_service, &config.redis).await;
+
+    // Build the application
+    let App { router: app, scheduler, events, pool_metrics, push_notifications } = build_app(&config, pool.clone(), replica_pool, cache_service.clone())?;
+    scheduler.start();
+
+    // Start connection monitoring
+    start_connection_monitoring(pool.clone(), &config.pool_monitor, pool_metrics).await;
+    info!("Connection monitoring started");
+
+    start_push_delivery(push_notifications, events.subscribe());
+
+    #[cfg(feature = "event-stream")]
+    rust_fintrack_backend::utils::start_event_stream(config.event_stream.clone(), events.subscribe());
+    #[cfg(not(feature = "event-stream"))]
+    {
+        let _ = events;
+        if config.event_stream.nats_url.is_some() {
+            warn!("EVENT_STREAM_NATS_URL is set but this build lacks the event-stream feature");
+        }
+    }
 
     // Start server
     let listener = TcpListener::bind(&config.server_address()).await?;
     info!("Server listening on {}", config.server_address());
 
     // Setup graceful shutdown
-    let server = axum::serve(listener, app);
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>());
     
     // Handle shutdown signals
     tokio::select! {
@@ -98,6 +83,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Let background task runs finish before their connections go away
+    scheduler.shutdown(Duration::from_secs(SCHEDULER_SHUTDOWN_GRACE_SECS)).await;
+
+    // Dump hot cache entries for the next start
+    save_cache_snapshot(&cache_service, &config.redis).await;
+
     // Close database pool gracefully
     info!("Closing database connections...");
     pool.close().await;
@@ -129,11 +120,4 @@ async fn shutdown_signal() {
         _ = ctrl_c => {},
         _ = terminate => {},
     }
-}
-
-async fn health_check() -> Result<Json<Value>, StatusCode> {
-    Ok(Json(json!({
-        "status": "ok",
-        "message": "Rust Fintrack Backend is running"
-    })))
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use axum::{
+    extract::Request,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::config::AdminConfig;
+use crate::middleware::AuthUser;
+use crate::utils::AppError;
+
+// Must sit inside auth_middleware so the authenticated user is already attached
+pub async fn admin_middleware(request: Request, next: Next) -> Result<Response, AppError> {
+    let auth_user = request
+        .extensions()
+        .get::<AuthUser>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Authentication required".to_string()))?;
+
+    let config = request
+        .extensions()
+        .get::<AdminConfig>()
+        .cloned()
+        .ok_or_else(|| AppError::InternalServerError("Admin config not found".to_string()))?;
+
+    if !config.is_admin(&auth_user.email) {
+        return Err(AppError::Forbidden("Admin access required".to_string()));
+    }
+
+    Ok(next.run(request).await)
+}
\ No newline at end of file
@@ -1,18 +1,28 @@
 use axum::{
-    extract::{FromRequestParts, Request},
-    http::{header::AUTHORIZATION, request::Parts},
+    extract::{FromRequestParts, Query, Request},
+    http::{header::AUTHORIZATION, request::Parts, Extensions},
     middleware::Next,
     response::Response,
 };
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::config::JwtConfig;
-use crate::utils::AppError;
+use crate::models::Claims;
+use crate::repositories::PostgresSessionRepository;
+use crate::services::SessionService;
+use crate::utils::{AppError, codes, CacheService, hash_token, jwt_cache_key, user_tokens_valid_after_key, session_cache_key};
+
+// How long a confirmed-active session, or a token cutoff read back from the users table,
+// is trusted before the table is checked again
+const ACTIVE_SESSION_CACHE_TTL_SECS: u64 = 60;
 
 #[derive(Clone)]
 pub struct AuthUser {
     pub id: Uuid,
     pub email: String,
+    pub session_id: Uuid,
+    pub issued_at: i64,
 }
 
 impl<S> FromRequestParts<S> for AuthUser
@@ -30,17 +40,15 @@ where
     }
 }
 
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
 pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    // Extract JWT config from request extensions
-    let jwt_config = request
-        .extensions()
-        .get::<JwtConfig>()
-        .ok_or_else(|| AppError::InternalServerError("JWT config not found".to_string()))?
-        .clone();
-
     let auth_header = request
         .headers()
         .get(AUTHORIZATION)
@@ -51,18 +59,122 @@ pub async fn auth_middleware(
         .strip_prefix("Bearer ")
         .ok_or_else(|| AppError::Unauthorized("Invalid authorization header format".to_string()))?;
 
-    let claims = jwt_config.verify_token(token)?;
+    let auth_user = authenticate_token(request.extensions(), token).await?;
+    request.extensions_mut().insert(auth_user);
 
-    let auth_user = AuthUser {
-        id: claims.sub,
-        email: claims.email,
-    };
+    Ok(next.run(request).await)
+}
 
+// Browsers cannot set headers on WebSocket or EventSource requests, so those routes take the access token as ?token=
+pub async fn query_token_auth_middleware(
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Query(query) = Query::<TokenQuery>::try_from_uri(request.uri())
+        .map_err(|_| AppError::Unauthorized("Missing access token".to_string()))?;
+
+    let auth_user = authenticate_token(request.extensions(), &query.token).await?;
     request.extensions_mut().insert(auth_user);
 
     Ok(next.run(request).await)
 }
 
+async fn authenticate_token(extensions: &Extensions, token: &str) -> Result<AuthUser, AppError> {
+    // Extract JWT config from request extensions
+    let jwt_config = extensions
+        .get::<JwtConfig>()
+        .ok_or_else(|| AppError::InternalServerError("JWT config not found".to_string()))?
+        .clone();
+
+    let cache = extensions.get::<CacheService>().cloned();
+    let claims = verified_claims(&jwt_config, cache.as_ref(), token).await?;
+
+    let session_service = extensions
+        .get::<SessionService<PostgresSessionRepository>>()
+        .ok_or_else(|| AppError::InternalServerError("Session service not found".to_string()))?
+        .clone();
+
+    // Revocation is checked on every request, cached claims or not.
+    // Tokens issued before a password change are no longer honoured.
+    if (claims.iat as i64) < tokens_valid_after(&session_service, cache.as_ref(), claims.sub).await? {
+        return Err(AppError::Unauthorized("Token has been revoked".to_string()).with_code(codes::TOKEN_REVOKED));
+    }
+
+    // Revoked sessions are cached as false so logout takes effect immediately. Both markers are
+    // read from Redis, never an instance's local tier, so every instance sees them at once.
+    let session_key = session_cache_key(&claims.sid);
+    let cached_state = match &cache {
+        Some(cache) => cache.get::<bool>(&session_key).await,
+        None => None,
+    };
+
+    let session_active = match cached_state {
+        Some(active) => active,
+        None => {
+            let active = session_service.validate_session(claims.sid).await?;
+            if active && let Some(cache) = &cache {
+                cache.set(&session_key, &true, Some(ACTIVE_SESSION_CACHE_TTL_SECS)).await;
+            }
+            active
+        }
+    };
+
+    if !session_active {
+        return Err(AppError::Unauthorized("Session has been revoked".to_string()).with_code(codes::TOKEN_REVOKED));
+    }
+
+    Ok(AuthUser {
+        id: claims.sub,
+        email: claims.email,
+        session_id: claims.sid,
+        issued_at: claims.iat as i64,
+    })
+}
+
+// The Redis marker first; when Redis is down or has evicted it, the cutoff persisted on the
+// user row, cached again briefly so a healthy Redis spares the next requests the lookup
+async fn tokens_valid_after(
+    session_service: &SessionService<PostgresSessionRepository>,
+    cache: Option<&CacheService>,
+    user_id: Uuid,
+) -> Result<i64, AppError> {
+    let key = user_tokens_valid_after_key(&user_id);
+    if let Some(cache) = cache
+        && let Some(valid_after) = cache.get::<i64>(&key).await
+    {
+        return Ok(valid_after);
+    }
+
+    let valid_after = session_service.tokens_valid_after(user_id).await?;
+    if let Some(cache) = cache {
+        cache.set(&key, &valid_after, Some(ACTIVE_SESSION_CACHE_TTL_SECS)).await;
+    }
+    Ok(valid_after)
+}
+
+// Verified claims are cached under the token's hash until the token expires, so repeat
+// requests skip signature verification
+async fn verified_claims(jwt_config: &JwtConfig, cache: Option<&CacheService>, token: &str) -> Result<Claims, AppError> {
+    let now = chrono::Utc::now().timestamp();
+    let cache_key = jwt_cache_key(&hash_token(token));
+
+    if let Some(cache) = cache
+        && let Some(claims) = cache.get::<Claims>(&cache_key).await
+        && (claims.exp as i64) > now
+    {
+        return Ok(claims);
+    }
+
+    let claims = jwt_config.verify_token(token)?;
+
+    let remaining_secs = claims.exp as i64 - now;
+    if remaining_secs > 0 && let Some(cache) = cache {
+        cache.set(&cache_key, &claims, Some(remaining_secs as u64)).await;
+    }
+
+    Ok(claims)
+}
+
 // Extension trait to easily extract AuthUser from request
 pub trait AuthUserExt {
     fn auth_user(&self) -> Result<&AuthUser, AppError>;
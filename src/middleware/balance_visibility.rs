@@ -0,0 +1,196 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::header::{CONTENT_LENGTH, CONTENT_TYPE},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::config::{BalanceVisibilityConfig, HiddenBalanceMode};
+use crate::middleware::AuthUser;
+use crate::models::UserResponse;
+use crate::repositories::PostgresUserRepository;
+use crate::services::UserService;
+use crate::utils::{AppError, CacheService, user_cache_key};
+
+const MASKED_VALUE: &str = "***";
+
+// Monetary fields hidden from users who turned on hide_balance
+const SENSITIVE_FIELDS: &[&str] = &[
+    "balance",
+    "total_balance",
+    "net_worth",
+    "amount",
+    "formatted_amount",
+    "total_amount",
+    "total_income",
+    "total_expenses",
+    "average_per_day",
+    "liquid_balance",
+    "monthly_cost",
+    "total_monthly_cost",
+    "total_yearly_cost",
+    "yearly_total",
+    "previous_yearly_total",
+    "category_mean",
+    "category_stddev",
+    "original_amount",
+];
+
+// Responses are buffered up to this size to be masked. Anything larger fails closed with a 500
+// rather than reaching a hide_balance user unmasked.
+const MAX_MASKED_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+pub async fn balance_visibility_middleware(
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(auth_user) = request.extensions().get::<AuthUser>().cloned() else {
+        return Ok(next.run(request).await);
+    };
+
+    let config = request
+        .extensions()
+        .get::<BalanceVisibilityConfig>()
+        .cloned()
+        .ok_or_else(|| AppError::InternalServerError("Balance visibility config not found".to_string()))?;
+
+    let cache = request.extensions().get::<CacheService>().cloned();
+    let user_service = request
+        .extensions()
+        .get::<UserService<PostgresUserRepository>>()
+        .ok_or_else(|| AppError::InternalServerError("User service not found".to_string()))?
+        .clone();
+
+    if !hide_balance_enabled(&user_service, cache.as_ref(), &auth_user).await? {
+        return Ok(next.run(request).await);
+    }
+
+    // Revealing hidden balances needs a token issued moments ago (see POST /auth/step-up)
+    if reveal_requested(&request) {
+        let auth_age = chrono::Utc::now().timestamp() - auth_user.issued_at;
+        if auth_age > config.reveal_max_auth_age_secs {
+            return Err(AppError::Forbidden(
+                "Recent authentication required to reveal balances".to_string(),
+            ));
+        }
+        return Ok(next.run(request).await);
+    }
+
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with("application/json"))
+        .unwrap_or(false);
+    if !is_json {
+        return Ok(response);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = masked_body(body, MAX_MASKED_BODY_BYTES, config.mode).await?;
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Ok(Response::from_parts(parts, body))
+}
+
+async fn masked_body(body: Body, limit: usize, mode: HiddenBalanceMode) -> Result<Body, AppError> {
+    let bytes = to_bytes(body, limit).await.map_err(|e| {
+        warn!("Refusing to send a response that could not be masked: {}", e);
+        AppError::InternalServerError("Response too large to hide balances; retry with reveal=true".to_string())
+    })?;
+
+    Ok(match serde_json::from_slice::<Value>(&bytes) {
+        Ok(mut json) => {
+            hide_sensitive_fields(&mut json, mode);
+            Body::from(serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec()))
+        }
+        Err(_) => Body::from(bytes),
+    })
+}
+
+async fn hide_balance_enabled(
+    user_service: &UserService<PostgresUserRepository>,
+    cache: Option<&CacheService>,
+    auth_user: &AuthUser,
+) -> Result<bool, AppError> {
+    if let Some(cache) = cache
+        && let Some(user) = cache.get::<UserResponse>(&user_cache_key(&auth_user.id)).await
+    {
+        return Ok(user.hide_balance);
+    }
+
+    let user = user_service.get_user_by_id(auth_user.id).await?;
+    let hide_balance = user.hide_balance;
+
+    if let Some(cache) = cache {
+        cache.set(&user_cache_key(&auth_user.id), &user, Some(300)).await;
+    }
+
+    Ok(hide_balance)
+}
+
+fn reveal_requested(request: &Request) -> bool {
+    request
+        .uri()
+        .query()
+        .map(|query| {
+            query.split('&').any(|pair| {
+                matches!(pair.split_once('='), Some(("reveal", value)) if value == "true" || value == "1")
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn hide_sensitive_fields(value: &mut Value, mode: HiddenBalanceMode) {
+    match value {
+        Value::Object(map) => {
+            match mode {
+                HiddenBalanceMode::Omit => map.retain(|key, _| !SENSITIVE_FIELDS.contains(&key.as_str())),
+                HiddenBalanceMode::Mask => {
+                    for (key, field) in map.iter_mut() {
+                        if SENSITIVE_FIELDS.contains(&key.as_str()) && !field.is_null() {
+                            *field = Value::String(MASKED_VALUE.to_string());
+                        }
+                    }
+                }
+            }
+            for field in map.values_mut() {
+                hide_sensitive_fields(field, mode);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                hide_sensitive_fields(item, mode);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn mask(body: &str, limit: usize) -> Result<String, AppError> {
+        let body = masked_body(Body::from(body.to_string()), limit, HiddenBalanceMode::Mask).await?;
+        let bytes = to_bytes(body, usize::MAX).await.expect("masked body");
+        Ok(String::from_utf8(bytes.to_vec()).expect("utf-8"))
+    }
+
+    #[tokio::test]
+    async fn money_fields_are_masked() {
+        let masked = mask(r#"{"data":{"balance":"10.00","name":"Wallet"}}"#, 1024).await.expect("masked");
+        assert_eq!(masked, r#"{"data":{"balance":"***","name":"Wallet"}}"#);
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_fail_closed() {
+        let body = format!(r#"{{"balance":"10.00","padding":"{}"}}"#, "x".repeat(64));
+        assert!(matches!(mask(&body, 32).await, Err(AppError::InternalServerError(_))));
+    }
+}
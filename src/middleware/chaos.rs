@@ -0,0 +1,104 @@
+use axum::{
+    extract::Request,
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use rand::Rng;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::utils::{AppError, CacheService};
+
+// Fault-injection headers, e.g.
+//   X-Chaos-Latency-Ms: 250 | 100-500
+//   X-Chaos-Fail: db,redis,error
+//   X-Chaos-Probability: 0.5
+pub const CHAOS_LATENCY_HEADER: &str = "x-chaos-latency-ms";
+pub const CHAOS_FAIL_HEADER: &str = "x-chaos-fail";
+pub const CHAOS_PROBABILITY_HEADER: &str = "x-chaos-probability";
+
+#[derive(Debug, Default)]
+struct ChaosPlan {
+    latency: Option<(u64, u64)>,
+    fail_db: bool,
+    fail_redis: bool,
+    fail_error: bool,
+    probability: f64,
+}
+
+impl ChaosPlan {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+        let latency = header(CHAOS_LATENCY_HEADER).and_then(|value| {
+            match value.split_once('-') {
+                Some((min, max)) => Some((min.trim().parse().ok()?, max.trim().parse().ok()?)),
+                None => value.trim().parse().ok().map(|ms| (ms, ms)),
+            }
+        });
+
+        let failures: Vec<String> = header(CHAOS_FAIL_HEADER)
+            .map(|value| value.split(',').map(|f| f.trim().to_lowercase()).collect())
+            .unwrap_or_default();
+
+        let probability = header(CHAOS_PROBABILITY_HEADER)
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0);
+
+        Self {
+            latency,
+            fail_db: failures.iter().any(|f| f == "db"),
+            fail_redis: failures.iter().any(|f| f == "redis"),
+            fail_error: failures.iter().any(|f| f == "error"),
+            probability,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.latency.is_none() && !self.fail_db && !self.fail_redis && !self.fail_error
+    }
+}
+
+pub async fn chaos_middleware(
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let plan = ChaosPlan::from_headers(request.headers());
+    if plan.is_empty() {
+        return Ok(next.run(request).await);
+    }
+
+    let (delay, triggered) = {
+        let mut rng = rand::thread_rng();
+        let delay = plan.latency.map(|(min, max)| rng.gen_range(min.min(max)..=max.max(min)));
+        (delay, rng.gen_bool(plan.probability))
+    };
+
+    if let Some(delay) = delay {
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+    }
+
+    if triggered {
+        let path = request.uri().path().to_string();
+
+        if plan.fail_error {
+            warn!("Chaos: injecting internal error for {}", path);
+            return Err(AppError::InternalServerError("Chaos: injected failure".to_string()));
+        }
+
+        if plan.fail_db {
+            warn!("Chaos: injecting database connection failure for {}", path);
+            return Err(AppError::DatabaseError("Chaos: injected connection drop".to_string()));
+        }
+
+        if plan.fail_redis {
+            // Every CacheService the request reaches, in state or extensions, behaves as disabled
+            warn!("Chaos: disabling cache for {}", path);
+            return Ok(CacheService::with_injected_outage(next.run(request)).await);
+        }
+    }
+
+    Ok(next.run(request).await)
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use axum::{
+    extract::{ConnectInfo, FromRef, FromRequestParts},
+    http::{header::USER_AGENT, request::Parts},
+};
+use std::net::{IpAddr, SocketAddr};
+
+use crate::config::HttpConfig;
+use crate::models::ClientInfo;
+use crate::utils::AppError;
+
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    S: Send + Sync,
+    HttpConfig: FromRef<S>,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let peer = ConnectInfo::<SocketAddr>::from_request_parts(parts, state)
+            .await
+            .ok()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let user_agent = header(USER_AGENT.as_str()).map(|agent| agent.chars().take(512).collect());
+
+        // Forwarding headers are only believed from a configured proxy, since anyone else can
+        // send them. The proxy appends the address it saw, so the client is the rightmost entry
+        // that isn't another trusted proxy; anything left of it was supplied by the client.
+        let http = HttpConfig::from_ref(state);
+        let ip_address = match peer {
+            Some(peer) if http.trusts(peer) => header("x-forwarded-for")
+                .and_then(|forwarded| forwarded_client(&forwarded, &http))
+                .or_else(|| header("x-real-ip").and_then(|ip| ip.parse::<IpAddr>().ok()))
+                .or(Some(peer)),
+            peer => peer,
+        }
+        .map(|ip| ip.to_string());
+
+        let device_id = header("x-device-id").map(|id| id.chars().take(128).collect());
+
+        Ok(ClientInfo {
+            user_agent,
+            ip_address,
+            device_id,
+        })
+    }
+}
+
+fn forwarded_client(forwarded: &str, http: &HttpConfig) -> Option<IpAddr> {
+    forwarded
+        .rsplit(',')
+        .map(|hop| hop.trim().parse::<IpAddr>().ok())
+        .find(|hop| !hop.is_some_and(|ip| http.trusts(ip)))
+        .flatten()
+}
\ No newline at end of file
@@ -1,7 +1,23 @@
+pub mod admin;
 pub mod auth;
+pub mod balance_visibility;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod client_info;
+pub mod precondition;
 pub mod cors;
 pub mod logging;
+pub mod query_budget;
+pub mod security;
+pub mod timeout;
 
+pub use admin::*;
 pub use auth::*;
+pub use balance_visibility::*;
+#[cfg(feature = "chaos")]
+pub use chaos::*;
 pub use cors::*;
-pub use logging::*;
\ No newline at end of file
+pub use logging::*;
+pub use query_budget::*;
+pub use security::*;
+pub use timeout::*;
\ No newline at end of file
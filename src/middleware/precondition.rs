@@ -0,0 +1,26 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header::IF_UNMODIFIED_SINCE, request::Parts},
+};
+use chrono::{DateTime, Utc};
+
+use crate::models::IfUnmodifiedSince;
+use crate::utils::AppError;
+
+impl<S> FromRequestParts<S> for IfUnmodifiedSince
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let since = parts
+            .headers
+            .get(IF_UNMODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value.trim()).ok())
+            .map(|date| date.with_timezone(&Utc));
+
+        Ok(IfUnmodifiedSince(since))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::cell::Cell;
+use std::fmt;
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::{warn, Event, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::config::MetricsConfig;
+use crate::utils::StatementMetrics;
+
+// sqlx reports every executed statement as an event on this target
+pub const STATEMENT_LOG_TARGET: &str = "sqlx::query";
+
+// Longest SQL text kept in a slow statement log line
+const MAX_LOGGED_SQL_CHARS: usize = 500;
+
+tokio::task_local! {
+    static STATEMENT_COUNT: Cell<usize>;
+    static STATEMENT_ROUTE: String;
+}
+
+// Counts statement events towards the request running on the current task.
+// Work spawned onto other tasks is not attributed to the request.
+pub struct StatementCountLayer;
+
+impl<S: Subscriber> Layer<S> for StatementCountLayer {
+    fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, S>) {
+        let _ = STATEMENT_COUNT.try_with(|count| count.set(count.get() + 1));
+    }
+}
+
+// Logs statements that ran longer than the threshold, with the route that issued them
+// and the SQL collapsed onto one line. Statements from background tasks show as "background".
+pub struct SlowQueryLayer {
+    threshold: Duration,
+}
+
+impl SlowQueryLayer {
+    pub fn new(threshold_ms: u64) -> Self {
+        Self {
+            threshold: Duration::from_millis(threshold_ms),
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SlowQueryLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if self.threshold.is_zero() {
+            return;
+        }
+
+        let mut statement = StatementFields::default();
+        event.record(&mut statement);
+        if statement.elapsed_secs < self.threshold.as_secs_f64() {
+            return;
+        }
+
+        // sqlx leaves db.statement empty when the summary already is the whole statement
+        let sql = if statement.sql.trim().is_empty() { &statement.summary } else { &statement.sql };
+        let route = STATEMENT_ROUTE
+            .try_with(Clone::clone)
+            .unwrap_or_else(|_| "background".to_string());
+
+        warn!(
+            route = %route,
+            elapsed_ms = (statement.elapsed_secs * 1000.0).round() as u64,
+            threshold_ms = self.threshold.as_millis() as u64,
+            sql = %sql_shape(sql),
+            "Slow database statement"
+        );
+    }
+}
+
+#[derive(Default)]
+struct StatementFields {
+    summary: String,
+    sql: String,
+    elapsed_secs: f64,
+}
+
+impl Visit for StatementFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = value.to_string(),
+            "db.statement" => self.sql = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+// Bind parameters are already placeholders, so collapsing whitespace leaves the statement's shape
+fn sql_shape(sql: &str) -> String {
+    let shape = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+    match shape.char_indices().nth(MAX_LOGGED_SQL_CHARS) {
+        Some((end, _)) => format!("{} …", &shape[..end]),
+        None => shape,
+    }
+}
+
+pub fn is_statement_event(metadata: &Metadata<'_>) -> bool {
+    metadata.target() == STATEMENT_LOG_TARGET
+}
+
+// Flags requests that issue more statements than the configured budget, which
+// usually means a query is running once per pocket or transaction in a loop
+pub async fn query_budget_middleware(request: Request, next: Next) -> Response {
+    let config = request.extensions().get::<MetricsConfig>().cloned();
+    let metrics = request.extensions().get::<StatementMetrics>().cloned();
+    let (Some(config), Some(metrics)) = (config, metrics) else {
+        return next.run(request).await;
+    };
+
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let route = format!("{} {}", request.method(), route);
+
+    let scoped_route = route.clone();
+    STATEMENT_COUNT
+        .scope(Cell::new(0), STATEMENT_ROUTE.scope(scoped_route, async move {
+            let response = next.run(request).await;
+
+            let statements = STATEMENT_COUNT.with(Cell::get);
+            let over_budget = config.statement_budget > 0 && statements > config.statement_budget;
+            if over_budget {
+                warn!(
+                    route = %route,
+                    statements,
+                    budget = config.statement_budget,
+                    "Request exceeded the database statement budget"
+                );
+            }
+            metrics.record(&route, statements, over_budget);
+
+            response
+        }))
+        .await
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    http::{header, HeaderValue},
+    Router,
+};
+use tower_http::{limit::RequestBodyLimitLayer, set_header::SetResponseHeaderLayer};
+
+use crate::config::HttpConfig;
+
+// Applied outermost so every route, including ones that raise their own body limit,
+// is bounded by the upload cap. Request timeouts are per route, see request_timeout_middleware.
+pub fn security_layers(router: Router, config: &HttpConfig) -> Router {
+    let router = router
+        .layer(DefaultBodyLimit::max(config.max_body_bytes))
+        // Rejects by Content-Length before reading, and cuts off chunked bodies past the cap
+        .layer(RequestBodyLimitLayer::new(config.max_upload_bytes))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_FRAME_OPTIONS,
+            HeaderValue::from_static("DENY"),
+        ));
+
+    if config.hsts_max_age_seconds == 0 {
+        return router;
+    }
+
+    let hsts = format!("max-age={}; includeSubDomains", config.hsts_max_age_seconds);
+    router.layer(SetResponseHeaderLayer::if_not_present(
+        header::STRICT_TRANSPORT_SECURITY,
+        HeaderValue::from_str(&hsts).expect("HSTS header value is ASCII"),
+    ))
+}
\ No newline at end of file
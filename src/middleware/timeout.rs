@@ -0,0 +1,38 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::config::HttpConfig;
+use crate::routes::{timeout_budget, TimeoutBudget};
+use crate::utils::AppError;
+
+// Answers with 408 once a request outlives its route's budget. Only the handler is
+// bounded; a streamed response body keeps flowing after the headers are sent.
+pub async fn request_timeout_middleware(request: Request, next: Next) -> Response {
+    let Some(config) = request.extensions().get::<HttpConfig>().cloned() else {
+        return next.run(request).await;
+    };
+
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let seconds = match timeout_budget(request.method().as_str(), &route) {
+        TimeoutBudget::Standard => config.request_timeout_seconds,
+        TimeoutBudget::Long => config.long_request_timeout_seconds,
+    };
+    let method = request.method().clone();
+
+    match tokio::time::timeout(Duration::from_secs(seconds), next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!(route = %format!("{} {}", method, route), budget_secs = seconds, "Request timed out");
+            AppError::RequestTimeout("The request took too long to complete".to_string()).into_response()
+        }
+    }
+}
\ No newline at end of file
@@ -1,8 +1,19 @@
 use serde::{Serialize, Deserialize};
+use validator::Validate;
+
+use crate::models::{FormatAmounts, MoneyFormatter};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AccountSummaryQuery {
+    pub include_archived: Option<bool>,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccountSummaryResponse {
     pub total_balance: String,
+    // The total balance, filled in per request from the user's locale and currency preferences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted_amount: Option<String>,
     pub accounts: Vec<AccountInfo>,
     pub total_income: String,
     pub total_expenses: String,
@@ -14,5 +25,22 @@ pub struct AccountInfo {
     pub id: String,
     pub name: String,
     pub balance: String,
+    pub currency: String,
+    // The balance in the pocket's own currency, written the user's way
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted_amount: Option<String>,
     pub account_type: String, // "pocket" for now, can be extended later
+}
+
+impl FormatAmounts for AccountSummaryResponse {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        self.formatted_amount = formatter.format_str(&self.total_balance);
+        self.accounts.format_amounts(formatter);
+    }
+}
+
+impl FormatAmounts for AccountInfo {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        self.formatted_amount = formatter.in_currency(&self.currency).format_str(&self.balance);
+    }
 }
\ No newline at end of file
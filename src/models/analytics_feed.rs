@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const FEED_TYPE_CATEGORY_SUMMARY: &str = "monthly_category_summary";
+pub const FEED_TYPE_CASHFLOW: &str = "monthly_cashflow";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AnalyticsFeed {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub feed_type: String,
+    pub label: Option<String>,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalyticsFeedResponse {
+    pub id: Uuid,
+    pub feed_type: String,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAnalyticsFeedRequest {
+    #[validate(custom(function = "validate_feed_type"))]
+    pub feed_type: String,
+    #[validate(length(min = 1, max = 100, message = "Label must be between 1 and 100 characters"))]
+    pub label: Option<String>,
+}
+
+// Like CreatedShareTokenResponse, the one feed response that carries the raw token
+#[derive(Debug, Serialize)]
+pub struct CreatedAnalyticsFeedResponse {
+    pub token: String,
+    pub feed: AnalyticsFeedResponse,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AnalyticsFeedQuery {
+    // Trailing months including the current one
+    pub months: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryFeedRow {
+    pub month: String,
+    pub category: String,
+    pub total: Decimal,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashflowFeedRow {
+    pub month: String,
+    pub income: Decimal,
+    pub expenses: Decimal,
+    pub net: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnalyticsFeedRows {
+    Categories(Vec<CategoryFeedRow>),
+    Cashflow(Vec<CashflowFeedRow>),
+}
+
+// Flat document so dashboard tools can point a JSON datasource straight at `rows`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsFeedDocument {
+    pub feed_type: String,
+    pub label: Option<String>,
+    pub currency: String,
+    pub months: u32,
+    pub generated_at: DateTime<Utc>,
+    pub rows: AnalyticsFeedRows,
+}
+
+impl From<AnalyticsFeed> for AnalyticsFeedResponse {
+    fn from(feed: AnalyticsFeed) -> Self {
+        Self {
+            id: feed.id,
+            feed_type: feed.feed_type,
+            label: feed.label,
+            created_at: feed.created_at,
+            last_used_at: feed.last_used_at,
+            revoked_at: feed.revoked_at,
+        }
+    }
+}
+
+fn validate_feed_type(feed_type: &str) -> Result<(), validator::ValidationError> {
+    if feed_type == FEED_TYPE_CATEGORY_SUMMARY || feed_type == FEED_TYPE_CASHFLOW {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_feed_type"))
+    }
+}
\ No newline at end of file
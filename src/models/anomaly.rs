@@ -0,0 +1,66 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+// An expense compared against the category's history before it
+#[derive(Debug, Clone, FromRow)]
+pub struct SpendingAnomaly {
+    pub transaction_id: i64,
+    pub description: String,
+    pub category: String,
+    pub amount: Decimal,
+    pub transaction_date: NaiveDate,
+    pub category_mean: Decimal,
+    pub category_stddev: Decimal,
+    pub sample_size: i64,
+    pub z_score: Decimal,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AnomalyQuery {
+    // How far back to look for unusual expenses, default 30
+    #[validate(range(min = 1, max = 365, message = "Days must be between 1 and 365"))]
+    pub days: Option<i64>,
+    // Standard deviations above the category mean, default 3
+    #[validate(range(min = 1.0, max = 10.0, message = "Threshold must be between 1 and 10"))]
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnomaliesResponse {
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub threshold: String,
+    pub anomalies: Vec<AnomalyItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnomalyItem {
+    pub transaction_id: i64,
+    pub description: String,
+    pub category: String,
+    pub amount: String,
+    pub transaction_date: NaiveDate,
+    pub category_mean: String,
+    pub category_stddev: String,
+    pub sample_size: i64,
+    pub z_score: String,
+}
+
+impl From<SpendingAnomaly> for AnomalyItem {
+    fn from(anomaly: SpendingAnomaly) -> Self {
+        Self {
+            transaction_id: anomaly.transaction_id,
+            description: anomaly.description,
+            category: anomaly.category,
+            amount: anomaly.amount.to_string(),
+            transaction_date: anomaly.transaction_date,
+            category_mean: anomaly.category_mean.to_string(),
+            category_stddev: anomaly.category_stddev.to_string(),
+            sample_size: anomaly.sample_size,
+            z_score: anomaly.z_score.to_string(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const AUDIT_ENTITY_TRANSACTION: &str = "transaction";
+pub const AUDIT_ENTITY_POCKET: &str = "pocket";
+
+pub const AUDIT_ACTION_CREATE: &str = "create";
+pub const AUDIT_ACTION_UPDATE: &str = "update";
+pub const AUDIT_ACTION_DELETE: &str = "delete";
+pub const AUDIT_ACTION_ADJUST: &str = "adjust";
+pub const AUDIT_ACTION_ARCHIVE: &str = "archive";
+pub const AUDIT_ACTION_UNARCHIVE: &str = "unarchive";
+pub const AUDIT_ACTION_POST: &str = "post";
+pub const AUDIT_ACTION_CANCEL: &str = "cancel";
+pub const AUDIT_ACTION_SHARE: &str = "share";
+pub const AUDIT_ACTION_UNSHARE: &str = "unshare";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: String,
+    pub summary: String,
+    pub details: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogEntryResponse {
+    pub id: i64,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: String,
+    pub summary: String,
+    pub details: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AuditLogQuery {
+    #[validate(range(min = 1, message = "Page must be greater than 0"))]
+    pub page: Option<i32>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: Option<i32>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub action: Option<String>,
+    pub from_date: Option<String>, // YYYY-MM-DD, inclusive
+    pub to_date: Option<String>,   // YYYY-MM-DD, inclusive
+    // Matched against the summary and the recorded details
+    pub q: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListAuditLogResponse {
+    pub data: Vec<AuditLogEntryResponse>,
+    pub page: i32,
+    pub limit: i32,
+    pub total_items: i64,
+}
+
+impl From<AuditLogEntry> for AuditLogEntryResponse {
+    fn from(entry: AuditLogEntry) -> Self {
+        Self {
+            id: entry.id,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            action: entry.action,
+            summary: entry.summary,
+            details: entry.details,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+impl AuditLogEntry {
+    pub fn to_response(self) -> AuditLogEntryResponse {
+        AuditLogEntryResponse::from(self)
+    }
+}
\ No newline at end of file
@@ -16,13 +16,29 @@ pub struct RegisterRequest {
     pub name: String,
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
-    #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
+    // Checked against the password policy by the service that sets it
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StepUpRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct RefreshTokenRequest {
+    #[validate(length(min = 1, message = "Refresh token is required"))]
+    pub refresh_token: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AuthResponse {
     pub token: String,
+    // Only issued to clients that identify their device
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
     pub user: super::UserResponse,
 }
 
@@ -30,17 +46,23 @@ pub struct AuthResponse {
 pub struct Claims {
     pub sub: Uuid, // user id
     pub email: String,
+    pub sid: Uuid, // session id
     pub exp: usize, // expiration time
     pub iat: usize, // issued at
+    pub iss: String,
+    pub aud: String,
 }
 
 impl Claims {
-    pub fn new(user_id: Uuid, email: String, exp: usize) -> Self {
+    pub fn new(user_id: Uuid, email: String, session_id: Uuid, exp: usize, iss: String, aud: String) -> Self {
         Self {
             sub: user_id,
             email,
+            sid: session_id,
             exp,
             iat: chrono::Utc::now().timestamp() as usize,
+            iss,
+            aud,
         }
     }
 }
\ No newline at end of file
@@ -1,49 +1,69 @@
-use chrono::{DateTime, Utc, NaiveDate};
+use chrono::{DateTime, Days, Months, Utc, NaiveDate};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::{FormatAmounts, Money, MoneyFormatter, validate_positive_money};
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct Budget {
     pub id: i64,
-    pub user_id: Uuid,
-    pub category: String,
+    // Whoever created a shared budget, None once they have deleted their account
+    pub user_id: Option<Uuid>,
+    // Set when the budget is shared with an organization
+    pub organization_id: Option<Uuid>,
+    // None marks an overall budget covering every expense category
+    pub category: Option<String>,
     pub target_amount: rust_decimal::Decimal,
     pub period_type: String,
     pub period_start: NaiveDate,
     pub period_end: NaiveDate,
     pub is_active: bool,
+    // Percentages of the target that trigger an alert, ascending
+    pub alert_thresholds: Vec<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
 pub struct BudgetResponse {
     pub id: i64,
-    pub category: String,
+    pub organization_id: Option<Uuid>,
+    pub category: Option<String>,
     pub target_amount: String,
+    // The target amount, filled in per request from the user's locale and currency preferences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub formatted_amount: Option<String>,
     pub period_type: String,
     pub period_start: String,
     pub period_end: String,
     pub is_active: bool,
+    pub alert_thresholds: Vec<i32>,
     pub created_at: String,
     pub updated_at: String,
 }
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateBudgetRequest {
+    // Omit or send null to create an overall spending cap
     #[validate(length(min = 1, max = 100, message = "Category must be between 1 and 100 characters"))]
-    pub category: String,
+    pub category: Option<String>,
     
-    #[validate(range(min = 0.01, message = "Target amount must be greater than 0"))]
-    pub target_amount: f64,
+    #[validate(custom(function = "validate_positive_money", message = "Target amount must be greater than 0 with at most 2 decimal places"))]
+    pub target_amount: Money,
     
     #[validate(custom(function = "validate_period_type"))]
     pub period_type: String,
     
     pub period_start: String, // YYYY-MM-DD format
     pub period_end: String,   // YYYY-MM-DD format
+
+    // Defaults to 50, 80 and 100 percent; an empty list turns alerts off
+    #[validate(custom(function = "validate_alert_thresholds"))]
+    pub alert_thresholds: Option<Vec<i32>>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -51,8 +71,8 @@ pub struct UpdateBudgetRequest {
     #[validate(length(min = 1, max = 100, message = "Category must be between 1 and 100 characters"))]
     pub category: Option<String>,
     
-    #[validate(range(min = 0.01, message = "Target amount must be greater than 0"))]
-    pub target_amount: Option<f64>,
+    #[validate(custom(function = "validate_positive_money", message = "Target amount must be greater than 0 with at most 2 decimal places"))]
+    pub target_amount: Option<Money>,
     
     #[validate(custom(function = "validate_period_type"))]
     pub period_type: Option<String>,
@@ -60,18 +80,39 @@ pub struct UpdateBudgetRequest {
     pub period_start: Option<String>, // YYYY-MM-DD format
     pub period_end: Option<String>,   // YYYY-MM-DD format
     pub is_active: Option<bool>,
+
+    #[validate(custom(function = "validate_alert_thresholds"))]
+    pub alert_thresholds: Option<Vec<i32>>,
+    // Optimistic concurrency token, the updated_at this edit is based on
+    pub version: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::InputObject), graphql(name = "BudgetFilter", rename_fields = "snake_case"))]
 pub struct ListBudgetsQuery {
+    #[validate(range(min = 1, message = "Page must be greater than 0"))]
     pub page: Option<i64>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
     pub limit: Option<i64>,
     pub category: Option<String>,
+    // Comma-separated, matches any of the listed categories exactly
+    pub categories: Option<String>,
     pub period_type: Option<String>,
     pub is_active: Option<bool>,
+    pub min_amount: Option<String>,
+    pub max_amount: Option<String>,
+    pub sort_by: Option<String>,
+    pub order: Option<String>,
+}
+
+impl ListBudgetsQuery {
+    pub fn category_list(&self) -> Vec<String> {
+        super::split_list(self.categories.as_deref())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(name = "BudgetPage", rename_fields = "snake_case"))]
 pub struct ListBudgetsResponse {
     pub data: Vec<BudgetResponse>,
     pub page: i64,
@@ -89,7 +130,7 @@ pub struct BudgetSummaryResponse {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BudgetPerformanceItem {
-    pub category: String,
+    pub category: Option<String>,
     pub target_amount: String,
     pub spent_amount: String,
     pub remaining_amount: String,
@@ -107,9 +148,26 @@ pub struct BudgetPerformanceResponse {
     pub overall_percentage: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetDetailPerformanceResponse {
+    pub budget: BudgetResponse,
+    pub spent_amount: String,
+    pub remaining_amount: String,
+    pub percentage_used: f64,
+    pub daily_burn_rate: String,
+    pub projected_spend: String,
+    pub days_elapsed: i64,
+    pub days_remaining: i64,
+    // Thresholds already alerted in the current period
+    pub alerted_thresholds: Vec<i32>,
+    pub transactions: Vec<crate::models::TransactionResponse>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BudgetSuggestionItem {
-    pub category: String,
+    // The budget to adjust, None when suggesting a new budget
+    pub budget_id: Option<i64>,
+    pub category: Option<String>,
     pub suggested_amount: String,
     pub reason: String,
     pub confidence: f64,
@@ -120,6 +178,79 @@ pub struct BudgetSuggestionsResponse {
     pub suggestions: Vec<BudgetSuggestionItem>,
 }
 
+// A finished period of a budget, recorded when the budget rolls over to the next one
+#[derive(Debug, Clone, FromRow)]
+pub struct BudgetPeriod {
+    pub id: i64,
+    pub budget_id: i64,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub target_amount: rust_decimal::Decimal,
+    pub actual_amount: rust_decimal::Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BudgetHistoryQuery {
+    // Most recent closed periods to return, default 12
+    #[validate(range(min = 1, max = 120, message = "Limit must be between 1 and 120"))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetHistoryItem {
+    pub period_start: String,
+    pub period_end: String,
+    pub target_amount: String,
+    pub actual_amount: String,
+    pub remaining_amount: String,
+    pub percentage_used: f64,
+    pub within_budget: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetHistoryResponse {
+    pub budget: BudgetResponse,
+    // Oldest first
+    pub periods: Vec<BudgetHistoryItem>,
+}
+
+// The period that follows one ending on `period_end`, the same length as the budget's period type
+pub fn next_budget_period(period_type: &str, period_end: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let start = period_end.checked_add_days(Days::new(1))?;
+    let following = match period_type {
+        "weekly" => start.checked_add_days(Days::new(7))?,
+        "monthly" => start.checked_add_months(Months::new(1))?,
+        "quarterly" => start.checked_add_months(Months::new(3))?,
+        "yearly" => start.checked_add_months(Months::new(12))?,
+        _ => return None,
+    };
+
+    Some((start, following.pred_opt()?))
+}
+
+pub const DEFAULT_BUDGET_ALERT_THRESHOLDS: [i32; 3] = [50, 80, 100];
+const MAX_BUDGET_ALERT_THRESHOLDS: usize = 5;
+const MAX_BUDGET_ALERT_THRESHOLD_PERCENT: i32 = 200;
+
+pub fn validate_alert_thresholds(thresholds: &[i32]) -> Result<(), validator::ValidationError> {
+    if thresholds.len() > MAX_BUDGET_ALERT_THRESHOLDS {
+        return Err(validator::ValidationError::new("too_many_alert_thresholds"));
+    }
+    if thresholds.iter().any(|threshold| !(1..=MAX_BUDGET_ALERT_THRESHOLD_PERCENT).contains(threshold)) {
+        return Err(validator::ValidationError::new("invalid_alert_threshold"));
+    }
+    Ok(())
+}
+
+// Sorted and without repeats, the form stored on the budget
+pub fn normalize_alert_thresholds(thresholds: Option<&[i32]>) -> Vec<i32> {
+    let mut thresholds = thresholds.map(<[i32]>::to_vec).unwrap_or_else(|| DEFAULT_BUDGET_ALERT_THRESHOLDS.to_vec());
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    thresholds
+}
+
 pub fn validate_period_type(period_type: &str) -> Result<(), validator::ValidationError> {
     match period_type {
         "weekly" | "monthly" | "quarterly" | "yearly" => Ok(()),
@@ -131,18 +262,33 @@ impl Budget {
     pub fn to_response(&self) -> BudgetResponse {
         BudgetResponse {
             id: self.id,
+            organization_id: self.organization_id,
             category: self.category.clone(),
             target_amount: self.target_amount.to_string(),
+            formatted_amount: None,
             period_type: self.period_type.clone(),
             period_start: self.period_start.format("%Y-%m-%d").to_string(),
             period_end: self.period_end.format("%Y-%m-%d").to_string(),
             is_active: self.is_active,
+            alert_thresholds: self.alert_thresholds.clone(),
             created_at: self.created_at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
             updated_at: self.updated_at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
         }
     }
 }
 
+impl FormatAmounts for BudgetResponse {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        self.formatted_amount = formatter.format_str(&self.target_amount);
+    }
+}
+
+impl FormatAmounts for ListBudgetsResponse {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        self.data.format_amounts(formatter);
+    }
+}
+
 impl From<Budget> for BudgetResponse {
     fn from(budget: Budget) -> Self {
         budget.to_response()
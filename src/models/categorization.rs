@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+// A description with a known category, from past transactions or feedback
+#[derive(Debug, Clone, FromRow)]
+pub struct LabeledExample {
+    pub description: String,
+    pub category: String,
+    pub from_feedback: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CategoryFeedback {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub description: String,
+    pub suggested_category: Option<String>,
+    pub chosen_category: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySuggestion {
+    pub category: String,
+    pub confidence: f64,
+    pub source: String, // provider name, e.g. "embedding" or "rules"
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SuggestCategoryRequest {
+    #[validate(length(min = 1, max = 500, message = "Description must be between 1 and 500 characters"))]
+    pub description: String,
+    pub transaction_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestCategoryResponse {
+    pub suggestion: Option<CategorySuggestion>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CategoryFeedbackRequest {
+    #[validate(length(min = 1, max = 500, message = "Description must be between 1 and 500 characters"))]
+    pub description: String,
+    #[validate(length(max = 100, message = "Suggested category must be at most 100 characters"))]
+    pub suggested_category: Option<String>,
+    #[validate(length(min = 1, max = 100, message = "Chosen category must be between 1 and 100 characters"))]
+    pub chosen_category: String,
+}
\ No newline at end of file
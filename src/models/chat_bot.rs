@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::CreateTransactionRequest;
+
+pub const CHAT_PROVIDER_TELEGRAM: &str = "telegram";
+pub const CHAT_PROVIDER_SLACK: &str = "slack";
+pub const CHAT_PROVIDERS: &[&str] = &[CHAT_PROVIDER_TELEGRAM, CHAT_PROVIDER_SLACK];
+
+#[derive(Debug, Clone, FromRow)]
+pub struct LinkedAccount {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub provider: String,
+    // The provider's own user ID; Slack's is prefixed with the workspace ID
+    pub external_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkedAccountResponse {
+    pub provider: String,
+    pub external_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateLinkCodeRequest {
+    #[validate(custom(function = "validate_chat_provider"))]
+    pub provider: String,
+}
+
+// Sent to the bot as "/link <code>" before it expires
+#[derive(Debug, Serialize)]
+pub struct LinkCodeResponse {
+    pub provider: String,
+    pub code: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+// A chat message reduced to what the bot needs, whichever service it came from
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub provider: &'static str,
+    pub external_id: String,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TelegramUpdate {
+    pub message: Option<TelegramMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TelegramMessage {
+    pub chat: TelegramChat,
+    pub from: Option<TelegramUser>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TelegramChat {
+    pub id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TelegramUser {
+    pub id: i64,
+}
+
+// A slash command invocation, posted as a form
+#[derive(Debug, Deserialize)]
+pub struct SlackCommand {
+    pub team_id: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub text: String,
+}
+
+// What the bot does with a message
+#[derive(Debug)]
+pub enum ChatAction {
+    Reply(String),
+    // A linked user's expense, recorded by the caller under the usual transaction rules
+    LogExpense { user_id: Uuid, request: Box<CreateTransactionRequest> },
+}
+
+impl From<LinkedAccount> for LinkedAccountResponse {
+    fn from(account: LinkedAccount) -> Self {
+        Self {
+            provider: account.provider,
+            external_id: account.external_id,
+            created_at: account.created_at,
+        }
+    }
+}
+
+fn validate_chat_provider(provider: &str) -> Result<(), validator::ValidationError> {
+    if CHAT_PROVIDERS.contains(&provider) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_chat_provider"))
+    }
+}
\ No newline at end of file
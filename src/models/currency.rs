@@ -0,0 +1,67 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+use crate::models::{JobResponse, UserResponse};
+
+// Rates are stored against USD so any two currencies can be converted through it
+pub const PIVOT_CURRENCY: &str = "USD";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExchangeRate {
+    pub currency: String,
+    pub rate_date: NaiveDate,
+    pub usd_rate: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+// A month-end pocket balance together with the currency it was recorded in
+#[derive(Debug, Clone, FromRow)]
+pub struct RestatementSnapshot {
+    pub id: i64,
+    pub snapshot_month: NaiveDate,
+    pub balance: Decimal,
+    pub currency: String,
+}
+
+// An amount entered in one currency and what it came to in another at the time
+#[derive(Debug, Clone)]
+pub struct CurrencyConversion {
+    pub original_amount: Decimal,
+    pub original_currency: String,
+    pub exchange_rate: Decimal,
+    pub converted_amount: Decimal,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateBaseCurrencyRequest {
+    #[validate(custom(function = "validate_currency_code"))]
+    pub base_currency: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BaseCurrencyChangeResponse {
+    pub user: UserResponse,
+    // Absent when the base currency was already set to the requested one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restatement_job: Option<JobResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestatementResult {
+    pub base_currency: String,
+    pub snapshots_restated: u64,
+    // Snapshots left unconverted because no rate existed on or before their month end
+    pub snapshots_missing_rate: u64,
+    pub cache_entries_invalidated: usize,
+}
+
+pub fn validate_currency_code(code: &str) -> Result<(), validator::ValidationError> {
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_currency_code"))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{Money, validate_non_negative_money, validate_positive_money};
+
+pub const DEBT_TYPE_LOAN: &str = "loan";
+pub const DEBT_TYPE_CREDIT_CARD: &str = "credit_card";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Debt {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub name: String,
+    pub debt_type: String,
+    // Outstanding balance
+    pub principal: Decimal,
+    // Annual percentage rate, e.g. 19.99
+    pub apr: Decimal,
+    pub minimum_payment: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebtResponse {
+    pub id: i64,
+    pub name: String,
+    pub debt_type: String,
+    pub principal: String,
+    pub apr: String,
+    pub minimum_payment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateDebtRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_debt_type"))]
+    pub debt_type: String,
+    #[validate(custom(function = "validate_non_negative_money", message = "Principal must be 0 or more with at most 2 decimal places"))]
+    pub principal: Money,
+    #[validate(length(min = 1, message = "APR is required"))]
+    pub apr: String,
+    #[validate(custom(function = "validate_positive_money", message = "Minimum payment must be greater than 0 with at most 2 decimal places"))]
+    pub minimum_payment: Money,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateDebtRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    #[validate(custom(function = "validate_non_negative_money", message = "Principal must be 0 or more with at most 2 decimal places"))]
+    pub principal: Option<Money>,
+    #[validate(length(min = 1, message = "APR is required"))]
+    pub apr: Option<String>,
+    #[validate(custom(function = "validate_positive_money", message = "Minimum payment must be greater than 0 with at most 2 decimal places"))]
+    pub minimum_payment: Option<Money>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PayoffPlanQuery {
+    // Paid on top of the minimum every month
+    #[validate(custom(function = "validate_non_negative_money", message = "Extra payment must be 0 or more with at most 2 decimal places"))]
+    pub extra_payment: Option<Money>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PayoffPlanResponse {
+    pub debt_id: i64,
+    pub monthly_payment: String,
+    pub months: usize,
+    pub payoff_date: Option<NaiveDate>,
+    pub total_interest: String,
+    pub total_paid: String,
+    pub schedule: Vec<PayoffScheduleEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PayoffScheduleEntry {
+    pub month: usize,
+    pub payment_date: NaiveDate,
+    pub payment: String,
+    pub principal: String,
+    pub interest: String,
+    pub remaining_balance: String,
+}
+
+pub fn validate_debt_type(debt_type: &str) -> Result<(), validator::ValidationError> {
+    match debt_type {
+        DEBT_TYPE_LOAN | DEBT_TYPE_CREDIT_CARD => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_debt_type")),
+    }
+}
+
+impl From<Debt> for DebtResponse {
+    fn from(debt: Debt) -> Self {
+        Self {
+            id: debt.id,
+            name: debt.name,
+            debt_type: debt.debt_type,
+            principal: debt.principal.to_string(),
+            apr: debt.apr.to_string(),
+            minimum_payment: debt.minimum_payment.to_string(),
+            created_at: debt.created_at,
+            updated_at: debt.updated_at,
+        }
+    }
+}
+
+impl Debt {
+    pub fn to_response(self) -> DebtResponse {
+        DebtResponse::from(self)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const PUSH_PROVIDER_FCM: &str = "fcm";
+pub const PUSH_PROVIDER_APNS: &str = "apns";
+pub const PUSH_PROVIDERS: &[&str] = &[PUSH_PROVIDER_FCM, PUSH_PROVIDER_APNS];
+
+pub const NOTIFICATION_BUDGET_ALERT: &str = "budget_alert";
+// An expense far above its category's usual amount
+pub const NOTIFICATION_LARGE_TRANSACTION: &str = "large_transaction";
+pub const NOTIFICATION_KINDS: &[&str] = &[NOTIFICATION_BUDGET_ALERT, NOTIFICATION_LARGE_TRANSACTION];
+
+#[derive(Clone)]
+pub struct Device {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub provider: String,
+    // The FCM registration token or APNs device token the app was handed, stored sealed
+    pub token: String,
+    pub name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_registered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceResponse {
+    pub id: i64,
+    pub provider: String,
+    pub name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_registered_at: DateTime<Utc>,
+}
+
+// Apps register on every launch, since either service may rotate the token
+#[derive(Debug, Deserialize, Validate)]
+pub struct RegisterDeviceRequest {
+    #[validate(custom(function = "validate_push_provider"))]
+    pub provider: String,
+    #[validate(length(min = 1, max = 4096, message = "Token must be between 1 and 4096 characters"))]
+    pub token: String,
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, FromRow)]
+pub struct NotificationPreference {
+    #[validate(custom(function = "validate_notification_kind"))]
+    pub kind: String,
+    pub push: bool,
+}
+
+// Kinds left out keep their current setting
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateNotificationPreferencesRequest {
+    #[validate(length(min = 1, message = "At least one preference is required"))]
+    #[validate(nested)]
+    pub preferences: Vec<NotificationPreference>,
+}
+
+// A notification as the phone shows it, whichever service delivers it
+#[derive(Debug, Clone, Serialize)]
+pub struct PushMessage {
+    pub kind: &'static str,
+    pub title: String,
+    pub body: String,
+    // Lets the app open the budget or transaction the notification is about
+    pub resource_id: String,
+}
+
+// What a large-transaction notification says about it. The amount is in the transaction's pocket
+// currency, or the owner's base currency outside a pocket.
+#[derive(Debug, Clone, FromRow)]
+pub struct PushTransactionSummary {
+    pub description: String,
+    pub amount: Decimal,
+    pub currency: String,
+    // The owner hides balances, so the notification leaves the amount out
+    pub hide_balance: bool,
+}
+
+impl From<Device> for DeviceResponse {
+    fn from(device: Device) -> Self {
+        Self {
+            id: device.id,
+            provider: device.provider,
+            name: device.name,
+            created_at: device.created_at,
+            last_registered_at: device.last_registered_at,
+        }
+    }
+}
+
+fn validate_push_provider(provider: &str) -> Result<(), validator::ValidationError> {
+    if PUSH_PROVIDERS.contains(&provider) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_push_provider"))
+    }
+}
+
+fn validate_notification_kind(kind: &str) -> Result<(), validator::ValidationError> {
+    if NOTIFICATION_KINDS.contains(&kind) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_notification_kind"))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// An opted-in user still owed the digest for a given month
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestRecipient {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub base_currency: String,
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestCategory {
+    pub category: String,
+    pub amount: Decimal,
+}
+
+// Budget standing as of the end of the digest month
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestBudget {
+    pub category: Option<String>,
+    pub target_amount: Decimal,
+    pub spent_amount: Decimal,
+    pub period_end: NaiveDate,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonthlyDigest {
+    pub month: NaiveDate,
+    pub income: Decimal,
+    pub expenses: Decimal,
+    pub top_categories: Vec<DigestCategory>,
+    pub budgets: Vec<DigestBudget>,
+}
\ No newline at end of file
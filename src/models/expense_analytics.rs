@@ -79,4 +79,42 @@ impl Default for RecentTransactionsQuery {
     fn default() -> Self {
         Self { limit: Some(10) }
     }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct HeatmapQuery {
+    // Defaults to the current year
+    #[validate(range(min = 1970, max = 9999, message = "Year must be between 1970 and 9999"))]
+    pub year: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeatmapDay {
+    pub date: chrono::NaiveDate,
+    pub total_amount: Decimal,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeatmapWeekday {
+    pub weekday: String,
+    pub total_amount: Decimal,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeatmapHour {
+    pub hour: u32,
+    pub total_amount: Decimal,
+    pub transaction_count: i64,
+}
+
+// Days with no spending are left out; weekdays run Monday to Sunday and hours 0-23 (UTC),
+// both always complete
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeatmapResponse {
+    pub year: i32,
+    pub days: Vec<HeatmapDay>,
+    pub weekdays: Vec<HeatmapWeekday>,
+    pub hours: Vec<HeatmapHour>,
 }
\ No newline at end of file
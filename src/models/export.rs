@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ExportTransactionsQuery {
+    pub format: Option<String>,
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ExportFile {
+    pub filename: String,
+    pub content_type: &'static str,
+    pub content: String,
+}
\ No newline at end of file
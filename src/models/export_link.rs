@@ -0,0 +1,90 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const EXPORT_LINK_RESOURCE_SUMMARY: &str = "summary";
+pub const EXPORT_LINK_RESOURCE_TRANSACTIONS: &str = "transactions";
+pub const EXPORT_LINK_RESOURCE_REPORT: &str = "report";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ExportLink {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub label: Option<String>,
+    pub token_hash: String,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportLinkResponse {
+    pub id: Uuid,
+    pub label: Option<String>,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateExportLinkRequest {
+    #[validate(length(min = 1, max = 100, message = "Label must be between 1 and 100 characters"))]
+    pub label: Option<String>,
+    // YYYY-MM-DD, both ends inclusive
+    pub from_date: String,
+    pub to_date: String,
+    // Unlike pocket share links these always expire
+    #[validate(range(min = 1, max = 90, message = "Expiry must be between 1 and 90 days"))]
+    pub expires_in_days: i64,
+}
+
+// Like CreatedShareTokenResponse, the one export link response that carries the raw token
+#[derive(Debug, Serialize)]
+pub struct CreatedExportLinkResponse {
+    pub token: String,
+    pub export_link: ExportLinkResponse,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ExportLinkAccess {
+    pub id: i64,
+    pub resource: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub accessed_at: DateTime<Utc>,
+}
+
+// What the recipient sees when opening the link
+#[derive(Debug, Serialize)]
+pub struct SharedExportSummary {
+    pub label: Option<String>,
+    pub owner_name: String,
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub expires_at: DateTime<Utc>,
+    // YYYY-MM months whose statements fall entirely inside the range
+    pub report_months: Vec<String>,
+}
+
+impl From<ExportLink> for ExportLinkResponse {
+    fn from(link: ExportLink) -> Self {
+        Self {
+            id: link.id,
+            label: link.label,
+            from_date: link.from_date,
+            to_date: link.to_date,
+            created_at: link.created_at,
+            expires_at: link.expires_at,
+            last_used_at: link.last_used_at,
+            revoked_at: link.revoked_at,
+        }
+    }
+}
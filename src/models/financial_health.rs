@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub const HEALTH_RATING_EXCELLENT: &str = "excellent";
+pub const HEALTH_RATING_GOOD: &str = "good";
+pub const HEALTH_RATING_FAIR: &str = "fair";
+pub const HEALTH_RATING_POOR: &str = "poor";
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct FinancialHealthQuery {
+    // Trailing months of income and spending to measure, default 3
+    #[validate(range(min = 1, max = 24, message = "Months must be between 1 and 24"))]
+    pub months: Option<u32>,
+}
+
+// One scored component. Value and score are null when there is nothing to measure,
+// e.g. no income in the period or no active budgets.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthMetric {
+    pub value: Option<String>,
+    pub score: Option<u32>,
+    // The value at which the component scores 100
+    pub target: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FinancialHealthResponse {
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    // Average of the component scores that could be computed, 0-100
+    pub score: Option<u32>,
+    pub rating: Option<String>,
+    pub total_income: String,
+    pub total_expenses: String,
+    pub liquid_balance: String,
+    pub savings_rate: HealthMetric,
+    pub expense_to_income_ratio: HealthMetric,
+    pub budget_adherence: HealthMetric,
+    pub emergency_fund_months: HealthMetric,
+}
\ No newline at end of file
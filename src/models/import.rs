@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::importers::ImportLocale;
+use crate::models::JobResponse;
+
+// Locale settings left out are auto-detected from the uploaded file
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportTransactionsQuery {
+    pub account_id: Option<Uuid>,
+    pub delimiter: Option<String>,
+    pub decimal_separator: Option<String>,
+    pub thousands_separator: Option<String>,
+    pub date_format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportTransactionsResponse {
+    pub imported: usize,
+    // Rows already committed by an earlier, interrupted upload of the same file
+    pub resumed_from: usize,
+    pub delimiter: String,
+    pub locale: ImportLocale,
+    pub reaggregation_job: Option<JobResponse>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ImportCheckpoint {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub fingerprint: String,
+    pub total_rows: i32,
+    pub rows_committed: i32,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::CreateTransactionRequest;
+
+pub const INBOUND_EMAIL_CREATED: &str = "created";
+pub const INBOUND_EMAIL_DUPLICATE: &str = "duplicate";
+pub const INBOUND_EMAIL_REJECTED: &str = "rejected";
+
+// Metadata key tying a transaction to the message it came from. Transactions carrying it wait
+// for their owner instead of being posted when their date arrives.
+pub const EMAIL_MESSAGE_ID_KEY: &str = "email_message_id";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct InboundEmailAddress {
+    pub user_id: Uuid,
+    pub local_part: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct InboundEmailOwner {
+    pub user_id: Uuid,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InboundAddressResponse {
+    pub address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// A received message reduced to what ingestion needs, whichever provider delivered it
+#[derive(Debug, Clone)]
+pub struct InboundEmail {
+    pub message_id: String,
+    pub recipients: Vec<String>,
+    pub from: String,
+    pub subject: String,
+    pub text: String,
+    // The provider saw DMARC pass, or a DKIM signature aligned with the From domain
+    pub authenticated: bool,
+}
+
+// Rejections are answered with 200 too, since providers retry anything else
+#[derive(Debug, Serialize)]
+pub struct InboundEmailOutcome {
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip)]
+    pub user_id: Option<Uuid>,
+}
+
+impl InboundEmailOutcome {
+    pub fn rejected(reason: impl Into<String>) -> Self {
+        Self {
+            status: INBOUND_EMAIL_REJECTED,
+            transaction_id: None,
+            reason: Some(reason.into()),
+            user_id: None,
+        }
+    }
+}
+
+// What ingesting a message leaves to the caller
+#[derive(Debug)]
+pub enum InboundEmailAction {
+    Done(InboundEmailOutcome),
+    // A pending expense for the owner to review, recorded by the caller under the usual transaction rules
+    RecordExpense { user_id: Uuid, request: Box<CreateTransactionRequest> },
+}
+
+// Mailgun's route forward, posted as a form
+#[derive(Debug, Deserialize)]
+pub struct MailgunInboundEmail {
+    pub recipient: String,
+    pub from: String,
+    #[serde(default)]
+    pub subject: String,
+    #[serde(rename = "body-plain", default)]
+    pub body_plain: String,
+    #[serde(rename = "Message-Id")]
+    pub message_id: Option<String>,
+    // JSON list of [name, value] pairs
+    #[serde(rename = "message-headers")]
+    pub message_headers: Option<String>,
+    pub timestamp: String,
+    pub token: String,
+    pub signature: String,
+}
+
+// The SNS envelope SES receipt notifications arrive in
+#[derive(Debug, Deserialize)]
+pub struct SnsMessage {
+    #[serde(rename = "Type")]
+    pub message_type: String,
+    #[serde(rename = "Message")]
+    pub message: String,
+    #[serde(rename = "SubscribeURL")]
+    pub subscribe_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SesNotification {
+    pub notification_type: String,
+    pub mail: SesMail,
+    pub receipt: SesReceipt,
+    // The raw MIME message, present when the receipt rule's SNS action carries content
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SesMail {
+    pub message_id: String,
+    pub common_headers: SesCommonHeaders,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SesCommonHeaders {
+    #[serde(default)]
+    pub from: Vec<String>,
+    pub subject: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SesReceipt {
+    pub recipients: Vec<String>,
+    pub spf_verdict: SesVerdict,
+    pub dkim_verdict: SesVerdict,
+    // Missing when the receipt rule predates DMARC evaluation
+    pub dmarc_verdict: Option<SesVerdict>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SesVerdict {
+    pub status: String,
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const JOB_TYPE_REAGGREGATE: &str = "reaggregate";
+pub const JOB_TYPE_CURRENCY_RESTATEMENT: &str = "currency_restatement";
+
+pub const JOB_STATUS_QUEUED: &str = "queued";
+pub const JOB_STATUS_RUNNING: &str = "running";
+pub const JOB_STATUS_COMPLETED: &str = "completed";
+pub const JOB_STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Job {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub job_type: String,
+    pub status: String,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub processed_items: i32,
+    pub total_items: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobResponse {
+    pub id: Uuid,
+    pub job_type: String,
+    pub status: String,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub processed_items: i32,
+    pub total_items: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReaggregationResult {
+    pub snapshots_refreshed: u64,
+    pub cache_entries_invalidated: usize,
+}
+
+impl From<Job> for JobResponse {
+    fn from(job: Job) -> Self {
+        Self {
+            id: job.id,
+            job_type: job.job_type,
+            status: job.status,
+            result: job.result,
+            error: job.error,
+            processed_items: job.processed_items,
+            total_items: job.total_items,
+            created_at: job.created_at,
+            started_at: job.started_at,
+            completed_at: job.completed_at,
+        }
+    }
+}
+
+impl Job {
+    pub fn to_response(self) -> JobResponse {
+        JobResponse::from(self)
+    }
+}
\ No newline at end of file
@@ -6,6 +6,38 @@ pub mod budget;
 pub mod account_summary;
 pub mod expense_analytics;
 pub mod income_analytics;
+pub mod spending_limit;
+pub mod export;
+pub mod session;
+pub mod status;
+pub mod categorization;
+pub mod pocket_import;
+pub mod job;
+pub mod import;
+pub mod inbound_email;
+pub mod chat_bot;
+pub mod device;
+pub mod pocket_adjustment;
+pub mod currency;
+pub mod audit;
+pub mod digest;
+pub mod share_token;
+pub mod report;
+pub mod reminder;
+pub mod refund;
+pub mod preference;
+pub mod export_link;
+pub mod analytics_feed;
+pub mod task;
+pub mod email_template;
+pub mod organization;
+pub mod debt;
+pub mod subscription;
+pub mod anomaly;
+pub mod financial_health;
+pub mod money;
+pub mod monthly_aggregate;
+pub mod precondition;
 
 pub use user::*;
 pub use auth::*;
@@ -14,4 +46,36 @@ pub use transaction::*;
 pub use budget::*;
 pub use account_summary::*;
 pub use expense_analytics::*;
-pub use income_analytics::*;
\ No newline at end of file
+pub use income_analytics::*;
+pub use spending_limit::*;
+pub use export::*;
+pub use session::*;
+pub use status::*;
+pub use categorization::*;
+pub use pocket_import::*;
+pub use job::*;
+pub use import::*;
+pub use inbound_email::*;
+pub use chat_bot::*;
+pub use device::*;
+pub use pocket_adjustment::*;
+pub use currency::*;
+pub use audit::*;
+pub use digest::*;
+pub use share_token::*;
+pub use report::*;
+pub use reminder::*;
+pub use refund::*;
+pub use preference::*;
+pub use export_link::*;
+pub use analytics_feed::*;
+pub use task::*;
+pub use email_template::*;
+pub use organization::*;
+pub use debt::*;
+pub use subscription::*;
+pub use anomaly::*;
+pub use financial_health::*;
+pub use money::*;
+pub use monthly_aggregate::*;
+pub use precondition::*;
\ No newline at end of file
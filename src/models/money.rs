@@ -0,0 +1,262 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::utils::{AppError, codes};
+
+// Amount columns are DECIMAL(_, 2), so no currency is stored finer than cents
+pub const MONEY_SCALE: u32 = 2;
+
+// Every Money parse error starts with this; ValidatedJson reports it as INVALID_AMOUNT
+pub const INVALID_MONEY: &str = "invalid money amount";
+
+// ISO 4217 currencies without a minor unit
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "UYI", "VND", "VUV", "XAF", "XOF", "XPF",
+];
+
+// A monetary amount taken from a request. Decimal strings such as "12.50" are the canonical
+// form; plain JSON numbers are still accepted but read from their decimal text, never
+// through a binary float conversion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(Decimal);
+
+impl Money {
+    pub fn new(amount: Decimal) -> Self {
+        Self(amount)
+    }
+
+    pub fn amount(self) -> Decimal {
+        self.0
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > Decimal::ZERO
+    }
+
+    // Decimal places actually used, so "12.50" counts as one
+    pub fn scale(self) -> u32 {
+        self.0.normalize().scale()
+    }
+
+    // The amount if it fits the currency's minor unit, so a JPY amount can't carry cents
+    pub fn for_currency(self, currency: &str) -> Result<Decimal, AppError> {
+        let scale = currency_scale(currency);
+        if self.scale() > scale {
+            return Err(AppError::ValidationError(format!(
+                "{} amounts allow at most {} decimal places",
+                currency, scale
+            ))
+            .with_code(codes::INVALID_AMOUNT));
+        }
+        Ok(self.0)
+    }
+}
+
+// Decimal places a currency's amounts may carry, capped by what the columns store
+pub fn currency_scale(currency: &str) -> u32 {
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency) { 0 } else { MONEY_SCALE }
+}
+
+// How a locale writes amounts: separators and where the currency symbol goes
+#[derive(Debug, Clone, Copy)]
+struct NumberStyle {
+    decimal: char,
+    group: char,
+    symbol_first: bool,
+    // A space between symbol and number even when the symbol isn't a letter code
+    spaced: bool,
+}
+
+// Only the language part decides; regional variants of these languages agree on the rules
+fn number_style(locale: &str) -> NumberStyle {
+    let language = locale.split('-').next().unwrap_or_default().to_ascii_lowercase();
+    match language.as_str() {
+        "id" | "nl" | "pt" => NumberStyle { decimal: ',', group: '.', symbol_first: true, spaced: true },
+        "de" | "es" | "it" | "tr" => NumberStyle { decimal: ',', group: '.', symbol_first: false, spaced: true },
+        "fr" => NumberStyle { decimal: ',', group: ' ', symbol_first: false, spaced: true },
+        _ => NumberStyle { decimal: '.', group: ',', symbol_first: true, spaced: false },
+    }
+}
+
+fn currency_symbol(currency: &str) -> &str {
+    match currency {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "CNY" => "CN¥",
+        "IDR" => "Rp",
+        "INR" => "₹",
+        "KRW" => "₩",
+        "SGD" => "S$",
+        "AUD" => "A$",
+        "CAD" => "CA$",
+        "MYR" => "RM",
+        "THB" => "฿",
+        "PHP" => "₱",
+        "VND" => "₫",
+        "BRL" => "R$",
+        other => other,
+    }
+}
+
+// Renders amounts the way a user's locale writes them, e.g. "Rp 1.250.000,00" for id-ID
+// or "$1,250.00" for en-US, rounded to the currency's minor unit
+#[derive(Debug, Clone)]
+pub struct MoneyFormatter {
+    currency: String,
+    style: NumberStyle,
+}
+
+impl MoneyFormatter {
+    pub fn new(locale: &str, currency: &str) -> Self {
+        Self {
+            currency: currency.to_string(),
+            style: number_style(locale),
+        }
+    }
+
+    // The same locale rules applied to another currency, e.g. a pocket's own
+    pub fn in_currency(&self, currency: &str) -> Self {
+        Self {
+            currency: currency.to_string(),
+            style: self.style,
+        }
+    }
+
+    pub fn format(&self, amount: Decimal) -> String {
+        let scale = currency_scale(&self.currency) as usize;
+        let rounded = amount.abs().round_dp_with_strategy(scale as u32, RoundingStrategy::MidpointAwayFromZero);
+        let digits = format!("{:.*}", scale, rounded);
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits.as_str(), ""));
+
+        let mut number = String::with_capacity(digits.len() + whole.len() / 3);
+        for (index, digit) in whole.chars().enumerate() {
+            if index > 0 && (whole.len() - index).is_multiple_of(3) {
+                number.push(self.style.group);
+            }
+            number.push(digit);
+        }
+        if !fraction.is_empty() {
+            number.push(self.style.decimal);
+            number.push_str(fraction);
+        }
+
+        let symbol = currency_symbol(&self.currency);
+        let gap = if self.style.spaced || symbol.ends_with(|c: char| c.is_ascii_alphabetic()) { " " } else { "" };
+        let sign = if rounded.is_zero() || !amount.is_sign_negative() { "" } else { "-" };
+
+        if self.style.symbol_first {
+            format!("{}{}{}{}", sign, symbol, gap, number)
+        } else {
+            format!("{}{}{}{}", sign, number, gap, symbol)
+        }
+    }
+
+    // Response amounts are decimal strings; anything that doesn't parse stays unformatted
+    pub fn format_str(&self, amount: &str) -> Option<String> {
+        Decimal::from_str(amount).ok().map(|amount| self.format(amount))
+    }
+}
+
+// Responses that can carry locale-formatted copies of their amounts. Formatting happens per
+// request, after caching, so a preference change shows up without invalidating anything.
+pub trait FormatAmounts {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter);
+}
+
+impl<T: FormatAmounts> FormatAmounts for Vec<T> {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        for item in self.iter_mut() {
+            item.format_amounts(formatter);
+        }
+    }
+}
+
+impl FromStr for Money {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() || !trimmed.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+')) {
+            return Err(format!("{} '{}'", INVALID_MONEY, value));
+        }
+
+        Decimal::from_str(trimmed).map(Self).map_err(|_| format!("{} '{}'", INVALID_MONEY, value))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(amount: Decimal) -> Self {
+        Self(amount)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+struct MoneyVisitor;
+
+impl de::Visitor<'_> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a decimal amount such as \"12.50\"")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Money, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Money, E> {
+        Ok(Money(Decimal::from(value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Money, E> {
+        Ok(Money(Decimal::from(value)))
+    }
+
+    // The shortest text that round-trips the float, so 19.99 stays 19.99
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Money, E> {
+        self.visit_str(&value.to_string())
+    }
+}
+
+pub fn validate_money_scale(money: &Money) -> Result<(), validator::ValidationError> {
+    if money.scale() <= MONEY_SCALE {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("too_many_decimals"))
+    }
+}
+
+pub fn validate_positive_money(money: &Money) -> Result<(), validator::ValidationError> {
+    if !money.is_positive() {
+        return Err(validator::ValidationError::new("not_positive"));
+    }
+    validate_money_scale(money)
+}
+
+pub fn validate_non_negative_money(money: &Money) -> Result<(), validator::ValidationError> {
+    if money.amount() < Decimal::ZERO {
+        return Err(validator::ValidationError::new("negative"));
+    }
+    validate_money_scale(money)
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+// Posted income or expense in one category for one calendar month, refunds netted
+#[derive(Debug, Clone, FromRow)]
+pub struct MonthlyCategoryTotal {
+    // First day of the month
+    pub month: NaiveDate,
+    pub category: String,
+    pub total_amount: Decimal,
+    pub transaction_count: i64,
+}
\ No newline at end of file
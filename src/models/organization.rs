@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const ORG_ROLE_OWNER: &str = "owner";
+pub const ORG_ROLE_ADMIN: &str = "admin";
+pub const ORG_ROLE_MEMBER: &str = "member";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// A membership joined with the member's profile
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OrganizationMember {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OrganizationMember {
+    pub fn has_role(&self, required: &str) -> bool {
+        role_rank(&self.role) >= role_rank(required)
+    }
+}
+
+// Owners manage the organization itself, admins manage members and shared records,
+// members read shared records and record transactions in shared pockets
+pub fn role_rank(role: &str) -> u8 {
+    match role {
+        ORG_ROLE_OWNER => 3,
+        ORG_ROLE_ADMIN => 2,
+        ORG_ROLE_MEMBER => 1,
+        _ => 0,
+    }
+}
+
+pub fn validate_org_role(role: &str) -> Result<(), validator::ValidationError> {
+    match role {
+        ORG_ROLE_OWNER | ORG_ROLE_ADMIN | ORG_ROLE_MEMBER => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_role")),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationResponse {
+    pub id: Uuid,
+    pub name: String,
+    // The caller's role in this organization
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Organization {
+    pub fn to_response(self, role: &str) -> OrganizationResponse {
+        OrganizationResponse {
+            id: self.id,
+            name: self.name,
+            role: role.to_string(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateOrganizationRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateOrganizationRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AddOrganizationMemberRequest {
+    // The account must already exist
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    #[validate(custom(function = "validate_org_role"))]
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateOrganizationMemberRequest {
+    #[validate(custom(function = "validate_org_role"))]
+    pub role: String,
+}
\ No newline at end of file
@@ -5,23 +5,78 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::validate_currency_code;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Pocket {
     pub id: Uuid,
-    pub user_id: Uuid,
+    // Whoever created a shared pocket, None once they have deleted their account
+    pub user_id: Option<Uuid>,
+    // Set when the pocket is shared with an organization
+    pub organization_id: Option<Uuid>,
     pub name: String,
     pub emoji: String,
     pub balance: Decimal,
+    pub archived: bool,
+    pub sort_order: i32,
+    #[sqlx(rename = "pocket_group")]
+    pub group: Option<String>,
+    pub currency: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Just enough for widgets that poll balances
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PocketQuickBalance {
+    pub id: Uuid,
+    pub name: String,
+    pub balance: Decimal,
+}
+
+// A pocket whose stored balance drifted from its posted transactions
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PocketBalanceCorrection {
+    pub pocket_id: Uuid,
+    // Creator of a shared pocket, None once their account is purged
+    pub user_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub name: String,
+    pub previous_balance: Decimal,
+    pub balance: Decimal,
+    // Stored minus replayed; positive means the pocket showed more than it holds
+    pub drift: Decimal,
+}
+
+impl PocketBalanceCorrection {
+    // The user whose cached views and live feed show the pocket; shared pockets are in neither
+    pub fn owner(&self) -> Option<Uuid> {
+        match self.organization_id {
+            Some(_) => None,
+            None => self.user_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceRepairReport {
+    pub dry_run: bool,
+    pub pockets_checked: usize,
+    pub corrections: Vec<PocketBalanceCorrection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
 pub struct PocketResponse {
     pub id: Uuid,
+    pub organization_id: Option<Uuid>,
     pub name: String,
     pub emoji: String,
     pub balance: Decimal,
+    pub archived: bool,
+    pub sort_order: i32,
+    pub group: Option<String>,
+    pub currency: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -32,6 +87,11 @@ pub struct CreatePocketRequest {
     pub name: String,
     #[validate(length(min = 1, max = 10, message = "Emoji must be between 1 and 10 characters"))]
     pub emoji: String,
+    #[validate(length(max = 50, message = "Group must be at most 50 characters"))]
+    pub group: Option<String>,
+    // Defaults to the owner's base currency
+    #[validate(custom(function = "validate_currency_code"))]
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -40,15 +100,59 @@ pub struct UpdatePocketRequest {
     pub name: Option<String>,
     #[validate(length(min = 1, max = 10, message = "Emoji must be between 1 and 10 characters"))]
     pub emoji: Option<String>,
+    // An empty string removes the pocket from its group
+    #[validate(length(max = 50, message = "Group must be at most 50 characters"))]
+    pub group: Option<String>,
+    // updated_at as last read, checked like If-Unmodified-Since
+    pub version: Option<DateTime<Utc>>,
+}
+
+// The complete pocket layout, first item shown first
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReorderPocketsRequest {
+    #[validate(length(min = 1, message = "At least one pocket is required"))]
+    #[validate(nested)]
+    pub pockets: Vec<ReorderPocketItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReorderPocketItem {
+    pub id: Uuid,
+    #[validate(length(max = 50, message = "Group must be at most 50 characters"))]
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListPocketsQuery {
+    pub include_archived: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RepairBalancesQuery {
+    // Limits the repair to one user's pockets; every personal pocket otherwise
+    pub user_id: Option<Uuid>,
+    // Reports drift without correcting it
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeletePocketQuery {
+    // Moves the pocket's transactions here instead of refusing the delete
+    pub reassign_to: Option<Uuid>,
 }
 
 impl From<Pocket> for PocketResponse {
     fn from(pocket: Pocket) -> Self {
         Self {
             id: pocket.id,
+            organization_id: pocket.organization_id,
             name: pocket.name,
             emoji: pocket.emoji,
             balance: pocket.balance,
+            archived: pocket.archived,
+            sort_order: pocket.sort_order,
+            group: pocket.group,
+            currency: pocket.currency,
             created_at: pocket.created_at,
             updated_at: pocket.updated_at,
         }
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::models::{Money, PocketResponse, TransactionResponse, validate_money_scale};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePocketAdjustmentRequest {
+    // "set" makes `amount` the new balance, "delta" adds it to the current one
+    #[validate(custom(function = "validate_adjustment_mode"))]
+    pub mode: String,
+    #[validate(custom(function = "validate_money_scale", message = "Amount must have at most 2 decimal places"))]
+    pub amount: Money,
+    #[validate(length(min = 1, max = 500, message = "Reason must be between 1 and 500 characters"))]
+    pub reason: String,
+    pub adjustment_date: Option<String>, // YYYY-MM-DD, defaults to today
+}
+
+#[derive(Debug, Serialize)]
+pub struct PocketAdjustmentResponse {
+    pub pocket: PocketResponse,
+    pub adjustment: TransactionResponse,
+}
+
+fn validate_adjustment_mode(mode: &str) -> Result<(), validator::ValidationError> {
+    match mode {
+        "set" | "delta" => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_adjustment_mode")),
+    }
+}
\ No newline at end of file
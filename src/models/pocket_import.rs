@@ -0,0 +1,77 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{JobResponse, PocketResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PocketBalanceSnapshot {
+    pub id: i64,
+    pub pocket_id: Uuid,
+    pub user_id: Uuid,
+    pub snapshot_month: NaiveDate,
+    pub balance: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PocketBalanceSnapshotResponse {
+    pub month: String,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportPocketsRequest {
+    #[validate(length(min = 1, max = 50, message = "Between 1 and 50 pockets can be imported at once"))]
+    #[validate(nested)]
+    pub pockets: Vec<ImportPocketItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ImportPocketItem {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    #[validate(length(min = 1, max = 10, message = "Emoji must be between 1 and 10 characters"))]
+    pub emoji: String,
+    #[validate(length(min = 1, message = "Opening balance is required"))]
+    pub opening_balance: String,
+    // YYYY-MM-DD, defaults to today
+    pub opening_date: Option<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub history: Vec<HistoricalBalance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct HistoricalBalance {
+    // YYYY-MM
+    #[validate(length(equal = 7, message = "Month must use the YYYY-MM format"))]
+    pub month: String,
+    #[validate(length(min = 1, message = "Balance is required"))]
+    pub balance: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedPocket {
+    pub pocket: PocketResponse,
+    pub adjustment_transaction_id: Option<i64>,
+    pub snapshots: Vec<PocketBalanceSnapshotResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportPocketsResponse {
+    pub pockets: Vec<ImportedPocket>,
+    pub reaggregation_job: Option<JobResponse>,
+}
+
+impl From<PocketBalanceSnapshot> for PocketBalanceSnapshotResponse {
+    fn from(snapshot: PocketBalanceSnapshot) -> Self {
+        Self {
+            month: snapshot.snapshot_month.format("%Y-%m").to_string(),
+            balance: snapshot.balance,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use chrono::{DateTime, SubsecRound, Utc};
+
+use crate::utils::{AppError, codes};
+
+// What the client last saw of a record it is updating, so an edit made from another device in
+// the meantime is reported as a conflict instead of being silently overwritten
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Precondition {
+    // The record's updated_at exactly as a response returned it
+    pub version: Option<DateTime<Utc>>,
+    // From If-Unmodified-Since, which only carries whole seconds
+    pub unmodified_since: Option<DateTime<Utc>>,
+}
+
+// The If-Unmodified-Since header; a value that is not an HTTP date is ignored as RFC 9110 asks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IfUnmodifiedSince(pub Option<DateTime<Utc>>);
+
+impl Precondition {
+    pub fn new(version: Option<DateTime<Utc>>, IfUnmodifiedSince(unmodified_since): IfUnmodifiedSince) -> Self {
+        Self { version, unmodified_since }
+    }
+
+    pub fn check(&self, updated_at: DateTime<Utc>) -> Result<(), AppError> {
+        let stale_version = self.version.is_some_and(|version| version != updated_at);
+        let modified = self.unmodified_since.is_some_and(|since| updated_at.trunc_subsecs(0) > since);
+
+        if stale_version || modified {
+            return Err(Self::conflict());
+        }
+        Ok(())
+    }
+
+    // The updated_at a guarded write must still find once check has passed on it; None leaves the
+    // write unconditional
+    pub fn expected(&self, updated_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (self.version.is_some() || self.unmodified_since.is_some()).then_some(updated_at)
+    }
+
+    pub fn conflict() -> AppError {
+        AppError::Conflict("The record was changed since you loaded it; reload it and apply your edit again".to_string())
+            .with_code(codes::STALE_VERSION)
+    }
+}
\ No newline at end of file
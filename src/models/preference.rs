@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+use crate::models::{MoneyFormatter, validate_currency_code};
+
+pub const DEFAULT_LOCALE: &str = "en-US";
+pub const DEFAULT_WEEK_START: &str = "monday";
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+// The currency falls back to the user's base currency until preferences are saved
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UserPreferences {
+    pub currency: String,
+    pub locale: String,
+    pub week_start: String,
+    pub timezone: String,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl UserPreferences {
+    pub fn money_formatter(&self) -> MoneyFormatter {
+        MoneyFormatter::new(&self.locale, &self.currency)
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePreferencesRequest {
+    #[validate(custom(function = "validate_currency_code"))]
+    pub currency: String,
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: String,
+    #[validate(custom(function = "validate_week_start"))]
+    pub week_start: String,
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: String,
+}
+
+// Language with an optional region, e.g. "id" or "en-US"
+fn validate_locale(locale: &str) -> Result<(), validator::ValidationError> {
+    let mut parts = locale.split('-');
+    let language = parts.next().unwrap_or_default();
+    let region = parts.next();
+
+    let language_ok = (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+    let region_ok = match region {
+        None => true,
+        Some(region) => {
+            (region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()))
+                || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit()))
+        }
+    };
+
+    if language_ok && region_ok && parts.next().is_none() {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_locale"))
+    }
+}
+
+fn validate_week_start(week_start: &str) -> Result<(), validator::ValidationError> {
+    match week_start {
+        "monday" | "sunday" | "saturday" => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_week_start")),
+    }
+}
+
+// IANA zone names such as "Asia/Jakarta"
+fn validate_timezone(timezone: &str) -> Result<(), validator::ValidationError> {
+    match timezone.parse::<chrono_tz::Tz>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(validator::ValidationError::new("invalid_timezone")),
+    }
+}
\ No newline at end of file
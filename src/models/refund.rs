@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{Money, Transaction, validate_money_scale};
+
+pub const REFUND_STATUS_REQUESTED: &str = "requested";
+pub const REFUND_STATUS_RECEIVED: &str = "received";
+pub const REFUND_STATUS_WRITTEN_OFF: &str = "written_off";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Refund {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub original_transaction_id: i64,
+    // Income transaction carrying the money back, set once received
+    pub refund_transaction_id: Option<i64>,
+    pub requested_amount: Decimal,
+    pub received_amount: Option<Decimal>,
+    pub status: String,
+    pub note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RefundResponse {
+    pub id: i64,
+    pub original_transaction_id: i64,
+    pub refund_transaction_id: Option<i64>,
+    pub requested_amount: String,
+    pub received_amount: Option<String>,
+    pub status: String,
+    pub note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateRefundRequest {
+    // Defaults to whatever part of the expense isn't already claimed
+    #[validate(custom(function = "validate_money_scale", message = "Amount must have at most 2 decimal places"))]
+    pub amount: Option<Money>,
+    #[validate(length(max = 500, message = "Note must be at most 500 characters"))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReceiveRefundRequest {
+    // The income transaction the refund arrived as
+    #[validate(range(min = 1, message = "Transaction id must be greater than 0"))]
+    pub transaction_id: i64,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListRefundsQuery {
+    pub status: Option<String>,
+}
+
+// A received refund as analytics see it: money returned against an earlier expense
+#[derive(Debug, Clone, FromRow)]
+pub struct RefundLink {
+    pub original_transaction_id: i64,
+    pub refund_transaction_id: i64,
+    pub amount: Decimal,
+}
+
+impl From<Refund> for RefundResponse {
+    fn from(refund: Refund) -> Self {
+        Self {
+            id: refund.id,
+            original_transaction_id: refund.original_transaction_id,
+            refund_transaction_id: refund.refund_transaction_id,
+            requested_amount: refund.requested_amount.to_string(),
+            received_amount: refund.received_amount.map(|amount| amount.to_string()),
+            status: refund.status,
+            note: refund.note,
+            resolved_at: refund.resolved_at,
+            created_at: refund.created_at,
+            updated_at: refund.updated_at,
+        }
+    }
+}
+
+// Shrinks each refunded expense by what came back and drops the refund credits themselves,
+// so the money is netted in the original expense's category and period rather than counted as income
+pub fn net_refunds(transactions: Vec<Transaction>, refunds: &[RefundLink]) -> Vec<Transaction> {
+    if refunds.is_empty() {
+        return transactions;
+    }
+
+    let refund_ids: HashSet<i64> = refunds.iter().map(|refund| refund.refund_transaction_id).collect();
+    let mut refunded: HashMap<i64, Decimal> = HashMap::new();
+    for refund in refunds {
+        *refunded.entry(refund.original_transaction_id).or_insert(Decimal::ZERO) += refund.amount;
+    }
+
+    transactions
+        .into_iter()
+        .filter(|transaction| !refund_ids.contains(&transaction.id))
+        .map(|mut transaction| {
+            if let Some(amount) = refunded.get(&transaction.id) {
+                let remaining = (transaction.amount.abs() - amount).max(Decimal::ZERO);
+                transaction.amount = if transaction.amount.is_sign_negative() { -remaining } else { remaining };
+            }
+            transaction
+        })
+        .collect()
+}
\ No newline at end of file
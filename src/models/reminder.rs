@@ -0,0 +1,99 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const REMINDER_STATUS_OPEN: &str = "open";
+pub const REMINDER_STATUS_DONE: &str = "done";
+pub const REMINDER_STATUS_DISMISSED: &str = "dismissed";
+
+// Always loaded together with the transaction it follows up on
+#[derive(Debug, Clone, FromRow)]
+pub struct Reminder {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub transaction_id: i64,
+    pub note: String,
+    pub remind_on: NaiveDate,
+    pub status: String,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub transaction_description: String,
+    pub transaction_amount: Decimal,
+    pub transaction_date: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReminderResponse {
+    pub id: i64,
+    pub transaction_id: i64,
+    pub note: String,
+    pub remind_on: NaiveDate,
+    pub status: String,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub transaction_description: String,
+    pub transaction_amount: String,
+    pub transaction_date: NaiveDate,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateReminderRequest {
+    #[validate(length(min = 1, max = 500, message = "Note must be between 1 and 500 characters"))]
+    pub note: String,
+    pub remind_on: String, // YYYY-MM-DD format
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateReminderRequest {
+    #[validate(length(min = 1, max = 500, message = "Note must be between 1 and 500 characters"))]
+    pub note: Option<String>,
+    // Moving the date of an already notified reminder notifies again on the new date
+    pub remind_on: Option<String>, // YYYY-MM-DD format
+    #[validate(custom(function = "validate_reminder_status"))]
+    pub status: Option<String>,
+}
+
+// A due reminder along with where to send its notification
+#[derive(Debug, Clone, FromRow)]
+pub struct ReminderNotification {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub user_email: String,
+    pub user_locale: Option<String>,
+    pub note: String,
+    pub remind_on: NaiveDate,
+    pub transaction_description: String,
+    pub transaction_amount: Decimal,
+    pub transaction_date: NaiveDate,
+}
+
+pub fn validate_reminder_status(status: &str) -> Result<(), validator::ValidationError> {
+    match status {
+        REMINDER_STATUS_OPEN | REMINDER_STATUS_DONE | REMINDER_STATUS_DISMISSED => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_reminder_status")),
+    }
+}
+
+impl From<Reminder> for ReminderResponse {
+    fn from(reminder: Reminder) -> Self {
+        Self {
+            id: reminder.id,
+            transaction_id: reminder.transaction_id,
+            note: reminder.note,
+            remind_on: reminder.remind_on,
+            status: reminder.status,
+            notified_at: reminder.notified_at,
+            created_at: reminder.created_at,
+            updated_at: reminder.updated_at,
+            transaction_description: reminder.transaction_description,
+            transaction_amount: reminder.transaction_amount.to_string(),
+            transaction_date: reminder.transaction_date,
+        }
+    }
+}
\ No newline at end of file
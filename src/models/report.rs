@@ -0,0 +1,64 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use validator::Validate;
+
+use crate::models::TransactionResponse;
+use crate::utils::AppError;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MonthlyReportQuery {
+    // YYYY-MM
+    pub month: String,
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Pdf,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pdf" => Ok(ReportFormat::Pdf),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(AppError::ValidationError("Format must be one of 'pdf' or 'json'".to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportCategory {
+    pub category: String,
+    pub transaction_type: String,
+    pub amount: Decimal,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonthlyReport {
+    pub month: NaiveDate,
+    pub period_end: NaiveDate,
+    pub owner_name: String,
+    pub currency: String,
+    pub income: Decimal,
+    pub expenses: Decimal,
+    // Net of manual balance corrections made during the month
+    pub adjustments: Decimal,
+    pub net: Decimal,
+    pub categories: Vec<ReportCategory>,
+    pub transactions: Vec<TransactionResponse>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ReportFile {
+    pub filename: String,
+    pub content_type: &'static str,
+    pub content: Vec<u8>,
+}
\ No newline at end of file
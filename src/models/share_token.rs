@@ -0,0 +1,77 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PocketShareToken {
+    pub id: Uuid,
+    pub pocket_id: Uuid,
+    pub user_id: Uuid,
+    pub label: Option<String>,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShareTokenResponse {
+    pub id: Uuid,
+    pub pocket_id: Uuid,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateShareTokenRequest {
+    #[validate(length(min = 1, max = 100, message = "Label must be between 1 and 100 characters"))]
+    pub label: Option<String>,
+    // Links never expire when left out
+    #[validate(range(min = 1, max = 365, message = "Expiry must be between 1 and 365 days"))]
+    pub expires_in_days: Option<i64>,
+}
+
+// The raw token is only ever returned here, at creation time
+#[derive(Debug, Serialize)]
+pub struct CreatedShareTokenResponse {
+    pub token: String,
+    pub share_token: ShareTokenResponse,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SharedBalancePoint {
+    pub month: NaiveDate,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedPocketSummary {
+    pub name: String,
+    pub emoji: String,
+    pub balance: Decimal,
+    pub currency: String,
+    // Month-end balances, oldest first
+    pub trend: Vec<SharedBalancePoint>,
+    pub as_of: DateTime<Utc>,
+}
+
+impl From<PocketShareToken> for ShareTokenResponse {
+    fn from(token: PocketShareToken) -> Self {
+        Self {
+            id: token.id,
+            pocket_id: token.pocket_id,
+            label: token.label,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+        }
+    }
+}
\ No newline at end of file
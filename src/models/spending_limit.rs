@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{Money, validate_positive_money};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SpendingLimit {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub category: String,
+    pub monthly_limit: Decimal,
+    pub enforcement: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpendingLimitResponse {
+    pub id: i64,
+    pub category: String,
+    pub monthly_limit: String,
+    pub enforcement: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSpendingLimitRequest {
+    #[validate(length(min = 1, max = 100, message = "Category must be between 1 and 100 characters"))]
+    pub category: String,
+    #[validate(custom(function = "validate_positive_money", message = "Monthly limit must be greater than 0 with at most 2 decimal places"))]
+    pub monthly_limit: Money,
+    #[validate(custom(function = "validate_enforcement"))]
+    pub enforcement: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateSpendingLimitRequest {
+    #[validate(custom(function = "validate_positive_money", message = "Monthly limit must be greater than 0 with at most 2 decimal places"))]
+    pub monthly_limit: Option<Money>,
+    #[validate(custom(function = "validate_enforcement"))]
+    pub enforcement: Option<String>,
+}
+
+// Outcome of checking a new expense against the category's monthly limit
+#[derive(Debug)]
+pub enum SpendingLimitCheck {
+    WithinLimit,
+    SoftExceeded(String),
+    HardExceeded(String),
+}
+
+pub fn validate_enforcement(enforcement: &str) -> Result<(), validator::ValidationError> {
+    match enforcement {
+        "hard" | "soft" => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_enforcement")),
+    }
+}
+
+impl From<SpendingLimit> for SpendingLimitResponse {
+    fn from(limit: SpendingLimit) -> Self {
+        Self {
+            id: limit.id,
+            category: limit.category,
+            monthly_limit: limit.monthly_limit.to_string(),
+            enforcement: limit.enforcement,
+            created_at: limit.created_at,
+            updated_at: limit.updated_at,
+        }
+    }
+}
+
+impl SpendingLimit {
+    pub fn to_response(self) -> SpendingLimitResponse {
+        SpendingLimitResponse::from(self)
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.enforcement == "hard"
+    }
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub healthy: bool,
+    pub notice: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencySnapshot {
+    pub dependencies: Vec<DependencyStatus>,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusResponse {
+    pub status: String, // "operational" or "degraded"
+    pub version: String,
+    pub started_at: DateTime<Utc>,
+    pub uptime_seconds: i64,
+    pub checked_at: DateTime<Utc>,
+    pub dependencies: Vec<DependencyStatus>,
+    pub notices: Vec<String>,
+}
+
+// One dependency as seen by the readiness probe
+#[derive(Debug, Serialize)]
+pub struct ProbeCheck {
+    pub name: String,
+    pub status: String, // "up", "down" or "disabled"
+    // Only a required dependency being down marks the instance not ready
+    pub required: bool,
+    pub latency_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: String, // "ready" or "not_ready"
+    pub checks: Vec<ProbeCheck>,
+    pub checked_at: DateTime<Utc>,
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+pub const CADENCE_WEEKLY: &str = "weekly";
+pub const CADENCE_MONTHLY: &str = "monthly";
+pub const CADENCE_QUARTERLY: &str = "quarterly";
+pub const CADENCE_YEARLY: &str = "yearly";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionsResponse {
+    pub subscriptions: Vec<SubscriptionItem>,
+    pub total_monthly_cost: String,
+    pub total_yearly_cost: String,
+}
+
+// A merchant charged at a steady cadence and a steady amount
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionItem {
+    pub merchant: String,
+    pub category: Option<String>,
+    pub cadence: String,
+    // The most recent charge
+    pub amount: String,
+    pub monthly_cost: String,
+    pub charge_count: usize,
+    pub first_charge_date: NaiveDate,
+    pub last_charge_date: NaiveDate,
+    pub next_expected_charge: NaiveDate,
+    // Charged over the last 12 months and the 12 months before that
+    pub yearly_total: String,
+    pub previous_yearly_total: String,
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const TASK_PENDING_TRANSACTIONS: &str = "pending_transactions";
+pub const TASK_REMINDER_NOTIFICATIONS: &str = "reminder_notifications";
+pub const TASK_MONTHLY_DIGEST: &str = "monthly_digest";
+pub const TASK_ACCOUNT_PURGE: &str = "account_purge";
+pub const TASK_BUDGET_ROLLOVER: &str = "budget_rollover";
+pub const TASK_BUDGET_ALERTS: &str = "budget_alerts";
+pub const TASK_TRANSACTION_ARCHIVE: &str = "transaction_archive";
+
+pub const TASK_TRIGGER_SCHEDULE: &str = "schedule";
+pub const TASK_TRIGGER_MANUAL: &str = "manual";
+
+pub const TASK_RUN_STATUS_RUNNING: &str = "running";
+pub const TASK_RUN_STATUS_SUCCEEDED: &str = "succeeded";
+pub const TASK_RUN_STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskRun {
+    pub id: Uuid,
+    pub task_name: String,
+    pub trigger: String,
+    pub status: String,
+    pub items_processed: Option<i32>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskSummary {
+    pub name: String,
+    // Human-readable, e.g. "every 900s" or "cron 0 8 1 * *"
+    pub schedule: String,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run: Option<TaskRun>,
+    // Most recent run that did not fail, so a streak of failures is easy to spot
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ListTaskRunsQuery {
+    pub limit: Option<i64>,
+}
\ No newline at end of file
@@ -5,6 +5,14 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::{FormatAmounts, Money, MoneyFormatter, validate_currency_code, validate_money_scale, EMAIL_MESSAGE_ID_KEY};
+
+pub const TRANSACTION_STATUS_PENDING: &str = "pending";
+pub const TRANSACTION_STATUS_POSTED: &str = "posted";
+pub const TRANSACTION_STATUS_CANCELLED: &str = "cancelled";
+
+const MAX_METADATA_BYTES: usize = 4096;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Transaction {
     pub id: i64,
@@ -15,20 +23,39 @@ pub struct Transaction {
     pub category: Option<String>,
     pub transaction_type: String,
     pub transaction_date: NaiveDate,
+    pub status: String,
+    pub notes: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    // Set when the amount was entered in another currency and converted into the pocket's
+    pub original_amount: Option<Decimal>,
+    pub original_currency: Option<String>,
+    // Pocket currency units per original currency unit on the transaction date
+    pub exchange_rate: Option<Decimal>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(complex, rename_fields = "snake_case"))]
 pub struct TransactionResponse {
     pub id: i64,
     pub user_id: Uuid,
     pub account_id: Option<Uuid>,
     pub description: String,
     pub amount: String,
+    // Filled in per request from the user's locale and currency preferences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "graphql", graphql(skip))]
+    pub formatted_amount: Option<String>,
     pub category: Option<String>,
     pub transaction_type: String,
     pub transaction_date: NaiveDate,
+    pub status: String,
+    pub notes: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    pub original_amount: Option<String>,
+    pub original_currency: Option<String>,
+    pub exchange_rate: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -38,13 +65,25 @@ pub struct CreateTransactionRequest {
     pub account_id: Option<Uuid>,
     #[validate(length(min = 1, max = 500, message = "Description must be between 1 and 500 characters"))]
     pub description: String,
-    #[validate(length(min = 1, message = "Amount is required"))]
-    pub amount: String,
+    #[validate(custom(function = "validate_money_scale", message = "Amount must have at most 2 decimal places"))]
+    pub amount: Money,
     #[validate(length(min = 1, max = 100, message = "Category must be between 1 and 100 characters"))]
     pub category: String,
     #[validate(custom(function = "validate_transaction_type"))]
     pub transaction_type: String,
     pub transaction_date: String,
+    #[serde(default)]
+    pub override_limit: bool,
+    // Only allowed for future dates; the transaction leaves balances untouched until it posts
+    #[serde(default)]
+    pub pending: bool,
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+    #[validate(custom(function = "validate_metadata"))]
+    pub metadata: Option<serde_json::Value>,
+    // Currency the amount is given in, converted into the pocket's at the transaction date's rate
+    #[validate(custom(function = "validate_currency_code"))]
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -52,26 +91,70 @@ pub struct UpdateTransactionRequest {
     pub account_id: Option<Uuid>,
     #[validate(length(min = 1, max = 500, message = "Description must be between 1 and 500 characters"))]
     pub description: String,
-    #[validate(length(min = 1, message = "Amount is required"))]
-    pub amount: String,
+    #[validate(custom(function = "validate_money_scale", message = "Amount must have at most 2 decimal places"))]
+    pub amount: Money,
     #[validate(length(min = 1, max = 100, message = "Category must be between 1 and 100 characters"))]
     pub category: String,
     #[validate(custom(function = "validate_transaction_type"))]
     pub transaction_type: String,
     pub transaction_date: String,
+    // Left out to keep the current value; an empty string or object clears it
+    #[validate(length(max = 2000, message = "Notes must be at most 2000 characters"))]
+    pub notes: Option<String>,
+    #[validate(custom(function = "validate_metadata"))]
+    pub metadata: Option<serde_json::Value>,
+    // The updated_at last read; the update fails with 409 if the record has changed since
+    pub version: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Validate)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::InputObject), graphql(name = "TransactionFilter", rename_fields = "snake_case"))]
 pub struct ListTransactionsQuery {
+    #[validate(range(min = 1, message = "Page must be greater than 0"))]
     pub page: Option<i32>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
     pub limit: Option<i32>,
     pub category: Option<String>,
+    // Comma-separated, matches any of the listed categories
+    pub categories: Option<String>,
     pub from_date: Option<String>,
     pub to_date: Option<String>,
     pub transaction_type: Option<String>,
+    pub account_id: Option<Uuid>,
+    pub min_amount: Option<String>,
+    pub max_amount: Option<String>,
+    pub sort_by: Option<String>,
+    pub order: Option<String>,
+    pub status: Option<String>,
+}
+
+impl CreateTransactionRequest {
+    // Emailed receipts wait for their owner rather than their date, so they may be pending on any day
+    pub fn awaits_review(&self) -> bool {
+        self.metadata.as_ref().is_some_and(|metadata| metadata.get(EMAIL_MESSAGE_ID_KEY).is_some())
+    }
+}
+
+impl ListTransactionsQuery {
+    pub fn category_list(&self) -> Vec<String> {
+        split_list(self.categories.as_deref())
+    }
+}
+
+pub fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|value| {
+            value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(name = "TransactionPage", rename_fields = "snake_case"))]
 pub struct ListTransactionsResponse {
     pub data: Vec<TransactionResponse>,
     pub page: i32,
@@ -79,6 +162,18 @@ pub struct ListTransactionsResponse {
     pub total_items: i64,
 }
 
+impl FormatAmounts for TransactionResponse {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        self.formatted_amount = formatter.format_str(&self.amount);
+    }
+}
+
+impl FormatAmounts for ListTransactionsResponse {
+    fn format_amounts(&mut self, formatter: &MoneyFormatter) {
+        self.data.format_amounts(formatter);
+    }
+}
+
 fn validate_transaction_type(transaction_type: &str) -> Result<(), validator::ValidationError> {
     if transaction_type == "income" || transaction_type == "expense" {
         Ok(())
@@ -87,6 +182,18 @@ fn validate_transaction_type(transaction_type: &str) -> Result<(), validator::Va
     }
 }
 
+// Metadata is a small JSON object of integration-defined keys, such as external IDs
+fn validate_metadata(metadata: &serde_json::Value) -> Result<(), validator::ValidationError> {
+    if !metadata.is_object() {
+        return Err(validator::ValidationError::new("metadata_must_be_object"));
+    }
+    if metadata.to_string().len() > MAX_METADATA_BYTES {
+        return Err(validator::ValidationError::new("metadata_too_large"));
+    }
+
+    Ok(())
+}
+
 impl From<Transaction> for TransactionResponse {
     fn from(transaction: Transaction) -> Self {
         Self {
@@ -95,9 +202,16 @@ impl From<Transaction> for TransactionResponse {
             account_id: transaction.account_id,
             description: transaction.description,
             amount: transaction.amount.to_string(),
+            formatted_amount: None,
             category: transaction.category,
             transaction_type: transaction.transaction_type,
             transaction_date: transaction.transaction_date,
+            status: transaction.status,
+            notes: transaction.notes,
+            metadata: transaction.metadata,
+            original_amount: transaction.original_amount.map(|amount| amount.to_string()),
+            original_currency: transaction.original_currency,
+            exchange_rate: transaction.exchange_rate.map(|rate| rate.normalize().to_string()),
             created_at: transaction.created_at,
             updated_at: transaction.updated_at,
         }
@@ -108,4 +222,23 @@ impl Transaction {
     pub fn to_response(self) -> TransactionResponse {
         TransactionResponse::from(self)
     }
+
+    // Amounts are stored unsigned, the sign comes from the transaction type
+    pub fn signed_amount(&self) -> Decimal {
+        match self.transaction_type.as_str() {
+            "expense" => -self.amount.abs(),
+            // Adjustments carry their own sign
+            "adjustment" => self.amount,
+            _ => self.amount.abs(),
+        }
+    }
+
+    // Pending and cancelled transactions don't touch the pocket balance
+    pub fn balance_effect(&self) -> Decimal {
+        if self.status == TRANSACTION_STATUS_POSTED {
+            self.signed_amount()
+        } else {
+            Decimal::ZERO
+        }
+    }
 }
\ No newline at end of file
@@ -11,20 +11,46 @@ pub struct User {
     pub email: String,
     pub password: String,
     pub hide_balance: bool,
+    pub base_currency: String,
+    pub monthly_digest: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject), graphql(rename_fields = "snake_case"))]
 pub struct UserResponse {
     pub id: Uuid,
     pub name: String,
     pub email: String,
     pub hide_balance: bool,
+    pub base_currency: String,
+    pub monthly_digest: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct ListUsersQuery {
+    #[validate(range(min = 1, message = "Page must be greater than 0"))]
+    pub page: Option<i64>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: Option<i64>,
+    // Case-insensitive substring matches
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub sort_by: Option<String>,
+    pub order: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListUsersResponse {
+    pub data: Vec<UserResponse>,
+    pub page: i64,
+    pub limit: i64,
+    pub total_items: i64,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct UpdateUserNameRequest {
     #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
@@ -36,6 +62,69 @@ pub struct UpdateHideBalanceRequest {
     pub hide_balance: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UpdateMonthlyDigestRequest {
+    pub monthly_digest: bool,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+    // Checked against the password policy by the service that sets it
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangeEmailRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub new_email: String,
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct VerifyEmailChangeRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountDeletionResponse {
+    pub deletion_scheduled_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserDataExport {
+    pub exported_at: DateTime<Utc>,
+    pub user: UserResponse,
+    pub pockets: Vec<super::PocketResponse>,
+    pub transactions: Vec<super::TransactionResponse>,
+    pub budgets: Vec<super::BudgetResponse>,
+    pub spending_limits: Vec<super::SpendingLimitResponse>,
+    pub debts: Vec<super::DebtResponse>,
+    pub refunds: Vec<super::RefundResponse>,
+    pub reminders: Vec<super::ReminderResponse>,
+    pub preferences: Option<super::UserPreferences>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PendingEmailChange {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub new_email: String,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
 impl From<User> for UserResponse {
     fn from(user: User) -> Self {
         Self {
@@ -43,6 +132,8 @@ impl From<User> for UserResponse {
             name: user.name,
             email: user.email,
             hide_balance: user.hide_balance,
+            base_currency: user.base_currency,
+            monthly_digest: user.monthly_digest,
             created_at: user.created_at,
             updated_at: user.updated_at,
         }
@@ -0,0 +1,97 @@
+use uuid::Uuid;
+
+use crate::models::{role_rank, Budget, Debt, Job, Pocket, Session, SpendingLimit, Transaction, ORG_ROLE_ADMIN, ORG_ROLE_MEMBER};
+
+// Repositories only load the caller's own rows (`find_by_id_for_user`), and every record is
+// checked against `permits` before use, so one missed filter can't expose someone else's data.
+// Either way the answer is 404, so callers never learn whether another user's record exists
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Read,
+    Write,
+    // Adding transactions to a pocket
+    Record,
+}
+
+// A record that belongs to a single user
+pub trait Owned {
+    // None for a shared record whose creator has since deleted their account
+    fn owner_id(&self) -> Option<Uuid>;
+
+    // Shared records are governed by organization roles, not by who created them
+    fn organization_id(&self) -> Option<Uuid> {
+        None
+    }
+}
+
+pub fn permits<R: Owned>(user_id: Uuid, action: Action, resource: &R) -> bool {
+    permits_as(user_id, None, action, resource)
+}
+
+// `role` is the caller's role in the record's organization, None when they aren't a member
+pub fn permits_as<R: Owned>(user_id: Uuid, role: Option<&str>, action: Action, resource: &R) -> bool {
+    if resource.organization_id().is_some() {
+        // Members see shared records and record their spending in shared pockets, admins change them
+        let required = match action {
+            Action::Read | Action::Record => ORG_ROLE_MEMBER,
+            Action::Write => ORG_ROLE_ADMIN,
+        };
+        return role.is_some_and(|role| role_rank(role) >= role_rank(required));
+    }
+
+    match action {
+        // Pockets shared through share tokens are read without a user, so they never reach here
+        Action::Read | Action::Write | Action::Record => resource.owner_id() == Some(user_id),
+    }
+}
+
+impl Owned for Pocket {
+    fn owner_id(&self) -> Option<Uuid> {
+        self.user_id
+    }
+
+    fn organization_id(&self) -> Option<Uuid> {
+        self.organization_id
+    }
+}
+
+impl Owned for Transaction {
+    fn owner_id(&self) -> Option<Uuid> {
+        Some(self.user_id)
+    }
+}
+
+impl Owned for Budget {
+    fn owner_id(&self) -> Option<Uuid> {
+        self.user_id
+    }
+
+    fn organization_id(&self) -> Option<Uuid> {
+        self.organization_id
+    }
+}
+
+impl Owned for SpendingLimit {
+    fn owner_id(&self) -> Option<Uuid> {
+        Some(self.user_id)
+    }
+}
+
+impl Owned for Job {
+    fn owner_id(&self) -> Option<Uuid> {
+        Some(self.user_id)
+    }
+}
+
+impl Owned for Session {
+    fn owner_id(&self) -> Option<Uuid> {
+        Some(self.user_id)
+    }
+}
+
+impl Owned for Debt {
+    fn owner_id(&self) -> Option<Uuid> {
+        Some(self.user_id)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,76 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::AnalyticsFeed;
+use crate::repositories::hashed_token::HashedTokenTable;
+use crate::utils::AppError;
+
+const ANALYTICS_FEEDS: HashedTokenTable = HashedTokenTable {
+    table: "analytics_feeds",
+    columns: "id, user_id, feed_type, label, token_hash, created_at, last_used_at, revoked_at",
+    scope: &["user_id"],
+    expires: false,
+    not_found: "Analytics feed not found",
+};
+
+#[async_trait::async_trait]
+pub trait AnalyticsFeedRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, feed_type: &str, label: Option<&str>, token_hash: &str) -> Result<AnalyticsFeed, AppError>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<AnalyticsFeed>, AppError>;
+    async fn count_active(&self, user_id: Uuid) -> Result<i64, AppError>;
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<AnalyticsFeed, AppError>;
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<AnalyticsFeed>, AppError>;
+    async fn touch(&self, id: Uuid) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresAnalyticsFeedRepository {
+    pool: PgPool,
+}
+
+impl PostgresAnalyticsFeedRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsFeedRepository for PostgresAnalyticsFeedRepository {
+    async fn create(&self, user_id: Uuid, feed_type: &str, label: Option<&str>, token_hash: &str) -> Result<AnalyticsFeed, AppError> {
+        let feed = sqlx::query_as!(
+            AnalyticsFeed,
+            r#"INSERT INTO analytics_feeds (id, user_id, feed_type, label, token_hash)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, user_id, feed_type, label, token_hash, created_at AS "created_at!", last_used_at, revoked_at"#,
+            Uuid::new_v4(),
+            user_id,
+            feed_type,
+            label,
+            token_hash
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(feed)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<AnalyticsFeed>, AppError> {
+        ANALYTICS_FEEDS.find_by_scope(&self.pool, &[user_id]).await
+    }
+
+    async fn count_active(&self, user_id: Uuid) -> Result<i64, AppError> {
+        ANALYTICS_FEEDS.count_active(&self.pool, &[user_id]).await
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<AnalyticsFeed, AppError> {
+        ANALYTICS_FEEDS.revoke(&self.pool, id, &[user_id]).await
+    }
+
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<AnalyticsFeed>, AppError> {
+        ANALYTICS_FEEDS.find_active_by_hash(&self.pool, token_hash).await
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AppError> {
+        ANALYTICS_FEEDS.touch(&self.pool, id).await
+    }
+}
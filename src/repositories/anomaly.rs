@@ -0,0 +1,99 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::SpendingAnomaly;
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait AnomalyRepository: Clone + Send + Sync {
+    async fn find_in_range(&self, user_id: Uuid, history_from: NaiveDate, from_date: NaiveDate, to_date: NaiveDate, threshold: Decimal, min_samples: i64) -> Result<Vec<SpendingAnomaly>, AppError>;
+    async fn check_transaction(&self, user_id: Uuid, transaction_id: i64, history_from: NaiveDate, threshold: Decimal, min_samples: i64) -> Result<Option<SpendingAnomaly>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresAnomalyRepository {
+    pool: PgPool,
+}
+
+impl PostgresAnomalyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl AnomalyRepository for PostgresAnomalyRepository {
+    // Expenses from `from_date` on are scored against the same category between `history_from`
+    // and `from_date`, so a burst of large charges can't raise its own baseline
+    async fn find_in_range(&self, user_id: Uuid, history_from: NaiveDate, from_date: NaiveDate, to_date: NaiveDate, threshold: Decimal, min_samples: i64) -> Result<Vec<SpendingAnomaly>, AppError> {
+        let anomalies = sqlx::query_as!(
+            SpendingAnomaly,
+            r#"WITH history AS (
+                 SELECT category, AVG(ABS(amount)) AS mean, STDDEV_SAMP(ABS(amount)) AS stddev, COUNT(*) AS samples
+                 FROM all_transactions
+                 WHERE user_id = $1 AND transaction_type = 'expense' AND status = 'posted' AND category IS NOT NULL
+                     AND transaction_date >= $2 AND transaction_date < $3
+                 GROUP BY category
+             ),
+             scored AS (
+                 SELECT t.id, t.description, t.category, ABS(t.amount) AS amount, t.transaction_date,
+                        h.mean, h.stddev, h.samples, (ABS(t.amount) - h.mean) / h.stddev AS z_score
+                 FROM all_transactions t
+                 JOIN history h ON h.category = t.category
+                 WHERE t.user_id = $1 AND t.transaction_type = 'expense' AND t.status = 'posted' AND t.transaction_date >= $3 AND t.transaction_date <= $4
+                     AND h.samples >= $6 AND h.stddev > 0
+             )
+             SELECT id AS "transaction_id!", description AS "description!", category AS "category!", amount AS "amount!",
+                    transaction_date AS "transaction_date!",
+                    ROUND(mean, 2) AS "category_mean!", ROUND(stddev, 2) AS "category_stddev!",
+                    samples AS "sample_size!", ROUND(z_score, 2) AS "z_score!"
+             FROM scored
+             WHERE z_score >= $5
+             ORDER BY ROUND(z_score, 2) DESC, transaction_date DESC"#,
+            user_id,
+            history_from,
+            from_date,
+            to_date,
+            threshold,
+            min_samples
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(anomalies)
+    }
+
+    // Scores a single expense against the category's earlier expenses
+    async fn check_transaction(&self, user_id: Uuid, transaction_id: i64, history_from: NaiveDate, threshold: Decimal, min_samples: i64) -> Result<Option<SpendingAnomaly>, AppError> {
+        let anomaly = sqlx::query_as!(
+            SpendingAnomaly,
+            r#"WITH target AS (
+                 SELECT id, description, category, ABS(amount) AS amount, transaction_date
+                 FROM transactions
+                 WHERE id = $2 AND user_id = $1 AND transaction_type = 'expense' AND status = 'posted' AND category IS NOT NULL
+             ),
+             history AS (
+                 SELECT AVG(ABS(t.amount)) AS mean, STDDEV_SAMP(ABS(t.amount)) AS stddev, COUNT(*) AS samples
+                 FROM all_transactions t, target
+                 WHERE t.user_id = $1 AND t.transaction_type = 'expense' AND t.status = 'posted' AND t.category = target.category AND t.id <> target.id
+                     AND t.transaction_date >= $3 AND t.transaction_date <= target.transaction_date
+             )
+             SELECT target.id AS transaction_id, target.description, target.category, target.amount AS "amount!", target.transaction_date,
+                    ROUND(h.mean, 2) AS "category_mean!", ROUND(h.stddev, 2) AS "category_stddev!",
+                    h.samples AS "sample_size!", ROUND((target.amount - h.mean) / h.stddev, 2) AS "z_score!"
+             FROM target, history h
+             WHERE h.samples >= $5 AND h.stddev > 0 AND (target.amount - h.mean) / h.stddev >= $4"#,
+            user_id,
+            transaction_id,
+            history_from,
+            threshold,
+            min_samples
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(anomaly)
+    }
+}
\ No newline at end of file
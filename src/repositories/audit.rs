@@ -0,0 +1,196 @@
+use chrono::{Days, NaiveDate};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::models::{AuditLogEntry, AuditLogQuery};
+use crate::repositories::{Counted, Page, ReadPreference, read_pool};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait AuditRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, entity_type: &str, entity_id: &str, action: &str, summary: &str, details: Option<serde_json::Value>) -> Result<(), AppError>;
+    async fn find_by_user_id(&self, user_id: Uuid, query: &AuditLogQuery, limit: i64, offset: i64, read: ReadPreference) -> Result<Vec<AuditLogEntry>, AppError>;
+    // The requested page and the filtered total from a single query
+    async fn find_page_by_user_id(&self, user_id: Uuid, query: &AuditLogQuery, limit: i64, offset: i64, read: ReadPreference) -> Result<Page<AuditLogEntry>, AppError>;
+    async fn count_by_user_id(&self, user_id: Uuid, query: &AuditLogQuery, read: ReadPreference) -> Result<i64, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresAuditRepository {
+    pool: PgPool,
+    replica: Option<PgPool>,
+}
+
+impl PostgresAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, replica: None }
+    }
+
+    pub fn with_replica(mut self, replica: Option<PgPool>) -> Self {
+        self.replica = replica;
+        self
+    }
+
+    fn reader(&self, read: ReadPreference) -> &PgPool {
+        read_pool(&self.pool, self.replica.as_ref(), read)
+    }
+
+    // Appends the WHERE clause for a search, binding every value through the builder
+    fn push_filters(builder: &mut QueryBuilder<'static, Postgres>, user_id: Uuid, filters: AuditFilters) {
+        builder.push(" WHERE user_id = ").push_bind(user_id);
+
+        if let Some(entity_type) = filters.entity_type {
+            builder.push(" AND entity_type = ").push_bind(entity_type);
+        }
+
+        if let Some(entity_id) = filters.entity_id {
+            builder.push(" AND entity_id = ").push_bind(entity_id);
+        }
+
+        if let Some(action) = filters.action {
+            builder.push(" AND action = ").push_bind(action);
+        }
+
+        if let Some(from_date) = filters.from_date {
+            builder.push(" AND created_at >= ").push_bind(from_date);
+        }
+
+        // The end date is inclusive, so compare against the start of the following day
+        if let Some(to_date) = filters.to_date {
+            builder.push(" AND created_at < ").push_bind(to_date);
+        }
+
+        if let Some(pattern) = filters.text_pattern {
+            builder
+                .push(" AND (summary ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR details::text ILIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+    }
+}
+
+// Typed form of AuditLogQuery, parsed once before building SQL
+struct AuditFilters {
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+    action: Option<String>,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    text_pattern: Option<String>,
+}
+
+impl AuditFilters {
+    fn from_query(query: &AuditLogQuery) -> Result<Self, AppError> {
+        let parse_date = |value: &Option<String>, field: &str| {
+            value
+                .as_deref()
+                .map(|value| {
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                        AppError::ValidationError(format!("Invalid {} format. Use YYYY-MM-DD", field))
+                    })
+                })
+                .transpose()
+        };
+
+        let to_date = parse_date(&query.to_date, "to_date")?
+            .map(|date| date.checked_add_days(Days::new(1)).unwrap_or(date));
+
+        // LIKE wildcards in the search text are matched literally
+        let text_pattern = query
+            .q
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .map(|q| {
+                let escaped = q.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+                format!("%{}%", escaped)
+            });
+
+        Ok(Self {
+            entity_type: query.entity_type.clone(),
+            entity_id: query.entity_id.clone(),
+            action: query.action.clone(),
+            from_date: parse_date(&query.from_date, "from_date")?,
+            to_date,
+            text_pattern,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for PostgresAuditRepository {
+    async fn create(&self, user_id: Uuid, entity_type: &str, entity_id: &str, action: &str, summary: &str, details: Option<serde_json::Value>) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO audit_log (user_id, entity_type, entity_id, action, summary, details)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            user_id,
+            entity_type,
+            entity_id,
+            action,
+            summary,
+            details
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid, query: &AuditLogQuery, limit: i64, offset: i64, read: ReadPreference) -> Result<Vec<AuditLogEntry>, AppError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT id, user_id, entity_type, entity_id, action, summary, details, created_at
+             FROM audit_log"
+        );
+        Self::push_filters(&mut builder, user_id, AuditFilters::from_query(query)?);
+        builder
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let entries = builder
+            .build_query_as::<AuditLogEntry>()
+            .fetch_all(self.reader(read))
+            .await?;
+
+        Ok(entries)
+    }
+
+    async fn find_page_by_user_id(&self, user_id: Uuid, query: &AuditLogQuery, limit: i64, offset: i64, read: ReadPreference) -> Result<Page<AuditLogEntry>, AppError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT id, user_id, entity_type, entity_id, action, summary, details, created_at,
+                    COUNT(*) OVER() AS total_items
+             FROM audit_log"
+        );
+        Self::push_filters(&mut builder, user_id, AuditFilters::from_query(query)?);
+        builder
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = builder
+            .build_query_as::<Counted<AuditLogEntry>>()
+            .fetch_all(self.reader(read))
+            .await?;
+
+        match Page::from_counted(rows, offset) {
+            Some(page) => Ok(page),
+            None => Ok(Page::empty(self.count_by_user_id(user_id, query, read).await?)),
+        }
+    }
+
+    async fn count_by_user_id(&self, user_id: Uuid, query: &AuditLogQuery, read: ReadPreference) -> Result<i64, AppError> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM audit_log");
+        Self::push_filters(&mut builder, user_id, AuditFilters::from_query(query)?);
+
+        let count = builder
+            .build_query_scalar::<i64>()
+            .fetch_one(self.reader(read))
+            .await?;
+
+        Ok(count)
+    }
+}
\ No newline at end of file
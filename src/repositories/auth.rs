@@ -1,13 +1,16 @@
-use sqlx::{PgPool, Row};
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::models::{User, RegisterRequest};
-use crate::utils::AppError;
+use crate::utils::{AppError, codes};
 
 #[async_trait::async_trait]
 pub trait AuthRepository: Clone + Send + Sync {
     async fn create_user(&self, request: &RegisterRequest, hashed_password: String) -> Result<User, AppError>;
     async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
+    async fn find_user_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
+    async fn find_locale(&self, user_id: Uuid) -> Result<Option<String>, AppError>;
+    async fn update_password_hash(&self, id: Uuid, hashed_password: &str) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -27,47 +30,38 @@ impl AuthRepository for PostgresAuthRepository {
         let user_id = Uuid::new_v4();
         let now = chrono::Utc::now();
 
-        let row = sqlx::query(
-            "INSERT INTO users (id, name, email, password, hide_balance, created_at, updated_at)
+        let user = sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, name, email, password, hide_balance, created_at, updated_at)
              VALUES ($1, $2, $3, $4, $5, $6, $7)
-             RETURNING id, name, email, password, hide_balance, created_at, updated_at"
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user_id,
+            request.name,
+            request.email,
+            hashed_password,
+            false, // default hide_balance to false
+            now,
+            now
         )
-        .bind(user_id)
-        .bind(&request.name)
-        .bind(&request.email)
-        .bind(&hashed_password)
-        .bind(false) // default hide_balance to false
-        .bind(now)
-        .bind(now)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| {
             if e.to_string().contains("duplicate key") {
-                AppError::Conflict("Email already exists".to_string())
+                AppError::Conflict("Email already exists".to_string()).with_code(codes::EMAIL_TAKEN)
             } else {
                 AppError::DatabaseError(e.to_string())
             }
         })?;
 
-        let user = User {
-            id: row.get("id"),
-            name: row.get("name"),
-            email: row.get("email"),
-            password: row.get("password"),
-            hide_balance: row.get("hide_balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        };
-
         Ok(user)
     }
 
     async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
-        let row = sqlx::query(
+        let row = sqlx::query!(
             "SELECT id, email, password 
-             FROM users WHERE email = $1"
+             FROM users WHERE email = $1",
+            email
         )
-        .bind(email)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -77,11 +71,13 @@ impl AuthRepository for PostgresAuthRepository {
                 // For auth purposes, we only need id, email, and password
                 // Other fields will be fetched separately if needed
                 let user = User {
-                    id: row.get("id"),
+                    id: row.id,
                     name: String::new(), // Will be populated later if needed
-                    email: row.get("email"),
-                    password: row.get("password"),
+                    email: row.email,
+                    password: row.password,
                     hide_balance: false, // Default value
+                    base_currency: String::new(), // Will be populated later if needed
+                    monthly_digest: false, // Default value
                     created_at: chrono::Utc::now(), // Placeholder
                     updated_at: chrono::Utc::now(), // Placeholder
                 };
@@ -90,4 +86,34 @@ impl AuthRepository for PostgresAuthRepository {
             None => Ok(None),
         }
     }
+
+    async fn find_user_by_id(&self, id: Uuid) -> Result<Option<User>, AppError> {
+        let user = sqlx::query_as!(
+            User,
+            r#"SELECT id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM users WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn find_locale(&self, user_id: Uuid) -> Result<Option<String>, AppError> {
+        let locale = sqlx::query_scalar!("SELECT locale FROM user_preferences WHERE user_id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(locale)
+    }
+
+    // Transparent upgrade of the stored hash, not a user-visible change, so updated_at stays put
+    async fn update_password_hash(&self, id: Uuid, hashed_password: &str) -> Result<(), AppError> {
+        sqlx::query!("UPDATE users SET password = $1 WHERE id = $2", hashed_password, id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,22 +1,50 @@
 use async_trait::async_trait;
 use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::str::FromStr;
 use uuid::Uuid;
 
-use crate::models::{Budget, CreateBudgetRequest, UpdateBudgetRequest, ListBudgetsQuery};
-use crate::utils::AppError;
+use crate::models::{Budget, BudgetPeriod, CreateBudgetRequest, UpdateBudgetRequest, ListBudgetsQuery, Precondition, Transaction, normalize_alert_thresholds};
+use crate::repositories::{Counted, Page};
+use crate::utils::{AppError, codes};
+use crate::policy::{Action, permits_as};
+
+const BUDGET_COLUMNS: &str =
+    "id, user_id, organization_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at, updated_at";
 
 #[async_trait]
 pub trait BudgetRepository: Send + Sync + Clone {
-    async fn find_by_id(&self, id: i64) -> Result<Option<Budget>, AppError>;
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<Budget>, AppError>;
+    // A personal budget of the user's, or a shared one of an organization they belong to along
+    // with their role in it
+    async fn find_by_id_with_role(&self, id: i64, user_id: Uuid) -> Result<Option<(Budget, Option<String>)>, AppError>;
     async fn find_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<Vec<Budget>, AppError>;
+    // The requested page and the filtered total from a single query
+    async fn find_page_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<Page<Budget>, AppError>;
     async fn create(&self, user_id: Uuid, request: &CreateBudgetRequest) -> Result<Budget, AppError>;
-    async fn update(&self, id: i64, user_id: Uuid, request: &UpdateBudgetRequest) -> Result<Budget, AppError>;
+    async fn find_page_by_organization_id(&self, organization_id: Uuid, query: &ListBudgetsQuery) -> Result<Page<Budget>, AppError>;
+    async fn create_in_organization(&self, organization_id: Uuid, user_id: Uuid, request: &CreateBudgetRequest) -> Result<Budget, AppError>;
+    async fn update(&self, id: i64, user_id: Uuid, request: &UpdateBudgetRequest, precondition: &Precondition) -> Result<Budget, AppError>;
     async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError>;
     async fn count_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<i64, AppError>;
     async fn get_categories(&self, user_id: Uuid) -> Result<Vec<String>, AppError>;
     async fn get_budget_performance(&self, user_id: Uuid) -> Result<Vec<(Budget, Decimal)>, AppError>;
+    async fn find_counted_transactions(&self, budget: &Budget) -> Result<Vec<Transaction>, AppError>;
+    // Active budgets whose current period ended before `as_of`, oldest first
+    async fn find_expired(&self, as_of: NaiveDate, limit: i64) -> Result<Vec<Budget>, AppError>;
+    // Records the budget's current period with its actual spend and moves the budget on to the next one.
+    // False when another instance already rolled it over.
+    async fn close_period(&self, budget: &Budget, next_start: NaiveDate, next_end: NaiveDate) -> Result<bool, AppError>;
+    // Most recent closed periods first
+    async fn find_periods(&self, budget_id: i64, limit: i64) -> Result<Vec<BudgetPeriod>, AppError>;
+    // Active personal budgets with alerts on whose period covers `as_of`, with their spend so far
+    async fn find_alert_candidates(&self, as_of: NaiveDate) -> Result<Vec<(Budget, Decimal)>, AppError>;
+    // False when the threshold was already alerted in this period
+    async fn record_alert(&self, budget: &Budget, threshold: i32, spent_amount: Decimal) -> Result<bool, AppError>;
+    async fn find_alerted_thresholds(&self, budget_id: i64, period_start: NaiveDate) -> Result<Vec<i32>, AppError>;
+    // Posted expense totals per category and calendar month (first day) within the range
+    async fn get_monthly_category_spend(&self, user_id: Uuid, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<(String, NaiveDate, Decimal)>, AppError>;
 }
 
 #[derive(Clone)]
@@ -29,82 +57,63 @@ impl PostgresBudgetRepository {
         Self { pool }
     }
 
-    fn build_where_clause(&self, query: &ListBudgetsQuery) -> String {
-        let mut conditions = vec!["user_id = $1".to_string()];
-        let mut param_count = 2;
-
-        if query.category.is_some() {
-            conditions.push(format!("category ILIKE ${}", param_count));
-            param_count += 1;
-        }
-
-        if query.period_type.is_some() {
-            conditions.push(format!("period_type = ${}", param_count));
-            param_count += 1;
-        }
+    // Filters, sorts and pages a list query; `columns` is everything between SELECT and FROM
+    fn list_query(columns: &str, scope: BudgetScope, query: &ListBudgetsQuery) -> Result<QueryBuilder<'static, Postgres>, AppError> {
+        let limit = query.limit.unwrap_or(20);
+        let offset = (query.page.unwrap_or(1) - 1) * limit;
 
-        if query.is_active.is_some() {
-            conditions.push(format!("is_active = ${}", param_count));
-        }
+        let mut builder = QueryBuilder::new(format!("SELECT {} FROM budgets", columns));
+        Self::push_filters(&mut builder, scope, BudgetFilters::from_query(query)?);
+        builder
+            .push(Self::order_by_clause(query))
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
 
-        if conditions.len() > 1 {
-            format!("WHERE {}", conditions.join(" AND "))
-        } else {
-            "WHERE user_id = $1".to_string()
-        }
+        Ok(builder)
     }
-}
 
-#[async_trait]
-impl BudgetRepository for PostgresBudgetRepository {
-    async fn find_by_id(&self, id: i64) -> Result<Option<Budget>, AppError> {
-        let budget = sqlx::query_as::<_, Budget>(
-            "SELECT id, user_id, category, target_amount, period_type, period_start, period_end, is_active, created_at, updated_at 
-             FROM budgets WHERE id = $1"
-        )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    async fn find_in_scope(&self, scope: BudgetScope, query: &ListBudgetsQuery) -> Result<Vec<Budget>, AppError> {
+        let budgets = Self::list_query(BUDGET_COLUMNS, scope, query)?
+            .build_query_as::<Budget>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        Ok(budget)
+        Ok(budgets)
     }
 
-    async fn find_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<Vec<Budget>, AppError> {
-        let where_clause = self.build_where_clause(query);
-        let limit = query.limit.unwrap_or(20);
-        let offset = (query.page.unwrap_or(1) - 1) * limit;
-
-        let sql = format!(
-            "SELECT id, user_id, category, target_amount, period_type, period_start, period_end, is_active, created_at, updated_at 
-             FROM budgets {} ORDER BY created_at DESC LIMIT {} OFFSET {}",
-            where_clause, limit, offset
-        );
-
-        let mut sql_query = sqlx::query_as::<_, Budget>(&sql)
-            .bind(user_id);
-
-        if let Some(ref category) = query.category {
-            sql_query = sql_query.bind(format!("%{}%", category));
-        }
+    async fn page_in_scope(&self, scope: BudgetScope, query: &ListBudgetsQuery) -> Result<Page<Budget>, AppError> {
+        let columns = format!("{}, COUNT(*) OVER() AS total_items", BUDGET_COLUMNS);
+        let rows = Self::list_query(&columns, scope, query)?
+            .build_query_as::<Counted<Budget>>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        if let Some(ref period_type) = query.period_type {
-            sql_query = sql_query.bind(period_type);
+        let offset = (query.page.unwrap_or(1) - 1) * query.limit.unwrap_or(20);
+        match Page::from_counted(rows, offset) {
+            Some(page) => Ok(page),
+            None => Ok(Page::empty(self.count_in_scope(scope, query).await?)),
         }
+    }
 
-        if let Some(is_active) = query.is_active {
-            sql_query = sql_query.bind(is_active);
-        }
+    async fn count_in_scope(&self, scope: BudgetScope, query: &ListBudgetsQuery) -> Result<i64, AppError> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM budgets");
+        Self::push_filters(&mut builder, scope, BudgetFilters::from_query(query)?);
 
-        let budgets = sql_query
-            .fetch_all(&self.pool)
+        let count = builder
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        Ok(budgets)
+        Ok(count)
     }
 
-    async fn create(&self, user_id: Uuid, request: &CreateBudgetRequest) -> Result<Budget, AppError> {
+    // Overlapping budgets are checked against the same owner: the user's own, or the organization's
+    async fn insert(&self, user_id: Uuid, organization_id: Option<Uuid>, request: &CreateBudgetRequest) -> Result<Budget, AppError> {
         // Parse dates
         let period_start = NaiveDate::parse_from_str(&request.period_start, "%Y-%m-%d")
             .map_err(|_| AppError::ValidationError("Invalid period_start format. Use YYYY-MM-DD".to_string()))?;
@@ -114,43 +123,50 @@ impl BudgetRepository for PostgresBudgetRepository {
 
         // Validate date range
         if period_end <= period_start {
-            return Err(AppError::ValidationError("Period end must be after period start".to_string()));
+            return Err(AppError::ValidationError("Period end must be after period start".to_string()).with_code(codes::INVALID_DATE_RANGE));
         }
 
-        // Convert amount to Decimal
-        let target_amount = Decimal::from_f64_retain(request.target_amount)
-            .ok_or_else(|| AppError::ValidationError("Invalid target amount".to_string()))?;
+        let target_amount = request.target_amount.amount();
 
         // Check for duplicate active budget in same category and period
-        let existing = sqlx::query(
+        let existing = sqlx::query!(
             "SELECT id FROM budgets 
-             WHERE user_id = $1 AND category = $2 AND is_active = true 
-             AND (period_start <= $4 AND period_end >= $3)"
+             WHERE (user_id = $1 OR $5::uuid IS NOT NULL) AND organization_id IS NOT DISTINCT FROM $5
+             AND category IS NOT DISTINCT FROM $2 AND is_active = true 
+             AND (period_start <= $4 AND period_end >= $3)",
+            user_id,
+            request.category,
+            period_start,
+            period_end,
+            organization_id
         )
-        .bind(user_id)
-        .bind(&request.category)
-        .bind(period_start)
-        .bind(period_end)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         if existing.is_some() {
-            return Err(AppError::ValidationError("An active budget already exists for this category in the specified period".to_string()));
+            let message = match request.category {
+                Some(_) => "An active budget already exists for this category in the specified period",
+                None => "An active overall budget already exists in the specified period",
+            };
+            return Err(AppError::ValidationError(message.to_string()));
         }
 
-        let budget = sqlx::query_as::<_, Budget>(
-            "INSERT INTO budgets (user_id, category, target_amount, period_type, period_start, period_end, is_active, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, true, $7, $7)
-             RETURNING id, user_id, category, target_amount, period_type, period_start, period_end, is_active, created_at, updated_at"
+        let budget = sqlx::query_as!(
+            Budget,
+            r#"INSERT INTO budgets (user_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at, updated_at, organization_id)
+             VALUES ($1, $2, $3, $4, $5, $6, true, $9, $7, $7, $8)
+             RETURNING id, user_id, organization_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user_id,
+            request.category,
+            target_amount,
+            request.period_type,
+            period_start,
+            period_end,
+            Utc::now(),
+            organization_id,
+            &normalize_alert_thresholds(request.alert_thresholds.as_deref())
         )
-        .bind(user_id)
-        .bind(&request.category)
-        .bind(target_amount)
-        .bind(&request.period_type)
-        .bind(period_start)
-        .bind(period_end)
-        .bind(Utc::now())
         .fetch_one(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -158,15 +174,174 @@ impl BudgetRepository for PostgresBudgetRepository {
         Ok(budget)
     }
 
-    async fn update(&self, id: i64, user_id: Uuid, request: &UpdateBudgetRequest) -> Result<Budget, AppError> {
-        // First check if budget exists and belongs to user
-        let existing = self.find_by_id(id).await?
-            .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+    // Maps the validated sort options onto fixed SQL, request text never reaches the query
+    fn order_by_clause(query: &ListBudgetsQuery) -> &'static str {
+        let ascending = query.order.as_deref() == Some("asc");
+        match (query.sort_by.as_deref(), ascending) {
+            (Some("amount"), true) => " ORDER BY target_amount ASC, id ASC",
+            (Some("amount"), false) => " ORDER BY target_amount DESC, id DESC",
+            (Some("date"), true) => " ORDER BY period_start ASC, id ASC",
+            (Some("date"), false) => " ORDER BY period_start DESC, id DESC",
+            (Some("category"), true) => " ORDER BY category ASC, created_at DESC",
+            (Some("category"), false) => " ORDER BY category DESC, created_at DESC",
+            (_, true) => " ORDER BY created_at ASC",
+            (_, false) => " ORDER BY created_at DESC",
+        }
+    }
+
+    // Appends the WHERE clause for a list query, binding every value through the builder
+    fn push_filters(builder: &mut QueryBuilder<'static, Postgres>, scope: BudgetScope, filters: BudgetFilters) {
+        match scope {
+            BudgetScope::User(user_id) => {
+                builder.push(" WHERE user_id = ").push_bind(user_id).push(" AND organization_id IS NULL");
+            }
+            BudgetScope::Organization(organization_id) => {
+                builder.push(" WHERE organization_id = ").push_bind(organization_id);
+            }
+        }
+
+        if let Some(category) = filters.category {
+            builder.push(" AND category ILIKE ").push_bind(format!("%{}%", category));
+        }
+
+        if !filters.categories.is_empty() {
+            builder.push(" AND category = ANY(").push_bind(filters.categories).push(")");
+        }
+
+        if let Some(period_type) = filters.period_type {
+            builder.push(" AND period_type = ").push_bind(period_type);
+        }
+
+        if let Some(is_active) = filters.is_active {
+            builder.push(" AND is_active = ").push_bind(is_active);
+        }
 
-        if existing.user_id != user_id {
-            return Err(AppError::Forbidden("Access denied".to_string()));
+        if let Some(min_amount) = filters.min_amount {
+            builder.push(" AND target_amount >= ").push_bind(min_amount);
         }
 
+        if let Some(max_amount) = filters.max_amount {
+            builder.push(" AND target_amount <= ").push_bind(max_amount);
+        }
+    }
+}
+
+// Personal budgets and an organization's shared budgets are listed separately
+#[derive(Clone, Copy)]
+enum BudgetScope {
+    User(Uuid),
+    Organization(Uuid),
+}
+
+// Typed form of ListBudgetsQuery, parsed once before building SQL
+struct BudgetFilters {
+    category: Option<String>,
+    categories: Vec<String>,
+    period_type: Option<String>,
+    is_active: Option<bool>,
+    min_amount: Option<Decimal>,
+    max_amount: Option<Decimal>,
+}
+
+impl BudgetFilters {
+    fn from_query(query: &ListBudgetsQuery) -> Result<Self, AppError> {
+        let parse_amount = |value: &Option<String>, field: &str| {
+            value
+                .as_deref()
+                .map(|value| {
+                    Decimal::from_str(value)
+                        .map_err(|_| AppError::ValidationError(format!("Invalid {} format", field)))
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            category: query.category.clone(),
+            categories: query.category_list(),
+            period_type: query.period_type.clone(),
+            is_active: query.is_active,
+            min_amount: parse_amount(&query.min_amount, "min_amount")?,
+            max_amount: parse_amount(&query.max_amount, "max_amount")?,
+        })
+    }
+}
+
+#[async_trait]
+impl BudgetRepository for PostgresBudgetRepository {
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<Budget>, AppError> {
+        let budget = sqlx::query_as!(
+            Budget,
+            r#"SELECT id, user_id, organization_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at AS "created_at!", updated_at AS "updated_at!" 
+             FROM budgets WHERE id = $1 AND user_id = $2 AND organization_id IS NULL"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(budget)
+    }
+
+    async fn find_by_id_with_role(&self, id: i64, user_id: Uuid) -> Result<Option<(Budget, Option<String>)>, AppError> {
+        let row = sqlx::query!(
+            r#"SELECT b.id, b.user_id, b.organization_id, b.category, b.target_amount, b.period_type, b.period_start, b.period_end,
+                    b.is_active, b.alert_thresholds, b.created_at AS "created_at!", b.updated_at AS "updated_at!", m.role AS "role?"
+             FROM budgets b
+             LEFT JOIN organization_members m ON m.organization_id = b.organization_id AND m.user_id = $2
+             WHERE b.id = $1 AND ((b.organization_id IS NULL AND b.user_id = $2) OR m.user_id IS NOT NULL)"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| {
+            let budget = Budget {
+                id: row.id,
+                user_id: row.user_id,
+                organization_id: row.organization_id,
+                category: row.category,
+                target_amount: row.target_amount,
+                period_type: row.period_type,
+                period_start: row.period_start,
+                period_end: row.period_end,
+                is_active: row.is_active,
+                alert_thresholds: row.alert_thresholds,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+            (budget, row.role)
+        }))
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<Vec<Budget>, AppError> {
+        self.find_in_scope(BudgetScope::User(user_id), query).await
+    }
+
+    async fn find_page_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<Page<Budget>, AppError> {
+        self.page_in_scope(BudgetScope::User(user_id), query).await
+    }
+
+    async fn create(&self, user_id: Uuid, request: &CreateBudgetRequest) -> Result<Budget, AppError> {
+        self.insert(user_id, None, request).await
+    }
+
+    async fn find_page_by_organization_id(&self, organization_id: Uuid, query: &ListBudgetsQuery) -> Result<Page<Budget>, AppError> {
+        self.page_in_scope(BudgetScope::Organization(organization_id), query).await
+    }
+
+    async fn create_in_organization(&self, organization_id: Uuid, user_id: Uuid, request: &CreateBudgetRequest) -> Result<Budget, AppError> {
+        self.insert(user_id, Some(organization_id), request).await
+    }
+
+    async fn update(&self, id: i64, user_id: Uuid, request: &UpdateBudgetRequest, precondition: &Precondition) -> Result<Budget, AppError> {
+        // First check if budget exists and the user may change it
+        let (existing, _) = self.find_by_id_with_role(id, user_id).await?
+            .filter(|(budget, role)| permits_as(user_id, role.as_deref(), Action::Write, budget))
+            .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+        precondition.check(existing.updated_at)?;
+
         let mut update_fields = Vec::new();
         let mut param_count = 1;
 
@@ -200,6 +375,11 @@ impl BudgetRepository for PostgresBudgetRepository {
             param_count += 1;
         }
 
+        if request.alert_thresholds.is_some() {
+            update_fields.push(format!("alert_thresholds = ${}", param_count));
+            param_count += 1;
+        }
+
         if update_fields.is_empty() {
             return Ok(existing);
         }
@@ -208,9 +388,11 @@ impl BudgetRepository for PostgresBudgetRepository {
         param_count += 1;
 
         let sql = format!(
-            "UPDATE budgets SET {} WHERE id = ${} AND user_id = ${}
-             RETURNING id, user_id, category, target_amount, period_type, period_start, period_end, is_active, created_at, updated_at",
-            update_fields.join(", "), param_count, param_count + 1
+            "UPDATE budgets SET {} WHERE id = ${} AND ((organization_id IS NULL AND user_id = ${})
+                     OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = ${2} AND role IN ('owner', 'admin')))
+                 AND (${3}::timestamptz IS NULL OR updated_at = ${3})
+             RETURNING id, user_id, organization_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at, updated_at",
+            update_fields.join(", "), param_count, param_count + 1, param_count + 2
         );
 
         let mut query = sqlx::query_as::<_, Budget>(&sql);
@@ -220,9 +402,7 @@ impl BudgetRepository for PostgresBudgetRepository {
         }
 
         if let Some(target_amount) = request.target_amount {
-            let decimal_amount = Decimal::from_f64_retain(target_amount)
-                .ok_or_else(|| AppError::ValidationError("Invalid target amount".to_string()))?;
-            query = query.bind(decimal_amount);
+            query = query.bind(target_amount.amount());
         }
 
         if let Some(ref period_type) = request.period_type {
@@ -245,23 +425,42 @@ impl BudgetRepository for PostgresBudgetRepository {
             query = query.bind(is_active);
         }
 
-        query = query.bind(Utc::now()).bind(id).bind(user_id);
+        if let Some(ref thresholds) = request.alert_thresholds {
+            query = query.bind(normalize_alert_thresholds(Some(thresholds)));
+        }
+
+        query = query.bind(Utc::now()).bind(id).bind(user_id).bind(precondition.expected(existing.updated_at));
 
         let budget = query
-            .fetch_one(&self.pool)
+            .fetch_optional(&self.pool)
             .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| match precondition.expected(existing.updated_at) {
+                // Another write landed between the check and this update
+                Some(_) => Precondition::conflict(),
+                // Or the budget was deleted, or the caller lost the role to change it
+                None => AppError::NotFound("Budget not found".to_string()),
+            })?;
 
         Ok(budget)
     }
 
     async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM budgets WHERE id = $1 AND user_id = $2")
-            .bind(id)
-            .bind(user_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        self.find_by_id_with_role(id, user_id)
+            .await?
+            .filter(|(budget, role)| permits_as(user_id, role.as_deref(), Action::Write, budget))
+            .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+
+        let result = sqlx::query!(
+            "DELETE FROM budgets WHERE id = $1
+             AND ((organization_id IS NULL AND user_id = $2)
+                 OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = $2 AND role IN ('owner', 'admin')))",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound("Budget not found".to_string()));
@@ -271,37 +470,15 @@ impl BudgetRepository for PostgresBudgetRepository {
     }
 
     async fn count_by_user_id(&self, user_id: Uuid, query: &ListBudgetsQuery) -> Result<i64, AppError> {
-        let where_clause = self.build_where_clause(query);
-
-        let sql = format!("SELECT COUNT(*) FROM budgets {}", where_clause);
-        let mut sql_query = sqlx::query(&sql)
-            .bind(user_id);
-
-        if let Some(ref category) = query.category {
-            sql_query = sql_query.bind(format!("%{}%", category));
-        }
-
-        if let Some(ref period_type) = query.period_type {
-            sql_query = sql_query.bind(period_type);
-        }
-
-        if let Some(is_active) = query.is_active {
-            sql_query = sql_query.bind(is_active);
-        }
-
-        let row = sql_query
-            .fetch_one(&self.pool)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-        Ok(row.get::<i64, _>(0))
+        self.count_in_scope(BudgetScope::User(user_id), query).await
     }
 
     async fn get_categories(&self, user_id: Uuid) -> Result<Vec<String>, AppError> {
-        let categories = sqlx::query_scalar::<_, String>(
-            "SELECT DISTINCT category FROM budgets WHERE user_id = $1 ORDER BY category"
+        let categories = sqlx::query_scalar!(
+            r#"SELECT DISTINCT category AS "category!"
+             FROM budgets WHERE user_id = $1 AND organization_id IS NULL AND category IS NOT NULL ORDER BY category"#,
+            user_id
         )
-        .bind(user_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -309,22 +486,24 @@ impl BudgetRepository for PostgresBudgetRepository {
         Ok(categories)
     }
 
+    // Overall budgets (no category) count every expense in their period
     async fn get_budget_performance(&self, user_id: Uuid) -> Result<Vec<(Budget, Decimal)>, AppError> {
-        let rows = sqlx::query(
-            "SELECT b.id, b.user_id, b.category, b.target_amount, b.period_type, b.period_start, b.period_end, 
-                    b.is_active, b.created_at, b.updated_at,
-                    COALESCE(SUM(CASE WHEN t.transaction_type = 'expense' THEN t.amount ELSE 0 END), 0) as spent_amount
+        let rows = sqlx::query!(
+            r#"SELECT b.id, b.user_id, b.organization_id, b.category, b.target_amount, b.period_type, b.period_start, b.period_end, 
+                    b.is_active, b.alert_thresholds, b.created_at AS "created_at!", b.updated_at AS "updated_at!",
+                    COALESCE(SUM(CASE WHEN t.transaction_type = 'expense' THEN t.amount ELSE 0 END), 0) AS "spent_amount!"
              FROM budgets b
-             LEFT JOIN transactions t ON t.user_id = b.user_id 
-                 AND t.category = b.category 
+             LEFT JOIN all_transactions t ON t.user_id = b.user_id 
+                 AND (b.category IS NULL OR t.category = b.category) 
                  AND t.transaction_date >= b.period_start 
                  AND t.transaction_date <= b.period_end
-             WHERE b.user_id = $1 AND b.is_active = true
-             GROUP BY b.id, b.user_id, b.category, b.target_amount, b.period_type, b.period_start, b.period_end, 
-                      b.is_active, b.created_at, b.updated_at
-             ORDER BY b.created_at DESC"
+                 AND t.status = 'posted'
+             WHERE b.user_id = $1 AND b.organization_id IS NULL AND b.is_active = true
+             GROUP BY b.id, b.user_id, b.organization_id, b.category, b.target_amount, b.period_type, b.period_start, b.period_end, 
+                      b.is_active, b.alert_thresholds, b.created_at, b.updated_at
+             ORDER BY b.created_at DESC"#,
+            user_id
         )
-        .bind(user_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -332,21 +511,208 @@ impl BudgetRepository for PostgresBudgetRepository {
         let mut results = Vec::new();
         for row in rows {
             let budget = Budget {
-                id: row.get("id"),
-                user_id: row.get("user_id"),
-                category: row.get("category"),
-                target_amount: row.get("target_amount"),
-                period_type: row.get("period_type"),
-                period_start: row.get("period_start"),
-                period_end: row.get("period_end"),
-                is_active: row.get("is_active"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
+                id: row.id,
+                user_id: row.user_id,
+                organization_id: row.organization_id,
+                category: row.category,
+                target_amount: row.target_amount,
+                period_type: row.period_type,
+                period_start: row.period_start,
+                period_end: row.period_end,
+                is_active: row.is_active,
+                alert_thresholds: row.alert_thresholds,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
             };
-            let spent_amount: Decimal = row.get("spent_amount");
+            let spent_amount: Decimal = row.spent_amount;
             results.push((budget, spent_amount));
         }
 
         Ok(results)
     }
+    // Same matching rules as get_budget_performance: expenses in the budget's category and period
+    async fn find_counted_transactions(&self, budget: &Budget) -> Result<Vec<Transaction>, AppError> {
+        let transactions = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id AS "id!", user_id AS "user_id!", account_id, description AS "description!", amount AS "amount!",
+                    category AS "category!", transaction_type AS "transaction_type!", transaction_date AS "transaction_date!",
+                    status AS "status!", notes, metadata, original_amount, original_currency, exchange_rate,
+                    created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM all_transactions
+             WHERE user_id = $1 AND ($2::text IS NULL OR category = $2) AND transaction_type = 'expense'
+                 AND transaction_date >= $3 AND transaction_date <= $4 AND status = 'posted'
+             ORDER BY transaction_date DESC, created_at DESC"#,
+            budget.user_id,
+            budget.category,
+            budget.period_start,
+            budget.period_end
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(transactions)
+    }
+    async fn find_expired(&self, as_of: NaiveDate, limit: i64) -> Result<Vec<Budget>, AppError> {
+        let budgets = sqlx::query_as!(
+            Budget,
+            r#"SELECT id, user_id, organization_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM budgets
+             WHERE is_active = true AND period_end < $1
+             ORDER BY period_end, id
+             LIMIT $2"#,
+            as_of,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(budgets)
+    }
+
+    async fn close_period(&self, budget: &Budget, next_start: NaiveDate, next_end: NaiveDate) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Guarded on the old period so two instances never record the same period twice
+        let advanced = sqlx::query!(
+            "UPDATE budgets SET period_start = $1, period_end = $2, updated_at = NOW()
+             WHERE id = $3 AND period_start = $4",
+            next_start,
+            next_end,
+            budget.id,
+            budget.period_start
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() == 1;
+
+        if !advanced {
+            return Ok(false);
+        }
+
+        // Same matching rules as get_budget_performance
+        sqlx::query!(
+            "INSERT INTO budget_periods (budget_id, period_start, period_end, target_amount, actual_amount)
+             SELECT $1, $2, $3, $4, COALESCE(SUM(amount), 0)
+             FROM all_transactions
+             WHERE user_id = $5 AND ($6::text IS NULL OR category = $6) AND transaction_type = 'expense'
+                 AND transaction_date >= $2 AND transaction_date <= $3 AND status = 'posted'
+             ON CONFLICT (budget_id, period_start) DO NOTHING",
+            budget.id,
+            budget.period_start,
+            budget.period_end,
+            budget.target_amount,
+            budget.user_id,
+            budget.category
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn find_periods(&self, budget_id: i64, limit: i64) -> Result<Vec<BudgetPeriod>, AppError> {
+        let periods = sqlx::query_as!(
+            BudgetPeriod,
+            r#"SELECT id, budget_id, period_start, period_end, target_amount, actual_amount, created_at AS "created_at!"
+             FROM budget_periods
+             WHERE budget_id = $1
+             ORDER BY period_start DESC
+             LIMIT $2"#,
+            budget_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(periods)
+    }
+    async fn find_alert_candidates(&self, as_of: NaiveDate) -> Result<Vec<(Budget, Decimal)>, AppError> {
+        let rows = sqlx::query!(
+            r#"SELECT b.id, b.user_id, b.organization_id, b.category, b.target_amount, b.period_type, b.period_start, b.period_end,
+                    b.is_active, b.alert_thresholds, b.created_at AS "created_at!", b.updated_at AS "updated_at!",
+                    COALESCE(SUM(t.amount), 0) AS "spent_amount!"
+             FROM budgets b
+             LEFT JOIN all_transactions t ON t.user_id = b.user_id
+                 AND (b.category IS NULL OR t.category = b.category)
+                 AND t.transaction_type = 'expense'
+                 AND t.transaction_date >= b.period_start
+                 AND t.transaction_date <= b.period_end
+                 AND t.status = 'posted'
+             WHERE b.is_active = true AND b.organization_id IS NULL
+                 AND cardinality(b.alert_thresholds) > 0
+                 AND b.period_start <= $1 AND b.period_end >= $1
+             GROUP BY b.id"#,
+            as_of
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            let budget = Budget {
+                id: row.id,
+                user_id: row.user_id,
+                organization_id: row.organization_id,
+                category: row.category,
+                target_amount: row.target_amount,
+                period_type: row.period_type,
+                period_start: row.period_start,
+                period_end: row.period_end,
+                is_active: row.is_active,
+                alert_thresholds: row.alert_thresholds,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+            results.push((budget, row.spent_amount));
+        }
+
+        Ok(results)
+    }
+
+    async fn record_alert(&self, budget: &Budget, threshold: i32, spent_amount: Decimal) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "INSERT INTO budget_alerts (budget_id, period_start, threshold, spent_amount)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (budget_id, period_start, threshold) DO NOTHING",
+            budget.id,
+            budget.period_start,
+            threshold,
+            spent_amount
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn find_alerted_thresholds(&self, budget_id: i64, period_start: NaiveDate) -> Result<Vec<i32>, AppError> {
+        let thresholds = sqlx::query_scalar!(
+            "SELECT threshold FROM budget_alerts WHERE budget_id = $1 AND period_start = $2 ORDER BY threshold",
+            budget_id,
+            period_start
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(thresholds)
+    }
+    async fn get_monthly_category_spend(&self, user_id: Uuid, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<(String, NaiveDate, Decimal)>, AppError> {
+        let rows = sqlx::query!(
+            r#"SELECT category AS "category!", date_trunc('month', transaction_date)::date AS "month!", SUM(amount) AS "spent!"
+             FROM all_transactions
+             WHERE user_id = $1 AND transaction_type = 'expense' AND status = 'posted' AND category IS NOT NULL
+                 AND transaction_date >= $2 AND transaction_date <= $3
+             GROUP BY category, 2
+             ORDER BY category, 2"#,
+            user_id,
+            from_date,
+            to_date
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.category, row.month, row.spent)).collect())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{CategoryFeedback, LabeledExample};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait CategorizationRepository: Clone + Send + Sync {
+    async fn find_labeled_examples(&self, user_id: Uuid, limit: i64) -> Result<Vec<LabeledExample>, AppError>;
+    async fn create_feedback(&self, user_id: Uuid, description: &str, suggested_category: Option<&str>, chosen_category: &str) -> Result<CategoryFeedback, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresCategorizationRepository {
+    pool: PgPool,
+}
+
+impl PostgresCategorizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl CategorizationRepository for PostgresCategorizationRepository {
+    async fn find_labeled_examples(&self, user_id: Uuid, limit: i64) -> Result<Vec<LabeledExample>, AppError> {
+        // Feedback first so the most deliberate labels survive the limit
+        let examples = sqlx::query_as!(
+            LabeledExample,
+            r#"SELECT description AS "description!", category AS "category!", from_feedback AS "from_feedback!"
+             FROM ((SELECT description, chosen_category AS category, TRUE AS from_feedback, created_at
+                    FROM category_feedback WHERE user_id = $1
+                    ORDER BY created_at DESC LIMIT $2)
+                   UNION ALL
+                   (SELECT description, category, FALSE AS from_feedback, created_at
+                    FROM transactions WHERE user_id = $1 AND category <> ''
+                    ORDER BY created_at DESC LIMIT $2)) examples
+             ORDER BY from_feedback DESC, created_at DESC
+             LIMIT $2"#,
+            user_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(examples)
+    }
+
+    async fn create_feedback(&self, user_id: Uuid, description: &str, suggested_category: Option<&str>, chosen_category: &str) -> Result<CategoryFeedback, AppError> {
+        let feedback = sqlx::query_as!(
+            CategoryFeedback,
+            r#"INSERT INTO category_feedback (user_id, description, suggested_category, chosen_category, created_at)
+             VALUES ($1, $2, $3, $4, NOW())
+             RETURNING id, user_id, description, suggested_category, chosen_category, created_at AS "created_at!""#,
+            user_id,
+            description,
+            suggested_category,
+            chosen_category
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(feedback)
+    }
+}
\ No newline at end of file
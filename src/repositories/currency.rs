@@ -0,0 +1,123 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{ExchangeRate, RestatementSnapshot, PIVOT_CURRENCY};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait CurrencyRepository: Clone + Send + Sync {
+    async fn is_supported(&self, currency: &str) -> Result<bool, AppError>;
+    async fn find_rates(&self, currencies: &[String]) -> Result<Vec<ExchangeRate>, AppError>;
+    async fn find_base_currency(&self, user_id: Uuid) -> Result<Option<String>, AppError>;
+    async fn count_snapshots(&self, user_id: Uuid) -> Result<i64, AppError>;
+    async fn find_snapshot_currencies(&self, user_id: Uuid) -> Result<Vec<String>, AppError>;
+    async fn find_snapshot_batch(&self, user_id: Uuid, after_id: i64, limit: i64) -> Result<Vec<RestatementSnapshot>, AppError>;
+    async fn update_converted_balances(&self, ids: &[i64], balances: &[Option<Decimal>], currency: &str) -> Result<u64, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresCurrencyRepository {
+    pool: PgPool,
+}
+
+impl PostgresCurrencyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl CurrencyRepository for PostgresCurrencyRepository {
+    async fn is_supported(&self, currency: &str) -> Result<bool, AppError> {
+        if currency == PIVOT_CURRENCY {
+            return Ok(true);
+        }
+
+        let supported: bool = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM exchange_rates WHERE currency = $1) AS "exists!""#, currency)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(supported)
+    }
+
+    async fn find_rates(&self, currencies: &[String]) -> Result<Vec<ExchangeRate>, AppError> {
+        let rates = sqlx::query_as!(
+            ExchangeRate,
+            r#"SELECT currency, rate_date, usd_rate, created_at AS "created_at!"
+             FROM exchange_rates WHERE currency = ANY($1)
+             ORDER BY currency, rate_date"#,
+            currencies
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rates)
+    }
+
+    async fn find_base_currency(&self, user_id: Uuid) -> Result<Option<String>, AppError> {
+        let currency = sqlx::query_scalar!("SELECT base_currency FROM users WHERE id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(currency)
+    }
+
+    async fn count_snapshots(&self, user_id: Uuid) -> Result<i64, AppError> {
+        let count: i64 = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM pocket_balance_snapshots WHERE user_id = $1"#, user_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn find_snapshot_currencies(&self, user_id: Uuid) -> Result<Vec<String>, AppError> {
+        let currencies = sqlx::query_scalar!(
+            "SELECT DISTINCT p.currency
+             FROM pocket_balance_snapshots s
+             JOIN pockets p ON p.id = s.pocket_id
+             WHERE s.user_id = $1",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(currencies)
+    }
+
+    async fn find_snapshot_batch(&self, user_id: Uuid, after_id: i64, limit: i64) -> Result<Vec<RestatementSnapshot>, AppError> {
+        let snapshots = sqlx::query_as!(
+            RestatementSnapshot,
+            "SELECT s.id, s.snapshot_month, s.balance, p.currency
+             FROM pocket_balance_snapshots s
+             JOIN pockets p ON p.id = s.pocket_id
+             WHERE s.user_id = $1 AND s.id > $2
+             ORDER BY s.id
+             LIMIT $3",
+            user_id,
+            after_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+
+    async fn update_converted_balances(&self, ids: &[i64], balances: &[Option<Decimal>], currency: &str) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            "UPDATE pocket_balance_snapshots s
+             SET converted_balance = v.converted_balance, converted_currency = $3
+             FROM UNNEST($1::bigint[], $2::numeric[]) AS v(id, converted_balance)
+             WHERE s.id = v.id",
+            ids,
+            // Unconvertible snapshots carry NULL, which the inferred numeric[] type cannot express
+            balances as _,
+            currency
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::Debt;
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait DebtRepository: Clone + Send + Sync {
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<Debt>, AppError>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Debt>, AppError>;
+    async fn create(&self, user_id: Uuid, name: &str, debt_type: &str, principal: Decimal, apr: Decimal, minimum_payment: Decimal) -> Result<Debt, AppError>;
+    async fn update(&self, id: i64, user_id: Uuid, name: Option<&str>, principal: Option<Decimal>, apr: Option<Decimal>, minimum_payment: Option<Decimal>) -> Result<Debt, AppError>;
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresDebtRepository {
+    pool: PgPool,
+}
+
+impl PostgresDebtRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DebtRepository for PostgresDebtRepository {
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<Debt>, AppError> {
+        let debt = sqlx::query_as!(
+            Debt,
+            r#"SELECT id, user_id, name, debt_type, principal, apr, minimum_payment, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM debts WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(debt)
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Debt>, AppError> {
+        let debts = sqlx::query_as!(
+            Debt,
+            r#"SELECT id, user_id, name, debt_type, principal, apr, minimum_payment, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM debts WHERE user_id = $1 ORDER BY apr DESC, id"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(debts)
+    }
+
+    async fn create(&self, user_id: Uuid, name: &str, debt_type: &str, principal: Decimal, apr: Decimal, minimum_payment: Decimal) -> Result<Debt, AppError> {
+        let debt = sqlx::query_as!(
+            Debt,
+            r#"INSERT INTO debts (user_id, name, debt_type, principal, apr, minimum_payment, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+             RETURNING id, user_id, name, debt_type, principal, apr, minimum_payment, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user_id,
+            name,
+            debt_type,
+            principal,
+            apr,
+            minimum_payment,
+            Utc::now()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(debt)
+    }
+
+    async fn update(&self, id: i64, user_id: Uuid, name: Option<&str>, principal: Option<Decimal>, apr: Option<Decimal>, minimum_payment: Option<Decimal>) -> Result<Debt, AppError> {
+        let debt = sqlx::query_as!(
+            Debt,
+            r#"UPDATE debts
+             SET name = COALESCE($1, name), principal = COALESCE($2, principal), apr = COALESCE($3, apr),
+                 minimum_payment = COALESCE($4, minimum_payment), updated_at = NOW()
+             WHERE id = $5 AND user_id = $6
+             RETURNING id, user_id, name, debt_type, principal, apr, minimum_payment, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            name,
+            principal,
+            apr,
+            minimum_payment,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        debt.ok_or_else(|| AppError::NotFound("Debt not found".to_string()))
+    }
+
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!("DELETE FROM debts WHERE id = $1 AND user_id = $2", id, user_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Debt not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,190 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{Device, NotificationPreference, PushTransactionSummary, RegisterDeviceRequest};
+use crate::utils::{hash_token, AppError, Encrypted, FieldCipher};
+
+// A devices row before its token is opened
+struct DeviceRow {
+    id: i64,
+    user_id: Uuid,
+    provider: String,
+    sealed_token: Encrypted<String>,
+    name: Option<String>,
+    created_at: DateTime<Utc>,
+    last_registered_at: DateTime<Utc>,
+}
+
+#[async_trait::async_trait]
+pub trait DeviceRepository: Clone + Send + Sync {
+    // Registering a known token refreshes it and moves it to this user
+    async fn upsert(&self, user_id: Uuid, request: &RegisterDeviceRequest) -> Result<Device, AppError>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Device>, AppError>;
+    async fn delete(&self, user_id: Uuid, id: i64) -> Result<bool, AppError>;
+    // For tokens the push service reports as no longer valid
+    async fn delete_by_token(&self, token: &str) -> Result<(), AppError>;
+    // Only kinds the user has changed are stored
+    async fn find_preferences(&self, user_id: Uuid) -> Result<Vec<NotificationPreference>, AppError>;
+    async fn upsert_preferences(&self, user_id: Uuid, preferences: &[NotificationPreference]) -> Result<(), AppError>;
+    // The budget's category, None for an overall budget, and the threshold it most recently reached
+    async fn find_latest_budget_alert(&self, user_id: Uuid, budget_id: i64) -> Result<Option<(Option<String>, i32)>, AppError>;
+    async fn find_transaction_summary(&self, user_id: Uuid, transaction_id: i64) -> Result<Option<PushTransactionSummary>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresDeviceRepository {
+    pool: PgPool,
+    cipher: FieldCipher,
+}
+
+impl PostgresDeviceRepository {
+    pub fn new(pool: PgPool, cipher: FieldCipher) -> Self {
+        Self { pool, cipher }
+    }
+
+    fn open(&self, row: DeviceRow) -> Result<Device, AppError> {
+        Ok(Device {
+            id: row.id,
+            user_id: row.user_id,
+            provider: row.provider,
+            token: self.cipher.open(&row.sealed_token)?,
+            name: row.name,
+            created_at: row.created_at,
+            last_registered_at: row.last_registered_at,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl DeviceRepository for PostgresDeviceRepository {
+    async fn upsert(&self, user_id: Uuid, request: &RegisterDeviceRequest) -> Result<Device, AppError> {
+        // Without a key there is nowhere to keep the token, which only happens with push switched off
+        if !self.cipher.is_configured() {
+            return Err(AppError::BadRequest("Push notifications are not enabled on this server".to_string()));
+        }
+
+        // Sealed again on every registration, which also moves it to the current key
+        let row = sqlx::query_as!(
+            DeviceRow,
+            r#"INSERT INTO devices (user_id, provider, token_hash, sealed_token, name)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (token_hash) DO UPDATE
+             SET user_id = EXCLUDED.user_id, provider = EXCLUDED.provider, sealed_token = EXCLUDED.sealed_token,
+                 name = EXCLUDED.name, last_registered_at = NOW()
+             RETURNING id, user_id, provider, sealed_token AS "sealed_token: Encrypted<String>", name, created_at, last_registered_at"#,
+            user_id,
+            request.provider,
+            hash_token(&request.token),
+            self.cipher.seal(&request.token)? as _,
+            request.name
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.open(row)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Device>, AppError> {
+        let rows = sqlx::query_as!(
+            DeviceRow,
+            r#"SELECT id, user_id, provider, sealed_token AS "sealed_token: Encrypted<String>", name, created_at, last_registered_at
+             FROM devices WHERE user_id = $1 ORDER BY last_registered_at DESC"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| self.open(row)).collect()
+    }
+
+    async fn delete(&self, user_id: Uuid, id: i64) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM devices WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_by_token(&self, token: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "DELETE FROM devices WHERE token_hash = $1",
+            hash_token(token)
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_preferences(&self, user_id: Uuid) -> Result<Vec<NotificationPreference>, AppError> {
+        let preferences = sqlx::query_as!(
+            NotificationPreference,
+            "SELECT kind, push FROM notification_preferences WHERE user_id = $1",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    async fn upsert_preferences(&self, user_id: Uuid, preferences: &[NotificationPreference]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        for preference in preferences {
+            sqlx::query!(
+                "INSERT INTO notification_preferences (user_id, kind, push)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (user_id, kind) DO UPDATE SET push = EXCLUDED.push, updated_at = NOW()",
+                user_id,
+                preference.kind,
+                preference.push
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_latest_budget_alert(&self, user_id: Uuid, budget_id: i64) -> Result<Option<(Option<String>, i32)>, AppError> {
+        let alert = sqlx::query!(
+            "SELECT b.category, a.threshold
+             FROM budget_alerts a
+             JOIN budgets b ON b.id = a.budget_id
+             WHERE a.budget_id = $1 AND b.user_id = $2
+             ORDER BY a.created_at DESC, a.threshold DESC
+             LIMIT 1",
+            budget_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(alert.map(|alert| (alert.category, alert.threshold)))
+    }
+
+    async fn find_transaction_summary(&self, user_id: Uuid, transaction_id: i64) -> Result<Option<PushTransactionSummary>, AppError> {
+        let summary = sqlx::query_as!(
+            PushTransactionSummary,
+            r#"SELECT t.description, ABS(t.amount) AS "amount!", COALESCE(p.currency, u.base_currency) AS "currency!", u.hide_balance AS "hide_balance!"
+             FROM transactions t
+             JOIN users u ON u.id = t.user_id
+             LEFT JOIN pockets p ON p.id = t.account_id
+             WHERE t.id = $1 AND t.user_id = $2"#,
+            transaction_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{DigestBudget, DigestCategory, DigestRecipient};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait DigestRepository: Clone + Send + Sync {
+    async fn find_due_recipients(&self, month: NaiveDate, limit: i64) -> Result<Vec<DigestRecipient>, AppError>;
+    async fn find_totals(&self, user_id: Uuid, start: NaiveDate, end: NaiveDate) -> Result<(Decimal, Decimal), AppError>;
+    async fn find_top_categories(&self, user_id: Uuid, start: NaiveDate, end: NaiveDate, limit: i64) -> Result<Vec<DigestCategory>, AppError>;
+    async fn find_budget_status(&self, user_id: Uuid, start: NaiveDate, end: NaiveDate) -> Result<Vec<DigestBudget>, AppError>;
+    async fn mark_sent(&self, user_id: Uuid, month: NaiveDate) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresDigestRepository {
+    pool: PgPool,
+}
+
+impl PostgresDigestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DigestRepository for PostgresDigestRepository {
+    async fn find_due_recipients(&self, month: NaiveDate, limit: i64) -> Result<Vec<DigestRecipient>, AppError> {
+        // Accounts waiting out their deletion grace period are not mailed
+        let recipients = sqlx::query_as!(
+            DigestRecipient,
+            "SELECT u.id, u.name, u.email, u.base_currency, p.locale
+             FROM users u
+             LEFT JOIN user_preferences p ON p.user_id = u.id
+             WHERE u.monthly_digest AND u.deletion_scheduled_at IS NULL
+                 AND (u.last_digest_month IS NULL OR u.last_digest_month < $1)
+             ORDER BY u.id
+             LIMIT $2",
+            month,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recipients)
+    }
+
+    async fn find_totals(&self, user_id: Uuid, start: NaiveDate, end: NaiveDate) -> Result<(Decimal, Decimal), AppError> {
+        let totals = sqlx::query!(
+            r#"SELECT
+                 COALESCE(SUM(ABS(amount)) FILTER (WHERE transaction_type = 'income'), 0) AS "income!",
+                 COALESCE(SUM(ABS(amount)) FILTER (WHERE transaction_type = 'expense'), 0) AS "expense!"
+             FROM all_transactions
+             WHERE user_id = $1 AND transaction_date >= $2 AND transaction_date <= $3 AND status = 'posted'"#,
+            user_id,
+            start,
+            end
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((totals.income, totals.expense))
+    }
+
+    async fn find_top_categories(&self, user_id: Uuid, start: NaiveDate, end: NaiveDate, limit: i64) -> Result<Vec<DigestCategory>, AppError> {
+        let categories = sqlx::query_as!(
+            DigestCategory,
+            r#"SELECT category AS "category!", SUM(ABS(amount)) AS "amount!"
+             FROM all_transactions
+             WHERE user_id = $1 AND transaction_type = 'expense'
+                 AND transaction_date >= $2 AND transaction_date <= $3 AND status = 'posted'
+             GROUP BY category
+             ORDER BY SUM(ABS(amount)) DESC, category
+             LIMIT $4"#,
+            user_id,
+            start,
+            end,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(categories)
+    }
+
+    async fn find_budget_status(&self, user_id: Uuid, start: NaiveDate, end: NaiveDate) -> Result<Vec<DigestBudget>, AppError> {
+        // Spending counts from the budget's own start up to the end of the digest month
+        let budgets = sqlx::query_as!(
+            DigestBudget,
+            r#"SELECT b.category, b.target_amount, b.period_end,
+                    COALESCE(SUM(ABS(t.amount)), 0) AS "spent_amount!"
+             FROM budgets b
+             LEFT JOIN all_transactions t ON t.user_id = b.user_id
+                 AND t.transaction_type = 'expense'
+                 AND (b.category IS NULL OR t.category = b.category)
+                 AND t.transaction_date >= b.period_start
+                 AND t.transaction_date <= LEAST(b.period_end, $3)
+                 AND t.status = 'posted'
+             WHERE b.user_id = $1 AND b.organization_id IS NULL AND b.is_active = true
+                 AND b.period_start <= $3 AND b.period_end >= $2
+             GROUP BY b.id, b.category, b.target_amount, b.period_end
+             ORDER BY b.category NULLS FIRST"#,
+            user_id,
+            start,
+            end
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(budgets)
+    }
+
+    async fn mark_sent(&self, user_id: Uuid, month: NaiveDate) -> Result<(), AppError> {
+        sqlx::query!("UPDATE users SET last_digest_month = $1 WHERE id = $2", month, user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
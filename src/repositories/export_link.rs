@@ -0,0 +1,133 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{ClientInfo, ExportLink, ExportLinkAccess};
+use crate::repositories::hashed_token::HashedTokenTable;
+use crate::utils::AppError;
+
+const EXPORT_LINKS: HashedTokenTable = HashedTokenTable {
+    table: "export_links",
+    columns: "id, user_id, label, token_hash, from_date, to_date, created_at, expires_at, last_used_at, revoked_at",
+    scope: &["user_id"],
+    expires: true,
+    not_found: "Export link not found",
+};
+
+#[async_trait::async_trait]
+pub trait ExportLinkRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, label: Option<&str>, token_hash: &str, from_date: NaiveDate, to_date: NaiveDate, expires_at: DateTime<Utc>) -> Result<ExportLink, AppError>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ExportLink>, AppError>;
+    async fn find_by_id(&self, id: Uuid, user_id: Uuid) -> Result<Option<ExportLink>, AppError>;
+    async fn count_active(&self, user_id: Uuid) -> Result<i64, AppError>;
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<ExportLink, AppError>;
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<ExportLink>, AppError>;
+    async fn record_access(&self, id: Uuid, resource: &str, client: &ClientInfo) -> Result<(), AppError>;
+    async fn find_access_log(&self, id: Uuid, limit: i64) -> Result<Vec<ExportLinkAccess>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresExportLinkRepository {
+    pool: PgPool,
+}
+
+impl PostgresExportLinkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExportLinkRepository for PostgresExportLinkRepository {
+    async fn create(&self, user_id: Uuid, label: Option<&str>, token_hash: &str, from_date: NaiveDate, to_date: NaiveDate, expires_at: DateTime<Utc>) -> Result<ExportLink, AppError> {
+        let link = sqlx::query_as!(
+            ExportLink,
+            r#"INSERT INTO export_links (id, user_id, label, token_hash, from_date, to_date, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING id, user_id, label, token_hash, from_date, to_date, created_at AS "created_at!", expires_at, last_used_at, revoked_at"#,
+            Uuid::new_v4(),
+            user_id,
+            label,
+            token_hash,
+            from_date,
+            to_date,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ExportLink>, AppError> {
+        EXPORT_LINKS.find_by_scope(&self.pool, &[user_id]).await
+    }
+
+    async fn find_by_id(&self, id: Uuid, user_id: Uuid) -> Result<Option<ExportLink>, AppError> {
+        let link = sqlx::query_as!(
+            ExportLink,
+            r#"SELECT id, user_id, label, token_hash, from_date, to_date, created_at AS "created_at!", expires_at, last_used_at, revoked_at
+             FROM export_links WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn count_active(&self, user_id: Uuid) -> Result<i64, AppError> {
+        EXPORT_LINKS.count_active(&self.pool, &[user_id]).await
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<ExportLink, AppError> {
+        EXPORT_LINKS.revoke(&self.pool, id, &[user_id]).await
+    }
+
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<ExportLink>, AppError> {
+        EXPORT_LINKS.find_active_by_hash(&self.pool, token_hash).await
+    }
+
+    async fn record_access(&self, id: Uuid, resource: &str, client: &ClientInfo) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO export_link_access_logs (export_link_id, resource, ip_address, user_agent)
+             VALUES ($1, $2, $3, $4)",
+            id,
+            resource,
+            client.ip_address,
+            client.user_agent
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE export_links SET last_used_at = NOW() WHERE id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_access_log(&self, id: Uuid, limit: i64) -> Result<Vec<ExportLinkAccess>, AppError> {
+        let entries = sqlx::query_as!(
+            ExportLinkAccess,
+            r#"SELECT id, resource, ip_address, user_agent, accessed_at AS "accessed_at!"
+             FROM export_link_access_logs
+             WHERE export_link_id = $1
+             ORDER BY accessed_at DESC
+             LIMIT $2"#,
+            id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
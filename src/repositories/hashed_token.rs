@@ -0,0 +1,106 @@
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::utils::AppError;
+
+// A table of bearer links stored by the hash of their token (pocket share tokens, export links,
+// analytics feeds). Listing, counting, revoking and resolving a link work the same for each, so
+// they live here; the repositories keep their own create and anything specific to the link.
+pub(crate) struct HashedTokenTable {
+    pub table: &'static str,
+    pub columns: &'static str,
+    // Columns that tie a link to its owner, bound in this order. Passing fewer values matches on
+    // the leading columns only, e.g. every share token of a pocket whoever created it
+    pub scope: &'static [&'static str],
+    // Whether the table has an expires_at column; NULL there means the link never expires
+    pub expires: bool,
+    pub not_found: &'static str,
+}
+
+impl HashedTokenTable {
+    fn scope_filter(&self, first_param: usize, scope: &[Uuid]) -> String {
+        self.scope[..scope.len()]
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format!("{} = ${}", column, first_param + i))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    fn active_filter(&self) -> &'static str {
+        if self.expires {
+            "revoked_at IS NULL AND (expires_at IS NULL OR expires_at > NOW())"
+        } else {
+            "revoked_at IS NULL"
+        }
+    }
+
+    pub async fn find_by_scope<T>(&self, pool: &PgPool, scope: &[Uuid]) -> Result<Vec<T>, AppError>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let sql = format!(
+            "SELECT {} FROM {} WHERE {} ORDER BY created_at DESC",
+            self.columns, self.table, self.scope_filter(1, scope)
+        );
+        let mut query = sqlx::query_as::<_, T>(&sql);
+        for id in scope {
+            query = query.bind(*id);
+        }
+
+        Ok(query.fetch_all(pool).await?)
+    }
+
+    pub async fn count_active(&self, pool: &PgPool, scope: &[Uuid]) -> Result<i64, AppError> {
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {} AND {}",
+            self.table, self.scope_filter(1, scope), self.active_filter()
+        );
+        let mut query = sqlx::query_scalar::<_, i64>(&sql);
+        for id in scope {
+            query = query.bind(*id);
+        }
+
+        Ok(query.fetch_one(pool).await?)
+    }
+
+    // Revoking twice keeps the original revocation time
+    pub async fn revoke<T>(&self, pool: &PgPool, id: Uuid, scope: &[Uuid]) -> Result<T, AppError>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let sql = format!(
+            "UPDATE {} SET revoked_at = COALESCE(revoked_at, NOW()) WHERE id = $1 AND {} RETURNING {}",
+            self.table, self.scope_filter(2, scope), self.columns
+        );
+        let mut query = sqlx::query_as::<_, T>(&sql).bind(id);
+        for id in scope {
+            query = query.bind(*id);
+        }
+
+        query
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(self.not_found.to_string()))
+    }
+
+    pub async fn find_active_by_hash<T>(&self, pool: &PgPool, token_hash: &str) -> Result<Option<T>, AppError>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let sql = format!(
+            "SELECT {} FROM {} WHERE token_hash = $1 AND {}",
+            self.columns, self.table, self.active_filter()
+        );
+
+        Ok(sqlx::query_as::<_, T>(&sql).bind(token_hash).fetch_optional(pool).await?)
+    }
+
+    pub async fn touch(&self, pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+        let sql = format!("UPDATE {} SET last_used_at = NOW() WHERE id = $1", self.table);
+        sqlx::query(&sql).bind(id).execute(pool).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::ImportCheckpoint;
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait ImportCheckpointRepository: Clone + Send + Sync {
+    // Returns the unfinished checkpoint for this file, creating one if there is none
+    async fn find_or_create(&self, user_id: Uuid, fingerprint: &str, total_rows: i32) -> Result<ImportCheckpoint, AppError>;
+    // Returns false when another upload has moved the checkpoint since it was read
+    async fn advance_with(&self, conn: &mut PgConnection, id: Uuid, expected_rows: i32, rows_committed: i32, completed: bool) -> Result<bool, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresImportCheckpointRepository {
+    pool: PgPool,
+}
+
+impl PostgresImportCheckpointRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ImportCheckpointRepository for PostgresImportCheckpointRepository {
+    async fn find_or_create(&self, user_id: Uuid, fingerprint: &str, total_rows: i32) -> Result<ImportCheckpoint, AppError> {
+        let checkpoint = sqlx::query_as!(
+            ImportCheckpoint,
+            r#"INSERT INTO import_checkpoints (id, user_id, fingerprint, total_rows)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id, fingerprint) WHERE completed_at IS NULL
+             DO UPDATE SET updated_at = NOW()
+             RETURNING id, user_id, fingerprint, total_rows, rows_committed, completed_at, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            Uuid::new_v4(),
+            user_id,
+            fingerprint,
+            total_rows
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(checkpoint)
+    }
+
+    async fn advance_with(&self, conn: &mut PgConnection, id: Uuid, expected_rows: i32, rows_committed: i32, completed: bool) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "UPDATE import_checkpoints
+             SET rows_committed = $1,
+                 completed_at = CASE WHEN $2 THEN NOW() ELSE NULL END,
+                 updated_at = NOW()
+             WHERE id = $3 AND rows_committed = $4 AND completed_at IS NULL",
+            rows_committed,
+            completed,
+            id,
+            expected_rows
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
\ No newline at end of file
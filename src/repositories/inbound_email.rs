@@ -0,0 +1,85 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{InboundEmailAddress, InboundEmailOwner};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait InboundEmailRepository: Clone + Send + Sync {
+    async fn find_address(&self, user_id: Uuid) -> Result<Option<InboundEmailAddress>, AppError>;
+    // Replaces any earlier address, which stops accepting mail at once
+    async fn replace_address(&self, user_id: Uuid, local_part: &str) -> Result<InboundEmailAddress, AppError>;
+    async fn find_owner(&self, local_part: &str) -> Result<Option<InboundEmailOwner>, AppError>;
+    async fn message_exists(&self, user_id: Uuid, message_id: &str) -> Result<bool, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresInboundEmailRepository {
+    pool: PgPool,
+}
+
+impl PostgresInboundEmailRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl InboundEmailRepository for PostgresInboundEmailRepository {
+    async fn find_address(&self, user_id: Uuid) -> Result<Option<InboundEmailAddress>, AppError> {
+        let address = sqlx::query_as!(
+            InboundEmailAddress,
+            "SELECT user_id, local_part, created_at FROM inbound_email_addresses WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(address)
+    }
+
+    async fn replace_address(&self, user_id: Uuid, local_part: &str) -> Result<InboundEmailAddress, AppError> {
+        let address = sqlx::query_as!(
+            InboundEmailAddress,
+            "INSERT INTO inbound_email_addresses (user_id, local_part)
+             VALUES ($1, $2)
+             ON CONFLICT (user_id) DO UPDATE SET local_part = EXCLUDED.local_part, created_at = NOW()
+             RETURNING user_id, local_part, created_at",
+            user_id,
+            local_part
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(address)
+    }
+
+    async fn find_owner(&self, local_part: &str) -> Result<Option<InboundEmailOwner>, AppError> {
+        let owner = sqlx::query_as!(
+            InboundEmailOwner,
+            "SELECT u.id AS user_id, u.email
+             FROM inbound_email_addresses a
+             JOIN users u ON u.id = a.user_id
+             WHERE a.local_part = $1",
+            local_part
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(owner)
+    }
+
+    async fn message_exists(&self, user_id: Uuid, message_id: &str) -> Result<bool, AppError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                 SELECT 1 FROM all_transactions WHERE user_id = $1 AND metadata->>'email_message_id' = $2
+             ) AS "exists!""#,
+            user_id,
+            message_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{Job, JOB_STATUS_COMPLETED, JOB_STATUS_FAILED, JOB_STATUS_QUEUED, JOB_STATUS_RUNNING};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait JobRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, job_type: &str) -> Result<Job, AppError>;
+    async fn find_by_id_for_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Job>, AppError>;
+    async fn find_recent_by_user_id(&self, user_id: Uuid, limit: i64) -> Result<Vec<Job>, AppError>;
+    async fn mark_running(&self, id: Uuid) -> Result<(), AppError>;
+    async fn update_progress(&self, id: Uuid, processed_items: i32, total_items: i32) -> Result<(), AppError>;
+    async fn mark_completed(&self, id: Uuid, result: serde_json::Value) -> Result<(), AppError>;
+    async fn mark_failed(&self, id: Uuid, error: &str) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresJobRepository {
+    pool: PgPool,
+}
+
+impl PostgresJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl JobRepository for PostgresJobRepository {
+    async fn create(&self, user_id: Uuid, job_type: &str) -> Result<Job, AppError> {
+        let job = sqlx::query_as!(
+            Job,
+            r#"INSERT INTO jobs (id, user_id, job_type, status, created_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, user_id, job_type, status, result, error, processed_items, total_items, created_at AS "created_at!", started_at, completed_at"#,
+            Uuid::new_v4(),
+            user_id,
+            job_type,
+            JOB_STATUS_QUEUED,
+            Utc::now()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn find_by_id_for_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Job>, AppError> {
+        let job = sqlx::query_as!(
+            Job,
+            r#"SELECT id, user_id, job_type, status, result, error, processed_items, total_items, created_at AS "created_at!", started_at, completed_at
+             FROM jobs WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn find_recent_by_user_id(&self, user_id: Uuid, limit: i64) -> Result<Vec<Job>, AppError> {
+        let jobs = sqlx::query_as!(
+            Job,
+            r#"SELECT id, user_id, job_type, status, result, error, processed_items, total_items, created_at AS "created_at!", started_at, completed_at
+             FROM jobs WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2"#,
+            user_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    async fn mark_running(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE jobs SET status = $1, started_at = $2 WHERE id = $3",
+            JOB_STATUS_RUNNING,
+            Utc::now(),
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_progress(&self, id: Uuid, processed_items: i32, total_items: i32) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE jobs SET processed_items = $1, total_items = $2 WHERE id = $3",
+            processed_items,
+            total_items,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_completed(&self, id: Uuid, result: serde_json::Value) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE jobs SET status = $1, result = $2, completed_at = $3 WHERE id = $4",
+            JOB_STATUS_COMPLETED,
+            result,
+            Utc::now(),
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: Uuid, error: &str) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE jobs SET status = $1, error = $2, completed_at = $3 WHERE id = $4",
+            JOB_STATUS_FAILED,
+            error,
+            Utc::now(),
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::LinkedAccount;
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait LinkedAccountRepository: Clone + Send + Sync {
+    // Replaces any unused code the user holds for the provider
+    async fn create_link_code(&self, user_id: Uuid, provider: &str, code_hash: &str, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+    // Spends an unexpired code and links the external account to its owner, moving the external
+    // account off any user it was linked to before
+    async fn redeem_link_code(&self, provider: &str, code_hash: &str, external_id: &str) -> Result<Option<LinkedAccount>, AppError>;
+    async fn find_by_external_id(&self, provider: &str, external_id: &str) -> Result<Option<LinkedAccount>, AppError>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<LinkedAccount>, AppError>;
+    async fn delete(&self, user_id: Uuid, provider: &str) -> Result<bool, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresLinkedAccountRepository {
+    pool: PgPool,
+}
+
+impl PostgresLinkedAccountRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl LinkedAccountRepository for PostgresLinkedAccountRepository {
+    async fn create_link_code(&self, user_id: Uuid, provider: &str, code_hash: &str, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM account_link_codes WHERE user_id = $1 AND (provider = $2 OR expires_at <= NOW())",
+            user_id,
+            provider
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO account_link_codes (code_hash, user_id, provider, expires_at)
+             VALUES ($1, $2, $3, $4)",
+            code_hash,
+            user_id,
+            provider,
+            expires_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn redeem_link_code(&self, provider: &str, code_hash: &str, external_id: &str) -> Result<Option<LinkedAccount>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let user_id = sqlx::query_scalar!(
+            "DELETE FROM account_link_codes
+             WHERE code_hash = $1 AND provider = $2 AND expires_at > NOW()
+             RETURNING user_id",
+            code_hash,
+            provider
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(user_id) = user_id else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "DELETE FROM linked_accounts WHERE provider = $1 AND external_id = $2",
+            provider,
+            external_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let account = sqlx::query_as!(
+            LinkedAccount,
+            "INSERT INTO linked_accounts (user_id, provider, external_id)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (user_id, provider) DO UPDATE SET external_id = EXCLUDED.external_id, created_at = NOW()
+             RETURNING id, user_id, provider, external_id, created_at",
+            user_id,
+            provider,
+            external_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(account))
+    }
+
+    async fn find_by_external_id(&self, provider: &str, external_id: &str) -> Result<Option<LinkedAccount>, AppError> {
+        let account = sqlx::query_as!(
+            LinkedAccount,
+            "SELECT id, user_id, provider, external_id, created_at
+             FROM linked_accounts WHERE provider = $1 AND external_id = $2",
+            provider,
+            external_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<LinkedAccount>, AppError> {
+        let accounts = sqlx::query_as!(
+            LinkedAccount,
+            "SELECT id, user_id, provider, external_id, created_at
+             FROM linked_accounts WHERE user_id = $1 ORDER BY provider",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    async fn delete(&self, user_id: Uuid, provider: &str) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM linked_accounts WHERE user_id = $1 AND provider = $2",
+            user_id,
+            provider
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
\ No newline at end of file
@@ -3,9 +3,60 @@ pub mod pocket;
 pub mod user;
 pub mod transaction;
 pub mod budget;
+pub mod spending_limit;
+pub mod session;
+pub mod unit_of_work;
+pub mod categorization;
+pub mod job;
+pub mod currency;
+pub mod audit;
+pub mod digest;
+pub mod share_token;
+mod hashed_token;
+pub mod reminder;
+pub mod refund;
+pub mod preference;
+pub mod export_link;
+pub mod analytics_feed;
+pub mod task_run;
+pub mod import_checkpoint;
+pub mod inbound_email;
+pub mod linked_account;
+pub mod device;
+pub mod read_preference;
+pub mod organization;
+pub mod debt;
+pub mod anomaly;
+pub mod page;
+pub mod monthly_aggregate;
 
 pub use auth::*;
 pub use pocket::*;
 pub use user::*;
 pub use transaction::*;
-pub use budget::*;
\ No newline at end of file
+pub use budget::*;
+pub use spending_limit::*;
+pub use session::*;
+pub use unit_of_work::*;
+pub use categorization::*;
+pub use job::*;
+pub use currency::*;
+pub use audit::*;
+pub use digest::*;
+pub use share_token::*;
+pub use reminder::*;
+pub use refund::*;
+pub use preference::*;
+pub use export_link::*;
+pub use analytics_feed::*;
+pub use task_run::*;
+pub use import_checkpoint::*;
+pub use inbound_email::*;
+pub use linked_account::*;
+pub use device::*;
+pub use read_preference::*;
+pub use organization::*;
+pub use debt::*;
+pub use anomaly::*;
+pub use page::*;
+pub use monthly_aggregate::*;
\ No newline at end of file
@@ -0,0 +1,148 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::MonthlyCategoryTotal;
+use crate::utils::AppError;
+
+// Posted income and expense for user $1, archived rows included, with received refunds netted
+// against their original expense and the refund credits left out, matching net_refunds
+const NETTED_TRANSACTIONS: &str = "
+    SELECT t.transaction_type, t.transaction_date, t.category,
+           GREATEST(ABS(t.amount) - COALESCE(refunded.amount, 0), 0) AS amount
+    FROM all_transactions t
+    LEFT JOIN (
+        SELECT r.original_transaction_id, SUM(r.received_amount) AS amount
+        FROM transaction_refunds r
+        JOIN all_transactions c ON c.id = r.refund_transaction_id
+        WHERE r.user_id = $1 AND r.status = 'received'
+        GROUP BY r.original_transaction_id
+    ) refunded ON refunded.original_transaction_id = t.id
+    WHERE t.user_id = $1 AND t.status = 'posted' AND t.transaction_type IN ('income', 'expense')
+        AND NOT EXISTS (
+            SELECT 1 FROM transaction_refunds r
+            WHERE r.refund_transaction_id = t.id AND r.status = 'received'
+        )";
+
+#[async_trait::async_trait]
+pub trait MonthlyAggregateRepository: Clone + Send + Sync {
+    // Recomputes the user's months marked stale by writes; returns how many were refreshed
+    async fn refresh_stale(&self, user_id: Uuid) -> Result<u64, AppError>;
+    // Totals per month and category within the range. Whole months come from the aggregates and
+    // partial months at either end are summed from transactions, so call refresh_stale first.
+    async fn find_totals(&self, user_id: Uuid, transaction_type: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<MonthlyCategoryTotal>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresMonthlyAggregateRepository {
+    pool: PgPool,
+}
+
+impl PostgresMonthlyAggregateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Marks the months of the given transactions, and of any expense they refund, stale on the
+// writer's connection so the mark commits with the write. Touching an existing mark locks it, which makes a concurrent refresh
+// wait for this write instead of recomputing the month without it.
+pub async fn mark_months_stale(conn: &mut PgConnection, user_id: Uuid, transaction_ids: &[i64]) -> Result<(), AppError> {
+    sqlx::query!(
+        "INSERT INTO monthly_aggregate_refreshes (user_id, month)
+         SELECT DISTINCT user_id, date_trunc('month', transaction_date)::date
+         FROM transactions
+         WHERE user_id = $1 AND (id = ANY($2) OR id IN (
+             SELECT original_transaction_id FROM transaction_refunds WHERE refund_transaction_id = ANY($2)
+         ))
+         ON CONFLICT (user_id, month) DO UPDATE SET marked_at = NOW()",
+        user_id,
+        transaction_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+fn next_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).and_then(|first| first.checked_add_months(Months::new(1))).unwrap_or(date)
+}
+
+#[async_trait::async_trait]
+impl MonthlyAggregateRepository for PostgresMonthlyAggregateRepository {
+    async fn refresh_stale(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let months = sqlx::query_scalar!(
+            "DELETE FROM monthly_aggregate_refreshes WHERE user_id = $1 RETURNING month",
+            user_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let (Some(first), Some(last)) = (months.iter().min(), months.iter().max()) else {
+            return Ok(0);
+        };
+        let (first, end) = (*first, next_month(*last));
+
+        sqlx::query!(
+            "DELETE FROM monthly_category_aggregates WHERE user_id = $1 AND month = ANY($2)",
+            user_id,
+            &months
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(&format!(
+            "INSERT INTO monthly_category_aggregates (user_id, transaction_type, month, category, total_amount, transaction_count)
+             SELECT $1, n.transaction_type, date_trunc('month', n.transaction_date)::date, n.category, SUM(n.amount), COUNT(*)
+             FROM ({}) n
+             WHERE n.transaction_date >= $2 AND n.transaction_date < $3
+                 AND date_trunc('month', n.transaction_date)::date = ANY($4)
+             GROUP BY 2, 3, 4",
+            NETTED_TRANSACTIONS
+        ))
+        .bind(user_id)
+        .bind(first)
+        .bind(end)
+        .bind(&months)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(months.len() as u64)
+    }
+
+    async fn find_totals(&self, user_id: Uuid, transaction_type: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<MonthlyCategoryTotal>, AppError> {
+        // Whole months as [full_from, full_to); an empty span past the range when there are none
+        let after_range = to_date + Days::new(1);
+        let full_from = if from_date.day() == 1 { from_date } else { next_month(from_date) };
+        let full_to = if after_range.day() == 1 { after_range } else { to_date.with_day(1).unwrap_or(to_date) };
+        let (full_from, full_to) = if full_from < full_to { (full_from, full_to) } else { (after_range, after_range) };
+
+        let totals = sqlx::query_as::<_, MonthlyCategoryTotal>(&format!(
+            "SELECT month, category, total_amount, transaction_count
+             FROM monthly_category_aggregates
+             WHERE user_id = $1 AND transaction_type = $2 AND month >= $3 AND month < $4
+             UNION ALL
+             SELECT date_trunc('month', n.transaction_date)::date, n.category, SUM(n.amount), COUNT(*)
+             FROM ({}) n
+             WHERE n.transaction_type = $2
+                 AND ((n.transaction_date >= $5 AND n.transaction_date < $3)
+                     OR (n.transaction_date >= $4 AND n.transaction_date <= $6))
+             GROUP BY 1, 2",
+            NETTED_TRANSACTIONS
+        ))
+        .bind(user_id)
+        .bind(transaction_type)
+        .bind(full_from)
+        .bind(full_to)
+        .bind(from_date)
+        .bind(to_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+}
\ No newline at end of file
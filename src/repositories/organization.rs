@@ -0,0 +1,236 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::{Organization, OrganizationMember, ORG_ROLE_OWNER};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait OrganizationRepository: Clone + Send + Sync {
+    async fn create(&self, name: &str, owner_id: Uuid) -> Result<Organization, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, AppError>;
+    async fn find_for_user(&self, user_id: Uuid) -> Result<Vec<(Organization, String)>, AppError>;
+    async fn update_name(&self, id: Uuid, name: &str) -> Result<Organization, AppError>;
+    async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    async fn find_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<OrganizationMember>, AppError>;
+    async fn list_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, AppError>;
+    async fn add_member(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<OrganizationMember, AppError>;
+    // Both refuse to take away the last owner
+    async fn update_member_role(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<OrganizationMember, AppError>;
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresOrganizationRepository {
+    pool: PgPool,
+}
+
+impl PostgresOrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl OrganizationRepository for PostgresOrganizationRepository {
+    // The creator becomes the first owner in the same transaction, so no organization is ever ownerless
+    async fn create(&self, name: &str, owner_id: Uuid) -> Result<Organization, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let organization = sqlx::query_as!(
+            Organization,
+            "INSERT INTO organizations (id, name) VALUES ($1, $2)
+             RETURNING id, name, created_at, updated_at",
+            Uuid::new_v4(),
+            name
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO organization_members (organization_id, user_id, role) VALUES ($1, $2, $3)",
+            organization.id,
+            owner_id,
+            ORG_ROLE_OWNER
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(organization)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, AppError> {
+        let organization = sqlx::query_as!(
+            Organization,
+            "SELECT id, name, created_at, updated_at FROM organizations WHERE id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(organization)
+    }
+
+    async fn find_for_user(&self, user_id: Uuid) -> Result<Vec<(Organization, String)>, AppError> {
+        let rows = sqlx::query!(
+            "SELECT o.id, o.name, o.created_at, o.updated_at, m.role
+             FROM organizations o
+             JOIN organization_members m ON m.organization_id = o.id
+             WHERE m.user_id = $1
+             ORDER BY o.name ASC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (Organization { id: row.id, name: row.name, created_at: row.created_at, updated_at: row.updated_at }, row.role))
+            .collect())
+    }
+
+    async fn update_name(&self, id: Uuid, name: &str) -> Result<Organization, AppError> {
+        let organization = sqlx::query_as!(
+            Organization,
+            "UPDATE organizations SET name = $2, updated_at = NOW() WHERE id = $1
+             RETURNING id, name, created_at, updated_at",
+            id,
+            name
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+        Ok(organization)
+    }
+
+    // Shared pockets, budgets and memberships go with it through ON DELETE CASCADE
+    async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM organizations WHERE id = $1",
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Organization not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn find_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<OrganizationMember>, AppError> {
+        let member = sqlx::query_as!(
+            OrganizationMember,
+            "SELECT m.organization_id, m.user_id, u.email, u.name, m.role, m.created_at FROM organization_members m JOIN users u ON u.id = m.user_id
+             WHERE m.organization_id = $1 AND m.user_id = $2",
+            organization_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(member)
+    }
+
+    async fn list_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, AppError> {
+        let members = sqlx::query_as!(
+            OrganizationMember,
+            "SELECT m.organization_id, m.user_id, u.email, u.name, m.role, m.created_at FROM organization_members m JOIN users u ON u.id = m.user_id
+             WHERE m.organization_id = $1
+             ORDER BY m.created_at ASC",
+            organization_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    async fn add_member(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<OrganizationMember, AppError> {
+        let inserted = sqlx::query!(
+            "INSERT INTO organization_members (organization_id, user_id, role) VALUES ($1, $2, $3)
+             ON CONFLICT (organization_id, user_id) DO NOTHING",
+            organization_id,
+            user_id,
+            role
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if inserted.rows_affected() == 0 {
+            return Err(AppError::Conflict("User is already a member of this organization".to_string()));
+        }
+
+        self.find_member(organization_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))
+    }
+
+    async fn update_member_role(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<OrganizationMember, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        if role != ORG_ROLE_OWNER {
+            ensure_other_owner(&mut tx, organization_id, user_id).await?;
+        }
+
+        let result = sqlx::query!(
+            "UPDATE organization_members SET role = $3 WHERE organization_id = $1 AND user_id = $2",
+            organization_id,
+            user_id,
+            role
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Member not found".to_string()));
+        }
+
+        tx.commit().await?;
+
+        self.find_member(organization_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))
+    }
+
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        ensure_other_owner(&mut tx, organization_id, user_id).await?;
+
+        let result = sqlx::query!(
+            "DELETE FROM organization_members WHERE organization_id = $1 AND user_id = $2",
+            organization_id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Member not found".to_string()));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+// Locks the organization's owner rows until the transaction ends, so two owners demoting or
+// removing each other at once can't both see the other one still in place
+async fn ensure_other_owner(tx: &mut Transaction<'_, Postgres>, organization_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    let owners = sqlx::query_scalar!(
+        "SELECT user_id FROM organization_members WHERE organization_id = $1 AND role = $2 ORDER BY user_id FOR UPDATE",
+        organization_id,
+        ORG_ROLE_OWNER
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    if owners.len() <= 1 && owners.contains(&user_id) {
+        return Err(AppError::Conflict("An organization must keep at least one owner".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+// One page of a list along with how many rows match across every page
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+}
+
+// A list row that also selected `COUNT(*) OVER() AS total_items`, so the page and its
+// total come back in one round trip. The window is counted before LIMIT and OFFSET apply.
+pub struct Counted<T> {
+    item: T,
+    total_items: i64,
+}
+
+impl<'r, T: FromRow<'r, PgRow>> FromRow<'r, PgRow> for Counted<T> {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            item: T::from_row(row)?,
+            total_items: row.try_get("total_items")?,
+        })
+    }
+}
+
+impl<T> Page<T> {
+    // None for an empty page past the first: no row carried the total, so the caller counts
+    pub fn from_counted(rows: Vec<Counted<T>>, offset: i64) -> Option<Self> {
+        match rows.first() {
+            Some(first) => {
+                let total_items = first.total_items;
+                Some(Self { items: rows.into_iter().map(|row| row.item).collect(), total_items })
+            }
+            None if offset == 0 => Some(Self::empty(0)),
+            None => None,
+        }
+    }
+
+    pub fn empty(total_items: i64) -> Self {
+        Self { items: Vec::new(), total_items }
+    }
+}
\ No newline at end of file
@@ -1,17 +1,34 @@
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
-use sqlx::{PgPool, Row};
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
-use crate::models::{Pocket, CreatePocketRequest, UpdatePocketRequest};
-use crate::utils::AppError;
+use crate::models::{Pocket, PocketQuickBalance, PocketBalanceCorrection, BalanceRepairReport, CreatePocketRequest, UpdatePocketRequest, PocketBalanceSnapshot, Precondition};
+use crate::utils::{AppError, codes};
+use crate::policy::{Action, permits_as};
 
 #[async_trait::async_trait]
 pub trait PocketRepository: Clone + Send + Sync {
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Pocket>, AppError>;
-    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Pocket>, AppError>;
+    async fn find_by_id_for_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Pocket>, AppError>;
+    // A personal pocket of the user's, or a shared one of an organization they belong to along
+    // with their role in it
+    async fn find_by_id_with_role(&self, id: Uuid, user_id: Uuid) -> Result<Option<(Pocket, Option<String>)>, AppError>;
+    async fn find_by_user_id(&self, user_id: Uuid, include_archived: bool) -> Result<Vec<Pocket>, AppError>;
+    async fn find_by_ids(&self, user_id: Uuid, ids: &[Uuid]) -> Result<Vec<Pocket>, AppError>;
+    async fn find_quick_balances(&self, user_id: Uuid) -> Result<Vec<PocketQuickBalance>, AppError>;
     async fn create(&self, user_id: Uuid, request: &CreatePocketRequest) -> Result<Pocket, AppError>;
-    async fn update(&self, id: Uuid, user_id: Uuid, request: &UpdatePocketRequest) -> Result<Pocket, AppError>;
-    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+    async fn find_by_organization_id(&self, organization_id: Uuid, include_archived: bool) -> Result<Vec<Pocket>, AppError>;
+    async fn create_in_organization(&self, organization_id: Uuid, user_id: Uuid, request: &CreatePocketRequest) -> Result<Pocket, AppError>;
+    async fn update(&self, id: Uuid, user_id: Uuid, request: &UpdatePocketRequest, precondition: &Precondition) -> Result<Pocket, AppError>;
+    async fn set_archived(&self, id: Uuid, user_id: Uuid, archived: bool) -> Result<Pocket, AppError>;
+    async fn reorder(&self, user_id: Uuid, ids: &[Uuid], groups: &[Option<String>]) -> Result<(), AppError>;
+    async fn delete(&self, id: Uuid, user_id: Uuid, reassign_to: Option<Uuid>) -> Result<(), AppError>;
+    async fn adjust_balance_with(&self, conn: &mut PgConnection, id: Uuid, user_id: Uuid, delta: Decimal) -> Result<(), AppError>;
+    async fn find_by_id_for_update_with(&self, conn: &mut PgConnection, id: Uuid, user_id: Uuid) -> Result<Option<Pocket>, AppError>;
+    async fn create_with(&self, conn: &mut PgConnection, user_id: Uuid, request: &CreatePocketRequest) -> Result<Pocket, AppError>;
+    async fn create_snapshot_with(&self, conn: &mut PgConnection, pocket_id: Uuid, user_id: Uuid, month: NaiveDate, balance: Decimal) -> Result<PocketBalanceSnapshot, AppError>;
+    async fn refresh_current_snapshots(&self, user_id: Uuid) -> Result<u64, AppError>;
+    async fn repair_balances(&self, user_id: Option<Uuid>, dry_run: bool) -> Result<BalanceRepairReport, AppError>;
 }
 
 #[derive(Clone)]
@@ -27,12 +44,13 @@ impl PostgresPocketRepository {
 
 #[async_trait::async_trait]
 impl PocketRepository for PostgresPocketRepository {
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Pocket>, AppError> {
-        let row = sqlx::query(
-            "SELECT id, user_id, name, emoji, balance, created_at, updated_at 
-             FROM pockets WHERE id = $1"
+    async fn find_by_id_for_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Pocket>, AppError> {
+        let row = sqlx::query!(
+            r#"SELECT id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group, currency, created_at AS "created_at!", updated_at AS "updated_at!" 
+             FROM pockets WHERE id = $1 AND user_id = $2 AND organization_id IS NULL"#,
+            id,
+            user_id
         )
-        .bind(id)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -40,13 +58,18 @@ impl PocketRepository for PostgresPocketRepository {
         match row {
             Some(row) => {
                 let pocket = Pocket {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            name: row.get("name"),
-            emoji: row.get("emoji"),
-            balance: row.get("balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            user_id: row.user_id,
+            organization_id: row.organization_id,
+            name: row.name,
+            emoji: row.emoji,
+            balance: row.balance,
+            archived: row.archived,
+            sort_order: row.sort_order,
+            group: row.pocket_group,
+            currency: row.currency,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         };
                 Ok(Some(pocket))
             }
@@ -54,107 +77,384 @@ impl PocketRepository for PostgresPocketRepository {
         }
     }
 
-    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Pocket>, AppError> {
-        let rows = sqlx::query(
-            "SELECT id, user_id, name, emoji, balance, created_at, updated_at 
-             FROM pockets WHERE user_id = $1 ORDER BY created_at DESC"
+    async fn find_by_id_with_role(&self, id: Uuid, user_id: Uuid) -> Result<Option<(Pocket, Option<String>)>, AppError> {
+        let row = sqlx::query!(
+            r#"SELECT p.id, p.user_id, p.organization_id, p.name, p.emoji, p.balance AS "balance!", p.archived, p.sort_order, p.pocket_group, p.currency,
+                    p.created_at AS "created_at!", p.updated_at AS "updated_at!", m.role AS "role?"
+             FROM pockets p
+             LEFT JOIN organization_members m ON m.organization_id = p.organization_id AND m.user_id = $2
+             WHERE p.id = $1 AND ((p.organization_id IS NULL AND p.user_id = $2) OR m.user_id IS NOT NULL)"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| {
+            let pocket = Pocket {
+                id: row.id,
+                user_id: row.user_id,
+                organization_id: row.organization_id,
+                name: row.name,
+                emoji: row.emoji,
+                balance: row.balance,
+                archived: row.archived,
+                sort_order: row.sort_order,
+                group: row.pocket_group,
+                currency: row.currency,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+            (pocket, row.role)
+        }))
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid, include_archived: bool) -> Result<Vec<Pocket>, AppError> {
+        let rows = sqlx::query!(
+            r#"SELECT id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group, currency, created_at AS "created_at!", updated_at AS "updated_at!" 
+             FROM pockets WHERE user_id = $1 AND organization_id IS NULL AND ($2 OR archived = false) ORDER BY sort_order, created_at DESC"#,
+            user_id,
+            include_archived
         )
-        .bind(user_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         let pockets = rows.into_iter().map(|row| Pocket {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            name: row.get("name"),
-            emoji: row.get("emoji"),
-            balance: row.get("balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            user_id: row.user_id,
+            organization_id: row.organization_id,
+            name: row.name,
+            emoji: row.emoji,
+            balance: row.balance,
+            archived: row.archived,
+            sort_order: row.sort_order,
+            group: row.pocket_group,
+            currency: row.currency,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         }).collect();
 
         Ok(pockets)
     }
 
+    async fn find_by_ids(&self, user_id: Uuid, ids: &[Uuid]) -> Result<Vec<Pocket>, AppError> {
+        let rows = sqlx::query!(
+            r#"SELECT id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group, currency, created_at AS "created_at!", updated_at AS "updated_at!" 
+             FROM pockets WHERE user_id = $1 AND organization_id IS NULL AND id = ANY($2)"#,
+            user_id,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let pockets = rows.into_iter().map(|row| Pocket {
+            id: row.id,
+            user_id: row.user_id,
+            organization_id: row.organization_id,
+            name: row.name,
+            emoji: row.emoji,
+            balance: row.balance,
+            archived: row.archived,
+            sort_order: row.sort_order,
+            group: row.pocket_group,
+            currency: row.currency,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }).collect();
+
+        Ok(pockets)
+    }
+
+    async fn find_quick_balances(&self, user_id: Uuid) -> Result<Vec<PocketQuickBalance>, AppError> {
+        let balances = sqlx::query_as!(
+            PocketQuickBalance,
+            r#"SELECT id, name, balance AS "balance!"
+             FROM pockets WHERE user_id = $1 AND organization_id IS NULL AND archived = false ORDER BY sort_order, created_at DESC"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(balances)
+    }
+
     async fn create(&self, user_id: Uuid, request: &CreatePocketRequest) -> Result<Pocket, AppError> {
         let pocket_id = Uuid::new_v4();
         let now = chrono::Utc::now();
 
-        let row = sqlx::query(
-            "INSERT INTO pockets (id, user_id, name, emoji, balance, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5, $6, $7) 
-             RETURNING id, user_id, name, emoji, balance, created_at, updated_at"
-        )
-        .bind(pocket_id)
-        .bind(user_id)
-        .bind(&request.name)
-        .bind(&request.emoji)
-        .bind(Decimal::ZERO)
-        .bind(now)
-        .bind(now)
+        let row = sqlx::query!(
+            r#"INSERT INTO pockets (id, user_id, name, emoji, balance, pocket_group, currency, sort_order, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, (SELECT base_currency FROM users WHERE id = $2)),
+                     (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM pockets WHERE user_id = $2 AND organization_id IS NULL), $8, $9) 
+             RETURNING id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group, currency, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            pocket_id,
+            user_id,
+            request.name,
+            request.emoji,
+            Decimal::ZERO,
+            request.group,
+            request.currency,
+            now,
+            now
+        )
         .fetch_one(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         let pocket = Pocket {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            name: row.get("name"),
-            emoji: row.get("emoji"),
-            balance: row.get("balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            user_id: row.user_id,
+            organization_id: row.organization_id,
+            name: row.name,
+            emoji: row.emoji,
+            balance: row.balance,
+            archived: row.archived,
+            sort_order: row.sort_order,
+            group: row.pocket_group,
+            currency: row.currency,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         };
 
         Ok(pocket)
     }
 
-    async fn update(&self, id: Uuid, user_id: Uuid, request: &UpdatePocketRequest) -> Result<Pocket, AppError> {
-        // First check if pocket exists and belongs to user
-        let existing_pocket = self.find_by_id(id).await?;
-        let pocket = existing_pocket.ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
-        
-        if pocket.user_id != user_id {
-            return Err(AppError::Forbidden("Access denied".to_string()));
-        }
+    async fn find_by_organization_id(&self, organization_id: Uuid, include_archived: bool) -> Result<Vec<Pocket>, AppError> {
+        let pockets = sqlx::query_as!(
+            Pocket,
+            r#"SELECT id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group AS "group", currency, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM pockets WHERE organization_id = $1 AND ($2 OR archived = false) ORDER BY sort_order, created_at DESC"#,
+            organization_id,
+            include_archived
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        // Simple update with all fields
-        let row = sqlx::query(
-            "UPDATE pockets SET name = COALESCE($1, name), emoji = COALESCE($2, emoji), updated_at = NOW()
-             WHERE id = $3 AND user_id = $4
-             RETURNING id, user_id, name, emoji, balance, created_at, updated_at"
-        )
-        .bind(request.name.as_ref())
-        .bind(request.emoji.as_ref())
-        .bind(id)
-        .bind(user_id)
+        Ok(pockets)
+    }
+
+    // The creator's base currency is the default, as for personal pockets
+    async fn create_in_organization(&self, organization_id: Uuid, user_id: Uuid, request: &CreatePocketRequest) -> Result<Pocket, AppError> {
+        let now = chrono::Utc::now();
+
+        let pocket = sqlx::query_as!(
+            Pocket,
+            r#"INSERT INTO pockets (id, user_id, organization_id, name, emoji, balance, pocket_group, currency, sort_order, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, (SELECT base_currency FROM users WHERE id = $2)),
+                     (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM pockets WHERE organization_id = $3), $9, $9)
+             RETURNING id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group AS "group", currency, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            Uuid::new_v4(),
+            user_id,
+            organization_id,
+            request.name,
+            request.emoji,
+            Decimal::ZERO,
+            request.group,
+            request.currency,
+            now
+        )
         .fetch_one(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
+
+        Ok(pocket)
+    }
+
+    async fn update(&self, id: Uuid, user_id: Uuid, request: &UpdatePocketRequest, precondition: &Precondition) -> Result<Pocket, AppError> {
+        // First check if pocket exists and the user may change it
+        let (existing, _) = self.find_by_id_with_role(id, user_id)
+            .await?
+            .filter(|(pocket, role)| permits_as(user_id, role.as_deref(), Action::Write, pocket))
+            .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
+        precondition.check(existing.updated_at)?;
+
+        // Simple update with all fields
+        let row = sqlx::query!(
+            r#"UPDATE pockets SET name = COALESCE($1, name), emoji = COALESCE($2, emoji),
+                 pocket_group = CASE WHEN $3::text IS NULL THEN pocket_group ELSE NULLIF($3, '') END,
+                 updated_at = NOW()
+             WHERE id = $4 AND ($6::timestamptz IS NULL OR updated_at = $6)
+                 AND ((organization_id IS NULL AND user_id = $5)
+                     OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = $5 AND role IN ('owner', 'admin')))
+             RETURNING id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group, currency, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            request.name.as_ref(),
+            request.emoji.as_ref(),
+            request.group.as_ref(),
+            id,
+            user_id,
+            precondition.expected(existing.updated_at)
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| match precondition.expected(existing.updated_at) {
+            // Another write landed between the check and this update
+            Some(_) => Precondition::conflict(),
+            // Or the pocket was deleted, or the caller lost the role to change it
+            None => AppError::NotFound("Pocket not found".to_string()),
+        })?;
 
         let pocket = Pocket {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            name: row.get("name"),
-            emoji: row.get("emoji"),
-            balance: row.get("balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            user_id: row.user_id,
+            organization_id: row.organization_id,
+            name: row.name,
+            emoji: row.emoji,
+            balance: row.balance,
+            archived: row.archived,
+            sort_order: row.sort_order,
+            group: row.pocket_group,
+            currency: row.currency,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         };
 
         Ok(pocket)
     }
 
-    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
-        let result = sqlx::query(
-            "DELETE FROM pockets WHERE id = $1 AND user_id = $2"
+    async fn set_archived(&self, id: Uuid, user_id: Uuid, archived: bool) -> Result<Pocket, AppError> {
+        let pocket = sqlx::query_as!(
+            Pocket,
+            r#"UPDATE pockets SET archived = $1, updated_at = NOW()
+             WHERE id = $2 AND user_id = $3 AND organization_id IS NULL
+             RETURNING id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group AS "group", currency, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            archived,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        pocket.ok_or_else(|| AppError::NotFound("Pocket not found or access denied".to_string()))
+    }
+
+    // Positions follow the order of `ids`, all rows change in a single statement
+    async fn reorder(&self, user_id: Uuid, ids: &[Uuid], groups: &[Option<String>]) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE pockets p
+             SET sort_order = v.position::int, pocket_group = v.pocket_group, updated_at = NOW()
+             FROM UNNEST($1::uuid[], $2::text[]) WITH ORDINALITY AS v(id, pocket_group, position)
+             WHERE p.id = v.id AND p.user_id = $3 AND p.organization_id IS NULL",
+            ids,
+            groups as _,
+            user_id
         )
-        .bind(id)
-        .bind(user_id)
         .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
+
+        Ok(())
+    }
+
+    // Refuses to orphan transactions: they either move to `reassign_to`, carrying
+    // their balance effect with them, or the delete fails and the pocket should be archived
+    async fn delete(&self, id: Uuid, user_id: Uuid, reassign_to: Option<Uuid>) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let source_currency = sqlx::query_scalar!(
+            "SELECT currency FROM pockets WHERE id = $1 AND user_id = $2 AND organization_id IS NULL FOR UPDATE",
+            id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Pocket not found or access denied".to_string()))?;
+
+        match reassign_to {
+            Some(target_id) => {
+                if target_id == id {
+                    return Err(AppError::ValidationError("Cannot reassign transactions to the pocket being deleted".to_string()));
+                }
+
+                let target = sqlx::query!(
+                    "SELECT archived, currency FROM pockets WHERE id = $1 AND user_id = $2 AND organization_id IS NULL FOR UPDATE",
+                    target_id,
+                    user_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Target pocket not found".to_string()))?;
+
+                if target.archived {
+                    return Err(AppError::ValidationError("Cannot reassign transactions to an archived pocket".to_string()).with_code(codes::POCKET_ARCHIVED));
+                }
+
+                // Moved amounts stay in the currency they were recorded in
+                if target.currency != source_currency {
+                    return Err(AppError::ValidationError(format!(
+                        "Cannot reassign {} transactions to a {} pocket",
+                        source_currency, target.currency
+                    )));
+                }
+
+                let moved_balance = sqlx::query_scalar!(
+                    r#"WITH moved AS (
+                         UPDATE transactions SET account_id = $1, updated_at = NOW()
+                         WHERE account_id = $2 AND user_id = $3
+                         RETURNING amount, transaction_type, status
+                     ), moved_archived AS (
+                         UPDATE transactions_archive SET account_id = $1
+                         WHERE account_id = $2 AND user_id = $3
+                         RETURNING amount, transaction_type, status
+                     )
+                     SELECT COALESCE(SUM(CASE
+                         WHEN status <> 'posted' THEN 0
+                         WHEN transaction_type = 'expense' THEN -ABS(amount)
+                         WHEN transaction_type = 'adjustment' THEN amount
+                         ELSE ABS(amount)
+                     END), 0) AS "moved_balance!"
+                     FROM (SELECT * FROM moved UNION ALL SELECT * FROM moved_archived) m"#,
+                    target_id,
+                    id,
+                    user_id
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+
+                self.adjust_balance_with(&mut tx, target_id, user_id, moved_balance).await?;
+            }
+            None => {
+                let referencing = sqlx::query_scalar!(
+                    r#"SELECT (SELECT COUNT(*) FROM transactions WHERE account_id = $1 AND user_id = $2)
+                     + (SELECT COUNT(*) FROM transactions_archive WHERE account_id = $1 AND user_id = $2) AS "count!""#,
+                    id,
+                    user_id
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+
+                if referencing > 0 {
+                    return Err(AppError::Conflict(format!(
+                        "Pocket has {} transactions; archive it or pass reassign_to to move them",
+                        referencing
+                    )));
+                }
+            }
+        }
+
+        sqlx::query!(
+            "DELETE FROM pockets WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Shared pockets hold every member's transactions, so any member may move their balance
+    async fn adjust_balance_with(&self, conn: &mut PgConnection, id: Uuid, user_id: Uuid, delta: Decimal) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            "UPDATE pockets SET balance = balance + $1, updated_at = NOW()
+             WHERE id = $2 AND ((organization_id IS NULL AND user_id = $3)
+                 OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = $3))",
+            delta,
+            id,
+            user_id
+        )
+        .execute(&mut *conn)
+        .await?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound("Pocket not found or access denied".to_string()));
@@ -162,4 +462,131 @@ impl PocketRepository for PostgresPocketRepository {
 
         Ok(())
     }
+    async fn find_by_id_for_update_with(&self, conn: &mut PgConnection, id: Uuid, user_id: Uuid) -> Result<Option<Pocket>, AppError> {
+        let pocket = sqlx::query_as!(
+            Pocket,
+            r#"SELECT id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group AS "group", currency, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM pockets WHERE id = $1 AND user_id = $2 AND organization_id IS NULL FOR UPDATE"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(pocket)
+    }
+
+    async fn create_with(&self, conn: &mut PgConnection, user_id: Uuid, request: &CreatePocketRequest) -> Result<Pocket, AppError> {
+        let now = chrono::Utc::now();
+
+        let pocket = sqlx::query_as!(
+            Pocket,
+            r#"INSERT INTO pockets (id, user_id, name, emoji, balance, pocket_group, currency, sort_order, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, (SELECT base_currency FROM users WHERE id = $2)),
+                     (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM pockets WHERE user_id = $2 AND organization_id IS NULL), $8, $9) 
+             RETURNING id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group AS "group", currency, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            Uuid::new_v4(),
+            user_id,
+            request.name,
+            request.emoji,
+            Decimal::ZERO,
+            request.group,
+            request.currency,
+            now,
+            now
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(pocket)
+    }
+
+    async fn create_snapshot_with(&self, conn: &mut PgConnection, pocket_id: Uuid, user_id: Uuid, month: NaiveDate, balance: Decimal) -> Result<PocketBalanceSnapshot, AppError> {
+        let snapshot = sqlx::query_as!(
+            PocketBalanceSnapshot,
+            r#"INSERT INTO pocket_balance_snapshots (pocket_id, user_id, snapshot_month, balance)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id, pocket_id, user_id, snapshot_month, balance, created_at AS "created_at!""#,
+            pocket_id,
+            user_id,
+            month,
+            balance
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(snapshot)
+    }
+    // Writes each pocket's live balance into this month's snapshot
+    async fn refresh_current_snapshots(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            "INSERT INTO pocket_balance_snapshots (pocket_id, user_id, snapshot_month, balance)
+             SELECT id, user_id, date_trunc('month', CURRENT_DATE)::date, balance
+             FROM pockets WHERE user_id = $1 AND organization_id IS NULL
+             ON CONFLICT (pocket_id, snapshot_month) DO UPDATE SET balance = EXCLUDED.balance",
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+    async fn repair_balances(&self, user_id: Option<Uuid>, dry_run: bool) -> Result<BalanceRepairReport, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Hold the pockets so no transaction lands between replaying and writing
+        let pockets_checked = if dry_run {
+            sqlx::query_scalar!(
+                "SELECT id FROM pockets WHERE ($1::uuid IS NULL OR user_id = $1)",
+                user_id
+            )
+            .fetch_all(&mut *tx)
+            .await?
+            .len()
+        } else {
+            sqlx::query_scalar!(
+                "SELECT id FROM pockets WHERE ($1::uuid IS NULL OR user_id = $1) FOR UPDATE",
+                user_id
+            )
+            .fetch_all(&mut *tx)
+            .await?
+            .len()
+        };
+
+        // One replay for both modes; the dry run skips the write. The UPDATE runs whether or not
+        // the outer SELECT reads it, and that SELECT still sees the balances from before it.
+        let corrections = sqlx::query_as!(
+            PocketBalanceCorrection,
+            r#"WITH expected AS (
+                 SELECT p.id, p.balance AS previous_balance, COALESCE(SUM(CASE
+                     WHEN t.status <> 'posted' THEN 0
+                     WHEN t.transaction_type = 'expense' THEN -ABS(t.amount)
+                     WHEN t.transaction_type = 'adjustment' THEN t.amount
+                     ELSE ABS(t.amount)
+                 END), 0) AS balance
+                 FROM pockets p
+                 -- A shared pocket holds every member's transactions, a personal one only its owner's
+                 LEFT JOIN all_transactions t ON t.account_id = p.id AND (p.organization_id IS NOT NULL OR t.user_id = p.user_id)
+                 WHERE ($1::uuid IS NULL OR p.user_id = $1)
+                 GROUP BY p.id, p.balance
+             ),
+             repaired AS (
+                 UPDATE pockets SET balance = expected.balance, updated_at = NOW()
+                 FROM expected
+                 WHERE NOT $2 AND pockets.id = expected.id AND expected.previous_balance <> expected.balance
+             )
+             SELECT p.id AS pocket_id, p.user_id, p.organization_id, p.name, expected.previous_balance AS "previous_balance!",
+                    expected.balance AS "balance!", expected.previous_balance - expected.balance AS "drift!"
+             FROM pockets p JOIN expected ON expected.id = p.id
+             WHERE expected.previous_balance <> expected.balance
+             ORDER BY p.organization_id NULLS FIRST, p.user_id, p.sort_order"#,
+            user_id,
+            dry_run
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(BalanceRepairReport { dry_run, pockets_checked, corrections })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{UpdatePreferencesRequest, UserPreferences, DEFAULT_LOCALE, DEFAULT_TIMEZONE, DEFAULT_WEEK_START};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait PreferenceRepository: Clone + Send + Sync {
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<UserPreferences>, AppError>;
+    async fn upsert(&self, user_id: Uuid, request: &UpdatePreferencesRequest) -> Result<UserPreferences, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresPreferenceRepository {
+    pool: PgPool,
+}
+
+impl PostgresPreferenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl PreferenceRepository for PostgresPreferenceRepository {
+    // Joined from users so that a missing preferences row still yields the defaults
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<UserPreferences>, AppError> {
+        let preferences = sqlx::query_as!(
+            UserPreferences,
+            r#"SELECT COALESCE(p.currency, u.base_currency) AS "currency!",
+                    COALESCE(p.locale, $2) AS "locale!",
+                    COALESCE(p.week_start, $3) AS "week_start!",
+                    COALESCE(p.timezone, $4) AS "timezone!",
+                    p.updated_at AS "updated_at?"
+             FROM users u
+             LEFT JOIN user_preferences p ON p.user_id = u.id
+             WHERE u.id = $1"#,
+            user_id,
+            DEFAULT_LOCALE,
+            DEFAULT_WEEK_START,
+            DEFAULT_TIMEZONE
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    async fn upsert(&self, user_id: Uuid, request: &UpdatePreferencesRequest) -> Result<UserPreferences, AppError> {
+        let preferences = sqlx::query_as!(
+            UserPreferences,
+            r#"INSERT INTO user_preferences (user_id, currency, locale, week_start, timezone)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (user_id) DO UPDATE
+                 SET currency = EXCLUDED.currency,
+                     locale = EXCLUDED.locale,
+                     week_start = EXCLUDED.week_start,
+                     timezone = EXCLUDED.timezone,
+                     updated_at = NOW()
+             RETURNING currency, locale, week_start, timezone, updated_at AS "updated_at!""#,
+            user_id,
+            request.currency,
+            request.locale,
+            request.week_start,
+            request.timezone
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(preferences)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use sqlx::PgPool;
+
+// Which pool a read is served from. The replica can trail the primary slightly, so reads
+// that must see a write made moments earlier ask for Primary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadPreference {
+    Primary,
+    Replica,
+}
+
+// Falls back to the primary when no replica is configured
+pub fn read_pool<'a>(primary: &'a PgPool, replica: Option<&'a PgPool>, read: ReadPreference) -> &'a PgPool {
+    match (read, replica) {
+        (ReadPreference::Replica, Some(replica)) => replica,
+        _ => primary,
+    }
+}
\ No newline at end of file
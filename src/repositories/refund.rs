@@ -0,0 +1,164 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::Refund;
+use crate::repositories::mark_months_stale;
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait RefundRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, original_transaction_id: i64, amount: Decimal, note: Option<&str>) -> Result<Refund, AppError>;
+    async fn find_by_id(&self, id: i64, user_id: Uuid) -> Result<Option<Refund>, AppError>;
+    async fn find_by_original(&self, original_transaction_id: i64, user_id: Uuid) -> Result<Vec<Refund>, AppError>;
+    async fn find_by_user(&self, user_id: Uuid, status: Option<&str>) -> Result<Vec<Refund>, AppError>;
+    async fn claimed_amount(&self, original_transaction_id: i64, exclude_id: Option<i64>) -> Result<Decimal, AppError>;
+    async fn mark_received(&self, id: i64, user_id: Uuid, refund_transaction_id: i64, amount: Decimal) -> Result<Refund, AppError>;
+    async fn mark_written_off(&self, id: i64, user_id: Uuid) -> Result<Refund, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresRefundRepository {
+    pool: PgPool,
+}
+
+impl PostgresRefundRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl RefundRepository for PostgresRefundRepository {
+    async fn create(&self, user_id: Uuid, original_transaction_id: i64, amount: Decimal, note: Option<&str>) -> Result<Refund, AppError> {
+        let refund = sqlx::query_as!(
+            Refund,
+            r#"INSERT INTO transaction_refunds (user_id, original_transaction_id, requested_amount, note)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                       status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user_id,
+            original_transaction_id,
+            amount,
+            note
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(refund)
+    }
+
+    async fn find_by_id(&self, id: i64, user_id: Uuid) -> Result<Option<Refund>, AppError> {
+        let refund = sqlx::query_as!(
+            Refund,
+            r#"SELECT id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                    status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM transaction_refunds WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(refund)
+    }
+
+    async fn find_by_original(&self, original_transaction_id: i64, user_id: Uuid) -> Result<Vec<Refund>, AppError> {
+        let refunds = sqlx::query_as!(
+            Refund,
+            r#"SELECT id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                    status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM transaction_refunds WHERE original_transaction_id = $1 AND user_id = $2
+             ORDER BY created_at"#,
+            original_transaction_id,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(refunds)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid, status: Option<&str>) -> Result<Vec<Refund>, AppError> {
+        let refunds = sqlx::query_as!(
+            Refund,
+            r#"SELECT id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                    status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM transaction_refunds
+             WHERE user_id = $1 AND ($2::text IS NULL OR status = $2)
+             ORDER BY created_at DESC"#,
+            user_id,
+            status
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(refunds)
+    }
+
+    async fn claimed_amount(&self, original_transaction_id: i64, exclude_id: Option<i64>) -> Result<Decimal, AppError> {
+        // Written-off claims free up their share of the expense again
+        let claimed = sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(COALESCE(received_amount, requested_amount)), 0) AS "claimed!"
+             FROM transaction_refunds
+             WHERE original_transaction_id = $1 AND status <> 'written_off'
+                 AND ($2::bigint IS NULL OR id <> $2)"#,
+            original_transaction_id,
+            exclude_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(claimed)
+    }
+
+    async fn mark_received(&self, id: i64, user_id: Uuid, refund_transaction_id: i64, amount: Decimal) -> Result<Refund, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let refund = sqlx::query_as!(
+            Refund,
+            r#"UPDATE transaction_refunds
+             SET status = 'received', refund_transaction_id = $3, received_amount = $4, resolved_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND user_id = $2 AND status <> 'received'
+             RETURNING id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                       status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            id,
+            user_id,
+            refund_transaction_id,
+            amount
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("duplicate key") {
+                AppError::Conflict("Transaction is already linked to another refund".to_string())
+            } else {
+                AppError::from(e)
+            }
+        })?
+        .ok_or_else(|| AppError::Conflict("Refund has already been received".to_string()))?;
+
+        // Netting moves the original expense's month and drops the credit from its own
+        mark_months_stale(&mut tx, user_id, &[refund.original_transaction_id, refund_transaction_id]).await?;
+        tx.commit().await?;
+
+        Ok(refund)
+    }
+
+    async fn mark_written_off(&self, id: i64, user_id: Uuid) -> Result<Refund, AppError> {
+        let refund = sqlx::query_as!(
+            Refund,
+            r#"UPDATE transaction_refunds
+             SET status = 'written_off', resolved_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND user_id = $2 AND status = 'requested'
+             RETURNING id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                       status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        refund.ok_or_else(|| AppError::Conflict("Only requested refunds can be written off".to_string()))
+    }
+}
\ No newline at end of file
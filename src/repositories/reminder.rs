@@ -0,0 +1,158 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{Reminder, ReminderNotification};
+use crate::utils::AppError;
+
+// Reminder columns plus the transaction summary shown next to them
+const REMINDER_COLUMNS: &str = "r.id, r.user_id, r.transaction_id, r.note, r.remind_on, r.status, r.notified_at, r.created_at, r.updated_at,
+     t.description AS transaction_description, t.amount AS transaction_amount, t.transaction_date";
+
+#[async_trait::async_trait]
+pub trait ReminderRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, transaction_id: i64, note: &str, remind_on: NaiveDate) -> Result<Option<Reminder>, AppError>;
+    async fn find_by_transaction(&self, transaction_id: i64, user_id: Uuid) -> Result<Vec<Reminder>, AppError>;
+    async fn find_due(&self, user_id: Uuid, on_date: NaiveDate) -> Result<Vec<Reminder>, AppError>;
+    async fn update(&self, id: i64, user_id: Uuid, note: Option<&str>, remind_on: Option<NaiveDate>, status: Option<&str>) -> Result<Option<Reminder>, AppError>;
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<bool, AppError>;
+    async fn find_unnotified_due(&self, on_date: NaiveDate, limit: i64) -> Result<Vec<ReminderNotification>, AppError>;
+    async fn mark_notified(&self, ids: &[i64]) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresReminderRepository {
+    pool: PgPool,
+}
+
+impl PostgresReminderRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReminderRepository for PostgresReminderRepository {
+    async fn create(&self, user_id: Uuid, transaction_id: i64, note: &str, remind_on: NaiveDate) -> Result<Option<Reminder>, AppError> {
+        // Inserts nothing unless the transaction belongs to the user
+        let reminder = sqlx::query_as::<_, Reminder>(&format!(
+            "WITH r AS (
+                 INSERT INTO transaction_reminders (user_id, transaction_id, note, remind_on)
+                 SELECT $1, id, $3, $4 FROM transactions WHERE id = $2 AND user_id = $1
+                 RETURNING *
+             )
+             SELECT {} FROM r JOIN transactions t ON t.id = r.transaction_id",
+            REMINDER_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(transaction_id)
+        .bind(note)
+        .bind(remind_on)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(reminder)
+    }
+
+    async fn find_by_transaction(&self, transaction_id: i64, user_id: Uuid) -> Result<Vec<Reminder>, AppError> {
+        let reminders = sqlx::query_as::<_, Reminder>(&format!(
+            "SELECT {} FROM transaction_reminders r JOIN transactions t ON t.id = r.transaction_id
+             WHERE r.transaction_id = $1 AND r.user_id = $2
+             ORDER BY r.remind_on, r.id",
+            REMINDER_COLUMNS
+        ))
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reminders)
+    }
+
+    async fn find_due(&self, user_id: Uuid, on_date: NaiveDate) -> Result<Vec<Reminder>, AppError> {
+        let reminders = sqlx::query_as::<_, Reminder>(&format!(
+            "SELECT {} FROM transaction_reminders r JOIN transactions t ON t.id = r.transaction_id
+             WHERE r.user_id = $1 AND r.status = 'open' AND r.remind_on <= $2
+             ORDER BY r.remind_on, r.id",
+            REMINDER_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(on_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reminders)
+    }
+
+    async fn update(&self, id: i64, user_id: Uuid, note: Option<&str>, remind_on: Option<NaiveDate>, status: Option<&str>) -> Result<Option<Reminder>, AppError> {
+        // A new date means a new notification, so the sent marker is cleared when it moves
+        let reminder = sqlx::query_as::<_, Reminder>(&format!(
+            "WITH r AS (
+                 UPDATE transaction_reminders
+                 SET note = COALESCE($3, note),
+                     remind_on = COALESCE($4, remind_on),
+                     status = COALESCE($5, status),
+                     notified_at = CASE WHEN $4::date IS NOT NULL AND $4::date <> remind_on THEN NULL ELSE notified_at END,
+                     updated_at = NOW()
+                 WHERE id = $1 AND user_id = $2
+                 RETURNING *
+             )
+             SELECT {} FROM r JOIN transactions t ON t.id = r.transaction_id",
+            REMINDER_COLUMNS
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(note)
+        .bind(remind_on)
+        .bind(status)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(reminder)
+    }
+
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "DELETE FROM transaction_reminders WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_unnotified_due(&self, on_date: NaiveDate, limit: i64) -> Result<Vec<ReminderNotification>, AppError> {
+        let reminders = sqlx::query_as!(
+            ReminderNotification,
+            r#"SELECT r.id, r.user_id, u.name AS user_name, u.email AS user_email, p.locale AS "user_locale?", r.note, r.remind_on,
+                    t.description AS transaction_description, t.amount AS transaction_amount, t.transaction_date
+             FROM transaction_reminders r
+             JOIN transactions t ON t.id = r.transaction_id
+             JOIN users u ON u.id = r.user_id
+             LEFT JOIN user_preferences p ON p.user_id = u.id
+             WHERE r.status = 'open' AND r.remind_on <= $1 AND r.notified_at IS NULL
+                 AND u.deletion_scheduled_at IS NULL
+             ORDER BY r.user_id, r.remind_on, r.id
+             LIMIT $2"#,
+            on_date,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reminders)
+    }
+
+    async fn mark_notified(&self, ids: &[i64]) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE transaction_reminders SET notified_at = NOW() WHERE id = ANY($1)",
+            ids
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
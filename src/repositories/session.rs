@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{Session, ClientInfo, RefreshToken};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait SessionRepository: Clone + Send + Sync {
+    async fn create(&self, user_id: Uuid, client: &ClientInfo, expires_at: DateTime<Utc>) -> Result<Session, AppError>;
+    async fn find_by_id_for_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Session>, AppError>;
+    async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<Session>, AppError>;
+    async fn touch_if_active(&self, id: Uuid) -> Result<bool, AppError>;
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<bool, AppError>;
+    async fn revoke_all_except(&self, user_id: Uuid, keep_id: Option<Uuid>) -> Result<Vec<Uuid>, AppError>;
+    async fn extend(&self, id: Uuid, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+    async fn create_refresh_token(&self, session: &Session, device_id: &str, token_hash: &str, expires_at: DateTime<Utc>) -> Result<RefreshToken, AppError>;
+    async fn find_refresh_token_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError>;
+    async fn consume_refresh_token(&self, id: Uuid) -> Result<bool, AppError>;
+    async fn revoke_family(&self, session_id: Uuid) -> Result<(), AppError>;
+    // The cutoff lives on the user row, so it outlasts the cached marker
+    async fn set_tokens_valid_after(&self, user_id: Uuid, valid_after: DateTime<Utc>) -> Result<(), AppError>;
+    async fn find_tokens_valid_after(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresSessionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSessionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionRepository for PostgresSessionRepository {
+    async fn create(&self, user_id: Uuid, client: &ClientInfo, expires_at: DateTime<Utc>) -> Result<Session, AppError> {
+        let session = sqlx::query_as!(
+            Session,
+            r#"INSERT INTO sessions (id, user_id, user_agent, ip_address, created_at, last_seen_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $5, $6)
+             RETURNING id, user_id, user_agent, ip_address, created_at AS "created_at!", last_seen_at AS "last_seen_at!", expires_at, revoked_at"#,
+            Uuid::new_v4(),
+            user_id,
+            client.user_agent,
+            client.ip_address,
+            Utc::now(),
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    async fn find_by_id_for_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Session>, AppError> {
+        let session = sqlx::query_as!(
+            Session,
+            r#"SELECT id, user_id, user_agent, ip_address, created_at AS "created_at!", last_seen_at AS "last_seen_at!", expires_at, revoked_at
+             FROM sessions WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<Session>, AppError> {
+        let sessions = sqlx::query_as!(
+            Session,
+            r#"SELECT id, user_id, user_agent, ip_address, created_at AS "created_at!", last_seen_at AS "last_seen_at!", expires_at, revoked_at
+             FROM sessions
+             WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+             ORDER BY last_seen_at DESC"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    async fn touch_if_active(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "UPDATE sessions SET last_seen_at = NOW()
+             WHERE id = $1 AND revoked_at IS NULL AND expires_at > NOW()",
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "UPDATE sessions SET revoked_at = NOW()
+             WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+            id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_all_except(&self, user_id: Uuid, keep_id: Option<Uuid>) -> Result<Vec<Uuid>, AppError> {
+        let ids = sqlx::query_scalar!(
+            "UPDATE sessions SET revoked_at = NOW()
+             WHERE user_id = $1 AND revoked_at IS NULL AND ($2::uuid IS NULL OR id <> $2)
+             RETURNING id",
+            user_id,
+            keep_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+    async fn extend(&self, id: Uuid, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query!("UPDATE sessions SET expires_at = $1, last_seen_at = NOW() WHERE id = $2 AND revoked_at IS NULL", expires_at, id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_refresh_token(&self, session: &Session, device_id: &str, token_hash: &str, expires_at: DateTime<Utc>) -> Result<RefreshToken, AppError> {
+        let token = sqlx::query_as!(
+            RefreshToken,
+            r#"INSERT INTO refresh_tokens (id, session_id, user_id, device_id, token_hash, created_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING id, session_id, user_id, device_id, token_hash, created_at AS "created_at!", expires_at, used_at, revoked_at"#,
+            Uuid::new_v4(),
+            session.id,
+            session.user_id,
+            device_id,
+            token_hash,
+            Utc::now(),
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn find_refresh_token_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        let token = sqlx::query_as!(
+            RefreshToken,
+            r#"SELECT id, session_id, user_id, device_id, token_hash, created_at AS "created_at!", expires_at, used_at, revoked_at
+             FROM refresh_tokens WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    // Marks a token as spent; false means another request already rotated it
+    async fn consume_refresh_token(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query!(
+            "UPDATE refresh_tokens SET used_at = NOW()
+             WHERE id = $1 AND used_at IS NULL AND revoked_at IS NULL",
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_family(&self, session_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("UPDATE refresh_tokens SET revoked_at = NOW() WHERE session_id = $1 AND revoked_at IS NULL", session_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!("UPDATE sessions SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL", session_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn set_tokens_valid_after(&self, user_id: Uuid, valid_after: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET tokens_valid_after = GREATEST(tokens_valid_after, $2) WHERE id = $1",
+            user_id,
+            valid_after
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_tokens_valid_after(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>, AppError> {
+        let valid_after = sqlx::query_scalar!(
+            "SELECT tokens_valid_after FROM users WHERE id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .flatten();
+
+        Ok(valid_after)
+    }
+}
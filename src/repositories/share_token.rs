@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{PocketShareToken, SharedBalancePoint};
+use crate::repositories::hashed_token::HashedTokenTable;
+use crate::utils::AppError;
+
+const SHARE_TOKENS: HashedTokenTable = HashedTokenTable {
+    table: "pocket_share_tokens",
+    columns: "id, pocket_id, user_id, label, token_hash, created_at, expires_at, last_used_at, revoked_at",
+    scope: &["pocket_id", "user_id"],
+    expires: true,
+    not_found: "Share token not found",
+};
+
+#[async_trait::async_trait]
+pub trait ShareTokenRepository: Clone + Send + Sync {
+    async fn create(&self, pocket_id: Uuid, user_id: Uuid, label: Option<&str>, token_hash: &str, expires_at: Option<DateTime<Utc>>) -> Result<PocketShareToken, AppError>;
+    async fn find_by_pocket(&self, pocket_id: Uuid, user_id: Uuid) -> Result<Vec<PocketShareToken>, AppError>;
+    async fn count_active(&self, pocket_id: Uuid) -> Result<i64, AppError>;
+    async fn revoke(&self, id: Uuid, pocket_id: Uuid, user_id: Uuid) -> Result<PocketShareToken, AppError>;
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<PocketShareToken>, AppError>;
+    async fn touch(&self, id: Uuid) -> Result<(), AppError>;
+    async fn find_trend(&self, pocket_id: Uuid, months: i64) -> Result<Vec<SharedBalancePoint>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresShareTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresShareTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ShareTokenRepository for PostgresShareTokenRepository {
+    async fn create(&self, pocket_id: Uuid, user_id: Uuid, label: Option<&str>, token_hash: &str, expires_at: Option<DateTime<Utc>>) -> Result<PocketShareToken, AppError> {
+        let token = sqlx::query_as!(
+            PocketShareToken,
+            r#"INSERT INTO pocket_share_tokens (id, pocket_id, user_id, label, token_hash, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING id, pocket_id, user_id, label, token_hash, created_at AS "created_at!", expires_at, last_used_at, revoked_at"#,
+            Uuid::new_v4(),
+            pocket_id,
+            user_id,
+            label,
+            token_hash,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn find_by_pocket(&self, pocket_id: Uuid, user_id: Uuid) -> Result<Vec<PocketShareToken>, AppError> {
+        SHARE_TOKENS.find_by_scope(&self.pool, &[pocket_id, user_id]).await
+    }
+
+    async fn count_active(&self, pocket_id: Uuid) -> Result<i64, AppError> {
+        SHARE_TOKENS.count_active(&self.pool, &[pocket_id]).await
+    }
+
+    async fn revoke(&self, id: Uuid, pocket_id: Uuid, user_id: Uuid) -> Result<PocketShareToken, AppError> {
+        SHARE_TOKENS.revoke(&self.pool, id, &[pocket_id, user_id]).await
+    }
+
+    async fn find_active_by_hash(&self, token_hash: &str) -> Result<Option<PocketShareToken>, AppError> {
+        SHARE_TOKENS.find_active_by_hash(&self.pool, token_hash).await
+    }
+
+    async fn touch(&self, id: Uuid) -> Result<(), AppError> {
+        SHARE_TOKENS.touch(&self.pool, id).await
+    }
+
+    async fn find_trend(&self, pocket_id: Uuid, months: i64) -> Result<Vec<SharedBalancePoint>, AppError> {
+        let points = sqlx::query_as!(
+            SharedBalancePoint,
+            "SELECT month, balance FROM (
+                 SELECT snapshot_month AS month, balance
+                 FROM pocket_balance_snapshots
+                 WHERE pocket_id = $1
+                 ORDER BY snapshot_month DESC
+                 LIMIT $2
+             ) recent
+             ORDER BY month",
+            pocket_id,
+            months
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(points)
+    }
+}
\ No newline at end of file
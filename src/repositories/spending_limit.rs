@@ -0,0 +1,145 @@
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::SpendingLimit;
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait SpendingLimitRepository: Clone + Send + Sync {
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<SpendingLimit>, AppError>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<SpendingLimit>, AppError>;
+    // Locks the limit until the caller's transaction ends, so expenses in the category are checked one at a time
+    async fn find_by_category_for_update_with(&self, conn: &mut PgConnection, user_id: Uuid, category: &str) -> Result<Option<SpendingLimit>, AppError>;
+    async fn create(&self, user_id: Uuid, category: &str, monthly_limit: Decimal, enforcement: &str) -> Result<SpendingLimit, AppError>;
+    async fn update(&self, id: i64, user_id: Uuid, monthly_limit: Option<Decimal>, enforcement: Option<&str>) -> Result<SpendingLimit, AppError>;
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError>;
+    async fn get_spent_amount_with(&self, conn: &mut PgConnection, user_id: Uuid, category: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<Decimal, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresSpendingLimitRepository {
+    pool: PgPool,
+}
+
+impl PostgresSpendingLimitRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SpendingLimitRepository for PostgresSpendingLimitRepository {
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<SpendingLimit>, AppError> {
+        let limit = sqlx::query_as!(
+            SpendingLimit,
+            r#"SELECT id, user_id, category, monthly_limit, enforcement, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM spending_limits WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(limit)
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<SpendingLimit>, AppError> {
+        let limits = sqlx::query_as!(
+            SpendingLimit,
+            r#"SELECT id, user_id, category, monthly_limit, enforcement, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM spending_limits WHERE user_id = $1 ORDER BY category"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(limits)
+    }
+
+    async fn find_by_category_for_update_with(&self, conn: &mut PgConnection, user_id: Uuid, category: &str) -> Result<Option<SpendingLimit>, AppError> {
+        let limit = sqlx::query_as!(
+            SpendingLimit,
+            r#"SELECT id, user_id, category, monthly_limit, enforcement, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM spending_limits WHERE user_id = $1 AND category = $2 FOR UPDATE"#,
+            user_id,
+            category
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(limit)
+    }
+
+    async fn create(&self, user_id: Uuid, category: &str, monthly_limit: Decimal, enforcement: &str) -> Result<SpendingLimit, AppError> {
+        let limit = sqlx::query_as!(
+            SpendingLimit,
+            r#"INSERT INTO spending_limits (user_id, category, monthly_limit, enforcement, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $5)
+             RETURNING id, user_id, category, monthly_limit, enforcement, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user_id,
+            category,
+            monthly_limit,
+            enforcement,
+            Utc::now()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("duplicate key") {
+                AppError::Conflict("A spending limit already exists for this category".to_string())
+            } else {
+                AppError::DatabaseError(e.to_string())
+            }
+        })?;
+
+        Ok(limit)
+    }
+
+    async fn update(&self, id: i64, user_id: Uuid, monthly_limit: Option<Decimal>, enforcement: Option<&str>) -> Result<SpendingLimit, AppError> {
+        let limit = sqlx::query_as!(
+            SpendingLimit,
+            r#"UPDATE spending_limits
+             SET monthly_limit = COALESCE($1, monthly_limit), enforcement = COALESCE($2, enforcement), updated_at = NOW()
+             WHERE id = $3 AND user_id = $4
+             RETURNING id, user_id, category, monthly_limit, enforcement, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            monthly_limit,
+            enforcement,
+            id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        limit.ok_or_else(|| AppError::NotFound("Spending limit not found".to_string()))
+    }
+
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!("DELETE FROM spending_limits WHERE id = $1 AND user_id = $2", id, user_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Spending limit not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn get_spent_amount_with(&self, conn: &mut PgConnection, user_id: Uuid, category: &str, from_date: NaiveDate, to_date: NaiveDate) -> Result<Decimal, AppError> {
+        let spent = sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(ABS(amount)), 0) AS "spent!" FROM all_transactions
+             WHERE user_id = $1 AND category = $2 AND transaction_type = 'expense'
+             AND transaction_date >= $3 AND transaction_date <= $4 AND status IN ('posted', 'pending')"#,
+            user_id,
+            category,
+            from_date,
+            to_date
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(spent)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{TaskRun, TASK_RUN_STATUS_FAILED, TASK_RUN_STATUS_RUNNING};
+use crate::utils::AppError;
+
+#[async_trait::async_trait]
+pub trait TaskRunRepository: Clone + Send + Sync {
+    // Returns None when the task already has a run in progress
+    async fn start(&self, task_name: &str, trigger: &str) -> Result<Option<TaskRun>, AppError>;
+    async fn finish(&self, id: Uuid, status: &str, items_processed: Option<i32>, error: Option<&str>) -> Result<(), AppError>;
+    async fn fail_stale(&self, task_name: &str, started_before: DateTime<Utc>) -> Result<u64, AppError>;
+    async fn find_latest_per_task(&self) -> Result<Vec<TaskRun>, AppError>;
+    async fn find_last_success_per_task(&self) -> Result<Vec<(String, DateTime<Utc>)>, AppError>;
+    async fn find_recent_by_task(&self, task_name: &str, limit: i64) -> Result<Vec<TaskRun>, AppError>;
+}
+
+#[derive(Clone)]
+pub struct PostgresTaskRunRepository {
+    pool: PgPool,
+}
+
+impl PostgresTaskRunRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskRunRepository for PostgresTaskRunRepository {
+    async fn start(&self, task_name: &str, trigger: &str) -> Result<Option<TaskRun>, AppError> {
+        // The partial unique index on running rows turns a concurrent start into a no-op
+        let run = sqlx::query_as!(
+            TaskRun,
+            "INSERT INTO task_runs (id, task_name, trigger, status, started_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (task_name) WHERE status = 'running' DO NOTHING
+             RETURNING id, task_name, trigger, status, items_processed, error, started_at, finished_at",
+            Uuid::new_v4(),
+            task_name,
+            trigger,
+            TASK_RUN_STATUS_RUNNING,
+            Utc::now()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    async fn finish(&self, id: Uuid, status: &str, items_processed: Option<i32>, error: Option<&str>) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE task_runs SET status = $1, items_processed = $2, error = $3, finished_at = $4
+             WHERE id = $5",
+            status,
+            items_processed,
+            error,
+            Utc::now(),
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fail_stale(&self, task_name: &str, started_before: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query!(
+            "UPDATE task_runs SET status = $1, error = 'Abandoned, the run never reported back', finished_at = $2
+             WHERE task_name = $3 AND status = $4 AND started_at < $5",
+            TASK_RUN_STATUS_FAILED,
+            Utc::now(),
+            task_name,
+            TASK_RUN_STATUS_RUNNING,
+            started_before
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_latest_per_task(&self) -> Result<Vec<TaskRun>, AppError> {
+        let runs = sqlx::query_as!(
+            TaskRun,
+            "SELECT DISTINCT ON (task_name) id, task_name, trigger, status, items_processed, error, started_at, finished_at
+             FROM task_runs
+             ORDER BY task_name, started_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(runs)
+    }
+
+    async fn find_last_success_per_task(&self) -> Result<Vec<(String, DateTime<Utc>)>, AppError> {
+        let rows = sqlx::query!(
+            r#"SELECT task_name, MAX(finished_at) AS "finished_at!"
+             FROM task_runs
+             WHERE status = 'succeeded'
+             GROUP BY task_name"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.task_name, row.finished_at)).collect())
+    }
+
+    async fn find_recent_by_task(&self, task_name: &str, limit: i64) -> Result<Vec<TaskRun>, AppError> {
+        let runs = sqlx::query_as!(
+            TaskRun,
+            "SELECT id, task_name, trigger, status, items_processed, error, started_at, finished_at
+             FROM task_runs WHERE task_name = $1 ORDER BY started_at DESC LIMIT $2",
+            task_name,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(runs)
+    }
+}
\ No newline at end of file
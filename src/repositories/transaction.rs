@@ -1,242 +1,355 @@
-use chrono::{NaiveDate, Utc};
+use chrono::{Months, NaiveDate, Utc};
 use rust_decimal::Decimal;
-use sqlx::{PgPool, Row};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 use std::str::FromStr;
 use uuid::Uuid;
 
-use crate::models::{Transaction, CreateTransactionRequest, UpdateTransactionRequest, ListTransactionsQuery};
-use crate::utils::AppError;
+use crate::models::{
+    Transaction, CreateTransactionRequest, CurrencyConversion, UpdateTransactionRequest, ListTransactionsQuery, RefundLink,
+    TRANSACTION_STATUS_PENDING, TRANSACTION_STATUS_POSTED,
+};
+use crate::repositories::{Counted, Page, ReadPreference, mark_months_stale, read_pool};
+use crate::utils::{AppError, codes};
+
+const TRANSACTION_COLUMNS: &str =
+    "id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at, updated_at";
 
 #[async_trait::async_trait]
 pub trait TransactionRepository: Clone + Send + Sync {
-    async fn find_by_id(&self, id: i64) -> Result<Option<Transaction>, AppError>;
-    async fn find_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery) -> Result<Vec<Transaction>, AppError>;
-    async fn find_by_date_range(&self, user_id: Uuid, from_date: chrono::DateTime<Utc>, to_date: chrono::DateTime<Utc>) -> Result<Vec<Transaction>, AppError>;
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<Transaction>, AppError>;
+    async fn find_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery, read: ReadPreference) -> Result<Vec<Transaction>, AppError>;
+    // The requested page and the filtered total from a single query
+    async fn find_page_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery, read: ReadPreference) -> Result<Page<Transaction>, AppError>;
+    async fn find_by_date_range(&self, user_id: Uuid, from_date: NaiveDate, to_date: NaiveDate, read: ReadPreference) -> Result<Vec<Transaction>, AppError>;
     async fn create(&self, user_id: Uuid, request: &CreateTransactionRequest) -> Result<Transaction, AppError>;
     async fn update(&self, id: i64, user_id: Uuid, request: &UpdateTransactionRequest) -> Result<Transaction, AppError>;
     async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError>;
-    async fn count_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery) -> Result<i64, AppError>;
+    async fn find_by_id_for_update_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid) -> Result<Option<Transaction>, AppError>;
+    async fn create_with(&self, conn: &mut PgConnection, user_id: Uuid, request: &CreateTransactionRequest, conversion: Option<&CurrencyConversion>) -> Result<Transaction, AppError>;
+    async fn update_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid, request: &UpdateTransactionRequest) -> Result<Transaction, AppError>;
+    async fn delete_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid) -> Result<(), AppError>;
+    async fn count_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery, read: ReadPreference) -> Result<i64, AppError>;
+    async fn find_all_by_user_id(&self, user_id: Uuid, from_date: Option<NaiveDate>, to_date: Option<NaiveDate>, read: ReadPreference) -> Result<Vec<Transaction>, AppError>;
+    async fn set_status_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid, status: &str) -> Result<Transaction, AppError>;
+    async fn find_due_pending(&self, on_date: NaiveDate, limit: i64) -> Result<Vec<Transaction>, AppError>;
+    async fn find_received_refunds(&self, user_id: Uuid, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<RefundLink>, AppError>;
+    // First days of the months before the cutoff that still hold archivable rows
+    async fn find_archivable_months(&self, before: NaiveDate) -> Result<Vec<NaiveDate>, AppError>;
+    // Moves the month's archivable rows into its archive partition; one owner id per moved row
+    async fn archive_month(&self, month: NaiveDate) -> Result<Vec<Uuid>, AppError>;
 }
 
 #[derive(Clone)]
 pub struct PostgresTransactionRepository {
     pool: PgPool,
+    replica: Option<PgPool>,
 }
 
 impl PostgresTransactionRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self { pool, replica: None }
+    }
+
+    pub fn with_replica(mut self, replica: Option<PgPool>) -> Self {
+        self.replica = replica;
+        self
+    }
+
+    fn reader(&self, read: ReadPreference) -> &PgPool {
+        read_pool(&self.pool, self.replica.as_ref(), read)
+    }
+
+    // Maps the validated sort options onto fixed SQL, request text never reaches the query
+    fn order_by_clause(query: &ListTransactionsQuery) -> &'static str {
+        let ascending = query.order.as_deref() == Some("asc");
+        match (query.sort_by.as_deref(), ascending) {
+            (Some("amount"), true) => " ORDER BY amount ASC, id ASC",
+            (Some("amount"), false) => " ORDER BY amount DESC, id DESC",
+            (Some("category"), true) => " ORDER BY category ASC, transaction_date DESC",
+            (Some("category"), false) => " ORDER BY category DESC, transaction_date DESC",
+            (Some("created_at"), true) => " ORDER BY created_at ASC",
+            (Some("created_at"), false) => " ORDER BY created_at DESC",
+            (_, true) => " ORDER BY transaction_date ASC, created_at ASC",
+            (_, false) => " ORDER BY transaction_date DESC, created_at DESC",
+        }
     }
 
-    fn build_where_clause(&self, query: &ListTransactionsQuery) -> (String, Vec<String>) {
-        let mut conditions = vec!["user_id = $1".to_string()];
-        let mut params = Vec::new();
-        let mut param_count = 2;
+    // Filters, sorts and pages a list query; `columns` is everything between SELECT and FROM.
+    // Lists cover archived months too, a date filter keeps the archive partitions pruned.
+    fn list_query(columns: &str, user_id: Uuid, query: &ListTransactionsQuery) -> Result<QueryBuilder<'static, Postgres>, AppError> {
+        let mut builder = QueryBuilder::new(format!("SELECT {} FROM all_transactions", columns));
+        Self::push_filters(&mut builder, user_id, TransactionFilters::from_query(query)?);
+        builder
+            .push(Self::order_by_clause(query))
+            .push(" LIMIT ")
+            .push_bind(Self::limit(query))
+            .push(" OFFSET ")
+            .push_bind(Self::offset(query));
+
+        Ok(builder)
+    }
+
+    fn limit(query: &ListTransactionsQuery) -> i64 {
+        query.limit.unwrap_or(20) as i64
+    }
+
+    fn offset(query: &ListTransactionsQuery) -> i64 {
+        (query.page.unwrap_or(1) as i64 - 1) * Self::limit(query)
+    }
+
+    // Appends the WHERE clause for a list query, binding every value through the builder
+    fn push_filters(builder: &mut QueryBuilder<'static, Postgres>, user_id: Uuid, filters: TransactionFilters) {
+        builder.push(" WHERE user_id = ").push_bind(user_id);
+
+        if let Some(category) = filters.category {
+            builder.push(" AND category = ").push_bind(category);
+        }
+
+        if !filters.categories.is_empty() {
+            builder.push(" AND category = ANY(").push_bind(filters.categories).push(")");
+        }
+
+        if let Some(transaction_type) = filters.transaction_type {
+            builder.push(" AND transaction_type = ").push_bind(transaction_type);
+        }
+
+        if let Some(account_id) = filters.account_id {
+            builder.push(" AND account_id = ").push_bind(account_id);
+        }
 
-        if let Some(category) = &query.category {
-            conditions.push(format!("category = ${}", param_count));
-            params.push(category.clone());
-            param_count += 1;
+        if let Some(from_date) = filters.from_date {
+            builder.push(" AND transaction_date >= ").push_bind(from_date);
         }
 
-        if let Some(transaction_type) = &query.transaction_type {
-            conditions.push(format!("transaction_type = ${}", param_count));
-            params.push(transaction_type.clone());
-            param_count += 1;
+        if let Some(to_date) = filters.to_date {
+            builder.push(" AND transaction_date <= ").push_bind(to_date);
         }
 
-        if let Some(from_date) = &query.from_date {
-            conditions.push(format!("transaction_date >= ${}", param_count));
-            params.push(from_date.clone());
-            param_count += 1;
+        if let Some(status) = filters.status {
+            builder.push(" AND status = ").push_bind(status);
         }
 
-        if let Some(to_date) = &query.to_date {
-            conditions.push(format!("transaction_date <= ${}", param_count));
-            params.push(to_date.clone());
+        if let Some(min_amount) = filters.min_amount {
+            builder.push(" AND amount >= ").push_bind(min_amount);
         }
 
-        let where_clause = if conditions.len() > 1 {
-            format!("WHERE {}", conditions.join(" AND "))
-        } else {
-            "WHERE user_id = $1".to_string()
+        if let Some(max_amount) = filters.max_amount {
+            builder.push(" AND amount <= ").push_bind(max_amount);
+        }
+    }
+}
+
+// Typed form of ListTransactionsQuery, parsed once before building SQL
+struct TransactionFilters {
+    category: Option<String>,
+    categories: Vec<String>,
+    transaction_type: Option<String>,
+    account_id: Option<Uuid>,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    min_amount: Option<Decimal>,
+    max_amount: Option<Decimal>,
+    status: Option<String>,
+}
+
+impl TransactionFilters {
+    fn from_query(query: &ListTransactionsQuery) -> Result<Self, AppError> {
+        let parse_date = |value: &Option<String>, field: &str| {
+            value
+                .as_deref()
+                .map(|value| {
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                        AppError::ValidationError(format!("Invalid {} format. Use YYYY-MM-DD", field))
+                    })
+                })
+                .transpose()
+        };
+        let parse_amount = |value: &Option<String>, field: &str| {
+            value
+                .as_deref()
+                .map(|value| {
+                    Decimal::from_str(value)
+                        .map_err(|_| AppError::ValidationError(format!("Invalid {} format", field)))
+                })
+                .transpose()
         };
 
-        (where_clause, params)
+        Ok(Self {
+            category: query.category.clone(),
+            categories: query.category_list(),
+            transaction_type: query.transaction_type.clone(),
+            account_id: query.account_id,
+            from_date: parse_date(&query.from_date, "from_date")?,
+            to_date: parse_date(&query.to_date, "to_date")?,
+            min_amount: parse_amount(&query.min_amount, "min_amount")?,
+            max_amount: parse_amount(&query.max_amount, "max_amount")?,
+            status: query.status.clone(),
+        })
     }
 }
 
 #[async_trait::async_trait]
 impl TransactionRepository for PostgresTransactionRepository {
-    async fn find_by_id(&self, id: i64) -> Result<Option<Transaction>, AppError> {
-        let row = sqlx::query(
-            "SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date, created_at, updated_at
-             FROM transactions WHERE id = $1"
+    async fn find_by_id_for_user(&self, id: i64, user_id: Uuid) -> Result<Option<Transaction>, AppError> {
+        let transaction = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id AS "id!", user_id AS "user_id!", account_id, description AS "description!", amount AS "amount!", category,
+                    transaction_type AS "transaction_type!", transaction_date AS "transaction_date!", status AS "status!", notes, metadata,
+                    original_amount, original_currency, exchange_rate, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM all_transactions WHERE id = $1 AND user_id = $2"#,
+            id,
+            user_id
         )
-        .bind(id)
         .fetch_optional(&self.pool)
         .await?;
-        
-        match row {
-            Some(row) => {
-                let transaction = Transaction {
-                    id: row.get("id"),
-                    user_id: row.get("user_id"),
-                    account_id: row.get("account_id"),
-                    description: row.get("description"),
-                    amount: row.get("amount"),
-                    category: row.get("category"),
-                    transaction_type: row.get("transaction_type"),
-                    transaction_date: row.get("transaction_date"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                };
-                Ok(Some(transaction))
-            }
-            None => Ok(None),
-        }
+
+        Ok(transaction)
     }
 
-    async fn find_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery) -> Result<Vec<Transaction>, AppError> {
-        let page = query.page.unwrap_or(1);
-        let limit = query.limit.unwrap_or(20);
-        let offset = (page - 1) * limit;
+    async fn find_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery, read: ReadPreference) -> Result<Vec<Transaction>, AppError> {
+        let transactions = Self::list_query(TRANSACTION_COLUMNS, user_id, query)?
+            .build_query_as::<Transaction>()
+            .fetch_all(self.reader(read))
+            .await?;
 
-        let (where_clause, params) = self.build_where_clause(query);
-        
-        let sql = format!(
-            "SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date, created_at, updated_at
-             FROM transactions
-             {}
-             ORDER BY transaction_date DESC, created_at DESC
-             LIMIT {} OFFSET {}",
-            where_clause, limit, offset
-        );
+        Ok(transactions)
+    }
 
-        let mut query_builder = sqlx::query(&sql).bind(user_id);
-        
-        for param in params {
-            query_builder = query_builder.bind(param);
-        }
+    async fn find_page_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery, read: ReadPreference) -> Result<Page<Transaction>, AppError> {
+        let columns = format!("{}, COUNT(*) OVER() AS total_items", TRANSACTION_COLUMNS);
+        let rows = Self::list_query(&columns, user_id, query)?
+            .build_query_as::<Counted<Transaction>>()
+            .fetch_all(self.reader(read))
+            .await?;
 
-        let rows = query_builder.fetch_all(&self.pool).await?;
-        
-        let transactions = rows.into_iter().map(|row| Transaction {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            account_id: row.get("account_id"),
-            description: row.get("description"),
-            amount: row.get("amount"),
-            category: row.get("category"),
-            transaction_type: row.get("transaction_type"),
-            transaction_date: row.get("transaction_date"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }).collect();
-        
-        Ok(transactions)
+        match Page::from_counted(rows, Self::offset(query)) {
+            Some(page) => Ok(page),
+            None => Ok(Page::empty(self.count_by_user_id(user_id, query, read).await?)),
+        }
     }
 
     async fn create(&self, user_id: Uuid, request: &CreateTransactionRequest) -> Result<Transaction, AppError> {
-        // Parse amount
-        let amount = Decimal::from_str(&request.amount)
-            .map_err(|_| AppError::ValidationError("Invalid amount format".to_string()))?;
+        let mut conn = self.pool.acquire().await?;
+        self.create_with(&mut conn, user_id, request, None).await
+    }
+
+    async fn update(&self, id: i64, user_id: Uuid, request: &UpdateTransactionRequest) -> Result<Transaction, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        self.update_with(&mut conn, id, user_id, request).await
+    }
+
+    async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        let mut conn = self.pool.acquire().await?;
+        self.delete_with(&mut conn, id, user_id).await
+    }
+
+    async fn find_by_id_for_update_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid) -> Result<Option<Transaction>, AppError> {
+        // Row lock keeps concurrent edits from applying the same balance change twice
+        let transaction = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM transactions WHERE id = $1 AND user_id = $2
+             FOR UPDATE"#,
+            id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(transaction)
+    }
+
+    async fn create_with(&self, conn: &mut PgConnection, user_id: Uuid, request: &CreateTransactionRequest, conversion: Option<&CurrencyConversion>) -> Result<Transaction, AppError> {
+        let amount = check_amount_sign(request.amount.amount(), &request.transaction_type)?;
+        let amount = conversion.map_or(amount, |conversion| conversion.converted_amount);
 
         // Parse transaction date
         let transaction_date = NaiveDate::parse_from_str(&request.transaction_date, "%Y-%m-%d")
-            .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()))?;
+            .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let now = Utc::now();
 
-        let row = sqlx::query(
-            "INSERT INTO transactions (user_id, account_id, description, amount, category, transaction_type, transaction_date, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-             RETURNING id, user_id, account_id, description, amount, category, transaction_type, transaction_date, created_at, updated_at"
+        let transaction = sqlx::query_as!(
+            Transaction,
+            r#"INSERT INTO transactions (user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+             RETURNING id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user_id,
+            request.account_id,
+            request.description,
+            amount,
+            request.category,
+            request.transaction_type,
+            transaction_date,
+            if request.pending { TRANSACTION_STATUS_PENDING } else { TRANSACTION_STATUS_POSTED },
+            request.notes.as_deref().filter(|notes| !notes.is_empty()),
+            request.metadata.as_ref().filter(|metadata| metadata.as_object().is_some_and(|map| !map.is_empty())),
+            conversion.map(|conversion| conversion.original_amount),
+            conversion.map(|conversion| &conversion.original_currency),
+            conversion.map(|conversion| conversion.exchange_rate),
+            now,
+            now
         )
-        .bind(user_id)
-        .bind(request.account_id)
-        .bind(&request.description)
-        .bind(amount)
-        .bind(&request.category)
-        .bind(&request.transaction_type)
-        .bind(transaction_date)
-        .bind(now)
-        .bind(now)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
         .await?;
-        
-        let transaction = Transaction {
-            id: row.get("id"),
-            user_id: row.get("user_id"),
-            account_id: row.get("account_id"),
-            description: row.get("description"),
-            amount: row.get("amount"),
-            category: row.get("category"),
-            transaction_type: row.get("transaction_type"),
-            transaction_date: row.get("transaction_date"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        };
+        mark_months_stale(conn, user_id, &[transaction.id]).await?;
         
         Ok(transaction)
     }
 
-    async fn update(&self, id: i64, user_id: Uuid, request: &UpdateTransactionRequest) -> Result<Transaction, AppError> {
-        // Parse amount
-        let amount = Decimal::from_str(&request.amount)
-            .map_err(|_| AppError::ValidationError("Invalid amount format".to_string()))?;
+    async fn update_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid, request: &UpdateTransactionRequest) -> Result<Transaction, AppError> {
+        let amount = check_amount_sign(request.amount.amount(), &request.transaction_type)?;
 
         // Parse transaction date
         let transaction_date = NaiveDate::parse_from_str(&request.transaction_date, "%Y-%m-%d")
-            .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()))?;
+            .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let now = Utc::now();
 
-        let row = sqlx::query(
-            "UPDATE transactions 
-             SET account_id = $1, description = $2, amount = $3, category = $4, transaction_type = $5, transaction_date = $6, updated_at = $7
+        // The month the transaction leaves goes stale as well as the one it lands in
+        mark_months_stale(conn, user_id, &[id]).await?;
+
+        // Edited amounts are in the pocket's currency, so a new amount or pocket drops the recorded conversion
+        let transaction = sqlx::query_as!(
+            Transaction,
+            r#"UPDATE transactions 
+             SET account_id = $1, description = $2, amount = $3, category = $4, transaction_type = $5, transaction_date = $6, updated_at = $7,
+                 original_amount = CASE WHEN amount = $3 AND account_id IS NOT DISTINCT FROM $1 THEN original_amount END,
+                 original_currency = CASE WHEN amount = $3 AND account_id IS NOT DISTINCT FROM $1 THEN original_currency END,
+                 exchange_rate = CASE WHEN amount = $3 AND account_id IS NOT DISTINCT FROM $1 THEN exchange_rate END,
+                 notes = CASE WHEN $10::text IS NULL THEN notes ELSE NULLIF($10, '') END,
+                 metadata = CASE WHEN $11::jsonb IS NULL THEN metadata WHEN $11::jsonb = '{}'::jsonb THEN NULL ELSE $11::jsonb END
              WHERE id = $8 AND user_id = $9
-             RETURNING id, user_id, account_id, description, amount, category, transaction_type, transaction_date, created_at, updated_at"
+             RETURNING id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            request.account_id,
+            request.description,
+            amount,
+            request.category,
+            request.transaction_type,
+            transaction_date,
+            now,
+            id,
+            user_id,
+            request.notes,
+            request.metadata
         )
-        .bind(request.account_id)
-        .bind(&request.description)
-        .bind(amount)
-        .bind(&request.category)
-        .bind(&request.transaction_type)
-        .bind(transaction_date)
-        .bind(now)
-        .bind(id)
-        .bind(user_id)
-        .fetch_optional(&self.pool)
-        .await?;
-        
-        match row {
-            Some(row) => {
-                let transaction = Transaction {
-                    id: row.get("id"),
-                    user_id: row.get("user_id"),
-                    account_id: row.get("account_id"),
-                    description: row.get("description"),
-                    amount: row.get("amount"),
-                    category: row.get("category"),
-                    transaction_type: row.get("transaction_type"),
-                    transaction_date: row.get("transaction_date"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                };
-                Ok(transaction)
-            }
-            None => Err(AppError::NotFound("Transaction not found or access denied".to_string())),
-        }
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Transaction not found or access denied".to_string()))?;
+        mark_months_stale(conn, user_id, &[id]).await?;
+
+        Ok(transaction)
     }
 
-    async fn delete(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
-        let result = sqlx::query(
-            "DELETE FROM transactions WHERE id = $1 AND user_id = $2"
+    async fn delete_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        mark_months_stale(conn, user_id, &[id]).await?;
+
+        let result = sqlx::query!(
+            "DELETE FROM transactions WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
         )
-        .bind(id)
-        .bind(user_id)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
         
         if result.rows_affected() == 0 {
@@ -246,59 +359,192 @@ impl TransactionRepository for PostgresTransactionRepository {
         Ok(())
     }
 
-    async fn find_by_date_range(&self, user_id: Uuid, from_date: chrono::DateTime<Utc>, to_date: chrono::DateTime<Utc>) -> Result<Vec<Transaction>, AppError> {
-        let sql = "
-            SELECT id, user_id, account_id, amount, description, category, 
-                   transaction_type, transaction_date, created_at, updated_at
-            FROM transactions 
-            WHERE user_id = $1 AND transaction_date >= $2 AND transaction_date <= $3
-            ORDER BY transaction_date DESC
-        ";
-
-        let rows = sqlx::query(sql)
-            .bind(user_id)
-            .bind(from_date)
-            .bind(to_date)
-            .fetch_all(&self.pool)
+    async fn find_by_date_range(&self, user_id: Uuid, from_date: NaiveDate, to_date: NaiveDate, read: ReadPreference) -> Result<Vec<Transaction>, AppError> {
+        let transactions = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id AS "id!", user_id AS "user_id!", account_id, amount AS "amount!", description AS "description!",
+                    category AS "category!", transaction_type AS "transaction_type!", transaction_date AS "transaction_date!",
+                    status AS "status!", notes, metadata, original_amount, original_currency, exchange_rate,
+                    created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM all_transactions
+             WHERE user_id = $1 AND transaction_date >= $2 AND transaction_date <= $3 AND status = 'posted'
+             ORDER BY transaction_date DESC"#,
+            user_id,
+            from_date,
+            to_date
+        )
+        .fetch_all(self.reader(read))
+        .await?;
+
+        Ok(transactions)
+    }
+
+    async fn count_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery, read: ReadPreference) -> Result<i64, AppError> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM all_transactions");
+        Self::push_filters(&mut builder, user_id, TransactionFilters::from_query(query)?);
+
+        let count = builder
+            .build_query_scalar::<i64>()
+            .fetch_one(self.reader(read))
             .await?;
 
-        let mut transactions = Vec::new();
-        for row in rows {
-            let transaction = Transaction {
-                id: row.get("id"),
-                user_id: row.get("user_id"),
-                account_id: row.get("account_id"),
-                amount: row.get("amount"),
-                description: row.get("description"),
-                category: row.get("category"),
-                transaction_type: row.get("transaction_type"),
-                transaction_date: row.get("transaction_date"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            };
-            transactions.push(transaction);
-        }
+        Ok(count)
+    }
+
+    async fn find_all_by_user_id(&self, user_id: Uuid, from_date: Option<NaiveDate>, to_date: Option<NaiveDate>, read: ReadPreference) -> Result<Vec<Transaction>, AppError> {
+        let transactions = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id AS "id!", user_id AS "user_id!", account_id, amount AS "amount!", description AS "description!",
+                    category AS "category!", transaction_type AS "transaction_type!", transaction_date AS "transaction_date!",
+                    status AS "status!", notes, metadata, original_amount, original_currency, exchange_rate,
+                    created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM all_transactions
+             WHERE user_id = $1 AND status = 'posted'
+               AND ($2::date IS NULL OR transaction_date >= $2)
+               AND ($3::date IS NULL OR transaction_date <= $3)
+             ORDER BY transaction_date ASC, id ASC"#,
+            user_id,
+            from_date,
+            to_date
+        )
+        .fetch_all(self.reader(read))
+        .await?;
 
         Ok(transactions)
     }
 
-    async fn count_by_user_id(&self, user_id: Uuid, query: &ListTransactionsQuery) -> Result<i64, AppError> {
-        let (where_clause, params) = self.build_where_clause(query);
-        
-        let sql = format!(
-            "SELECT COUNT(*) as count FROM transactions {}",
-            where_clause
-        );
+    async fn set_status_with(&self, conn: &mut PgConnection, id: i64, user_id: Uuid, status: &str) -> Result<Transaction, AppError> {
+        let transaction = sqlx::query_as!(
+            Transaction,
+            r#"UPDATE transactions SET status = $1, updated_at = NOW()
+             WHERE id = $2 AND user_id = $3
+             RETURNING id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            status,
+            id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Transaction not found or access denied".to_string()))?;
+        mark_months_stale(conn, user_id, &[id]).await?;
 
-        let mut query_builder = sqlx::query(&sql).bind(user_id);
-        
-        for param in params {
-            query_builder = query_builder.bind(param);
-        }
+        Ok(transaction)
+    }
 
-        let row = query_builder.fetch_one(&self.pool).await?;
-        let count: i64 = row.get("count");
-        
-        Ok(count)
+    async fn find_due_pending(&self, on_date: NaiveDate, limit: i64) -> Result<Vec<Transaction>, AppError> {
+        // Emailed receipts stay pending until their owner reviews them
+        let transactions = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status, notes, metadata, original_amount, original_currency, exchange_rate, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM transactions
+             WHERE status = 'pending' AND transaction_date <= $1
+                 AND metadata->>'email_message_id' IS NULL
+             ORDER BY transaction_date ASC, id ASC
+             LIMIT $2"#,
+            on_date,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transactions)
     }
+
+    // Received refunds touching the range through either the original expense or the credit
+    async fn find_received_refunds(&self, user_id: Uuid, from_date: NaiveDate, to_date: NaiveDate) -> Result<Vec<RefundLink>, AppError> {
+        let refunds = sqlx::query_as!(
+            RefundLink,
+            r#"SELECT r.original_transaction_id, r.refund_transaction_id AS "refund_transaction_id!", r.received_amount AS "amount!"
+             FROM transaction_refunds r
+             JOIN all_transactions o ON o.id = r.original_transaction_id
+             JOIN all_transactions c ON c.id = r.refund_transaction_id
+             WHERE r.user_id = $1 AND r.status = 'received'
+                 AND ((o.transaction_date >= $2 AND o.transaction_date <= $3)
+                     OR (c.transaction_date >= $2 AND c.transaction_date <= $3))"#,
+            user_id,
+            from_date,
+            to_date
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(refunds)
+    }
+
+    async fn find_archivable_months(&self, before: NaiveDate) -> Result<Vec<NaiveDate>, AppError> {
+        // Rows the archive job may move: settled, and not referenced by a refund or reminder, whose
+        // foreign keys would otherwise delete them along with the row
+        let months = sqlx::query_scalar!(
+            r#"SELECT DISTINCT date_trunc('month', t.transaction_date)::date AS "month!"
+             FROM transactions t
+             WHERE t.transaction_date < $1 AND t.status <> 'pending'
+                 AND NOT EXISTS (
+                     SELECT 1 FROM transaction_refunds r WHERE r.original_transaction_id = t.id OR r.refund_transaction_id = t.id
+                 )
+                 AND NOT EXISTS (SELECT 1 FROM transaction_reminders m WHERE m.transaction_id = t.id)
+             ORDER BY 1"#,
+            before
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(months)
+    }
+
+    async fn archive_month(&self, month: NaiveDate) -> Result<Vec<Uuid>, AppError> {
+        let end = month + Months::new(1);
+        let mut tx = self.pool.begin().await?;
+
+        // Bounds come from dates, so nothing user supplied reaches the DDL
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS transactions_archive_{} PARTITION OF transactions_archive
+             FOR VALUES FROM ('{}') TO ('{}')",
+            month.format("%Y_%m"),
+            month,
+            end
+        ))
+        .execute(&mut *tx)
+        .await?;
+
+        // The rows find_archivable_months picks the months by
+        let owners = sqlx::query_scalar!(
+            "WITH moved AS (
+                 DELETE FROM transactions t
+                 WHERE t.transaction_date >= $1 AND t.transaction_date < $2 AND t.status <> 'pending'
+                     AND NOT EXISTS (
+                         SELECT 1 FROM transaction_refunds r WHERE r.original_transaction_id = t.id OR r.refund_transaction_id = t.id
+                     )
+                     AND NOT EXISTS (SELECT 1 FROM transaction_reminders m WHERE m.transaction_id = t.id)
+                 RETURNING id, user_id, account_id, description, amount, category, transaction_type, transaction_date, status,
+                           notes, metadata, original_amount, original_currency, exchange_rate, created_at, updated_at
+             )
+             INSERT INTO transactions_archive (id, user_id, account_id, description, amount, category, transaction_type, transaction_date,
+                                               status, notes, metadata, original_amount, original_currency, exchange_rate, created_at, updated_at)
+             SELECT id, user_id, account_id, description, amount, category, transaction_type, transaction_date,
+                    status, notes, metadata, original_amount, original_currency, exchange_rate, created_at, updated_at
+             FROM moved
+             RETURNING user_id",
+            month,
+            end
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(owners)
+    }
+}
+
+// Income and expense amounts are stored unsigned and take their direction from the type, so a
+// negative one contradicts its type. Adjustments keep whatever sign they are given.
+fn check_amount_sign(amount: Decimal, transaction_type: &str) -> Result<Decimal, AppError> {
+    if amount.is_sign_negative() && !amount.is_zero() && transaction_type != "adjustment" {
+        return Err(AppError::ValidationError(format!(
+            "Amount must not be negative, transaction_type '{}' already sets the direction",
+            transaction_type
+        ))
+        .with_code(codes::INVALID_AMOUNT));
+    }
+
+    Ok(amount)
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+
+use crate::utils::AppError;
+
+// Hands out database transactions so a service can run several repository
+// calls atomically. Repositories expose `*_with` variants that take the
+// connection from a TxnContext instead of using their own pool.
+#[derive(Clone)]
+pub struct UnitOfWork {
+    pool: PgPool,
+}
+
+impl UnitOfWork {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn begin(&self) -> Result<TxnContext, AppError> {
+        let transaction = self.pool.begin().await?;
+        Ok(TxnContext { transaction })
+    }
+
+    // True when every connection the pool may open is checked out
+    pub fn is_saturated(&self) -> bool {
+        self.pool.num_idle() == 0 && self.pool.size() >= self.pool.options().get_max_connections()
+    }
+}
+
+// An open database transaction. Dropping it without calling commit rolls back.
+pub struct TxnContext {
+    transaction: Transaction<'static, Postgres>,
+}
+
+impl TxnContext {
+    pub fn conn(&mut self) -> &mut PgConnection {
+        &mut self.transaction
+    }
+
+    pub async fn commit(self) -> Result<(), AppError> {
+        self.transaction.commit().await?;
+        Ok(())
+    }
+
+    pub async fn rollback(self) -> Result<(), AppError> {
+        self.transaction.rollback().await?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,8 +1,10 @@
-use sqlx::{PgPool, Row};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use crate::models::User;
-use crate::utils::AppError;
+use crate::models::{User, ListUsersQuery, PendingEmailChange, Pocket, Transaction, Budget, SpendingLimit, Debt, Refund, Reminder, UserPreferences};
+use crate::repositories::{Counted, Page};
+use crate::utils::{AppError, codes};
 
 #[async_trait::async_trait]
 pub trait UserRepository: Clone + Send + Sync {
@@ -11,7 +13,37 @@ pub trait UserRepository: Clone + Send + Sync {
     async fn create(&self, user: User) -> Result<User, AppError>;
     async fn update_name(&self, id: Uuid, name: &str) -> Result<User, AppError>;
     async fn update_hide_balance(&self, id: Uuid, hide_balance: bool) -> Result<User, AppError>;
-    async fn list_all(&self) -> Result<Vec<User>, AppError>;
+    async fn update_base_currency(&self, id: Uuid, base_currency: &str) -> Result<User, AppError>;
+    async fn update_monthly_digest(&self, id: Uuid, monthly_digest: bool) -> Result<User, AppError>;
+    // The requested page and the filtered total from a single query
+    async fn list_page(&self, query: &ListUsersQuery, limit: i64, offset: i64) -> Result<Page<User>, AppError>;
+    async fn count(&self, query: &ListUsersQuery) -> Result<i64, AppError>;
+    async fn update_password(&self, id: Uuid, hashed_password: &str) -> Result<User, AppError>;
+    async fn update_email(&self, id: Uuid, email: &str) -> Result<User, AppError>;
+    async fn create_email_change(&self, user_id: Uuid, new_email: &str, token_hash: &str, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+    async fn find_email_change(&self, user_id: Uuid, token_hash: &str) -> Result<Option<PendingEmailChange>, AppError>;
+    async fn delete_email_changes(&self, user_id: Uuid) -> Result<(), AppError>;
+    async fn find_locale(&self, user_id: Uuid) -> Result<Option<String>, AppError>;
+    async fn schedule_deletion(&self, id: Uuid, scheduled_at: DateTime<Utc>) -> Result<DateTime<Utc>, AppError>;
+    async fn cancel_deletion(&self, id: Uuid) -> Result<(), AppError>;
+    async fn find_due_deletions(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, AppError>;
+    async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    async fn find_export_data(&self, id: Uuid) -> Result<UserExportRows, AppError>;
+}
+
+// Every row the account owns, for the personal data export; add a field with each table
+// that holds user data
+#[derive(Debug)]
+pub struct UserExportRows {
+    pub pockets: Vec<Pocket>,
+    pub transactions: Vec<Transaction>,
+    pub budgets: Vec<Budget>,
+    pub spending_limits: Vec<SpendingLimit>,
+    pub debts: Vec<Debt>,
+    pub refunds: Vec<Refund>,
+    pub reminders: Vec<Reminder>,
+    // None until the user saves preferences
+    pub preferences: Option<UserPreferences>,
 }
 
 #[derive(Clone)]
@@ -23,29 +55,54 @@ impl PostgresUserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    fn push_filters(builder: &mut QueryBuilder<'static, Postgres>, query: &ListUsersQuery) {
+        builder.push(" WHERE TRUE");
+
+        if let Some(pattern) = like_pattern(query.email.as_deref()) {
+            builder.push(" AND email ILIKE ").push_bind(pattern);
+        }
+
+        if let Some(pattern) = like_pattern(query.name.as_deref()) {
+            builder.push(" AND name ILIKE ").push_bind(pattern);
+        }
+    }
+}
+
+// LIKE wildcards in the search text are matched literally
+fn like_pattern(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            let escaped = value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            format!("%{}%", escaped)
+        })
 }
 
 #[async_trait::async_trait]
 impl UserRepository for PostgresUserRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError> {
-        let row = sqlx::query(
-            "SELECT id, name, email, password, hide_balance, created_at, updated_at
-             FROM users WHERE id = $1"
+        let row = sqlx::query!(
+            r#"SELECT id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM users WHERE id = $1"#,
+            id
         )
-        .bind(id)
         .fetch_optional(&self.pool)
         .await?;
         
         match row {
             Some(row) => {
                 let user = User {
-                    id: row.get("id"),
-                    name: row.get("name"),
-                    email: row.get("email"),
-                    password: row.get("password"),
-                    hide_balance: row.get("hide_balance"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
+                    id: row.id,
+                    name: row.name,
+                    email: row.email,
+                    password: row.password,
+                    hide_balance: row.hide_balance,
+                    base_currency: row.base_currency,
+                    monthly_digest: row.monthly_digest,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
                 };
                 Ok(Some(user))
             }
@@ -54,24 +111,26 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
-        let row = sqlx::query(
-            "SELECT id, name, email, password, hide_balance, created_at, updated_at
-             FROM users WHERE email = $1"
+        let row = sqlx::query!(
+            r#"SELECT id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM users WHERE email = $1"#,
+            email
         )
-        .bind(email)
         .fetch_optional(&self.pool)
         .await?;
         
         match row {
             Some(row) => {
                 let user = User {
-                    id: row.get("id"),
-                    name: row.get("name"),
-                    email: row.get("email"),
-                    password: row.get("password"),
-                    hide_balance: row.get("hide_balance"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
+                    id: row.id,
+                    name: row.name,
+                    email: row.email,
+                    password: row.password,
+                    hide_balance: row.hide_balance,
+                    base_currency: row.base_currency,
+                    monthly_digest: row.monthly_digest,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
                 };
                 Ok(Some(user))
             }
@@ -80,101 +139,452 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn create(&self, user: User) -> Result<User, AppError> {
-        let row = sqlx::query(
-            "INSERT INTO users (id, name, email, password, hide_balance, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7)
-             RETURNING id, name, email, password, hide_balance, created_at, updated_at"
-        )
-        .bind(&user.id)
-        .bind(&user.name)
-        .bind(&user.email)
-        .bind(&user.password)
-        .bind(user.hide_balance)
-        .bind(&user.created_at)
-        .bind(&user.updated_at)
+        let row = sqlx::query!(
+            r#"INSERT INTO users (id, name, email, password, hide_balance, base_currency, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            user.id,
+            user.name,
+            user.email,
+            user.password,
+            user.hide_balance,
+            user.base_currency,
+            user.created_at,
+            user.updated_at
+        )
         .fetch_one(&self.pool)
         .await?;
         
         let created_user = User {
-            id: row.get("id"),
-            name: row.get("name"),
-            email: row.get("email"),
-            password: row.get("password"),
-            hide_balance: row.get("hide_balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         };
         
         Ok(created_user)
     }
 
     async fn update_name(&self, id: Uuid, name: &str) -> Result<User, AppError> {
-        let row = sqlx::query(
-            "UPDATE users SET name = $1, updated_at = NOW()
+        let row = sqlx::query!(
+            r#"UPDATE users SET name = $1, updated_at = NOW()
              WHERE id = $2
-             RETURNING id, name, email, password, hide_balance, created_at, updated_at"
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            name,
+            id
         )
-        .bind(name)
-        .bind(id)
         .fetch_one(&self.pool)
         .await?;
         
         let updated_user = User {
-            id: row.get("id"),
-            name: row.get("name"),
-            email: row.get("email"),
-            password: row.get("password"),
-            hide_balance: row.get("hide_balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         };
         
         Ok(updated_user)
     }
 
     async fn update_hide_balance(&self, id: Uuid, hide_balance: bool) -> Result<User, AppError> {
-        let row = sqlx::query(
-            "UPDATE users SET hide_balance = $1, updated_at = NOW()
+        let row = sqlx::query!(
+            r#"UPDATE users SET hide_balance = $1, updated_at = NOW()
              WHERE id = $2
-             RETURNING id, name, email, password, hide_balance, created_at, updated_at"
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            hide_balance,
+            id
         )
-        .bind(hide_balance)
-        .bind(id)
         .fetch_one(&self.pool)
         .await?;
         
         let updated_user = User {
-            id: row.get("id"),
-            name: row.get("name"),
-            email: row.get("email"),
-            password: row.get("password"),
-            hide_balance: row.get("hide_balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
         };
         
         Ok(updated_user)
     }
 
-    async fn list_all(&self) -> Result<Vec<User>, AppError> {
-        let rows = sqlx::query(
-            "SELECT id, name, email, password, hide_balance, created_at, updated_at
-             FROM users
-             ORDER BY created_at DESC"
+    async fn update_base_currency(&self, id: Uuid, base_currency: &str) -> Result<User, AppError> {
+        let row = sqlx::query!(
+            r#"UPDATE users SET base_currency = $1, updated_at = NOW()
+             WHERE id = $2
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            base_currency,
+            id
         )
-        .fetch_all(&self.pool)
+        .fetch_one(&self.pool)
+        .await?;
+        
+        let updated_user = User {
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        };
+        
+        Ok(updated_user)
+    }
+
+    async fn update_monthly_digest(&self, id: Uuid, monthly_digest: bool) -> Result<User, AppError> {
+        let row = sqlx::query!(
+            r#"UPDATE users SET monthly_digest = $1, updated_at = NOW()
+             WHERE id = $2
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            monthly_digest,
+            id
+        )
+        .fetch_one(&self.pool)
         .await?;
         
-        let users = rows.into_iter().map(|row| User {
-            id: row.get("id"),
-            name: row.get("name"),
-            email: row.get("email"),
-            password: row.get("password"),
-            hide_balance: row.get("hide_balance"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        }).collect();
+        let updated_user = User {
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        };
         
-        Ok(users)
+        Ok(updated_user)
+    }
+
+    async fn list_page(&self, query: &ListUsersQuery, limit: i64, offset: i64) -> Result<Page<User>, AppError> {
+        let mut builder = QueryBuilder::new(
+            "SELECT id, name, email, password, hide_balance, base_currency, monthly_digest, created_at, updated_at,
+                    COUNT(*) OVER() AS total_items
+             FROM users"
+        );
+        Self::push_filters(&mut builder, query);
+
+        // Both are checked against a whitelist by the service before reaching here
+        let sort_by = match query.sort_by.as_deref() {
+            Some("name") => "name",
+            Some("email") => "email",
+            _ => "created_at",
+        };
+        let order = if query.order.as_deref() == Some("asc") { "ASC" } else { "DESC" };
+        builder
+            .push(format!(" ORDER BY {} {}, id {} LIMIT ", sort_by, order, order))
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = builder
+            .build_query_as::<Counted<User>>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        match Page::from_counted(rows, offset) {
+            Some(page) => Ok(page),
+            None => Ok(Page::empty(self.count(query).await?)),
+        }
+    }
+
+    async fn count(&self, query: &ListUsersQuery) -> Result<i64, AppError> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM users");
+        Self::push_filters(&mut builder, query);
+
+        let count = builder
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn update_password(&self, id: Uuid, hashed_password: &str) -> Result<User, AppError> {
+        let row = sqlx::query!(
+            r#"UPDATE users SET password = $1, updated_at = NOW()
+             WHERE id = $2
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            hashed_password,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let updated_user = User {
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        };
+
+        Ok(updated_user)
+    }
+
+    async fn update_email(&self, id: Uuid, email: &str) -> Result<User, AppError> {
+        let row = sqlx::query!(
+            r#"UPDATE users SET email = $1, updated_at = NOW()
+             WHERE id = $2
+             RETURNING id, name, email, password, hide_balance AS "hide_balance!", base_currency, monthly_digest, created_at AS "created_at!", updated_at AS "updated_at!""#,
+            email,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("duplicate key") {
+                AppError::Conflict("Email already exists".to_string()).with_code(codes::EMAIL_TAKEN)
+            } else {
+                AppError::from(e)
+            }
+        })?;
+
+        let updated_user = User {
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            password: row.password,
+            hide_balance: row.hide_balance,
+            base_currency: row.base_currency,
+            monthly_digest: row.monthly_digest,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        };
+
+        Ok(updated_user)
+    }
+
+    async fn create_email_change(&self, user_id: Uuid, new_email: &str, token_hash: &str, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        // Only the most recent request stays valid
+        self.delete_email_changes(user_id).await?;
+
+        sqlx::query!(
+            "INSERT INTO email_change_requests (user_id, new_email, token_hash, expires_at)
+             VALUES ($1, $2, $3, $4)",
+            user_id,
+            new_email,
+            token_hash,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_email_change(&self, user_id: Uuid, token_hash: &str) -> Result<Option<PendingEmailChange>, AppError> {
+        let pending = sqlx::query_as!(
+            PendingEmailChange,
+            r#"SELECT id, user_id, new_email, token_hash, expires_at, created_at AS "created_at!"
+             FROM email_change_requests WHERE user_id = $1 AND token_hash = $2"#,
+            user_id,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(pending)
+    }
+
+    async fn delete_email_changes(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query!(
+            "DELETE FROM email_change_requests WHERE user_id = $1",
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_locale(&self, user_id: Uuid) -> Result<Option<String>, AppError> {
+        let locale = sqlx::query_scalar!(
+            "SELECT locale FROM user_preferences WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(locale)
+    }
+
+    async fn schedule_deletion(&self, id: Uuid, scheduled_at: DateTime<Utc>) -> Result<DateTime<Utc>, AppError> {
+        // Keep the original date if the user asks twice
+        let scheduled = sqlx::query_scalar!(
+            r#"UPDATE users SET deletion_scheduled_at = COALESCE(deletion_scheduled_at, $1), updated_at = NOW()
+             WHERE id = $2
+             RETURNING deletion_scheduled_at AS "deletion_scheduled_at!""#,
+            scheduled_at,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(scheduled)
+    }
+
+    async fn cancel_deletion(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query!(
+            "UPDATE users SET deletion_scheduled_at = NULL, updated_at = NOW()
+             WHERE id = $1 AND deletion_scheduled_at IS NOT NULL",
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("No pending account deletion".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn find_due_deletions(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, AppError> {
+        let ids = sqlx::query_scalar!(
+            "SELECT id FROM users WHERE deletion_scheduled_at IS NOT NULL AND deletion_scheduled_at <= $1",
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Shared pockets and budgets stay with their organization and only lose their creator,
+        // so personal ones are removed here rather than by the cascade
+        sqlx::query!(
+            "DELETE FROM pockets WHERE user_id = $1 AND organization_id IS NULL",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM budgets WHERE user_id = $1 AND organization_id IS NULL",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Transactions, limits and the rest cascade from users
+        sqlx::query!(
+            "DELETE FROM users WHERE id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_export_data(&self, id: Uuid) -> Result<UserExportRows, AppError> {
+        let pockets = sqlx::query_as!(
+            Pocket,
+            r#"SELECT id, user_id, organization_id, name, emoji, balance AS "balance!", archived, sort_order, pocket_group AS "group", currency, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM pockets WHERE user_id = $1 AND organization_id IS NULL ORDER BY created_at"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let transactions = sqlx::query_as!(
+            Transaction,
+            r#"SELECT id AS "id!", user_id AS "user_id!", account_id, description AS "description!", amount AS "amount!",
+                    category AS "category!", transaction_type AS "transaction_type!", transaction_date AS "transaction_date!",
+                    status AS "status!", notes, metadata, original_amount, original_currency, exchange_rate,
+                    created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM all_transactions WHERE user_id = $1
+             ORDER BY transaction_date, id"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let budgets = sqlx::query_as!(
+            Budget,
+            r#"SELECT id, user_id, organization_id, category, target_amount, period_type, period_start, period_end, is_active, alert_thresholds, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM budgets WHERE user_id = $1 AND organization_id IS NULL ORDER BY created_at"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let spending_limits = sqlx::query_as!(
+            SpendingLimit,
+            r#"SELECT id, user_id, category, monthly_limit, enforcement, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM spending_limits WHERE user_id = $1 ORDER BY category"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let debts = sqlx::query_as!(
+            Debt,
+            r#"SELECT id, user_id, name, debt_type, principal, apr, minimum_payment, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM debts WHERE user_id = $1 ORDER BY id"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let refunds = sqlx::query_as!(
+            Refund,
+            r#"SELECT id, user_id, original_transaction_id, refund_transaction_id, requested_amount, received_amount,
+                    status, note, resolved_at, created_at AS "created_at!", updated_at AS "updated_at!"
+             FROM transaction_refunds WHERE user_id = $1 ORDER BY id"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let reminders = sqlx::query_as!(
+            Reminder,
+            r#"SELECT r.id, r.user_id, r.transaction_id, r.note, r.remind_on, r.status, r.notified_at,
+                    r.created_at AS "created_at!", r.updated_at AS "updated_at!",
+                    t.description AS transaction_description, t.amount AS transaction_amount, t.transaction_date
+             FROM transaction_reminders r
+             JOIN transactions t ON t.id = r.transaction_id
+             WHERE r.user_id = $1 ORDER BY r.id"#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let preferences = sqlx::query_as!(
+            UserPreferences,
+            "SELECT currency, locale, week_start, timezone, updated_at FROM user_preferences WHERE user_id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(UserExportRows { pockets, transactions, budgets, spending_limits, debts, refunds, reminders, preferences })
     }
 }
\ No newline at end of file
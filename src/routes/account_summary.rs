@@ -4,12 +4,13 @@ use axum::{
 };
 
 use crate::handlers::account_summary::get_account_summary;
-use crate::middleware::auth_middleware;
-use crate::services::AccountSummaryService;
-use crate::repositories::{PostgresPocketRepository, PostgresTransactionRepository};
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn account_summary_routes() -> Router<AccountSummaryService<PostgresPocketRepository, PostgresTransactionRepository>> {
+pub fn account_summary_routes() -> Router<AppState> {
     Router::new()
-        .route("/account-summary", get(get_account_summary))
+        .route(paths::ACCOUNT_SUMMARY, get(get_account_summary))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
         .layer(axum::middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
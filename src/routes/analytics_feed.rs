@@ -0,0 +1,24 @@
+use axum::{
+    middleware,
+    routing::{delete, get},
+    Router,
+};
+
+use crate::handlers::analytics_feed::{
+    create_analytics_feed, get_analytics_feed, list_analytics_feeds, revoke_analytics_feed,
+};
+use crate::middleware::auth::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn analytics_feed_routes() -> Router<AppState> {
+    let owner_routes = Router::new()
+        .route(paths::ANALYTICS_FEEDS, get(list_analytics_feeds).post(create_analytics_feed))
+        .route(paths::ANALYTICS_FEED, delete(revoke_analytics_feed))
+        .route_layer(middleware::from_fn(auth_middleware));
+
+    // Dashboards poll with the feed token alone, so the feed itself sits outside auth
+    Router::new()
+        .route(paths::SHARED_ANALYTICS_FEED, get(get_analytics_feed))
+        .merge(owner_routes)
+}
\ No newline at end of file
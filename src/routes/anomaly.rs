@@ -0,0 +1,16 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::anomaly::get_anomalies;
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn anomaly_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::ANOMALIES, get(get_anomalies))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::audit::{get_audit_log, export_audit_log};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn audit_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::AUDIT_LOG, get(get_audit_log))
+        .route(paths::AUDIT_LOG_EXPORT, get(export_audit_log))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
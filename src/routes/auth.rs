@@ -1,11 +1,15 @@
-use axum::{routing::post, Router};
+use axum::{middleware, routing::{get, post}, Router};
 
-use crate::handlers::auth::{login, register};
-use crate::services::AuthService;
-use crate::repositories::PostgresAuthRepository;
+use crate::handlers::auth::{jwks, login, refresh, register, step_up};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn auth_routes() -> Router<AuthService<PostgresAuthRepository>> {
+pub fn auth_routes() -> Router<AppState> {
     Router::new()
-        .route("/login", post(login))
-        .route("/register", post(register))
+        .route(paths::JWKS, get(jwks))
+        .route(paths::AUTH_LOGIN, post(login))
+        .route(paths::AUTH_REGISTER, post(register))
+        .route(paths::AUTH_REFRESH, post(refresh))
+        .route(paths::AUTH_STEP_UP, post(step_up).layer(middleware::from_fn(auth_middleware)))
 }
\ No newline at end of file
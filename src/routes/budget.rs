@@ -5,19 +5,22 @@ use axum::{
 
 use crate::handlers::budget::{
     get_budgets, get_budget_by_id, create_budget, update_budget, delete_budget,
-    get_budget_summary, get_budget_performance, get_budget_categories, get_budget_suggestions
+    get_budget_summary, get_budget_performance, get_budget_categories, get_budget_suggestions,
+    get_budget_detail_performance, get_budget_history,
 };
 use crate::middleware::auth_middleware;
-use crate::services::BudgetService;
-use crate::repositories::PostgresBudgetRepository;
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn budget_routes() -> Router<BudgetService<PostgresBudgetRepository>> {
+pub fn budget_routes() -> Router<AppState> {
     Router::new()
-        .route("/budgets", get(get_budgets).post(create_budget))
-        .route("/budgets/{id}", get(get_budget_by_id).put(update_budget).delete(delete_budget))
-        .route("/budgets/summary", get(get_budget_summary))
-        .route("/budgets/performance", get(get_budget_performance))
-        .route("/budgets/categories", get(get_budget_categories))
-        .route("/budgets/suggestions", get(get_budget_suggestions))
+        .route(paths::BUDGETS, get(get_budgets).post(create_budget))
+        .route(paths::BUDGET, get(get_budget_by_id).put(update_budget).delete(delete_budget))
+        .route(paths::BUDGET_SUMMARY, get(get_budget_summary))
+        .route(paths::BUDGET_PERFORMANCE, get(get_budget_performance))
+        .route(paths::BUDGET_DETAIL_PERFORMANCE, get(get_budget_detail_performance))
+        .route(paths::BUDGET_HISTORY, get(get_budget_history))
+        .route(paths::BUDGET_CATEGORIES, get(get_budget_categories))
+        .route(paths::BUDGET_SUGGESTIONS, get(get_budget_suggestions))
         .layer(axum::middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
@@ -0,0 +1,16 @@
+use axum::{
+    routing::post,
+    Router,
+};
+
+use crate::handlers::categorization::{suggest_category, submit_category_feedback};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn categorization_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::CATEGORIZATION_SUGGEST, post(suggest_category))
+        .route(paths::CATEGORIZATION_FEEDBACK, post(submit_category_feedback))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use axum::{
+    middleware,
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::handlers::chat_bot::{create_link_code, list_linked_accounts, receive_slack_command, receive_telegram_update, unlink_account};
+use crate::middleware::auth::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn chat_bot_routes() -> Router<AppState> {
+    let owner_routes = Router::new()
+        .route(paths::USER_LINKED_ACCOUNTS, get(list_linked_accounts))
+        .route(paths::USER_LINKED_ACCOUNT, delete(unlink_account))
+        .route(paths::USER_LINK_CODES, post(create_link_code))
+        .route_layer(middleware::from_fn(auth_middleware));
+
+    // Chat services sign their requests; the handlers verify them in place of a session
+    Router::new()
+        .route(paths::CHAT_WEBHOOK_TELEGRAM, post(receive_telegram_update))
+        .route(paths::CHAT_WEBHOOK_SLACK, post(receive_slack_command))
+        .merge(owner_routes)
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use axum::{
+    routing::put,
+    Router,
+};
+
+use crate::handlers::currency::update_base_currency;
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn currency_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::USER_BASE_CURRENCY, put(update_base_currency))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::debt::{
+    get_debts, get_debt_by_id, create_debt, update_debt, delete_debt, get_debt_payoff_plan
+};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn debt_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::DEBTS, get(get_debts).post(create_debt))
+        .route(paths::DEBT, get(get_debt_by_id).put(update_debt).delete(delete_debt))
+        .route(paths::DEBT_PAYOFF_PLAN, get(get_debt_payoff_plan))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::email_template::{list_email_templates, preview_email_template};
+use crate::middleware::{admin_middleware, auth_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn email_template_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::ADMIN_EMAIL_TEMPLATES, get(list_email_templates))
+        .route(paths::ADMIN_EMAIL_TEMPLATE_PREVIEW, get(preview_email_template))
+        .layer(axum::middleware::from_fn(admin_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -5,18 +5,20 @@ use axum::{
 
 use crate::handlers::expense_analytics::{
     get_expense_summary, get_expense_category_summary, get_expense_monthly_trend,
-    get_expense_daily_trend, get_recent_expense_transactions,
+    get_expense_daily_trend, get_expense_heatmap, get_recent_expense_transactions,
 };
-use crate::middleware::auth_middleware;
-use crate::services::ExpenseAnalyticsService;
-use crate::repositories::PostgresTransactionRepository;
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn expense_analytics_routes() -> Router<ExpenseAnalyticsService<PostgresTransactionRepository>> {
+pub fn expense_analytics_routes() -> Router<AppState> {
     Router::new()
-        .route("/expense-analytics/summary", get(get_expense_summary))
-        .route("/expense-analytics/category-summary", get(get_expense_category_summary))
-        .route("/expense-analytics/monthly-trend", get(get_expense_monthly_trend))
-        .route("/expense-analytics/daily-trend", get(get_expense_daily_trend))
-        .route("/expense-analytics/recent", get(get_recent_expense_transactions))
+        .route(paths::EXPENSE_SUMMARY, get(get_expense_summary))
+        .route(paths::EXPENSE_CATEGORY_SUMMARY, get(get_expense_category_summary))
+        .route(paths::EXPENSE_MONTHLY_TREND, get(get_expense_monthly_trend))
+        .route(paths::EXPENSE_DAILY_TREND, get(get_expense_daily_trend))
+        .route(paths::EXPENSE_RECENT, get(get_recent_expense_transactions))
+        .route(paths::EXPENSE_HEATMAP, get(get_expense_heatmap))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
         .layer(axum::middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
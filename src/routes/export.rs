@@ -0,0 +1,15 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::export::export_transactions;
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn export_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::EXPORT_TRANSACTIONS, get(export_transactions))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use axum::{
+    middleware,
+    routing::{delete, get},
+    Router,
+};
+
+use crate::handlers::export_link::{
+    create_export_link, get_export_link_access_log, get_shared_export, get_shared_export_report,
+    get_shared_export_transactions, list_export_links, revoke_export_link,
+};
+use crate::middleware::auth::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn export_link_routes() -> Router<AppState> {
+    let owner_routes = Router::new()
+        .route(paths::EXPORT_LINKS, get(list_export_links).post(create_export_link))
+        .route(paths::EXPORT_LINK, delete(revoke_export_link))
+        .route(paths::EXPORT_LINK_ACCESS_LOG, get(get_export_link_access_log))
+        .route_layer(middleware::from_fn(auth_middleware));
+
+    // The token itself is the credential, so the shared exports sit outside auth
+    Router::new()
+        .route(paths::SHARED_EXPORT, get(get_shared_export))
+        .route(paths::SHARED_EXPORT_TRANSACTIONS, get(get_shared_export_transactions))
+        .route(paths::SHARED_EXPORT_REPORT, get(get_shared_export_report))
+        .merge(owner_routes)
+}
\ No newline at end of file
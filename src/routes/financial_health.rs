@@ -0,0 +1,16 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::financial_health::get_financial_health;
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn financial_health_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::FINANCIAL_HEALTH, get(get_financial_health))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use axum::{
+    routing::post,
+    Router,
+};
+
+use crate::handlers::graphql::graphql;
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn graphql_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::GRAPHQL, post(graphql))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use axum::{
+    routing::post,
+    Router,
+};
+
+use crate::handlers::import::import_transactions;
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn import_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::IMPORT_TRANSACTIONS, post(import_transactions))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
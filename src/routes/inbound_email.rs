@@ -0,0 +1,22 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::handlers::inbound_email::{generate_inbound_address, get_inbound_address, receive_mailgun_email, receive_ses_email};
+use crate::middleware::auth::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn inbound_email_routes() -> Router<AppState> {
+    let owner_routes = Router::new()
+        .route(paths::USER_INBOUND_ADDRESS, get(get_inbound_address).post(generate_inbound_address))
+        .route_layer(middleware::from_fn(auth_middleware));
+
+    // Mail providers can't hold a session, so the handlers check their signatures instead
+    Router::new()
+        .route(paths::INBOUND_EMAIL_MAILGUN, post(receive_mailgun_email))
+        .route(paths::INBOUND_EMAIL_SES, post(receive_ses_email))
+        .merge(owner_routes)
+}
\ No newline at end of file
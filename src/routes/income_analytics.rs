@@ -7,16 +7,17 @@ use crate::handlers::income_analytics::{
     get_income_summary, get_income_category_summary, get_income_monthly_trend,
     get_income_daily_trend, get_recent_income_transactions,
 };
-use crate::middleware::auth_middleware;
-use crate::services::IncomeAnalyticsService;
-use crate::repositories::PostgresTransactionRepository;
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn income_analytics_routes() -> Router<IncomeAnalyticsService<PostgresTransactionRepository>> {
+pub fn income_analytics_routes() -> Router<AppState> {
     Router::new()
-        .route("/income-analytics/summary", get(get_income_summary))
-        .route("/income-analytics/category-summary", get(get_income_category_summary))
-        .route("/income-analytics/monthly-trend", get(get_income_monthly_trend))
-        .route("/income-analytics/daily-trend", get(get_income_daily_trend))
-        .route("/income-analytics/recent", get(get_recent_income_transactions))
+        .route(paths::INCOME_SUMMARY, get(get_income_summary))
+        .route(paths::INCOME_CATEGORY_SUMMARY, get(get_income_category_summary))
+        .route(paths::INCOME_MONTHLY_TREND, get(get_income_monthly_trend))
+        .route(paths::INCOME_DAILY_TREND, get(get_income_daily_trend))
+        .route(paths::INCOME_RECENT, get(get_recent_income_transactions))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
         .layer(axum::middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
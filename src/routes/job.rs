@@ -0,0 +1,16 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::job::{get_jobs, get_job_by_id};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn job_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::JOBS, get(get_jobs))
+        .route(paths::JOB, get(get_job_by_id))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
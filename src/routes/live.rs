@@ -0,0 +1,15 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::live::live_updates;
+use crate::middleware::query_token_auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn live_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::LIVE_SOCKET, get(live_updates))
+        .layer(axum::middleware::from_fn(query_token_auth_middleware))
+}
\ No newline at end of file
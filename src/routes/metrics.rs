@@ -0,0 +1,14 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::metrics::get_metrics;
+use crate::routes::paths;
+use crate::state::AppState;
+
+// Guarded by METRICS_TOKEN instead of user auth so scrapers can reach it
+pub fn metrics_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::METRICS, get(get_metrics))
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+use axum::{
+    middleware,
+    routing::{delete, get},
+    Router,
+};
+
+use crate::handlers::notification::{
+    delete_device, get_notification_preferences, list_devices, notification_stream, register_device, update_notification_preferences,
+};
+use crate::middleware::{auth::auth_middleware, query_token_auth_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn notification_routes() -> Router<AppState> {
+    let push_routes = Router::new()
+        .route(paths::DEVICES, get(list_devices).post(register_device))
+        .route(paths::DEVICE, delete(delete_device))
+        .route(paths::NOTIFICATION_PREFERENCES, get(get_notification_preferences).put(update_notification_preferences))
+        .route_layer(middleware::from_fn(auth_middleware));
+
+    Router::new()
+        .route(paths::NOTIFICATION_STREAM, get(notification_stream))
+        .layer(axum::middleware::from_fn(query_token_auth_middleware))
+        .merge(push_routes)
+}
\ No newline at end of file
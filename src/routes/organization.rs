@@ -0,0 +1,29 @@
+use axum::{
+    middleware,
+    routing::{get, put},
+    Router,
+};
+
+use crate::handlers::organization::{
+    add_organization_member, create_organization, create_organization_budget, create_organization_pocket,
+    delete_organization, delete_organization_budget, get_organization, get_organization_budgets, get_organization_pockets,
+    list_organization_members, list_organizations, remove_organization_member, update_organization,
+    update_organization_budget, update_organization_member, update_organization_pocket,
+};
+use crate::middleware::{auth::auth_middleware, balance_visibility::balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn organization_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::ORGANIZATIONS, get(list_organizations).post(create_organization))
+        .route(paths::ORGANIZATION, get(get_organization).put(update_organization).delete(delete_organization))
+        .route(paths::ORGANIZATION_MEMBERS, get(list_organization_members).post(add_organization_member))
+        .route(paths::ORGANIZATION_MEMBER, put(update_organization_member).delete(remove_organization_member))
+        .route(paths::ORGANIZATION_POCKETS, get(get_organization_pockets).post(create_organization_pocket))
+        .route(paths::ORGANIZATION_POCKET, put(update_organization_pocket))
+        .route(paths::ORGANIZATION_BUDGETS, get(get_organization_budgets).post(create_organization_budget))
+        .route(paths::ORGANIZATION_BUDGET, put(update_organization_budget).delete(delete_organization_budget))
+        .route_layer(middleware::from_fn(balance_visibility_middleware))
+        .route_layer(middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,140 @@
+// Every path the API serves. Route modules register through these constants and the
+// route table in `table.rs` describes them, so a path is spelled out exactly once.
+
+pub const HEALTH: &str = "/health";
+pub const HEALTH_LIVE: &str = "/health/live";
+pub const HEALTH_READY: &str = "/health/ready";
+pub const STATUS: &str = "/status";
+pub const METRICS: &str = "/metrics";
+
+pub const JWKS: &str = "/.well-known/jwks.json";
+pub const AUTH_LOGIN: &str = "/auth/login";
+pub const AUTH_REGISTER: &str = "/auth/register";
+pub const AUTH_REFRESH: &str = "/auth/refresh";
+pub const AUTH_STEP_UP: &str = "/auth/step-up";
+pub const AUTH_SESSIONS: &str = "/auth/sessions";
+pub const AUTH_SESSION: &str = "/auth/sessions/{id}";
+pub const AUTH_LOGOUT: &str = "/auth/logout";
+
+pub const USERS: &str = "/users";
+pub const USER_ME: &str = "/users/me";
+pub const USER_CANCEL_DELETION: &str = "/users/me/cancel-deletion";
+pub const USER_EXPORT: &str = "/users/me/export";
+pub const USER_PASSWORD: &str = "/users/me/password";
+pub const USER_EMAIL: &str = "/users/me/email";
+pub const USER_EMAIL_VERIFY: &str = "/users/me/email/verify";
+pub const USER_BASE_CURRENCY: &str = "/users/me/base-currency";
+pub const USER_PREFERENCES: &str = "/users/me/preferences";
+pub const USER_INBOUND_ADDRESS: &str = "/users/me/inbound-address";
+pub const USER_LINKED_ACCOUNTS: &str = "/users/me/linked-accounts";
+pub const USER_LINKED_ACCOUNT: &str = "/users/me/linked-accounts/{provider}";
+pub const USER_LINK_CODES: &str = "/users/me/link-codes";
+pub const USER_NAME: &str = "/users/name";
+pub const USER_HIDE_BALANCE: &str = "/users/hide-balance";
+pub const USER_MONTHLY_DIGEST: &str = "/users/monthly-digest";
+
+pub const POCKETS: &str = "/pockets";
+pub const POCKET_BALANCES: &str = "/pockets/balances";
+pub const POCKET_REORDER: &str = "/pockets/reorder";
+pub const POCKET_IMPORT: &str = "/pockets/import";
+pub const POCKET: &str = "/pockets/{id}";
+pub const POCKET_ARCHIVE: &str = "/pockets/{id}/archive";
+pub const POCKET_UNARCHIVE: &str = "/pockets/{id}/unarchive";
+pub const POCKET_ADJUSTMENTS: &str = "/pockets/{id}/adjustments";
+pub const POCKET_TRANSACTIONS: &str = "/pockets/{id}/transactions";
+pub const POCKET_SHARE_TOKENS: &str = "/pockets/{id}/share-tokens";
+pub const POCKET_SHARE_TOKEN: &str = "/pockets/{id}/share-tokens/{token_id}";
+pub const SHARED_POCKET_SUMMARY: &str = "/shared/{token}/summary";
+
+pub const ORGANIZATIONS: &str = "/organizations";
+pub const ORGANIZATION: &str = "/organizations/{id}";
+pub const ORGANIZATION_MEMBERS: &str = "/organizations/{id}/members";
+pub const ORGANIZATION_MEMBER: &str = "/organizations/{id}/members/{user_id}";
+pub const ORGANIZATION_POCKETS: &str = "/organizations/{id}/pockets";
+pub const ORGANIZATION_POCKET: &str = "/organizations/{id}/pockets/{pocket_id}";
+pub const ORGANIZATION_BUDGETS: &str = "/organizations/{id}/budgets";
+pub const ORGANIZATION_BUDGET: &str = "/organizations/{id}/budgets/{budget_id}";
+
+pub const TRANSACTIONS: &str = "/transactions";
+pub const TRANSACTION: &str = "/transactions/{id}";
+pub const TRANSACTION_POST: &str = "/transactions/{id}/post";
+pub const TRANSACTION_CANCEL: &str = "/transactions/{id}/cancel";
+pub const TRANSACTION_REFUNDS: &str = "/transactions/{id}/refunds";
+pub const TRANSACTION_REMINDERS: &str = "/transactions/{id}/reminders";
+pub const IMPORT_TRANSACTIONS: &str = "/imports/transactions";
+pub const INBOUND_EMAIL_MAILGUN: &str = "/webhooks/inbound-email/mailgun";
+pub const INBOUND_EMAIL_SES: &str = "/webhooks/inbound-email/ses";
+pub const CHAT_WEBHOOK_TELEGRAM: &str = "/webhooks/chat/telegram";
+pub const CHAT_WEBHOOK_SLACK: &str = "/webhooks/chat/slack";
+pub const EXPORT_TRANSACTIONS: &str = "/exports/transactions";
+
+pub const REFUNDS: &str = "/refunds";
+pub const REFUND_RECEIVE: &str = "/refunds/{id}/receive";
+pub const REFUND_WRITE_OFF: &str = "/refunds/{id}/write-off";
+
+pub const REMINDERS_DUE: &str = "/reminders/due";
+pub const REMINDER: &str = "/reminders/{id}";
+
+pub const BUDGETS: &str = "/budgets";
+pub const BUDGET: &str = "/budgets/{id}";
+pub const BUDGET_SUMMARY: &str = "/budgets/summary";
+pub const BUDGET_PERFORMANCE: &str = "/budgets/performance";
+pub const BUDGET_DETAIL_PERFORMANCE: &str = "/budgets/{id}/performance";
+pub const BUDGET_HISTORY: &str = "/budgets/{id}/history";
+pub const BUDGET_CATEGORIES: &str = "/budgets/categories";
+pub const BUDGET_SUGGESTIONS: &str = "/budgets/suggestions";
+
+pub const SPENDING_LIMITS: &str = "/spending-limits";
+pub const SPENDING_LIMIT: &str = "/spending-limits/{id}";
+pub const DEBTS: &str = "/debts";
+pub const DEBT: &str = "/debts/{id}";
+pub const DEBT_PAYOFF_PLAN: &str = "/debts/{id}/payoff-plan";
+
+pub const ACCOUNT_SUMMARY: &str = "/account-summary";
+pub const EXPENSE_SUMMARY: &str = "/expense-analytics/summary";
+pub const EXPENSE_CATEGORY_SUMMARY: &str = "/expense-analytics/category-summary";
+pub const EXPENSE_MONTHLY_TREND: &str = "/expense-analytics/monthly-trend";
+pub const EXPENSE_DAILY_TREND: &str = "/expense-analytics/daily-trend";
+pub const EXPENSE_RECENT: &str = "/expense-analytics/recent";
+pub const EXPENSE_HEATMAP: &str = "/analytics/expenses/heatmap";
+pub const INCOME_SUMMARY: &str = "/income-analytics/summary";
+pub const INCOME_CATEGORY_SUMMARY: &str = "/income-analytics/category-summary";
+pub const INCOME_MONTHLY_TREND: &str = "/income-analytics/monthly-trend";
+pub const INCOME_DAILY_TREND: &str = "/income-analytics/daily-trend";
+pub const INCOME_RECENT: &str = "/income-analytics/recent";
+pub const ANALYTICS_FEEDS: &str = "/analytics/feeds";
+pub const ANALYTICS_FEED: &str = "/analytics/feeds/{id}";
+pub const SUBSCRIPTIONS: &str = "/analytics/subscriptions";
+pub const ANOMALIES: &str = "/analytics/anomalies";
+pub const FINANCIAL_HEALTH: &str = "/analytics/health";
+pub const SHARED_ANALYTICS_FEED: &str = "/shared/feeds/{token}";
+
+pub const MONTHLY_REPORT: &str = "/reports/monthly";
+pub const EXPORT_LINKS: &str = "/export-links";
+pub const EXPORT_LINK: &str = "/export-links/{id}";
+pub const EXPORT_LINK_ACCESS_LOG: &str = "/export-links/{id}/access-log";
+pub const SHARED_EXPORT: &str = "/shared/exports/{token}";
+pub const SHARED_EXPORT_TRANSACTIONS: &str = "/shared/exports/{token}/transactions";
+pub const SHARED_EXPORT_REPORT: &str = "/shared/exports/{token}/reports/monthly";
+
+pub const CATEGORIZATION_SUGGEST: &str = "/categorization/suggest";
+pub const CATEGORIZATION_FEEDBACK: &str = "/categorization/feedback";
+pub const JOBS: &str = "/jobs";
+pub const JOB: &str = "/jobs/{id}";
+pub const AUDIT_LOG: &str = "/audit-log";
+pub const AUDIT_LOG_EXPORT: &str = "/audit-log/export";
+
+pub const LIVE_SOCKET: &str = "/ws";
+pub const NOTIFICATION_STREAM: &str = "/notifications/stream";
+pub const NOTIFICATION_PREFERENCES: &str = "/notifications/preferences";
+pub const DEVICES: &str = "/devices";
+pub const DEVICE: &str = "/devices/{id}";
+pub const GRAPHQL: &str = "/graphql";
+
+pub const ADMIN_ROUTES: &str = "/admin/routes";
+pub const ADMIN_EMAIL_TEMPLATES: &str = "/admin/email-templates";
+pub const ADMIN_EMAIL_TEMPLATE_PREVIEW: &str = "/admin/email-templates/{name}/preview";
+pub const ADMIN_TASKS: &str = "/admin/tasks";
+pub const ADMIN_TASK_RUNS: &str = "/admin/tasks/{name}/runs";
+pub const ADMIN_TASK_RUN: &str = "/admin/tasks/{name}/run";
+pub const ADMIN_REPAIR_BALANCES: &str = "/admin/repair/balances";
\ No newline at end of file
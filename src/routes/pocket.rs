@@ -1,19 +1,25 @@
 use axum::{
     middleware,
-    routing::get,
+    routing::{get, post, put},
     Router,
 };
 
 use crate::handlers::pocket::{
-    create_pocket, delete_pocket, get_pocket_by_id, get_pockets, update_pocket,
+    archive_pocket, create_pocket, delete_pocket, get_pocket_balances, get_pocket_by_id, get_pockets, reorder_pockets,
+    unarchive_pocket, update_pocket,
 };
-use crate::middleware::auth::auth_middleware;
-use crate::repositories::PostgresPocketRepository;
-use crate::services::PocketService;
+use crate::middleware::{auth::auth_middleware, balance_visibility::balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn pocket_routes() -> Router<PocketService<PostgresPocketRepository>> {
+pub fn pocket_routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(get_pockets).post(create_pocket))
-        .route("/{id}", get(get_pocket_by_id).put(update_pocket).delete(delete_pocket))
+        .route(paths::POCKETS, get(get_pockets).post(create_pocket))
+        .route(paths::POCKET_BALANCES, get(get_pocket_balances))
+        .route(paths::POCKET_REORDER, put(reorder_pockets))
+        .route(paths::POCKET, get(get_pocket_by_id).put(update_pocket).delete(delete_pocket))
+        .route(paths::POCKET_ARCHIVE, post(archive_pocket))
+        .route(paths::POCKET_UNARCHIVE, post(unarchive_pocket))
+        .route_layer(middleware::from_fn(balance_visibility_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
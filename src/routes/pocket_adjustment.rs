@@ -0,0 +1,17 @@
+use axum::{
+    middleware,
+    routing::post,
+    Router,
+};
+
+use crate::handlers::pocket_adjustment::create_pocket_adjustment;
+use crate::middleware::{auth::auth_middleware, balance_visibility::balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn pocket_adjustment_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::POCKET_ADJUSTMENTS, post(create_pocket_adjustment))
+        .route_layer(middleware::from_fn(balance_visibility_middleware))
+        .route_layer(middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
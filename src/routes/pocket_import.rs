@@ -0,0 +1,15 @@
+use axum::{
+    routing::post,
+    Router,
+};
+
+use crate::handlers::pocket_import::import_pockets;
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn pocket_import_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::POCKET_IMPORT, post(import_pockets))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::preference::{get_preferences, update_preferences};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn preference_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::USER_PREFERENCES, get(get_preferences).put(update_preferences))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::handlers::refund::{create_refund, get_refunds, get_transaction_refunds, receive_refund, write_off_refund};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn refund_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::TRANSACTION_REFUNDS, get(get_transaction_refunds).post(create_refund))
+        .route(paths::REFUNDS, get(get_refunds))
+        .route(paths::REFUND_RECEIVE, post(receive_refund))
+        .route(paths::REFUND_WRITE_OFF, post(write_off_refund))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
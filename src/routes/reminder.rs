@@ -0,0 +1,19 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+
+use crate::handlers::reminder::{
+    create_reminder, delete_reminder, get_due_reminders, get_transaction_reminders, update_reminder,
+};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn reminder_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::TRANSACTION_REMINDERS, get(get_transaction_reminders).post(create_reminder))
+        .route(paths::REMINDERS_DUE, get(get_due_reminders))
+        .route(paths::REMINDER, put(update_reminder).delete(delete_reminder))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
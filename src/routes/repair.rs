@@ -0,0 +1,13 @@
+use axum::{routing::post, Router};
+
+use crate::handlers::repair::repair_balances;
+use crate::middleware::{admin_middleware, auth_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn repair_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::ADMIN_REPAIR_BALANCES, post(repair_balances))
+        .layer(axum::middleware::from_fn(admin_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
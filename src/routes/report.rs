@@ -0,0 +1,15 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::report::get_monthly_report;
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn report_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::MONTHLY_REPORT, get(get_monthly_report))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use axum::{
+    routing::{get, delete, post},
+    Router,
+};
+
+use crate::handlers::session::{get_sessions, revoke_session, revoke_other_sessions, logout};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn session_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::AUTH_SESSIONS, get(get_sessions).delete(revoke_other_sessions))
+        .route(paths::AUTH_SESSION, delete(revoke_session))
+        .route(paths::AUTH_LOGOUT, post(logout))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use axum::{
+    middleware,
+    routing::{delete, get},
+    Router,
+};
+
+use crate::handlers::share_token::{create_share_token, get_shared_summary, list_share_tokens, revoke_share_token};
+use crate::middleware::auth::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn share_token_routes() -> Router<AppState> {
+    let owner_routes = Router::new()
+        .route(paths::POCKET_SHARE_TOKENS, get(list_share_tokens).post(create_share_token))
+        .route(paths::POCKET_SHARE_TOKEN, delete(revoke_share_token))
+        .route_layer(middleware::from_fn(auth_middleware));
+
+    // The token itself is the credential, so the shared view sits outside auth
+    Router::new()
+        .route(paths::SHARED_POCKET_SUMMARY, get(get_shared_summary))
+        .merge(owner_routes)
+}
\ No newline at end of file
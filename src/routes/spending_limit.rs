@@ -0,0 +1,19 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::spending_limit::{
+    get_spending_limits, get_spending_limit_by_id, create_spending_limit,
+    update_spending_limit, delete_spending_limit
+};
+use crate::middleware::auth_middleware;
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn spending_limit_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::SPENDING_LIMITS, get(get_spending_limits).post(create_spending_limit))
+        .route(paths::SPENDING_LIMIT, get(get_spending_limit_by_id).put(update_spending_limit).delete(delete_spending_limit))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,18 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::status::{get_status, liveness, readiness};
+use crate::routes::paths;
+use crate::state::AppState;
+
+// Public on purpose so clients can show outage banners before signing in, and so
+// orchestrator probes need no credentials
+pub fn status_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::HEALTH, get(liveness))
+        .route(paths::HEALTH_LIVE, get(liveness))
+        .route(paths::HEALTH_READY, get(readiness))
+        .route(paths::STATUS, get(get_status))
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::subscription_analytics::get_subscriptions;
+use crate::middleware::{auth_middleware, balance_visibility_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn subscription_analytics_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::SUBSCRIPTIONS, get(get_subscriptions))
+        .layer(axum::middleware::from_fn(balance_visibility_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
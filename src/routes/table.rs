@@ -0,0 +1,255 @@
+use axum::{
+    routing::get,
+    Router,
+};
+use serde::Serialize;
+
+use crate::handlers::route_table::list_routes;
+use crate::middleware::{admin_middleware, auth_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+// Who may call a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Access {
+    Public,
+    // Bearer access token
+    User,
+    // Bearer access token whose email is listed in ADMIN_EMAILS
+    Admin,
+    // Access token in the `token` query parameter, for WebSocket and EventSource clients
+    QueryToken,
+    // The share or feed token in the path is the only credential
+    LinkToken,
+    // METRICS_TOKEN as a bearer token
+    MetricsToken,
+    // The calling service's request signature or shared secret
+    Webhook,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum CachePolicy {
+    None,
+    // Response is kept in Redis per user and dropped when the user's data changes
+    Server { ttl_secs: u64 },
+    // Cache-Control: private, max-age
+    Private { max_age_secs: u64 },
+    // Cache-Control: public, max-age, for responses that are the same for every caller
+    Public { max_age_secs: u64 },
+    // Cache-Control: no-store, for responses that must stop the moment a link is revoked
+    NoStore,
+}
+
+// How long a request may run before it is answered with 408, see HttpConfig
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutBudget {
+    Standard,
+    // Whole-history exports, reports and file imports
+    Long,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RouteSpec {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub access: Access,
+    pub cache: CachePolicy,
+    pub timeout: TimeoutBudget,
+}
+
+const fn route(method: &'static str, path: &'static str, access: Access, cache: CachePolicy) -> RouteSpec {
+    RouteSpec { method, path, access, cache, timeout: TimeoutBudget::Standard }
+}
+
+impl RouteSpec {
+    const fn long_running(mut self) -> Self {
+        self.timeout = TimeoutBudget::Long;
+        self
+    }
+}
+
+// Budget for a matched route; routes missing from the table get the standard one
+pub fn timeout_budget(method: &str, path: &str) -> TimeoutBudget {
+    ROUTE_TABLE
+        .iter()
+        .find(|spec| spec.method == method && spec.path == path)
+        .map(|spec| spec.timeout)
+        .unwrap_or(TimeoutBudget::Standard)
+}
+
+// One entry per method and path the API serves. Keep in step with the route modules
+// when adding an endpoint; GET /admin/routes serves this table for review.
+pub const ROUTE_TABLE: &[RouteSpec] = &[
+    route("GET", paths::HEALTH, Access::Public, CachePolicy::None),
+    route("GET", paths::HEALTH_LIVE, Access::Public, CachePolicy::None),
+    route("GET", paths::HEALTH_READY, Access::Public, CachePolicy::None),
+    route("GET", paths::STATUS, Access::Public, CachePolicy::None),
+    route("GET", paths::METRICS, Access::MetricsToken, CachePolicy::None),
+    route("GET", paths::JWKS, Access::Public, CachePolicy::Public { max_age_secs: 300 }),
+    route("POST", paths::AUTH_LOGIN, Access::Public, CachePolicy::None),
+    route("POST", paths::AUTH_REGISTER, Access::Public, CachePolicy::None),
+    route("POST", paths::AUTH_REFRESH, Access::Public, CachePolicy::None),
+    route("POST", paths::AUTH_STEP_UP, Access::User, CachePolicy::None),
+    route("GET", paths::AUTH_SESSIONS, Access::User, CachePolicy::None),
+    route("DELETE", paths::AUTH_SESSIONS, Access::User, CachePolicy::None),
+    route("DELETE", paths::AUTH_SESSION, Access::User, CachePolicy::None),
+    route("POST", paths::AUTH_LOGOUT, Access::User, CachePolicy::None),
+    route("GET", paths::USERS, Access::Admin, CachePolicy::None),
+    route("GET", paths::USER_ME, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("DELETE", paths::USER_ME, Access::User, CachePolicy::None),
+    route("POST", paths::USER_CANCEL_DELETION, Access::User, CachePolicy::None),
+    route("GET", paths::USER_EXPORT, Access::User, CachePolicy::None).long_running(),
+    route("PUT", paths::USER_PASSWORD, Access::User, CachePolicy::None),
+    route("PUT", paths::USER_EMAIL, Access::User, CachePolicy::None),
+    route("POST", paths::USER_EMAIL_VERIFY, Access::User, CachePolicy::None),
+    route("PUT", paths::USER_BASE_CURRENCY, Access::User, CachePolicy::None),
+    route("GET", paths::USER_PREFERENCES, Access::User, CachePolicy::None),
+    route("PUT", paths::USER_PREFERENCES, Access::User, CachePolicy::None),
+    route("GET", paths::USER_INBOUND_ADDRESS, Access::User, CachePolicy::None),
+    route("POST", paths::USER_INBOUND_ADDRESS, Access::User, CachePolicy::None),
+    route("GET", paths::USER_LINKED_ACCOUNTS, Access::User, CachePolicy::None),
+    route("DELETE", paths::USER_LINKED_ACCOUNT, Access::User, CachePolicy::None),
+    route("POST", paths::USER_LINK_CODES, Access::User, CachePolicy::None),
+    route("PATCH", paths::USER_NAME, Access::User, CachePolicy::None),
+    route("PATCH", paths::USER_HIDE_BALANCE, Access::User, CachePolicy::None),
+    route("PATCH", paths::USER_MONTHLY_DIGEST, Access::User, CachePolicy::None),
+    route("GET", paths::POCKETS, Access::User, CachePolicy::Server { ttl_secs: 180 }),
+    route("POST", paths::POCKETS, Access::User, CachePolicy::None),
+    route("GET", paths::POCKET_BALANCES, Access::User, CachePolicy::Server { ttl_secs: 3600 }),
+    route("PUT", paths::POCKET_REORDER, Access::User, CachePolicy::None),
+    route("POST", paths::POCKET_IMPORT, Access::User, CachePolicy::None).long_running(),
+    route("GET", paths::POCKET, Access::User, CachePolicy::None),
+    route("PUT", paths::POCKET, Access::User, CachePolicy::None),
+    route("DELETE", paths::POCKET, Access::User, CachePolicy::None),
+    route("POST", paths::POCKET_ARCHIVE, Access::User, CachePolicy::None),
+    route("POST", paths::POCKET_UNARCHIVE, Access::User, CachePolicy::None),
+    route("POST", paths::POCKET_ADJUSTMENTS, Access::User, CachePolicy::None),
+    route("GET", paths::POCKET_TRANSACTIONS, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("GET", paths::POCKET_SHARE_TOKENS, Access::User, CachePolicy::None),
+    route("POST", paths::POCKET_SHARE_TOKENS, Access::User, CachePolicy::None),
+    route("DELETE", paths::POCKET_SHARE_TOKEN, Access::User, CachePolicy::None),
+    route("GET", paths::SHARED_POCKET_SUMMARY, Access::LinkToken, CachePolicy::NoStore),
+    // Organization routes are deliberately uncached. Cache entries are keyed by user, while a
+    // shared pocket's balance moves with every member's transactions and roles change under
+    // other members, so any per-user entry would go stale for everyone else in the organization.
+    route("GET", paths::ORGANIZATIONS, Access::User, CachePolicy::None),
+    route("POST", paths::ORGANIZATIONS, Access::User, CachePolicy::None),
+    route("GET", paths::ORGANIZATION, Access::User, CachePolicy::None),
+    route("PUT", paths::ORGANIZATION, Access::User, CachePolicy::None),
+    route("DELETE", paths::ORGANIZATION, Access::User, CachePolicy::None),
+    route("GET", paths::ORGANIZATION_MEMBERS, Access::User, CachePolicy::None),
+    route("POST", paths::ORGANIZATION_MEMBERS, Access::User, CachePolicy::None),
+    route("PUT", paths::ORGANIZATION_MEMBER, Access::User, CachePolicy::None),
+    route("DELETE", paths::ORGANIZATION_MEMBER, Access::User, CachePolicy::None),
+    route("GET", paths::ORGANIZATION_POCKETS, Access::User, CachePolicy::None),
+    route("POST", paths::ORGANIZATION_POCKETS, Access::User, CachePolicy::None),
+    route("PUT", paths::ORGANIZATION_POCKET, Access::User, CachePolicy::None),
+    route("GET", paths::ORGANIZATION_BUDGETS, Access::User, CachePolicy::None),
+    route("POST", paths::ORGANIZATION_BUDGETS, Access::User, CachePolicy::None),
+    route("PUT", paths::ORGANIZATION_BUDGET, Access::User, CachePolicy::None),
+    route("DELETE", paths::ORGANIZATION_BUDGET, Access::User, CachePolicy::None),
+    route("GET", paths::TRANSACTIONS, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("POST", paths::TRANSACTIONS, Access::User, CachePolicy::None),
+    route("GET", paths::TRANSACTION, Access::User, CachePolicy::None),
+    route("PUT", paths::TRANSACTION, Access::User, CachePolicy::None),
+    route("DELETE", paths::TRANSACTION, Access::User, CachePolicy::None),
+    route("POST", paths::TRANSACTION_POST, Access::User, CachePolicy::None),
+    route("POST", paths::TRANSACTION_CANCEL, Access::User, CachePolicy::None),
+    route("GET", paths::TRANSACTION_REFUNDS, Access::User, CachePolicy::None),
+    route("POST", paths::TRANSACTION_REFUNDS, Access::User, CachePolicy::None),
+    route("GET", paths::TRANSACTION_REMINDERS, Access::User, CachePolicy::None),
+    route("POST", paths::TRANSACTION_REMINDERS, Access::User, CachePolicy::None),
+    route("POST", paths::IMPORT_TRANSACTIONS, Access::User, CachePolicy::None).long_running(),
+    route("POST", paths::INBOUND_EMAIL_MAILGUN, Access::Webhook, CachePolicy::None),
+    route("POST", paths::INBOUND_EMAIL_SES, Access::Webhook, CachePolicy::None),
+    route("POST", paths::CHAT_WEBHOOK_TELEGRAM, Access::Webhook, CachePolicy::None),
+    route("POST", paths::CHAT_WEBHOOK_SLACK, Access::Webhook, CachePolicy::None),
+    route("GET", paths::EXPORT_TRANSACTIONS, Access::User, CachePolicy::None).long_running(),
+    route("GET", paths::REFUNDS, Access::User, CachePolicy::None),
+    route("POST", paths::REFUND_RECEIVE, Access::User, CachePolicy::None),
+    route("POST", paths::REFUND_WRITE_OFF, Access::User, CachePolicy::None),
+    route("GET", paths::REMINDERS_DUE, Access::User, CachePolicy::None),
+    route("PUT", paths::REMINDER, Access::User, CachePolicy::None),
+    route("DELETE", paths::REMINDER, Access::User, CachePolicy::None),
+    route("GET", paths::BUDGETS, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("POST", paths::BUDGETS, Access::User, CachePolicy::None),
+    route("GET", paths::BUDGET, Access::User, CachePolicy::None),
+    route("PUT", paths::BUDGET, Access::User, CachePolicy::None),
+    route("DELETE", paths::BUDGET, Access::User, CachePolicy::None),
+    route("GET", paths::BUDGET_SUMMARY, Access::User, CachePolicy::Server { ttl_secs: 600 }),
+    route("GET", paths::BUDGET_PERFORMANCE, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("GET", paths::BUDGET_DETAIL_PERFORMANCE, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("GET", paths::BUDGET_HISTORY, Access::User, CachePolicy::None),
+    route("GET", paths::BUDGET_CATEGORIES, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::BUDGET_SUGGESTIONS, Access::User, CachePolicy::Server { ttl_secs: 1800 }),
+    route("GET", paths::SPENDING_LIMITS, Access::User, CachePolicy::None),
+    route("POST", paths::SPENDING_LIMITS, Access::User, CachePolicy::None),
+    route("GET", paths::SPENDING_LIMIT, Access::User, CachePolicy::None),
+    route("PUT", paths::SPENDING_LIMIT, Access::User, CachePolicy::None),
+    route("DELETE", paths::SPENDING_LIMIT, Access::User, CachePolicy::None),
+    route("GET", paths::DEBTS, Access::User, CachePolicy::None),
+    route("POST", paths::DEBTS, Access::User, CachePolicy::None),
+    route("GET", paths::DEBT, Access::User, CachePolicy::None),
+    route("PUT", paths::DEBT, Access::User, CachePolicy::None),
+    route("DELETE", paths::DEBT, Access::User, CachePolicy::None),
+    route("GET", paths::DEBT_PAYOFF_PLAN, Access::User, CachePolicy::None),
+    route("GET", paths::ACCOUNT_SUMMARY, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("GET", paths::EXPENSE_SUMMARY, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::EXPENSE_CATEGORY_SUMMARY, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::EXPENSE_MONTHLY_TREND, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::EXPENSE_DAILY_TREND, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::EXPENSE_RECENT, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("GET", paths::EXPENSE_HEATMAP, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::INCOME_SUMMARY, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::INCOME_CATEGORY_SUMMARY, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::INCOME_MONTHLY_TREND, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::INCOME_DAILY_TREND, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::INCOME_RECENT, Access::User, CachePolicy::Server { ttl_secs: 300 }),
+    route("GET", paths::ANALYTICS_FEEDS, Access::User, CachePolicy::None),
+    route("POST", paths::ANALYTICS_FEEDS, Access::User, CachePolicy::None),
+    route("DELETE", paths::ANALYTICS_FEED, Access::User, CachePolicy::None),
+    route("GET", paths::SUBSCRIPTIONS, Access::User, CachePolicy::Server { ttl_secs: 3600 }),
+    route("GET", paths::ANOMALIES, Access::User, CachePolicy::None),
+    route("GET", paths::FINANCIAL_HEALTH, Access::User, CachePolicy::Server { ttl_secs: 900 }),
+    route("GET", paths::SHARED_ANALYTICS_FEED, Access::LinkToken, CachePolicy::Private { max_age_secs: 300 }),
+    route("GET", paths::MONTHLY_REPORT, Access::User, CachePolicy::None).long_running(),
+    route("GET", paths::EXPORT_LINKS, Access::User, CachePolicy::None),
+    route("POST", paths::EXPORT_LINKS, Access::User, CachePolicy::None),
+    route("DELETE", paths::EXPORT_LINK, Access::User, CachePolicy::None),
+    route("GET", paths::EXPORT_LINK_ACCESS_LOG, Access::User, CachePolicy::None),
+    route("GET", paths::SHARED_EXPORT, Access::LinkToken, CachePolicy::NoStore),
+    route("GET", paths::SHARED_EXPORT_TRANSACTIONS, Access::LinkToken, CachePolicy::NoStore).long_running(),
+    route("GET", paths::SHARED_EXPORT_REPORT, Access::LinkToken, CachePolicy::NoStore).long_running(),
+    route("POST", paths::CATEGORIZATION_SUGGEST, Access::User, CachePolicy::None),
+    route("POST", paths::CATEGORIZATION_FEEDBACK, Access::User, CachePolicy::None),
+    route("GET", paths::JOBS, Access::User, CachePolicy::None),
+    route("GET", paths::JOB, Access::User, CachePolicy::None),
+    route("GET", paths::AUDIT_LOG, Access::User, CachePolicy::None),
+    route("GET", paths::AUDIT_LOG_EXPORT, Access::User, CachePolicy::None).long_running(),
+    route("GET", paths::LIVE_SOCKET, Access::QueryToken, CachePolicy::None),
+    route("GET", paths::NOTIFICATION_STREAM, Access::QueryToken, CachePolicy::None),
+    route("GET", paths::NOTIFICATION_PREFERENCES, Access::User, CachePolicy::None),
+    route("PUT", paths::NOTIFICATION_PREFERENCES, Access::User, CachePolicy::None),
+    route("GET", paths::DEVICES, Access::User, CachePolicy::None),
+    route("POST", paths::DEVICES, Access::User, CachePolicy::None),
+    route("DELETE", paths::DEVICE, Access::User, CachePolicy::None),
+    // Only served when built with the graphql feature
+    route("POST", paths::GRAPHQL, Access::User, CachePolicy::None),
+    route("GET", paths::ADMIN_ROUTES, Access::Admin, CachePolicy::None),
+    route("GET", paths::ADMIN_EMAIL_TEMPLATES, Access::Admin, CachePolicy::None),
+    route("GET", paths::ADMIN_EMAIL_TEMPLATE_PREVIEW, Access::Admin, CachePolicy::None),
+    route("GET", paths::ADMIN_TASKS, Access::Admin, CachePolicy::None),
+    route("GET", paths::ADMIN_TASK_RUNS, Access::Admin, CachePolicy::None),
+    route("POST", paths::ADMIN_TASK_RUN, Access::Admin, CachePolicy::None),
+    route("POST", paths::ADMIN_REPAIR_BALANCES, Access::Admin, CachePolicy::None).long_running(),
+];
+
+pub fn route_table_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::ADMIN_ROUTES, get(list_routes))
+        .layer(axum::middleware::from_fn(admin_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::handlers::task::{list_tasks, list_task_runs, trigger_task};
+use crate::middleware::{admin_middleware, auth_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn task_routes() -> Router<AppState> {
+    Router::new()
+        .route(paths::ADMIN_TASKS, get(list_tasks))
+        .route(paths::ADMIN_TASK_RUNS, get(list_task_runs))
+        .route(paths::ADMIN_TASK_RUN, post(trigger_task))
+        .layer(axum::middleware::from_fn(admin_middleware))
+        .layer(axum::middleware::from_fn(auth_middleware))
+}
\ No newline at end of file
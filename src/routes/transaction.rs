@@ -1,19 +1,23 @@
 use axum::{
-    routing::{get},
+    routing::{get, post},
     Router,
 };
 
 use crate::handlers::transaction::{
     get_transactions, get_transaction_by_id, create_transaction, 
-    update_transaction, delete_transaction
+    update_transaction, delete_transaction, get_pocket_transactions,
+    post_transaction, cancel_transaction
 };
 use crate::middleware::auth_middleware;
-use crate::services::TransactionService;
-use crate::repositories::PostgresTransactionRepository;
+use crate::routes::paths;
+use crate::state::AppState;
 
-pub fn transaction_routes() -> Router<TransactionService<PostgresTransactionRepository>> {
+pub fn transaction_routes() -> Router<AppState> {
     Router::new()
-        .route("/transactions", get(get_transactions).post(create_transaction))
-        .route("/transactions/{id}", get(get_transaction_by_id).put(update_transaction).delete(delete_transaction))
+        .route(paths::TRANSACTIONS, get(get_transactions).post(create_transaction))
+        .route(paths::TRANSACTION, get(get_transaction_by_id).put(update_transaction).delete(delete_transaction))
+        .route(paths::TRANSACTION_POST, post(post_transaction))
+        .route(paths::TRANSACTION_CANCEL, post(cancel_transaction))
+        .route(paths::POCKET_TRANSACTIONS, get(get_pocket_transactions))
         .layer(axum::middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
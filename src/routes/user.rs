@@ -1,19 +1,33 @@
 use axum::{
     middleware,
-    routing::{get, patch},
+    routing::{get, patch, post, put},
     Router,
 };
 
-use crate::handlers::user::{get_me, list_users, update_hide_balance, update_name};
-use crate::middleware::auth::auth_middleware;
-use crate::repositories::PostgresUserRepository;
-use crate::services::UserService;
+use crate::handlers::user::{
+    cancel_deletion, change_password, delete_me, export_me, get_me, list_users, request_email_change,
+    update_hide_balance, update_monthly_digest, update_name, verify_email_change,
+};
+use crate::middleware::{admin_middleware, auth_middleware};
+use crate::routes::paths;
+use crate::state::AppState;
+
+pub fn user_routes() -> Router<AppState> {
+    // Searching by email would let any account probe for registered addresses
+    let admin = Router::new()
+        .route(paths::USERS, get(list_users))
+        .route_layer(middleware::from_fn(admin_middleware));
 
-pub fn user_routes() -> Router<UserService<PostgresUserRepository>> {
     Router::new()
-        .route("/me", get(get_me))
-        .route("/name", patch(update_name))
-        .route("/hide-balance", patch(update_hide_balance))
-        .route("/", get(list_users))
+        .route(paths::USER_ME, get(get_me).delete(delete_me))
+        .route(paths::USER_CANCEL_DELETION, post(cancel_deletion))
+        .route(paths::USER_EXPORT, get(export_me))
+        .route(paths::USER_PASSWORD, put(change_password))
+        .route(paths::USER_EMAIL, put(request_email_change))
+        .route(paths::USER_EMAIL_VERIFY, post(verify_email_change))
+        .route(paths::USER_NAME, patch(update_name))
+        .route(paths::USER_HIDE_BALANCE, patch(update_hide_balance))
+        .route(paths::USER_MONTHLY_DIGEST, patch(update_monthly_digest))
+        .merge(admin)
         .route_layer(middleware::from_fn(auth_middleware))
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use tracing::info;
+
+use crate::jobs::Schedule;
+use crate::models::TASK_ACCOUNT_PURGE;
+use crate::repositories::UserRepository;
+use crate::services::{ScheduledTask, UserService};
+use crate::utils::{AppError, CacheService};
+
+pub struct AccountPurgeWorker<R: UserRepository> {
+    user_service: UserService<R>,
+    cache: CacheService,
+    check_interval_secs: u64,
+}
+
+impl<R: UserRepository> AccountPurgeWorker<R> {
+    pub fn new(user_service: UserService<R>, cache: CacheService, check_interval_secs: u64) -> Self {
+        Self {
+            user_service,
+            cache,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: UserRepository + 'static> ScheduledTask for AccountPurgeWorker<R> {
+    fn name(&self) -> &'static str {
+        TASK_ACCOUNT_PURGE
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let ids = self.user_service.purge_due_deletions().await?;
+        for id in &ids {
+            // Every per-user cache key embeds the user id
+            let removed = self.cache.delete_pattern(&format!("*{}*", id)).await;
+            info!("Purged account {} and {} cache entries", id, removed);
+        }
+
+        Ok(ids.len() as u64)
+    }
+}
\ No newline at end of file
@@ -2,7 +2,7 @@ use rust_decimal::Decimal;
 use uuid::Uuid;
 
 use crate::models::{AccountSummaryResponse, AccountInfo};
-use crate::repositories::{PocketRepository, TransactionRepository};
+use crate::repositories::{PocketRepository, TransactionRepository, ReadPreference};
 use crate::utils::AppError;
 
 #[derive(Clone)]
@@ -19,9 +19,9 @@ impl<P: PocketRepository, T: TransactionRepository> AccountSummaryService<P, T>
         }
     }
 
-    pub async fn get_account_summary(&self, user_id: Uuid) -> Result<AccountSummaryResponse, AppError> {
-        // Get all pockets (accounts) for the user
-        let pockets = self.pocket_repository.find_by_user_id(user_id).await?;
+    pub async fn get_account_summary(&self, user_id: Uuid, include_archived: bool) -> Result<AccountSummaryResponse, AppError> {
+        // Get the user's pockets (accounts), archived ones only when asked for
+        let pockets = self.pocket_repository.find_by_user_id(user_id, include_archived).await?;
         
         let mut total_balance = Decimal::new(0, 0);
         let mut accounts = Vec::new();
@@ -32,6 +32,8 @@ impl<P: PocketRepository, T: TransactionRepository> AccountSummaryService<P, T>
                 id: pocket.id.to_string(),
                 name: pocket.name,
                 balance: pocket.balance.to_string(),
+                currency: pocket.currency,
+                formatted_amount: None,
                 account_type: "pocket".to_string(),
             });
         }
@@ -44,6 +46,7 @@ impl<P: PocketRepository, T: TransactionRepository> AccountSummaryService<P, T>
 
         Ok(AccountSummaryResponse {
             total_balance: total_balance.to_string(),
+            formatted_amount: None,
             accounts,
             total_income: total_income.to_string(),
             total_expenses: total_expenses.to_string(),
@@ -57,12 +60,19 @@ impl<P: PocketRepository, T: TransactionRepository> AccountSummaryService<P, T>
             page: None,
             limit: None,
             category: None,
+            categories: None,
             from_date: None,
             to_date: None,
             transaction_type: None,
+            account_id: None,
+            min_amount: None,
+            max_amount: None,
+            sort_by: None,
+            order: None,
+            status: Some(crate::models::TRANSACTION_STATUS_POSTED.to_string()),
         };
 
-        let transactions = self.transaction_repository.find_by_user_id(user_id, &query).await?;
+        let transactions = self.transaction_repository.find_by_user_id(user_id, &query, ReadPreference::Replica).await?;
         
         let mut total_income = Decimal::new(0, 0);
         let mut total_expenses = Decimal::new(0, 0);
@@ -0,0 +1,167 @@
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::models::{
+    AnalyticsFeed, AnalyticsFeedDocument, AnalyticsFeedResponse, AnalyticsFeedRows, CashflowFeedRow, CategoryFeedRow,
+    CreateAnalyticsFeedRequest, CreatedAnalyticsFeedResponse, Transaction, FEED_TYPE_CATEGORY_SUMMARY, net_refunds,
+};
+use crate::repositories::{AnalyticsFeedRepository, TransactionRepository, UserRepository, ReadPreference};
+use crate::utils::{AppError, generate_token, hash_token};
+
+const ANALYTICS_FEED_TOKEN_LENGTH: usize = 40;
+const MAX_ACTIVE_ANALYTICS_FEEDS: i64 = 20;
+const DEFAULT_FEED_MONTHS: u32 = 6;
+const MAX_FEED_MONTHS: u32 = 24;
+
+#[derive(Clone)]
+pub struct AnalyticsFeedService<F: AnalyticsFeedRepository, T: TransactionRepository, U: UserRepository> {
+    repository: F,
+    transaction_repository: T,
+    user_repository: U,
+}
+
+impl<F, T, U> AnalyticsFeedService<F, T, U>
+where
+    F: AnalyticsFeedRepository,
+    T: TransactionRepository,
+    U: UserRepository,
+{
+    pub fn new(repository: F, transaction_repository: T, user_repository: U) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+            user_repository,
+        }
+    }
+
+    pub async fn create_feed(&self, user_id: Uuid, request: CreateAnalyticsFeedRequest) -> Result<CreatedAnalyticsFeedResponse, AppError> {
+        if self.repository.count_active(user_id).await? >= MAX_ACTIVE_ANALYTICS_FEEDS {
+            return Err(AppError::Conflict(format!(
+                "At most {} analytics feeds can be active at once",
+                MAX_ACTIVE_ANALYTICS_FEEDS
+            )));
+        }
+
+        let token = generate_token(ANALYTICS_FEED_TOKEN_LENGTH);
+        let feed = self
+            .repository
+            .create(user_id, &request.feed_type, request.label.as_deref(), &hash_token(&token))
+            .await?;
+
+        Ok(CreatedAnalyticsFeedResponse {
+            token,
+            feed: feed.into(),
+        })
+    }
+
+    pub async fn list_feeds(&self, user_id: Uuid) -> Result<Vec<AnalyticsFeedResponse>, AppError> {
+        let feeds = self.repository.find_by_user(user_id).await?;
+        Ok(feeds.into_iter().map(AnalyticsFeedResponse::from).collect())
+    }
+
+    pub async fn revoke_feed(&self, user_id: Uuid, id: Uuid) -> Result<AnalyticsFeedResponse, AppError> {
+        let feed = self.repository.revoke(id, user_id).await?;
+        Ok(feed.into())
+    }
+
+    // Unknown and revoked tokens look the same to the caller
+    pub async fn resolve(&self, token: &str) -> Result<AnalyticsFeed, AppError> {
+        let feed = self
+            .repository
+            .find_active_by_hash(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Feed not found".to_string()))?;
+
+        self.repository.touch(feed.id).await?;
+        Ok(feed)
+    }
+
+    pub async fn build_document(&self, feed: &AnalyticsFeed, months: u32) -> Result<AnalyticsFeedDocument, AppError> {
+        let today = Utc::now().date_naive();
+        let current_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today);
+        let from_date = current_month - Months::new(months - 1);
+
+        let user = self
+            .user_repository
+            .find_by_id(feed.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Feed not found".to_string()))?;
+        let transactions = self
+            .transaction_repository
+            .find_by_date_range(feed.user_id, from_date, today, ReadPreference::Replica)
+            .await?;
+        let refunds = self
+            .transaction_repository
+            .find_received_refunds(feed.user_id, from_date, today)
+            .await?;
+        let transactions = net_refunds(transactions, &refunds);
+
+        let rows = if feed.feed_type == FEED_TYPE_CATEGORY_SUMMARY {
+            AnalyticsFeedRows::Categories(category_rows(&transactions))
+        } else {
+            AnalyticsFeedRows::Cashflow(cashflow_rows(&transactions))
+        };
+
+        Ok(AnalyticsFeedDocument {
+            feed_type: feed.feed_type.clone(),
+            label: feed.label.clone(),
+            currency: user.base_currency,
+            months,
+            generated_at: Utc::now(),
+            rows,
+        })
+    }
+}
+
+pub fn feed_months(months: Option<u32>) -> u32 {
+    months.unwrap_or(DEFAULT_FEED_MONTHS).clamp(1, MAX_FEED_MONTHS)
+}
+
+// Expense spending per month and category
+fn category_rows(transactions: &[Transaction]) -> Vec<CategoryFeedRow> {
+    let mut totals: BTreeMap<(String, String), (Decimal, i64)> = BTreeMap::new();
+
+    for transaction in transactions.iter().filter(|t| t.transaction_type == "expense") {
+        let month = transaction.transaction_date.format("%Y-%m").to_string();
+        let category = transaction.category.clone().unwrap_or_else(|| "Uncategorized".to_string());
+        let entry = totals.entry((month, category)).or_insert((Decimal::ZERO, 0));
+        entry.0 += transaction.amount.abs();
+        entry.1 += 1;
+    }
+
+    totals
+        .into_iter()
+        .map(|((month, category), (total, transaction_count))| CategoryFeedRow {
+            month,
+            category,
+            total,
+            transaction_count,
+        })
+        .collect()
+}
+
+// Adjustments are bookkeeping corrections rather than cash flow, so they are left out
+fn cashflow_rows(transactions: &[Transaction]) -> Vec<CashflowFeedRow> {
+    let mut totals: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+
+    for transaction in transactions {
+        let month = transaction.transaction_date.format("%Y-%m").to_string();
+        match transaction.transaction_type.as_str() {
+            "income" => totals.entry(month).or_default().0 += transaction.amount.abs(),
+            "expense" => totals.entry(month).or_default().1 += transaction.amount.abs(),
+            _ => {}
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(month, (income, expenses))| CashflowFeedRow {
+            month,
+            income,
+            expenses,
+            net: income - expenses,
+        })
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use chrono::{Days, Months, Utc};
+use rust_decimal::Decimal;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::models::{AnomaliesResponse, AnomalyItem, AnomalyQuery};
+use crate::repositories::AnomalyRepository;
+use crate::utils::{AppError, EventBus, LiveAction, LiveResource};
+
+const DEFAULT_WINDOW_DAYS: i64 = 30;
+// Standard deviations above the category mean
+const DEFAULT_THRESHOLD: i64 = 3;
+// Expenses are compared against the year before the window
+const HISTORY_MONTHS: u32 = 12;
+// A category needs this many earlier expenses before anything in it counts as unusual
+const MIN_SAMPLES: i64 = 5;
+
+#[derive(Clone)]
+pub struct AnomalyService<A: AnomalyRepository> {
+    repository: A,
+    events: EventBus,
+    alerts_enabled: bool,
+}
+
+impl<A: AnomalyRepository> AnomalyService<A> {
+    pub fn new(repository: A, events: EventBus, alerts_enabled: bool) -> Self {
+        Self {
+            repository,
+            events,
+            alerts_enabled,
+        }
+    }
+
+    pub async fn get_anomalies(&self, user_id: Uuid, query: AnomalyQuery) -> Result<AnomaliesResponse, AppError> {
+        let days = query.days.unwrap_or(DEFAULT_WINDOW_DAYS);
+        let threshold = match query.threshold {
+            Some(threshold) => Decimal::from_f64_retain(threshold)
+                .ok_or_else(|| AppError::invalid_field("threshold", "Invalid threshold"))?
+                .round_dp(2),
+            None => Decimal::from(DEFAULT_THRESHOLD),
+        };
+
+        let to_date = Utc::now().date_naive();
+        let from_date = to_date - Days::new(days as u64 - 1);
+        let history_from = from_date - Months::new(HISTORY_MONTHS);
+
+        let anomalies = self
+            .repository
+            .find_in_range(user_id, history_from, from_date, to_date, threshold, MIN_SAMPLES)
+            .await?;
+
+        Ok(AnomaliesResponse {
+            from_date,
+            to_date,
+            threshold: threshold.to_string(),
+            anomalies: anomalies.into_iter().map(AnomalyItem::from).collect(),
+        })
+    }
+
+    // Best effort: a failed check never fails the request that created the expense
+    pub async fn alert_if_unusual(&self, user_id: Uuid, transaction_id: i64) {
+        if !self.alerts_enabled {
+            return;
+        }
+
+        let history_from = Utc::now().date_naive() - Months::new(HISTORY_MONTHS);
+        match self
+            .repository
+            .check_transaction(user_id, transaction_id, history_from, Decimal::from(DEFAULT_THRESHOLD), MIN_SAMPLES)
+            .await
+        {
+            Ok(Some(_)) => self.events.publish(user_id, LiveResource::Anomaly, LiveAction::Created, transaction_id),
+            Ok(None) => {}
+            Err(e) => warn!("Anomaly check failed for transaction {}: {}", transaction_id, e),
+        }
+    }
+}
\ No newline at end of file
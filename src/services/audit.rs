@@ -0,0 +1,83 @@
+use chrono::Utc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::exporters::escape_csv_field;
+use crate::models::{AuditLogQuery, ExportFile, ListAuditLogResponse};
+use crate::repositories::{AuditRepository, ReadPreference};
+use crate::utils::AppError;
+
+// Upper bound for a single CSV export; narrower filters are needed beyond it
+const MAX_EXPORT_ROWS: i64 = 10_000;
+
+#[derive(Clone)]
+pub struct AuditService<R: AuditRepository> {
+    repository: R,
+}
+
+impl<R: AuditRepository> AuditService<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    // Recording happens after the change has been committed, so a failure here
+    // is logged rather than turned into an error for a request that already succeeded
+    pub async fn record(&self, user_id: Uuid, entity_type: &str, entity_id: &str, action: &str, summary: &str, details: Option<serde_json::Value>) {
+        if let Err(e) = self
+            .repository
+            .create(user_id, entity_type, entity_id, action, summary, details)
+            .await
+        {
+            warn!("Failed to record audit entry for {} {}: {}", entity_type, entity_id, e);
+        }
+    }
+
+    pub async fn list_entries(&self, user_id: Uuid, query: AuditLogQuery) -> Result<ListAuditLogResponse, AppError> {
+        let page = query.page.unwrap_or(1);
+        let limit = query.limit.unwrap_or(20);
+        let offset = (page as i64 - 1) * limit as i64;
+        let entries = self.repository.find_page_by_user_id(user_id, &query, limit as i64, offset, ReadPreference::Replica).await?;
+
+        Ok(ListAuditLogResponse {
+            data: entries.items.into_iter().map(|entry| entry.to_response()).collect(),
+            page,
+            limit,
+            total_items: entries.total_items,
+        })
+    }
+
+    pub async fn export_entries(&self, user_id: Uuid, query: AuditLogQuery) -> Result<ExportFile, AppError> {
+        let total_items = self.repository.count_by_user_id(user_id, &query, ReadPreference::Replica).await?;
+        if total_items > MAX_EXPORT_ROWS {
+            return Err(AppError::ValidationError(format!(
+                "Export matches {} entries; narrow the filters to at most {}",
+                total_items, MAX_EXPORT_ROWS
+            )));
+        }
+
+        let entries = self.repository.find_by_user_id(user_id, &query, MAX_EXPORT_ROWS, 0, ReadPreference::Replica).await?;
+
+        let mut content = String::from("id,timestamp,entity_type,entity_id,action,summary,details\n");
+        for entry in entries {
+            let fields = [
+                entry.id.to_string(),
+                entry.created_at.to_rfc3339(),
+                entry.entity_type,
+                entry.entity_id,
+                entry.action,
+                entry.summary,
+                entry.details.map(|details| details.to_string()).unwrap_or_default(),
+            ];
+
+            let line: Vec<String> = fields.iter().map(|field| escape_csv_field(field)).collect();
+            content.push_str(&line.join(","));
+            content.push('\n');
+        }
+
+        Ok(ExportFile {
+            filename: format!("audit-log-{}.csv", Utc::now().format("%Y%m%d")),
+            content_type: "text/csv; charset=utf-8",
+            content,
+        })
+    }
+}
\ No newline at end of file
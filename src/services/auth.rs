@@ -1,63 +1,242 @@
-use bcrypt::{hash, verify, DEFAULT_COST};
+use std::sync::Arc;
 
-use crate::config::JwtConfig;
-use crate::models::{AuthResponse, LoginRequest, RegisterRequest};
-use crate::repositories::AuthRepository;
-use crate::utils::AppError;
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::config::{JwtConfig, PasswordPolicyConfig};
+use crate::models::{AuthResponse, LoginRequest, RegisterRequest, StepUpRequest, RefreshTokenRequest, ClientInfo, Session, User, DEFAULT_LOCALE};
+use crate::repositories::{AuthRepository, SessionRepository};
+use crate::services::{RefreshRotation, SessionService};
+use crate::utils::{
+    AppError, codes, CacheService, EMAIL_TEMPLATE_SESSION_REUSE, Mailer, PasswordHasher, check_new_password,
+    login_failures_email_key, login_failures_ip_key,
+};
+
+// Failed sign-ins tolerated per account, and per client address across accounts, within the window
+const LOGIN_MAX_FAILURES_PER_ACCOUNT: u64 = 5;
+const LOGIN_MAX_FAILURES_PER_IP: u64 = 20;
+const LOGIN_FAILURE_WINDOW_SECS: u64 = 15 * 60;
+
+pub enum RefreshOutcome {
+    Refreshed(AuthResponse),
+    // Token reuse was detected; the session is revoked and the caller must drop its cached state
+    FamilyRevoked(Uuid),
+}
 
 #[derive(Clone)]
-pub struct AuthService<R: AuthRepository> {
+pub struct AuthService<R: AuthRepository, S: SessionRepository> {
     repository: R,
     jwt_config: JwtConfig,
+    session_service: SessionService<S>,
+    mailer: Mailer,
+    cache: CacheService,
+    hasher: Arc<dyn PasswordHasher>,
+    password_policy: PasswordPolicyConfig,
 }
 
-impl<R: AuthRepository> AuthService<R> {
-    pub fn new(repository: R, jwt_config: JwtConfig) -> Self {
+impl<R: AuthRepository, S: SessionRepository> AuthService<R, S> {
+    pub fn new(repository: R, jwt_config: JwtConfig, session_service: SessionService<S>, mailer: Mailer, cache: CacheService, hasher: Arc<dyn PasswordHasher>, password_policy: PasswordPolicyConfig) -> Self {
         Self {
             repository,
             jwt_config,
+            session_service,
+            mailer,
+            cache,
+            hasher,
+            password_policy,
         }
     }
 
-    pub async fn register(&self, request: RegisterRequest) -> Result<AuthResponse, AppError> {
+    pub async fn register(&self, request: RegisterRequest, client: ClientInfo) -> Result<AuthResponse, AppError> {
+        let user = self.create_account(&request).await?;
+
+        // Generate token bound to a new session
+        let session = self.session_service.start_session(user.id, &client).await?;
+        let token = self.jwt_config.create_token(user.id, user.email.clone(), session.id)?;
+        let refresh_token = self.issue_refresh_token(&session, &client).await?;
+
+        Ok(AuthResponse {
+            token,
+            refresh_token,
+            user: user.to_response(),
+        })
+    }
+
+    // Creates the account without signing it in, for registration and operator tooling alike
+    pub async fn create_account(&self, request: &RegisterRequest) -> Result<User, AppError> {
+        check_new_password(&self.password_policy, "password", &request.password).await?;
+
         // Hash password
-        let hashed_password = hash(&request.password, DEFAULT_COST)
-            .map_err(|e| AppError::InternalServerError(format!("Password hashing failed: {}", e)))?;
+        let hashed_password = self.hasher.hash(&request.password)?;
+
+        self.repository.create_user(request, hashed_password).await
+    }
 
-        // Create user
-        let user = self.repository.create_user(&request, hashed_password).await?;
+    pub async fn login(&self, request: LoginRequest, client: ClientInfo) -> Result<AuthResponse, AppError> {
+        // Locked out even with the right password, so guessing can't continue behind the lock
+        self.check_login_lockout(&request.email, &client).await?;
 
-        // Generate token
-        let token = self.jwt_config.create_token(user.id, user.email.clone())?;
+        // Find user by email
+        let Some(user) = self.repository.find_user_by_email(&request.email).await? else {
+            return Err(self.record_login_failure(&request.email, &client).await);
+        };
+
+        // Verify password
+        if !self.verify_password(&user, &request.password).await? {
+            return Err(self.record_login_failure(&request.email, &client).await);
+        }
+        self.cache.delete(&login_failures_email_key(&request.email)).await;
+
+        // Generate token bound to a new session
+        let session = self.session_service.start_session(user.id, &client).await?;
+        let token = self.jwt_config.create_token(user.id, user.email.clone(), session.id)?;
+        let refresh_token = self.issue_refresh_token(&session, &client).await?;
 
         Ok(AuthResponse {
             token,
+            refresh_token,
             user: user.to_response(),
         })
     }
 
-    pub async fn login(&self, request: LoginRequest) -> Result<AuthResponse, AppError> {
-        // Find user by email
+    // Re-confirms the password and reissues the token for the same session,
+    // giving it a fresh issued-at for actions that need recent authentication.
+    // Shares the sign-in failure counters, so a stolen access token can't be used to guess the password
+    pub async fn step_up(&self, user_id: Uuid, session_id: Uuid, request: StepUpRequest, client: ClientInfo) -> Result<AuthResponse, AppError> {
         let user = self
             .repository
-            .find_user_by_email(&request.email)
+            .find_user_by_id(user_id)
             .await?
-            .ok_or_else(|| AppError::Unauthorized("Invalid credentials".to_string()))?;
+            .ok_or_else(|| AppError::Unauthorized("Invalid credentials".to_string()).with_code(codes::INVALID_CREDENTIALS))?;
 
-        // Verify password
-        let is_valid = verify(&request.password, &user.password)
-            .map_err(|e| AppError::InternalServerError(format!("Password verification failed: {}", e)))?;
+        self.check_login_lockout(&user.email, &client).await?;
 
-        if !is_valid {
-            return Err(AppError::Unauthorized("Invalid credentials".to_string()));
+        if !self.verify_password(&user, &request.password).await? {
+            return Err(self.record_login_failure(&user.email, &client).await);
         }
+        self.cache.delete(&login_failures_email_key(&user.email)).await;
 
-        // Generate token
-        let token = self.jwt_config.create_token(user.id, user.email.clone())?;
+        let token = self.jwt_config.create_token(user.id, user.email.clone(), session_id)?;
 
         Ok(AuthResponse {
             token,
+            refresh_token: None,
             user: user.to_response(),
         })
     }
+
+    pub async fn refresh(&self, request: RefreshTokenRequest, client: ClientInfo) -> Result<RefreshOutcome, AppError> {
+        let rotation = self
+            .session_service
+            .rotate_refresh_token(&request.refresh_token, client.device_id.as_deref())
+            .await?;
+
+        let (session, refresh_token) = match rotation {
+            RefreshRotation::Rotated { session, refresh_token } => (session, refresh_token),
+            RefreshRotation::Compromised { session_id, user_id } => {
+                self.send_reuse_alert(user_id, &client).await;
+                return Ok(RefreshOutcome::FamilyRevoked(session_id));
+            }
+        };
+
+        let user = self
+            .repository
+            .find_user_by_id(session.user_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+        let token = self.jwt_config.create_token(user.id, user.email.clone(), session.id)?;
+
+        Ok(RefreshOutcome::Refreshed(AuthResponse {
+            token,
+            refresh_token: Some(refresh_token),
+            user: user.to_response(),
+        }))
+    }
+
+    async fn issue_refresh_token(&self, session: &Session, client: &ClientInfo) -> Result<Option<String>, AppError> {
+        match client.device_id.as_deref() {
+            Some(device_id) => Ok(Some(self.session_service.issue_refresh_token(session, device_id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    // The revocation has already happened, so a failed alert is only logged
+    async fn send_reuse_alert(&self, user_id: Uuid, client: &ClientInfo) {
+        let user = match self.repository.find_user_by_id(user_id).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Could not load user {} for refresh token alert: {}", user_id, e);
+                return;
+            }
+        };
+
+        let locale = self.repository.find_locale(user_id).await.ok().flatten();
+        let context = json!({
+            "name": user.name,
+            "ip_address": client.ip_address,
+            "user_agent": client.user_agent,
+        });
+
+        let locale = locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        if let Err(e) = self
+            .mailer
+            .send_template(&user.email, EMAIL_TEMPLATE_SESSION_REUSE, locale, &context)
+            .await
+        {
+            warn!("Failed to send refresh token alert to user {}: {}", user_id, e);
+        }
+    }
+
+    // A correct password against a legacy hash is the only chance to upgrade it
+    async fn verify_password(&self, user: &User, password: &str) -> Result<bool, AppError> {
+        if !self.hasher.verify(password, &user.password)? {
+            return Ok(false);
+        }
+
+        if self.hasher.needs_rehash(&user.password) {
+            let rehashed = self.hasher.hash(password)?;
+            if let Err(e) = self.repository.update_password_hash(user.id, &rehashed).await {
+                warn!("Failed to upgrade password hash for user {}: {}", user.id, e);
+            }
+        }
+        Ok(true)
+    }
+
+    // Counters live in Redis; without it sign-in is not throttled
+    async fn check_login_lockout(&self, email: &str, client: &ClientInfo) -> Result<(), AppError> {
+        let account_failures = self.cache.counter(&login_failures_email_key(email)).await.unwrap_or(0);
+        let ip_failures = match &client.ip_address {
+            Some(ip_address) => self.cache.counter(&login_failures_ip_key(ip_address)).await.unwrap_or(0),
+            None => 0,
+        };
+
+        if account_failures >= LOGIN_MAX_FAILURES_PER_ACCOUNT || ip_failures >= LOGIN_MAX_FAILURES_PER_IP {
+            return Err(AppError::TooManyRequests(format!(
+                "Too many failed sign-in attempts, try again in {} minutes",
+                LOGIN_FAILURE_WINDOW_SECS / 60
+            ))
+            .with_code(codes::ACCOUNT_LOCKED));
+        }
+        Ok(())
+    }
+
+    async fn record_login_failure(&self, email: &str, client: &ClientInfo) -> AppError {
+        let account_failures = self
+            .cache
+            .increment(&login_failures_email_key(email), LOGIN_FAILURE_WINDOW_SECS)
+            .await;
+        if let Some(ip_address) = &client.ip_address {
+            self.cache
+                .increment(&login_failures_ip_key(ip_address), LOGIN_FAILURE_WINDOW_SECS)
+                .await;
+        }
+
+        if account_failures == Some(LOGIN_MAX_FAILURES_PER_ACCOUNT) {
+            warn!("Sign-in locked for {} after {} failed attempts", email, LOGIN_MAX_FAILURES_PER_ACCOUNT);
+        }
+        AppError::Unauthorized("Invalid credentials".to_string()).with_code(codes::INVALID_CREDENTIALS)
+    }
+
 }
\ No newline at end of file
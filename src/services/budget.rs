@@ -1,90 +1,142 @@
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use std::collections::{HashMap, HashSet};
+use tracing::{info, warn};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use uuid::Uuid;
 
 use crate::models::{
-    BudgetResponse, CreateBudgetRequest, UpdateBudgetRequest, 
+    Budget, BudgetResponse, CreateBudgetRequest, UpdateBudgetRequest, 
     ListBudgetsQuery, ListBudgetsResponse, BudgetSummaryResponse,
     BudgetPerformanceResponse, BudgetPerformanceItem, BudgetSuggestionsResponse,
-    BudgetSuggestionItem
+    BudgetSuggestionItem, BudgetDetailPerformanceResponse, BudgetHistoryQuery, BudgetHistoryResponse,
+    BudgetHistoryItem, IfUnmodifiedSince, Precondition, next_budget_period, DEFAULT_BUDGET_ALERT_THRESHOLDS,
 };
-use crate::repositories::BudgetRepository;
-use crate::utils::AppError;
+use crate::repositories::{BudgetRepository, Page};
+use crate::utils::{AppError, EventBus, LiveAction, LiveResource, validate_sort};
+use crate::policy::{Action, permits, permits_as};
+
+const DEFAULT_HISTORY_LIMIT: i64 = 12;
+// Budgets rolled over per run; the rest wait for the next run
+const ROLLOVER_BATCH_SIZE: i64 = 500;
+// A budget left untouched for years catches up at most this many periods per run
+const MAX_CATCH_UP_PERIODS: usize = 120;
+// New budgets are suggested from this many complete months of spending
+const SUGGESTION_HISTORY_MONTHS: u32 = 6;
+// A category needs spending in at least this many of those months to be worth a budget
+const SUGGESTION_MIN_ACTIVE_MONTHS: usize = 3;
 
 #[derive(Clone)]
 pub struct BudgetService<R: BudgetRepository> {
     repository: R,
+    events: EventBus,
 }
 
 impl<R: BudgetRepository> BudgetService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, events: EventBus) -> Self {
+        Self { repository, events }
     }
 
     pub async fn get_budget_by_id(&self, id: i64, user_id: Uuid) -> Result<BudgetResponse, AppError> {
         let budget = self
             .repository
-            .find_by_id(id)
+            .find_by_id_for_user(id, user_id)
             .await?
+            .filter(|budget| permits(user_id, Action::Read, budget))
             .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
 
-        // Check if budget belongs to user
-        if budget.user_id != user_id {
-            return Err(AppError::Forbidden("Access denied".to_string()));
-        }
-
         Ok(budget.to_response())
     }
 
     pub async fn list_budgets(&self, user_id: Uuid, query: ListBudgetsQuery) -> Result<ListBudgetsResponse, AppError> {
-        // Validate query parameters
-        if let Some(page) = query.page {
-            if page < 1 {
-                return Err(AppError::ValidationError("Page must be greater than 0".to_string()));
-            }
-        }
+        Self::validate_list_query(&query)?;
 
-        if let Some(limit) = query.limit {
-            if limit < 1 || limit > 100 {
-                return Err(AppError::ValidationError("Limit must be between 1 and 100".to_string()));
-            }
-        }
+        let page = self.repository.find_page_by_user_id(user_id, &query).await?;
 
+        Ok(Self::list_response(page, &query))
+    }
+
+    // Callers check the organization role first
+    pub async fn list_organization_budgets(&self, organization_id: Uuid, query: ListBudgetsQuery) -> Result<ListBudgetsResponse, AppError> {
+        Self::validate_list_query(&query)?;
+
+        let page = self.repository.find_page_by_organization_id(organization_id, &query).await?;
+
+        Ok(Self::list_response(page, &query))
+    }
+
+    fn validate_list_query(query: &ListBudgetsQuery) -> Result<(), AppError> {
         // Validate period type if provided
-        if let Some(ref period_type) = query.period_type {
-            if !["weekly", "monthly", "quarterly", "yearly"].contains(&period_type.as_str()) {
-                return Err(AppError::ValidationError("Period type must be 'weekly', 'monthly', 'quarterly', or 'yearly'".to_string()));
-            }
+        if let Some(ref period_type) = query.period_type
+            && !["weekly", "monthly", "quarterly", "yearly"].contains(&period_type.as_str()) {
+            return Err(AppError::invalid_field("period_type", "Period type must be 'weekly', 'monthly', 'quarterly', or 'yearly'"));
         }
 
-        let budgets = self.repository.find_by_user_id(user_id, &query).await?;
-        let total_items = self.repository.count_by_user_id(user_id, &query).await?;
+        validate_sort(query.sort_by.as_deref(), query.order.as_deref())
+    }
 
-        let budget_responses = budgets
+    fn list_response(page: Page<Budget>, query: &ListBudgetsQuery) -> ListBudgetsResponse {
+        let budget_responses = page
+            .items
             .into_iter()
             .map(|budget| budget.to_response())
             .collect();
 
-        Ok(ListBudgetsResponse {
+        ListBudgetsResponse {
             data: budget_responses,
             page: query.page.unwrap_or(1),
             limit: query.limit.unwrap_or(20),
-            total_items,
-        })
+            total_items: page.total_items,
+        }
     }
 
     pub async fn create_budget(&self, user_id: Uuid, request: CreateBudgetRequest) -> Result<BudgetResponse, AppError> {
         let budget = self.repository.create(user_id, &request).await?;
+        self.events.publish(user_id, LiveResource::Budget, LiveAction::Created, budget.id);
         Ok(budget.to_response())
     }
 
-    pub async fn update_budget(&self, id: i64, user_id: Uuid, request: UpdateBudgetRequest) -> Result<BudgetResponse, AppError> {
-        let budget = self.repository.update(id, user_id, &request).await?;
+    pub async fn create_organization_budget(&self, organization_id: Uuid, user_id: Uuid, request: CreateBudgetRequest) -> Result<BudgetResponse, AppError> {
+        let budget = self.repository.create_in_organization(organization_id, user_id, &request).await?;
+        self.events.publish(user_id, LiveResource::Budget, LiveAction::Created, budget.id);
+        Ok(budget.to_response())
+    }
+
+    pub async fn update_budget(&self, id: i64, user_id: Uuid, request: UpdateBudgetRequest, unmodified_since: IfUnmodifiedSince) -> Result<BudgetResponse, AppError> {
+        let precondition = Precondition::new(request.version, unmodified_since);
+        let budget = self.repository.update(id, user_id, &request, &precondition).await?;
+        self.events.publish(user_id, LiveResource::Budget, LiveAction::Updated, budget.id);
         Ok(budget.to_response())
     }
 
     pub async fn delete_budget(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
-        self.repository.delete(id, user_id).await
+        self.repository.delete(id, user_id).await?;
+        self.events.publish(user_id, LiveResource::Budget, LiveAction::Deleted, id);
+        Ok(())
+    }
+
+    pub async fn update_organization_budget(&self, organization_id: Uuid, id: i64, user_id: Uuid, request: UpdateBudgetRequest, unmodified_since: IfUnmodifiedSince) -> Result<BudgetResponse, AppError> {
+        self.require_shared_write(organization_id, id, user_id).await?;
+        self.update_budget(id, user_id, request, unmodified_since).await
+    }
+
+    pub async fn delete_organization_budget(&self, organization_id: Uuid, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        self.require_shared_write(organization_id, id, user_id).await?;
+        self.delete_budget(id, user_id).await
+    }
+
+    // Members see the budget, so they are told they can't change it rather than that it's missing
+    async fn require_shared_write(&self, organization_id: Uuid, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        let (budget, role) = self
+            .repository
+            .find_by_id_with_role(id, user_id)
+            .await?
+            .filter(|(budget, role)| budget.organization_id == Some(organization_id) && permits_as(user_id, role.as_deref(), Action::Read, budget))
+            .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+        if !permits_as(user_id, role.as_deref(), Action::Write, &budget) {
+            return Err(AppError::Forbidden("Only organization admins can change shared budgets".to_string()));
+        }
+        Ok(())
     }
 
     pub async fn get_budget_summary(&self, user_id: Uuid) -> Result<BudgetSummaryResponse, AppError> {
@@ -92,16 +144,26 @@ impl<R: BudgetRepository> BudgetService<R> {
             page: None,
             limit: None,
             category: None,
+            categories: None,
             period_type: None,
             is_active: None,
+            min_amount: None,
+            max_amount: None,
+            sort_by: None,
+            order: None,
         };
 
         let active_budgets_query = ListBudgetsQuery {
             page: None,
             limit: None,
             category: None,
+            categories: None,
             period_type: None,
             is_active: Some(true),
+            min_amount: None,
+            max_amount: None,
+            sort_by: None,
+            order: None,
         };
 
         let total_budgets = self.repository.count_by_user_id(user_id, &all_budgets_query).await?;
@@ -165,20 +227,168 @@ impl<R: BudgetRepository> BudgetService<R> {
         })
     }
 
+    pub async fn get_budget_detail_performance(&self, id: i64, user_id: Uuid) -> Result<BudgetDetailPerformanceResponse, AppError> {
+        let budget = self
+            .repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|budget| permits(user_id, Action::Read, budget))
+            .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+
+        let transactions = self.repository.find_counted_transactions(&budget).await?;
+        let spent_amount: Decimal = transactions.iter().map(|t| t.amount).sum();
+        let remaining_amount = budget.target_amount - spent_amount;
+        let percentage_used = if budget.target_amount > Decimal::new(0, 0) {
+            (spent_amount / budget.target_amount * Decimal::new(100, 0)).to_f64().unwrap_or(0.0)
+        } else {
+            0.0
+        };
+
+        // Days are counted inclusively on both ends of the period
+        let today = Utc::now().date_naive();
+        let total_days = (budget.period_end - budget.period_start).num_days() + 1;
+        let days_elapsed = ((today.min(budget.period_end) - budget.period_start).num_days() + 1).clamp(0, total_days);
+        let days_remaining = total_days - days_elapsed;
+
+        let daily_burn_rate = if days_elapsed > 0 {
+            spent_amount / Decimal::from(days_elapsed)
+        } else {
+            Decimal::new(0, 0)
+        };
+        let projected_spend = spent_amount + daily_burn_rate * Decimal::from(days_remaining);
+        let alerted_thresholds = self.repository.find_alerted_thresholds(budget.id, budget.period_start).await?;
+
+        Ok(BudgetDetailPerformanceResponse {
+            budget: budget.to_response(),
+            spent_amount: spent_amount.to_string(),
+            remaining_amount: remaining_amount.to_string(),
+            percentage_used,
+            daily_burn_rate: daily_burn_rate.round_dp(2).to_string(),
+            projected_spend: projected_spend.round_dp(2).to_string(),
+            days_elapsed,
+            days_remaining,
+            alerted_thresholds,
+            transactions: transactions.into_iter().map(|t| t.to_response()).collect(),
+        })
+    }
+
+    pub async fn get_budget_history(&self, id: i64, user_id: Uuid, query: BudgetHistoryQuery) -> Result<BudgetHistoryResponse, AppError> {
+        let budget = self
+            .repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|budget| permits(user_id, Action::Read, budget))
+            .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))?;
+
+        let mut periods = self
+            .repository
+            .find_periods(budget.id, query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+            .await?;
+        periods.reverse();
+
+        Ok(BudgetHistoryResponse {
+            budget: budget.to_response(),
+            periods: periods
+                .into_iter()
+                .map(|period| {
+                    let percentage_used = if period.target_amount > Decimal::ZERO {
+                        (period.actual_amount / period.target_amount * Decimal::ONE_HUNDRED).round_dp(2).to_f64().unwrap_or(0.0)
+                    } else {
+                        0.0
+                    };
+
+                    BudgetHistoryItem {
+                        period_start: period.period_start.format("%Y-%m-%d").to_string(),
+                        period_end: period.period_end.format("%Y-%m-%d").to_string(),
+                        target_amount: period.target_amount.to_string(),
+                        actual_amount: period.actual_amount.to_string(),
+                        remaining_amount: (period.target_amount - period.actual_amount).to_string(),
+                        percentage_used,
+                        within_budget: period.actual_amount <= period.target_amount,
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    // Closes every period that ended before `today` and starts the next one, returning the owners
+    // of the budgets that moved
+    pub async fn roll_over_expired_budgets(&self, today: NaiveDate) -> Result<Vec<Uuid>, AppError> {
+        let mut user_ids = Vec::new();
+
+        for mut budget in self.repository.find_expired(today, ROLLOVER_BATCH_SIZE).await? {
+            for _ in 0..MAX_CATCH_UP_PERIODS {
+                if budget.period_end >= today {
+                    break;
+                }
+                let Some((next_start, next_end)) = next_budget_period(&budget.period_type, budget.period_end) else {
+                    warn!("Budget {} has an unknown period type '{}'", budget.id, budget.period_type);
+                    break;
+                };
+                if !self.repository.close_period(&budget, next_start, next_end).await? {
+                    break;
+                }
+                budget.period_start = next_start;
+                budget.period_end = next_end;
+            }
+
+            // A shared budget whose creator has left has nobody to notify
+            if let Some(user_id) = budget.user_id {
+                self.events.publish(user_id, LiveResource::Budget, LiveAction::Updated, budget.id);
+                if !user_ids.contains(&user_id) {
+                    user_ids.push(user_id);
+                }
+            }
+        }
+
+        Ok(user_ids)
+    }
+
+    // Records every threshold that current spending has reached and alerts the owner once per
+    // budget for the highest new one. Returns how many budgets raised an alert.
+    pub async fn evaluate_alerts(&self, today: NaiveDate) -> Result<usize, AppError> {
+        let mut alerted = 0;
+
+        for (budget, spent_amount) in self.repository.find_alert_candidates(today).await? {
+            if budget.target_amount <= Decimal::ZERO {
+                continue;
+            }
+            let percentage_used = spent_amount / budget.target_amount * Decimal::ONE_HUNDRED;
+
+            let mut reached = None;
+            for &threshold in budget.alert_thresholds.iter().filter(|&&t| Decimal::from(t) <= percentage_used) {
+                if self.repository.record_alert(&budget, threshold, spent_amount).await? {
+                    reached = Some(threshold);
+                }
+            }
+
+            if let Some(threshold) = reached {
+                info!("Budget {} reached {}% of its target", budget.id, threshold);
+                if let Some(user_id) = budget.user_id {
+                    self.events.publish(user_id, LiveResource::BudgetAlert, LiveAction::Created, budget.id);
+                }
+                alerted += 1;
+            }
+        }
+
+        Ok(alerted)
+    }
+
     pub async fn get_budget_categories(&self, user_id: Uuid) -> Result<Vec<String>, AppError> {
         self.repository.get_categories(user_id).await
     }
 
+    // Adjusts existing budgets by their current usage and proposes new ones for categories
+    // that see steady spending without a budget
     pub async fn get_budget_suggestions(&self, user_id: Uuid) -> Result<BudgetSuggestionsResponse, AppError> {
-        // This is a simplified implementation. In a real application, you might want to:
-        // 1. Analyze historical spending patterns
-        // 2. Use machine learning algorithms
-        // 3. Consider seasonal variations
-        // 4. Factor in income changes
-        
         let budget_performance = self.repository.get_budget_performance(user_id).await?;
         let mut suggestions = Vec::new();
 
+        let budgeted: HashSet<String> = budget_performance
+            .iter()
+            .filter_map(|(budget, _)| budget.category.clone())
+            .collect();
+
         for (budget, spent_amount) in budget_performance {
             let percentage_used = if budget.target_amount > Decimal::new(0, 0) {
                 (spent_amount / budget.target_amount * Decimal::new(100, 0)).to_f64().unwrap_or(0.0)
@@ -186,30 +396,131 @@ impl<R: BudgetRepository> BudgetService<R> {
                 0.0
             };
 
-            // Generate suggestions based on spending patterns
-            if percentage_used > 90.0 {
+            // The budget's own alert thresholds bound what counts as over- or under-used
+            let (lowest, highest) = alert_bounds(&budget.alert_thresholds);
+
+            if percentage_used >= highest as f64 {
                 let suggested_increase = budget.target_amount * Decimal::new(120, 2); // 20% increase
                 suggestions.push(BudgetSuggestionItem {
+                    budget_id: Some(budget.id),
                     category: budget.category.clone(),
                     suggested_amount: suggested_increase.to_string(),
-                    reason: "You're consistently exceeding this budget. Consider increasing it by 20%.".to_string(),
+                    reason: format!("You've reached {}% of this budget, its highest alert threshold. Consider increasing it by 20%.", highest),
                     confidence: 0.85,
                 });
-            } else if percentage_used < 50.0 {
+            } else if percentage_used < lowest as f64 {
                 let suggested_decrease = budget.target_amount * Decimal::new(80, 2); // 20% decrease
                 suggestions.push(BudgetSuggestionItem {
+                    budget_id: Some(budget.id),
                     category: budget.category.clone(),
                     suggested_amount: suggested_decrease.to_string(),
-                    reason: "You're using less than 50% of this budget. Consider reducing it by 20%.".to_string(),
+                    reason: format!("You're using less than {}% of this budget. Consider reducing it by 20%.", lowest),
                     confidence: 0.75,
                 });
             }
         }
 
-        // Add suggestions for categories without budgets
-        // This would require analyzing transaction categories that don't have budgets
-        // For now, we'll keep it simple
+        // Complete calendar months only, so a half-finished month doesn't drag the median down
+        let current_month = Utc::now().date_naive().with_day(1).unwrap_or_default();
+        let months: Vec<NaiveDate> = (1..=SUGGESTION_HISTORY_MONTHS)
+            .rev()
+            .filter_map(|back| current_month.checked_sub_months(Months::new(back)))
+            .collect();
+        if let (Some(&from_date), Some(to_date)) = (months.first(), current_month.pred_opt()) {
+            let spend = self.repository.get_monthly_category_spend(user_id, from_date, to_date).await?;
+            suggestions.extend(suggest_new_budgets(&spend, &months, &budgeted));
+        }
 
         Ok(BudgetSuggestionsResponse { suggestions })
     }
-}
\ No newline at end of file
+}
+
+// Lowest and highest alert threshold, falling back to the defaults when alerts are off.
+// A single threshold only marks the top of the range, so the lower bound stays at the default.
+fn alert_bounds(thresholds: &[i32]) -> (i32, i32) {
+    let default_lowest = DEFAULT_BUDGET_ALERT_THRESHOLDS.iter().copied().min().unwrap_or(0);
+    let default_highest = DEFAULT_BUDGET_ALERT_THRESHOLDS.iter().copied().max().unwrap_or(0);
+    let highest = thresholds.iter().copied().max().unwrap_or(default_highest);
+    let lowest = match thresholds.len() {
+        0 | 1 => default_lowest,
+        _ => thresholds.iter().copied().min().unwrap_or(default_lowest),
+    };
+    (lowest, highest)
+}
+
+// One suggestion per unbudgeted category with regular spending, sized at its median month.
+// Confidence falls as the months vary more around their mean.
+fn suggest_new_budgets(
+    spend: &[(String, NaiveDate, Decimal)],
+    months: &[NaiveDate],
+    budgeted: &HashSet<String>,
+) -> Vec<BudgetSuggestionItem> {
+    let mut by_category: HashMap<&str, HashMap<NaiveDate, Decimal>> = HashMap::new();
+    for (category, month, amount) in spend {
+        if !budgeted.contains(category) {
+            *by_category.entry(category).or_default().entry(*month).or_default() += *amount;
+        }
+    }
+
+    let mut suggestions: Vec<(Decimal, BudgetSuggestionItem)> = by_category
+        .into_iter()
+        .filter(|(_, totals)| totals.len() >= SUGGESTION_MIN_ACTIVE_MONTHS)
+        .map(|(category, totals)| {
+            let mut amounts: Vec<Decimal> = months
+                .iter()
+                .map(|month| totals.get(month).copied().unwrap_or_default())
+                .collect();
+            amounts.sort();
+
+            let middle = amounts.len() / 2;
+            let median = if amounts.len().is_multiple_of(2) {
+                (amounts[middle - 1] + amounts[middle]) / Decimal::TWO
+            } else {
+                amounts[middle]
+            }
+            .round_dp(2);
+
+            let values: Vec<f64> = amounts.iter().map(|amount| amount.to_f64().unwrap_or(0.0)).collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / values.len() as f64;
+            let variation = if mean > 0.0 { variance.sqrt() / mean } else { 1.0 };
+            let confidence = ((1.0 / (1.0 + variation)).clamp(0.3, 0.95) * 100.0).round() / 100.0;
+
+            let item = BudgetSuggestionItem {
+                budget_id: None,
+                category: Some(category.to_string()),
+                suggested_amount: median.to_string(),
+                reason: format!(
+                    "You spent a median of {} a month on {} over the last {} months without a budget.",
+                    median, category, months.len()
+                ),
+                confidence,
+            };
+            (median, item)
+        })
+        .collect();
+
+    // Largest first, where a budget makes the most difference
+    suggestions.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.category.cmp(&b.1.category)));
+    suggestions.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_thresholds_use_the_default_bounds() {
+        assert_eq!(alert_bounds(&[]), (50, 100));
+    }
+
+    #[test]
+    fn a_single_threshold_keeps_the_default_lower_bound() {
+        assert_eq!(alert_bounds(&[100]), (50, 100));
+    }
+
+    #[test]
+    fn several_thresholds_bound_the_range() {
+        assert_eq!(alert_bounds(&[90, 60, 120]), (60, 120));
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::Utc;
+use tracing::info;
+
+use crate::jobs::Schedule;
+use crate::models::TASK_BUDGET_ALERTS;
+use crate::repositories::BudgetRepository;
+use crate::services::{BudgetService, ScheduledTask};
+use crate::utils::AppError;
+
+pub struct BudgetAlertWorker<R: BudgetRepository> {
+    budget_service: BudgetService<R>,
+    check_interval_secs: u64,
+}
+
+impl<R: BudgetRepository> BudgetAlertWorker<R> {
+    pub fn new(budget_service: BudgetService<R>, check_interval_secs: u64) -> Self {
+        Self {
+            budget_service,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: BudgetRepository + 'static> ScheduledTask for BudgetAlertWorker<R> {
+    fn name(&self) -> &'static str {
+        TASK_BUDGET_ALERTS
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let alerted = self.budget_service.evaluate_alerts(Utc::now().date_naive()).await?;
+        if alerted > 0 {
+            info!("Raised alerts for {} budgets", alerted);
+        }
+
+        Ok(alerted as u64)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use chrono::Utc;
+use tracing::info;
+
+use crate::jobs::Schedule;
+use crate::models::TASK_BUDGET_ROLLOVER;
+use crate::repositories::BudgetRepository;
+use crate::services::{BudgetService, ScheduledTask};
+use crate::utils::{AppError, CacheService};
+
+pub struct BudgetRolloverWorker<R: BudgetRepository> {
+    budget_service: BudgetService<R>,
+    cache: CacheService,
+    check_interval_secs: u64,
+}
+
+impl<R: BudgetRepository> BudgetRolloverWorker<R> {
+    pub fn new(budget_service: BudgetService<R>, cache: CacheService, check_interval_secs: u64) -> Self {
+        Self {
+            budget_service,
+            cache,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: BudgetRepository + 'static> ScheduledTask for BudgetRolloverWorker<R> {
+    fn name(&self) -> &'static str {
+        TASK_BUDGET_ROLLOVER
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let user_ids = self.budget_service.roll_over_expired_budgets(Utc::now().date_naive()).await?;
+        for user_id in &user_ids {
+            // Budget lists and performance views still show the old period
+            self.cache.invalidate_user_derived(user_id).await;
+        }
+        if !user_ids.is_empty() {
+            info!("Rolled over expired budgets for {} users", user_ids.len());
+        }
+
+        Ok(user_ids.len() as u64)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::categorization::{CategoryModel, RulesEngine};
+use crate::models::{
+    CategoryFeedback, CategoryFeedbackRequest, SuggestCategoryRequest, SuggestCategoryResponse
+};
+use crate::repositories::CategorizationRepository;
+use crate::utils::AppError;
+
+// How much history the model compares a new description against
+const MAX_EXAMPLES: i64 = 1000;
+// Below this the model's guess is discarded in favour of the rules engine
+const MIN_MODEL_CONFIDENCE: f64 = 0.5;
+
+#[derive(Clone)]
+pub struct CategorizationService<R: CategorizationRepository> {
+    repository: R,
+    model: Option<Arc<dyn CategoryModel>>,
+    rules: RulesEngine,
+}
+
+impl<R: CategorizationRepository> CategorizationService<R> {
+    pub fn new(repository: R, model: Option<Box<dyn CategoryModel>>) -> Self {
+        Self {
+            repository,
+            model: model.map(Arc::from),
+            rules: RulesEngine,
+        }
+    }
+
+    pub async fn suggest_category(&self, user_id: Uuid, request: SuggestCategoryRequest) -> Result<SuggestCategoryResponse, AppError> {
+        let examples = self.repository.find_labeled_examples(user_id, MAX_EXAMPLES).await?;
+
+        if let Some(model) = &self.model
+            && let Some(suggestion) = model.suggest(&request.description, &examples).await
+            && suggestion.confidence >= MIN_MODEL_CONFIDENCE
+        {
+            return Ok(SuggestCategoryResponse { suggestion: Some(suggestion) });
+        }
+
+        let suggestion = self
+            .rules
+            .suggest(&request.description, request.transaction_type.as_deref(), &examples);
+
+        Ok(SuggestCategoryResponse { suggestion })
+    }
+
+    pub async fn record_feedback(&self, user_id: Uuid, request: CategoryFeedbackRequest) -> Result<CategoryFeedback, AppError> {
+        self.repository
+            .create_feedback(
+                user_id,
+                request.description.trim(),
+                request.suggested_category.as_deref(),
+                request.chosen_category.trim(),
+            )
+            .await
+    }
+}
\ No newline at end of file
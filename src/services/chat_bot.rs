@@ -0,0 +1,234 @@
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::config::ChatBotConfig;
+use crate::importers::{parse_quick_entry, DEFAULT_CATEGORY};
+use crate::models::{
+    ChatAction, ChatMessage, CreateLinkCodeRequest, CreateTransactionRequest, LinkCodeResponse, LinkedAccountResponse, Money,
+    SlackCommand, SuggestCategoryRequest, TelegramUpdate, TransactionResponse, CHAT_PROVIDER_SLACK, CHAT_PROVIDER_TELEGRAM,
+    MONEY_SCALE,
+};
+use crate::repositories::{CategorizationRepository, LinkedAccountRepository};
+use crate::services::CategorizationService;
+use crate::utils::{AppError, generate_token, hash_token};
+
+const LINK_CODE_LENGTH: usize = 8;
+const LINK_CODE_TTL_MINUTES: i64 = 15;
+// Slack's own guidance for refusing replayed requests
+const MAX_SLACK_SIGNATURE_AGE_SECS: i64 = 5 * 60;
+const HELP_REPLY: &str = "Send an expense like \"coffee 4.50\". To link this chat to your account, create a link code in the app and send \"/link <code>\".";
+const NO_AMOUNT_REPLY: &str = "I couldn't find an amount in that. Try something like \"coffee 4.50\".";
+
+#[derive(Clone)]
+pub struct ChatBotService<L: LinkedAccountRepository, C: CategorizationRepository> {
+    repository: L,
+    categorization_service: CategorizationService<C>,
+    config: ChatBotConfig,
+}
+
+impl<L: LinkedAccountRepository, C: CategorizationRepository> ChatBotService<L, C> {
+    pub fn new(repository: L, categorization_service: CategorizationService<C>, config: ChatBotConfig) -> Self {
+        Self {
+            repository,
+            categorization_service,
+            config,
+        }
+    }
+
+    pub async fn create_link_code(&self, user_id: Uuid, request: CreateLinkCodeRequest) -> Result<LinkCodeResponse, AppError> {
+        if self.provider_secret(&request.provider).is_none() {
+            return Err(AppError::ValidationError(format!("The {} bot is not enabled", request.provider)));
+        }
+
+        let code = generate_token(LINK_CODE_LENGTH).to_uppercase();
+        let expires_at = Utc::now() + Duration::minutes(LINK_CODE_TTL_MINUTES);
+        self.repository
+            .create_link_code(user_id, &request.provider, &hash_token(&code), expires_at)
+            .await?;
+
+        Ok(LinkCodeResponse {
+            provider: request.provider,
+            code,
+            expires_at,
+        })
+    }
+
+    pub async fn list_links(&self, user_id: Uuid) -> Result<Vec<LinkedAccountResponse>, AppError> {
+        let accounts = self.repository.find_by_user(user_id).await?;
+        Ok(accounts.into_iter().map(LinkedAccountResponse::from).collect())
+    }
+
+    pub async fn unlink(&self, user_id: Uuid, provider: &str) -> Result<(), AppError> {
+        if !self.repository.delete(user_id, provider).await? {
+            return Err(AppError::NotFound(format!("No {} account is linked", provider)));
+        }
+        Ok(())
+    }
+
+    // Telegram echoes the setWebhook secret_token in a header on every update. Returns the chat to
+    // answer in with the message, or None for updates without text.
+    pub fn telegram_message(&self, secret_token: Option<&str>, body: &str) -> Result<Option<(i64, ChatMessage)>, AppError> {
+        let secret = self
+            .config
+            .telegram_secret_token
+            .as_deref()
+            .ok_or_else(|| AppError::NotFound("Not found".to_string()))?;
+        let provided = secret_token.ok_or_else(|| AppError::Unauthorized("Missing webhook secret".to_string()))?;
+        if hash_token(provided) != hash_token(secret) {
+            return Err(AppError::Unauthorized("Invalid webhook secret".to_string()));
+        }
+
+        let update: TelegramUpdate = serde_json::from_str(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid Telegram update: {}", e)))?;
+        let Some(message) = update.message else {
+            return Ok(None);
+        };
+        let Some(text) = message.text else {
+            return Ok(None);
+        };
+
+        // In group chats the sender, not the chat, is who gets linked
+        let external_id = message.from.map_or(message.chat.id, |from| from.id).to_string();
+        Ok(Some((
+            message.chat.id,
+            ChatMessage {
+                provider: CHAT_PROVIDER_TELEGRAM,
+                external_id,
+                text,
+            },
+        )))
+    }
+
+    // Slack signs "v0:<timestamp>:<raw body>" with the app's signing secret
+    pub fn slack_message(&self, timestamp: Option<&str>, signature: Option<&str>, body: &str) -> Result<ChatMessage, AppError> {
+        let secret = self
+            .config
+            .slack_signing_secret
+            .as_deref()
+            .ok_or_else(|| AppError::NotFound("Not found".to_string()))?;
+        let invalid = || AppError::Unauthorized("Invalid request signature".to_string());
+
+        let (timestamp, signature) = timestamp.zip(signature).ok_or_else(invalid)?;
+        let sent_at: i64 = timestamp.parse().map_err(|_| invalid())?;
+        if (Utc::now().timestamp() - sent_at).abs() > MAX_SLACK_SIGNATURE_AGE_SECS {
+            return Err(invalid());
+        }
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| AppError::InternalServerError(format!("Invalid signing secret: {}", e)))?;
+        mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+        let expected = format!("v0={:x}", mac.finalize().into_bytes());
+        if hash_token(&expected) != hash_token(signature) {
+            return Err(invalid());
+        }
+
+        let command: SlackCommand = serde_urlencoded::from_str(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid slash command: {}", e)))?;
+        Ok(ChatMessage {
+            provider: CHAT_PROVIDER_SLACK,
+            external_id: format!("{}:{}", command.team_id, command.user_id),
+            text: command.text,
+        })
+    }
+
+    pub async fn handle(&self, message: ChatMessage) -> Result<ChatAction, AppError> {
+        let text = message.text.trim();
+        let (command, argument) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let argument = argument.trim();
+        // Telegram appends the bot's name to commands sent in groups, as in "/link@fintrack_bot"
+        let command = command.trim_start_matches('/').split('@').next().unwrap_or_default().to_lowercase();
+        let single_word = !argument.is_empty() && !argument.contains(char::is_whitespace);
+
+        match command.as_str() {
+            "start" | "help" if argument.is_empty() => Ok(ChatAction::Reply(HELP_REPLY.to_string())),
+            // Telegram deep links arrive as "/start <code>"
+            "start" | "link" if single_word => self.link(&message, argument).await,
+            "unlink" if argument.is_empty() => self.unlink_chat(&message).await,
+            _ => self.expense(&message, text).await,
+        }
+    }
+
+    async fn link(&self, message: &ChatMessage, code: &str) -> Result<ChatAction, AppError> {
+        let linked = self
+            .repository
+            .redeem_link_code(message.provider, &hash_token(&code.to_uppercase()), &message.external_id)
+            .await?;
+
+        let reply = match linked {
+            Some(_) => "Linked! Send an expense like \"coffee 4.50\" to log it.",
+            None => "That link code is invalid or has expired. Create a new one in the app.",
+        };
+        Ok(ChatAction::Reply(reply.to_string()))
+    }
+
+    async fn unlink_chat(&self, message: &ChatMessage) -> Result<ChatAction, AppError> {
+        let Some(account) = self.repository.find_by_external_id(message.provider, &message.external_id).await? else {
+            return Ok(ChatAction::Reply("This chat isn't linked to an account.".to_string()));
+        };
+
+        self.repository.delete(account.user_id, message.provider).await?;
+        Ok(ChatAction::Reply("Unlinked. Expenses sent here won't be logged anymore.".to_string()))
+    }
+
+    async fn expense(&self, message: &ChatMessage, text: &str) -> Result<ChatAction, AppError> {
+        let Some(account) = self.repository.find_by_external_id(message.provider, &message.external_id).await? else {
+            return Ok(ChatAction::Reply(HELP_REPLY.to_string()));
+        };
+        let Some((description, amount)) = parse_quick_entry(text) else {
+            return Ok(ChatAction::Reply(NO_AMOUNT_REPLY.to_string()));
+        };
+
+        let suggestion = self
+            .categorization_service
+            .suggest_category(
+                account.user_id,
+                SuggestCategoryRequest {
+                    description: description.clone(),
+                    transaction_type: Some("expense".to_string()),
+                },
+            )
+            .await?
+            .suggestion;
+
+        Ok(ChatAction::LogExpense {
+            user_id: account.user_id,
+            request: Box::new(CreateTransactionRequest {
+                account_id: None,
+                description,
+                amount: Money::new(amount.round_dp(MONEY_SCALE)),
+                category: suggestion.map_or_else(|| DEFAULT_CATEGORY.to_string(), |suggestion| suggestion.category),
+                transaction_type: "expense".to_string(),
+                transaction_date: Utc::now().date_naive().format("%Y-%m-%d").to_string(),
+                override_limit: false,
+                pending: false,
+                notes: None,
+                metadata: Some(serde_json::json!({ "source": message.provider })),
+                currency: None,
+            }),
+        })
+    }
+
+    fn provider_secret(&self, provider: &str) -> Option<&str> {
+        match provider {
+            CHAT_PROVIDER_TELEGRAM => self.config.telegram_secret_token.as_deref(),
+            CHAT_PROVIDER_SLACK => self.config.slack_signing_secret.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub fn expense_logged_reply(transaction: &TransactionResponse, warning: Option<&str>) -> String {
+    let mut reply = format!(
+        "Logged {} for {} under {}.",
+        transaction.amount,
+        transaction.description,
+        transaction.category.as_deref().unwrap_or(DEFAULT_CATEGORY)
+    );
+    if let Some(warning) = warning {
+        reply.push(' ');
+        reply.push_str(warning);
+    }
+    reply
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::models::{
+    BaseCurrencyChangeResponse, ExchangeRate, JobResponse, RestatementResult, UpdateBaseCurrencyRequest,
+    JOB_TYPE_CURRENCY_RESTATEMENT, PIVOT_CURRENCY,
+};
+use crate::repositories::{CurrencyRepository, JobRepository, UserRepository};
+use crate::utils::{AppError, CacheService, DistributedLock, SNAPSHOT_LOCK_TTL, SNAPSHOT_LOCK_WAIT, user_snapshots_lock};
+
+const RESTATEMENT_BATCH_SIZE: i64 = 500;
+
+#[derive(Clone)]
+pub struct CurrencyService<U: UserRepository, C: CurrencyRepository, J: JobRepository> {
+    user_repository: U,
+    currency_repository: C,
+    job_repository: J,
+    cache: CacheService,
+    locks: DistributedLock,
+}
+
+impl<U, C, J> CurrencyService<U, C, J>
+where
+    U: UserRepository + 'static,
+    C: CurrencyRepository + 'static,
+    J: JobRepository + 'static,
+{
+    pub fn new(user_repository: U, currency_repository: C, job_repository: J, cache: CacheService) -> Self {
+        Self {
+            user_repository,
+            currency_repository,
+            job_repository,
+            locks: DistributedLock::new(cache.clone()),
+            cache,
+        }
+    }
+
+    pub async fn change_base_currency(&self, user_id: Uuid, request: UpdateBaseCurrencyRequest) -> Result<BaseCurrencyChangeResponse, AppError> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        if user.base_currency == request.base_currency {
+            return Ok(BaseCurrencyChangeResponse {
+                user: user.to_response(),
+                restatement_job: None,
+            });
+        }
+
+        if !self.currency_repository.is_supported(&request.base_currency).await? {
+            return Err(AppError::ValidationError(format!(
+                "No exchange rates are available for {}",
+                request.base_currency
+            )));
+        }
+
+        let user = self.user_repository.update_base_currency(user_id, &request.base_currency).await?;
+        let job = self.enqueue_restatement(user_id).await?;
+
+        Ok(BaseCurrencyChangeResponse {
+            user: user.to_response(),
+            restatement_job: Some(job),
+        })
+    }
+
+    // Restatement runs in the background; its progress is reported through the jobs API
+    async fn enqueue_restatement(&self, user_id: Uuid) -> Result<JobResponse, AppError> {
+        let job = self.job_repository.create(user_id, JOB_TYPE_CURRENCY_RESTATEMENT).await?;
+
+        let service = self.clone();
+        let job_id = job.id;
+        tokio::spawn(async move {
+            service.run_restatement(job_id, user_id).await;
+        });
+
+        Ok(job.to_response())
+    }
+
+    async fn run_restatement(&self, job_id: Uuid, user_id: Uuid) {
+        // Queues behind a reaggregation or an earlier restatement of the same user, on any instance
+        let guard = match self.locks.acquire(&user_snapshots_lock(&user_id), SNAPSHOT_LOCK_TTL, SNAPSHOT_LOCK_WAIT).await {
+            Ok(guard) => guard,
+            Err(e) => {
+                error!("Restatement job {} could not start: {}", job_id, e);
+                if let Err(e) = self.job_repository.mark_failed(job_id, &e.to_string()).await {
+                    error!("Failed to record outcome of restatement job {}: {}", job_id, e);
+                }
+                return;
+            }
+        };
+
+        if let Err(e) = self.job_repository.mark_running(job_id).await {
+            error!("Failed to start restatement job {}: {}", job_id, e);
+            return;
+        }
+
+        let outcome = match self.restate(job_id, user_id).await {
+            Ok(result) => {
+                let result = serde_json::to_value(result).unwrap_or_default();
+                self.job_repository.mark_completed(job_id, result).await
+            }
+            Err(e) => {
+                error!("Restatement job {} failed: {}", job_id, e);
+                self.job_repository.mark_failed(job_id, &e.to_string()).await
+            }
+        };
+
+        guard.release().await;
+
+        match outcome {
+            Ok(()) => info!("Restatement job {} finished for user {}", job_id, user_id),
+            Err(e) => error!("Failed to record outcome of restatement job {}: {}", job_id, e),
+        }
+    }
+
+    async fn restate(&self, job_id: Uuid, user_id: Uuid) -> Result<RestatementResult, AppError> {
+        let base_currency = self.current_base_currency(user_id).await?;
+        let total_items = self.currency_repository.count_snapshots(user_id).await? as i32;
+        self.job_repository.update_progress(job_id, 0, total_items).await?;
+
+        let mut currencies = self.currency_repository.find_snapshot_currencies(user_id).await?;
+        currencies.push(base_currency.clone());
+        let rates = RateTable::new(self.currency_repository.find_rates(&currencies).await?);
+
+        let today = Utc::now().date_naive();
+        let mut after_id = 0;
+        let mut processed_items = 0;
+        let mut snapshots_restated = 0;
+        let mut snapshots_missing_rate = 0;
+
+        loop {
+            // A newer change queues its own job, so this one stops rather than overwrite it
+            if self.current_base_currency(user_id).await? != base_currency {
+                return Err(AppError::Conflict("Base currency changed again; a newer restatement job supersedes this one".to_string()));
+            }
+
+            let batch = self
+                .currency_repository
+                .find_snapshot_batch(user_id, after_id, RESTATEMENT_BATCH_SIZE)
+                .await?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after_id = last.id;
+
+            let ids: Vec<i64> = batch.iter().map(|snapshot| snapshot.id).collect();
+            let balances: Vec<Option<Decimal>> = batch
+                .iter()
+                .map(|snapshot| {
+                    let rate_date = month_end(snapshot.snapshot_month).min(today);
+                    rates.convert(snapshot.balance, &snapshot.currency, &base_currency, rate_date)
+                })
+                .collect();
+            snapshots_missing_rate += balances.iter().filter(|balance| balance.is_none()).count() as u64;
+
+            snapshots_restated += self
+                .currency_repository
+                .update_converted_balances(&ids, &balances, &base_currency)
+                .await?;
+            processed_items += batch.len() as i32;
+            self.job_repository.update_progress(job_id, processed_items, total_items.max(processed_items)).await?;
+        }
+
+        // Analytics and summaries are computed on read, so dropping their caches
+        // is enough for them to be served in the new base currency
+        let cache_entries_invalidated = self.cache.invalidate_user_derived(&user_id).await;
+
+        Ok(RestatementResult {
+            base_currency,
+            snapshots_restated,
+            snapshots_missing_rate,
+            cache_entries_invalidated,
+        })
+    }
+
+    async fn current_base_currency(&self, user_id: Uuid) -> Result<String, AppError> {
+        self.currency_repository
+            .find_base_currency(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+}
+
+// Units of `to` per unit of `from` on the date, from the latest rates published by then
+pub async fn exchange_rate_on<C: CurrencyRepository>(currency_repository: &C, from: &str, to: &str, date: NaiveDate) -> Result<Option<Decimal>, AppError> {
+    let rates = RateTable::new(currency_repository.find_rates(&[from.to_string(), to.to_string()]).await?);
+    Ok(rates.rate_between(from, to, date))
+}
+
+// Historical rates per currency, each sorted by date
+struct RateTable {
+    rates: HashMap<String, Vec<(NaiveDate, Decimal)>>,
+}
+
+impl RateTable {
+    fn new(rates: Vec<ExchangeRate>) -> Self {
+        let mut table: HashMap<String, Vec<(NaiveDate, Decimal)>> = HashMap::new();
+        for rate in rates {
+            table.entry(rate.currency).or_default().push((rate.rate_date, rate.usd_rate));
+        }
+        for history in table.values_mut() {
+            history.sort_by_key(|(date, _)| *date);
+        }
+
+        Self { rates: table }
+    }
+
+    // Latest rate published on or before the given date
+    fn rate_on(&self, currency: &str, date: NaiveDate) -> Option<Decimal> {
+        if currency == PIVOT_CURRENCY {
+            return Some(Decimal::ONE);
+        }
+
+        let history = self.rates.get(currency)?;
+        let index = history.partition_point(|(rate_date, _)| *rate_date <= date);
+        index.checked_sub(1).map(|i| history[i].1)
+    }
+
+    // Rounded to what exchange_rates stores
+    fn rate_between(&self, from: &str, to: &str, date: NaiveDate) -> Option<Decimal> {
+        if from == to {
+            return Some(Decimal::ONE);
+        }
+
+        Some((self.rate_on(to, date)? / self.rate_on(from, date)?).round_dp(10))
+    }
+
+    fn convert(&self, amount: Decimal, from: &str, to: &str, date: NaiveDate) -> Option<Decimal> {
+        if from == to {
+            return Some(amount);
+        }
+
+        let from_rate = self.rate_on(from, date)?;
+        let to_rate = self.rate_on(to, date)?;
+        Some((amount / from_rate * to_rate).round_dp(2))
+    }
+}
+
+fn month_end(month: NaiveDate) -> NaiveDate {
+    month
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(month)
+}
\ No newline at end of file
@@ -0,0 +1,172 @@
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::{
+    CreateDebtRequest, DebtResponse, Money, PayoffPlanQuery, PayoffPlanResponse, PayoffScheduleEntry, UpdateDebtRequest,
+};
+use crate::repositories::DebtRepository;
+use crate::utils::AppError;
+use crate::policy::{Action, permits};
+
+// Fifty years; a plan that runs longer is treated as never paying off
+const MAX_PAYOFF_MONTHS: usize = 600;
+
+#[derive(Clone)]
+pub struct DebtService<R: DebtRepository> {
+    repository: R,
+}
+
+impl<R: DebtRepository> DebtService<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn get_debt_by_id(&self, id: i64, user_id: Uuid) -> Result<DebtResponse, AppError> {
+        let debt = self
+            .repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|debt| permits(user_id, Action::Read, debt))
+            .ok_or_else(|| AppError::NotFound("Debt not found".to_string()))?;
+
+        Ok(debt.to_response())
+    }
+
+    pub async fn list_debts(&self, user_id: Uuid) -> Result<Vec<DebtResponse>, AppError> {
+        let debts = self.repository.find_by_user_id(user_id).await?;
+        Ok(debts.into_iter().map(|debt| debt.to_response()).collect())
+    }
+
+    pub async fn create_debt(&self, user_id: Uuid, request: CreateDebtRequest) -> Result<DebtResponse, AppError> {
+        let principal = request.principal.amount();
+        let apr = parse_apr(&request.apr)?;
+        let minimum_payment = request.minimum_payment.amount();
+
+        let debt = self
+            .repository
+            .create(user_id, &request.name, &request.debt_type, principal, apr, minimum_payment)
+            .await?;
+        Ok(debt.to_response())
+    }
+
+    pub async fn update_debt(&self, id: i64, user_id: Uuid, request: UpdateDebtRequest) -> Result<DebtResponse, AppError> {
+        let principal = request.principal.map(Money::amount);
+        let apr = request.apr.as_deref().map(parse_apr).transpose()?;
+        let minimum_payment = request.minimum_payment.map(Money::amount);
+
+        let debt = self
+            .repository
+            .update(id, user_id, request.name.as_deref(), principal, apr, minimum_payment)
+            .await?;
+        Ok(debt.to_response())
+    }
+
+    pub async fn delete_debt(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        self.repository.delete(id, user_id).await
+    }
+
+    pub async fn get_payoff_plan(&self, id: i64, user_id: Uuid, query: PayoffPlanQuery) -> Result<PayoffPlanResponse, AppError> {
+        let debt = self
+            .repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|debt| permits(user_id, Action::Read, debt))
+            .ok_or_else(|| AppError::NotFound("Debt not found".to_string()))?;
+
+        let extra_payment = query.extra_payment.map(Money::amount).unwrap_or(Decimal::ZERO);
+        let monthly_payment = debt.minimum_payment + extra_payment;
+        let start = Utc::now().date_naive();
+
+        let schedule = amortize(debt.principal, debt.apr, monthly_payment, start)?;
+        let total_interest: Decimal = schedule.iter().map(|entry| entry.interest).sum();
+        let total_paid: Decimal = schedule.iter().map(|entry| entry.payment).sum();
+
+        Ok(PayoffPlanResponse {
+            debt_id: debt.id,
+            monthly_payment: monthly_payment.to_string(),
+            months: schedule.len(),
+            payoff_date: schedule.last().map(|entry| entry.payment_date),
+            total_interest: total_interest.to_string(),
+            total_paid: total_paid.to_string(),
+            schedule: schedule.into_iter().map(PayoffScheduleEntry::from).collect(),
+        })
+    }
+}
+
+// One month of an amortization schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmortizationRow {
+    pub month: usize,
+    pub payment_date: NaiveDate,
+    pub payment: Decimal,
+    pub principal: Decimal,
+    pub interest: Decimal,
+    pub remaining_balance: Decimal,
+}
+
+// Interest accrues monthly at apr / 12 and is rounded to cents before the payment is applied.
+// The final payment only covers what is left.
+pub fn amortize(balance: Decimal, apr: Decimal, monthly_payment: Decimal, start: NaiveDate) -> Result<Vec<AmortizationRow>, AppError> {
+    let monthly_rate = apr / Decimal::from(1200);
+    let mut remaining = balance;
+    let mut schedule = Vec::new();
+
+    while remaining > Decimal::ZERO {
+        if schedule.len() >= MAX_PAYOFF_MONTHS {
+            return Err(AppError::ValidationError(format!(
+                "This payment would take more than {} months to pay off the debt",
+                MAX_PAYOFF_MONTHS
+            )));
+        }
+
+        let interest = (remaining * monthly_rate).round_dp(2);
+        if monthly_payment <= interest {
+            return Err(AppError::ValidationError(
+                "The monthly payment does not cover the interest, so the debt would never be paid off".to_string(),
+            ));
+        }
+
+        let payment = monthly_payment.min(remaining + interest);
+        let principal = payment - interest;
+        remaining -= principal;
+
+        let month = schedule.len() + 1;
+        schedule.push(AmortizationRow {
+            month,
+            payment_date: start
+                .checked_add_months(Months::new(month as u32))
+                .unwrap_or(NaiveDate::MAX),
+            payment,
+            principal,
+            interest,
+            remaining_balance: remaining,
+        });
+    }
+
+    Ok(schedule)
+}
+
+impl From<AmortizationRow> for PayoffScheduleEntry {
+    fn from(row: AmortizationRow) -> Self {
+        Self {
+            month: row.month,
+            payment_date: row.payment_date,
+            payment: row.payment.to_string(),
+            principal: row.principal.to_string(),
+            interest: row.interest.to_string(),
+            remaining_balance: row.remaining_balance.to_string(),
+        }
+    }
+}
+
+fn parse_apr(apr: &str) -> Result<Decimal, AppError> {
+    let apr = Decimal::from_str(apr).map_err(|_| AppError::invalid_field("apr", "Invalid apr format"))?;
+
+    if apr < Decimal::ZERO || apr > Decimal::from(100) {
+        return Err(AppError::invalid_field("apr", "APR must be between 0 and 100"));
+    }
+
+    Ok(apr)
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::jobs::Schedule;
+use crate::models::{DigestRecipient, MonthlyDigest, DEFAULT_LOCALE, TASK_MONTHLY_DIGEST};
+use crate::repositories::DigestRepository;
+use crate::services::ScheduledTask;
+use crate::utils::{AppError, Mailer, month_label, EMAIL_TEMPLATE_MONTHLY_DIGEST};
+
+// Recipients handled per query, so a large user base is mailed in bounded chunks
+const DIGEST_BATCH_SIZE: i64 = 100;
+const DIGEST_TOP_CATEGORIES: i64 = 5;
+
+#[derive(Clone)]
+pub struct DigestService<D: DigestRepository> {
+    repository: D,
+    mailer: Mailer,
+}
+
+impl<D: DigestRepository> DigestService<D> {
+    pub fn new(repository: D, mailer: Mailer) -> Self {
+        Self { repository, mailer }
+    }
+
+    // Sends last month's digest to every opted-in user who hasn't had it yet, returning how many were sent
+    pub async fn send_due_digests(&self, today: NaiveDate) -> Result<usize, AppError> {
+        if !self.mailer.is_enabled() {
+            return Ok(0);
+        }
+
+        let month_start = today.with_day(1).unwrap_or(today);
+        let digest_month = month_start - Months::new(1);
+        let mut sent = 0;
+
+        loop {
+            let recipients = self.repository.find_due_recipients(month_start, DIGEST_BATCH_SIZE).await?;
+            if recipients.is_empty() {
+                break;
+            }
+
+            let mut failed = 0;
+            for recipient in &recipients {
+                match self.send_digest(recipient, digest_month).await {
+                    Ok(()) => sent += 1,
+                    Err(e) => {
+                        warn!("Monthly digest for user {} failed: {}", recipient.id, e);
+                        failed += 1;
+                    }
+                }
+                // Failed users are marked too, otherwise they'd be retried every run and block later batches
+                self.repository.mark_sent(recipient.id, month_start).await?;
+            }
+
+            if failed == recipients.len() {
+                warn!("Every digest in the batch failed, stopping until the next run");
+                break;
+            }
+        }
+
+        Ok(sent)
+    }
+
+    pub async fn build_digest(&self, user_id: uuid::Uuid, month: NaiveDate) -> Result<MonthlyDigest, AppError> {
+        let end = (month + Months::new(1)).pred_opt().unwrap_or(month);
+        let (income, expenses) = self.repository.find_totals(user_id, month, end).await?;
+        let top_categories = self.repository.find_top_categories(user_id, month, end, DIGEST_TOP_CATEGORIES).await?;
+        let budgets = self.repository.find_budget_status(user_id, month, end).await?;
+
+        Ok(MonthlyDigest {
+            month,
+            income,
+            expenses,
+            top_categories,
+            budgets,
+        })
+    }
+
+    async fn send_digest(&self, recipient: &DigestRecipient, month: NaiveDate) -> Result<(), AppError> {
+        let digest = self.build_digest(recipient.id, month).await?;
+        let locale = recipient.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        let context = digest_context(recipient, &digest, locale);
+
+        self.mailer
+            .send_template(&recipient.email, EMAIL_TEMPLATE_MONTHLY_DIGEST, locale, &context)
+            .await
+    }
+}
+
+fn digest_context(recipient: &DigestRecipient, digest: &MonthlyDigest, locale: &str) -> serde_json::Value {
+    let top_categories: Vec<serde_json::Value> = digest
+        .top_categories
+        .iter()
+        .map(|category| json!({ "category": category.category, "amount": category.amount.round_dp(2) }))
+        .collect();
+
+    let budgets: Vec<serde_json::Value> = digest
+        .budgets
+        .iter()
+        .map(|budget| {
+            let used = if budget.target_amount > Decimal::ZERO {
+                (budget.spent_amount / budget.target_amount * Decimal::from(100)).round()
+            } else {
+                Decimal::ZERO
+            };
+            json!({
+                "category": budget.category,
+                "spent": budget.spent_amount.round_dp(2),
+                "target": budget.target_amount.round_dp(2),
+                "used_percent": used,
+                "over_budget": budget.spent_amount > budget.target_amount,
+                "period_end": budget.period_end,
+            })
+        })
+        .collect();
+
+    json!({
+        "name": recipient.name,
+        "period": month_label(locale, digest.month),
+        "currency": recipient.base_currency,
+        "income": digest.income.round_dp(2),
+        "expenses": digest.expenses.round_dp(2),
+        "net": (digest.income - digest.expenses).round_dp(2),
+        "top_categories": top_categories,
+        "budgets": budgets,
+    })
+}
+
+pub struct MonthlyDigestWorker<D: DigestRepository> {
+    digest_service: DigestService<D>,
+    check_interval_secs: u64,
+}
+
+impl<D: DigestRepository> MonthlyDigestWorker<D> {
+    pub fn new(digest_service: DigestService<D>, check_interval_secs: u64) -> Self {
+        Self {
+            digest_service,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<D: DigestRepository + 'static> ScheduledTask for MonthlyDigestWorker<D> {
+    fn name(&self) -> &'static str {
+        TASK_MONTHLY_DIGEST
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let sent = self.digest_service.send_due_digests(Utc::now().date_naive()).await?;
+        if sent > 0 {
+            info!("Sent {} monthly digests", sent);
+        }
+
+        Ok(sent as u64)
+    }
+}
\ No newline at end of file
@@ -1,29 +1,65 @@
 use crate::models::{
     ExpenseSummaryResponse, CategorySummaryResponse, CategorySummaryItem,
     TrendResponse, TrendItem, RecentTransactionsResponse, RecentTransactionItem,
-    DateRangeQuery, RecentTransactionsQuery
+    DateRangeQuery, RecentTransactionsQuery, Transaction, MonthlyCategoryTotal, net_refunds,
+    HeatmapQuery, HeatmapResponse, HeatmapDay, HeatmapWeekday, HeatmapHour,
 };
-use crate::repositories::TransactionRepository;
-use crate::utils::AppError;
-use chrono::NaiveDate;
+use crate::repositories::{TransactionRepository, MonthlyAggregateRepository, ReadPreference};
+use crate::utils::{AppError, codes};
+use chrono::{Datelike, NaiveDate, Timelike, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tracing::info;
 
+const WEEKDAY_NAMES: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
 #[derive(Clone)]
-pub struct ExpenseAnalyticsService<T>
+pub struct ExpenseAnalyticsService<T, A>
 where
     T: TransactionRepository,
+    A: MonthlyAggregateRepository,
 {
     transaction_repo: T,
+    aggregate_repository: A,
 }
 
-impl<T> ExpenseAnalyticsService<T>
+impl<T, A> ExpenseAnalyticsService<T, A>
 where
     T: TransactionRepository,
+    A: MonthlyAggregateRepository,
 {
-    pub fn new(transaction_repo: T) -> Self {
-        Self { transaction_repo }
+    pub fn new(transaction_repo: T, aggregate_repository: A) -> Self {
+        Self { transaction_repo, aggregate_repository }
+    }
+
+    // Per month and category totals for the range from the materialized aggregates
+    async fn load_totals(
+        &self,
+        user_id: uuid::Uuid,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+    ) -> Result<Vec<MonthlyCategoryTotal>, AppError> {
+        self.aggregate_repository.refresh_stale(user_id).await?;
+        self.aggregate_repository
+            .find_totals(user_id, "expense", from_date, to_date)
+            .await
+    }
+
+    // Posted transactions in the range with received refunds netted against their original expense
+    async fn load_transactions(
+        &self,
+        user_id: uuid::Uuid,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let transactions = self.transaction_repo
+            .find_by_date_range(user_id, from_date, to_date, ReadPreference::Replica)
+            .await?;
+        let refunds = self.transaction_repo
+            .find_received_refunds(user_id, from_date, to_date)
+            .await?;
+
+        Ok(net_refunds(transactions, &refunds))
     }
 
     pub async fn get_expense_summary(
@@ -35,25 +71,17 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        // Get expense transactions (negative amounts)
-        let transactions = self.transaction_repo
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
+        // Get expense transactions
+        let transactions = self.load_transactions(user_id, from_date, to_date).await?;
 
         let expense_transactions: Vec<_> = transactions
             .into_iter()
-            .filter(|t| t.amount < Decimal::ZERO)
+            .filter(|t| t.transaction_type == "expense")
             .collect();
 
         let total_expenses = expense_transactions
@@ -64,7 +92,7 @@ where
         let total_transactions = expense_transactions.len() as i64;
 
         // Calculate days between dates
-        let days = (to_date.date_naive() - from_date.date_naive()).num_days() + 1;
+        let days = (to_date - from_date).num_days() + 1;
         let average_per_day = if days > 0 {
             total_expenses / Decimal::from(days)
         } else {
@@ -89,39 +117,23 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
-
-        // Get expense transactions (negative amounts)
-        let transactions = self.transaction_repo
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        let expense_transactions: Vec<_> = transactions
-            .into_iter()
-            .filter(|t| t.amount < Decimal::ZERO)
-            .collect();
+        let totals = self.load_totals(user_id, from_date, to_date).await?;
 
         // Group by category
         let mut category_totals: HashMap<String, (Decimal, i64)> = HashMap::new();
         let mut total_expenses = Decimal::ZERO;
 
-        for transaction in &expense_transactions {
-            let amount = transaction.amount.abs();
-            total_expenses += amount;
-            
-            let category = transaction.category.clone().unwrap_or_else(|| "Uncategorized".to_string());
-            let (current_amount, current_count) = category_totals.entry(category).or_insert((Decimal::ZERO, 0));
-            *current_amount += amount;
-            *current_count += 1;
+        for total in totals {
+            total_expenses += total.total_amount;
+
+            let (current_amount, current_count) = category_totals.entry(total.category).or_insert((Decimal::ZERO, 0));
+            *current_amount += total.total_amount;
+            *current_count += total.transaction_count;
         }
 
         // Convert to response format
@@ -162,37 +174,22 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        // Get expense transactions (negative amounts)
-        let transactions = self.transaction_repo
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
-
-        let expense_transactions: Vec<_> = transactions
-            .into_iter()
-            .filter(|t| t.amount < Decimal::ZERO)
-            .collect();
+        let totals = self.load_totals(user_id, from_date, to_date).await?;
 
         // Group by month
         let mut monthly_totals: HashMap<String, (Decimal, i64)> = HashMap::new();
 
-        for transaction in &expense_transactions {
-            let month_key = transaction.transaction_date.format("%Y-%m").to_string();
-            let amount = transaction.amount.abs();
-            
+        for total in totals {
+            let month_key = total.month.format("%Y-%m").to_string();
+
             let (current_amount, current_count) = monthly_totals.entry(month_key).or_insert((Decimal::ZERO, 0));
-            *current_amount += amount;
-            *current_count += 1;
+            *current_amount += total.total_amount;
+            *current_count += total.transaction_count;
         }
 
         // Convert to response format and sort by period
@@ -223,25 +220,17 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        // Get expense transactions (negative amounts)
-        let transactions = self.transaction_repo
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
+        // Get expense transactions
+        let transactions = self.load_transactions(user_id, from_date, to_date).await?;
 
         let expense_transactions: Vec<_> = transactions
             .into_iter()
-            .filter(|t| t.amount < Decimal::ZERO)
+            .filter(|t| t.transaction_type == "expense")
             .collect();
 
         // Group by day
@@ -275,6 +264,66 @@ where
         })
     }
 
+    // Spending by calendar day, by weekday of the transaction date and by the hour it was recorded
+    pub async fn get_heatmap(
+        &self,
+        user_id: uuid::Uuid,
+        query: HeatmapQuery,
+    ) -> Result<HeatmapResponse, AppError> {
+        let year = query.year.unwrap_or_else(|| Utc::now().year());
+        info!("Getting expense heatmap for user {} for {}", user_id, year);
+
+        let from_date = NaiveDate::from_ymd_opt(year, 1, 1)
+            .ok_or_else(|| AppError::invalid_field("year", "Invalid year"))?;
+        let to_date = NaiveDate::from_ymd_opt(year, 12, 31)
+            .ok_or_else(|| AppError::invalid_field("year", "Invalid year"))?;
+
+        let transactions = self.load_transactions(user_id, from_date, to_date).await?;
+
+        let mut days: BTreeMap<NaiveDate, (Decimal, i64)> = BTreeMap::new();
+        let mut weekdays = [(Decimal::ZERO, 0i64); 7];
+        let mut hours = [(Decimal::ZERO, 0i64); 24];
+
+        for transaction in transactions.iter().filter(|t| t.transaction_type == "expense") {
+            let amount = transaction.amount.abs();
+            let buckets = [
+                days.entry(transaction.transaction_date).or_insert((Decimal::ZERO, 0)),
+                &mut weekdays[transaction.transaction_date.weekday().num_days_from_monday() as usize],
+                &mut hours[transaction.created_at.hour() as usize],
+            ];
+            for (total, count) in buckets {
+                *total += amount;
+                *count += 1;
+            }
+        }
+
+        Ok(HeatmapResponse {
+            year,
+            days: days
+                .into_iter()
+                .map(|(date, (total_amount, transaction_count))| HeatmapDay { date, total_amount, transaction_count })
+                .collect(),
+            weekdays: weekdays
+                .into_iter()
+                .enumerate()
+                .map(|(index, (total_amount, transaction_count))| HeatmapWeekday {
+                    weekday: WEEKDAY_NAMES[index].to_string(),
+                    total_amount,
+                    transaction_count,
+                })
+                .collect(),
+            hours: hours
+                .into_iter()
+                .enumerate()
+                .map(|(hour, (total_amount, transaction_count))| HeatmapHour {
+                    hour: hour as u32,
+                    total_amount,
+                    transaction_count,
+                })
+                .collect(),
+        })
+    }
+
     pub async fn get_recent_transactions(
         &self,
         user_id: uuid::Uuid,
@@ -289,16 +338,23 @@ where
                 page: Some(1),
                 limit: Some(limit),
                 category: None,
+                categories: None,
                 from_date: None,
                 to_date: None,
                 transaction_type: Some("expense".to_string()),
-            })
+                account_id: None,
+                min_amount: None,
+                max_amount: None,
+                sort_by: None,
+                order: None,
+                status: Some(crate::models::TRANSACTION_STATUS_POSTED.to_string()),
+            }, ReadPreference::Replica)
             .await?;
 
-        // Filter for expenses only (negative amounts) and convert to response format
+        // Filter for expenses only and convert to response format
         let expense_transactions: Vec<RecentTransactionItem> = transactions
             .into_iter()
-            .filter(|t| t.amount < Decimal::ZERO)
+            .filter(|t| t.transaction_type == "expense")
             .take(limit as usize)
             .map(|t| RecentTransactionItem {
                 id: t.id,
@@ -0,0 +1,68 @@
+use chrono::{NaiveDate, Utc};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::exporters::{ExportData, ExportFormat};
+use crate::models::{ExportFile, ExportTransactionsQuery};
+use crate::repositories::{CurrencyRepository, PocketRepository, TransactionRepository, ReadPreference};
+use crate::utils::{AppError, codes};
+
+#[derive(Clone)]
+pub struct ExportService<T: TransactionRepository, P: PocketRepository, C: CurrencyRepository> {
+    transaction_repository: T,
+    pocket_repository: P,
+    currency_repository: C,
+}
+
+impl<T: TransactionRepository, P: PocketRepository, C: CurrencyRepository> ExportService<T, P, C> {
+    pub fn new(transaction_repository: T, pocket_repository: P, currency_repository: C) -> Self {
+        Self {
+            transaction_repository,
+            pocket_repository,
+            currency_repository,
+        }
+    }
+
+    pub async fn export_transactions(&self, user_id: Uuid, query: ExportTransactionsQuery) -> Result<ExportFile, AppError> {
+        let format = match query.format.as_deref() {
+            Some(format) => ExportFormat::from_str(format)?,
+            None => ExportFormat::Csv,
+        };
+
+        let from_date = parse_date(query.from_date.as_deref(), "from_date")?;
+        let to_date = parse_date(query.to_date.as_deref(), "to_date")?;
+        if let (Some(from), Some(to)) = (from_date, to_date) && from > to {
+            return Err(AppError::ValidationError("from_date must not be after to_date".to_string()).with_code(codes::INVALID_DATE_RANGE));
+        }
+
+        let transactions = self
+            .transaction_repository
+            .find_all_by_user_id(user_id, from_date, to_date, ReadPreference::Replica)
+            .await?;
+        let pockets = self.pocket_repository.find_by_user_id(user_id, true).await?;
+        let base_currency = self
+            .currency_repository
+            .find_base_currency(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let exporter = format.exporter();
+        let content = exporter.export(&ExportData::new(transactions, pockets, base_currency));
+
+        Ok(ExportFile {
+            filename: format!("transactions-{}.{}", Utc::now().format("%Y%m%d"), exporter.file_extension()),
+            content_type: exporter.content_type(),
+            content,
+        })
+    }
+}
+
+fn parse_date(value: Option<&str>, field: &str) -> Result<Option<NaiveDate>, AppError> {
+    value
+        .map(|value| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                AppError::ValidationError(format!("Invalid {} format. Use YYYY-MM-DD", field))
+            })
+        })
+        .transpose()
+}
\ No newline at end of file
@@ -0,0 +1,192 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::models::{
+    ClientInfo, CreateExportLinkRequest, CreatedExportLinkResponse, ExportFile, ExportLink, ExportLinkAccess,
+    ExportLinkResponse, ExportTransactionsQuery, MonthlyReport, ReportFile, SharedExportSummary,
+    EXPORT_LINK_RESOURCE_REPORT, EXPORT_LINK_RESOURCE_SUMMARY, EXPORT_LINK_RESOURCE_TRANSACTIONS,
+};
+use crate::repositories::{CurrencyRepository, ExportLinkRepository, PocketRepository, TransactionRepository, UserRepository};
+use crate::services::{ExportService, ReportService};
+use crate::utils::{AppError, codes, generate_token, hash_token};
+
+const EXPORT_LINK_TOKEN_LENGTH: usize = 40;
+const MAX_ACTIVE_EXPORT_LINKS: i64 = 10;
+const ACCESS_LOG_LIMIT: i64 = 200;
+
+#[derive(Clone)]
+pub struct ExportLinkService<L: ExportLinkRepository, T: TransactionRepository, P: PocketRepository, U: UserRepository, C: CurrencyRepository> {
+    repository: L,
+    user_repository: U,
+    export_service: ExportService<T, P, C>,
+    report_service: ReportService<T, U>,
+}
+
+impl<L, T, P, U, C> ExportLinkService<L, T, P, U, C>
+where
+    L: ExportLinkRepository,
+    T: TransactionRepository,
+    P: PocketRepository,
+    U: UserRepository,
+    C: CurrencyRepository,
+{
+    pub fn new(repository: L, user_repository: U, export_service: ExportService<T, P, C>, report_service: ReportService<T, U>) -> Self {
+        Self {
+            repository,
+            user_repository,
+            export_service,
+            report_service,
+        }
+    }
+
+    pub async fn create_link(&self, user_id: Uuid, request: CreateExportLinkRequest) -> Result<CreatedExportLinkResponse, AppError> {
+        let from_date = parse_date(&request.from_date, "from_date")?;
+        let to_date = parse_date(&request.to_date, "to_date")?;
+        if from_date > to_date {
+            return Err(AppError::ValidationError("from_date must not be after to_date".to_string()).with_code(codes::INVALID_DATE_RANGE));
+        }
+
+        if self.repository.count_active(user_id).await? >= MAX_ACTIVE_EXPORT_LINKS {
+            return Err(AppError::Conflict(format!(
+                "At most {} export links can be active at once",
+                MAX_ACTIVE_EXPORT_LINKS
+            )));
+        }
+
+        let token = generate_token(EXPORT_LINK_TOKEN_LENGTH);
+        let expires_at = Utc::now() + Duration::days(request.expires_in_days);
+        let link = self
+            .repository
+            .create(user_id, request.label.as_deref(), &hash_token(&token), from_date, to_date, expires_at)
+            .await?;
+
+        Ok(CreatedExportLinkResponse {
+            token,
+            export_link: link.into(),
+        })
+    }
+
+    pub async fn list_links(&self, user_id: Uuid) -> Result<Vec<ExportLinkResponse>, AppError> {
+        let links = self.repository.find_by_user(user_id).await?;
+        Ok(links.into_iter().map(ExportLinkResponse::from).collect())
+    }
+
+    pub async fn revoke_link(&self, user_id: Uuid, id: Uuid) -> Result<ExportLinkResponse, AppError> {
+        let link = self.repository.revoke(id, user_id).await?;
+        Ok(link.into())
+    }
+
+    pub async fn access_log(&self, user_id: Uuid, id: Uuid) -> Result<Vec<ExportLinkAccess>, AppError> {
+        self.repository
+            .find_by_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Export link not found".to_string()))?;
+
+        self.repository.find_access_log(id, ACCESS_LOG_LIMIT).await
+    }
+
+    pub async fn shared_summary(&self, token: &str, client: &ClientInfo) -> Result<SharedExportSummary, AppError> {
+        let link = self.open(token, EXPORT_LINK_RESOURCE_SUMMARY, client).await?;
+        let owner = self
+            .user_repository
+            .find_by_id(link.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Export link not found".to_string()))?;
+
+        Ok(SharedExportSummary {
+            report_months: report_months(link.from_date, link.to_date)
+                .into_iter()
+                .map(|month| month.format("%Y-%m").to_string())
+                .collect(),
+            label: link.label,
+            owner_name: owner.name,
+            from_date: link.from_date,
+            to_date: link.to_date,
+            expires_at: link.expires_at,
+        })
+    }
+
+    // Requested dates are narrowed to the shared range rather than rejected
+    pub async fn shared_transactions(&self, token: &str, query: ExportTransactionsQuery, client: &ClientInfo) -> Result<ExportFile, AppError> {
+        let link = self.open(token, EXPORT_LINK_RESOURCE_TRANSACTIONS, client).await?;
+
+        let from_date = match query.from_date.as_deref() {
+            Some(date) => parse_date(date, "from_date")?.max(link.from_date),
+            None => link.from_date,
+        };
+        let to_date = match query.to_date.as_deref() {
+            Some(date) => parse_date(date, "to_date")?.min(link.to_date),
+            None => link.to_date,
+        };
+        if from_date > to_date {
+            return Err(AppError::ValidationError("The requested dates fall outside the shared range".to_string()));
+        }
+
+        self.export_service
+            .export_transactions(
+                link.user_id,
+                ExportTransactionsQuery {
+                    format: query.format,
+                    from_date: Some(from_date.to_string()),
+                    to_date: Some(to_date.to_string()),
+                },
+            )
+            .await
+    }
+
+    pub async fn shared_report(&self, token: &str, month: &str, client: &ClientInfo) -> Result<MonthlyReport, AppError> {
+        let link = self.open_report(token, month, client).await?;
+        self.report_service.monthly_report(link.user_id, month).await
+    }
+
+    pub async fn shared_report_pdf(&self, token: &str, month: &str, client: &ClientInfo) -> Result<ReportFile, AppError> {
+        let link = self.open_report(token, month, client).await?;
+        self.report_service.monthly_report_pdf(link.user_id, month).await
+    }
+
+    // A statement covers its whole month, so only months entirely inside the range are shared
+    async fn open_report(&self, token: &str, month: &str, client: &ClientInfo) -> Result<ExportLink, AppError> {
+        let link = self.open(token, EXPORT_LINK_RESOURCE_REPORT, client).await?;
+
+        let start = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError("Invalid month format. Use YYYY-MM".to_string()))?;
+        if !report_months(link.from_date, link.to_date).contains(&start) {
+            return Err(AppError::ValidationError("That month is not covered by this export link".to_string()));
+        }
+
+        Ok(link)
+    }
+
+    // Unknown, expired and revoked links all look the same to the caller
+    async fn open(&self, token: &str, resource: &str, client: &ClientInfo) -> Result<ExportLink, AppError> {
+        let link = self
+            .repository
+            .find_active_by_hash(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Export link not found".to_string()))?;
+
+        self.repository.record_access(link.id, resource, client).await?;
+        Ok(link)
+    }
+}
+
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::ValidationError(format!("Invalid {} format. Use YYYY-MM-DD", field)))
+}
+
+fn report_months(from_date: NaiveDate, to_date: NaiveDate) -> Vec<NaiveDate> {
+    let mut month = from_date.with_day(1).unwrap_or(from_date);
+    if month < from_date {
+        month = month + Months::new(1);
+    }
+
+    let mut months = Vec::new();
+    while let Some(month_end) = (month + Months::new(1)).pred_opt()
+        && month_end <= to_date
+    {
+        months.push(month);
+        month = month + Months::new(1);
+    }
+    months
+}
\ No newline at end of file
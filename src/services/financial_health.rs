@@ -0,0 +1,153 @@
+use chrono::{Days, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use uuid::Uuid;
+
+use crate::models::{
+    currency_scale, FinancialHealthQuery, FinancialHealthResponse, HealthMetric, net_refunds, HEALTH_RATING_EXCELLENT,
+    HEALTH_RATING_FAIR, HEALTH_RATING_GOOD, HEALTH_RATING_POOR,
+};
+use crate::repositories::{BudgetRepository, CurrencyRepository, PocketRepository, ReadPreference, TransactionRepository};
+use crate::services::exchange_rate_on;
+use crate::utils::AppError;
+
+const DEFAULT_MONTHS: u32 = 3;
+// Saving a fifth of income scores full marks
+const TARGET_SAVINGS_RATE_PERCENT: i64 = 20;
+// Spending half of income or less scores full marks, spending all of it scores zero
+const TARGET_EXPENSE_RATIO_PERCENT: i64 = 50;
+const TARGET_EMERGENCY_FUND_MONTHS: i64 = 6;
+
+#[derive(Clone)]
+pub struct FinancialHealthService<P: PocketRepository, T: TransactionRepository, B: BudgetRepository, C: CurrencyRepository> {
+    pocket_repository: P,
+    transaction_repository: T,
+    budget_repository: B,
+    currency_repository: C,
+}
+
+impl<P: PocketRepository, T: TransactionRepository, B: BudgetRepository, C: CurrencyRepository> FinancialHealthService<P, T, B, C> {
+    pub fn new(pocket_repository: P, transaction_repository: T, budget_repository: B, currency_repository: C) -> Self {
+        Self {
+            pocket_repository,
+            transaction_repository,
+            budget_repository,
+            currency_repository,
+        }
+    }
+
+    pub async fn get_health(&self, user_id: Uuid, query: FinancialHealthQuery) -> Result<FinancialHealthResponse, AppError> {
+        let months = query.months.unwrap_or(DEFAULT_MONTHS);
+        let to_date = Utc::now().date_naive();
+        let from_date = to_date
+            .checked_sub_months(Months::new(months))
+            .and_then(|date| date.checked_add_days(Days::new(1)))
+            .unwrap_or(NaiveDate::MIN);
+
+        let transactions = self
+            .transaction_repository
+            .find_by_date_range(user_id, from_date, to_date, ReadPreference::Replica)
+            .await?;
+        let refunds = self.transaction_repository.find_received_refunds(user_id, from_date, to_date).await?;
+
+        let mut total_income = Decimal::ZERO;
+        let mut total_expenses = Decimal::ZERO;
+        for transaction in net_refunds(transactions, &refunds) {
+            match transaction.transaction_type.as_str() {
+                "income" => total_income += transaction.amount,
+                "expense" => total_expenses += transaction.amount,
+                _ => {}
+            }
+        }
+
+        // Every active personal pocket counts as money that could be reached in an emergency,
+        // converted into the base currency at today's rate
+        let base_currency = self
+            .currency_repository
+            .find_base_currency(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let mut liquid_balance = Decimal::ZERO;
+        for pocket in self.pocket_repository.find_by_user_id(user_id, false).await? {
+            let rate = exchange_rate_on(&self.currency_repository, &pocket.currency, &base_currency, to_date)
+                .await?
+                .ok_or_else(|| AppError::ValidationError(format!(
+                    "No exchange rate from {} to {} is available for {}",
+                    pocket.currency, base_currency, to_date
+                )))?;
+            liquid_balance += pocket.balance * rate;
+        }
+        let liquid_balance = liquid_balance.round_dp(currency_scale(&base_currency));
+
+        let budgets = self.budget_repository.get_budget_performance(user_id).await?;
+        let budgets_kept = budgets.iter().filter(|(budget, spent)| *spent <= budget.target_amount).count();
+
+        let hundred = Decimal::ONE_HUNDRED;
+
+        let savings_rate = (total_income > Decimal::ZERO).then(|| (total_income - total_expenses) / total_income);
+        let expense_ratio = (total_income > Decimal::ZERO).then(|| total_expenses / total_income);
+        let adherence = (!budgets.is_empty()).then(|| Decimal::from(budgets_kept) / Decimal::from(budgets.len()));
+        let average_monthly_spend = total_expenses / Decimal::from(months);
+        let fund_months = (average_monthly_spend > Decimal::ZERO).then(|| liquid_balance.max(Decimal::ZERO) / average_monthly_spend);
+
+        let savings_rate = HealthMetric {
+            value: savings_rate.map(|rate| (rate * hundred).round_dp(2).to_string()),
+            score: savings_rate.map(|rate| linear_score(rate * hundred, Decimal::ZERO, Decimal::from(TARGET_SAVINGS_RATE_PERCENT))),
+            target: TARGET_SAVINGS_RATE_PERCENT.to_string(),
+        };
+        let expense_to_income_ratio = HealthMetric {
+            value: expense_ratio.map(|ratio| (ratio * hundred).round_dp(2).to_string()),
+            score: expense_ratio
+                .map(|ratio| linear_score(ratio * hundred, hundred, Decimal::from(TARGET_EXPENSE_RATIO_PERCENT))),
+            target: TARGET_EXPENSE_RATIO_PERCENT.to_string(),
+        };
+        let budget_adherence = HealthMetric {
+            value: adherence.map(|share| (share * hundred).round_dp(2).to_string()),
+            score: adherence.map(|share| linear_score(share * hundred, Decimal::ZERO, hundred)),
+            target: hundred.to_string(),
+        };
+        let emergency_fund_months = HealthMetric {
+            value: fund_months.map(|months| months.round_dp(1).to_string()),
+            score: fund_months.map(|months| linear_score(months, Decimal::ZERO, Decimal::from(TARGET_EMERGENCY_FUND_MONTHS))),
+            target: TARGET_EMERGENCY_FUND_MONTHS.to_string(),
+        };
+
+        let scores: Vec<u32> = [&savings_rate, &expense_to_income_ratio, &budget_adherence, &emergency_fund_months]
+            .iter()
+            .filter_map(|metric| metric.score)
+            .collect();
+        let score = (!scores.is_empty()).then(|| scores.iter().sum::<u32>() / scores.len() as u32);
+
+        Ok(FinancialHealthResponse {
+            from_date,
+            to_date,
+            score,
+            rating: score.map(|score| rating(score).to_string()),
+            total_income: total_income.to_string(),
+            total_expenses: total_expenses.to_string(),
+            liquid_balance: liquid_balance.to_string(),
+            savings_rate,
+            expense_to_income_ratio,
+            budget_adherence,
+            emergency_fund_months,
+        })
+    }
+}
+
+// 0 at `zero_at`, 100 at `full_at`, clamped in between; works in either direction
+fn linear_score(value: Decimal, zero_at: Decimal, full_at: Decimal) -> u32 {
+    let position = (value - zero_at) / (full_at - zero_at);
+    (position.clamp(Decimal::ZERO, Decimal::ONE) * Decimal::ONE_HUNDRED)
+        .round()
+        .to_u32()
+        .unwrap_or(0)
+}
+
+fn rating(score: u32) -> &'static str {
+    match score {
+        80.. => HEALTH_RATING_EXCELLENT,
+        60..=79 => HEALTH_RATING_GOOD,
+        40..=59 => HEALTH_RATING_FAIR,
+        _ => HEALTH_RATING_POOR,
+    }
+}
\ No newline at end of file
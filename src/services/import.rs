@@ -0,0 +1,161 @@
+use rust_decimal::Decimal;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::importers::{date_format_from_pattern, parse_transactions, ImportOptions};
+use crate::models::{CreateTransactionRequest, ImportTransactionsQuery, ImportTransactionsResponse, Money};
+use crate::repositories::{ImportCheckpointRepository, PocketRepository, TransactionRepository, UnitOfWork};
+use crate::utils::{AppError, codes, hash_token};
+use crate::policy::{Action, permits};
+
+const MAX_IMPORT_ROWS: usize = 5000;
+const IMPORT_BATCH_SIZE: usize = 250;
+const SATURATION_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_SATURATION_WAIT: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+pub struct ImportService<T: TransactionRepository, P: PocketRepository, C: ImportCheckpointRepository> {
+    transaction_repository: T,
+    pocket_repository: P,
+    checkpoint_repository: C,
+    unit_of_work: UnitOfWork,
+}
+
+impl<T: TransactionRepository, P: PocketRepository, C: ImportCheckpointRepository> ImportService<T, P, C> {
+    pub fn new(transaction_repository: T, pocket_repository: P, checkpoint_repository: C, unit_of_work: UnitOfWork) -> Self {
+        Self {
+            transaction_repository,
+            pocket_repository,
+            checkpoint_repository,
+            unit_of_work,
+        }
+    }
+
+    pub async fn import_transactions(&self, user_id: Uuid, query: ImportTransactionsQuery, content: String) -> Result<ImportTransactionsResponse, AppError> {
+        if let Some(account_id) = query.account_id {
+            let pocket = self
+                .pocket_repository
+                .find_by_id_for_user(account_id, user_id)
+                .await?
+                .filter(|pocket| permits(user_id, Action::Write, pocket))
+                .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
+            if pocket.archived {
+                return Err(AppError::ValidationError("Cannot import into an archived pocket".to_string()).with_code(codes::POCKET_ARCHIVED));
+            }
+        }
+
+        let options = import_options(&query)?;
+        let parsed = parse_transactions(&content, &options)?;
+        if parsed.rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::ValidationError(format!(
+                "A single import can contain at most {} rows",
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        let total_rows = parsed.rows.len();
+        let mut resumed_from = 0;
+
+        if total_rows > 0 {
+            let checkpoint = self
+                .checkpoint_repository
+                .find_or_create(user_id, &import_fingerprint(&query, &content), total_rows as i32)
+                .await?;
+            resumed_from = checkpoint.rows_committed as usize;
+            let mut committed = resumed_from;
+
+            // Each batch commits together with its checkpoint, so a retry after a failure
+            // picks up exactly where the last committed batch ended
+            for batch in parsed.rows[resumed_from..].chunks(IMPORT_BATCH_SIZE) {
+                self.wait_for_pool_capacity().await;
+
+                let mut txn = self.unit_of_work.begin().await?;
+                let mut balance_delta = Decimal::ZERO;
+
+                for row in batch {
+                    let request = CreateTransactionRequest {
+                        account_id: query.account_id,
+                        description: row.description.clone(),
+                        amount: Money::new(row.amount),
+                        category: row.category.clone(),
+                        transaction_type: row.transaction_type.clone(),
+                        transaction_date: row.date.format("%Y-%m-%d").to_string(),
+                        override_limit: true,
+                        pending: false,
+                        notes: None,
+                        metadata: None,
+                        currency: None,
+                    };
+                    let transaction = self.transaction_repository.create_with(txn.conn(), user_id, &request, None).await?;
+                    balance_delta += transaction.balance_effect();
+                }
+
+                if let Some(account_id) = query.account_id {
+                    self.pocket_repository
+                        .adjust_balance_with(txn.conn(), account_id, user_id, balance_delta)
+                        .await?;
+                }
+
+                let next = committed + batch.len();
+                let advanced = self
+                    .checkpoint_repository
+                    .advance_with(txn.conn(), checkpoint.id, committed as i32, next as i32, next == total_rows)
+                    .await?;
+                if !advanced {
+                    return Err(AppError::Conflict("This file is already being imported".to_string()));
+                }
+
+                txn.commit().await?;
+                committed = next;
+            }
+        }
+
+        Ok(ImportTransactionsResponse {
+            imported: total_rows - resumed_from,
+            resumed_from,
+            delimiter: parsed.delimiter.to_string(),
+            locale: parsed.locale,
+            reaggregation_job: None,
+        })
+    }
+
+    // Yields to interactive requests while the pool has no free connections.
+    // After the cap the batch goes ahead and waits on the pool like any other query.
+    async fn wait_for_pool_capacity(&self) {
+        let mut waited = Duration::ZERO;
+        while self.unit_of_work.is_saturated() && waited < MAX_SATURATION_WAIT {
+            tokio::time::sleep(SATURATION_BACKOFF).await;
+            waited += SATURATION_BACKOFF;
+        }
+    }
+}
+
+// Same file, target pocket and parsing options as an earlier upload means the same rows
+fn import_fingerprint(query: &ImportTransactionsQuery, content: &str) -> String {
+    hash_token(&format!(
+        "{:?}|{:?}|{:?}|{:?}|{:?}\n{}",
+        query.account_id, query.delimiter, query.decimal_separator, query.thousands_separator, query.date_format, content
+    ))
+}
+
+fn import_options(query: &ImportTransactionsQuery) -> Result<ImportOptions, AppError> {
+    Ok(ImportOptions {
+        delimiter: single_char(query.delimiter.as_deref(), "delimiter", &[",", ";", "\t", "tab"])?,
+        decimal_separator: single_char(query.decimal_separator.as_deref(), "decimal_separator", &[".", ","])?,
+        thousands_separator: single_char(query.thousands_separator.as_deref(), "thousands_separator", &[".", ",", " ", "'"])?,
+        date_format: query.date_format.as_deref().map(date_format_from_pattern).transpose()?,
+    })
+}
+
+fn single_char(value: Option<&str>, name: &str, allowed: &[&str]) -> Result<Option<char>, AppError> {
+    match value {
+        None => Ok(None),
+        Some("tab") if allowed.contains(&"tab") => Ok(Some('\t')),
+        Some(value) if allowed.contains(&value) => Ok(value.chars().next()),
+        Some(_) => Err(AppError::ValidationError(format!(
+            "{} must be one of: {}",
+            name,
+            allowed.iter().map(|a| format!("'{}'", a.escape_default())).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,300 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::config::InboundEmailConfig;
+use crate::importers::{parse_receipt_total, plain_text_from_mime, receipt_description, DEFAULT_CATEGORY};
+use crate::models::{
+    CreateTransactionRequest, InboundAddressResponse, InboundEmail, InboundEmailAction, InboundEmailAddress, InboundEmailOutcome,
+    MailgunInboundEmail, Money, SesNotification, SesVerdict, SnsMessage, SuggestCategoryRequest,
+    EMAIL_MESSAGE_ID_KEY, INBOUND_EMAIL_DUPLICATE, MONEY_SCALE,
+};
+use crate::repositories::{CategorizationRepository, InboundEmailRepository};
+use crate::services::CategorizationService;
+use crate::utils::{AppError, generate_token, hash_token};
+
+const ADDRESS_PREFIX: &str = "receipts-";
+const ADDRESS_TOKEN_LENGTH: usize = 24;
+// Older Mailgun signatures are refused so a captured request can't be replayed later
+const MAX_SIGNATURE_AGE_SECS: i64 = 15 * 60;
+const SES_RECEIVED: &str = "Received";
+
+#[derive(Clone)]
+pub struct InboundEmailService<I: InboundEmailRepository, C: CategorizationRepository> {
+    repository: I,
+    categorization_service: CategorizationService<C>,
+    config: InboundEmailConfig,
+}
+
+impl<I: InboundEmailRepository, C: CategorizationRepository> InboundEmailService<I, C> {
+    pub fn new(repository: I, categorization_service: CategorizationService<C>, config: InboundEmailConfig) -> Self {
+        Self {
+            repository,
+            categorization_service,
+            config,
+        }
+    }
+
+    pub async fn get_address(&self, user_id: Uuid) -> Result<InboundAddressResponse, AppError> {
+        let domain = self.domain()?;
+        let address = self
+            .repository
+            .find_address(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("No inbound address has been generated yet".to_string()))?;
+
+        Ok(address_response(address, domain))
+    }
+
+    // Replaces any previous address, which stops accepting mail at once
+    pub async fn generate_address(&self, user_id: Uuid) -> Result<InboundAddressResponse, AppError> {
+        let domain = self.domain()?;
+        let local_part = format!("{}{}", ADDRESS_PREFIX, generate_token(ADDRESS_TOKEN_LENGTH).to_lowercase());
+        let address = self.repository.replace_address(user_id, &local_part).await?;
+
+        Ok(address_response(address, domain))
+    }
+
+    pub async fn receive_mailgun(&self, email: MailgunInboundEmail) -> Result<InboundEmailAction, AppError> {
+        let signing_key = self.provider_secret(self.config.mailgun_signing_key.as_deref())?;
+        verify_mailgun_signature(signing_key, &email)?;
+
+        let headers: Vec<(String, String)> = email
+            .message_headers
+            .as_deref()
+            .and_then(|headers| serde_json::from_str(headers).ok())
+            .unwrap_or_default();
+        let authenticated = mailgun_dkim_aligned(&headers, &email.from);
+
+        self.ingest(InboundEmail {
+            message_id: email.message_id.unwrap_or(email.token),
+            recipients: email.recipient.split(',').map(|recipient| recipient.trim().to_string()).collect(),
+            from: email.from,
+            subject: email.subject,
+            text: email.body_plain,
+            authenticated,
+        })
+        .await
+    }
+
+    // SNS can't sign with our secret, so the subscription URL carries it as basic auth credentials
+    pub async fn receive_ses(&self, authorization: Option<&str>, body: &str) -> Result<InboundEmailAction, AppError> {
+        let secret = self.provider_secret(self.config.ses_secret.as_deref())?;
+        let password = authorization
+            .and_then(|value| value.strip_prefix("Basic "))
+            .and_then(|encoded| STANDARD.decode(encoded.trim()).ok())
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .and_then(|credentials| credentials.split_once(':').map(|(_, password)| password.to_string()))
+            .ok_or_else(|| AppError::Unauthorized("Missing webhook credentials".to_string()))?;
+        if hash_token(&password) != hash_token(secret) {
+            return Err(AppError::Unauthorized("Invalid webhook credentials".to_string()));
+        }
+
+        let envelope: SnsMessage = serde_json::from_str(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid SNS message: {}", e)))?;
+        match envelope.message_type.as_str() {
+            "Notification" => {}
+            "SubscriptionConfirmation" => {
+                info!(
+                    "SES inbound email subscription awaits confirmation at {}",
+                    envelope.subscribe_url.as_deref().unwrap_or("(no SubscribeURL)")
+                );
+                return Ok(rejected("Subscription confirmation logged for the operator"));
+            }
+            other => return Ok(rejected(format!("Ignored SNS message type {}", other))),
+        }
+
+        let notification: SesNotification = serde_json::from_str(&envelope.message)
+            .map_err(|e| AppError::BadRequest(format!("Invalid SES notification: {}", e)))?;
+        if notification.notification_type != SES_RECEIVED {
+            return Ok(rejected(format!(
+                "Ignored SES notification type {}",
+                notification.notification_type
+            )));
+        }
+        let Some(content) = notification.content else {
+            return Ok(rejected("The SES receipt rule must include the message content"));
+        };
+
+        // DMARC already requires the passing SPF or DKIM check to be aligned with From
+        let authenticated = notification
+            .receipt
+            .dmarc_verdict
+            .as_ref()
+            .is_some_and(|verdict: &SesVerdict| verdict.status.eq_ignore_ascii_case("pass"));
+        let headers = notification.mail.common_headers;
+
+        self.ingest(InboundEmail {
+            message_id: notification.mail.message_id,
+            recipients: notification.receipt.recipients,
+            from: headers.from.into_iter().next().unwrap_or_default(),
+            subject: headers.subject.unwrap_or_default(),
+            text: plain_text_from_mime(&content),
+            authenticated,
+        })
+        .await
+    }
+
+    async fn ingest(&self, email: InboundEmail) -> Result<InboundEmailAction, AppError> {
+        let domain = self.domain()?;
+        let Some(local_part) = email.recipients.iter().find_map(|recipient| {
+            let (local, host) = mailbox_address(recipient).rsplit_once('@')?;
+            host.eq_ignore_ascii_case(domain).then(|| local.to_lowercase())
+        }) else {
+            return Ok(rejected(format!("No recipient at {}", domain)));
+        };
+
+        let Some(owner) = self.repository.find_owner(&local_part).await? else {
+            return Ok(rejected("Unknown inbound address"));
+        };
+
+        // Only the owner's verified account address may forward receipts, and only in mail the
+        // provider could authenticate, since a From header alone is trivial to forge
+        if !mailbox_address(&email.from).eq_ignore_ascii_case(&owner.email) {
+            return Ok(rejected("Sender is not the address owner"));
+        }
+        if !email.authenticated {
+            return Ok(rejected("Sender failed DMARC and aligned DKIM checks"));
+        }
+
+        if self.repository.message_exists(owner.user_id, &email.message_id).await? {
+            return Ok(InboundEmailAction::Done(InboundEmailOutcome {
+                status: INBOUND_EMAIL_DUPLICATE,
+                transaction_id: None,
+                reason: None,
+                user_id: None,
+            }));
+        }
+
+        let Some(amount) = parse_receipt_total(&email.text) else {
+            return Ok(rejected("No total found in the receipt"));
+        };
+        let description = receipt_description(&email.subject);
+        let suggestion = self
+            .categorization_service
+            .suggest_category(
+                owner.user_id,
+                SuggestCategoryRequest {
+                    description: description.clone(),
+                    transaction_type: Some("expense".to_string()),
+                },
+            )
+            .await?
+            .suggestion;
+
+        // Pending with no pocket: the owner picks one and posts it once they've checked the total
+        let request = CreateTransactionRequest {
+            account_id: None,
+            description,
+            amount: Money::new(amount.round_dp(MONEY_SCALE)),
+            category: suggestion.map_or_else(|| DEFAULT_CATEGORY.to_string(), |suggestion| suggestion.category),
+            transaction_type: "expense".to_string(),
+            transaction_date: Utc::now().date_naive().format("%Y-%m-%d").to_string(),
+            override_limit: false,
+            pending: true,
+            notes: None,
+            metadata: Some(serde_json::json!({ "source": "email", EMAIL_MESSAGE_ID_KEY: email.message_id })),
+            currency: None,
+        };
+
+        Ok(InboundEmailAction::RecordExpense {
+            user_id: owner.user_id,
+            request: Box::new(request),
+        })
+    }
+
+    fn domain(&self) -> Result<&str, AppError> {
+        self.config
+            .domain
+            .as_deref()
+            .ok_or_else(|| AppError::NotFound("Email-in is not enabled".to_string()))
+    }
+
+    fn provider_secret<'a>(&self, secret: Option<&'a str>) -> Result<&'a str, AppError> {
+        self.domain()?;
+        secret.ok_or_else(|| AppError::NotFound("This email provider is not configured".to_string()))
+    }
+}
+
+fn address_response(address: InboundEmailAddress, domain: &str) -> InboundAddressResponse {
+    InboundAddressResponse {
+        address: format!("{}@{}", address.local_part, domain),
+        created_at: address.created_at,
+    }
+}
+
+// Mailgun signs the timestamp followed by the token with the webhook signing key
+fn verify_mailgun_signature(signing_key: &str, email: &MailgunInboundEmail) -> Result<(), AppError> {
+    let invalid = || AppError::Unauthorized("Invalid webhook signature".to_string());
+
+    let timestamp: i64 = email.timestamp.parse().map_err(|_| invalid())?;
+    if (Utc::now().timestamp() - timestamp).abs() > MAX_SIGNATURE_AGE_SECS {
+        return Err(invalid());
+    }
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key.as_bytes())
+        .map_err(|e| AppError::InternalServerError(format!("Invalid signing key: {}", e)))?;
+    mac.update(email.timestamp.as_bytes());
+    mac.update(email.token.as_bytes());
+    let expected = format!("{:x}", mac.finalize().into_bytes());
+
+    if hash_token(&expected) != hash_token(&email.signature.to_lowercase()) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn rejected(reason: impl Into<String>) -> InboundEmailAction {
+    InboundEmailAction::Done(InboundEmailOutcome::rejected(reason))
+}
+
+// Mailgun reports no DMARC result, so its DKIM pass only counts when every signature is the From
+// domain's own. SPF checks the envelope sender, which needn't match From at all.
+fn mailgun_dkim_aligned(headers: &[(String, String)], from: &str) -> bool {
+    let values = |name: &str| {
+        headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+            .collect::<Vec<_>>()
+    };
+    let from_domain = sender_domain(from);
+    let signers = values("DKIM-Signature");
+
+    values("X-Mailgun-Dkim-Check-Result").iter().any(|value| value.eq_ignore_ascii_case("pass"))
+        && !signers.is_empty()
+        && signers
+            .iter()
+            .all(|signature| dkim_signing_domain(signature).is_some_and(|signer| aligned(from_domain, signer)))
+}
+
+// The d= tag of a DKIM-Signature header
+fn dkim_signing_domain(signature: &str) -> Option<&str> {
+    signature
+        .split(';')
+        .filter_map(|tag| tag.split_once('='))
+        .find(|(name, _)| name.trim() == "d")
+        .map(|(_, value)| value.trim())
+}
+
+fn sender_domain(mailbox: &str) -> &str {
+    mailbox_address(mailbox).rsplit_once('@').map_or("", |(_, domain)| domain)
+}
+
+// Relaxed alignment: the signer is the From domain or one of its parents
+fn aligned(from_domain: &str, signer: &str) -> bool {
+    let from_domain = from_domain.to_ascii_lowercase();
+    let signer = signer.to_ascii_lowercase();
+    signer.contains('.') && (from_domain == signer || from_domain.ends_with(&format!(".{}", signer)))
+}
+
+// The bare address from a mailbox like "Jane <jane@example.com>"
+fn mailbox_address(mailbox: &str) -> &str {
+    match (mailbox.rfind('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => mailbox[start + 1..end].trim(),
+        _ => mailbox.trim(),
+    }
+}
\ No newline at end of file
@@ -1,29 +1,62 @@
 use crate::models::{
     IncomeSummaryResponse, IncomeCategorySummaryResponse, IncomeCategorySummaryItem,
     IncomeTrendResponse, IncomeTrendItem, RecentIncomeTransactionsResponse, RecentIncomeTransactionItem,
-    IncomeDateRangeQuery, IncomeRecentTransactionsQuery
+    IncomeDateRangeQuery, IncomeRecentTransactionsQuery, Transaction, MonthlyCategoryTotal, net_refunds
 };
-use crate::repositories::TransactionRepository;
-use crate::utils::AppError;
+use crate::repositories::{TransactionRepository, MonthlyAggregateRepository, ReadPreference};
+use crate::utils::{AppError, codes};
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use tracing::info;
 
 #[derive(Clone)]
-pub struct IncomeAnalyticsService<T>
+pub struct IncomeAnalyticsService<T, A>
 where
     T: TransactionRepository,
+    A: MonthlyAggregateRepository,
 {
     transaction_repository: T,
+    aggregate_repository: A,
 }
 
-impl<T> IncomeAnalyticsService<T>
+impl<T, A> IncomeAnalyticsService<T, A>
 where
     T: TransactionRepository,
+    A: MonthlyAggregateRepository,
 {
-    pub fn new(transaction_repository: T) -> Self {
-        Self { transaction_repository }
+    pub fn new(transaction_repository: T, aggregate_repository: A) -> Self {
+        Self { transaction_repository, aggregate_repository }
+    }
+
+    // Per month and category totals for the range from the materialized aggregates
+    async fn load_totals(
+        &self,
+        user_id: uuid::Uuid,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+    ) -> Result<Vec<MonthlyCategoryTotal>, AppError> {
+        self.aggregate_repository.refresh_stale(user_id).await?;
+        self.aggregate_repository
+            .find_totals(user_id, "income", from_date, to_date)
+            .await
+    }
+
+    // Posted transactions in the range with received refunds netted against their original expense
+    async fn load_transactions(
+        &self,
+        user_id: uuid::Uuid,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let transactions = self.transaction_repository
+            .find_by_date_range(user_id, from_date, to_date, ReadPreference::Replica)
+            .await?;
+        let refunds = self.transaction_repository
+            .find_received_refunds(user_id, from_date, to_date)
+            .await?;
+
+        Ok(net_refunds(transactions, &refunds))
     }
 
     pub async fn get_income_summary(
@@ -35,25 +68,17 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        // Get income transactions (positive amounts)
-        let transactions = self.transaction_repository
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
+        // Get income transactions
+        let transactions = self.load_transactions(user_id, from_date, to_date).await?;
 
         let income_transactions: Vec<_> = transactions
             .into_iter()
-            .filter(|t| t.amount > Decimal::ZERO)
+            .filter(|t| t.transaction_type == "income")
             .collect();
 
         let total_income = income_transactions
@@ -64,7 +89,7 @@ where
         let total_transactions = income_transactions.len() as i64;
 
         // Calculate days between dates
-        let days = (to_date.date_naive() - from_date.date_naive()).num_days() + 1;
+        let days = (to_date - from_date).num_days() + 1;
         let average_per_day = if days > 0 {
             total_income / Decimal::from(days)
         } else {
@@ -89,39 +114,23 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
-
-        // Get income transactions (positive amounts)
-        let transactions = self.transaction_repository
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        let income_transactions: Vec<_> = transactions
-            .into_iter()
-            .filter(|t| t.amount > Decimal::ZERO)
-            .collect();
+        let totals = self.load_totals(user_id, from_date, to_date).await?;
 
         // Group by category
         let mut category_totals: HashMap<String, (Decimal, i64)> = HashMap::new();
         let mut total_income = Decimal::ZERO;
 
-        for transaction in &income_transactions {
-            let amount = transaction.amount;
-            total_income += amount;
-            
-            let category = transaction.category.clone().unwrap_or_else(|| "Uncategorized".to_string());
-            let (current_amount, current_count) = category_totals.entry(category).or_insert((Decimal::ZERO, 0));
-            *current_amount += amount;
-            *current_count += 1;
+        for total in totals {
+            total_income += total.total_amount;
+
+            let (current_amount, current_count) = category_totals.entry(total.category).or_insert((Decimal::ZERO, 0));
+            *current_amount += total.total_amount;
+            *current_count += total.transaction_count;
         }
 
         // Convert to response format
@@ -162,37 +171,22 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        // Get income transactions (positive amounts)
-        let transactions = self.transaction_repository
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
-
-        let income_transactions: Vec<_> = transactions
-            .into_iter()
-            .filter(|t| t.amount > Decimal::ZERO)
-            .collect();
+        let totals = self.load_totals(user_id, from_date, to_date).await?;
 
         // Group by month
         let mut monthly_totals: HashMap<String, (Decimal, i64)> = HashMap::new();
 
-        for transaction in &income_transactions {
-            let month_key = transaction.transaction_date.format("%Y-%m").to_string();
-            let amount = transaction.amount;
-            
+        for total in totals {
+            let month_key = total.month.format("%Y-%m").to_string();
+
             let (current_amount, current_count) = monthly_totals.entry(month_key).or_insert((Decimal::ZERO, 0));
-            *current_amount += amount;
-            *current_count += 1;
+            *current_amount += total.total_amount;
+            *current_count += total.transaction_count;
         }
 
         // Convert to response format and sort by period
@@ -223,25 +217,17 @@ where
 
         // Parse dates
         let from_date = NaiveDate::parse_from_str(&query.from_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()))?
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid from_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
         let to_date = NaiveDate::parse_from_str(&query.to_date, "%Y-%m-%d")
-            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()))?
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+            .map_err(|_| AppError::BadRequest("Invalid to_date format".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
 
-        // Get income transactions (positive amounts)
-        let transactions = self.transaction_repository
-            .find_by_date_range(user_id, from_date, to_date)
-            .await?;
+        // Get income transactions
+        let transactions = self.load_transactions(user_id, from_date, to_date).await?;
 
         let income_transactions: Vec<_> = transactions
             .into_iter()
-            .filter(|t| t.amount > Decimal::ZERO)
+            .filter(|t| t.transaction_type == "income")
             .collect();
 
         // Group by day
@@ -291,16 +277,23 @@ where
             to_date: None,
             limit: Some(limit),
             page: Some(1),
+            categories: None,
+            account_id: None,
+            min_amount: None,
+            max_amount: None,
+            sort_by: None,
+            order: None,
+            status: Some(crate::models::TRANSACTION_STATUS_POSTED.to_string()),
         };
         
         let transactions = self.transaction_repository
-            .find_by_user_id(user_id, &query)
+            .find_by_user_id(user_id, &query, ReadPreference::Replica)
             .await?;
 
-        // Filter for income only (positive amounts) and convert to response format
+        // Filter for income only and convert to response format
         let income_transactions: Vec<RecentIncomeTransactionItem> = transactions
             .into_iter()
-            .filter(|t| t.amount > Decimal::ZERO)
+            .filter(|t| t.transaction_type == "income")
             .take(limit as usize)
             .map(|t| RecentIncomeTransactionItem {
                 id: t.id,
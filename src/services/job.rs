@@ -0,0 +1,35 @@
+use uuid::Uuid;
+
+use crate::models::JobResponse;
+use crate::repositories::JobRepository;
+use crate::utils::AppError;
+use crate::policy::{Action, permits};
+
+const RECENT_JOBS_LIMIT: i64 = 50;
+
+#[derive(Clone)]
+pub struct JobService<R: JobRepository> {
+    repository: R,
+}
+
+impl<R: JobRepository> JobService<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn get_job(&self, id: Uuid, user_id: Uuid) -> Result<JobResponse, AppError> {
+        let job = self
+            .repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|job| permits(user_id, Action::Read, job))
+            .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+
+        Ok(job.to_response())
+    }
+
+    pub async fn list_jobs(&self, user_id: Uuid) -> Result<Vec<JobResponse>, AppError> {
+        let jobs = self.repository.find_recent_by_user_id(user_id, RECENT_JOBS_LIMIT).await?;
+        Ok(jobs.into_iter().map(|job| job.to_response()).collect())
+    }
+}
\ No newline at end of file
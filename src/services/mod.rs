@@ -3,15 +3,83 @@ pub mod pocket;
 pub mod user;
 pub mod transaction;
 pub mod budget;
+pub mod budget_rollover;
+pub mod budget_alert;
 pub mod account_summary;
 pub mod expense_analytics;
 pub mod income_analytics;
+pub mod spending_limit;
+pub mod account_purge;
+pub mod export;
+pub mod session;
+pub mod status;
+pub mod categorization;
+pub mod pocket_import;
+pub mod job;
+pub mod reaggregation;
+pub mod import;
+pub mod pocket_adjustment;
+pub mod currency;
+pub mod audit;
+pub mod pending_transactions;
+pub mod transaction_archive;
+pub mod digest;
+pub mod share_token;
+pub mod report;
+pub mod reminder;
+pub mod refund;
+pub mod preference;
+pub mod export_link;
+pub mod analytics_feed;
+pub mod scheduler;
+pub mod organization;
+pub mod debt;
+pub mod subscription_analytics;
+pub mod anomaly;
+pub mod financial_health;
+pub mod inbound_email;
+pub mod chat_bot;
+pub mod push_notification;
 
 pub use auth::*;
 pub use pocket::*;
 pub use user::*;
 pub use transaction::*;
 pub use budget::*;
+pub use budget_rollover::*;
+pub use budget_alert::*;
 pub use account_summary::*;
 pub use expense_analytics::*;
-pub use income_analytics::*;
\ No newline at end of file
+pub use income_analytics::*;
+pub use spending_limit::*;
+pub use account_purge::*;
+pub use export::*;
+pub use session::*;
+pub use status::*;
+pub use categorization::*;
+pub use pocket_import::*;
+pub use job::*;
+pub use reaggregation::*;
+pub use import::*;
+pub use pocket_adjustment::*;
+pub use currency::*;
+pub use audit::*;
+pub use pending_transactions::*;
+pub use transaction_archive::*;
+pub use digest::*;
+pub use share_token::*;
+pub use report::*;
+pub use reminder::*;
+pub use refund::*;
+pub use preference::*;
+pub use export_link::*;
+pub use analytics_feed::*;
+pub use scheduler::*;
+pub use organization::*;
+pub use debt::*;
+pub use subscription_analytics::*;
+pub use anomaly::*;
+pub use financial_health::*;
+pub use inbound_email::*;
+pub use chat_bot::*;
+pub use push_notification::*;
\ No newline at end of file
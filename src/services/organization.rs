@@ -0,0 +1,142 @@
+use uuid::Uuid;
+
+use crate::models::{
+    AddOrganizationMemberRequest, CreateOrganizationRequest, OrganizationMember, OrganizationResponse,
+    UpdateOrganizationMemberRequest, UpdateOrganizationRequest, ORG_ROLE_ADMIN, ORG_ROLE_MEMBER, ORG_ROLE_OWNER,
+};
+use crate::repositories::{OrganizationRepository, UserRepository};
+use crate::utils::AppError;
+
+#[derive(Clone)]
+pub struct OrganizationService<O: OrganizationRepository, U: UserRepository> {
+    repository: O,
+    user_repository: U,
+}
+
+impl<O: OrganizationRepository, U: UserRepository> OrganizationService<O, U> {
+    pub fn new(repository: O, user_repository: U) -> Self {
+        Self {
+            repository,
+            user_repository,
+        }
+    }
+
+    // Non-members get 404 so organization ids can't be probed
+    pub async fn require_role(&self, organization_id: Uuid, user_id: Uuid, role: &str) -> Result<OrganizationMember, AppError> {
+        let member = self
+            .repository
+            .find_member(organization_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+        if !member.has_role(role) {
+            return Err(AppError::Forbidden(format!("This action requires the {} role", role)));
+        }
+
+        Ok(member)
+    }
+
+    pub async fn create_organization(&self, user_id: Uuid, request: CreateOrganizationRequest) -> Result<OrganizationResponse, AppError> {
+        let organization = self.repository.create(request.name.trim(), user_id).await?;
+        Ok(organization.to_response(ORG_ROLE_OWNER))
+    }
+
+    pub async fn list_organizations(&self, user_id: Uuid) -> Result<Vec<OrganizationResponse>, AppError> {
+        let organizations = self.repository.find_for_user(user_id).await?;
+        Ok(organizations
+            .into_iter()
+            .map(|(organization, role)| organization.to_response(&role))
+            .collect())
+    }
+
+    pub async fn get_organization(&self, organization_id: Uuid, user_id: Uuid) -> Result<OrganizationResponse, AppError> {
+        let member = self.require_role(organization_id, user_id, ORG_ROLE_MEMBER).await?;
+        let organization = self
+            .repository
+            .find_by_id(organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+        Ok(organization.to_response(&member.role))
+    }
+
+    pub async fn update_organization(&self, organization_id: Uuid, user_id: Uuid, request: UpdateOrganizationRequest) -> Result<OrganizationResponse, AppError> {
+        let member = self.require_role(organization_id, user_id, ORG_ROLE_OWNER).await?;
+        let organization = self.repository.update_name(organization_id, request.name.trim()).await?;
+        Ok(organization.to_response(&member.role))
+    }
+
+    pub async fn delete_organization(&self, organization_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        self.require_role(organization_id, user_id, ORG_ROLE_OWNER).await?;
+        self.repository.delete(organization_id).await
+    }
+
+    pub async fn list_members(&self, organization_id: Uuid, user_id: Uuid) -> Result<Vec<OrganizationMember>, AppError> {
+        self.require_role(organization_id, user_id, ORG_ROLE_MEMBER).await?;
+        self.repository.list_members(organization_id).await
+    }
+
+    // Admins manage members, but only owners can hand out ownership
+    pub async fn add_member(&self, organization_id: Uuid, user_id: Uuid, request: AddOrganizationMemberRequest) -> Result<OrganizationMember, AppError> {
+        let actor = self.require_role(organization_id, user_id, ORG_ROLE_ADMIN).await?;
+        Self::ensure_can_assign(&actor, &request.role)?;
+
+        // Unknown emails and existing members get the same answer, so members can't probe
+        // which addresses have accounts
+        let not_added = || AppError::Conflict("That account can't be added to this organization".to_string());
+        let user = self
+            .user_repository
+            .find_by_email(&request.email)
+            .await?
+            .ok_or_else(not_added)?;
+
+        match self.repository.add_member(organization_id, user.id, &request.role).await {
+            Err(AppError::Conflict(_)) => Err(not_added()),
+            result => result,
+        }
+    }
+
+    pub async fn update_member(&self, organization_id: Uuid, user_id: Uuid, member_id: Uuid, request: UpdateOrganizationMemberRequest) -> Result<OrganizationMember, AppError> {
+        let actor = self.require_role(organization_id, user_id, ORG_ROLE_ADMIN).await?;
+        let target = self.find_member(organization_id, member_id).await?;
+
+        Self::ensure_can_manage(&actor, &target)?;
+        Self::ensure_can_assign(&actor, &request.role)?;
+
+        self.repository.update_member_role(organization_id, member_id, &request.role).await
+    }
+
+    // Any member may leave; removing someone else takes an admin
+    pub async fn remove_member(&self, organization_id: Uuid, user_id: Uuid, member_id: Uuid) -> Result<(), AppError> {
+        if member_id == user_id {
+            self.require_role(organization_id, user_id, ORG_ROLE_MEMBER).await?;
+        } else {
+            let actor = self.require_role(organization_id, user_id, ORG_ROLE_ADMIN).await?;
+            let target = self.find_member(organization_id, member_id).await?;
+            Self::ensure_can_manage(&actor, &target)?;
+        }
+
+        self.repository.remove_member(organization_id, member_id).await
+    }
+
+    async fn find_member(&self, organization_id: Uuid, member_id: Uuid) -> Result<OrganizationMember, AppError> {
+        self.repository
+            .find_member(organization_id, member_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))
+    }
+
+    fn ensure_can_assign(actor: &OrganizationMember, role: &str) -> Result<(), AppError> {
+        if role == ORG_ROLE_OWNER && actor.role != ORG_ROLE_OWNER {
+            return Err(AppError::Forbidden("Only owners can assign the owner role".to_string()));
+        }
+        Ok(())
+    }
+
+    fn ensure_can_manage(actor: &OrganizationMember, target: &OrganizationMember) -> Result<(), AppError> {
+        if target.role == ORG_ROLE_OWNER && actor.role != ORG_ROLE_OWNER {
+            return Err(AppError::Forbidden("Only owners can change another owner".to_string()));
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
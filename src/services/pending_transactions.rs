@@ -0,0 +1,54 @@
+use tracing::info;
+
+use crate::jobs::Schedule;
+use crate::models::TASK_PENDING_TRANSACTIONS;
+use crate::repositories::{CurrencyRepository, PocketRepository, SpendingLimitRepository, TransactionRepository};
+use crate::services::{ScheduledTask, TransactionService};
+use crate::utils::{AppError, CacheService, user_cache_key};
+
+pub struct PendingTransactionWorker<R: TransactionRepository, L: SpendingLimitRepository, P: PocketRepository, C: CurrencyRepository> {
+    transaction_service: TransactionService<R, L, P, C>,
+    cache: CacheService,
+    check_interval_secs: u64,
+}
+
+impl<R: TransactionRepository, L: SpendingLimitRepository, P: PocketRepository, C: CurrencyRepository> PendingTransactionWorker<R, L, P, C> {
+    pub fn new(transaction_service: TransactionService<R, L, P, C>, cache: CacheService, check_interval_secs: u64) -> Self {
+        Self {
+            transaction_service,
+            cache,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R, L, P, C> ScheduledTask for PendingTransactionWorker<R, L, P, C>
+where
+    R: TransactionRepository + 'static,
+    L: SpendingLimitRepository + 'static,
+    P: PocketRepository + 'static,
+    C: CurrencyRepository + 'static,
+{
+    fn name(&self) -> &'static str {
+        TASK_PENDING_TRANSACTIONS
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let user_ids = self.transaction_service.post_due_transactions().await?;
+        for user_id in &user_ids {
+            // Posting moves balances, so everything derived from them is stale
+            self.cache.delete(&user_cache_key(user_id)).await;
+            self.cache.invalidate_user_derived(user_id).await;
+        }
+        if !user_ids.is_empty() {
+            info!("Posted due pending transactions for {} users", user_ids.len());
+        }
+
+        Ok(user_ids.len() as u64)
+    }
+}
\ No newline at end of file
@@ -1,51 +1,152 @@
+use std::collections::HashSet;
 use uuid::Uuid;
 
-use crate::models::{PocketResponse, CreatePocketRequest, UpdatePocketRequest};
+use crate::models::{PocketResponse, PocketQuickBalance, BalanceRepairReport, CreatePocketRequest, UpdatePocketRequest, ReorderPocketsRequest, IfUnmodifiedSince, Precondition};
 use crate::repositories::PocketRepository;
-use crate::utils::AppError;
+use crate::utils::{AppError, EventBus, LiveAction, LiveResource};
+use crate::policy::{Action, permits, permits_as};
 
 #[derive(Clone)]
 pub struct PocketService<R: PocketRepository> {
     repository: R,
+    events: EventBus,
 }
 
 impl<R: PocketRepository> PocketService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, events: EventBus) -> Self {
+        Self { repository, events }
     }
 
     pub async fn get_pocket_by_id(&self, id: Uuid, user_id: Uuid) -> Result<PocketResponse, AppError> {
         let pocket = self
             .repository
-            .find_by_id(id)
+            .find_by_id_for_user(id, user_id)
             .await?
+            .filter(|pocket| permits(user_id, Action::Read, pocket))
             .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
 
-        // Check ownership
-        if pocket.user_id != user_id {
-            return Err(AppError::Forbidden("Access denied".to_string()));
-        }
-
         Ok(pocket.to_response())
     }
 
-    pub async fn get_user_pockets(&self, user_id: Uuid) -> Result<Vec<PocketResponse>, AppError> {
-        let pockets = self.repository.find_by_user_id(user_id).await?;
+    pub async fn get_user_pockets(&self, user_id: Uuid, include_archived: bool) -> Result<Vec<PocketResponse>, AppError> {
+        let pockets = self.repository.find_by_user_id(user_id, include_archived).await?;
         let pocket_responses = pockets.into_iter().map(|pocket| pocket.to_response()).collect();
         Ok(pocket_responses)
     }
 
+    pub async fn get_pockets_by_ids(&self, user_id: Uuid, ids: &[Uuid]) -> Result<Vec<PocketResponse>, AppError> {
+        let pockets = self.repository.find_by_ids(user_id, ids).await?;
+        Ok(pockets.into_iter().map(|pocket| pocket.to_response()).collect())
+    }
+
+    pub async fn get_quick_balances(&self, user_id: Uuid) -> Result<Vec<PocketQuickBalance>, AppError> {
+        self.repository.find_quick_balances(user_id).await
+    }
+
     pub async fn create_pocket(&self, user_id: Uuid, request: CreatePocketRequest) -> Result<PocketResponse, AppError> {
         let pocket = self.repository.create(user_id, &request).await?;
+        self.events.publish(user_id, LiveResource::Pocket, LiveAction::Created, pocket.id);
         Ok(pocket.to_response())
     }
 
-    pub async fn update_pocket(&self, id: Uuid, user_id: Uuid, request: UpdatePocketRequest) -> Result<PocketResponse, AppError> {
-        let pocket = self.repository.update(id, user_id, &request).await?;
+    // Callers check the organization role first
+    pub async fn get_organization_pockets(&self, organization_id: Uuid, include_archived: bool) -> Result<Vec<PocketResponse>, AppError> {
+        let pockets = self.repository.find_by_organization_id(organization_id, include_archived).await?;
+        Ok(pockets.into_iter().map(|pocket| pocket.to_response()).collect())
+    }
+
+    pub async fn create_organization_pocket(&self, organization_id: Uuid, user_id: Uuid, request: CreatePocketRequest) -> Result<PocketResponse, AppError> {
+        let pocket = self.repository.create_in_organization(organization_id, user_id, &request).await?;
+        self.events.publish(user_id, LiveResource::Pocket, LiveAction::Created, pocket.id);
         Ok(pocket.to_response())
     }
 
-    pub async fn delete_pocket(&self, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
-        self.repository.delete(id, user_id).await
+    pub async fn update_pocket(&self, id: Uuid, user_id: Uuid, request: UpdatePocketRequest, unmodified_since: IfUnmodifiedSince) -> Result<PocketResponse, AppError> {
+        let precondition = Precondition::new(request.version, unmodified_since);
+        let pocket = self.repository.update(id, user_id, &request, &precondition).await?;
+        self.events.publish(user_id, LiveResource::Pocket, LiveAction::Updated, pocket.id);
+        Ok(pocket.to_response())
+    }
+
+    // Members see the pocket, so they are told they can't change it rather than that it's missing
+    pub async fn update_organization_pocket(&self, organization_id: Uuid, id: Uuid, user_id: Uuid, request: UpdatePocketRequest, unmodified_since: IfUnmodifiedSince) -> Result<PocketResponse, AppError> {
+        let (pocket, role) = self
+            .repository
+            .find_by_id_with_role(id, user_id)
+            .await?
+            .filter(|(pocket, role)| pocket.organization_id == Some(organization_id) && permits_as(user_id, role.as_deref(), Action::Read, pocket))
+            .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
+        if !permits_as(user_id, role.as_deref(), Action::Write, &pocket) {
+            return Err(AppError::Forbidden("Only organization admins can change shared pockets".to_string()));
+        }
+
+        self.update_pocket(id, user_id, request, unmodified_since).await
+    }
+
+    // The request must list every active pocket exactly once so no position is left ambiguous
+    pub async fn reorder_pockets(&self, user_id: Uuid, request: ReorderPocketsRequest) -> Result<Vec<PocketResponse>, AppError> {
+        let existing: HashSet<Uuid> = self
+            .repository
+            .find_by_user_id(user_id, false)
+            .await?
+            .into_iter()
+            .map(|pocket| pocket.id)
+            .collect();
+
+        let mut seen = HashSet::new();
+        for item in &request.pockets {
+            if !seen.insert(item.id) {
+                return Err(AppError::ValidationError(format!("Pocket {} is listed more than once", item.id)));
+            }
+            if !existing.contains(&item.id) {
+                return Err(AppError::NotFound(format!("Pocket {} not found", item.id)));
+            }
+        }
+
+        if seen.len() != existing.len() {
+            return Err(AppError::ValidationError("The list must contain all of your active pockets".to_string()));
+        }
+
+        let ids: Vec<Uuid> = request.pockets.iter().map(|item| item.id).collect();
+        let groups: Vec<Option<String>> = request
+            .pockets
+            .into_iter()
+            .map(|item| item.group.filter(|group| !group.trim().is_empty()))
+            .collect();
+        self.repository.reorder(user_id, &ids, &groups).await?;
+        for id in &ids {
+            self.events.publish(user_id, LiveResource::Pocket, LiveAction::Updated, id);
+        }
+
+        self.get_user_pockets(user_id, false).await
+    }
+
+    pub async fn set_pocket_archived(&self, id: Uuid, user_id: Uuid, archived: bool) -> Result<PocketResponse, AppError> {
+        let pocket = self.repository.set_archived(id, user_id, archived).await?;
+        self.events.publish(user_id, LiveResource::Pocket, LiveAction::Updated, pocket.id);
+        Ok(pocket.to_response())
+    }
+
+    pub async fn delete_pocket(&self, id: Uuid, user_id: Uuid, reassign_to: Option<Uuid>) -> Result<(), AppError> {
+        self.repository.delete(id, user_id, reassign_to).await?;
+
+        self.events.publish(user_id, LiveResource::Pocket, LiveAction::Deleted, id);
+        if let Some(target) = reassign_to {
+            self.events.publish(user_id, LiveResource::Pocket, LiveAction::Updated, target);
+        }
+        Ok(())
+    }
+
+    // Replays posted transactions over the stored balances; corrects drift unless dry_run
+    pub async fn repair_balances(&self, user_id: Option<Uuid>, dry_run: bool) -> Result<BalanceRepairReport, AppError> {
+        let report = self.repository.repair_balances(user_id, dry_run).await?;
+        if !dry_run {
+            for correction in &report.corrections {
+                if let Some(owner) = correction.owner() {
+                    self.events.publish(owner, LiveResource::Pocket, LiveAction::Updated, correction.pocket_id);
+                }
+            }
+        }
+        Ok(report)
     }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use chrono::{NaiveDate, Utc};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::{CreatePocketAdjustmentRequest, CreateTransactionRequest, Money, PocketAdjustmentResponse};
+use crate::repositories::{PocketRepository, TransactionRepository, UnitOfWork};
+use crate::utils::{AppError, DistributedLock, codes, pocket_balance_lock};
+
+const ADJUSTMENT_CATEGORY: &str = "Balance Adjustment";
+const BALANCE_LOCK_TTL: Duration = Duration::from_secs(30);
+const BALANCE_LOCK_WAIT: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct PocketAdjustmentService<P: PocketRepository, T: TransactionRepository> {
+    pocket_repository: P,
+    transaction_repository: T,
+    unit_of_work: UnitOfWork,
+    locks: DistributedLock,
+}
+
+impl<P: PocketRepository, T: TransactionRepository> PocketAdjustmentService<P, T> {
+    pub fn new(pocket_repository: P, transaction_repository: T, unit_of_work: UnitOfWork, locks: DistributedLock) -> Self {
+        Self {
+            pocket_repository,
+            transaction_repository,
+            unit_of_work,
+            locks,
+        }
+    }
+
+    // Every balance change is backed by an `adjustment` transaction, so the pocket's
+    // history still explains its balance after a manual correction
+    pub async fn adjust_balance(&self, pocket_id: Uuid, user_id: Uuid, request: CreatePocketAdjustmentRequest) -> Result<PocketAdjustmentResponse, AppError> {
+        let adjustment_date = match request.adjustment_date.as_deref() {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?,
+            None => Utc::now().date_naive(),
+        };
+
+        // Adjustments of one pocket queue here rather than on its row lock, so waiting
+        // requests on any instance don't each hold a pool connection
+        let guard = self.locks.acquire(&pocket_balance_lock(&pocket_id), BALANCE_LOCK_TTL, BALANCE_LOCK_WAIT).await?;
+
+        let mut txn = self.unit_of_work.begin().await?;
+
+        // Locked so a concurrent transaction can't move the balance between reading and adjusting it
+        let mut pocket = self
+            .pocket_repository
+            .find_by_id_for_update_with(txn.conn(), pocket_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
+
+        if pocket.archived {
+            return Err(AppError::ValidationError("Cannot adjust an archived pocket".to_string()).with_code(codes::POCKET_ARCHIVED));
+        }
+
+        let amount = request.amount.for_currency(&pocket.currency)?;
+        let delta = match request.mode.as_str() {
+            "set" => amount - pocket.balance,
+            _ => amount,
+        };
+        if delta.is_zero() {
+            return Err(AppError::ValidationError("Adjustment does not change the balance".to_string()));
+        }
+
+        let adjustment_request = CreateTransactionRequest {
+            account_id: Some(pocket.id),
+            description: request.reason,
+            amount: Money::new(delta),
+            category: ADJUSTMENT_CATEGORY.to_string(),
+            transaction_type: "adjustment".to_string(),
+            transaction_date: adjustment_date.format("%Y-%m-%d").to_string(),
+            override_limit: true,
+            pending: false,
+            notes: None,
+            metadata: None,
+            currency: None,
+        };
+        let adjustment = self
+            .transaction_repository
+            .create_with(txn.conn(), user_id, &adjustment_request, None)
+            .await?;
+        self.pocket_repository
+            .adjust_balance_with(txn.conn(), pocket.id, user_id, adjustment.balance_effect())
+            .await?;
+
+        txn.commit().await?;
+        guard.release().await;
+
+        pocket.balance += adjustment.balance_effect();
+        Ok(PocketAdjustmentResponse {
+            pocket: pocket.to_response(),
+            adjustment: adjustment.to_response(),
+        })
+    }
+}
\ No newline at end of file
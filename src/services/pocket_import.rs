@@ -0,0 +1,158 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::{
+    CreatePocketRequest, CreateTransactionRequest, ImportPocketItem, ImportPocketsRequest,
+    ImportPocketsResponse, ImportedPocket, Money,
+};
+use crate::repositories::{PocketRepository, TransactionRepository, UnitOfWork};
+use crate::utils::AppError;
+
+pub const OPENING_BALANCE_CATEGORY: &str = "Balance Adjustment";
+
+// An import item after parsing, so nothing is written until every pocket is valid
+struct ParsedPocket {
+    opening_balance: Decimal,
+    opening_date: NaiveDate,
+    history: Vec<(NaiveDate, Decimal)>,
+}
+
+#[derive(Clone)]
+pub struct PocketImportService<P: PocketRepository, T: TransactionRepository> {
+    pocket_repository: P,
+    transaction_repository: T,
+    unit_of_work: UnitOfWork,
+}
+
+impl<P: PocketRepository, T: TransactionRepository> PocketImportService<P, T> {
+    pub fn new(pocket_repository: P, transaction_repository: T, unit_of_work: UnitOfWork) -> Self {
+        Self {
+            pocket_repository,
+            transaction_repository,
+            unit_of_work,
+        }
+    }
+
+    // Creates every pocket with an opening adjustment entry and monthly snapshots, or nothing at all
+    pub async fn import_pockets(&self, user_id: Uuid, request: ImportPocketsRequest) -> Result<ImportPocketsResponse, AppError> {
+        let parsed = request
+            .pockets
+            .iter()
+            .map(parse_item)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut txn = self.unit_of_work.begin().await?;
+        let mut imported = Vec::with_capacity(parsed.len());
+
+        for (item, parsed) in request.pockets.into_iter().zip(parsed) {
+            let create_request = CreatePocketRequest {
+                name: item.name,
+                emoji: item.emoji,
+                group: None,
+                currency: None,
+            };
+            let mut pocket = self.pocket_repository.create_with(txn.conn(), user_id, &create_request).await?;
+
+            let mut adjustment_transaction_id = None;
+            if !parsed.opening_balance.is_zero() {
+                let adjustment = CreateTransactionRequest {
+                    account_id: Some(pocket.id),
+                    description: "Opening balance (imported)".to_string(),
+                    amount: Money::new(parsed.opening_balance.abs()),
+                    category: OPENING_BALANCE_CATEGORY.to_string(),
+                    transaction_type: if parsed.opening_balance.is_sign_negative() { "expense" } else { "income" }.to_string(),
+                    transaction_date: parsed.opening_date.format("%Y-%m-%d").to_string(),
+                    override_limit: true,
+                    pending: false,
+                    notes: None,
+                    metadata: None,
+                    currency: None,
+                };
+                let transaction = self.transaction_repository.create_with(txn.conn(), user_id, &adjustment, None).await?;
+                self.pocket_repository
+                    .adjust_balance_with(txn.conn(), pocket.id, user_id, transaction.balance_effect())
+                    .await?;
+
+                pocket.balance = transaction.balance_effect();
+                adjustment_transaction_id = Some(transaction.id);
+            }
+
+            let opening_month = first_of_month(parsed.opening_date);
+            let mut snapshots = Vec::with_capacity(parsed.history.len() + 1);
+            for (month, balance) in parsed.history.into_iter().chain(std::iter::once((opening_month, parsed.opening_balance))) {
+                let snapshot = self
+                    .pocket_repository
+                    .create_snapshot_with(txn.conn(), pocket.id, user_id, month, balance)
+                    .await?;
+                snapshots.push(snapshot.into());
+            }
+
+            imported.push(ImportedPocket {
+                pocket: pocket.to_response(),
+                adjustment_transaction_id,
+                snapshots,
+            });
+        }
+
+        txn.commit().await?;
+
+        Ok(ImportPocketsResponse {
+            pockets: imported,
+            reaggregation_job: None,
+        })
+    }
+}
+
+fn parse_item(item: &ImportPocketItem) -> Result<ParsedPocket, AppError> {
+    let opening_balance = Decimal::from_str(&item.opening_balance).map_err(|_| {
+        AppError::ValidationError(format!("Invalid opening balance for pocket '{}'", item.name))
+    })?;
+
+    let today = Utc::now().date_naive();
+    let opening_date = match item.opening_date.as_deref() {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError("Invalid opening date format. Use YYYY-MM-DD".to_string()))?,
+        None => today,
+    };
+    if opening_date > today {
+        return Err(AppError::ValidationError("Opening date cannot be in the future".to_string()));
+    }
+
+    let opening_month = first_of_month(opening_date);
+    let mut seen = HashSet::new();
+    let mut history = Vec::with_capacity(item.history.len());
+    for entry in &item.history {
+        let month = NaiveDate::parse_from_str(&format!("{}-01", entry.month), "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError("Invalid month format. Use YYYY-MM".to_string()))?;
+        if month >= opening_month {
+            return Err(AppError::ValidationError(format!(
+                "Historical balance for {} must be before the opening month of pocket '{}'",
+                entry.month, item.name
+            )));
+        }
+        if !seen.insert(month) {
+            return Err(AppError::ValidationError(format!(
+                "Duplicate historical balance for {} in pocket '{}'",
+                entry.month, item.name
+            )));
+        }
+
+        let balance = Decimal::from_str(&entry.balance)
+            .map_err(|_| AppError::ValidationError(format!("Invalid balance for {}", entry.month)))?;
+        history.push((month, balance));
+    }
+    history.sort_by_key(|(month, _)| *month);
+
+    Ok(ParsedPocket {
+        opening_balance,
+        opening_date,
+        history,
+    })
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
\ No newline at end of file
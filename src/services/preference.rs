@@ -0,0 +1,42 @@
+use uuid::Uuid;
+
+use crate::models::{MoneyFormatter, UpdatePreferencesRequest, UserPreferences};
+use crate::repositories::{CurrencyRepository, PreferenceRepository};
+use crate::utils::AppError;
+
+#[derive(Clone)]
+pub struct PreferenceService<P: PreferenceRepository, C: CurrencyRepository> {
+    repository: P,
+    currency_repository: C,
+}
+
+impl<P: PreferenceRepository, C: CurrencyRepository> PreferenceService<P, C> {
+    pub fn new(repository: P, currency_repository: C) -> Self {
+        Self {
+            repository,
+            currency_repository,
+        }
+    }
+
+    pub async fn get_preferences(&self, user_id: Uuid) -> Result<UserPreferences, AppError> {
+        self.repository
+            .find_by_user(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    pub async fn money_formatter(&self, user_id: Uuid) -> Result<MoneyFormatter, AppError> {
+        Ok(self.get_preferences(user_id).await?.money_formatter())
+    }
+
+    pub async fn update_preferences(&self, user_id: Uuid, request: UpdatePreferencesRequest) -> Result<UserPreferences, AppError> {
+        if !self.currency_repository.is_supported(&request.currency).await? {
+            return Err(AppError::ValidationError(format!(
+                "No exchange rates are available for {}",
+                request.currency
+            )));
+        }
+
+        self.repository.upsert(user_id, &request).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::models::{
+    DeviceResponse, MoneyFormatter, NotificationPreference, PushMessage, RegisterDeviceRequest, UpdateNotificationPreferencesRequest,
+    NOTIFICATION_BUDGET_ALERT, NOTIFICATION_KINDS, NOTIFICATION_LARGE_TRANSACTION,
+};
+use crate::repositories::{DeviceRepository, PreferenceRepository};
+use crate::utils::{AppError, LiveAction, LiveEvent, LiveResource, PushOutcome, PushSender};
+
+#[derive(Clone)]
+pub struct PushNotificationService<D: DeviceRepository, P: PreferenceRepository> {
+    repository: D,
+    preference_repository: P,
+    senders: Vec<Arc<dyn PushSender>>,
+}
+
+impl<D: DeviceRepository, P: PreferenceRepository> PushNotificationService<D, P> {
+    pub fn new(repository: D, preference_repository: P, senders: Vec<Arc<dyn PushSender>>) -> Self {
+        Self {
+            repository,
+            preference_repository,
+            senders,
+        }
+    }
+
+    // Devices are accepted even for a service that isn't configured, so they start receiving
+    // notifications as soon as it is
+    pub async fn register_device(&self, user_id: Uuid, request: RegisterDeviceRequest) -> Result<DeviceResponse, AppError> {
+        let device = self.repository.upsert(user_id, &request).await?;
+        Ok(DeviceResponse::from(device))
+    }
+
+    pub async fn list_devices(&self, user_id: Uuid) -> Result<Vec<DeviceResponse>, AppError> {
+        let devices = self.repository.find_by_user(user_id).await?;
+        Ok(devices.into_iter().map(DeviceResponse::from).collect())
+    }
+
+    pub async fn delete_device(&self, user_id: Uuid, id: i64) -> Result<(), AppError> {
+        if !self.repository.delete(user_id, id).await? {
+            return Err(AppError::NotFound("Device not found".to_string()));
+        }
+        Ok(())
+    }
+
+    // Every kind, with the ones the user never changed pushed by default
+    pub async fn get_preferences(&self, user_id: Uuid) -> Result<Vec<NotificationPreference>, AppError> {
+        let stored = self.repository.find_preferences(user_id).await?;
+        Ok(NOTIFICATION_KINDS
+            .iter()
+            .map(|kind| NotificationPreference {
+                kind: kind.to_string(),
+                push: stored.iter().find(|preference| preference.kind == *kind).is_none_or(|preference| preference.push),
+            })
+            .collect())
+    }
+
+    pub async fn update_preferences(&self, user_id: Uuid, request: UpdateNotificationPreferencesRequest) -> Result<Vec<NotificationPreference>, AppError> {
+        self.repository.upsert_preferences(user_id, &request.preferences).await?;
+        self.get_preferences(user_id).await
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.senders.is_empty()
+    }
+
+    // Pushes the event to the owner's phones when it is something they asked to be told about,
+    // returning how many devices it reached
+    pub async fn deliver(&self, event: &LiveEvent) -> Result<usize, AppError> {
+        let kind = match (event.resource, event.action) {
+            (LiveResource::BudgetAlert, LiveAction::Created) => NOTIFICATION_BUDGET_ALERT,
+            (LiveResource::Anomaly, LiveAction::Created) => NOTIFICATION_LARGE_TRANSACTION,
+            _ => return Ok(0),
+        };
+
+        let preferences = self.get_preferences(event.user_id).await?;
+        if !preferences.iter().any(|preference| preference.kind == kind && preference.push) {
+            return Ok(0);
+        }
+        let devices = self.repository.find_by_user(event.user_id).await?;
+        if devices.is_empty() {
+            return Ok(0);
+        }
+        let Some(message) = self.message(kind, event).await? else {
+            return Ok(0);
+        };
+
+        let mut delivered = 0;
+        for device in devices {
+            let Some(sender) = self.senders.iter().find(|sender| sender.provider() == device.provider) else {
+                continue;
+            };
+
+            match sender.send(&device.token, &message).await {
+                Ok(PushOutcome::Delivered) => delivered += 1,
+                Ok(PushOutcome::InvalidToken) => {
+                    info!("Forgetting {} device {} after its token was rejected", device.provider, device.id);
+                    self.repository.delete_by_token(&device.token).await?;
+                }
+                Err(e) => warn!("Push to {} device {} failed: {}", device.provider, device.id, e),
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    // None once the budget or transaction is gone
+    async fn message(&self, kind: &'static str, event: &LiveEvent) -> Result<Option<PushMessage>, AppError> {
+        let id: i64 = event
+            .id
+            .parse()
+            .map_err(|_| AppError::InternalServerError(format!("Unexpected {} id {}", kind, event.id)))?;
+
+        let message = if kind == NOTIFICATION_BUDGET_ALERT {
+            self.repository
+                .find_latest_budget_alert(event.user_id, id)
+                .await?
+                .map(|(category, threshold)| PushMessage {
+                    kind,
+                    title: "Budget alert".to_string(),
+                    body: match category {
+                        Some(category) => format!("You've used {}% of your {} budget.", threshold, category),
+                        None => format!("You've used {}% of your overall budget.", threshold),
+                    },
+                    resource_id: event.id.clone(),
+                })
+        } else {
+            let Some(summary) = self.repository.find_transaction_summary(event.user_id, id).await? else {
+                return Ok(None);
+            };
+            let body = if summary.hide_balance {
+                format!("{} is well above what you usually spend in this category.", summary.description)
+            } else {
+                let amount = self.money_formatter(event.user_id).await?.in_currency(&summary.currency).format(summary.amount);
+                format!("{} for {} is well above what you usually spend in this category.", amount, summary.description)
+            };
+            Some(PushMessage {
+                kind,
+                title: "Large transaction".to_string(),
+                body,
+                resource_id: event.id.clone(),
+            })
+        };
+        Ok(message)
+    }
+
+    // Amounts read the way the rest of the app shows them to this user
+    async fn money_formatter(&self, user_id: Uuid) -> Result<MoneyFormatter, AppError> {
+        self.preference_repository
+            .find_by_user(user_id)
+            .await?
+            .map(|preferences| preferences.money_formatter())
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+}
+
+// Pushes budget alerts and large-transaction warnings to phones as they are raised. Only alerts
+// raised by this instance are seen, and ones raised while it is behind are dropped.
+pub fn start_push_delivery<D: DeviceRepository + 'static, P: PreferenceRepository + 'static>(service: PushNotificationService<D, P>, mut events: broadcast::Receiver<LiveEvent>) {
+    if !service.is_enabled() {
+        return;
+    }
+
+    info!("Delivering push notifications through {} services", service.senders.len());
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Err(e) = service.deliver(&event).await {
+                        warn!("Push delivery for user {} failed: {}", event.user_id, e);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => warn!("Push delivery fell behind and skipped {} events", skipped),
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
\ No newline at end of file
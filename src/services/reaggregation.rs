@@ -0,0 +1,89 @@
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::models::{JobResponse, ReaggregationResult, JOB_TYPE_REAGGREGATE};
+use crate::repositories::{JobRepository, PocketRepository};
+use crate::utils::{AppError, CacheService, DistributedLock, SNAPSHOT_LOCK_TTL, SNAPSHOT_LOCK_WAIT, user_snapshots_lock};
+
+// Rebuilds everything derived from a user's transactions after a historical import.
+// The work runs in the background and is reported through the jobs API.
+#[derive(Clone)]
+pub struct ReaggregationService<J: JobRepository, P: PocketRepository> {
+    job_repository: J,
+    pocket_repository: P,
+    cache: CacheService,
+    locks: DistributedLock,
+}
+
+impl<J: JobRepository + 'static, P: PocketRepository + 'static> ReaggregationService<J, P> {
+    pub fn new(job_repository: J, pocket_repository: P, cache: CacheService) -> Self {
+        Self {
+            job_repository,
+            pocket_repository,
+            locks: DistributedLock::new(cache.clone()),
+            cache,
+        }
+    }
+
+    pub async fn enqueue(&self, user_id: Uuid) -> Result<JobResponse, AppError> {
+        let job = self.job_repository.create(user_id, JOB_TYPE_REAGGREGATE).await?;
+
+        let service = self.clone();
+        let job_id = job.id;
+        tokio::spawn(async move {
+            service.run(job_id, user_id).await;
+        });
+
+        Ok(job.to_response())
+    }
+
+    async fn run(&self, job_id: Uuid, user_id: Uuid) {
+        // Queues behind a restatement or an earlier reaggregation of the same user, on any instance
+        let guard = match self.locks.acquire(&user_snapshots_lock(&user_id), SNAPSHOT_LOCK_TTL, SNAPSHOT_LOCK_WAIT).await {
+            Ok(guard) => guard,
+            Err(e) => {
+                error!("Reaggregation job {} could not start: {}", job_id, e);
+                if let Err(e) = self.job_repository.mark_failed(job_id, &e.to_string()).await {
+                    error!("Failed to record outcome of reaggregation job {}: {}", job_id, e);
+                }
+                return;
+            }
+        };
+
+        if let Err(e) = self.job_repository.mark_running(job_id).await {
+            error!("Failed to start reaggregation job {}: {}", job_id, e);
+            return;
+        }
+
+        let outcome = match self.reaggregate(user_id).await {
+            Ok(result) => {
+                let result = serde_json::to_value(result).unwrap_or_default();
+                self.job_repository.mark_completed(job_id, result).await
+            }
+            Err(e) => {
+                error!("Reaggregation job {} failed: {}", job_id, e);
+                self.job_repository.mark_failed(job_id, &e.to_string()).await
+            }
+        };
+
+        guard.release().await;
+
+        match outcome {
+            Ok(()) => info!("Reaggregation job {} finished for user {}", job_id, user_id),
+            Err(e) => error!("Failed to record outcome of reaggregation job {}: {}", job_id, e),
+        }
+    }
+
+    async fn reaggregate(&self, user_id: Uuid) -> Result<ReaggregationResult, AppError> {
+        let snapshots_refreshed = self.pocket_repository.refresh_current_snapshots(user_id).await?;
+
+        // Budget performance and analytics are computed on read, so dropping
+        // their caches is enough for them to pick up the imported history
+        let cache_entries_invalidated = self.cache.invalidate_user_derived(&user_id).await;
+
+        Ok(ReaggregationResult {
+            snapshots_refreshed,
+            cache_entries_invalidated,
+        })
+    }
+}
\ No newline at end of file
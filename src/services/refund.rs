@@ -0,0 +1,122 @@
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::models::{
+    CreateRefundRequest, ListRefundsQuery, Money, ReceiveRefundRequest, RefundResponse, Transaction,
+    REFUND_STATUS_RECEIVED, REFUND_STATUS_REQUESTED, REFUND_STATUS_WRITTEN_OFF, TRANSACTION_STATUS_POSTED,
+};
+use crate::repositories::{RefundRepository, TransactionRepository};
+use crate::utils::AppError;
+use crate::policy::{Action, permits};
+
+#[derive(Clone)]
+pub struct RefundService<F: RefundRepository, T: TransactionRepository> {
+    repository: F,
+    transaction_repository: T,
+}
+
+impl<F: RefundRepository, T: TransactionRepository> RefundService<F, T> {
+    pub fn new(repository: F, transaction_repository: T) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+        }
+    }
+
+    pub async fn request_refund(&self, user_id: Uuid, transaction_id: i64, request: CreateRefundRequest) -> Result<RefundResponse, AppError> {
+        let original = self.owned_transaction(transaction_id, user_id).await?;
+        if original.transaction_type != "expense" || original.status != TRANSACTION_STATUS_POSTED {
+            return Err(AppError::ValidationError("Refunds can only be requested for posted expenses".to_string()));
+        }
+
+        let claimed = self.repository.claimed_amount(original.id, None).await?;
+        let available = original.amount.abs() - claimed;
+        if available <= Decimal::ZERO {
+            return Err(AppError::Conflict("The full amount of this expense is already claimed".to_string()));
+        }
+
+        let amount = request.amount.map(Money::amount).unwrap_or(available);
+        if amount <= Decimal::ZERO || amount > available {
+            return Err(AppError::ValidationError(format!("Amount must be greater than 0 and at most {}", available)));
+        }
+
+        let refund = self
+            .repository
+            .create(user_id, original.id, amount, request.note.as_deref())
+            .await?;
+
+        Ok(refund.into())
+    }
+
+    pub async fn list_for_transaction(&self, user_id: Uuid, transaction_id: i64) -> Result<Vec<RefundResponse>, AppError> {
+        self.owned_transaction(transaction_id, user_id).await?;
+
+        let refunds = self.repository.find_by_original(transaction_id, user_id).await?;
+        Ok(refunds.into_iter().map(RefundResponse::from).collect())
+    }
+
+    pub async fn list_refunds(&self, user_id: Uuid, query: ListRefundsQuery) -> Result<Vec<RefundResponse>, AppError> {
+        if let Some(status) = query.status.as_deref()
+            && ![REFUND_STATUS_REQUESTED, REFUND_STATUS_RECEIVED, REFUND_STATUS_WRITTEN_OFF].contains(&status)
+        {
+            return Err(AppError::ValidationError(
+                "Status must be one of 'requested', 'received' or 'written_off'".to_string(),
+            ));
+        }
+
+        let refunds = self.repository.find_by_user(user_id, query.status.as_deref()).await?;
+        Ok(refunds.into_iter().map(RefundResponse::from).collect())
+    }
+
+    // Links the income transaction the money came back as; a written-off refund can still arrive late
+    pub async fn receive_refund(&self, user_id: Uuid, id: i64, request: ReceiveRefundRequest) -> Result<RefundResponse, AppError> {
+        let refund = self
+            .repository
+            .find_by_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Refund not found".to_string()))?;
+        if refund.status == REFUND_STATUS_RECEIVED {
+            return Err(AppError::Conflict("Refund has already been received".to_string()));
+        }
+
+        let credit = self.owned_transaction(request.transaction_id, user_id).await?;
+        if credit.transaction_type != "income" || credit.status != TRANSACTION_STATUS_POSTED {
+            return Err(AppError::ValidationError("A refund must be received as a posted income transaction".to_string()));
+        }
+
+        let original = self.owned_transaction(refund.original_transaction_id, user_id).await?;
+        let claimed = self.repository.claimed_amount(original.id, Some(refund.id)).await?;
+        let available = original.amount.abs() - claimed;
+        let amount = credit.amount.abs();
+        if amount > available {
+            return Err(AppError::ValidationError(format!(
+                "Refund of {} exceeds the {} left unclaimed on the original expense",
+                amount, available
+            )));
+        }
+
+        let refund = self.repository.mark_received(id, user_id, credit.id, amount).await?;
+        Ok(refund.into())
+    }
+
+    pub async fn write_off_refund(&self, user_id: Uuid, id: i64) -> Result<RefundResponse, AppError> {
+        self.repository
+            .find_by_id(id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Refund not found".to_string()))?;
+
+        let refund = self.repository.mark_written_off(id, user_id).await?;
+        Ok(refund.into())
+    }
+
+    async fn owned_transaction(&self, id: i64, user_id: Uuid) -> Result<Transaction, AppError> {
+        let transaction = self
+            .transaction_repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|transaction| permits(user_id, Action::Write, transaction))
+            .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
+
+        Ok(transaction)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,170 @@
+use chrono::{NaiveDate, Utc};
+use serde_json::json;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::jobs::Schedule;
+use crate::models::{
+    CreateReminderRequest, DEFAULT_LOCALE, ReminderNotification, ReminderResponse, UpdateReminderRequest,
+    TASK_REMINDER_NOTIFICATIONS,
+};
+use crate::repositories::ReminderRepository;
+use crate::services::ScheduledTask;
+use crate::utils::{AppError, EMAIL_TEMPLATE_REMINDERS, Mailer};
+
+// Due reminders notified per query
+const REMINDER_BATCH_SIZE: i64 = 200;
+
+#[derive(Clone)]
+pub struct ReminderService<R: ReminderRepository> {
+    repository: R,
+    mailer: Mailer,
+}
+
+impl<R: ReminderRepository> ReminderService<R> {
+    pub fn new(repository: R, mailer: Mailer) -> Self {
+        Self { repository, mailer }
+    }
+
+    pub async fn create_reminder(&self, user_id: Uuid, transaction_id: i64, request: CreateReminderRequest) -> Result<ReminderResponse, AppError> {
+        let remind_on = parse_date(&request.remind_on)?;
+
+        let reminder = self
+            .repository
+            .create(user_id, transaction_id, &request.note, remind_on)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
+
+        Ok(reminder.into())
+    }
+
+    pub async fn list_for_transaction(&self, user_id: Uuid, transaction_id: i64) -> Result<Vec<ReminderResponse>, AppError> {
+        let reminders = self.repository.find_by_transaction(transaction_id, user_id).await?;
+        Ok(reminders.into_iter().map(ReminderResponse::from).collect())
+    }
+
+    pub async fn due_reminders(&self, user_id: Uuid) -> Result<Vec<ReminderResponse>, AppError> {
+        let reminders = self.repository.find_due(user_id, Utc::now().date_naive()).await?;
+        Ok(reminders.into_iter().map(ReminderResponse::from).collect())
+    }
+
+    pub async fn update_reminder(&self, id: i64, user_id: Uuid, request: UpdateReminderRequest) -> Result<ReminderResponse, AppError> {
+        let remind_on = request.remind_on.as_deref().map(parse_date).transpose()?;
+
+        let reminder = self
+            .repository
+            .update(id, user_id, request.note.as_deref(), remind_on, request.status.as_deref())
+            .await?
+            .ok_or_else(|| AppError::NotFound("Reminder not found".to_string()))?;
+
+        Ok(reminder.into())
+    }
+
+    pub async fn delete_reminder(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        if !self.repository.delete(id, user_id).await? {
+            return Err(AppError::NotFound("Reminder not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Emails each user their newly due reminders, returning how many reminders were notified
+    pub async fn notify_due_reminders(&self, today: NaiveDate) -> Result<usize, AppError> {
+        // Without email the reminders still show up through GET /reminders/due
+        if !self.mailer.is_enabled() {
+            return Ok(0);
+        }
+
+        let mut notified = 0;
+        loop {
+            let reminders = self.repository.find_unnotified_due(today, REMINDER_BATCH_SIZE).await?;
+            if reminders.is_empty() {
+                break;
+            }
+
+            // Rows are ordered by user, so each run of equal user ids becomes one email
+            for group in reminders.chunk_by(|a, b| a.user_id == b.user_id) {
+                if let Err(e) = self.send_notification(group).await {
+                    warn!("Reminder notification for user {} failed: {}", group[0].user_id, e);
+                }
+                // Marked even on failure so one bad address can't stall the queue
+                let ids: Vec<i64> = group.iter().map(|reminder| reminder.id).collect();
+                self.repository.mark_notified(&ids).await?;
+                notified += ids.len();
+            }
+
+            if (reminders.len() as i64) < REMINDER_BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(notified)
+    }
+
+    async fn send_notification(&self, reminders: &[ReminderNotification]) -> Result<(), AppError> {
+        let first = &reminders[0];
+        let items: Vec<serde_json::Value> = reminders
+            .iter()
+            .map(|reminder| {
+                json!({
+                    "note": reminder.note,
+                    "remind_on": reminder.remind_on,
+                    "description": reminder.transaction_description,
+                    "transaction_date": reminder.transaction_date,
+                    "amount": reminder.transaction_amount,
+                })
+            })
+            .collect();
+        let context = json!({
+            "name": first.user_name,
+            "single": reminders.len() == 1,
+            "count": reminders.len(),
+            "first_note": first.note,
+            "reminders": items,
+        });
+
+        let locale = first.user_locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        self.mailer
+            .send_template(&first.user_email, EMAIL_TEMPLATE_REMINDERS, locale, &context)
+            .await
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::ValidationError("Invalid remind_on format. Use YYYY-MM-DD".to_string()))
+}
+
+pub struct ReminderWorker<R: ReminderRepository> {
+    reminder_service: ReminderService<R>,
+    check_interval_secs: u64,
+}
+
+impl<R: ReminderRepository> ReminderWorker<R> {
+    pub fn new(reminder_service: ReminderService<R>, check_interval_secs: u64) -> Self {
+        Self {
+            reminder_service,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: ReminderRepository + 'static> ScheduledTask for ReminderWorker<R> {
+    fn name(&self) -> &'static str {
+        TASK_REMINDER_NOTIFICATIONS
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let notified = self.reminder_service.notify_due_reminders(Utc::now().date_naive()).await?;
+        if notified > 0 {
+            info!("Sent notifications for {} due reminders", notified);
+        }
+
+        Ok(notified as u64)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::exporters::render_monthly_report;
+use crate::models::{MonthlyReport, ReportCategory, ReportFile, TransactionResponse};
+use crate::repositories::{TransactionRepository, UserRepository, ReadPreference};
+use crate::utils::AppError;
+
+#[derive(Clone)]
+pub struct ReportService<T: TransactionRepository, U: UserRepository> {
+    transaction_repository: T,
+    user_repository: U,
+}
+
+impl<T: TransactionRepository, U: UserRepository> ReportService<T, U> {
+    pub fn new(transaction_repository: T, user_repository: U) -> Self {
+        Self {
+            transaction_repository,
+            user_repository,
+        }
+    }
+
+    pub async fn monthly_report(&self, user_id: Uuid, month: &str) -> Result<MonthlyReport, AppError> {
+        let start = parse_month(month)?;
+        let end = (start + Months::new(1)).pred_opt().unwrap_or(start);
+
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let transactions = self
+            .transaction_repository
+            .find_all_by_user_id(user_id, Some(start), Some(end), ReadPreference::Replica)
+            .await?;
+
+        let mut income = Decimal::ZERO;
+        let mut expenses = Decimal::ZERO;
+        let mut adjustments = Decimal::ZERO;
+        let mut categories: BTreeMap<(String, String), (Decimal, i64)> = BTreeMap::new();
+
+        for transaction in &transactions {
+            match transaction.transaction_type.as_str() {
+                "income" => income += transaction.amount.abs(),
+                "expense" => expenses += transaction.amount.abs(),
+                _ => adjustments += transaction.signed_amount(),
+            }
+
+            let category = transaction.category.clone().unwrap_or_else(|| "Uncategorized".to_string());
+            let entry = categories
+                .entry((transaction.transaction_type.clone(), category))
+                .or_insert((Decimal::ZERO, 0));
+            entry.0 += transaction.amount.abs();
+            entry.1 += 1;
+        }
+
+        // Largest amounts first within income, then expenses, then anything else
+        let mut categories: Vec<ReportCategory> = categories
+            .into_iter()
+            .map(|((transaction_type, category), (amount, transaction_count))| ReportCategory {
+                category,
+                transaction_type,
+                amount,
+                transaction_count,
+            })
+            .collect();
+        categories.sort_by(|a, b| {
+            type_rank(&a.transaction_type)
+                .cmp(&type_rank(&b.transaction_type))
+                .then(b.amount.cmp(&a.amount))
+        });
+
+        Ok(MonthlyReport {
+            month: start,
+            period_end: end,
+            owner_name: user.name,
+            currency: user.base_currency,
+            income,
+            expenses,
+            adjustments,
+            net: income - expenses + adjustments,
+            categories,
+            transactions: transactions.into_iter().map(TransactionResponse::from).collect(),
+            generated_at: Utc::now(),
+        })
+    }
+
+    pub async fn monthly_report_pdf(&self, user_id: Uuid, month: &str) -> Result<ReportFile, AppError> {
+        let report = self.monthly_report(user_id, month).await?;
+        let filename = format!("statement-{}.pdf", report.month.format("%Y-%m"));
+
+        // Rendering is CPU-bound and grows with the transaction count
+        let content = tokio::task::spawn_blocking(move || render_monthly_report(&report))
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Report rendering failed: {}", e)))?;
+
+        Ok(ReportFile {
+            filename,
+            content_type: "application/pdf",
+            content,
+        })
+    }
+}
+
+fn parse_month(month: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+        .map_err(|_| AppError::ValidationError("Invalid month format. Use YYYY-MM".to_string()))
+}
+
+fn type_rank(transaction_type: &str) -> u8 {
+    match transaction_type {
+        "income" => 0,
+        "expense" => 1,
+        _ => 2,
+    }
+}
\ No newline at end of file
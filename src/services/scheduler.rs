@@ -0,0 +1,234 @@
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::jobs::Schedule;
+use crate::models::{
+    ListTaskRunsQuery, TaskRun, TaskSummary, TASK_RUN_STATUS_FAILED, TASK_RUN_STATUS_SUCCEEDED,
+    TASK_TRIGGER_MANUAL, TASK_TRIGGER_SCHEDULE,
+};
+use crate::repositories::TaskRunRepository;
+use crate::utils::{AppError, CacheService, DistributedLock, LockGuard, codes, task_slot_key};
+
+// A run still marked running after this long belonged to a process that died mid-run
+const STALE_RUN_AFTER_SECS: i64 = 6 * 3600;
+
+// Slot markers only need to outlive clock skew between instances
+const SLOT_CLAIM_TTL_SECS: u64 = 3600;
+
+const DEFAULT_RUN_HISTORY_LIMIT: i64 = 20;
+const MAX_RUN_HISTORY_LIMIT: i64 = 100;
+
+// A unit of periodic background work. Runs must be safe to repeat, since an admin
+// can re-trigger one and a failed run is simply retried on the next tick.
+#[async_trait::async_trait]
+pub trait ScheduledTask: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn schedule(&self) -> Schedule;
+    // Returns how many items the run processed
+    async fn run(&self) -> Result<u64, AppError>;
+}
+
+#[derive(Clone)]
+pub struct SchedulerService<T: TaskRunRepository> {
+    repository: T,
+    cache: CacheService,
+    locks: DistributedLock,
+    tasks: Arc<Vec<Arc<dyn ScheduledTask>>>,
+    shutdown: Arc<watch::Sender<bool>>,
+    // Schedule loops and manual runs, awaited on shutdown so no run is cut off mid-write
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl<T: TaskRunRepository + 'static> SchedulerService<T> {
+    pub fn new(repository: T, cache: CacheService, tasks: Vec<Arc<dyn ScheduledTask>>) -> Self {
+        Self {
+            repository,
+            locks: DistributedLock::new(cache.clone()),
+            cache,
+            tasks: Arc::new(tasks),
+            shutdown: Arc::new(watch::channel(false).0),
+            handles: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn start(&self) {
+        for task in self.tasks.iter() {
+            info!("Scheduled task {} started ({})", task.name(), task.schedule());
+
+            let scheduler = self.clone();
+            let task = task.clone();
+            let mut shutdown = self.shutdown.subscribe();
+            self.track(tokio::spawn(async move {
+                let mut after = Utc::now();
+                loop {
+                    let Some(slot) = task.schedule().next_after(after) else {
+                        warn!("Task {} has no upcoming run, stopping its schedule", task.name());
+                        break;
+                    };
+                    let wait = (slot - Utc::now()).to_std().unwrap_or_default();
+                    tokio::select! {
+                        _ = tokio::time::sleep(wait) => {}
+                        _ = shutdown.changed() => break,
+                    }
+                    // A run that overran later slots fires once, not once per missed slot
+                    after = slot.max(Utc::now());
+
+                    if !scheduler.claim_slot(task.name(), slot).await {
+                        info!("Skipping task {} at {}, another instance claimed it", task.name(), slot);
+                        continue;
+                    }
+                    if let Err(e) = scheduler.execute(&task, TASK_TRIGGER_SCHEDULE).await {
+                        error!("Could not record run of task {}: {}", task.name(), e);
+                    }
+                }
+            }));
+        }
+    }
+
+    // Stops scheduling new runs and waits up to `grace` for in-flight ones to finish.
+    // Runs still going after that are left to the stale-run sweep.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.shutdown.send_replace(true);
+
+        let handles = std::mem::take(&mut *self.handles.lock().unwrap_or_else(|e| e.into_inner()));
+        let wait = async {
+            for handle in handles {
+                let _ = handle.await;
+            }
+        };
+        if tokio::time::timeout(grace, wait).await.is_err() {
+            warn!("Scheduler stopped with task runs still in flight");
+        } else {
+            info!("Scheduler stopped");
+        }
+    }
+
+    pub async fn list_tasks(&self) -> Result<Vec<TaskSummary>, AppError> {
+        let latest = self.repository.find_latest_per_task().await?;
+        let successes = self.repository.find_last_success_per_task().await?;
+
+        let summaries = self
+            .tasks
+            .iter()
+            .map(|task| TaskSummary {
+                name: task.name().to_string(),
+                schedule: task.schedule().to_string(),
+                next_run_at: task.schedule().next_after(Utc::now()),
+                last_run: latest.iter().find(|run| run.task_name == task.name()).cloned(),
+                last_success_at: successes
+                    .iter()
+                    .find(|(name, _)| name == task.name())
+                    .map(|(_, finished_at)| *finished_at),
+            })
+            .collect();
+
+        Ok(summaries)
+    }
+
+    pub async fn list_runs(&self, name: &str, query: ListTaskRunsQuery) -> Result<Vec<TaskRun>, AppError> {
+        let task = self.find_task(name)?;
+        let limit = query.limit.unwrap_or(DEFAULT_RUN_HISTORY_LIMIT).clamp(1, MAX_RUN_HISTORY_LIMIT);
+        self.repository.find_recent_by_task(task.name(), limit).await
+    }
+
+    // Starts a run right away and returns its record; the work itself continues in the background
+    pub async fn trigger(&self, name: &str) -> Result<TaskRun, AppError> {
+        let task = self.find_task(name)?.clone();
+        let already_running = || AppError::Conflict(format!("Task {} is already running", task.name())).with_code(codes::ALREADY_RUNNING);
+        let guard = self.lock_task(task.name()).await.ok_or_else(already_running)?;
+        let run = self
+            .begin(task.name(), TASK_TRIGGER_MANUAL)
+            .await?
+            .ok_or_else(already_running)?;
+
+        let scheduler = self.clone();
+        let run_id = run.id;
+        self.track(tokio::spawn(async move {
+            if let Err(e) = scheduler.complete(&task, run_id).await {
+                error!("Could not record run of task {}: {}", task.name(), e);
+            }
+            guard.release().await;
+        }));
+
+        Ok(run)
+    }
+
+    // Every instance wakes for the same slot; only the one that claims it runs the task.
+    // Without Redis there is no way to coordinate, so each instance runs its own slots.
+    async fn claim_slot(&self, name: &str, slot: DateTime<Utc>) -> bool {
+        self.cache
+            .claim(&task_slot_key(name, slot.timestamp()), SLOT_CLAIM_TTL_SECS)
+            .await
+            .unwrap_or(true)
+    }
+
+    // Held for a whole run, so a manual trigger on one instance never overlaps a run on another.
+    // A lock left by a crashed instance lapses along with its run record.
+    async fn lock_task(&self, name: &str) -> Option<LockGuard> {
+        self.locks
+            .try_acquire(&format!("task:{}", name), Duration::from_secs(STALE_RUN_AFTER_SECS as u64))
+            .await
+    }
+
+    fn track(&self, handle: JoinHandle<()>) {
+        let mut handles = self.handles.lock().unwrap_or_else(|e| e.into_inner());
+        handles.retain(|handle| !handle.is_finished());
+        handles.push(handle);
+    }
+
+    async fn execute(&self, task: &Arc<dyn ScheduledTask>, trigger: &str) -> Result<(), AppError> {
+        let Some(guard) = self.lock_task(task.name()).await else {
+            info!("Skipping task {}, another instance is running it", task.name());
+            return Ok(());
+        };
+
+        let result = match self.begin(task.name(), trigger).await? {
+            Some(run) => self.complete(task, run.id).await,
+            None => {
+                info!("Skipping task {}, a previous run is still in progress", task.name());
+                Ok(())
+            }
+        };
+        guard.release().await;
+        result
+    }
+
+    async fn begin(&self, name: &str, trigger: &str) -> Result<Option<TaskRun>, AppError> {
+        let stale_before = Utc::now() - chrono::Duration::seconds(STALE_RUN_AFTER_SECS);
+        let abandoned = self.repository.fail_stale(name, stale_before).await?;
+        if abandoned > 0 {
+            warn!("Marked {} abandoned runs of task {} as failed", abandoned, name);
+        }
+
+        self.repository.start(name, trigger).await
+    }
+
+    async fn complete(&self, task: &Arc<dyn ScheduledTask>, run_id: Uuid) -> Result<(), AppError> {
+        match task.run().await {
+            Ok(items) => {
+                let items = i32::try_from(items).unwrap_or(i32::MAX);
+                self.repository
+                    .finish(run_id, TASK_RUN_STATUS_SUCCEEDED, Some(items), None)
+                    .await
+            }
+            Err(e) => {
+                error!("Task {} failed: {}", task.name(), e);
+                self.repository
+                    .finish(run_id, TASK_RUN_STATUS_FAILED, None, Some(&e.to_string()))
+                    .await
+            }
+        }
+    }
+
+    fn find_task(&self, name: &str) -> Result<&Arc<dyn ScheduledTask>, AppError> {
+        self.tasks
+            .iter()
+            .find(|task| task.name() == name)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown task '{}'", name)))
+    }
+}
\ No newline at end of file
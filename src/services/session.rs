@@ -0,0 +1,134 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::config::REFRESH_TOKEN_TTL_DAYS;
+use crate::models::{Session, SessionResponse, ClientInfo};
+use crate::repositories::SessionRepository;
+use crate::utils::{AppError, generate_token, hash_token};
+use crate::policy::{Action, permits};
+
+const REFRESH_TOKEN_LENGTH: usize = 64;
+
+pub enum RefreshRotation {
+    Rotated { session: Session, refresh_token: String },
+    // A spent or foreign-device token was replayed and its family has been revoked
+    Compromised { session_id: Uuid, user_id: Uuid },
+}
+
+#[derive(Clone)]
+pub struct SessionService<R: SessionRepository> {
+    repository: R,
+    token_ttl: chrono::Duration,
+}
+
+impl<R: SessionRepository> SessionService<R> {
+    pub fn new(repository: R, token_ttl: chrono::Duration) -> Self {
+        Self { repository, token_ttl }
+    }
+
+    pub async fn start_session(&self, user_id: Uuid, client: &ClientInfo) -> Result<Session, AppError> {
+        // A session lives exactly as long as the token issued for it
+        let expires_at = Utc::now() + self.token_ttl;
+        self.repository.create(user_id, client, expires_at).await
+    }
+
+    pub async fn list_sessions(&self, user_id: Uuid, current_session_id: Uuid) -> Result<Vec<SessionResponse>, AppError> {
+        let sessions = self.repository.find_active_by_user_id(user_id).await?;
+        Ok(sessions
+            .into_iter()
+            .map(|session| session.to_response(current_session_id))
+            .collect())
+    }
+
+    // Confirms the session is still usable and records activity on it
+    pub async fn validate_session(&self, session_id: Uuid) -> Result<bool, AppError> {
+        self.repository.touch_if_active(session_id).await
+    }
+
+    pub async fn revoke_session(&self, session_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        self.repository
+            .find_by_id_for_user(session_id, user_id)
+            .await?
+            .filter(|session| permits(user_id, Action::Write, session))
+            .ok_or_else(|| AppError::NotFound("Session not found".to_string()))?;
+
+        if !self.repository.revoke(session_id, user_id).await? {
+            return Err(AppError::NotFound("Session already revoked".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn revoke_other_sessions(&self, user_id: Uuid, current_session_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        self.repository.revoke_all_except(user_id, Some(current_session_id)).await
+    }
+
+    pub async fn revoke_all_sessions(&self, user_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        self.repository.revoke_all_except(user_id, None).await
+    }
+
+    // Rejects every token issued before the given Unix time, on any session
+    pub async fn revoke_tokens_issued_before(&self, user_id: Uuid, revoked_before: i64) -> Result<(), AppError> {
+        let valid_after = chrono::DateTime::from_timestamp(revoked_before, 0)
+            .ok_or_else(|| AppError::InternalServerError("Invalid token cutoff".to_string()))?;
+        self.repository.set_tokens_valid_after(user_id, valid_after).await
+    }
+
+    // Unix time before which the user's tokens are rejected, 0 when there is no cutoff
+    pub async fn tokens_valid_after(&self, user_id: Uuid) -> Result<i64, AppError> {
+        let valid_after = self.repository.find_tokens_valid_after(user_id).await?;
+        Ok(valid_after.map(|at| at.timestamp()).unwrap_or(0))
+    }
+
+    pub async fn issue_refresh_token(&self, session: &Session, device_id: &str) -> Result<String, AppError> {
+        let token = generate_token(REFRESH_TOKEN_LENGTH);
+        let expires_at = Utc::now() + chrono::Duration::days(REFRESH_TOKEN_TTL_DAYS);
+        self.repository
+            .create_refresh_token(session, device_id, &hash_token(&token), expires_at)
+            .await?;
+        Ok(token)
+    }
+
+    // Exchanges a refresh token for its successor in the same family. Presenting a
+    // token that was already rotated, or from another device, revokes the family.
+    pub async fn rotate_refresh_token(&self, refresh_token: &str, device_id: Option<&str>) -> Result<RefreshRotation, AppError> {
+        let invalid = || AppError::Unauthorized("Invalid refresh token".to_string());
+
+        let token = self
+            .repository
+            .find_refresh_token_by_hash(&hash_token(refresh_token))
+            .await?
+            .ok_or_else(invalid)?;
+
+        if token.revoked_at.is_some() || token.expires_at <= Utc::now() {
+            return Err(invalid());
+        }
+
+        let session = self
+            .repository
+            .find_by_id_for_user(token.session_id, token.user_id)
+            .await?
+            .filter(|session| session.revoked_at.is_none())
+            .ok_or_else(invalid)?;
+
+        let compromised = device_id != Some(token.device_id.as_str())
+            || token.used_at.is_some()
+            || !self.repository.consume_refresh_token(token.id).await?;
+
+        if compromised {
+            self.repository.revoke_family(session.id).await?;
+            return Ok(RefreshRotation::Compromised {
+                session_id: session.id,
+                user_id: session.user_id,
+            });
+        }
+
+        // The new access token needs the session to outlive it
+        self.repository
+            .extend(session.id, Utc::now() + self.token_ttl)
+            .await?;
+        let refresh_token = self.issue_refresh_token(&session, &token.device_id).await?;
+
+        Ok(RefreshRotation::Rotated { session, refresh_token })
+    }
+}
\ No newline at end of file
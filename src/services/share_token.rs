@@ -0,0 +1,99 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::{CreateShareTokenRequest, CreatedShareTokenResponse, ShareTokenResponse, SharedPocketSummary};
+use crate::repositories::{PocketRepository, ShareTokenRepository};
+use crate::utils::{AppError, generate_token, hash_token};
+use crate::policy::{Action, permits};
+
+const SHARE_TOKEN_LENGTH: usize = 40;
+const MAX_ACTIVE_SHARE_TOKENS: i64 = 10;
+const SHARED_TREND_MONTHS: i64 = 12;
+
+#[derive(Clone)]
+pub struct ShareTokenService<S: ShareTokenRepository, P: PocketRepository> {
+    repository: S,
+    pocket_repository: P,
+}
+
+impl<S: ShareTokenRepository, P: PocketRepository> ShareTokenService<S, P> {
+    pub fn new(repository: S, pocket_repository: P) -> Self {
+        Self {
+            repository,
+            pocket_repository,
+        }
+    }
+
+    pub async fn create_token(&self, pocket_id: Uuid, user_id: Uuid, request: CreateShareTokenRequest) -> Result<CreatedShareTokenResponse, AppError> {
+        self.ensure_owner(pocket_id, user_id).await?;
+
+        if self.repository.count_active(pocket_id).await? >= MAX_ACTIVE_SHARE_TOKENS {
+            return Err(AppError::Conflict(format!(
+                "A pocket can have at most {} active share links",
+                MAX_ACTIVE_SHARE_TOKENS
+            )));
+        }
+
+        let token = generate_token(SHARE_TOKEN_LENGTH);
+        let expires_at = request.expires_in_days.map(|days| Utc::now() + Duration::days(days));
+        let share_token = self
+            .repository
+            .create(pocket_id, user_id, request.label.as_deref(), &hash_token(&token), expires_at)
+            .await?;
+
+        Ok(CreatedShareTokenResponse {
+            token,
+            share_token: share_token.into(),
+        })
+    }
+
+    pub async fn list_tokens(&self, pocket_id: Uuid, user_id: Uuid) -> Result<Vec<ShareTokenResponse>, AppError> {
+        self.ensure_owner(pocket_id, user_id).await?;
+
+        let tokens = self.repository.find_by_pocket(pocket_id, user_id).await?;
+        Ok(tokens.into_iter().map(ShareTokenResponse::from).collect())
+    }
+
+    pub async fn revoke_token(&self, pocket_id: Uuid, token_id: Uuid, user_id: Uuid) -> Result<ShareTokenResponse, AppError> {
+        let token = self.repository.revoke(token_id, pocket_id, user_id).await?;
+        Ok(token.into())
+    }
+
+    // Unknown, expired and revoked links all look the same to the caller
+    pub async fn shared_summary(&self, token: &str) -> Result<SharedPocketSummary, AppError> {
+        let not_found = || AppError::NotFound("Share link not found".to_string());
+
+        let share_token = self
+            .repository
+            .find_active_by_hash(&hash_token(token))
+            .await?
+            .ok_or_else(not_found)?;
+        let pocket = self
+            .pocket_repository
+            .find_by_id_for_user(share_token.pocket_id, share_token.user_id)
+            .await?
+            .ok_or_else(not_found)?;
+
+        let trend = self.repository.find_trend(pocket.id, SHARED_TREND_MONTHS).await?;
+        self.repository.touch(share_token.id).await?;
+
+        Ok(SharedPocketSummary {
+            name: pocket.name,
+            emoji: pocket.emoji,
+            balance: pocket.balance,
+            currency: pocket.currency,
+            trend,
+            as_of: Utc::now(),
+        })
+    }
+
+    async fn ensure_owner(&self, pocket_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        self.pocket_repository
+            .find_by_id_for_user(pocket_id, user_id)
+            .await?
+            .filter(|pocket| permits(user_id, Action::Write, pocket))
+            .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
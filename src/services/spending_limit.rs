@@ -0,0 +1,119 @@
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::{
+    SpendingLimitResponse, CreateSpendingLimitRequest, UpdateSpendingLimitRequest, SpendingLimitCheck, Money, Transaction,
+    TRANSACTION_STATUS_CANCELLED,
+};
+use crate::repositories::SpendingLimitRepository;
+use crate::utils::AppError;
+use crate::policy::{Action, permits};
+
+#[derive(Clone)]
+pub struct SpendingLimitService<R: SpendingLimitRepository> {
+    repository: R,
+}
+
+impl<R: SpendingLimitRepository> SpendingLimitService<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn get_spending_limit_by_id(&self, id: i64, user_id: Uuid) -> Result<SpendingLimitResponse, AppError> {
+        let limit = self
+            .repository
+            .find_by_id_for_user(id, user_id)
+            .await?
+            .filter(|limit| permits(user_id, Action::Read, limit))
+            .ok_or_else(|| AppError::NotFound("Spending limit not found".to_string()))?;
+
+        Ok(limit.to_response())
+    }
+
+    pub async fn list_spending_limits(&self, user_id: Uuid) -> Result<Vec<SpendingLimitResponse>, AppError> {
+        let limits = self.repository.find_by_user_id(user_id).await?;
+        Ok(limits.into_iter().map(|limit| limit.to_response()).collect())
+    }
+
+    pub async fn create_spending_limit(&self, user_id: Uuid, request: CreateSpendingLimitRequest) -> Result<SpendingLimitResponse, AppError> {
+        let monthly_limit = request.monthly_limit.amount();
+        let limit = self
+            .repository
+            .create(user_id, &request.category, monthly_limit, &request.enforcement)
+            .await?;
+        Ok(limit.to_response())
+    }
+
+    pub async fn update_spending_limit(&self, id: i64, user_id: Uuid, request: UpdateSpendingLimitRequest) -> Result<SpendingLimitResponse, AppError> {
+        let monthly_limit = request.monthly_limit.map(Money::amount);
+
+        let limit = self
+            .repository
+            .update(id, user_id, monthly_limit, request.enforcement.as_deref())
+            .await?;
+        Ok(limit.to_response())
+    }
+
+    pub async fn delete_spending_limit(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        self.repository.delete(id, user_id).await
+    }
+
+    // Checks whether recording `amount` as an expense on `date` would push the category over its
+    // monthly limit. `replacing` is the transaction being edited: what it already counts towards
+    // the month is set against the new amount, so edits that don't add spending always pass.
+    // The limit stays locked until `conn`'s transaction ends, so two expenses can't both pass
+    // against the same remaining headroom.
+    pub async fn check_expense_with(&self, conn: &mut PgConnection, user_id: Uuid, category: &str, amount: Decimal, date: NaiveDate, replacing: Option<&Transaction>) -> Result<SpendingLimitCheck, AppError> {
+        let limit = match self.repository.find_by_category_for_update_with(conn, user_id, category).await? {
+            Some(limit) => limit,
+            None => return Ok(SpendingLimitCheck::WithinLimit),
+        };
+
+        let (month_start, month_end) = month_bounds(date);
+        let replaced = replacing.filter(|transaction| {
+            transaction.transaction_type == "expense"
+                && transaction.category.as_deref() == Some(category)
+                && (month_start..=month_end).contains(&transaction.transaction_date)
+        });
+        if replaced.is_some_and(|transaction| amount.abs() <= transaction.amount.abs()) {
+            return Ok(SpendingLimitCheck::WithinLimit);
+        }
+
+        let spent = self
+            .repository
+            .get_spent_amount_with(conn, user_id, category, month_start, month_end)
+            .await?;
+
+        // Pending expenses count too, so posting a future-dated one can't slip past the limit
+        let already_counted = replaced
+            .filter(|transaction| transaction.status != TRANSACTION_STATUS_CANCELLED)
+            .map_or(Decimal::ZERO, |transaction| transaction.amount.abs());
+        let projected = spent - already_counted + amount.abs();
+        if projected <= limit.monthly_limit {
+            return Ok(SpendingLimitCheck::WithinLimit);
+        }
+
+        let message = format!(
+            "Monthly spending limit of {} for category '{}' exceeded ({} spent this month)",
+            limit.monthly_limit, category, projected
+        );
+
+        if limit.is_hard() {
+            Ok(SpendingLimitCheck::HardExceeded(message))
+        } else {
+            Ok(SpendingLimitCheck::SoftExceeded(message))
+        }
+    }
+}
+
+fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let start = NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap();
+    let next_month = if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1).unwrap()
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1).unwrap()
+    };
+    (start, next_month.pred_opt().unwrap())
+}
\ No newline at end of file
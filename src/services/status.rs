@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::models::{DependencySnapshot, DependencyStatus, ProbeCheck, ReadinessResponse, StatusResponse};
+use crate::utils::{ping_database, AppError, CacheService};
+
+const STATUS_SNAPSHOT_CACHE_KEY: &str = "status:dependencies";
+// Dependency checks are shared by every caller for this long
+const STATUS_SNAPSHOT_TTL_SECS: u64 = 15;
+const STATUS_RATE_LIMIT_PER_MINUTE: u64 = 30;
+const DATABASE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+const CACHE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+pub struct StatusService {
+    pool: PgPool,
+    cache: CacheService,
+    started_at: DateTime<Utc>,
+}
+
+impl StatusService {
+    pub fn new(pool: PgPool, cache: CacheService) -> Self {
+        Self {
+            pool,
+            cache,
+            started_at: Utc::now(),
+        }
+    }
+
+    // Fixed one-minute window per client; skipped entirely when Redis is unavailable
+    pub async fn check_rate_limit(&self, client_key: &str) -> Result<(), AppError> {
+        let key = format!("ratelimit:status:{}", client_key);
+        match self.cache.increment(&key, 60).await {
+            Some(count) if count > STATUS_RATE_LIMIT_PER_MINUTE => Err(AppError::TooManyRequests(
+                "Too many status requests, please slow down".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn get_status(&self) -> StatusResponse {
+        let snapshot = match self.cache.get::<DependencySnapshot>(STATUS_SNAPSHOT_CACHE_KEY).await {
+            Some(snapshot) => snapshot,
+            None => {
+                let snapshot = self.check_dependencies().await;
+                self.cache
+                    .set(STATUS_SNAPSHOT_CACHE_KEY, &snapshot, Some(STATUS_SNAPSHOT_TTL_SECS))
+                    .await;
+                snapshot
+            }
+        };
+
+        let notices: Vec<String> = snapshot
+            .dependencies
+            .iter()
+            .filter_map(|dependency| dependency.notice.clone())
+            .collect();
+        let degraded = snapshot.dependencies.iter().any(|dependency| !dependency.healthy);
+
+        StatusResponse {
+            status: if degraded { "degraded" } else { "operational" }.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: self.started_at,
+            uptime_seconds: (Utc::now() - self.started_at).num_seconds(),
+            checked_at: snapshot.checked_at,
+            dependencies: snapshot.dependencies,
+            notices,
+        }
+    }
+
+    // Probed fresh on every call so orchestrators see the current state. Redis is reported
+    // but not required: without it requests fall back to the in-process cache and Postgres.
+    pub async fn readiness(&self) -> ReadinessResponse {
+        let (database, cache) = tokio::join!(self.probe_database(), self.probe_cache());
+        let checks = vec![database, cache];
+        let ready = checks.iter().all(|check| !check.required || check.status == "up");
+
+        ReadinessResponse {
+            status: if ready { "ready" } else { "not_ready" }.to_string(),
+            checks,
+            checked_at: Utc::now(),
+        }
+    }
+
+    async fn probe_database(&self) -> ProbeCheck {
+        match ping_database(&self.pool, DATABASE_CHECK_TIMEOUT).await {
+            Ok(latency) => probe_up("database", true, latency),
+            Err(e) => {
+                warn!("Readiness check: database unavailable: {}", e);
+                probe_down("database", true, "unreachable")
+            }
+        }
+    }
+
+    async fn probe_cache(&self) -> ProbeCheck {
+        if !self.cache.is_enabled() {
+            return ProbeCheck {
+                name: "cache".to_string(),
+                status: "disabled".to_string(),
+                required: false,
+                latency_ms: None,
+                error: None,
+            };
+        }
+
+        let started = Instant::now();
+        match tokio::time::timeout(CACHE_CHECK_TIMEOUT, self.cache.ping()).await {
+            Ok(true) => probe_up("cache", false, started.elapsed()),
+            Ok(false) => probe_down("cache", false, "unreachable"),
+            Err(_) => probe_down("cache", false, "timed out"),
+        }
+    }
+
+    async fn check_dependencies(&self) -> DependencySnapshot {
+        let database_ok = ping_database(&self.pool, DATABASE_CHECK_TIMEOUT).await.is_ok();
+        let cache_ok = self.cache.ping().await;
+
+        DependencySnapshot {
+            dependencies: vec![
+                DependencyStatus {
+                    name: "database".to_string(),
+                    healthy: database_ok,
+                    notice: (!database_ok).then(|| {
+                        "We're having trouble reaching our database. Some features may be unavailable.".to_string()
+                    }),
+                },
+                DependencyStatus {
+                    name: "cache".to_string(),
+                    healthy: cache_ok,
+                    notice: (!cache_ok).then(|| {
+                        "Caching is unavailable. The app may respond more slowly than usual.".to_string()
+                    }),
+                },
+            ],
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+fn probe_up(name: &str, required: bool, latency: Duration) -> ProbeCheck {
+    ProbeCheck {
+        name: name.to_string(),
+        status: "up".to_string(),
+        required,
+        latency_ms: Some((latency.as_secs_f64() * 1000.0 * 100.0).round() / 100.0),
+        error: None,
+    }
+}
+
+fn probe_down(name: &str, required: bool, error: &str) -> ProbeCheck {
+    ProbeCheck {
+        name: name.to_string(),
+        status: "down".to_string(),
+        required,
+        latency_ms: None,
+        error: Some(error.to_string()),
+    }
+}
\ No newline at end of file
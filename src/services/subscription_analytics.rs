@@ -0,0 +1,237 @@
+use chrono::{Days, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::{
+    SubscriptionItem, SubscriptionsResponse, Transaction, CADENCE_MONTHLY, CADENCE_QUARTERLY, CADENCE_WEEKLY,
+    CADENCE_YEARLY,
+};
+use crate::repositories::{ReadPreference, TransactionRepository};
+use crate::utils::AppError;
+
+// Two years of history, so yearly charges show up twice and totals compare year over year
+const HISTORY_MONTHS: u32 = 24;
+// Charges of a subscription may drift this far from their typical amount (price changes, FX)
+const AMOUNT_TOLERANCE_PERCENT: i64 = 10;
+
+#[derive(Clone)]
+pub struct SubscriptionAnalyticsService<T: TransactionRepository> {
+    transaction_repo: T,
+}
+
+impl<T: TransactionRepository> SubscriptionAnalyticsService<T> {
+    pub fn new(transaction_repo: T) -> Self {
+        Self { transaction_repo }
+    }
+
+    pub async fn get_subscriptions(&self, user_id: Uuid) -> Result<SubscriptionsResponse, AppError> {
+        let today = Utc::now().date_naive();
+        let from_date = today.checked_sub_months(Months::new(HISTORY_MONTHS)).unwrap_or(NaiveDate::MIN);
+
+        let transactions = self
+            .transaction_repo
+            .find_by_date_range(user_id, from_date, today, ReadPreference::Replica)
+            .await?;
+
+        let subscriptions = detect_subscriptions(&transactions, today);
+        let total_monthly_cost: Decimal = subscriptions.iter().map(|s| s.monthly_cost).sum();
+
+        Ok(SubscriptionsResponse {
+            subscriptions: subscriptions.into_iter().map(SubscriptionItem::from).collect(),
+            total_monthly_cost: total_monthly_cost.round_dp(2).to_string(),
+            total_yearly_cost: (total_monthly_cost * Decimal::from(12)).round_dp(2).to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cadence {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl Cadence {
+    // Typical gap in days and how far a single gap may stray from it
+    fn window(self) -> (i64, i64) {
+        match self {
+            Self::Weekly => (7, 1),
+            Self::Monthly => (30, 4),
+            Self::Quarterly => (91, 7),
+            Self::Yearly => (365, 12),
+        }
+    }
+
+    fn from_gap(days: i64) -> Option<Self> {
+        [Self::Weekly, Self::Monthly, Self::Quarterly, Self::Yearly]
+            .into_iter()
+            .find(|cadence| cadence.matches(days))
+    }
+
+    fn matches(self, days: i64) -> bool {
+        let (typical, slack) = self.window();
+        (days - typical).abs() <= slack
+    }
+
+    // Yearly charges only have two years of history to show up in
+    fn min_charges(self) -> usize {
+        match self {
+            Self::Yearly => 2,
+            _ => 3,
+        }
+    }
+
+    fn next_after(self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Weekly => date.checked_add_days(Days::new(7)),
+            Self::Monthly => date.checked_add_months(Months::new(1)),
+            Self::Quarterly => date.checked_add_months(Months::new(3)),
+            Self::Yearly => date.checked_add_months(Months::new(12)),
+        }
+    }
+
+    fn monthly_factor(self) -> Decimal {
+        match self {
+            Self::Weekly => Decimal::from(52) / Decimal::from(12),
+            Self::Monthly => Decimal::ONE,
+            Self::Quarterly => Decimal::ONE / Decimal::from(3),
+            Self::Yearly => Decimal::ONE / Decimal::from(12),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Weekly => CADENCE_WEEKLY,
+            Self::Monthly => CADENCE_MONTHLY,
+            Self::Quarterly => CADENCE_QUARTERLY,
+            Self::Yearly => CADENCE_YEARLY,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DetectedSubscription {
+    pub merchant: String,
+    pub category: Option<String>,
+    pub cadence: Cadence,
+    pub amount: Decimal,
+    pub monthly_cost: Decimal,
+    pub charge_count: usize,
+    pub first_charge_date: NaiveDate,
+    pub last_charge_date: NaiveDate,
+    pub next_expected_charge: NaiveDate,
+    pub yearly_total: Decimal,
+    pub previous_yearly_total: Decimal,
+}
+
+// Groups expenses by merchant and keeps the ones charged at a regular cadence for a
+// steady amount. Merchants whose next charge is more than one period overdue are
+// treated as cancelled. Sorted by monthly cost, most expensive first.
+pub fn detect_subscriptions(transactions: &[Transaction], today: NaiveDate) -> Vec<DetectedSubscription> {
+    let mut by_merchant: HashMap<String, Vec<&Transaction>> = HashMap::new();
+    for transaction in transactions.iter().filter(|t| t.transaction_type == "expense") {
+        let key = merchant_key(&transaction.description);
+        if !key.is_empty() {
+            by_merchant.entry(key).or_default().push(transaction);
+        }
+    }
+
+    let year_ago = today.checked_sub_months(Months::new(12)).unwrap_or(NaiveDate::MIN);
+    let mut subscriptions: Vec<DetectedSubscription> = by_merchant
+        .into_values()
+        .filter_map(|mut charges| {
+            charges.sort_by_key(|t| (t.transaction_date, t.id));
+            detect(&charges, today, year_ago)
+        })
+        .collect();
+
+    subscriptions.sort_by(|a, b| b.monthly_cost.cmp(&a.monthly_cost).then_with(|| a.merchant.cmp(&b.merchant)));
+    subscriptions
+}
+
+fn detect(charges: &[&Transaction], today: NaiveDate, year_ago: NaiveDate) -> Option<DetectedSubscription> {
+    let first = charges.first()?;
+    let last = charges.last()?;
+
+    let gaps: Vec<i64> = charges
+        .windows(2)
+        .map(|pair| (pair[1].transaction_date - pair[0].transaction_date).num_days())
+        .collect();
+    let cadence = Cadence::from_gap(median(&gaps)?)?;
+
+    if charges.len() < cadence.min_charges() || !gaps.iter().all(|gap| cadence.matches(*gap)) {
+        return None;
+    }
+
+    let amounts: Vec<Decimal> = charges.iter().map(|t| t.amount.abs()).collect();
+    let typical = median(&amounts)?;
+    let tolerance = typical * Decimal::from(AMOUNT_TOLERANCE_PERCENT) / Decimal::from(100);
+    if amounts.iter().any(|amount| (*amount - typical).abs() > tolerance) {
+        return None;
+    }
+
+    let next_expected_charge = cadence.next_after(last.transaction_date)?;
+    let (typical_gap, _) = cadence.window();
+    if (today - next_expected_charge).num_days() > typical_gap {
+        return None;
+    }
+
+    let amount = last.amount.abs();
+    let (yearly_total, previous_yearly_total) = charges.iter().fold((Decimal::ZERO, Decimal::ZERO), |(current, previous), t| {
+        if t.transaction_date > year_ago {
+            (current + t.amount.abs(), previous)
+        } else {
+            (current, previous + t.amount.abs())
+        }
+    });
+
+    Some(DetectedSubscription {
+        merchant: last.description.trim().to_string(),
+        category: last.category.clone(),
+        cadence,
+        amount,
+        monthly_cost: (amount * cadence.monthly_factor()).round_dp(2),
+        charge_count: charges.len(),
+        first_charge_date: first.transaction_date,
+        last_charge_date: last.transaction_date,
+        next_expected_charge,
+        yearly_total,
+        previous_yearly_total,
+    })
+}
+
+// Card descriptors often carry a reference number that changes every charge
+fn merchant_key(description: &str) -> String {
+    description
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !word.chars().any(|c| c.is_ascii_digit()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn median<V: Copy + Ord>(values: &[V]) -> Option<V> {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    sorted.get(sorted.len() / 2).copied()
+}
+
+impl From<DetectedSubscription> for SubscriptionItem {
+    fn from(subscription: DetectedSubscription) -> Self {
+        Self {
+            merchant: subscription.merchant,
+            category: subscription.category,
+            cadence: subscription.cadence.as_str().to_string(),
+            amount: subscription.amount.to_string(),
+            monthly_cost: subscription.monthly_cost.to_string(),
+            charge_count: subscription.charge_count,
+            first_charge_date: subscription.first_charge_date,
+            last_charge_date: subscription.last_charge_date,
+            next_expected_charge: subscription.next_expected_charge,
+            yearly_total: subscription.yearly_total.to_string(),
+            previous_yearly_total: subscription.previous_yearly_total.to_string(),
+        }
+    }
+}
\ No newline at end of file
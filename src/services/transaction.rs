@@ -1,73 +1,101 @@
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::models::{
-    TransactionResponse, CreateTransactionRequest, UpdateTransactionRequest, 
-    ListTransactionsQuery, ListTransactionsResponse
+    Transaction, TransactionResponse, CreateTransactionRequest, UpdateTransactionRequest, 
+    ListTransactionsQuery, ListTransactionsResponse, CurrencyConversion, Pocket, SpendingLimitCheck, IfUnmodifiedSince, Precondition,
+    currency_scale,
+    TRANSACTION_STATUS_PENDING, TRANSACTION_STATUS_POSTED, TRANSACTION_STATUS_CANCELLED,
 };
-use crate::repositories::TransactionRepository;
-use crate::utils::AppError;
+use crate::repositories::{TransactionRepository, SpendingLimitRepository, PocketRepository, CurrencyRepository, UnitOfWork, TxnContext, ReadPreference};
+use crate::services::{SpendingLimitService, exchange_rate_on};
+use crate::utils::{AppError, codes, EventBus, LiveAction, LiveResource, validate_sort};
+use crate::policy::{Action, permits, permits_as};
+
+// Pending transactions posted per worker run; the rest wait for the next run
+const DUE_PENDING_BATCH_SIZE: i64 = 500;
 
 #[derive(Clone)]
-pub struct TransactionService<R: TransactionRepository> {
+pub struct TransactionService<R: TransactionRepository, L: SpendingLimitRepository, P: PocketRepository, C: CurrencyRepository> {
     repository: R,
+    pocket_repository: P,
+    currency_repository: C,
+    spending_limit_service: SpendingLimitService<L>,
+    unit_of_work: UnitOfWork,
+    events: EventBus,
 }
 
-impl<R: TransactionRepository> TransactionService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+impl<R: TransactionRepository, L: SpendingLimitRepository, P: PocketRepository, C: CurrencyRepository> TransactionService<R, L, P, C> {
+    pub fn new(repository: R, pocket_repository: P, currency_repository: C, spending_limit_service: SpendingLimitService<L>, unit_of_work: UnitOfWork, events: EventBus) -> Self {
+        Self {
+            repository,
+            pocket_repository,
+            currency_repository,
+            spending_limit_service,
+            unit_of_work,
+            events,
+        }
     }
 
     pub async fn get_transaction_by_id(&self, id: i64, user_id: Uuid) -> Result<TransactionResponse, AppError> {
         let transaction = self
             .repository
-            .find_by_id(id)
+            .find_by_id_for_user(id, user_id)
             .await?
+            .filter(|transaction| permits(user_id, Action::Read, transaction))
             .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
 
-        // Check if transaction belongs to user
-        if transaction.user_id != user_id {
-            return Err(AppError::Forbidden("Access denied".to_string()));
-        }
-
         Ok(transaction.to_response())
     }
 
     pub async fn list_transactions(&self, user_id: Uuid, query: ListTransactionsQuery) -> Result<ListTransactionsResponse, AppError> {
-        // Validate query parameters
-        if let Some(page) = query.page {
-            if page < 1 {
-                return Err(AppError::ValidationError("Page must be greater than 0".to_string()));
-            }
-        }
-
-        if let Some(limit) = query.limit {
-            if limit < 1 || limit > 100 {
-                return Err(AppError::ValidationError("Limit must be between 1 and 100".to_string()));
-            }
+        // Validate transaction type if provided
+        if let Some(ref transaction_type) = query.transaction_type
+            && transaction_type != "income" && transaction_type != "expense" {
+            return Err(AppError::invalid_field("transaction_type", "Transaction type must be 'income' or 'expense'"));
         }
 
-        // Validate transaction type if provided
-        if let Some(ref transaction_type) = query.transaction_type {
-            if transaction_type != "income" && transaction_type != "expense" {
-                return Err(AppError::ValidationError("Transaction type must be 'income' or 'expense'".to_string()));
-            }
+        if let Some(ref status) = query.status
+            && ![TRANSACTION_STATUS_PENDING, TRANSACTION_STATUS_POSTED, TRANSACTION_STATUS_CANCELLED].contains(&status.as_str())
+        {
+            return Err(AppError::invalid_field("status", "Status must be 'pending', 'posted' or 'cancelled'"));
         }
 
         // Validate date format if provided
         if let Some(ref from_date) = query.from_date {
             chrono::NaiveDate::parse_from_str(from_date, "%Y-%m-%d")
-                .map_err(|_| AppError::ValidationError("Invalid from_date format. Use YYYY-MM-DD".to_string()))?;
+                .map_err(|_| AppError::invalid_field("from_date", "Invalid from_date format. Use YYYY-MM-DD").with_code(codes::INVALID_DATE_FORMAT))?;
         }
 
         if let Some(ref to_date) = query.to_date {
             chrono::NaiveDate::parse_from_str(to_date, "%Y-%m-%d")
-                .map_err(|_| AppError::ValidationError("Invalid to_date format. Use YYYY-MM-DD".to_string()))?;
+                .map_err(|_| AppError::invalid_field("to_date", "Invalid to_date format. Use YYYY-MM-DD").with_code(codes::INVALID_DATE_FORMAT))?;
+        }
+
+        validate_sort(query.sort_by.as_deref(), query.order.as_deref())?;
+
+        // Validate amount range if provided
+        let min_amount = parse_amount_filter(query.min_amount.as_deref(), "min_amount")?;
+        let max_amount = parse_amount_filter(query.max_amount.as_deref(), "max_amount")?;
+        if let (Some(min), Some(max)) = (min_amount, max_amount) && min > max {
+            return Err(AppError::ValidationError("min_amount must not be greater than max_amount".to_string()).with_code(codes::INVALID_AMOUNT_RANGE));
+        }
+
+        // Only pockets the user can see work as a filter, it still lists their own transactions
+        if let Some(account_id) = query.account_id {
+            let pocket = self.pocket_repository.find_by_id_with_role(account_id, user_id).await?;
+            if pocket.is_none_or(|(pocket, role)| !permits_as(user_id, role.as_deref(), Action::Read, &pocket)) {
+                return Err(AppError::NotFound("Pocket not found".to_string()));
+            }
         }
 
-        let transactions = self.repository.find_by_user_id(user_id, &query).await?;
-        let total_items = self.repository.count_by_user_id(user_id, &query).await?;
+        let page = self.repository.find_page_by_user_id(user_id, &query, ReadPreference::Replica).await?;
 
-        let transaction_responses = transactions
+        let transaction_responses = page
+            .items
             .into_iter()
             .map(|transaction| transaction.to_response())
             .collect();
@@ -76,21 +104,291 @@ impl<R: TransactionRepository> TransactionService<R> {
             data: transaction_responses,
             page: query.page.unwrap_or(1),
             limit: query.limit.unwrap_or(20),
-            total_items,
+            total_items: page.total_items,
         })
     }
 
-    pub async fn create_transaction(&self, user_id: Uuid, request: CreateTransactionRequest) -> Result<TransactionResponse, AppError> {
-        let transaction = self.repository.create(user_id, &request).await?;
+    // Returns the created transaction plus a warning when a soft spending limit was exceeded
+    pub async fn create_transaction(&self, user_id: Uuid, request: CreateTransactionRequest) -> Result<(TransactionResponse, Option<String>), AppError> {
+        let mut warning = None;
+
+        if request.pending && !request.awaits_review() {
+            let transaction_date = NaiveDate::parse_from_str(&request.transaction_date, "%Y-%m-%d")
+                .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
+            if transaction_date <= Utc::now().date_naive() {
+                return Err(AppError::ValidationError("Only future-dated transactions can be pending".to_string()));
+            }
+        }
+
+        let pocket_currency = match request.account_id {
+            Some(account_id) => Some(self.ensure_pocket_accepts_transactions(account_id, user_id).await?.currency),
+            None => None,
+        };
+        let conversion = self.convert_amount(user_id, &request, pocket_currency.as_deref()).await?;
+
+        // The limit check, the transaction row and the pocket balance change commit together
+        let mut txn = self.unit_of_work.begin().await?;
+
+        if request.transaction_type == "expense" && !request.override_limit {
+            let amount = conversion.as_ref().map_or(request.amount.amount(), |conversion| conversion.converted_amount);
+            let transaction_date = NaiveDate::parse_from_str(&request.transaction_date, "%Y-%m-%d")
+                .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
+            warning = self.check_spending_limit(&mut txn, user_id, &request.category, amount, transaction_date, None).await?;
+        }
+
+        let transaction = self.repository.create_with(txn.conn(), user_id, &request, conversion.as_ref()).await?;
+        self.apply_to_pocket(&mut txn, transaction.account_id, user_id, transaction.balance_effect()).await?;
+        txn.commit().await?;
+
+        self.publish_change(user_id, LiveAction::Created, &transaction, None);
+        Ok((transaction.to_response(), warning))
+    }
+
+    // Like create_transaction, returns a warning when the edit takes spending over a soft limit
+    pub async fn update_transaction(&self, id: i64, user_id: Uuid, request: UpdateTransactionRequest, unmodified_since: IfUnmodifiedSince) -> Result<(TransactionResponse, Option<String>), AppError> {
+        let mut txn = self.unit_of_work.begin().await?;
+
+        // The row stays locked until commit, so checking it here is enough
+        let existing = self
+            .repository
+            .find_by_id_for_update_with(txn.conn(), id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Transaction not found or access denied".to_string()))?;
+        Precondition::new(request.version, unmodified_since).check(existing.updated_at)?;
+
+        if existing.status == TRANSACTION_STATUS_CANCELLED {
+            return Err(AppError::Conflict("Cancelled transactions cannot be edited".to_string()));
+        }
+
+        // Someone who left a shared pocket's organization can't move its balance any more, not
+        // even by taking their own transactions out of it
+        let current_pocket = match existing.account_id {
+            Some(pocket_id) => Some(self.writable_pocket(pocket_id, user_id).await?),
+            None => None,
+        };
+
+        if let Some(account_id) = request.account_id {
+            let pocket = match current_pocket {
+                Some(pocket) if pocket.id == account_id => pocket,
+                _ => self.ensure_pocket_accepts_transactions(account_id, user_id).await?,
+            };
+            request.amount.for_currency(&pocket.currency)?;
+        }
+
+        let mut warning = None;
+        if request.transaction_type == "expense" {
+            let transaction_date = NaiveDate::parse_from_str(&request.transaction_date, "%Y-%m-%d")
+                .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
+            warning = self
+                .check_spending_limit(&mut txn, user_id, &request.category, request.amount.amount(), transaction_date, Some(&existing))
+                .await?;
+        }
+
+        // Reverse the old effect before applying the new one, the pocket may have changed too
+        self.apply_to_pocket(&mut txn, existing.account_id, user_id, -existing.balance_effect()).await?;
+        let transaction = self.repository.update_with(txn.conn(), id, user_id, &request).await?;
+        self.apply_to_pocket(&mut txn, transaction.account_id, user_id, transaction.balance_effect()).await?;
+
+        txn.commit().await?;
+        self.publish_change(user_id, LiveAction::Updated, &transaction, existing.account_id);
+        Ok((transaction.to_response(), warning))
+    }
+
+    pub async fn delete_transaction(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
+        let mut txn = self.unit_of_work.begin().await?;
+
+        let existing = self
+            .repository
+            .find_by_id_for_update_with(txn.conn(), id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Transaction not found or access denied".to_string()))?;
+
+        if let Some(pocket_id) = existing.account_id {
+            self.writable_pocket(pocket_id, user_id).await?;
+        }
+
+        self.repository.delete_with(txn.conn(), id, user_id).await?;
+        self.apply_to_pocket(&mut txn, existing.account_id, user_id, -existing.balance_effect()).await?;
+
+        txn.commit().await?;
+        self.publish_change(user_id, LiveAction::Deleted, &existing, None);
+        Ok(())
+    }
+
+    // Clears a pending transaction early, moving its amount into the pocket balance
+    pub async fn post_transaction(&self, id: i64, user_id: Uuid) -> Result<TransactionResponse, AppError> {
+        let transaction = self.transition_pending(id, user_id, TRANSACTION_STATUS_POSTED).await?;
         Ok(transaction.to_response())
     }
 
-    pub async fn update_transaction(&self, id: i64, user_id: Uuid, request: UpdateTransactionRequest) -> Result<TransactionResponse, AppError> {
-        let transaction = self.repository.update(id, user_id, &request).await?;
+    pub async fn cancel_transaction(&self, id: i64, user_id: Uuid) -> Result<TransactionResponse, AppError> {
+        let transaction = self.transition_pending(id, user_id, TRANSACTION_STATUS_CANCELLED).await?;
         Ok(transaction.to_response())
     }
 
-    pub async fn delete_transaction(&self, id: i64, user_id: Uuid) -> Result<(), AppError> {
-        self.repository.delete(id, user_id).await
+    // Posts pending transactions whose date has arrived, returning the owners whose data changed
+    pub async fn post_due_transactions(&self) -> Result<Vec<Uuid>, AppError> {
+        let today = Utc::now().date_naive();
+        let due = self.repository.find_due_pending(today, DUE_PENDING_BATCH_SIZE).await?;
+
+        let mut user_ids = Vec::new();
+        for transaction in due {
+            match self.transition_pending(transaction.id, transaction.user_id, TRANSACTION_STATUS_POSTED).await {
+                Ok(_) => {
+                    if !user_ids.contains(&transaction.user_id) {
+                        user_ids.push(transaction.user_id);
+                    }
+                }
+                // Posted or cancelled by hand in the meantime
+                Err(AppError::Conflict(_)) => {}
+                Err(e) => warn!("Failed to post pending transaction {}: {}", transaction.id, e),
+            }
+        }
+
+        Ok(user_ids)
+    }
+
+    // Moves settled transactions dated before the cutoff into the archive a month at a time,
+    // returning how many rows moved and whose
+    pub async fn archive_transactions_before(&self, cutoff: NaiveDate) -> Result<(u64, Vec<Uuid>), AppError> {
+        let mut moved = 0;
+        let mut user_ids = Vec::new();
+        for month in self.repository.find_archivable_months(cutoff).await? {
+            let owners = self.repository.archive_month(month).await?;
+            moved += owners.len() as u64;
+            for owner in owners {
+                if !user_ids.contains(&owner) {
+                    user_ids.push(owner);
+                }
+            }
+        }
+
+        Ok((moved, user_ids))
+    }
+
+    async fn transition_pending(&self, id: i64, user_id: Uuid, status: &str) -> Result<Transaction, AppError> {
+        let mut txn = self.unit_of_work.begin().await?;
+
+        let existing = self
+            .repository
+            .find_by_id_for_update_with(txn.conn(), id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Transaction not found or access denied".to_string()))?;
+
+        if existing.status != TRANSACTION_STATUS_PENDING {
+            return Err(AppError::Conflict(format!("Transaction is already {}", existing.status)));
+        }
+
+        let transaction = self.repository.set_status_with(txn.conn(), id, user_id, status).await?;
+        self.apply_to_pocket(&mut txn, transaction.account_id, user_id, transaction.balance_effect()).await?;
+
+        txn.commit().await?;
+        self.publish_change(user_id, LiveAction::Updated, &transaction, None);
+        Ok(transaction)
     }
+
+    // A hard limit fails the write; a soft one comes back as the warning to report with it
+    async fn check_spending_limit(&self, txn: &mut TxnContext, user_id: Uuid, category: &str, amount: Decimal, date: NaiveDate, replacing: Option<&Transaction>) -> Result<Option<String>, AppError> {
+        match self
+            .spending_limit_service
+            .check_expense_with(txn.conn(), user_id, category, amount, date, replacing)
+            .await?
+        {
+            SpendingLimitCheck::WithinLimit => Ok(None),
+            SpendingLimitCheck::SoftExceeded(message) => Ok(Some(message)),
+            SpendingLimitCheck::HardExceeded(message) => Err(AppError::Conflict(message)),
+        }
+    }
+
+    // The user's own pockets, or shared ones of organizations they belong to
+    async fn writable_pocket(&self, pocket_id: Uuid, user_id: Uuid) -> Result<Pocket, AppError> {
+        self.pocket_repository
+            .find_by_id_with_role(pocket_id, user_id)
+            .await?
+            .filter(|(pocket, role)| permits_as(user_id, role.as_deref(), Action::Record, pocket))
+            .map(|(pocket, _)| pocket)
+            .ok_or_else(|| AppError::NotFound("Pocket not found".to_string()))
+    }
+
+    async fn ensure_pocket_accepts_transactions(&self, pocket_id: Uuid, user_id: Uuid) -> Result<Pocket, AppError> {
+        let pocket = self.writable_pocket(pocket_id, user_id).await?;
+
+        if pocket.archived {
+            return Err(AppError::ValidationError("Cannot add transactions to an archived pocket".to_string()).with_code(codes::POCKET_ARCHIVED));
+        }
+
+        Ok(pocket)
+    }
+
+    // Transactions are recorded in their pocket's currency, or the owner's base currency outside
+    // a pocket. An amount given in another one is converted at the rate on the transaction date,
+    // which is stored with it so later rate changes don't move past amounts.
+    async fn convert_amount(&self, user_id: Uuid, request: &CreateTransactionRequest, pocket_currency: Option<&str>) -> Result<Option<CurrencyConversion>, AppError> {
+        let recorded_currency = match (pocket_currency, request.currency.as_deref()) {
+            (Some(currency), _) => currency.to_string(),
+            (None, Some(_)) => self
+                .currency_repository
+                .find_base_currency(user_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("User not found".to_string()))?,
+            (None, None) => return Ok(None),
+        };
+
+        let Some(currency) = request.currency.as_deref().filter(|currency| *currency != recorded_currency) else {
+            request.amount.for_currency(&recorded_currency)?;
+            return Ok(None);
+        };
+
+        let original_amount = request.amount.for_currency(currency)?;
+        let transaction_date = NaiveDate::parse_from_str(&request.transaction_date, "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError("Invalid date format. Use YYYY-MM-DD".to_string()).with_code(codes::INVALID_DATE_FORMAT))?;
+        let exchange_rate = exchange_rate_on(&self.currency_repository, currency, &recorded_currency, transaction_date)
+            .await?
+            .ok_or_else(|| AppError::ValidationError(format!(
+                "No exchange rate from {} to {} is available for {}",
+                currency, recorded_currency, transaction_date
+            )))?;
+
+        Ok(Some(CurrencyConversion {
+            original_amount,
+            original_currency: currency.to_string(),
+            exchange_rate,
+            converted_amount: (original_amount * exchange_rate).round_dp(currency_scale(&recorded_currency)),
+        }))
+    }
+
+    // Pockets the transaction touched are announced too, since their balances moved with it
+    fn publish_change(&self, user_id: Uuid, action: LiveAction, transaction: &Transaction, previous_account_id: Option<Uuid>) {
+        self.events.publish(user_id, LiveResource::Transaction, action, transaction.id);
+
+        if let Some(pocket_id) = transaction.account_id {
+            self.events.publish(user_id, LiveResource::Pocket, LiveAction::Updated, pocket_id);
+        }
+        if let Some(pocket_id) = previous_account_id
+            && previous_account_id != transaction.account_id
+        {
+            self.events.publish(user_id, LiveResource::Pocket, LiveAction::Updated, pocket_id);
+        }
+    }
+
+    async fn apply_to_pocket(&self, txn: &mut TxnContext, account_id: Option<Uuid>, user_id: Uuid, delta: Decimal) -> Result<(), AppError> {
+        match account_id {
+            Some(pocket_id) => self.pocket_repository.adjust_balance_with(txn.conn(), pocket_id, user_id, delta).await,
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_amount_filter(value: Option<&str>, field: &str) -> Result<Option<Decimal>, AppError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+
+    let amount = Decimal::from_str(value)
+        .map_err(|_| AppError::invalid_field(field, format!("Invalid {} format", field)).with_code(codes::INVALID_AMOUNT))?;
+    if amount.is_sign_negative() {
+        return Err(AppError::invalid_field(field, format!("{} must not be negative", field)));
+    }
+
+    Ok(Some(amount))
 }
\ No newline at end of file
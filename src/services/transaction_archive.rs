@@ -0,0 +1,60 @@
+use chrono::{Datelike, Months, Utc};
+use tracing::info;
+
+use crate::jobs::Schedule;
+use crate::models::TASK_TRANSACTION_ARCHIVE;
+use crate::repositories::{CurrencyRepository, PocketRepository, SpendingLimitRepository, TransactionRepository};
+use crate::services::{ScheduledTask, TransactionService};
+use crate::utils::{AppError, CacheService};
+
+pub struct TransactionArchiveWorker<R: TransactionRepository, L: SpendingLimitRepository, P: PocketRepository, C: CurrencyRepository> {
+    transaction_service: TransactionService<R, L, P, C>,
+    cache: CacheService,
+    archive_after_years: u32,
+    check_interval_secs: u64,
+}
+
+impl<R: TransactionRepository, L: SpendingLimitRepository, P: PocketRepository, C: CurrencyRepository> TransactionArchiveWorker<R, L, P, C> {
+    pub fn new(transaction_service: TransactionService<R, L, P, C>, cache: CacheService, archive_after_years: u32, check_interval_secs: u64) -> Self {
+        Self {
+            transaction_service,
+            cache,
+            archive_after_years,
+            check_interval_secs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<R, L, P, C> ScheduledTask for TransactionArchiveWorker<R, L, P, C>
+where
+    R: TransactionRepository + 'static,
+    L: SpendingLimitRepository + 'static,
+    P: PocketRepository + 'static,
+    C: CurrencyRepository + 'static,
+{
+    fn name(&self) -> &'static str {
+        TASK_TRANSACTION_ARCHIVE
+    }
+
+    fn schedule(&self) -> Schedule {
+        Schedule::every(self.check_interval_secs)
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        // Whole months only, so each run fills archive partitions completely
+        let today = Utc::now().date_naive();
+        let cutoff = today.with_day(1).unwrap_or(today) - Months::new(12 * self.archive_after_years);
+
+        let (moved, user_ids) = self.transaction_service.archive_transactions_before(cutoff).await?;
+        for user_id in &user_ids {
+            // Cached transaction lists may still show the archived rows
+            self.cache.invalidate_user_derived(user_id).await;
+        }
+        if moved > 0 {
+            info!("Archived {} transactions dated before {} for {} users", moved, cutoff, user_ids.len());
+        }
+
+        Ok(moved)
+    }
+}
\ No newline at end of file
@@ -1,17 +1,40 @@
+use std::sync::Arc;
+
+use serde_json::json;
 use uuid::Uuid;
 
-use crate::models::{UserResponse, UpdateUserNameRequest, UpdateHideBalanceRequest};
+use crate::config::PasswordPolicyConfig;
+use crate::models::{
+    UserResponse, ListUsersQuery, ListUsersResponse, UpdateUserNameRequest, UpdateHideBalanceRequest, UpdateMonthlyDigestRequest,
+    ChangePasswordRequest, ChangeEmailRequest, VerifyEmailChangeRequest,
+    DeleteAccountRequest, AccountDeletionResponse, UserDataExport, RefundResponse, ReminderResponse, DEFAULT_LOCALE
+};
 use crate::repositories::UserRepository;
-use crate::utils::AppError;
+use crate::utils::{AppError, codes, EMAIL_TEMPLATE_EMAIL_CHANGE, Mailer, PasswordHasher, check_new_password, generate_token, hash_token};
+
+const USER_SORT_FIELDS: &[&str] = &["name", "email", "created_at"];
+
+// How long an email change verification token stays valid
+const EMAIL_CHANGE_TOKEN_TTL_HOURS: i64 = 24;
 
 #[derive(Clone)]
 pub struct UserService<R: UserRepository> {
     repository: R,
+    mailer: Mailer,
+    hasher: Arc<dyn PasswordHasher>,
+    password_policy: PasswordPolicyConfig,
+    deletion_grace_days: i64,
 }
 
 impl<R: UserRepository> UserService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, mailer: Mailer, hasher: Arc<dyn PasswordHasher>, password_policy: PasswordPolicyConfig, deletion_grace_days: i64) -> Self {
+        Self {
+            repository,
+            mailer,
+            hasher,
+            password_policy,
+            deletion_grace_days,
+        }
     }
 
     pub async fn get_user_by_id(&self, id: Uuid) -> Result<UserResponse, AppError> {
@@ -24,6 +47,16 @@ impl<R: UserRepository> UserService<R> {
         Ok(user.to_response())
     }
 
+    pub async fn get_user_by_email(&self, email: &str) -> Result<UserResponse, AppError> {
+        let user = self
+            .repository
+            .find_by_email(email)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        Ok(user.to_response())
+    }
+
     pub async fn update_user_name(&self, id: Uuid, request: UpdateUserNameRequest) -> Result<UserResponse, AppError> {
         let user = self.repository.update_name(id, &request.name).await?;
         Ok(user.to_response())
@@ -34,9 +67,164 @@ impl<R: UserRepository> UserService<R> {
         Ok(user.to_response())
     }
 
-    pub async fn list_users(&self) -> Result<Vec<UserResponse>, AppError> {
-        let users = self.repository.list_all().await?;
-        let user_responses = users.into_iter().map(|user| user.to_response()).collect();
-        Ok(user_responses)
+    pub async fn update_monthly_digest(&self, id: Uuid, request: UpdateMonthlyDigestRequest) -> Result<UserResponse, AppError> {
+        let user = self.repository.update_monthly_digest(id, request.monthly_digest).await?;
+        Ok(user.to_response())
+    }
+
+    pub async fn list_users(&self, query: ListUsersQuery) -> Result<ListUsersResponse, AppError> {
+        if let Some(sort_by) = query.sort_by.as_deref()
+            && !USER_SORT_FIELDS.contains(&sort_by)
+        {
+            return Err(AppError::invalid_field(
+                "sort_by",
+                format!("sort_by must be one of: {}", USER_SORT_FIELDS.join(", ")),
+            ));
+        }
+        if let Some(order) = query.order.as_deref()
+            && order != "asc"
+            && order != "desc"
+        {
+            return Err(AppError::invalid_field("order", "order must be 'asc' or 'desc'"));
+        }
+
+        let page = query.page.unwrap_or(1);
+        let limit = query.limit.unwrap_or(20);
+        let users = self.repository.list_page(&query, limit, (page - 1) * limit).await?;
+
+        Ok(ListUsersResponse {
+            data: users.items.into_iter().map(|user| user.to_response()).collect(),
+            page,
+            limit,
+            total_items: users.total_items,
+        })
+    }
+
+    pub async fn change_password(&self, id: Uuid, request: ChangePasswordRequest) -> Result<UserResponse, AppError> {
+        self.verify_current_password(id, &request.current_password).await?;
+        check_new_password(&self.password_policy, "new_password", &request.new_password).await?;
+
+        let hashed_password = self.hasher.hash(&request.new_password)?;
+
+        let user = self.repository.update_password(id, &hashed_password).await?;
+        Ok(user.to_response())
+    }
+
+    // Sets a new password without the current one, for operators helping a locked-out user.
+    // Signing out existing sessions is left to the caller, as with change_password.
+    pub async fn reset_password(&self, id: Uuid, new_password: &str) -> Result<UserResponse, AppError> {
+        check_new_password(&self.password_policy, "new_password", new_password).await?;
+
+        let hashed_password = self.hasher.hash(new_password)?;
+
+        let user = self.repository.update_password(id, &hashed_password).await?;
+        Ok(user.to_response())
+    }
+
+    pub async fn request_email_change(&self, id: Uuid, request: ChangeEmailRequest) -> Result<(), AppError> {
+        let user = self.verify_current_password(id, &request.current_password).await?;
+
+        if user.email.eq_ignore_ascii_case(&request.new_email) {
+            return Err(AppError::ValidationError("New email must be different from the current email".to_string()));
+        }
+
+        if self.repository.find_by_email(&request.new_email).await?.is_some() {
+            return Err(AppError::Conflict("Email already exists".to_string()).with_code(codes::EMAIL_TAKEN));
+        }
+
+        let token = generate_token(32);
+        let expires_at = chrono::Utc::now() + chrono::Duration::hours(EMAIL_CHANGE_TOKEN_TTL_HOURS);
+        self.repository
+            .create_email_change(id, &request.new_email, &hash_token(&token), expires_at)
+            .await?;
+
+        let locale = self.repository.find_locale(id).await?;
+        let context = json!({
+            "name": user.name,
+            "code": token,
+            "expires_in_hours": EMAIL_CHANGE_TOKEN_TTL_HOURS,
+        });
+        self.mailer
+            .send_template(
+                &request.new_email,
+                EMAIL_TEMPLATE_EMAIL_CHANGE,
+                locale.as_deref().unwrap_or(DEFAULT_LOCALE),
+                &context,
+            )
+            .await
+    }
+
+    pub async fn confirm_email_change(&self, id: Uuid, request: VerifyEmailChangeRequest) -> Result<UserResponse, AppError> {
+        let pending = self
+            .repository
+            .find_email_change(id, &hash_token(&request.token))
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Invalid verification token".to_string()))?;
+
+        if pending.expires_at < chrono::Utc::now() {
+            self.repository.delete_email_changes(id).await?;
+            return Err(AppError::BadRequest("Verification token has expired".to_string()));
+        }
+
+        let user = self.repository.update_email(id, &pending.new_email).await?;
+        self.repository.delete_email_changes(id).await?;
+
+        Ok(user.to_response())
+    }
+
+    pub async fn schedule_account_deletion(&self, id: Uuid, request: DeleteAccountRequest) -> Result<AccountDeletionResponse, AppError> {
+        self.verify_current_password(id, &request.password).await?;
+
+        let scheduled_at = chrono::Utc::now() + chrono::Duration::days(self.deletion_grace_days);
+        let deletion_scheduled_at = self.repository.schedule_deletion(id, scheduled_at).await?;
+
+        Ok(AccountDeletionResponse { deletion_scheduled_at })
+    }
+
+    pub async fn cancel_account_deletion(&self, id: Uuid) -> Result<(), AppError> {
+        self.repository.cancel_deletion(id).await
+    }
+
+    pub async fn export_user_data(&self, id: Uuid) -> Result<UserDataExport, AppError> {
+        let user = self.get_user_by_id(id).await?;
+        let rows = self.repository.find_export_data(id).await?;
+
+        Ok(UserDataExport {
+            exported_at: chrono::Utc::now(),
+            user,
+            pockets: rows.pockets.into_iter().map(|pocket| pocket.to_response()).collect(),
+            transactions: rows.transactions.into_iter().map(|transaction| transaction.to_response()).collect(),
+            budgets: rows.budgets.into_iter().map(|budget| budget.to_response()).collect(),
+            spending_limits: rows.spending_limits.into_iter().map(|limit| limit.to_response()).collect(),
+            debts: rows.debts.into_iter().map(|debt| debt.to_response()).collect(),
+            refunds: rows.refunds.into_iter().map(RefundResponse::from).collect(),
+            reminders: rows.reminders.into_iter().map(ReminderResponse::from).collect(),
+            preferences: rows.preferences,
+        })
+    }
+
+    // Permanently removes accounts whose grace period has elapsed, returning their ids
+    pub async fn purge_due_deletions(&self) -> Result<Vec<Uuid>, AppError> {
+        let due = self.repository.find_due_deletions(chrono::Utc::now()).await?;
+
+        for id in &due {
+            self.repository.delete(*id).await?;
+        }
+
+        Ok(due)
+    }
+
+    async fn verify_current_password(&self, id: Uuid, password: &str) -> Result<crate::models::User, AppError> {
+        let user = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        if !self.hasher.verify(password, &user.password)? {
+            return Err(AppError::Unauthorized("Current password is incorrect".to_string()));
+        }
+
+        Ok(user)
     }
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+use axum::extract::FromRef;
+use sqlx::PgPool;
+
+use crate::config::{HttpConfig, JwtConfig, MetricsConfig};
+use crate::repositories::{
+    PostgresAnalyticsFeedRepository, PostgresAnomalyRepository, PostgresAuditRepository, PostgresAuthRepository, PostgresBudgetRepository,
+    PostgresCategorizationRepository, PostgresCurrencyRepository, PostgresDebtRepository, PostgresDeviceRepository, PostgresExportLinkRepository,
+    PostgresImportCheckpointRepository, PostgresInboundEmailRepository, PostgresJobRepository, PostgresLinkedAccountRepository, PostgresMonthlyAggregateRepository, PostgresOrganizationRepository, PostgresPocketRepository, PostgresPreferenceRepository,
+    PostgresRefundRepository, PostgresReminderRepository, PostgresSessionRepository, PostgresShareTokenRepository,
+    PostgresSpendingLimitRepository, PostgresTaskRunRepository, PostgresTransactionRepository, PostgresUserRepository,
+};
+use crate::services::{
+    AccountSummaryService, AnalyticsFeedService, AnomalyService, AuditService, AuthService, BudgetService, CategorizationService, ChatBotService,
+    CurrencyService, DebtService, ExpenseAnalyticsService, FinancialHealthService, ExportLinkService, ExportService, ImportService, InboundEmailService, IncomeAnalyticsService,
+    JobService, OrganizationService, PocketAdjustmentService, PocketImportService, PocketService, PreferenceService, PushNotificationService, ReaggregationService,
+    RefundService, ReminderService, ReportService, SchedulerService, SessionService, ShareTokenService,
+    SpendingLimitService, StatusService, SubscriptionAnalyticsService, TransactionService, UserService,
+};
+use crate::utils::{CacheService, EmailTemplates, EventBus, PoolMetrics, StatementMetrics};
+
+// Everything a handler can extract with State<T>. Each field type appears once, so
+// FromRef hands out the matching clone; a new service only needs a field here.
+#[derive(Clone, FromRef)]
+pub struct AppState {
+    pub pool: PgPool,
+    pub cache: CacheService,
+    pub jwt: JwtConfig,
+    pub http: HttpConfig,
+    pub metrics_config: MetricsConfig,
+    pub statement_metrics: StatementMetrics,
+    pub pool_metrics: PoolMetrics,
+    pub email_templates: EmailTemplates,
+    pub event_bus: EventBus,
+    pub auth: AuthService<PostgresAuthRepository, PostgresSessionRepository>,
+    pub sessions: SessionService<PostgresSessionRepository>,
+    pub users: UserService<PostgresUserRepository>,
+    pub currency: CurrencyService<PostgresUserRepository, PostgresCurrencyRepository, PostgresJobRepository>,
+    pub preferences: PreferenceService<PostgresPreferenceRepository, PostgresCurrencyRepository>,
+    pub pockets: PocketService<PostgresPocketRepository>,
+    pub pocket_import: PocketImportService<PostgresPocketRepository, PostgresTransactionRepository>,
+    pub pocket_adjustments: PocketAdjustmentService<PostgresPocketRepository, PostgresTransactionRepository>,
+    pub share_tokens: ShareTokenService<PostgresShareTokenRepository, PostgresPocketRepository>,
+    pub transactions: TransactionService<PostgresTransactionRepository, PostgresSpendingLimitRepository, PostgresPocketRepository, PostgresCurrencyRepository>,
+    pub reminders: ReminderService<PostgresReminderRepository>,
+    pub refunds: RefundService<PostgresRefundRepository, PostgresTransactionRepository>,
+    pub budgets: BudgetService<PostgresBudgetRepository>,
+    pub organizations: OrganizationService<PostgresOrganizationRepository, PostgresUserRepository>,
+    pub spending_limits: SpendingLimitService<PostgresSpendingLimitRepository>,
+    pub debts: DebtService<PostgresDebtRepository>,
+    pub account_summary: AccountSummaryService<PostgresPocketRepository, PostgresTransactionRepository>,
+    pub expense_analytics: ExpenseAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>,
+    pub income_analytics: IncomeAnalyticsService<PostgresTransactionRepository, PostgresMonthlyAggregateRepository>,
+    pub subscription_analytics: SubscriptionAnalyticsService<PostgresTransactionRepository>,
+    pub anomalies: AnomalyService<PostgresAnomalyRepository>,
+    pub financial_health: FinancialHealthService<PostgresPocketRepository, PostgresTransactionRepository, PostgresBudgetRepository, PostgresCurrencyRepository>,
+    pub exports: ExportService<PostgresTransactionRepository, PostgresPocketRepository, PostgresCurrencyRepository>,
+    pub reports: ReportService<PostgresTransactionRepository, PostgresUserRepository>,
+    pub export_links: ExportLinkService<PostgresExportLinkRepository, PostgresTransactionRepository, PostgresPocketRepository, PostgresUserRepository, PostgresCurrencyRepository>,
+    pub analytics_feeds: AnalyticsFeedService<PostgresAnalyticsFeedRepository, PostgresTransactionRepository, PostgresUserRepository>,
+    pub imports: ImportService<PostgresTransactionRepository, PostgresPocketRepository, PostgresImportCheckpointRepository>,
+    pub categorization: CategorizationService<PostgresCategorizationRepository>,
+    pub inbound_email: InboundEmailService<PostgresInboundEmailRepository, PostgresCategorizationRepository>,
+    pub chat_bot: ChatBotService<PostgresLinkedAccountRepository, PostgresCategorizationRepository>,
+    pub push_notifications: PushNotificationService<PostgresDeviceRepository, PostgresPreferenceRepository>,
+    pub jobs: JobService<PostgresJobRepository>,
+    pub reaggregation: ReaggregationService<PostgresJobRepository, PostgresPocketRepository>,
+    pub audit: AuditService<PostgresAuditRepository>,
+    pub status: StatusService,
+    pub scheduler: SchedulerService<PostgresTaskRunRepository>,
+    #[cfg(feature = "graphql")]
+    pub graphql: crate::graphql::GraphqlService,
+}
\ No newline at end of file
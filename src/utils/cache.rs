@@ -1,12 +1,98 @@
+use rand::Rng;
 use redis::{Client, AsyncCommands};
 use redis::aio::ConnectionManager;
+use moka::Expiry;
+use moka::sync::Cache;
 use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant as StdInstant};
+use tokio::time::{sleep, Instant};
 use tracing::{error, info, warn};
 use crate::config::RedisConfig;
+use crate::utils::AppError;
 
+// Outlives any sane computation so a crashed filler can't block a key for long
+const FILL_LOCK_TTL_SECS: u64 = 10;
+// How long a request waits for another one's result before computing it itself
+const FILL_WAIT: Duration = Duration::from_secs(3);
+const FILL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+// Not-found results are remembered briefly so repeated lookups skip the database
+pub const NEGATIVE_CACHE_TTL_SECS: u64 = 30;
+
+// Compare-and-act on a token key, so only the holder that set it can release or extend it
+const RELEASE_TOKEN_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+const EXTEND_TOKEN_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+#[cfg(feature = "chaos")]
+tokio::task_local! {
+    // Set by the chaos middleware around a request that should find the cache disabled
+    static INJECTED_OUTAGE: ();
+}
+
+fn outage_injected() -> bool {
+    #[cfg(feature = "chaos")]
+    return INJECTED_OUTAGE.try_with(|_| ()).is_ok();
+    #[cfg(not(feature = "chaos"))]
+    return false;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: String,
+    pub value: String,
+    // Remaining lifetime in milliseconds, None for keys without an expiry
+    pub ttl_ms: Option<i64>,
+}
+
+// A copy of a cached value held in process memory
+#[derive(Clone)]
+struct LocalEntry {
+    value: String,
+    ttl: Duration,
+}
+
+struct LocalExpiry;
+
+impl Expiry<String, LocalEntry> for LocalExpiry {
+    fn expire_after_create(&self, _key: &String, entry: &LocalEntry, _created_at: StdInstant) -> Option<Duration> {
+        Some(entry.ttl)
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        entry: &LocalEntry,
+        _updated_at: StdInstant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(entry.ttl)
+    }
+}
+
+// Two tiers: a bounded in-process LRU in front of Redis. Reads try the local tier first and
+// copy Redis hits into it, writes and deletes go to both. While Redis is unreachable the local
+// tier serves on its own. Counters, claims and snapshots need Redis and report it as unavailable,
+// and revocation state (see is_redis_only_key) never enters the local tier. With the chaos
+// feature, with_injected_outage makes a request see the cache as disabled.
 #[derive(Clone)]
 pub struct CacheService {
-    connection_manager: Option<ConnectionManager>,
+    // Empty until Redis is reached, which may be long after startup
+    redis: Arc<RwLock<Option<ConnectionManager>>>,
+    local: Option<Cache<String, LocalEntry>>,
+    // Local copies live at most this long, bounding how stale another instance's invalidation leaves them
+    local_ttl: Duration,
     enabled: bool,
 }
 
@@ -14,86 +100,175 @@ impl CacheService {
     pub async fn new(config: &RedisConfig) -> Self {
         if !config.enabled {
             info!("Redis cache is disabled");
-            return Self {
-                connection_manager: None,
-                enabled: false,
-            };
+            return Self::disabled();
         }
 
-        let redis_url = config.build_url();
-        match Client::open(redis_url.as_str()) {
-            Ok(client) => {
-                match client.get_connection_manager().await {
-                    Ok(connection_manager) => {
-                        info!("Redis connection established successfully");
-                        Self {
-                            connection_manager: Some(connection_manager),
-                            enabled: true,
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to create Redis connection manager: {}", e);
-                        warn!("Running without Redis cache");
-                        Self {
-                            connection_manager: None,
-                            enabled: false,
-                        }
-                    }
-                }
+        let local = (config.local_cache_capacity > 0).then(|| {
+            Cache::builder()
+                .max_capacity(config.local_cache_capacity)
+                .expire_after(LocalExpiry)
+                .build()
+        });
+        let service = Self {
+            redis: Arc::new(RwLock::new(None)),
+            local,
+            local_ttl: Duration::from_secs(config.local_cache_ttl_seconds.max(1)),
+            enabled: true,
+        };
+
+        match connect(config).await {
+            Ok(connection_manager) => {
+                info!("Redis connection established successfully");
+                service.store_connection(connection_manager);
             }
             Err(e) => {
-                error!("Failed to create Redis client: {}", e);
-                warn!("Running without Redis cache");
-                Self {
-                    connection_manager: None,
-                    enabled: false,
+                error!("Failed to connect to Redis: {}", e);
+                warn!(
+                    "Running on the in-process cache only, retrying Redis every {}s",
+                    config.reconnect_interval_seconds
+                );
+                service.spawn_reconnect(config.clone());
+            }
+        }
+
+        service
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            redis: Arc::new(RwLock::new(None)),
+            local: None,
+            local_ttl: Duration::ZERO,
+            enabled: false,
+        }
+    }
+
+    // Once connected the ConnectionManager reconnects by itself, so this only covers
+    // Redis being unreachable when the process started
+    fn spawn_reconnect(&self, config: RedisConfig) {
+        let service = self.clone();
+        let interval = Duration::from_secs(config.reconnect_interval_seconds.max(1));
+
+        tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+                match connect(&config).await {
+                    Ok(connection_manager) => {
+                        service.store_connection(connection_manager);
+                        info!("Redis connection established after retrying");
+                        return;
+                    }
+                    Err(e) => warn!("Redis still unavailable: {}", e),
                 }
             }
+        });
+    }
+
+    fn store_connection(&self, connection_manager: ConnectionManager) {
+        if let Ok(mut redis) = self.redis.write() {
+            *redis = Some(connection_manager);
+        }
+    }
+
+    // Runs `future` as if the cache were disabled, for every CacheService it touches
+    #[cfg(feature = "chaos")]
+    pub async fn with_injected_outage<F: Future>(future: F) -> F::Output {
+        INJECTED_OUTAGE.scope((), future).await
+    }
+
+    fn connection(&self) -> Option<ConnectionManager> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.redis.read().ok()?.clone()
+    }
+
+    fn local(&self) -> Option<&Cache<String, LocalEntry>> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.local.as_ref()
+    }
+
+    fn get_local(&self, key: &str) -> Option<String> {
+        if is_redis_only_key(key) {
+            return None;
+        }
+        self.local()?.get(key).map(|entry| entry.value)
+    }
+
+    fn set_local(&self, key: &str, value: String, ttl_seconds: Option<u64>) -> bool {
+        let Some(local) = self.local() else {
+            return false;
+        };
+        if is_redis_only_key(key) {
+            return false;
+        }
+
+        let ttl = ttl_seconds
+            .map(|ttl| Duration::from_secs(ttl).min(self.local_ttl))
+            .unwrap_or(self.local_ttl);
+        local.insert(key.to_string(), LocalEntry { value, ttl });
+        true
+    }
+
+    pub(crate) fn delete_local_where(&self, matches: impl Fn(&str) -> bool) -> usize {
+        let Some(local) = self.local() else {
+            return 0;
+        };
+
+        let keys: Vec<Arc<String>> = local
+            .iter()
+            .filter(|(key, _)| matches(key))
+            .map(|(key, _)| key)
+            .collect();
+        for key in &keys {
+            local.invalidate(key.as_str());
         }
+        keys.len()
     }
 
     pub async fn get<T>(&self, key: &str) -> Option<T>
     where
         T: for<'de> Deserialize<'de>,
     {
-        if !self.enabled || self.connection_manager.is_none() {
-            return None;
-        }
-
-        let mut conn = self.connection_manager.as_ref()?.clone();
-        
-        match conn.get::<_, String>(key).await {
-            Ok(value) => {
-                match serde_json::from_str::<T>(&value) {
-                    Ok(deserialized) => Some(deserialized),
+        let value = match self.get_local(key) {
+            Some(value) => value,
+            None => {
+                let mut conn = self.connection()?;
+                match conn.get::<_, String>(key).await {
+                    Ok(value) => {
+                        self.set_local(key, value.clone(), None);
+                        value
+                    }
                     Err(e) => {
-                        error!("Failed to deserialize cached value for key '{}': {}", key, e);
-                        None
+                        if !e.to_string().contains("nil") {
+                            error!("Failed to get value from cache for key '{}': {}", key, e);
+                        }
+                        return None;
                     }
                 }
             }
+        };
+
+        match serde_json::from_str::<T>(&value) {
+            Ok(deserialized) => Some(deserialized),
             Err(e) => {
-                if !e.to_string().contains("nil") {
-                    error!("Failed to get value from cache for key '{}': {}", key, e);
-                }
+                error!("Failed to deserialize cached value for key '{}': {}", key, e);
                 None
             }
         }
     }
 
+    // True when the value landed in at least one tier
     pub async fn set<T>(&self, key: &str, value: &T, ttl_seconds: Option<u64>) -> bool
     where
         T: Serialize,
     {
-        if !self.enabled || self.connection_manager.is_none() {
+        if !self.is_enabled() {
             return false;
         }
 
-        let mut conn = match self.connection_manager.as_ref() {
-            Some(cm) => cm.clone(),
-            None => return false,
-        };
-
         let serialized = match serde_json::to_string(value) {
             Ok(s) => s,
             Err(e) => {
@@ -102,6 +277,11 @@ impl CacheService {
             }
         };
 
+        let stored_locally = self.set_local(key, serialized.clone(), ttl_seconds);
+        let Some(mut conn) = self.connection() else {
+            return stored_locally;
+        };
+
         let result = if let Some(ttl) = ttl_seconds {
             conn.set_ex::<_, _, ()>(key, serialized, ttl).await
         } else {
@@ -112,19 +292,99 @@ impl CacheService {
             Ok(_) => true,
             Err(e) => {
                 error!("Failed to set value in cache for key '{}': {}", key, e);
-                false
+                stored_locally
             }
         }
     }
 
-    pub async fn delete(&self, key: &str) -> bool {
-        if !self.enabled || self.connection_manager.is_none() {
+    // Reads every field of a hash, None when it is missing or any field fails to parse.
+    // The local tier keeps the field values as one JSON array.
+    pub async fn get_hash<T>(&self, key: &str) -> Option<Vec<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if let Some(values) = self.get_local(key) {
+            return serde_json::from_str::<Vec<T>>(&values)
+                .map_err(|e| error!("Failed to deserialize cached hash for key '{}': {}", key, e))
+                .ok();
+        }
+
+        let mut conn = self.connection()?;
+
+        let values: Vec<String> = match conn.hvals(key).await {
+            Ok(values) => values,
+            Err(e) => {
+                error!("Failed to read hash '{}' from cache: {}", key, e);
+                return None;
+            }
+        };
+
+        if values.is_empty() {
+            return None;
+        }
+
+        self.set_local(key, format!("[{}]", values.join(",")), None);
+        values
+            .iter()
+            .map(|value| serde_json::from_str::<T>(value))
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(|e| error!("Failed to deserialize cached hash field for key '{}': {}", key, e))
+            .ok()
+    }
+
+    // Replaces a hash in one round trip so readers never see it half written
+    pub async fn set_hash<T>(&self, key: &str, fields: &[(String, T)], ttl_seconds: u64) -> bool
+    where
+        T: Serialize,
+    {
+        if !self.is_enabled() || fields.is_empty() {
             return false;
         }
 
-        let mut conn = match self.connection_manager.as_ref() {
-            Some(cm) => cm.clone(),
-            None => return false,
+        let mut serialized = Vec::with_capacity(fields.len());
+        for (field, value) in fields {
+            match serde_json::to_string(value) {
+                Ok(s) => serialized.push((field.as_str(), s)),
+                Err(e) => {
+                    error!("Failed to serialize hash field '{}' for key '{}': {}", field, key, e);
+                    return false;
+                }
+            }
+        }
+
+        let values: Vec<&str> = serialized.iter().map(|(_, value)| value.as_str()).collect();
+        let stored_locally = self.set_local(key, format!("[{}]", values.join(",")), Some(ttl_seconds));
+        let Some(mut conn) = self.connection() else {
+            return stored_locally;
+        };
+
+        let result = redis::pipe()
+            .atomic()
+            .del(key)
+            .ignore()
+            .hset_multiple(key, &serialized)
+            .ignore()
+            .expire(key, ttl_seconds as i64)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await;
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Failed to set hash in cache for key '{}': {}", key, e);
+                stored_locally
+            }
+        }
+    }
+
+    pub async fn delete(&self, key: &str) -> bool {
+        if let Some(local) = self.local() {
+            local.invalidate(key);
+        }
+
+        let Some(mut conn) = self.connection() else {
+            return self.local().is_some();
         };
 
         match conn.del::<_, ()>(key).await {
@@ -136,14 +396,51 @@ impl CacheService {
         }
     }
 
+    // Deletes every key matching a glob pattern from both tiers, returning how many Redis removed
+    // (or the local count while Redis is unreachable)
+    pub async fn delete_pattern(&self, pattern: &str) -> usize {
+        let removed_locally = self.delete_local_where(|key| glob_matches(pattern, key));
+
+        let Some(mut conn) = self.connection() else {
+            return removed_locally;
+        };
+
+        let keys: Vec<String> = {
+            let mut iter = match conn.scan_match::<_, String>(pattern).await {
+                Ok(iter) => iter,
+                Err(e) => {
+                    error!("Failed to scan cache keys for pattern '{}': {}", pattern, e);
+                    return removed_locally;
+                }
+            };
+
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        if keys.is_empty() {
+            return 0;
+        }
+
+        match conn.del::<_, ()>(&keys).await {
+            Ok(_) => keys.len(),
+            Err(e) => {
+                error!("Failed to delete keys matching '{}' from cache: {}", pattern, e);
+                0
+            }
+        }
+    }
+
     pub async fn exists(&self, key: &str) -> bool {
-        if !self.enabled || self.connection_manager.is_none() {
-            return false;
+        if !is_redis_only_key(key) && self.local().is_some_and(|local| local.contains_key(key)) {
+            return true;
         }
 
-        let mut conn = match self.connection_manager.as_ref() {
-            Some(cm) => cm.clone(),
-            None => return false,
+        let Some(mut conn) = self.connection() else {
+            return false;
         };
 
         match conn.exists::<_, bool>(key).await {
@@ -155,11 +452,402 @@ impl CacheService {
         }
     }
 
+    // Records `key` in the set `tag`, refreshing the set's expiry
+    pub async fn tag(&self, tag: &str, key: &str, ttl_seconds: u64) -> bool {
+        let Some(mut conn) = self.connection() else {
+            return false;
+        };
+
+        let result = redis::pipe()
+            .sadd(tag, key)
+            .ignore()
+            .expire(tag, ttl_seconds as i64)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await;
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Failed to tag key '{}' with '{}' in cache: {}", key, tag, e);
+                false
+            }
+        }
+    }
+
+    // Deletes every key recorded in the tag sets, with their negative entries, in two round trips.
+    // Members are removed rather than the sets, so keys tagged meanwhile stay tracked.
+    // None when Redis is unavailable.
+    pub async fn invalidate_tags(&self, tags: &[String]) -> Option<usize> {
+        let mut conn = self.connection()?;
+
+        let mut read = redis::pipe();
+        for tag in tags {
+            read.smembers(tag);
+        }
+        let members: Vec<Vec<String>> = match read.query_async(&mut conn).await {
+            Ok(members) => members,
+            Err(e) => {
+                error!("Failed to read cache tags {:?}: {}", tags, e);
+                return None;
+            }
+        };
+
+        let removed: usize = members.iter().map(Vec::len).sum();
+        if removed == 0 {
+            return Some(0);
+        }
+
+        let mut delete = redis::pipe();
+        for (tag, keys) in tags.iter().zip(&members).filter(|(_, keys)| !keys.is_empty()) {
+            let misses: Vec<String> = keys.iter().map(|key| negative_cache_key(key)).collect();
+            delete.del(keys).ignore().del(misses).ignore().srem(tag, keys).ignore();
+        }
+        match delete.query_async::<()>(&mut conn).await {
+            Ok(_) => Some(removed),
+            Err(e) => {
+                error!("Failed to delete tagged cache keys: {}", e);
+                None
+            }
+        }
+    }
+
+    // Increments a counter, starting its TTL window on first use. None when the cache is unavailable.
+    pub async fn increment(&self, key: &str, ttl_seconds: u64) -> Option<u64> {
+        let mut conn = self.connection()?;
+
+        match conn.incr::<_, _, u64>(key, 1).await {
+            Ok(count) => {
+                if count == 1 {
+                    let _ = conn.expire::<_, ()>(key, ttl_seconds as i64).await;
+                }
+                Some(count)
+            }
+            Err(e) => {
+                error!("Failed to increment key '{}' in cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    // Reads a counter kept by increment straight from Redis, since every instance bumps it and a
+    // copy in the local tier would go stale. None when the cache is unavailable.
+    pub async fn counter(&self, key: &str) -> Option<u64> {
+        let mut conn = self.connection()?;
+
+        match conn.get::<_, Option<u64>>(key).await {
+            Ok(count) => Some(count.unwrap_or(0)),
+            Err(e) => {
+                error!("Failed to read counter '{}' from cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    // Sets a marker key only if nobody else has; true when this caller won. None when the cache is unavailable.
+    pub async fn claim(&self, key: &str, ttl_seconds: u64) -> Option<bool> {
+        let mut conn = self.connection()?;
+
+        match redis::cmd("SET")
+            .arg(key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async::<Option<String>>(&mut conn)
+            .await
+        {
+            Ok(reply) => Some(reply.is_some()),
+            Err(e) => {
+                error!("Failed to claim key '{}' in cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    // Sets `key` to an owner token only if it is absent; true when this caller won. None when Redis is unavailable.
+    pub async fn claim_token(&self, key: &str, token: &str, ttl: Duration) -> Option<bool> {
+        let mut conn = self.connection()?;
+
+        match redis::cmd("SET")
+            .arg(key)
+            .arg(token)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async::<Option<String>>(&mut conn)
+            .await
+        {
+            Ok(reply) => Some(reply.is_some()),
+            Err(e) => {
+                error!("Failed to claim key '{}' in cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    // Deletes `key` if it still holds `token`; false when it expired or someone else holds it now
+    pub async fn release_token(&self, key: &str, token: &str) -> Option<bool> {
+        let mut conn = self.connection()?;
+
+        match redis::Script::new(RELEASE_TOKEN_SCRIPT)
+            .key(key)
+            .arg(token)
+            .invoke_async::<i64>(&mut conn)
+            .await
+        {
+            Ok(deleted) => Some(deleted == 1),
+            Err(e) => {
+                error!("Failed to release key '{}' in cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    // Resets the expiry of `key` if it still holds `token`
+    pub async fn extend_token(&self, key: &str, token: &str, ttl: Duration) -> Option<bool> {
+        let mut conn = self.connection()?;
+
+        match redis::Script::new(EXTEND_TOKEN_SCRIPT)
+            .key(key)
+            .arg(token)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async::<i64>(&mut conn)
+            .await
+        {
+            Ok(extended) => Some(extended == 1),
+            Err(e) => {
+                error!("Failed to extend key '{}' in cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    // The cached value for `key`, or the result of `compute` stored under it. Only one caller
+    // per key computes at a time (a SET NX lock); the others poll for its result rather than
+    // sending the same heavy query to the pool. NotFound results are cached briefly as well.
+    pub async fn get_or_compute<T, F, Fut>(&self, key: &str, ttl_seconds: u64, compute: F) -> Result<T, AppError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        self.get_or_compute_tagged(key, ttl_seconds, None, compute).await
+    }
+
+    // get_or_compute that also records a filled key in the tag set `tag` (name, TTL)
+    pub async fn get_or_compute_tagged<T, F, Fut>(
+        &self,
+        key: &str,
+        ttl_seconds: u64,
+        tag: Option<(&str, u64)>,
+        compute: F,
+    ) -> Result<T, AppError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        if !self.is_enabled() {
+            return compute().await;
+        }
+
+        if let Some(cached) = self.lookup(key).await {
+            return cached;
+        }
+
+        let lock_key = fill_lock_key(key);
+        let holds_lock = self.claim(&lock_key, FILL_LOCK_TTL_SECS).await != Some(false);
+        if !holds_lock {
+            let deadline = Instant::now() + FILL_WAIT;
+            while Instant::now() < deadline {
+                sleep(FILL_POLL_INTERVAL).await;
+                if let Some(cached) = self.lookup(key).await {
+                    return cached;
+                }
+                // The holder gave up without caching anything, e.g. its query failed
+                if !self.exists(&lock_key).await {
+                    break;
+                }
+            }
+        }
+
+        let result = compute().await;
+        let filled = match &result {
+            Ok(value) => self.set(key, value, Some(jittered_ttl(ttl_seconds))).await,
+            Err(AppError::NotFound(message)) => {
+                self.set(&negative_cache_key(key), message, Some(NEGATIVE_CACHE_TTL_SECS)).await
+            }
+            Err(_) => false,
+        };
+        if let (true, Some((tag, tag_ttl))) = (filled, tag) {
+            self.tag(tag, key, tag_ttl).await;
+        }
+
+        if holds_lock {
+            self.delete(&lock_key).await;
+        }
+        result
+    }
+
+    async fn lookup<T: DeserializeOwned>(&self, key: &str) -> Option<Result<T, AppError>> {
+        if let Some(value) = self.get::<T>(key).await {
+            return Some(Ok(value));
+        }
+
+        self.get::<String>(&negative_cache_key(key))
+            .await
+            .map(|message| Err(AppError::NotFound(message)))
+    }
+
+    // Both the pocket list and the quick balance hash mirror pocket balances
+    pub async fn invalidate_pockets(&self, user_id: &uuid::Uuid) {
+        self.delete(&user_pockets_cache_key(user_id)).await;
+        self.delete(&user_balances_cache_key(user_id)).await;
+    }
+
+    // Reads up to `limit` keys matching a pattern together with their remaining TTL
+    pub async fn dump_entries(&self, pattern: &str, limit: usize, include: impl Fn(&str) -> bool) -> Vec<CacheEntry> {
+        let Some(mut conn) = self.connection() else {
+            return Vec::new();
+        };
+
+        let keys: Vec<String> = {
+            let mut iter = match conn.scan_match::<_, String>(pattern).await {
+                Ok(iter) => iter,
+                Err(e) => {
+                    error!("Failed to scan cache keys for pattern '{}': {}", pattern, e);
+                    return Vec::new();
+                }
+            };
+
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                if include(&key) {
+                    keys.push(key);
+                }
+                if keys.len() >= limit {
+                    break;
+                }
+            }
+            keys
+        };
+
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            let result = redis::pipe()
+                .get(&key)
+                .pttl(&key)
+                .query_async::<(Option<String>, i64)>(&mut conn)
+                .await;
+
+            match result {
+                // -2 means the key expired between the scan and the read
+                Ok((Some(value), ttl)) if ttl != -2 => entries.push(CacheEntry {
+                    key,
+                    value,
+                    ttl_ms: (ttl >= 0).then_some(ttl),
+                }),
+                Ok(_) => {}
+                Err(e) => error!("Failed to read cache entry '{}': {}", key, e),
+            }
+        }
+
+        entries
+    }
+
+    // Writes an entry only when the key is absent, so values cached since startup win
+    pub async fn restore_entry(&self, entry: &CacheEntry) -> bool {
+        let Some(mut conn) = self.connection() else {
+            return false;
+        };
+
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(&entry.key).arg(&entry.value).arg("NX");
+        if let Some(ttl) = entry.ttl_ms {
+            cmd.arg("PX").arg(ttl);
+        }
+
+        match cmd.query_async::<Option<String>>(&mut conn).await {
+            Ok(reply) => reply.is_some(),
+            Err(e) => {
+                error!("Failed to restore cache entry '{}': {}", entry.key, e);
+                false
+            }
+        }
+    }
+
+    pub async fn ping(&self) -> bool {
+        let Some(mut conn) = self.connection() else {
+            return false;
+        };
+
+        match redis::cmd("PING").query_async::<String>(&mut conn).await {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Redis ping failed: {}", e);
+                false
+            }
+        }
+    }
+
     pub fn is_enabled(&self) -> bool {
-        self.enabled
+        self.enabled && !outage_injected()
+    }
+
+    // Whether Redis itself is reachable, as opposed to the local tier serving alone
+    pub fn is_connected(&self) -> bool {
+        self.connection().is_some()
     }
 }
 
+async fn connect(config: &RedisConfig) -> redis::RedisResult<ConnectionManager> {
+    let client = Client::open(config.build_url().as_str())?;
+    let timeout = Duration::from_secs(config.connection_timeout);
+    match tokio::time::timeout(timeout, client.get_connection_manager()).await {
+        Ok(result) => result,
+        Err(_) => Err(redis::RedisError::from((redis::ErrorKind::IoError, "connection timed out"))),
+    }
+}
+
+// Redis glob matching limited to `*`, the only wildcard the invalidation patterns use
+fn glob_matches(pattern: &str, key: &str) -> bool {
+    let mut segments = pattern.split('*');
+    let Some(mut rest) = segments.next().and_then(|prefix| key.strip_prefix(prefix)) else {
+        return false;
+    };
+
+    let segments: Vec<&str> = segments.collect();
+    let Some((suffix, middle)) = segments.split_last() else {
+        return rest.is_empty();
+    };
+    for segment in middle {
+        match rest.find(segment) {
+            Some(index) => rest = &rest[index + segment.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(suffix)
+}
+
+// Adds up to 10% to a TTL so entries filled together don't all expire together
+pub fn jittered_ttl(ttl_seconds: u64) -> u64 {
+    let spread = ttl_seconds / 10;
+    if spread == 0 {
+        return ttl_seconds;
+    }
+    ttl_seconds + rand::thread_rng().gen_range(0..=spread)
+}
+
+// Both share the entry's prefix, so pattern invalidation clears them along with it
+fn fill_lock_key(key: &str) -> String {
+    format!("{}:lock", key)
+}
+
+fn negative_cache_key(key: &str) -> String {
+    format!("{}:miss", key)
+}
+
 // Helper functions for generating cache keys
 pub fn user_cache_key(user_id: &uuid::Uuid) -> String {
     format!("user:{}", user_id)
@@ -169,6 +857,46 @@ pub fn user_pockets_cache_key(user_id: &uuid::Uuid) -> String {
     format!("user:{}:pockets", user_id)
 }
 
+pub fn user_balances_cache_key(user_id: &uuid::Uuid) -> String {
+    format!("user:{}:balances", user_id)
+}
+
+// Entries worth carrying across a restart: user profiles and pocket lists
+pub fn is_warm_cache_key(key: &str) -> bool {
+    match key.strip_prefix("user:") {
+        Some(rest) => !rest.contains(':') || rest.ends_with(":pockets"),
+        None => false,
+    }
+}
+
 pub fn jwt_cache_key(token_hash: &str) -> String {
     format!("jwt:{}", token_hash)
+}
+
+pub fn user_tokens_valid_after_key(user_id: &uuid::Uuid) -> String {
+    format!("user:{}:tokens_valid_after", user_id)
+}
+
+pub fn session_cache_key(session_id: &uuid::Uuid) -> String {
+    format!("session:{}", session_id)
+}
+
+// A sign-out on one instance has to reach every other one at once, so these are read from
+// Redis only. With Redis down they read as missing and sessions are checked in the database.
+fn is_redis_only_key(key: &str) -> bool {
+    key.starts_with("session:") || key.ends_with(":tokens_valid_after")
+}
+
+// Failed sign-in counters, one per account and one per client address
+pub fn login_failures_email_key(email: &str) -> String {
+    format!("login_failures:email:{}", email.to_lowercase())
+}
+
+pub fn login_failures_ip_key(ip_address: &str) -> String {
+    format!("login_failures:ip:{}", ip_address)
+}
+
+// One marker per scheduled firing of a task, claimed by whichever instance gets there first
+pub fn task_slot_key(task_name: &str, slot: i64) -> String {
+    format!("task_slot:{}:{}", task_name, slot)
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use crate::config::RedisConfig;
+use crate::utils::cache::{CacheEntry, CacheService, is_warm_cache_key};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheSnapshot {
+    created_at: DateTime<Utc>,
+    entries: Vec<CacheEntry>,
+}
+
+// Dumps hot entries to the snapshot file so the next start doesn't hit Postgres cold
+pub async fn save_cache_snapshot(cache: &CacheService, config: &RedisConfig) {
+    let Some(path) = config.snapshot_path.as_deref() else {
+        return;
+    };
+    if !cache.is_connected() {
+        return;
+    }
+
+    let snapshot = CacheSnapshot {
+        created_at: Utc::now(),
+        entries: cache.dump_entries("user:*", config.snapshot_max_entries, is_warm_cache_key).await,
+    };
+
+    let serialized = match serde_json::to_vec(&snapshot) {
+        Ok(serialized) => serialized,
+        Err(e) => {
+            error!("Failed to serialize cache snapshot: {}", e);
+            return;
+        }
+    };
+
+    // Write beside the target and rename, so a crash mid-write never leaves a truncated snapshot
+    let tmp_path = format!("{}.tmp", path);
+    if let Err(e) = write_private(&tmp_path, &serialized).await {
+        error!("Failed to write cache snapshot to '{}': {}", tmp_path, e);
+        return;
+    }
+    if let Err(e) = tokio::fs::rename(&tmp_path, path).await {
+        error!("Failed to move cache snapshot into '{}': {}", path, e);
+        return;
+    }
+
+    info!("Saved {} cache entries to snapshot '{}'", snapshot.entries.len(), path);
+}
+
+// Preloads entries from the snapshot file, then removes it so a later crash can't replay stale data
+pub async fn load_cache_snapshot(cache: &CacheService, config: &RedisConfig) {
+    let Some(path) = config.snapshot_path.as_deref() else {
+        return;
+    };
+    if !cache.is_connected() {
+        return;
+    }
+
+    let contents = match tokio::fs::read(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            warn!("Failed to read cache snapshot '{}': {}", path, e);
+            return;
+        }
+    };
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        warn!("Failed to remove cache snapshot '{}': {}", path, e);
+    }
+
+    let snapshot = match serde_json::from_slice::<CacheSnapshot>(&contents) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            warn!("Ignoring unreadable cache snapshot '{}': {}", path, e);
+            return;
+        }
+    };
+
+    let age_ms = (Utc::now() - snapshot.created_at).num_milliseconds().max(0);
+    if age_ms > config.snapshot_max_age_seconds * 1000 {
+        info!("Ignoring cache snapshot '{}' taken {}s ago", path, age_ms / 1000);
+        return;
+    }
+
+    let mut restored = 0;
+    for mut entry in snapshot.entries {
+        // Time spent down counts against each entry's remaining lifetime
+        if let Some(ttl) = entry.ttl_ms {
+            let remaining = ttl - age_ms;
+            if remaining <= 0 {
+                continue;
+            }
+            entry.ttl_ms = Some(remaining);
+        }
+        if cache.restore_entry(&entry).await {
+            restored += 1;
+        }
+    }
+
+    info!("Preloaded {} cache entries from snapshot '{}'", restored, path);
+}
+
+// Snapshots hold user profiles, so keep them readable by the service account only
+async fn write_private(path: &str, contents: &[u8]) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(path).await?;
+    file.write_all(contents).await?;
+    file.sync_all().await
+}
\ No newline at end of file
@@ -1,11 +1,85 @@
+use chrono::Utc;
 use sqlx::PgPool;
-use std::time::Duration;
-use tokio::time::interval;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::{interval, timeout};
 use tracing::{info, warn, error};
 
+use crate::config::PoolMonitorConfig;
+use crate::utils::{LogAlertSink, PoolAlert, PoolAlertKind, PoolAlertSink, WebhookAlertSink};
+
+const DATABASE_PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PoolSample {
+    size: u32,
+    idle: u32,
+    max_connections: u32,
+    consecutive_exhausted: u32,
+    exhausted_checks: u64,
+    failed_health_checks: u64,
+    ping_seconds: Option<f64>,
+}
+
+// The monitor's latest view of the primary pool, rendered next to the statement metrics
+#[derive(Clone, Default)]
+pub struct PoolMetrics {
+    sample: Arc<Mutex<Option<PoolSample>>>,
+}
+
+impl PoolMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, sample: PoolSample) {
+        if let Ok(mut current) = self.sample.lock() {
+            *current = Some(sample);
+        }
+    }
+
+    fn last(&self) -> PoolSample {
+        self.sample.lock().ok().and_then(|sample| *sample).unwrap_or_default()
+    }
+
+    // Prometheus text exposition format, empty until the first check has run
+    pub fn render(&self) -> String {
+        let Some(sample) = self.sample.lock().ok().and_then(|sample| *sample) else {
+            return String::new();
+        };
+
+        let mut output = String::new();
+        let series = [
+            ("db_pool_connections", "gauge", "Connections currently open in the pool", sample.size as f64),
+            ("db_pool_idle_connections", "gauge", "Open connections not checked out", sample.idle as f64),
+            ("db_pool_max_connections", "gauge", "Most connections the pool may open", sample.max_connections as f64),
+            ("db_pool_exhausted_consecutive_checks", "gauge", "Checks in a row that found the pool exhausted", sample.consecutive_exhausted as f64),
+            ("db_pool_exhausted_checks_total", "counter", "Checks that found the pool exhausted", sample.exhausted_checks as f64),
+            ("db_health_check_failures_total", "counter", "Health check queries that failed or timed out", sample.failed_health_checks as f64),
+        ];
+
+        for (name, kind, help, value) in series {
+            let _ = writeln!(output, "# HELP {} {}", name, help);
+            let _ = writeln!(output, "# TYPE {} {}", name, kind);
+            let _ = writeln!(output, "{} {}", name, value);
+        }
+        if let Some(seconds) = sample.ping_seconds {
+            let _ = writeln!(output, "# HELP db_health_check_latency_seconds Round trip of the last successful health check");
+            let _ = writeln!(output, "# TYPE db_health_check_latency_seconds gauge");
+            let _ = writeln!(output, "db_health_check_latency_seconds {}", seconds);
+        }
+
+        output
+    }
+}
+
 pub struct ConnectionMonitor {
     pool: PgPool,
     check_interval: Duration,
+    metrics: PoolMetrics,
+    alert_after_checks: u32,
+    alert_sinks: Vec<Arc<dyn PoolAlertSink>>,
 }
 
 impl ConnectionMonitor {
@@ -13,9 +87,24 @@ impl ConnectionMonitor {
         Self {
             pool,
             check_interval: Duration::from_secs(check_interval_secs),
+            metrics: PoolMetrics::new(),
+            alert_after_checks: 3,
+            alert_sinks: Vec::new(),
         }
     }
 
+    pub fn with_metrics(mut self, metrics: PoolMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    // Alerts once the pool has been exhausted for this many checks in a row, and again on recovery
+    pub fn with_alerts(mut self, after_checks: u32, sinks: Vec<Arc<dyn PoolAlertSink>>) -> Self {
+        self.alert_after_checks = after_checks.max(1);
+        self.alert_sinks = sinks;
+        self
+    }
+
     pub async fn start_monitoring(&self) {
         let mut interval = interval(self.check_interval);
         
@@ -27,34 +116,95 @@ impl ConnectionMonitor {
 
     async fn check_pool_health(&self) {
         let pool_size = self.pool.size();
-        let idle_connections = self.pool.num_idle();
+        let idle_connections = self.pool.num_idle() as u32;
+        let max_connections = self.pool.options().get_max_connections();
         
         info!(
             "Connection Pool Status - Total: {}, Idle: {}, Active: {}",
             pool_size,
             idle_connections,
-            pool_size.saturating_sub(idle_connections as u32)
+            pool_size.saturating_sub(idle_connections)
         );
 
-        // Check if pool is under stress
-        if idle_connections == 0 && pool_size > 0 {
+        let previous = self.metrics.last();
+        let mut sample = PoolSample {
+            size: pool_size,
+            idle: idle_connections,
+            max_connections,
+            ..previous
+        };
+
+        // Exhausted only once the pool can't grow either; below the cap a new connection is opened
+        let exhausted = idle_connections == 0 && pool_size >= max_connections && pool_size > 0;
+        if exhausted {
             warn!("Connection pool exhausted! All connections are in use.");
+            sample.consecutive_exhausted += 1;
+            sample.exhausted_checks += 1;
+        } else {
+            sample.consecutive_exhausted = 0;
         }
 
         // Test connection health
-        match sqlx::query("SELECT 1").fetch_one(&self.pool).await {
-            Ok(_) => {
-                info!("Database connection health check: OK");
+        match ping_database(&self.pool, DATABASE_PING_TIMEOUT).await {
+            Ok(latency) => {
+                info!("Database connection health check: OK ({}ms)", latency.as_millis());
+                sample.ping_seconds = Some(latency.as_secs_f64());
             }
             Err(e) => {
                 error!("Database connection health check failed: {}", e);
+                sample.failed_health_checks += 1;
+            }
+        }
+
+        self.metrics.update(sample);
+
+        let alert = if sample.consecutive_exhausted == self.alert_after_checks {
+            Some((PoolAlertKind::Exhausted, sample.consecutive_exhausted))
+        } else if !exhausted && previous.consecutive_exhausted >= self.alert_after_checks {
+            Some((PoolAlertKind::Recovered, previous.consecutive_exhausted))
+        } else {
+            None
+        };
+
+        if let Some((kind, consecutive_checks)) = alert {
+            let alert = PoolAlert {
+                kind,
+                consecutive_checks,
+                size: pool_size,
+                idle: idle_connections,
+                max_connections,
+                at: Utc::now(),
+            };
+            for sink in &self.alert_sinks {
+                sink.notify(&alert).await;
             }
         }
     }
 }
 
-pub async fn start_connection_monitoring(pool: PgPool) {
-    let monitor = ConnectionMonitor::new(pool, 30); // Check every 30 seconds
+// Round trip of a trivial query, or why it failed
+pub async fn ping_database(pool: &PgPool, limit: Duration) -> Result<Duration, String> {
+    let started = Instant::now();
+    match timeout(limit, sqlx::query("SELECT 1").fetch_one(pool)).await {
+        Ok(Ok(_)) => Ok(started.elapsed()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("no response within {}ms", limit.as_millis())),
+    }
+}
+
+// Alerts always go to the log, and to the webhook when one is configured
+pub async fn start_connection_monitoring(pool: PgPool, config: &PoolMonitorConfig, metrics: PoolMetrics) {
+    let mut sinks: Vec<Arc<dyn PoolAlertSink>> = vec![Arc::new(LogAlertSink)];
+    if let Some(url) = config.alert_webhook_url.as_deref() {
+        match WebhookAlertSink::new(url) {
+            Ok(webhook) => sinks.push(Arc::new(webhook)),
+            Err(e) => error!("Pool alert webhook disabled: {}", e),
+        }
+    }
+
+    let monitor = ConnectionMonitor::new(pool, config.check_interval_seconds)
+        .with_metrics(metrics)
+        .with_alerts(config.alert_after_checks, sinks);
     
     tokio::spawn(async move {
         monitor.start_monitoring().await;
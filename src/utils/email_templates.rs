@@ -0,0 +1,206 @@
+use chrono::{Datelike, NaiveDate};
+use handlebars::Handlebars;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::utils::AppError;
+
+pub const EMAIL_TEMPLATE_EMAIL_CHANGE: &str = "email_change_verification";
+pub const EMAIL_TEMPLATE_SESSION_REUSE: &str = "session_reuse_alert";
+pub const EMAIL_TEMPLATE_MONTHLY_DIGEST: &str = "monthly_digest";
+pub const EMAIL_TEMPLATE_REMINDERS: &str = "transaction_reminders";
+
+// Every template must exist in this locale, the others fall back to it
+pub const DEFAULT_EMAIL_LOCALE: &str = "en";
+
+macro_rules! email_template {
+    ($locale:literal, $name:literal) => {
+        (
+            $name,
+            $locale,
+            include_str!(concat!("../../templates/emails/", $locale, "/", $name, ".subject.hbs")),
+            include_str!(concat!("../../templates/emails/", $locale, "/", $name, ".body.hbs")),
+        )
+    };
+}
+
+// (name, locale, subject, body), compiled into the binary so a deploy can't ship without them
+const TEMPLATES: &[(&str, &str, &str, &str)] = &[
+    email_template!("en", "email_change_verification"),
+    email_template!("en", "session_reuse_alert"),
+    email_template!("en", "monthly_digest"),
+    email_template!("en", "transaction_reminders"),
+    email_template!("id", "email_change_verification"),
+    email_template!("id", "session_reuse_alert"),
+    email_template!("id", "monthly_digest"),
+    email_template!("id", "transaction_reminders"),
+];
+
+const ID_MONTHS: [&str; 12] = [
+    "Januari", "Februari", "Maret", "April", "Mei", "Juni",
+    "Juli", "Agustus", "September", "Oktober", "November", "Desember",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedEmail {
+    pub template: String,
+    pub locale: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailTemplateInfo {
+    pub name: String,
+    pub locales: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct EmailTemplates {
+    registry: Arc<Handlebars<'static>>,
+}
+
+impl EmailTemplates {
+    pub fn new() -> Result<Self, AppError> {
+        let mut registry = Handlebars::new();
+        // Plain-text mail, and a typo in a placeholder should fail loudly rather than render blank
+        registry.register_escape_fn(handlebars::no_escape);
+        registry.set_strict_mode(true);
+
+        for (name, locale, subject, body) in TEMPLATES {
+            registry
+                .register_template_string(&subject_key(name, locale), subject.trim())
+                .and_then(|_| registry.register_template_string(&body_key(name, locale), *body))
+                .map_err(|e| AppError::InternalServerError(format!("Invalid email template {}/{}: {}", locale, name, e)))?;
+        }
+
+        if let Some(name) = template_names().find(|name| !registry.has_template(&subject_key(name, DEFAULT_EMAIL_LOCALE))) {
+            return Err(AppError::InternalServerError(format!(
+                "Email template {} has no {} variant",
+                name, DEFAULT_EMAIL_LOCALE
+            )));
+        }
+
+        Ok(Self {
+            registry: Arc::new(registry),
+        })
+    }
+
+    // Tries the full locale, then its language, then the default, so "id-ID" finds "id"
+    pub fn render<T: Serialize>(&self, name: &str, locale: &str, context: &T) -> Result<RenderedEmail, AppError> {
+        let locale = self
+            .resolve_locale(name, locale)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown email template '{}'", name)))?;
+
+        let render = |key: String| {
+            self.registry
+                .render(&key, context)
+                .map_err(|e| AppError::InternalServerError(format!("Failed to render email template {}: {}", key, e)))
+        };
+
+        Ok(RenderedEmail {
+            template: name.to_string(),
+            subject: render(subject_key(name, &locale))?.trim().to_string(),
+            body: render(body_key(name, &locale))?,
+            locale,
+        })
+    }
+
+    // Renders a template against built-in sample data, for checking copy without sending anything
+    pub fn preview(&self, name: &str, locale: &str) -> Result<RenderedEmail, AppError> {
+        let context = sample_context(name)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown email template '{}'", name)))?;
+        self.render(name, locale, &context)
+    }
+
+    pub fn list(&self) -> Vec<EmailTemplateInfo> {
+        template_names()
+            .map(|name| EmailTemplateInfo {
+                name: name.to_string(),
+                locales: TEMPLATES
+                    .iter()
+                    .filter(|(template, ..)| *template == name)
+                    .map(|(_, locale, ..)| locale.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn resolve_locale(&self, name: &str, locale: &str) -> Option<String> {
+        let locale = locale.to_lowercase();
+        let language = locale.split(['-', '_']).next().unwrap_or_default().to_string();
+
+        [locale, language, DEFAULT_EMAIL_LOCALE.to_string()]
+            .into_iter()
+            .find(|candidate| self.registry.has_template(&subject_key(name, candidate)))
+    }
+}
+
+// Month and year as written in the recipient's language
+pub fn month_label(locale: &str, month: NaiveDate) -> String {
+    if locale.to_lowercase().starts_with("id") {
+        format!("{} {}", ID_MONTHS[month.month0() as usize], month.year())
+    } else {
+        month.format("%B %Y").to_string()
+    }
+}
+
+fn template_names() -> impl Iterator<Item = &'static str> {
+    let mut names: Vec<&'static str> = TEMPLATES.iter().map(|(name, ..)| *name).collect();
+    names.sort_unstable();
+    names.dedup();
+    names.into_iter()
+}
+
+fn subject_key(name: &str, locale: &str) -> String {
+    format!("{}/{}.subject", locale, name)
+}
+
+fn body_key(name: &str, locale: &str) -> String {
+    format!("{}/{}.body", locale, name)
+}
+
+fn sample_context(name: &str) -> Option<Value> {
+    let context = match name {
+        EMAIL_TEMPLATE_EMAIL_CHANGE => json!({
+            "name": "Alex",
+            "code": "Xr4q9LmT2vB7nK1pW8sZ3cD6fH0jY5uA",
+            "expires_in_hours": 24,
+        }),
+        EMAIL_TEMPLATE_SESSION_REUSE => json!({
+            "name": "Alex",
+            "ip_address": "203.0.113.7",
+            "user_agent": "Mozilla/5.0 (X11; Linux x86_64)",
+        }),
+        EMAIL_TEMPLATE_MONTHLY_DIGEST => json!({
+            "name": "Alex",
+            "period": "September 2026",
+            "currency": "IDR",
+            "income": "12500000.00",
+            "expenses": "9800000.00",
+            "net": "2700000.00",
+            "top_categories": [
+                { "category": "Food", "amount": "3200000.00" },
+                { "category": "Transport", "amount": "1450000.00" },
+            ],
+            "budgets": [
+                { "category": "Food", "spent": "3200000.00", "target": "3000000.00", "used_percent": "107", "over_budget": true, "period_end": "2026-09-30" },
+                { "category": null, "spent": "9800000.00", "target": "11000000.00", "used_percent": "89", "over_budget": false, "period_end": "2026-09-30" },
+            ],
+        }),
+        EMAIL_TEMPLATE_REMINDERS => json!({
+            "name": "Alex",
+            "single": false,
+            "count": 2,
+            "first_note": "Chase the refund for the cancelled flight",
+            "reminders": [
+                { "note": "Chase the refund for the cancelled flight", "remind_on": "2026-10-01", "description": "Airline ticket", "transaction_date": "2026-09-12", "amount": "-1850000.00" },
+                { "note": "Check the disputed card charge", "remind_on": "2026-10-02", "description": "Online store", "transaction_date": "2026-09-20", "amount": "-420000.00" },
+            ],
+        }),
+        _ => return None,
+    };
+
+    Some(context)
+}
\ No newline at end of file
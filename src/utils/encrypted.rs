@@ -0,0 +1,234 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{Decode, Encode, Postgres, Type};
+
+use crate::config::EncryptionConfig;
+use crate::utils::AppError;
+
+// Sealed layout: version | key id | nonce | ciphertext and tag. The header is bound in as
+// associated data, so it can't be swapped without failing the tag check
+const FORMAT_VERSION: u8 = 1;
+const KEY_ID_BYTES: usize = 4;
+const NONCE_BYTES: usize = 12;
+const TAG_BYTES: usize = 16;
+const HEADER_BYTES: usize = 1 + KEY_ID_BYTES;
+
+struct FieldKey {
+    id: [u8; KEY_ID_BYTES],
+    cipher: Aes256Gcm,
+}
+
+// Seals and opens Encrypted columns with the keys from EncryptionConfig. Built once by
+// build_app and handed to the repositories that store sensitive values.
+#[derive(Clone)]
+pub struct FieldCipher {
+    // Current key first, then the retired ones
+    keys: Arc<Vec<FieldKey>>,
+}
+
+impl FieldCipher {
+    pub fn new(config: &EncryptionConfig) -> Result<Self, String> {
+        let keys = config
+            .decoded_keys()?
+            .into_iter()
+            .map(|key| {
+                // Identifies the key without revealing it, so rows name the key that sealed them
+                let digest = Sha256::digest(key);
+                let mut id = [0u8; KEY_ID_BYTES];
+                id.copy_from_slice(&digest[..KEY_ID_BYTES]);
+                FieldKey { id, cipher: Aes256Gcm::new(&key.into()) }
+            })
+            .collect();
+
+        Ok(Self { keys: Arc::new(keys) })
+    }
+
+    pub fn is_configured(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    pub fn seal<T: Serialize>(&self, value: &T) -> Result<Encrypted<T>, AppError> {
+        let plaintext = serde_json::to_vec(value)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to serialize field: {}", e)))?;
+        Ok(Encrypted::from_sealed(self.seal_bytes(&plaintext)?))
+    }
+
+    // Fails rather than fall back to plaintext when no key is configured
+    pub fn open<T: DeserializeOwned>(&self, value: &Encrypted<T>) -> Result<T, AppError> {
+        let plaintext = self.open_bytes(&value.sealed)?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to deserialize field: {}", e)))
+    }
+
+    // Whether the value was sealed with a retired key and should be sealed again
+    pub fn needs_reseal<T>(&self, value: &Encrypted<T>) -> bool {
+        self.keys.first().is_some_and(|key| value.key_id() != Some(&key.id[..]))
+    }
+
+    fn seal_bytes(&self, plaintext: &[u8]) -> Result<Vec<u8>, AppError> {
+        let key = self
+            .keys
+            .first()
+            .ok_or_else(|| AppError::InternalServerError("FIELD_ENCRYPTION_KEY is not configured".to_string()))?;
+
+        let mut sealed = Vec::with_capacity(HEADER_BYTES + NONCE_BYTES + plaintext.len() + TAG_BYTES);
+        sealed.push(FORMAT_VERSION);
+        sealed.extend_from_slice(&key.id);
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = key
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &sealed })
+            .map_err(|_| AppError::InternalServerError("Failed to encrypt field".to_string()))?;
+
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open_bytes(&self, sealed: &[u8]) -> Result<Vec<u8>, AppError> {
+        if sealed.len() < HEADER_BYTES + NONCE_BYTES || sealed[0] != FORMAT_VERSION {
+            return Err(AppError::InternalServerError("Encrypted field is malformed".to_string()));
+        }
+
+        let (header, rest) = sealed.split_at(HEADER_BYTES);
+        let (nonce, ciphertext) = rest.split_at(NONCE_BYTES);
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.id == header[1..])
+            .ok_or_else(|| AppError::InternalServerError("Encrypted field was sealed with an unknown key".to_string()))?;
+
+        key.cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+            .map_err(|_| AppError::InternalServerError("Encrypted field failed authentication".to_string()))
+    }
+}
+
+// A BYTEA column holding a serde value sealed with AES-256-GCM. It carries only the sealed
+// bytes; FieldCipher turns values into it and back, so nothing is encrypted without the keys
+// from configuration.
+pub struct Encrypted<T> {
+    sealed: Vec<u8>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T> Encrypted<T> {
+    fn from_sealed(sealed: Vec<u8>) -> Self {
+        Self { sealed, value: PhantomData }
+    }
+
+    fn key_id(&self) -> Option<&[u8]> {
+        self.sealed.get(1..HEADER_BYTES)
+    }
+}
+
+impl<T> Clone for Encrypted<T> {
+    fn clone(&self) -> Self {
+        Self::from_sealed(self.sealed.clone())
+    }
+}
+
+// Keeps the value out of logs and error messages
+impl<T> fmt::Debug for Encrypted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Encrypted([redacted])")
+    }
+}
+
+impl<T> Type<Postgres> for Encrypted<T> {
+    fn type_info() -> PgTypeInfo {
+        <Vec<u8> as Type<Postgres>>::type_info()
+    }
+}
+
+impl<T> Encode<'_, Postgres> for Encrypted<T> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <Vec<u8> as Encode<Postgres>>::encode_by_ref(&self.sealed, buf)
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for Encrypted<T> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::from_sealed(<Vec<u8> as Decode<Postgres>>::decode(value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const NEW_KEY: &str = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+
+    fn cipher(key: &str, previous_keys: &[&str]) -> FieldCipher {
+        FieldCipher::new(&EncryptionConfig {
+            key: Some(key.to_string()),
+            previous_keys: previous_keys.iter().map(|key| key.to_string()).collect(),
+        })
+        .expect("valid keys")
+    }
+
+    #[test]
+    fn sealed_values_open_to_the_original() {
+        let cipher = cipher(NEW_KEY, &[]);
+        let sealed = cipher.seal(&"fcm-token-1".to_string()).expect("seal");
+
+        assert!(!sealed.sealed.windows(11).any(|window| window == b"fcm-token-1"));
+        assert_eq!(cipher.open(&sealed).expect("open"), "fcm-token-1");
+        assert!(!cipher.needs_reseal(&sealed));
+    }
+
+    #[test]
+    fn sealing_twice_gives_different_ciphertexts() {
+        let cipher = cipher(NEW_KEY, &[]);
+        let first = cipher.seal(&42u32).expect("seal");
+        let second = cipher.seal(&42u32).expect("seal");
+
+        assert_ne!(first.sealed, second.sealed);
+    }
+
+    #[test]
+    fn values_sealed_before_a_rotation_open_with_the_retired_key() {
+        let sealed = cipher(OLD_KEY, &[]).seal(&"secret".to_string()).expect("seal");
+        let rotated = cipher(NEW_KEY, &[OLD_KEY]);
+
+        assert_eq!(rotated.open(&sealed).expect("open"), "secret");
+        assert!(rotated.needs_reseal(&sealed));
+        assert!(!rotated.needs_reseal(&rotated.seal(&"secret".to_string()).expect("seal")));
+    }
+
+    #[test]
+    fn values_sealed_with_a_dropped_key_do_not_open() {
+        let sealed = cipher(OLD_KEY, &[]).seal(&"secret".to_string()).expect("seal");
+
+        assert!(cipher(NEW_KEY, &[]).open(&sealed).is_err());
+    }
+
+    #[test]
+    fn tampered_values_fail_authentication() {
+        let cipher = cipher(NEW_KEY, &[]);
+        let mut sealed = cipher.seal(&"secret".to_string()).expect("seal");
+        let last = sealed.sealed.len() - 1;
+        sealed.sealed[last] ^= 1;
+
+        assert!(cipher.open(&sealed).is_err());
+    }
+
+    #[test]
+    fn sealing_without_a_key_fails() {
+        let cipher = FieldCipher::new(&EncryptionConfig::default()).expect("no keys");
+
+        assert!(!cipher.is_configured());
+        assert!(cipher.seal(&"secret".to_string()).is_err());
+    }
+}
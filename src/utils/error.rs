@@ -3,9 +3,54 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use serde::Serialize;
 use std::fmt;
 
+use crate::utils::ApiResponse;
+
+// Stable machine-readable codes. Clients branch on these, so never rename one.
+pub mod codes {
+    pub const BAD_REQUEST: &str = "BAD_REQUEST";
+    pub const VALIDATION_FAILED: &str = "VALIDATION_FAILED";
+    pub const NOT_FOUND: &str = "NOT_FOUND";
+    pub const UNAUTHORIZED: &str = "UNAUTHORIZED";
+    pub const FORBIDDEN: &str = "FORBIDDEN";
+    pub const CONFLICT: &str = "CONFLICT";
+    pub const RATE_LIMITED: &str = "RATE_LIMITED";
+    pub const PAYLOAD_TOO_LARGE: &str = "PAYLOAD_TOO_LARGE";
+    pub const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+    pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
+
+    pub const INVALID_DATE_FORMAT: &str = "INVALID_DATE_FORMAT";
+    pub const INVALID_DATE_RANGE: &str = "INVALID_DATE_RANGE";
+    pub const INVALID_AMOUNT: &str = "INVALID_AMOUNT";
+    pub const INVALID_AMOUNT_RANGE: &str = "INVALID_AMOUNT_RANGE";
+    pub const POCKET_ARCHIVED: &str = "POCKET_ARCHIVED";
+    pub const EMAIL_TAKEN: &str = "EMAIL_TAKEN";
+    pub const INVALID_CREDENTIALS: &str = "INVALID_CREDENTIALS";
+    pub const ACCOUNT_LOCKED: &str = "ACCOUNT_LOCKED";
+    pub const TOKEN_REVOKED: &str = "TOKEN_REVOKED";
+    pub const ALREADY_RUNNING: &str = "ALREADY_RUNNING";
+    pub const RESOURCE_BUSY: &str = "RESOURCE_BUSY";
+    pub const STALE_VERSION: &str = "STALE_VERSION";
+}
+
+// One failed check on one request field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+// The machine-readable half of an error response
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorPayload {
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+}
+
 #[derive(Debug)]
 pub enum AppError {
     DatabaseError(String),
@@ -16,6 +61,80 @@ pub enum AppError {
     Conflict(String),
     InternalServerError(String),
     BadRequest(String),
+    TooManyRequests(String),
+    PayloadTooLarge(String),
+    RequestTimeout(String),
+    // Any of the above with a more specific code and optional field-level details
+    Detailed {
+        code: &'static str,
+        details: Vec<FieldError>,
+        inner: Box<AppError>,
+    },
+}
+
+impl AppError {
+    // A validation failure pinned to one request field
+    pub fn invalid_field(field: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        AppError::ValidationError(message.clone()).with_details(vec![FieldError {
+            field: field.to_string(),
+            code: "invalid".to_string(),
+            message,
+        }])
+    }
+
+    pub fn with_code(self, code: &'static str) -> Self {
+        match self {
+            AppError::Detailed { details, inner, .. } => AppError::Detailed { code, details, inner },
+            other => AppError::Detailed { code, details: Vec::new(), inner: Box::new(other) },
+        }
+    }
+
+    pub fn with_details(self, details: Vec<FieldError>) -> Self {
+        match self {
+            AppError::Detailed { code, inner, .. } => AppError::Detailed { code, details, inner },
+            other => AppError::Detailed { code: other.code(), details, inner: Box::new(other) },
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::DatabaseError(_) | AppError::InternalServerError(_) => codes::INTERNAL_ERROR,
+            AppError::ValidationError(_) => codes::VALIDATION_FAILED,
+            AppError::NotFound(_) => codes::NOT_FOUND,
+            AppError::Unauthorized(_) => codes::UNAUTHORIZED,
+            AppError::Forbidden(_) => codes::FORBIDDEN,
+            AppError::Conflict(_) => codes::CONFLICT,
+            AppError::BadRequest(_) => codes::BAD_REQUEST,
+            AppError::TooManyRequests(_) => codes::RATE_LIMITED,
+            AppError::PayloadTooLarge(_) => codes::PAYLOAD_TOO_LARGE,
+            AppError::RequestTimeout(_) => codes::REQUEST_TIMEOUT,
+            AppError::Detailed { code, .. } => code,
+        }
+    }
+
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            AppError::DatabaseError(msg) => {
+                tracing::error!("Database error: {}", msg);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+            AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::InternalServerError(msg) => {
+                tracing::error!("Internal server error: {}", msg);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg.clone()),
+            AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.clone()),
+            AppError::RequestTimeout(msg) => (StatusCode::REQUEST_TIMEOUT, msg.clone()),
+            AppError::Detailed { inner, .. } => inner.status_and_message(),
+        }
+    }
 }
 
 impl fmt::Display for AppError {
@@ -29,6 +148,10 @@ impl fmt::Display for AppError {
             AppError::Conflict(msg) => write!(f, "Conflict: {}", msg),
             AppError::InternalServerError(msg) => write!(f, "Internal server error: {}", msg),
             AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            AppError::TooManyRequests(msg) => write!(f, "Too many requests: {}", msg),
+            AppError::PayloadTooLarge(msg) => write!(f, "Payload too large: {}", msg),
+            AppError::RequestTimeout(msg) => write!(f, "Request timeout: {}", msg),
+            AppError::Detailed { inner, .. } => inner.fmt(f),
         }
     }
 }
@@ -47,42 +170,38 @@ impl From<sqlx::Error> for AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match &self {
-            AppError::DatabaseError(msg) => {
-                tracing::error!("Database error: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
-            }
-            AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
-            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
-            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
-            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
-            AppError::InternalServerError(msg) => {
-                tracing::error!("Internal server error: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
-            }
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+        let (status, error_message) = self.status_and_message();
+        let payload = ErrorPayload {
+            code: self.code(),
+            details: match self {
+                AppError::Detailed { details, .. } => details,
+                _ => Vec::new(),
+            },
         };
 
-        let body = Json(json!({
-            "error": error_message
-        }));
-
-        (status, body).into_response()
+        // Errors use the same envelope as successful responses
+        (status, Json(ApiResponse::<()>::failure(error_message, payload))).into_response()
     }
 }
 
 // Helper function to convert validation errors
 pub fn validation_error(errors: validator::ValidationErrors) -> AppError {
-    let error_messages: Vec<String> = errors
+    let details: Vec<FieldError> = errors
         .field_errors()
         .iter()
         .flat_map(|(field, errors)| {
-            errors.iter().map(move |error| {
-                format!("{}: {}", field, error.message.as_ref().unwrap_or(&"Invalid value".into()))
+            errors.iter().map(move |error| FieldError {
+                field: field.to_string(),
+                code: error.code.to_string(),
+                message: error.message.as_ref().map(|m| m.to_string()).unwrap_or_else(|| "Invalid value".to_string()),
             })
         })
         .collect();
+    let message = details
+        .iter()
+        .map(|detail| format!("{}: {}", detail.field, detail.message))
+        .collect::<Vec<_>>()
+        .join(", ");
 
-    AppError::ValidationError(error_messages.join(", "))
+    AppError::ValidationError(message).with_details(details)
 }
\ No newline at end of file
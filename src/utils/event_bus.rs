@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+// Events buffered per subscriber before a slow socket starts missing them
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveResource {
+    Transaction,
+    Pocket,
+    Budget,
+    // An expense far above its category's usual amount, id is the transaction
+    Anomaly,
+    // A budget's spending reached one of its alert thresholds, id is the budget
+    BudgetAlert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveEvent {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub resource: LiveResource,
+    pub action: LiveAction,
+    // Transactions and budgets use numeric ids, pockets use UUIDs
+    pub id: String,
+    pub at: DateTime<Utc>,
+}
+
+// In-process fan-out of data changes to open /ws connections. Each instance only
+// sees changes made through itself, so clients behind a load balancer still need
+// to refetch on reconnect.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, user_id: Uuid, resource: LiveResource, action: LiveAction, id: impl ToString) {
+        // Sending only fails when nobody is connected, which is the common case
+        let _ = self.sender.send(LiveEvent {
+            user_id,
+            resource,
+            action,
+            id: id.to_string(),
+            at: Utc::now(),
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
\ No newline at end of file
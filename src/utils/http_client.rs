@@ -0,0 +1,118 @@
+use axum::http::StatusCode;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsConnector};
+
+pub type HttpError = Box<dyn std::error::Error + Send + Sync>;
+
+// Where outbound requests go, HTTPS on 443 unless a URL says otherwise
+#[derive(Debug, Clone)]
+pub struct HttpEndpoint {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+}
+
+impl HttpEndpoint {
+    pub fn https(host: &str) -> Self {
+        Self {
+            host: host.to_string(),
+            port: 443,
+            tls: true,
+        }
+    }
+
+    // An http:// or https:// URL split into the endpoint and the path
+    pub fn parse(url: &str) -> Result<(Self, String), String> {
+        let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(format!("URL '{}' must start with http:// or https://", url));
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| format!("URL '{}' has an invalid port", url))?),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+        if host.is_empty() {
+            return Err(format!("URL '{}' has no host", url));
+        }
+
+        Ok((
+            Self {
+                host: host.to_string(),
+                port,
+                tls,
+            },
+            path.to_string(),
+        ))
+    }
+}
+
+pub async fn http_get(endpoint: &HttpEndpoint, path: &str, headers: &[(&str, &str)]) -> Result<(StatusCode, String), HttpError> {
+    send(endpoint, "GET", path, headers, None).await
+}
+
+pub async fn http_post(
+    endpoint: &HttpEndpoint,
+    path: &str,
+    headers: &[(&str, &str)],
+    content_type: &str,
+    body: &str,
+) -> Result<(StatusCode, String), HttpError> {
+    send(endpoint, "POST", path, headers, Some((content_type, body))).await
+}
+
+// HTTP/1.0 closes the connection after the response and never chunks it, so the body is
+// everything after the headers once the stream ends
+async fn send(
+    endpoint: &HttpEndpoint,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: Option<(&str, &str)>,
+) -> Result<(StatusCode, String), HttpError> {
+    let mut request = format!("{} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: fintrack\r\n", method, path, endpoint.host);
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    match body {
+        Some((content_type, body)) => request.push_str(&format!(
+            "Content-Type: {}\r\nContent-Length: {}\r\n\r\n{}",
+            content_type,
+            body.len(),
+            body
+        )),
+        None => request.push_str("\r\n"),
+    }
+
+    let stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port)).await?;
+    let response = if endpoint.tls {
+        let connector = TlsConnector::from(native_tls::TlsConnector::new()?);
+        exchange(connector.connect(&endpoint.host, stream).await?, &request).await?
+    } else {
+        exchange(stream, &request).await?
+    };
+
+    let (head, body) = response.split_once("\r\n\r\n").ok_or("malformed response")?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .ok_or("malformed status line")?;
+    Ok((status, body.to_string()))
+}
+
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S, request: &str) -> std::io::Result<String> {
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await?;
+    Ok(String::from_utf8_lossy(&response).into_owned())
+}
\ No newline at end of file
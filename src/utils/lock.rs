@@ -0,0 +1,124 @@
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::utils::{AppError, CacheService, codes, generate_token};
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+const LOCK_TOKEN_LENGTH: usize = 32;
+
+// Reaggregation and restatement both rewrite a user's balance snapshots, one at a time
+pub const SNAPSHOT_LOCK_TTL: Duration = Duration::from_secs(60 * 60);
+pub const SNAPSHOT_LOCK_WAIT: Duration = Duration::from_secs(10 * 60);
+
+// A named critical section shared by every app instance. Holders take a lease with
+// SET NX PX and a random token, and only the token's owner can release or extend it,
+// so a holder that outlived its lease can't free someone else's. A crashed holder blocks
+// others for at most the lease TTL. Without Redis there is nothing to coordinate with:
+// every acquire succeeds and the database's row locks are the only guard left.
+#[derive(Clone)]
+pub struct DistributedLock {
+    cache: CacheService,
+}
+
+impl DistributedLock {
+    pub fn new(cache: CacheService) -> Self {
+        Self { cache }
+    }
+
+    // None while another holder has the lock
+    pub async fn try_acquire(&self, name: &str, ttl: Duration) -> Option<LockGuard> {
+        let key = lock_key(name);
+        let token = generate_token(LOCK_TOKEN_LENGTH);
+
+        match self.cache.claim_token(&key, &token, ttl).await {
+            Some(true) => Some(LockGuard {
+                lease: Some(Lease {
+                    cache: self.cache.clone(),
+                    key,
+                    token,
+                }),
+            }),
+            Some(false) => None,
+            None => Some(LockGuard { lease: None }),
+        }
+    }
+
+    // Retries until `wait` runs out, then gives up with RESOURCE_BUSY
+    pub async fn acquire(&self, name: &str, ttl: Duration, wait: Duration) -> Result<LockGuard, AppError> {
+        let deadline = Instant::now() + wait;
+        loop {
+            if let Some(guard) = self.try_acquire(name, ttl).await {
+                return Ok(guard);
+            }
+            if Instant::now() >= deadline {
+                // Lock names carry user ids, so they stay out of the response
+                debug!("Gave up waiting for lock {}", name);
+                return Err(AppError::Conflict("Another operation is in progress, try again shortly".to_string())
+                    .with_code(codes::RESOURCE_BUSY));
+            }
+            sleep(RETRY_INTERVAL).await;
+        }
+    }
+}
+
+struct Lease {
+    cache: CacheService,
+    key: String,
+    token: String,
+}
+
+// Held until released. A guard dropped without release, e.g. by an early `?` return,
+// frees the lock in the background instead of leaving it to expire.
+pub struct LockGuard {
+    // None when acquired without Redis
+    lease: Option<Lease>,
+}
+
+impl LockGuard {
+    // Pushes the expiry out for long-running work; false when the lease was already lost
+    pub async fn extend(&self, ttl: Duration) -> bool {
+        match &self.lease {
+            Some(lease) => lease.cache.extend_token(&lease.key, &lease.token, ttl).await.unwrap_or(false),
+            None => true,
+        }
+    }
+
+    pub async fn release(mut self) {
+        if let Some(lease) = self.lease.take() {
+            lease.release().await;
+        }
+    }
+}
+
+impl Lease {
+    async fn release(&self) {
+        if self.cache.release_token(&self.key, &self.token).await == Some(false) {
+            warn!("Lock '{}' expired before it was released", self.key);
+        }
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let Some(lease) = self.lease.take() else {
+            return;
+        };
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move { lease.release().await });
+        }
+    }
+}
+
+fn lock_key(name: &str) -> String {
+    format!("lock:{}", name)
+}
+
+pub fn user_snapshots_lock(user_id: &Uuid) -> String {
+    format!("snapshots:{}", user_id)
+}
+
+pub fn pocket_balance_lock(pocket_id: &Uuid) -> String {
+    format!("pocket_balance:{}", pocket_id)
+}
\ No newline at end of file
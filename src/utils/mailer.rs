@@ -0,0 +1,106 @@
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::config::EmailConfig;
+use crate::utils::{AppError, EmailTemplates};
+
+#[derive(Clone)]
+pub struct Mailer {
+    transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
+    from: Option<Mailbox>,
+    templates: EmailTemplates,
+    enabled: bool,
+}
+
+impl Mailer {
+    pub fn new(config: &EmailConfig, templates: EmailTemplates) -> Self {
+        if !config.enabled {
+            info!("Email delivery is disabled");
+            return Self::disabled(templates);
+        }
+
+        let from = match config.from_address.parse::<Mailbox>() {
+            Ok(from) => from,
+            Err(e) => {
+                error!("Invalid EMAIL_FROM address '{}': {}", config.from_address, e);
+                warn!("Running without email delivery");
+                return Self::disabled(templates);
+            }
+        };
+
+        let builder = match AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host) {
+            Ok(builder) => builder.port(config.smtp_port),
+            Err(e) => {
+                error!("Failed to create SMTP transport: {}", e);
+                warn!("Running without email delivery");
+                return Self::disabled(templates);
+            }
+        };
+
+        let builder = match (&config.smtp_username, &config.smtp_password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username.clone(), password.clone()))
+            }
+            _ => builder,
+        };
+
+        info!("SMTP transport configured for {}:{}", config.smtp_host, config.smtp_port);
+        Self {
+            transport: Some(builder.build()),
+            from: Some(from),
+            templates,
+            enabled: true,
+        }
+    }
+
+    pub fn disabled(templates: EmailTemplates) -> Self {
+        Self {
+            transport: None,
+            from: None,
+            templates,
+            enabled: false,
+        }
+    }
+
+    // Content lives in the template registry, callers only supply the data and the recipient's locale
+    pub async fn send_template<T: Serialize>(&self, to: &str, template: &str, locale: &str, context: &T) -> Result<(), AppError> {
+        let email = self.templates.render(template, locale, context)?;
+        self.send(to, &email.subject, email.body).await
+    }
+
+    pub async fn send(&self, to: &str, subject: &str, body: String) -> Result<(), AppError> {
+        let (transport, from) = match (&self.transport, &self.from) {
+            (Some(transport), Some(from)) if self.enabled => (transport, from),
+            _ => {
+                warn!("Email delivery disabled, skipping '{}' message", subject);
+                return Ok(());
+            }
+        };
+
+        let to = to
+            .parse::<Mailbox>()
+            .map_err(|_| AppError::ValidationError("Invalid recipient email address".to_string()))?;
+
+        let message = Message::builder()
+            .from(from.clone())
+            .to(to)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to build email: {}", e)))?;
+
+        transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to send email: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
\ No newline at end of file
@@ -1,11 +1,46 @@
 pub mod cache;
+pub mod cache_snapshot;
 pub mod connection_monitor;
 pub mod error;
+pub mod event_bus;
+pub mod http_client;
+#[cfg(feature = "event-stream")]
+pub mod nats;
+pub mod mailer;
+pub mod password;
+pub mod password_hasher;
+pub mod pool_alert;
+pub mod push;
+pub mod email_templates;
+pub mod encrypted;
+pub mod lock;
 pub mod response;
+pub mod token;
+pub mod typed_cache;
 pub mod validation;
+pub mod statement_metrics;
 
-pub use cache::{CacheService, user_cache_key, user_pockets_cache_key, jwt_cache_key};
-pub use connection_monitor::{ConnectionMonitor, start_connection_monitoring};
-pub use error::{AppError, validation_error};
+pub use cache::{CacheService, CacheEntry, is_warm_cache_key, user_cache_key, user_pockets_cache_key, user_balances_cache_key, jwt_cache_key, user_tokens_valid_after_key, session_cache_key, login_failures_email_key, login_failures_ip_key, task_slot_key};
+pub use cache_snapshot::{save_cache_snapshot, load_cache_snapshot};
+pub use connection_monitor::{ConnectionMonitor, PoolMetrics, ping_database, start_connection_monitoring};
+pub use error::{AppError, ErrorPayload, FieldError, codes, validation_error};
+pub use event_bus::{EventBus, LiveEvent, LiveResource, LiveAction};
+pub use http_client::{HttpEndpoint, HttpError, http_get, http_post};
+#[cfg(feature = "event-stream")]
+pub use nats::start_event_stream;
+pub use mailer::Mailer;
+pub use password::check_new_password;
+pub use password_hasher::{PasswordHasher, Argon2PasswordHasher};
+pub use encrypted::{Encrypted, FieldCipher};
+pub use pool_alert::{PoolAlert, PoolAlertKind, PoolAlertSink, LogAlertSink, WebhookAlertSink};
+pub use push::{PushOutcome, PushSender, FcmSender, ApnsSender, push_senders};
+pub use email_templates::{
+    EmailTemplates, RenderedEmail, EmailTemplateInfo, month_label, DEFAULT_EMAIL_LOCALE,
+    EMAIL_TEMPLATE_EMAIL_CHANGE, EMAIL_TEMPLATE_SESSION_REUSE, EMAIL_TEMPLATE_MONTHLY_DIGEST, EMAIL_TEMPLATE_REMINDERS,
+};
+pub use lock::{DistributedLock, LockGuard, SNAPSHOT_LOCK_TTL, SNAPSHOT_LOCK_WAIT, user_snapshots_lock, pocket_balance_lock};
 pub use response::{ApiResponse, success_response, created_response, no_content_response, error_response};
-pub use validation::{ValidatedJson, validate_data};
\ No newline at end of file
+pub use token::{base64url, generate_token, hash_token};
+pub use typed_cache::*;
+pub use validation::{ValidatedJson, ValidatedQuery, validate_data, validate_sort, SORT_FIELDS};
+pub use statement_metrics::StatementMetrics;
\ No newline at end of file
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::config::EventStreamConfig;
+use crate::utils::{LiveAction, LiveEvent, LiveResource};
+
+const DEFAULT_NATS_PORT: u16 = 4222;
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+// What downstream consumers receive. Unlike the /ws payload it names the owner,
+// since pipelines aggregate across users.
+#[derive(Debug, Serialize)]
+struct ChangeEvent {
+    user_id: Uuid,
+    resource: LiveResource,
+    action: LiveAction,
+    id: String,
+    at: DateTime<Utc>,
+}
+
+struct NatsEndpoint {
+    address: String,
+    user: Option<String>,
+    pass: Option<String>,
+}
+
+// Forwards every change on the in-process event bus to NATS. Delivery is best effort:
+// events raised while the server is unreachable are dropped, not queued.
+pub fn start_event_stream(config: EventStreamConfig, mut events: broadcast::Receiver<LiveEvent>) {
+    let Some(url) = config.nats_url.as_deref() else {
+        return;
+    };
+    let endpoint = match parse_nats_url(url) {
+        Ok(endpoint) => endpoint,
+        Err(e) => {
+            error!("Event streaming disabled: {}", e);
+            return;
+        }
+    };
+
+    info!("Publishing change events to NATS at {}", endpoint.address);
+    tokio::spawn(async move {
+        let mut delay = Duration::from_secs(1);
+        loop {
+            match publish_until_disconnected(&endpoint, &config.subject_prefix, &mut events).await {
+                Ok(()) => return,
+                Err(e) => warn!("NATS connection lost, retrying in {}s: {}", delay.as_secs(), e),
+            }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+
+            // Whatever piled up while disconnected is stale by now
+            events = events.resubscribe();
+        }
+    });
+}
+
+// Returns Ok only once the event bus is closed, i.e. on shutdown
+async fn publish_until_disconnected(
+    endpoint: &NatsEndpoint,
+    subject_prefix: &str,
+    events: &mut broadcast::Receiver<LiveEvent>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let stream = TcpStream::connect(&endpoint.address).await?;
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    // The server greets with INFO before accepting anything
+    let info = lines.next_line().await?.ok_or("connection closed before INFO")?;
+    if !info.starts_with("INFO") {
+        return Err(format!("unexpected greeting: {}", info).into());
+    }
+
+    let connect = json!({
+        "verbose": false,
+        "pedantic": false,
+        "name": "fintrack",
+        "lang": "rust",
+        "user": endpoint.user,
+        "pass": endpoint.pass,
+    });
+    writer.write_all(format!("CONNECT {}\r\nPING\r\n", connect).as_bytes()).await?;
+    // A PONG confirms the CONNECT was accepted; a rejected one gets -ERR instead
+    loop {
+        let line = lines.next_line().await?.ok_or("connection closed during handshake")?;
+        if line.starts_with("PONG") {
+            break;
+        }
+        if line.starts_with("-ERR") {
+            return Err(line.into());
+        }
+    }
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Event stream fell behind, {} change events were not published", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return Ok(()),
+                };
+
+                let subject = format!("{}.{}.{}", subject_prefix, resource_name(event.resource), action_name(event.action));
+                let payload = serde_json::to_vec(&ChangeEvent {
+                    user_id: event.user_id,
+                    resource: event.resource,
+                    action: event.action,
+                    id: event.id,
+                    at: event.at,
+                })?;
+
+                writer.write_all(format!("PUB {} {}\r\n", subject, payload.len()).as_bytes()).await?;
+                writer.write_all(&payload).await?;
+                writer.write_all(b"\r\n").await?;
+            }
+            line = lines.next_line() => {
+                let line = line?.ok_or("connection closed by server")?;
+                // The server drops clients that leave its keep-alive pings unanswered
+                if line.starts_with("PING") {
+                    writer.write_all(b"PONG\r\n").await?;
+                } else if line.starts_with("-ERR") {
+                    return Err(line.into());
+                }
+            }
+        }
+    }
+}
+
+fn parse_nats_url(url: &str) -> Result<NatsEndpoint, String> {
+    let rest = url
+        .strip_prefix("nats://")
+        .ok_or_else(|| format!("'{}' is not a nats:// URL", url))?;
+    let rest = rest.trim_end_matches('/');
+
+    let (credentials, host) = match rest.rsplit_once('@') {
+        Some((credentials, host)) => (Some(credentials), host),
+        None => (None, rest),
+    };
+    let (user, pass) = match credentials.map(|credentials| credentials.split_once(':')) {
+        Some(Some((user, pass))) => (Some(user.to_string()), Some(pass.to_string())),
+        Some(None) => (credentials.map(str::to_string), None),
+        None => (None, None),
+    };
+
+    if host.is_empty() {
+        return Err(format!("'{}' has no host", url));
+    }
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, DEFAULT_NATS_PORT)
+    };
+
+    Ok(NatsEndpoint { address, user, pass })
+}
+
+fn resource_name(resource: LiveResource) -> &'static str {
+    match resource {
+        LiveResource::Transaction => "transaction",
+        LiveResource::Pocket => "pocket",
+        LiveResource::Budget => "budget",
+        LiveResource::Anomaly => "anomaly",
+        LiveResource::BudgetAlert => "budget_alert",
+    }
+}
+
+fn action_name(action: LiveAction) -> &'static str {
+    match action {
+        LiveAction::Created => "created",
+        LiveAction::Updated => "updated",
+        LiveAction::Deleted => "deleted",
+    }
+}
\ No newline at end of file
@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use sha1::{Digest, Sha1};
+use tracing::warn;
+
+use crate::config::PasswordPolicyConfig;
+use crate::utils::{http_get, validation_error, AppError, HttpEndpoint, HttpError};
+
+const PWNED_PASSWORDS_HOST: &str = "api.pwnedpasswords.com";
+const PWNED_PASSWORDS_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Checks a password a user is about to set against the configured policy, reporting a
+// failure on `field` like any other validation error
+pub async fn check_new_password(policy: &PasswordPolicyConfig, field: &'static str, password: &str) -> Result<(), AppError> {
+    let missing = policy.violations(password);
+    if !missing.is_empty() {
+        let mut errors = validator::ValidationErrors::new();
+        errors.add(
+            field,
+            validator::ValidationError::new("weak_password")
+                .with_message(Cow::Owned(format!("Password must contain {}", missing.join(", ")))),
+        );
+        return Err(validation_error(errors));
+    }
+
+    if policy.breach_check {
+        ensure_password_not_breached(field, password).await?;
+    }
+    Ok(())
+}
+
+// Rejects passwords found in known breaches. The lookup failing open keeps sign-up
+// working when the breach API is unreachable.
+async fn ensure_password_not_breached(field: &str, password: &str) -> Result<(), AppError> {
+
+    let digest = format!("{:X}", Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = digest.split_at(5);
+
+    let body = match tokio::time::timeout(PWNED_PASSWORDS_TIMEOUT, fetch_pwned_range(prefix)).await {
+        Ok(Ok(body)) => body,
+        Ok(Err(e)) => {
+            warn!("Password breach check failed: {}", e);
+            return Ok(());
+        }
+        Err(_) => {
+            warn!("Password breach check timed out");
+            return Ok(());
+        }
+    };
+
+    // Lines are SUFFIX:COUNT; padding entries carry a count of 0
+    let breached = body.lines().any(|line| {
+        line.split_once(':').is_some_and(|(candidate, count)| {
+            candidate.eq_ignore_ascii_case(suffix) && count.trim().parse::<u64>().unwrap_or(0) > 0
+        })
+    });
+
+    if breached {
+        return Err(AppError::invalid_field(
+            field,
+            "This password has appeared in a data breach, please choose a different one",
+        ));
+    }
+    Ok(())
+}
+
+async fn fetch_pwned_range(prefix: &str) -> Result<String, HttpError> {
+    let endpoint = HttpEndpoint::https(PWNED_PASSWORDS_HOST);
+    let (status, body) = http_get(&endpoint, &format!("/range/{}", prefix), &[("Add-Padding", "true")]).await?;
+    if status != StatusCode::OK {
+        return Err(format!("unexpected status {}", status).into());
+    }
+    Ok(body)
+}
\ No newline at end of file
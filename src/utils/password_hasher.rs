@@ -0,0 +1,51 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+use crate::utils::AppError;
+
+pub trait PasswordHasher: Send + Sync {
+    fn hash(&self, password: &str) -> Result<String, AppError>;
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, AppError>;
+    // Whether a stored hash predates the current scheme and should be replaced on next sign-in
+    fn needs_rehash(&self, hash: &str) -> bool;
+}
+
+// Argon2id with the crate's recommended parameters. Verifies legacy bcrypt hashes too,
+// so accounts created before the switch keep working until they are re-hashed.
+#[derive(Default)]
+pub struct Argon2PasswordHasher {
+    argon2: Argon2<'static>,
+}
+
+impl PasswordHasher for Argon2PasswordHasher {
+    fn hash(&self, password: &str) -> Result<String, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AppError::InternalServerError(format!("Password hashing failed: {}", e)))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, AppError> {
+        if is_bcrypt_hash(hash) {
+            return bcrypt::verify(password, hash)
+                .map_err(|e| AppError::InternalServerError(format!("Password verification failed: {}", e)));
+        }
+
+        let parsed = PasswordHash::new(hash)
+            .map_err(|e| AppError::InternalServerError(format!("Password verification failed: {}", e)))?;
+        match self.argon2.verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(AppError::InternalServerError(format!("Password verification failed: {}", e))),
+        }
+    }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        !hash.starts_with("$argon2id$")
+    }
+}
+
+fn is_bcrypt_hash(hash: &str) -> bool {
+    ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|prefix| hash.starts_with(prefix))
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::utils::{http_post, HttpEndpoint, HttpError};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolAlertKind {
+    Exhausted,
+    Recovered,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolAlert {
+    pub kind: PoolAlertKind,
+    // Checks in a row the pool had no idle connection left to hand out
+    pub consecutive_checks: u32,
+    pub size: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+    pub at: DateTime<Utc>,
+}
+
+// Somewhere the connection monitor reports pool trouble. Delivery is best effort,
+// a failing sink only logs so monitoring itself never stops.
+#[async_trait::async_trait]
+pub trait PoolAlertSink: Send + Sync {
+    async fn notify(&self, alert: &PoolAlert);
+}
+
+pub struct LogAlertSink;
+
+#[async_trait::async_trait]
+impl PoolAlertSink for LogAlertSink {
+    async fn notify(&self, alert: &PoolAlert) {
+        match alert.kind {
+            PoolAlertKind::Exhausted => error!(
+                "Connection pool exhausted for {} consecutive checks ({} of {} connections in use)",
+                alert.consecutive_checks, alert.size.saturating_sub(alert.idle), alert.max_connections
+            ),
+            PoolAlertKind::Recovered => info!(
+                "Connection pool recovered after {} exhausted checks", alert.consecutive_checks
+            ),
+        }
+    }
+}
+
+// POSTs the alert as JSON to an http:// or https:// URL
+pub struct WebhookAlertSink {
+    endpoint: HttpEndpoint,
+    path: String,
+}
+
+impl WebhookAlertSink {
+    pub fn new(url: &str) -> Result<Self, String> {
+        let (endpoint, path) = HttpEndpoint::parse(url).map_err(|e| format!("Webhook {}", e))?;
+        Ok(Self { endpoint, path })
+    }
+
+    async fn post(&self, body: &str) -> Result<(), HttpError> {
+        let (status, _) = http_post(&self.endpoint, &self.path, &[], "application/json", body).await?;
+        if !status.is_success() {
+            return Err(format!("unexpected status {}", status).into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl PoolAlertSink for WebhookAlertSink {
+    async fn notify(&self, alert: &PoolAlert) {
+        let body = match serde_json::to_string(alert) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("Failed to serialize pool alert: {}", e);
+                return;
+            }
+        };
+
+        match tokio::time::timeout(WEBHOOK_TIMEOUT, self.post(&body)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Pool alert webhook to {} failed: {}", self.endpoint.host, e),
+            Err(_) => warn!("Pool alert webhook to {} timed out", self.endpoint.host),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,321 @@
+use axum::body::Bytes;
+use axum::http::{Method, Request, StatusCode};
+use chrono::Utc;
+use h2::client::SendRequest;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_native_tls::TlsConnector;
+use tracing::debug;
+
+use crate::config::PushConfig;
+use crate::models::{PushMessage, PUSH_PROVIDER_APNS, PUSH_PROVIDER_FCM};
+use crate::utils::{http_post, HttpEndpoint};
+
+type PushError = Box<dyn std::error::Error + Send + Sync>;
+
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+const GOOGLE_TOKEN_HOST: &str = "oauth2.googleapis.com";
+const FCM_HOST: &str = "fcm.googleapis.com";
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+const APNS_HOST: &str = "api.push.apple.com";
+const APNS_SANDBOX_HOST: &str = "api.sandbox.push.apple.com";
+// Apple refuses provider tokens older than an hour and throttles ones renewed more often than
+// every 20 minutes
+const APNS_TOKEN_LIFETIME: Duration = Duration::from_secs(50 * 60);
+// Google access tokens are renewed this long before they actually expire
+const ACCESS_TOKEN_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    Delivered,
+    // The app was uninstalled or the token rotated, so the device should be forgotten
+    InvalidToken,
+}
+
+#[async_trait::async_trait]
+pub trait PushSender: Send + Sync {
+    // The device provider this sender delivers to
+    fn provider(&self) -> &'static str;
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<PushOutcome, PushError>;
+}
+
+// One sender per push service with credentials configured
+pub fn push_senders(config: &PushConfig) -> Result<Vec<Arc<dyn PushSender>>, Box<dyn std::error::Error>> {
+    let mut senders: Vec<Arc<dyn PushSender>> = Vec::new();
+    if let Some(path) = &config.fcm_service_account_path {
+        senders.push(Arc::new(FcmSender::from_service_account(path)?));
+    }
+    if config.apns_enabled() {
+        senders.push(Arc::new(ApnsSender::new(config)?));
+    }
+    Ok(senders)
+}
+
+#[derive(Deserialize)]
+struct ServiceAccount {
+    project_id: String,
+    client_email: String,
+    private_key: String,
+}
+
+#[derive(Serialize)]
+struct GoogleAssertion<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: String,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct GoogleAccessToken {
+    access_token: String,
+    expires_in: u64,
+}
+
+// Firebase Cloud Messaging through the HTTP v1 API, authorized by a service account
+pub struct FcmSender {
+    project_id: String,
+    client_email: String,
+    key: EncodingKey,
+    access_token: Mutex<Option<(String, Instant)>>,
+}
+
+impl FcmSender {
+    pub fn from_service_account(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let account: ServiceAccount = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        Ok(Self {
+            key: EncodingKey::from_rsa_pem(account.private_key.as_bytes())?,
+            project_id: account.project_id,
+            client_email: account.client_email,
+            access_token: Mutex::new(None),
+        })
+    }
+
+    // Trades a signed assertion for an OAuth access token, reused until shortly before it expires
+    async fn access_token(&self) -> Result<String, PushError> {
+        let mut cached = self.access_token.lock().await;
+        if let Some((token, _)) = cached.as_ref().filter(|(_, expires_at)| Instant::now() + ACCESS_TOKEN_MARGIN < *expires_at) {
+            return Ok(token.clone());
+        }
+
+        let now = Utc::now().timestamp();
+        let assertion = encode(
+            &Header::new(Algorithm::RS256),
+            &GoogleAssertion {
+                iss: &self.client_email,
+                scope: FCM_SCOPE,
+                aud: format!("https://{}/token", GOOGLE_TOKEN_HOST),
+                iat: now,
+                exp: now + 3600,
+            },
+            &self.key,
+        )?;
+        let form = serde_urlencoded::to_string([
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", assertion.as_str()),
+        ])?;
+
+        let endpoint = HttpEndpoint::https(GOOGLE_TOKEN_HOST);
+        let (status, body) = http_post(&endpoint, "/token", &[], "application/x-www-form-urlencoded", &form).await?;
+        if status != StatusCode::OK {
+            return Err(format!("token request failed with status {}: {}", status, body).into());
+        }
+        let token: GoogleAccessToken = serde_json::from_str(&body)?;
+
+        *cached = Some((token.access_token.clone(), Instant::now() + Duration::from_secs(token.expires_in)));
+        Ok(token.access_token)
+    }
+
+    async fn post(&self, body: &str) -> Result<(StatusCode, String), PushError> {
+        let path = format!("/v1/projects/{}/messages:send", self.project_id);
+        let authorization = format!("Bearer {}", self.access_token().await?);
+        http_post(&HttpEndpoint::https(FCM_HOST), &path, &[("Authorization", &authorization)], "application/json", body).await
+    }
+}
+
+#[async_trait::async_trait]
+impl PushSender for FcmSender {
+    fn provider(&self) -> &'static str {
+        PUSH_PROVIDER_FCM
+    }
+
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<PushOutcome, PushError> {
+        let body = json!({
+            "message": {
+                "token": token,
+                "notification": { "title": message.title, "body": message.body },
+                "data": { "kind": message.kind, "resource_id": message.resource_id },
+            }
+        })
+        .to_string();
+        let (status, response) = tokio::time::timeout(PUSH_TIMEOUT, self.post(&body))
+            .await
+            .map_err(|_| "FCM request timed out")??;
+
+        match status {
+            StatusCode::OK => Ok(PushOutcome::Delivered),
+            // UNREGISTERED
+            StatusCode::NOT_FOUND => Ok(PushOutcome::InvalidToken),
+            StatusCode::UNAUTHORIZED => {
+                *self.access_token.lock().await = None;
+                Err("FCM rejected the access token".into())
+            }
+            status => Err(format!("FCM returned {}: {}", status, response).into()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ApnsClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+}
+
+#[derive(Deserialize)]
+struct ApnsError {
+    reason: String,
+}
+
+// Apple Push Notification service over HTTP/2, authorized by a token signing key. The
+// connection is kept open between notifications, as Apple asks.
+pub struct ApnsSender {
+    host: &'static str,
+    topic: String,
+    team_id: String,
+    header: Header,
+    key: EncodingKey,
+    provider_token: Mutex<Option<(String, Instant)>>,
+    connection: Mutex<Option<SendRequest<Bytes>>>,
+}
+
+impl ApnsSender {
+    pub fn new(config: &PushConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let required = |value: &Option<String>, name: &str| value.clone().ok_or_else(|| format!("{} must be set for APNs", name));
+
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(required(&config.apns_key_id, "APNS_KEY_ID")?);
+
+        Ok(Self {
+            host: if config.apns_sandbox { APNS_SANDBOX_HOST } else { APNS_HOST },
+            topic: required(&config.apns_topic, "APNS_TOPIC")?,
+            team_id: required(&config.apns_team_id, "APNS_TEAM_ID")?,
+            header,
+            key: EncodingKey::from_ec_pem(&std::fs::read(required(&config.apns_key_path, "APNS_KEY_PATH")?)?)?,
+            provider_token: Mutex::new(None),
+            connection: Mutex::new(None),
+        })
+    }
+
+    async fn provider_token(&self) -> Result<String, PushError> {
+        let mut cached = self.provider_token.lock().await;
+        if let Some((token, _)) = cached.as_ref().filter(|(_, issued_at)| issued_at.elapsed() < APNS_TOKEN_LIFETIME) {
+            return Ok(token.clone());
+        }
+
+        let token = encode(
+            &self.header,
+            &ApnsClaims {
+                iss: &self.team_id,
+                iat: Utc::now().timestamp(),
+            },
+            &self.key,
+        )?;
+        *cached = Some((token.clone(), Instant::now()));
+        Ok(token)
+    }
+
+    // The open connection, or a new one when Apple has closed it
+    async fn connection(&self) -> Result<SendRequest<Bytes>, PushError> {
+        let mut connection = self.connection.lock().await;
+        if let Some(client) = connection.clone() {
+            match client.ready().await {
+                Ok(client) => return Ok(client),
+                Err(e) => debug!("Reconnecting to APNs: {}", e),
+            }
+        }
+
+        let connector = native_tls::TlsConnector::builder().request_alpns(&["h2"]).build()?;
+        let stream = TcpStream::connect((self.host, 443)).await?;
+        let stream = TlsConnector::from(connector).connect(self.host, stream).await?;
+        let (client, driver) = h2::client::handshake(stream).await?;
+        tokio::spawn(async move {
+            if let Err(e) = driver.await {
+                debug!("APNs connection closed: {}", e);
+            }
+        });
+
+        *connection = Some(client.clone());
+        Ok(client)
+    }
+
+    async fn post(&self, token: &str, body: String) -> Result<(StatusCode, Vec<u8>), PushError> {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{}/3/device/{}", self.host, token))
+            .header("authorization", format!("bearer {}", self.provider_token().await?))
+            .header("apns-topic", &self.topic)
+            .header("apns-push-type", "alert")
+            .body(())?;
+
+        let mut client = self.connection().await?;
+        let (response, mut stream) = client.send_request(request, false)?;
+        stream.send_data(Bytes::from(body), true)?;
+
+        let response = response.await?;
+        let status = response.status();
+        let mut body = response.into_body();
+        let mut data = Vec::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk?;
+            let _ = body.flow_control().release_capacity(chunk.len());
+            data.extend_from_slice(&chunk);
+        }
+        Ok((status, data))
+    }
+}
+
+#[async_trait::async_trait]
+impl PushSender for ApnsSender {
+    fn provider(&self) -> &'static str {
+        PUSH_PROVIDER_APNS
+    }
+
+    async fn send(&self, token: &str, message: &PushMessage) -> Result<PushOutcome, PushError> {
+        let body = json!({
+            "aps": {
+                "alert": { "title": message.title, "body": message.body },
+                "sound": "default",
+            },
+            "kind": message.kind,
+            "resource_id": message.resource_id,
+        })
+        .to_string();
+
+        let (status, response) = tokio::time::timeout(PUSH_TIMEOUT, self.post(token, body))
+            .await
+            .map_err(|_| "APNs request timed out")??;
+        if status == StatusCode::OK {
+            return Ok(PushOutcome::Delivered);
+        }
+
+        let reason = serde_json::from_slice::<ApnsError>(&response)
+            .map(|error| error.reason)
+            .unwrap_or_default();
+        match (status, reason.as_str()) {
+            (StatusCode::GONE, _) | (StatusCode::BAD_REQUEST, "BadDeviceToken" | "DeviceTokenNotForTopic") => {
+                Ok(PushOutcome::InvalidToken)
+            }
+            (StatusCode::FORBIDDEN, "ExpiredProviderToken") => {
+                *self.provider_token.lock().await = None;
+                Err("APNs rejected an expired provider token".into())
+            }
+            (status, reason) => Err(format!("APNs returned {}: {}", status, reason).into()),
+        }
+    }
+}
\ No newline at end of file
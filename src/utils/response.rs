@@ -1,12 +1,19 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde::Serialize;
-use serde_json::json;
+use std::collections::BTreeMap;
+
+use crate::utils::error::ErrorPayload;
 
 #[derive(Serialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorPayload>,
+    // Failed fields and their messages, so forms can mark each input
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<BTreeMap<String, Vec<String>>>,
 }
 
 impl<T> ApiResponse<T>
@@ -18,6 +25,8 @@ where
             success: true,
             data: Some(data),
             message: None,
+            error: None,
+            errors: None,
         }
     }
 
@@ -26,6 +35,18 @@ where
             success: true,
             data: Some(data),
             message: Some(message),
+            error: None,
+            errors: None,
+        }
+    }
+
+    pub fn message(message: String) -> ApiResponse<()> {
+        ApiResponse {
+            success: true,
+            data: None,
+            message: Some(message),
+            error: None,
+            errors: None,
         }
     }
 
@@ -34,6 +55,23 @@ where
             success: false,
             data: None,
             message: Some(message),
+            error: None,
+            errors: None,
+        }
+    }
+
+    pub fn failure(message: String, error: ErrorPayload) -> ApiResponse<()> {
+        let mut errors: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for detail in &error.details {
+            errors.entry(detail.field.clone()).or_default().push(detail.message.clone());
+        }
+
+        ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message),
+            error: Some(error),
+            errors: (!errors.is_empty()).then_some(errors),
         }
     }
 }
@@ -61,11 +99,5 @@ pub fn no_content_response() -> impl IntoResponse {
 }
 
 pub fn error_response(status: StatusCode, message: &str) -> impl IntoResponse {
-    (
-        status,
-        Json(json!({
-            "success": false,
-            "error": message
-        })),
-    )
+    (status, Json(ApiResponse::<()>::error(message.to_string())))
 }
\ No newline at end of file
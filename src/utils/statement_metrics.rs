@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+// Metric name, type, help text and the value it reads from a route's stats
+type Series = (&'static str, &'static str, &'static str, fn(&RouteStatementStats) -> u64);
+
+#[derive(Debug, Clone, Default)]
+struct RouteStatementStats {
+    requests: u64,
+    statements: u64,
+    max_statements: usize,
+    over_budget: u64,
+}
+
+// In-process counters of database statements issued per route, rendered for scraping
+#[derive(Clone, Default)]
+pub struct StatementMetrics {
+    routes: Arc<Mutex<HashMap<String, RouteStatementStats>>>,
+}
+
+impl StatementMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, route: &str, statements: usize, over_budget: bool) {
+        let Ok(mut routes) = self.routes.lock() else {
+            return;
+        };
+
+        let stats = routes.entry(route.to_string()).or_default();
+        stats.requests += 1;
+        stats.statements += statements as u64;
+        stats.max_statements = stats.max_statements.max(statements);
+        if over_budget {
+            stats.over_budget += 1;
+        }
+    }
+
+    // Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let routes = match self.routes.lock() {
+            Ok(routes) => routes.clone(),
+            Err(_) => return String::new(),
+        };
+        let mut names: Vec<&String> = routes.keys().collect();
+        names.sort();
+
+        let mut output = String::new();
+        let series: [Series; 4] = [
+            ("http_requests_total", "counter", "Requests handled per route", |s| s.requests),
+            ("db_statements_total", "counter", "Database statements issued per route", |s| s.statements),
+            ("db_statements_per_request_max", "gauge", "Most statements issued by a single request", |s| s.max_statements as u64),
+            ("db_statement_budget_exceeded_total", "counter", "Requests that exceeded the statement budget", |s| s.over_budget),
+        ];
+
+        for (name, kind, help, value) in series {
+            let _ = writeln!(output, "# HELP {} {}", name, help);
+            let _ = writeln!(output, "# TYPE {} {}", name, kind);
+            for route in &names {
+                let _ = writeln!(output, "{}{{route=\"{}\"}} {}", name, escape_label(route), value(&routes[*route]));
+            }
+        }
+
+        output
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
\ No newline at end of file
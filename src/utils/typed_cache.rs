@@ -0,0 +1,168 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::{Display, Write};
+use std::future::Future;
+use std::marker::PhantomData;
+use uuid::Uuid;
+
+use crate::utils::{AppError, CacheService};
+
+// One family of cached values. Keys read `name:user_id:vN:parts...`, so every entry of a user
+// sits under one prefix; bump `version` when the cached type changes shape and old entries are
+// simply never read again instead of failing to deserialize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheNamespace {
+    pub name: &'static str,
+    pub version: u32,
+    pub ttl_seconds: u64,
+}
+
+impl CacheNamespace {
+    pub const fn new(name: &'static str, version: u32, ttl_seconds: u64) -> Self {
+        Self { name, version, ttl_seconds }
+    }
+
+    // Every entry of one user starts with this, whatever its version or parts
+    pub fn user_prefix(&self, user_id: &Uuid) -> String {
+        format!("{}:{}:", self.name, user_id)
+    }
+
+    // Redis set listing the user's keys in this namespace, so invalidation needn't scan the keyspace
+    pub fn tag_key(&self, user_id: &Uuid) -> String {
+        format!("cache_tags:{}:{}", self.name, user_id)
+    }
+}
+
+pub const TRANSACTIONS: CacheNamespace = CacheNamespace::new("transactions", 1, 300);
+pub const ACCOUNT_SUMMARY: CacheNamespace = CacheNamespace::new("account_summary", 1, 300);
+pub const BUDGETS: CacheNamespace = CacheNamespace::new("budgets", 1, 300);
+pub const BUDGET_SUMMARY: CacheNamespace = CacheNamespace::new("budget_summary", 1, 600);
+pub const BUDGET_PERFORMANCE: CacheNamespace = CacheNamespace::new("budget_performance", 1, 300);
+pub const BUDGET_DETAIL_PERFORMANCE: CacheNamespace = CacheNamespace::new("budget_detail_performance", 1, 300);
+pub const BUDGET_CATEGORIES: CacheNamespace = CacheNamespace::new("budget_categories", 1, 900);
+pub const BUDGET_SUGGESTIONS: CacheNamespace = CacheNamespace::new("budget_suggestions", 1, 1800);
+pub const EXPENSE_SUMMARY: CacheNamespace = CacheNamespace::new("expense_summary", 1, 900);
+pub const EXPENSE_CATEGORY_SUMMARY: CacheNamespace = CacheNamespace::new("expense_category_summary", 1, 900);
+pub const EXPENSE_MONTHLY_TREND: CacheNamespace = CacheNamespace::new("expense_monthly_trend", 1, 900);
+pub const EXPENSE_DAILY_TREND: CacheNamespace = CacheNamespace::new("expense_daily_trend", 1, 900);
+pub const EXPENSE_HEATMAP: CacheNamespace = CacheNamespace::new("expense_heatmap", 1, 900);
+pub const RECENT_EXPENSE_TRANSACTIONS: CacheNamespace = CacheNamespace::new("recent_expense_transactions", 1, 300);
+pub const INCOME_SUMMARY: CacheNamespace = CacheNamespace::new("income_summary", 1, 900);
+pub const INCOME_CATEGORY_SUMMARY: CacheNamespace = CacheNamespace::new("income_category_summary", 1, 900);
+pub const INCOME_MONTHLY_TREND: CacheNamespace = CacheNamespace::new("income_monthly_trend", 1, 900);
+pub const INCOME_DAILY_TREND: CacheNamespace = CacheNamespace::new("income_daily_trend", 1, 900);
+pub const RECENT_INCOME_TRANSACTIONS: CacheNamespace = CacheNamespace::new("recent_income_transactions", 1, 300);
+// Detection reads two years of history, so results are kept for an hour
+pub const SUBSCRIPTIONS: CacheNamespace = CacheNamespace::new("subscriptions", 1, 3600);
+pub const FINANCIAL_HEALTH: CacheNamespace = CacheNamespace::new("financial_health", 1, 900);
+pub const ANALYTICS_FEED: CacheNamespace = CacheNamespace::new("analytics_feed", 1, 300);
+
+// Invalidation groups: namespaces holding views of the same records, cleared together
+pub const BUDGET_VIEWS: &[CacheNamespace] = &[
+    BUDGETS, BUDGET_SUMMARY, BUDGET_PERFORMANCE, BUDGET_DETAIL_PERFORMANCE, BUDGET_CATEGORIES, BUDGET_SUGGESTIONS,
+    FINANCIAL_HEALTH,
+];
+
+// Everything a transaction write can change; budget definitions themselves are not among them
+pub const TRANSACTION_VIEWS: &[CacheNamespace] = &[
+    TRANSACTIONS, ACCOUNT_SUMMARY, BUDGET_SUMMARY, BUDGET_PERFORMANCE, BUDGET_DETAIL_PERFORMANCE, BUDGET_CATEGORIES,
+    BUDGET_SUGGESTIONS, EXPENSE_SUMMARY, EXPENSE_CATEGORY_SUMMARY, EXPENSE_MONTHLY_TREND, EXPENSE_DAILY_TREND,
+    EXPENSE_HEATMAP, RECENT_EXPENSE_TRANSACTIONS, INCOME_SUMMARY, INCOME_CATEGORY_SUMMARY, INCOME_MONTHLY_TREND,
+    INCOME_DAILY_TREND, RECENT_INCOME_TRANSACTIONS, SUBSCRIPTIONS, FINANCIAL_HEALTH, ANALYTICS_FEED,
+];
+
+// Views showing pocket names or balances
+pub const POCKET_VIEWS: &[CacheNamespace] = &[ACCOUNT_SUMMARY, FINANCIAL_HEALTH];
+
+// Everything computed from a user's transactions, budgets or pockets
+pub const USER_DERIVED_VIEWS: &[CacheNamespace] = &[
+    TRANSACTIONS, ACCOUNT_SUMMARY, BUDGETS, BUDGET_SUMMARY, BUDGET_PERFORMANCE, BUDGET_DETAIL_PERFORMANCE,
+    BUDGET_CATEGORIES, BUDGET_SUGGESTIONS, EXPENSE_SUMMARY, EXPENSE_CATEGORY_SUMMARY, EXPENSE_MONTHLY_TREND,
+    EXPENSE_DAILY_TREND, EXPENSE_HEATMAP, RECENT_EXPENSE_TRANSACTIONS, INCOME_SUMMARY, INCOME_CATEGORY_SUMMARY,
+    INCOME_MONTHLY_TREND, INCOME_DAILY_TREND, RECENT_INCOME_TRANSACTIONS, SUBSCRIPTIONS, FINANCIAL_HEALTH,
+    ANALYTICS_FEED,
+];
+
+// A key built by TypedCache, carrying the tag set it is recorded in when filled
+pub struct CacheKey {
+    key: String,
+    tag: String,
+}
+
+impl CacheKey {
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+}
+
+// A CacheService view that stores one type under one namespace and builds its keys
+pub struct TypedCache<T> {
+    cache: CacheService,
+    namespace: CacheNamespace,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned> TypedCache<T> {
+    pub fn key(&self, user_id: &Uuid, parts: &[&dyn Display]) -> CacheKey {
+        let mut key = format!("{}v{}", self.namespace.user_prefix(user_id), self.namespace.version);
+        for part in parts {
+            let _ = write!(key, ":{}", part);
+        }
+        CacheKey {
+            key,
+            tag: self.namespace.tag_key(user_id),
+        }
+    }
+
+    pub async fn get(&self, key: &CacheKey) -> Option<T> {
+        self.cache.get(&key.key).await
+    }
+
+    pub async fn set(&self, key: &CacheKey, value: &T) -> bool {
+        let stored = self.cache.set(&key.key, value, Some(self.namespace.ttl_seconds)).await;
+        if stored {
+            self.cache.tag(&key.tag, &key.key, self.tag_ttl()).await;
+        }
+        stored
+    }
+
+    pub async fn get_or_compute<F, Fut>(&self, key: &CacheKey, compute: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let tag = Some((key.tag.as_str(), self.tag_ttl()));
+        self.cache
+            .get_or_compute_tagged(&key.key, self.namespace.ttl_seconds, tag, compute)
+            .await
+    }
+
+    // Outlives every jittered entry it lists
+    fn tag_ttl(&self) -> u64 {
+        self.namespace.ttl_seconds * 2
+    }
+}
+
+impl CacheService {
+    pub fn typed<T>(&self, namespace: CacheNamespace) -> TypedCache<T> {
+        TypedCache {
+            cache: self.clone(),
+            namespace,
+            _value: PhantomData,
+        }
+    }
+
+    // Drops a user's entries in every namespace of the group, returning how many were removed
+    pub async fn invalidate_group(&self, group: &[CacheNamespace], user_id: &Uuid) -> usize {
+        let prefixes: Vec<String> = group.iter().map(|namespace| namespace.user_prefix(user_id)).collect();
+        let removed_locally = self.delete_local_where(|key| prefixes.iter().any(|prefix| key.starts_with(prefix.as_str())));
+
+        let tags: Vec<String> = group.iter().map(|namespace| namespace.tag_key(user_id)).collect();
+        self.invalidate_tags(&tags).await.unwrap_or(removed_locally)
+    }
+
+    // After a write that can touch anything the user sees: every derived view plus the pocket list
+    pub async fn invalidate_user_derived(&self, user_id: &Uuid) -> usize {
+        self.invalidate_pockets(user_id).await;
+        self.invalidate_group(USER_DERIVED_VIEWS, user_id).await
+    }
+}
\ No newline at end of file
@@ -1,12 +1,14 @@
 use axum::{
     body::Body,
-    extract::{FromRequest, Request},
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Query, Request},
+    http::{request::Parts, StatusCode},
     Json,
 };
 use serde::de::DeserializeOwned;
 use validator::Validate;
 
-use crate::utils::error::{validation_error, AppError};
+use crate::models::INVALID_MONEY;
+use crate::utils::error::{codes, validation_error, AppError};
 
 pub struct ValidatedJson<T>(pub T);
 
@@ -18,9 +20,16 @@ where
     type Rejection = AppError;
 
     async fn from_request(req: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
-        let Json(value) = Json::<T>::from_request(req, state)
-            .await
-            .map_err(|_| AppError::BadRequest("Invalid JSON".to_string()))?;
+        let Json(value) = Json::<T>::from_request(req, state).await.map_err(|rejection| match rejection {
+            // A malformed amount is a validation failure like any other, not broken JSON
+            JsonRejection::JsonDataError(error) if error.body_text().contains(INVALID_MONEY) => {
+                AppError::ValidationError(error.body_text()).with_code(codes::INVALID_AMOUNT)
+            }
+            rejection if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                AppError::PayloadTooLarge("Request body is too large".to_string())
+            }
+            _ => AppError::BadRequest("Invalid JSON".to_string()),
+        })?;
 
         value
             .validate()
@@ -30,7 +39,52 @@ where
     }
 }
 
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| AppError::BadRequest("Invalid query parameters".to_string()))?;
+
+        value
+            .validate()
+            .map_err(validation_error)?;
+
+        Ok(ValidatedQuery(value))
+    }
+}
+
 // Helper function to validate data manually
 pub fn validate_data<T: Validate>(data: &T) -> Result<(), AppError> {
     data.validate().map_err(validation_error)
+}
+
+// Columns a list endpoint may be sorted by; repositories map these onto SQL themselves
+pub const SORT_FIELDS: &[&str] = &["amount", "date", "category", "created_at"];
+
+pub fn validate_sort(sort_by: Option<&str>, order: Option<&str>) -> Result<(), AppError> {
+    if let Some(sort_by) = sort_by
+        && !SORT_FIELDS.contains(&sort_by)
+    {
+        return Err(AppError::invalid_field(
+            "sort_by",
+            format!("sort_by must be one of: {}", SORT_FIELDS.join(", ")),
+        ));
+    }
+
+    if let Some(order) = order
+        && order != "asc"
+        && order != "desc"
+    {
+        return Err(AppError::invalid_field("order", "order must be 'asc' or 'desc'"));
+    }
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+use axum::http::StatusCode;
+use chrono::{Months, Utc};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn an_expense_far_above_its_category_history_is_flagged() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive();
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    let history = ["20.00", "22.00", "18.00", "21.00", "19.00", "20.00"];
+    let mut expenses: Vec<(String, String)> = history
+        .iter()
+        .enumerate()
+        .map(|(index, amount)| {
+            let date = today.checked_sub_months(Months::new(index as u32 + 2)).expect("date");
+            (amount.to_string(), date.format("%Y-%m-%d").to_string())
+        })
+        .collect();
+    expenses.push(("250.00".to_string(), today.format("%Y-%m-%d").to_string()));
+
+    for (amount, date) in &expenses {
+        let expense = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": "Lunch",
+                    "amount": amount,
+                    "category": "Food",
+                    "transaction_type": "expense",
+                    "transaction_date": date,
+                }),
+            )
+            .await;
+        assert_eq!(expense.status, StatusCode::CREATED, "{}", expense.body);
+    }
+
+    let response = app.get("/analytics/anomalies", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let anomalies = response.body["data"]["anomalies"].as_array().expect("anomaly list");
+    assert_eq!(anomalies.len(), 1, "{}", response.body);
+    assert_eq!(anomalies[0]["amount"], json!("250.00"));
+    assert_eq!(anomalies[0]["category"], json!("Food"));
+    assert_eq!(anomalies[0]["sample_size"], json!(6));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn anomaly_query_is_validated_and_empty_history_flags_nothing() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app.get("/analytics/anomalies?threshold=0.5", &token).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST, "{}", response.body);
+
+    let response = app.get("/analytics/anomalies?days=7", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    assert_eq!(response.body["data"]["anomalies"], json!([]));
+}
\ No newline at end of file
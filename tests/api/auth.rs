@@ -0,0 +1,255 @@
+
+use axum::http::{HeaderName, Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::common::{Response, TestApp, PASSWORD};
+
+async fn login_from(app: &TestApp, forwarded_for: &str, email: &str, password: &str) -> Response {
+    let headers = [(HeaderName::from_static("x-forwarded-for"), forwarded_for)];
+    app.request_with_headers(
+        Method::POST,
+        "/auth/login",
+        None,
+        &headers,
+        Some(json!({ "email": email, "password": password })),
+    )
+    .await
+}
+
+async fn register_email(app: &TestApp) -> String {
+    let email = format!("lockout-{}@example.com", Uuid::new_v4().simple());
+    let registered = app
+        .request(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({ "name": "Test User", "email": email, "password": PASSWORD })),
+        )
+        .await;
+    assert_eq!(registered.status, StatusCode::CREATED, "{}", registered.body);
+    email
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn register_then_login_and_fetch_profile() {
+    let app = TestApp::spawn().await;
+
+    let email = format!("login-{}@example.com", Uuid::new_v4().simple());
+    let registered = app
+        .request(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({ "name": "Login User", "email": email, "password": PASSWORD })),
+        )
+        .await;
+    assert_eq!(registered.status, StatusCode::CREATED, "{}", registered.body);
+
+    let login = app
+        .request(Method::POST, "/auth/login", None, Some(json!({ "email": email, "password": PASSWORD })))
+        .await;
+    assert_eq!(login.status, StatusCode::OK, "{}", login.body);
+    let token = login.body["data"]["token"].as_str().expect("token in login response");
+
+    let me = app.get("/users/me", token).await;
+    assert_eq!(me.status, StatusCode::OK, "{}", me.body);
+    assert_eq!(me.body["data"]["email"], json!(email));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn login_with_wrong_password_is_rejected() {
+    let app = TestApp::spawn().await;
+
+    let email = format!("wrong-{}@example.com", Uuid::new_v4().simple());
+    app.request(
+        Method::POST,
+        "/auth/register",
+        None,
+        Some(json!({ "name": "Wrong Password", "email": email, "password": PASSWORD })),
+    )
+    .await;
+
+    let login = app
+        .request(
+            Method::POST,
+            "/auth/login",
+            None,
+            Some(json!({ "email": email, "password": "not-the-password" })),
+        )
+        .await;
+    assert_eq!(login.status, StatusCode::UNAUTHORIZED, "{}", login.body);
+    assert_eq!(login.body["error"]["code"], json!("INVALID_CREDENTIALS"));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn new_passwords_follow_the_configured_policy() {
+    let app = TestApp::spawn_with(|config| config.password_policy.require_digit = true).await;
+
+    let email = format!("policy-{}@example.com", Uuid::new_v4().simple());
+    let register = |password: &'static str| {
+        app.request(
+            Method::POST,
+            "/auth/register",
+            None,
+            Some(json!({ "name": "Policy User", "email": email, "password": password })),
+        )
+    };
+
+    let weak = register(PASSWORD).await;
+    assert_eq!(weak.status, StatusCode::BAD_REQUEST, "{}", weak.body);
+    assert_eq!(weak.body["error"]["details"][0]["field"], json!("password"));
+    assert_eq!(weak.body["error"]["details"][0]["code"], json!("weak_password"));
+
+    let registered = register("correct-horse-battery-9").await;
+    assert_eq!(registered.status, StatusCode::CREATED, "{}", registered.body);
+    let token = registered.body["data"]["token"].as_str().expect("token").to_string();
+
+    let change = |new_password: &'static str| {
+        app.request(
+            Method::PUT,
+            "/users/me/password",
+            Some(&token),
+            Some(json!({ "current_password": "correct-horse-battery-9", "new_password": new_password })),
+        )
+    };
+    let weak = change(PASSWORD).await;
+    assert_eq!(weak.status, StatusCode::BAD_REQUEST, "{}", weak.body);
+    assert_eq!(weak.body["error"]["details"][0]["field"], json!("new_password"));
+    let changed = change("another-horse-battery-7").await;
+    assert_eq!(changed.status, StatusCode::OK, "{}", changed.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn tokens_from_before_a_password_change_stay_revoked_without_the_cached_marker() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    // Issued-at has whole-second precision
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let changed = app
+        .request(
+            Method::PUT,
+            "/users/me/password",
+            Some(&token),
+            Some(json!({ "current_password": PASSWORD, "new_password": "another-horse-battery-7" })),
+        )
+        .await;
+    assert_eq!(changed.status, StatusCode::OK, "{}", changed.body);
+    let fresh = changed.body["data"]["token"].as_str().expect("token").to_string();
+
+    // Whether or not Redis is configured, the cutoff on the user row decides
+    let me = app.request(Method::GET, "/users/me", Some(&token), None).await;
+    assert_eq!(me.status, StatusCode::UNAUTHORIZED, "{}", me.body);
+    assert_eq!(me.body["error"]["code"], json!("TOKEN_REVOKED"));
+
+    let me = app.get("/users/me", &fresh).await;
+    assert_eq!(me.status, StatusCode::OK, "{}", me.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn protected_routes_require_a_token() {
+    let app = TestApp::spawn().await;
+
+    let response = app.request(Method::GET, "/users/me", None, None).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+
+    let response = app.get("/users/me", "not-a-jwt").await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn only_admins_can_list_and_search_users() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let admin = app.register_admin().await;
+
+    let refused = app.get("/users?email=example.com", &token).await;
+    assert_eq!(refused.status, StatusCode::FORBIDDEN, "{}", refused.body);
+
+    let listed = app.get("/users?email=example.com", &admin).await;
+    assert_eq!(listed.status, StatusCode::OK, "{}", listed.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn repeated_failed_logins_lock_the_account_and_the_client_address() {
+    let app = TestApp::spawn().await;
+    // The failure counters live in Redis, and sign-in is never locked without it
+    if !app.has_redis() {
+        return;
+    }
+    let random_ip = || {
+        let id = Uuid::new_v4().as_u128();
+        format!("2001:db8::{:x}:{:x}", (id >> 16) as u16, id as u16)
+    };
+
+    // Five wrong passwords lock the account, wherever they came from
+    let email = register_email(&app).await;
+    for _ in 0..5 {
+        let failed = login_from(&app, &random_ip(), &email, "wrong-password").await;
+        assert_eq!(failed.status, StatusCode::UNAUTHORIZED, "{}", failed.body);
+    }
+    let locked = login_from(&app, &random_ip(), &email, PASSWORD).await;
+    assert_eq!(locked.status, StatusCode::TOO_MANY_REQUESTS, "{}", locked.body);
+    assert_eq!(locked.body["error"]["code"], json!("ACCOUNT_LOCKED"));
+
+    // Twenty failures across accounts lock the client address. The client picks the leftmost
+    // X-Forwarded-For entries, so rotating them must not reset its count.
+    let client = random_ip();
+    for _ in 0..20 {
+        let forwarded_for = format!("{}, {}", random_ip(), client);
+        let nobody = format!("nobody-{}@example.com", Uuid::new_v4().simple());
+        let failed = login_from(&app, &forwarded_for, &nobody, "wrong-password").await;
+        assert_eq!(failed.status, StatusCode::UNAUTHORIZED, "{}", failed.body);
+    }
+    let email = register_email(&app).await;
+    let locked = login_from(&app, &format!("{}, {}", random_ip(), client), &email, PASSWORD).await;
+    assert_eq!(locked.status, StatusCode::TOO_MANY_REQUESTS, "{}", locked.body);
+
+    let elsewhere = login_from(&app, &random_ip(), &email, PASSWORD).await;
+    assert_eq!(elsewhere.status, StatusCode::OK, "{}", elsewhere.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn repeated_failed_step_ups_lock_the_account() {
+    let app = TestApp::spawn().await;
+    if !app.has_redis() {
+        return;
+    }
+    let token = app.register().await;
+
+    for _ in 0..5 {
+        let failed = app.post("/auth/step-up", &token, json!({ "password": "wrong-password" })).await;
+        assert_eq!(failed.status, StatusCode::UNAUTHORIZED, "{}", failed.body);
+    }
+    let locked = app.post("/auth/step-up", &token, json!({ "password": PASSWORD })).await;
+    assert_eq!(locked.status, StatusCode::TOO_MANY_REQUESTS, "{}", locked.body);
+    assert_eq!(locked.body["error"]["code"], json!("ACCOUNT_LOCKED"));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn logout_on_one_instance_signs_out_on_every_other() {
+    let app = TestApp::spawn().await;
+    let other = app.another_instance().await;
+    let token = app.register().await;
+
+    // The other instance confirms the session once, which it remembers as active
+    let before = other.get("/users/me", &token).await;
+    assert_eq!(before.status, StatusCode::OK, "{}", before.body);
+
+    let logout = app.request(Method::POST, "/auth/logout", Some(&token), None).await;
+    assert_eq!(logout.status, StatusCode::NO_CONTENT, "{}", logout.body);
+
+    let after = other.get("/users/me", &token).await;
+    assert_eq!(after.status, StatusCode::UNAUTHORIZED, "{}", after.body);
+    assert_eq!(after.body["error"]["code"], json!("TOKEN_REVOKED"));
+}
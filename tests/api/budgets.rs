@@ -0,0 +1,271 @@
+use axum::http::StatusCode;
+use chrono::{Datelike, Days, Months, Utc};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn create_and_list_budgets() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app
+        .post(
+            "/budgets",
+            &token,
+            json!({
+                "category": "Food",
+                "target_amount": 500.0,
+                "period_type": "monthly",
+                "period_start": "2025-01-01",
+                "period_end": "2025-01-31",
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    assert_eq!(created.body["data"]["category"], json!("Food"));
+
+    let list = app.get("/budgets", &token).await;
+    assert_eq!(list.status, StatusCode::OK, "{}", list.body);
+    assert_eq!(list.body["data"]["total_items"], json!(1));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn budget_with_unknown_period_is_rejected() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app
+        .post(
+            "/budgets",
+            &token,
+            json!({
+                "category": "Food",
+                "target_amount": 500.0,
+                "period_type": "fortnightly",
+                "period_start": "2025-01-01",
+                "period_end": "2025-01-14",
+            }),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST, "{}", response.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn ended_periods_roll_over_into_history() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    let created = app
+        .post(
+            "/budgets",
+            &token,
+            json!({
+                "category": "Food",
+                "target_amount": 100.0,
+                "period_type": "monthly",
+                "period_start": "2025-01-01",
+                "period_end": "2025-01-31",
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let budget_id = created.body["data"]["id"].as_i64().expect("budget id");
+
+    let expense = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Groceries",
+                "amount": "80.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": "2025-01-10",
+            }),
+        )
+        .await;
+    assert_eq!(expense.status, StatusCode::CREATED, "{}", expense.body);
+
+    app.run_task("budget_rollover").await;
+
+    let history = app.get(&format!("/budgets/{}/history?limit=120", budget_id), &token).await;
+    assert_eq!(history.status, StatusCode::OK, "{}", history.body);
+
+    // Every month from January 2025 up to the one before the current month is closed
+    let today = Utc::now().date_naive();
+    let closed_months = (today.year() * 12 + today.month0() as i32) - (2025 * 12);
+    let periods = history.body["data"]["periods"].as_array().expect("period list");
+    assert_eq!(periods.len() as i32, closed_months, "{}", history.body);
+    assert_eq!(periods[0]["period_start"], json!("2025-01-01"));
+    assert_eq!(periods[0]["actual_amount"], json!("80.00"));
+    assert_eq!(periods[0]["within_budget"], json!(true));
+    assert_eq!(periods[1]["period_start"], json!("2025-02-01"));
+    assert_eq!(periods[1]["period_end"], json!("2025-02-28"));
+    assert_eq!(periods[1]["within_budget"], json!(true));
+
+    let current_start = today.with_day(1).expect("first of month").format("%Y-%m-%d").to_string();
+    assert_eq!(history.body["data"]["budget"]["period_start"], json!(current_start));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn reached_alert_thresholds_are_recorded_once_per_period() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive();
+    let period_start = today.with_day(1).expect("first of month");
+    let period_end = period_start + Months::new(1) - Days::new(1);
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    let rejected = app
+        .post(
+            "/budgets",
+            &token,
+            json!({
+                "category": "Food",
+                "target_amount": 100.0,
+                "period_type": "monthly",
+                "period_start": period_start.format("%Y-%m-%d").to_string(),
+                "period_end": period_end.format("%Y-%m-%d").to_string(),
+                "alert_thresholds": [0, 100],
+            }),
+        )
+        .await;
+    assert_eq!(rejected.status, StatusCode::BAD_REQUEST, "{}", rejected.body);
+
+    let created = app
+        .post(
+            "/budgets",
+            &token,
+            json!({
+                "category": "Food",
+                "target_amount": 100.0,
+                "period_type": "monthly",
+                "period_start": period_start.format("%Y-%m-%d").to_string(),
+                "period_end": period_end.format("%Y-%m-%d").to_string(),
+                "alert_thresholds": [90, 50, 90],
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    assert_eq!(created.body["data"]["alert_thresholds"], json!([50, 90]));
+    let budget_id = created.body["data"]["id"].as_i64().expect("budget id");
+
+    let expense = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Groceries",
+                "amount": "60.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": today.format("%Y-%m-%d").to_string(),
+            }),
+        )
+        .await;
+    assert_eq!(expense.status, StatusCode::CREATED, "{}", expense.body);
+
+    app.run_task("budget_alerts").await;
+    app.run_task("budget_alerts").await;
+
+    let performance = app.get(&format!("/budgets/{}/performance", budget_id), &token).await;
+    assert_eq!(performance.status, StatusCode::OK, "{}", performance.body);
+    assert_eq!(performance.body["data"]["alerted_thresholds"], json!([50]));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn steady_spending_without_a_budget_gets_a_suggestion() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let this_month = Utc::now().date_naive().with_day(15).expect("mid month");
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    for (months_ago, amount) in [(1, "100.00"), (2, "120.00"), (3, "100.00"), (4, "110.00")] {
+        let date = this_month - Months::new(months_ago);
+        for category in ["Transport", "Food"] {
+            let expense = app
+                .post(
+                    "/transactions",
+                    &token,
+                    json!({
+                        "account_id": pocket_id,
+                        "description": category,
+                        "amount": amount,
+                        "category": category,
+                        "transaction_type": "expense",
+                        "transaction_date": date.format("%Y-%m-%d").to_string(),
+                    }),
+                )
+                .await;
+            assert_eq!(expense.status, StatusCode::CREATED, "{}", expense.body);
+        }
+    }
+
+    // Food already has a budget, so only Transport is suggested as a new one
+    let period_start = this_month.with_day(1).expect("first of month");
+    let budget = app
+        .post(
+            "/budgets",
+            &token,
+            json!({
+                "category": "Food",
+                "target_amount": 150.0,
+                "period_type": "monthly",
+                "period_start": period_start.format("%Y-%m-%d").to_string(),
+                "period_end": (period_start + Months::new(1) - Days::new(1)).format("%Y-%m-%d").to_string(),
+            }),
+        )
+        .await;
+    assert_eq!(budget.status, StatusCode::CREATED, "{}", budget.body);
+
+    let response = app.get("/budgets/suggestions", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let suggestions = response.body["data"]["suggestions"].as_array().expect("suggestion list");
+    let new_budgets: Vec<_> = suggestions.iter().filter(|s| s["budget_id"].is_null()).collect();
+    assert_eq!(new_budgets.len(), 1, "{}", response.body);
+    assert_eq!(new_budgets[0]["category"], json!("Transport"));
+    assert_eq!(new_budgets[0]["suggested_amount"], json!("100.00"));
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn target_amount_keeps_its_decimal_string() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let body = |target_amount: serde_json::Value| {
+        json!({
+            "category": "Food",
+            "target_amount": target_amount,
+            "period_type": "monthly",
+            "period_start": "2025-01-01",
+            "period_end": "2025-01-31",
+        })
+    };
+
+    let created = app.post("/budgets", &token, body(json!("0.10"))).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    assert_eq!(created.body["data"]["target_amount"], json!("0.10"));
+
+    let too_precise = app.post("/budgets", &token, body(json!("12.345"))).await;
+    assert_eq!(too_precise.status, StatusCode::BAD_REQUEST, "{}", too_precise.body);
+    assert_eq!(too_precise.body["error"]["code"], json!("VALIDATION_FAILED"));
+
+    let malformed = app.post("/budgets", &token, body(json!("12,50"))).await;
+    assert_eq!(malformed.status, StatusCode::BAD_REQUEST, "{}", malformed.body);
+    assert_eq!(malformed.body["error"]["code"], json!("INVALID_AMOUNT"));
+}
\ No newline at end of file
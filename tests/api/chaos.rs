@@ -0,0 +1,52 @@
+use axum::http::{HeaderName, Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn injected_redis_failure_reaches_handlers_using_the_cache_from_state() {
+    // The local tier caches on its own, so a stale entry shows the cache was consulted
+    let app = TestApp::spawn().await.another_instance().await;
+    let token = app.register().await;
+    let me = app.get("/users/me", &token).await;
+    let user_id: Uuid = me.body["data"]["id"].as_str().expect("user id").parse().expect("uuid");
+
+    let created = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "description": "Lunch",
+                "amount": "12.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": "2025-02-10",
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+
+    let path = "/expense-analytics/summary?from_date=2025-02-01&to_date=2025-02-28";
+    let cached = app.get(path, &token).await;
+    assert_eq!(cached.body["data"]["total_expenses"], json!("12.00"), "{}", cached.body);
+
+    // Written behind the API's back, so nothing invalidates the cached summary
+    sqlx::query(
+        "INSERT INTO transactions (user_id, description, amount, category, transaction_type, transaction_date, status)
+         VALUES ($1, 'Dinner', 30.00, 'Food', 'expense', '2025-02-11', 'posted')",
+    )
+    .bind(user_id)
+    .execute(app.pool())
+    .await
+    .expect("insert transaction");
+
+    let stale = app.get(path, &token).await;
+    assert_eq!(stale.body["data"]["total_expenses"], json!("12.00"), "{}", stale.body);
+
+    let headers = [(HeaderName::from_static("x-chaos-fail"), "redis")];
+    let degraded = app.request_with_headers(Method::GET, path, Some(&token), &headers, None).await;
+    assert_eq!(degraded.status, StatusCode::OK, "{}", degraded.body);
+    assert_eq!(degraded.body["data"]["total_expenses"], json!("42.00"), "{}", degraded.body);
+}
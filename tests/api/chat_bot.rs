@@ -0,0 +1,115 @@
+use axum::http::{HeaderName, StatusCode};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::common::{Response, TestApp, SLACK_SIGNING_SECRET, TELEGRAM_WEBHOOK_SECRET};
+
+const TELEGRAM_PATH: &str = "/webhooks/chat/telegram";
+const SLACK_PATH: &str = "/webhooks/chat/slack";
+
+async fn send_telegram(app: &TestApp, user_id: i64, text: &str, secret: &str) -> Response {
+    let update = json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1,
+            "chat": { "id": user_id, "type": "private" },
+            "from": { "id": user_id, "is_bot": false, "first_name": "Test" },
+            "text": text,
+        },
+    });
+    let headers = [(HeaderName::from_static("x-telegram-bot-api-secret-token"), secret)];
+    app.post_raw(TELEGRAM_PATH, "application/json", &headers, update.to_string()).await
+}
+
+async fn send_slack(app: &TestApp, text: &str, signing_secret: &str) -> Response {
+    let body = format!("team_id=T0001&user_id=U0001&command=%2Ffintrack&text={}", text.replace(' ', "+"));
+    let timestamp = Utc::now().timestamp().to_string();
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_secret.as_bytes()).expect("hmac key");
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    let signature = format!("v0={:x}", mac.finalize().into_bytes());
+
+    let headers = [
+        (HeaderName::from_static("x-slack-request-timestamp"), timestamp.as_str()),
+        (HeaderName::from_static("x-slack-signature"), signature.as_str()),
+    ];
+    app.post_raw(SLACK_PATH, "application/x-www-form-urlencoded", &headers, body).await
+}
+
+async fn link_code(app: &TestApp, token: &str, provider: &str) -> String {
+    let created = app.post("/users/me/link-codes", token, json!({ "provider": provider })).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    created.body["data"]["code"].as_str().expect("link code").to_string()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn linked_telegram_users_log_expenses_by_message() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let unlinked = send_telegram(&app, 4242, "coffee 4.50", TELEGRAM_WEBHOOK_SECRET).await;
+    assert_eq!(unlinked.status, StatusCode::OK, "{}", unlinked.body);
+    assert!(unlinked.body["text"].as_str().unwrap_or_default().contains("/link"), "{}", unlinked.body);
+
+    let code = link_code(&app, &token, "telegram").await;
+    let linked = send_telegram(&app, 4242, &format!("/link {}", code), TELEGRAM_WEBHOOK_SECRET).await;
+    assert_eq!(linked.status, StatusCode::OK, "{}", linked.body);
+    assert_eq!(linked.body["method"], "sendMessage");
+    assert_eq!(linked.body["chat_id"], 4242);
+    assert!(linked.body["text"].as_str().unwrap_or_default().starts_with("Linked"), "{}", linked.body);
+
+    // Codes are single use
+    let reused = send_telegram(&app, 5151, &format!("/link {}", code), TELEGRAM_WEBHOOK_SECRET).await;
+    assert!(reused.body["text"].as_str().unwrap_or_default().contains("invalid"), "{}", reused.body);
+
+    let logged = send_telegram(&app, 4242, "coffee 4.50", TELEGRAM_WEBHOOK_SECRET).await;
+    assert_eq!(logged.status, StatusCode::OK, "{}", logged.body);
+    assert!(logged.body["text"].as_str().unwrap_or_default().starts_with("Logged 4.50 for coffee"), "{}", logged.body);
+
+    let listed = app.get("/transactions", &token).await;
+    assert_eq!(listed.status, StatusCode::OK, "{}", listed.body);
+    let transactions = listed.body["data"]["data"].as_array().expect("transactions");
+    assert_eq!(transactions.len(), 1, "{}", listed.body);
+    assert_eq!(transactions[0]["description"], "coffee");
+    assert_eq!(transactions[0]["amount"], json!("4.50"));
+    assert_eq!(transactions[0]["transaction_type"], "expense");
+
+    let no_amount = send_telegram(&app, 4242, "just saying hi", TELEGRAM_WEBHOOK_SECRET).await;
+    assert!(no_amount.body["text"].as_str().unwrap_or_default().contains("couldn't find an amount"), "{}", no_amount.body);
+
+    let forged = send_telegram(&app, 4242, "coffee 4.50", "wrong-secret").await;
+    assert_eq!(forged.status, StatusCode::UNAUTHORIZED, "{}", forged.body);
+
+    let accounts = app.get("/users/me/linked-accounts", &token).await;
+    assert_eq!(accounts.body["data"][0]["provider"], "telegram", "{}", accounts.body);
+    assert_eq!(accounts.body["data"][0]["external_id"], "4242");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn slack_commands_are_verified_and_unlinkable() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let code = link_code(&app, &token, "slack").await;
+    let forged = send_slack(&app, &format!("link {}", code), "wrong-secret").await;
+    assert_eq!(forged.status, StatusCode::UNAUTHORIZED, "{}", forged.body);
+
+    let linked = send_slack(&app, &format!("link {}", code), SLACK_SIGNING_SECRET).await;
+    assert_eq!(linked.status, StatusCode::OK, "{}", linked.body);
+    assert_eq!(linked.body["response_type"], "ephemeral");
+
+    let logged = send_slack(&app, "12.00 team lunch", SLACK_SIGNING_SECRET).await;
+    let reply: &Value = &logged.body["text"];
+    assert!(reply.as_str().unwrap_or_default().starts_with("Logged 12.00 for team lunch"), "{}", logged.body);
+
+    let unlinked = app
+        .request(axum::http::Method::DELETE, "/users/me/linked-accounts/slack", Some(&token), None)
+        .await;
+    assert_eq!(unlinked.status, StatusCode::NO_CONTENT, "{}", unlinked.body);
+
+    let ignored = send_slack(&app, "coffee 3", SLACK_SIGNING_SECRET).await;
+    assert!(ignored.body["text"].as_str().unwrap_or_default().contains("/link"), "{}", ignored.body);
+}
\ No newline at end of file
@@ -0,0 +1,420 @@
+use std::env;
+use std::net::SocketAddr;
+use std::path::Path;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::connect_info::MockConnectInfo,
+    http::{header, HeaderName, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use sqlx::{postgres::PgConnectOptions, Connection, Executor, PgConnection, PgPool};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::redis::{Redis, REDIS_PORT};
+use testcontainers_modules::testcontainers::{runners::SyncRunner, Container, ImageExt};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use rust_fintrack_backend::build_app;
+use rust_fintrack_backend::config::{
+    AdminConfig, AppConfig, BalanceVisibilityConfig, ChatBotConfig, EmailConfig, EncryptionConfig, EventStreamConfig, HttpConfig,
+    InboundEmailConfig, JwtSettings, MetricsConfig, PasswordPolicyConfig, PoolMonitorConfig, PushConfig, RedisConfig,
+};
+use rust_fintrack_backend::models::{ListTaskRunsQuery, TASK_RUN_STATUS_RUNNING};
+use rust_fintrack_backend::repositories::PostgresTaskRunRepository;
+use rust_fintrack_backend::services::SchedulerService;
+use rust_fintrack_backend::utils::CacheService;
+
+pub const PASSWORD: &str = "correct-horse-battery";
+// Listed in the test config's ADMIN_EMAILS
+pub const ADMIN_EMAIL: &str = "admin@example.com";
+pub const INBOUND_EMAIL_DOMAIN: &str = "in.fintrack.test";
+pub const MAILGUN_SIGNING_KEY: &str = "mailgun-test-signing-key";
+pub const SES_WEBHOOK_SECRET: &str = "ses-test-secret";
+pub const TELEGRAM_WEBHOOK_SECRET: &str = "telegram-test-secret";
+pub const SLACK_SIGNING_SECRET: &str = "slack-test-signing-secret";
+// Every request arrives from here, as if through a load balancer listed in TRUSTED_PROXIES
+pub const PROXY_ADDR: [u8; 4] = [10, 0, 0, 1];
+
+// One router over a freshly migrated database. Postgres and Redis run in containers started
+// through testcontainers, unless TEST_DATABASE_URL points at a server of your own (Redis then
+// comes from TEST_REDIS_ADDR if set).
+pub struct TestApp {
+    router: Router,
+    scheduler: SchedulerService<PostgresTaskRunRepository>,
+    pool: PgPool,
+    config: AppConfig,
+    // Shared with every instance over the same database, which is dropped after the last one
+    database: Arc<TestDatabase>,
+}
+
+// A database created for one test, dropped again when the test is done with it
+struct TestDatabase {
+    admin_url: String,
+    name: String,
+    // Kept running until the database is gone
+    containers: Option<Arc<Containers>>,
+}
+
+impl Drop for TestDatabase {
+    // Drop can't await, so the cleanup runs to completion on a thread with its own runtime.
+    // FORCE closes the connections the app's pool and background tasks still hold.
+    fn drop(&mut self) {
+        let admin_url = self.admin_url.clone();
+        let statement = format!(r#"DROP DATABASE IF EXISTS "{}" WITH (FORCE)"#, self.name);
+        let dropped = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("build cleanup runtime");
+            runtime.block_on(async {
+                let mut admin = PgConnection::connect(&admin_url).await?;
+                admin.execute(statement.as_str()).await?;
+                admin.close().await
+            })
+        })
+        .join();
+
+        if !matches!(dropped, Ok(Ok(()))) {
+            eprintln!("Failed to drop test database {}", self.name);
+        }
+    }
+}
+
+// Postgres and Redis containers, shared by the tests running at the same time and removed once
+// the last of them is done
+struct Containers {
+    postgres_url: String,
+    redis_addr: String,
+    running: Option<(Container<Postgres>, Container<Redis>)>,
+}
+
+static CONTAINERS: Mutex<Weak<Containers>> = Mutex::new(Weak::new());
+
+impl Containers {
+    fn shared() -> Arc<Self> {
+        let mut shared = CONTAINERS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(containers) = shared.upgrade() {
+            return containers;
+        }
+
+        // The sync runner drives Docker on a runtime of its own, which can't start inside the test's
+        let containers = Arc::new(std::thread::spawn(Self::start).join().expect("start containers"));
+        *shared = Arc::downgrade(&containers);
+        containers
+    }
+
+    fn start() -> Self {
+        let postgres = Postgres::default()
+            .with_tag("16-alpine")
+            .start()
+            .expect("start Postgres container, is Docker running?");
+        let redis = Redis::default().with_tag("7-alpine").start().expect("start Redis container");
+
+        let postgres_url = format!(
+            "postgresql://postgres:postgres@{}:{}/postgres",
+            postgres.get_host().expect("Postgres host"),
+            postgres.get_host_port_ipv4(5432).expect("Postgres port")
+        );
+        let redis_addr = format!(
+            "{}:{}",
+            redis.get_host().expect("Redis host"),
+            redis.get_host_port_ipv4(REDIS_PORT).expect("Redis port")
+        );
+
+        Self {
+            postgres_url,
+            redis_addr,
+            running: Some((postgres, redis)),
+        }
+    }
+}
+
+impl Drop for Containers {
+    // Removing them blocks on the runner's own runtime, so it happens off the test's
+    fn drop(&mut self) {
+        let running = self.running.take();
+        if std::thread::spawn(move || drop(running)).join().is_err() {
+            eprintln!("Failed to remove test containers");
+        }
+    }
+}
+
+pub struct Response {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+impl TestApp {
+    // The tests are #[ignore]d so a plain `cargo test` lists them as not run; once opted in,
+    // a missing Docker or database is a failure rather than a silent pass
+    pub async fn spawn() -> Self {
+        Self::spawn_with(|_| {}).await
+    }
+
+    // Like spawn, with the test configuration adjusted first
+    pub async fn spawn_with(configure: impl FnOnce(&mut AppConfig)) -> Self {
+        let (admin_url, redis_addr, containers) = match env::var("TEST_DATABASE_URL") {
+            Ok(url) => (url, env::var("TEST_REDIS_ADDR").ok(), None),
+            Err(_) => {
+                let containers = Containers::shared();
+                (containers.postgres_url.clone(), Some(containers.redis_addr.clone()), Some(containers))
+            }
+        };
+
+        let database_name = format!("fintrack_test_{}", Uuid::new_v4().simple());
+        let mut admin = PgConnection::connect(&admin_url).await.expect("connect to TEST_DATABASE_URL");
+        admin
+            .execute(format!(r#"CREATE DATABASE "{}""#, database_name).as_str())
+            .await
+            .expect("create test database");
+        let database = Arc::new(TestDatabase {
+            admin_url: admin_url.clone(),
+            name: database_name.clone(),
+            containers,
+        });
+
+        let options = PgConnectOptions::from_str(&admin_url)
+            .expect("parse TEST_DATABASE_URL")
+            .database(&database_name);
+        let pool = PgPool::connect_with(options).await.expect("connect to test database");
+
+        let migrations = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        sqlx::migrate::Migrator::new(migrations)
+            .await
+            .expect("load migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+
+        let mut config = test_config(&admin_url, redis_addr);
+        if database.containers.is_some() {
+            // The Redis container takes no password, whatever REDIS_PASSWORD says
+            config.redis.password = None;
+        }
+        configure(&mut config);
+        let cache = CacheService::new(&config.redis).await;
+        Self::build(config, pool, cache, database)
+    }
+
+    // A second server over the same database with a cache of its own, as behind a load
+    // balancer. Its local tier is on; without Redis nothing is shared between them.
+    pub async fn another_instance(&self) -> Self {
+        let mut config = self.config.clone();
+        if !config.redis.enabled {
+            config.redis.enabled = true;
+            config.redis.addr = "127.0.0.1:1".to_string();
+        }
+        let cache = CacheService::new(&config.redis).await;
+        Self::build(config, self.pool.clone(), cache, self.database.clone())
+    }
+
+    fn build(config: AppConfig, pool: PgPool, cache: CacheService, database: Arc<TestDatabase>) -> Self {
+        let app = build_app(&config, pool.clone(), None, cache).expect("build app");
+
+        Self {
+            router: app.router.layer(MockConnectInfo(SocketAddr::from((PROXY_ADDR, 443)))),
+            scheduler: app.scheduler,
+            pool,
+            config,
+            database,
+        }
+    }
+
+    pub async fn request(&self, method: Method, path: &str, token: Option<&str>, body: Option<Value>) -> Response {
+        self.request_with_headers(method, path, token, &[], body).await
+    }
+
+    pub async fn request_with_headers(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        headers: &[(HeaderName, &str)],
+        body: Option<Value>,
+    ) -> Response {
+        let mut builder = Request::builder().method(method).uri(path);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        for (name, value) in headers {
+            builder = builder.header(name, *value);
+        }
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("build request");
+
+        self.send(request).await
+    }
+
+    // For webhooks, which post forms or plain text and carry no access token
+    pub async fn post_raw(&self, path: &str, content_type: &str, headers: &[(HeaderName, &str)], body: String) -> Response {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri(path)
+            .header(header::CONTENT_TYPE, content_type);
+        for (name, value) in headers {
+            builder = builder.header(name, *value);
+        }
+        self.send(builder.body(Body::from(body)).expect("build request")).await
+    }
+
+    async fn send(&self, request: Request<Body>) -> Response {
+        let response = self.router.clone().oneshot(request).await.expect("router is infallible");
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.expect("read body");
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+        };
+
+        Response { status, body }
+    }
+
+    pub async fn get(&self, path: &str, token: &str) -> Response {
+        self.request(Method::GET, path, Some(token), None).await
+    }
+
+    pub async fn post(&self, path: &str, token: &str, body: Value) -> Response {
+        self.request(Method::POST, path, Some(token), Some(body)).await
+    }
+
+    // Turns on hide_balance, after which money fields come back as "***" (the default mask mode)
+    pub async fn hide_balances(&self, token: &str) {
+        let response = self
+            .request(Method::PATCH, "/users/hide-balance", Some(token), Some(serde_json::json!({ "hide_balance": true })))
+            .await;
+        assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    }
+
+    // Runs a scheduled task right away and waits for the run to finish
+    pub async fn run_task(&self, name: &str) {
+        let run = self.scheduler.trigger(name).await.expect("trigger task");
+        for _ in 0..100 {
+            let runs = self
+                .scheduler
+                .list_runs(name, ListTaskRunsQuery { limit: Some(5) })
+                .await
+                .expect("list task runs");
+            if runs.iter().any(|r| r.id == run.id && r.status != TASK_RUN_STATUS_RUNNING) {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        panic!("task {} did not finish", name);
+    }
+
+    // Without Redis there are no lockouts or shared caches to test
+    pub fn has_redis(&self) -> bool {
+        self.config.redis.enabled
+    }
+
+    // For arranging state the API can't produce, such as a drifted balance
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    // Registers a new account with a unique email and returns its access token
+    pub async fn register(&self) -> String {
+        self.register_as(&format!("user-{}@example.com", Uuid::new_v4().simple())).await
+    }
+
+    pub async fn register_admin(&self) -> String {
+        self.register_as(ADMIN_EMAIL).await
+    }
+
+    async fn register_as(&self, email: &str) -> String {
+        let response = self
+            .request(
+                Method::POST,
+                "/auth/register",
+                None,
+                Some(serde_json::json!({ "name": "Test User", "email": email, "password": PASSWORD })),
+            )
+            .await;
+        assert_eq!(response.status, StatusCode::CREATED, "{}", response.body);
+        response.body["data"]["token"].as_str().expect("token in register response").to_string()
+    }
+}
+
+fn test_config(database_url: &str, redis_addr: Option<String>) -> AppConfig {
+    let mut redis = RedisConfig::from_env();
+    match redis_addr {
+        Some(addr) => {
+            redis.addr = addr;
+            redis.enabled = true;
+        }
+        None => redis.enabled = false,
+    }
+    redis.snapshot_path = None;
+
+    let mut email = EmailConfig::from_env();
+    email.enabled = false;
+
+    AppConfig {
+        database_url: database_url.to_string(),
+        jwt_secret: "integration-test-secret".to_string(),
+        jwt: JwtSettings {
+            token_ttl_hours: 1,
+            issuer: "fintrack-test".to_string(),
+            audience: "fintrack-test".to_string(),
+            leeway_secs: 0,
+            algorithm: "HS256".to_string(),
+            private_key_path: None,
+            public_key_path: None,
+            key_id: "test".to_string(),
+            invalid: Vec::new(),
+        },
+        port: 0,
+        host: "127.0.0.1".to_string(),
+        redis,
+        email,
+        inbound_email: InboundEmailConfig {
+            domain: Some(INBOUND_EMAIL_DOMAIN.to_string()),
+            mailgun_signing_key: Some(MAILGUN_SIGNING_KEY.to_string()),
+            ses_secret: Some(SES_WEBHOOK_SECRET.to_string()),
+        },
+        chat_bot: ChatBotConfig {
+            telegram_secret_token: Some(TELEGRAM_WEBHOOK_SECRET.to_string()),
+            slack_signing_secret: Some(SLACK_SIGNING_SECRET.to_string()),
+        },
+        push: PushConfig {
+            fcm_service_account_path: None,
+            apns_key_path: None,
+            apns_key_id: None,
+            apns_team_id: None,
+            apns_topic: None,
+            apns_sandbox: false,
+            invalid: Vec::new(),
+        },
+        balance_visibility: BalanceVisibilityConfig::from_env(),
+        metrics: MetricsConfig::from_env(),
+        admin: AdminConfig { emails: vec![ADMIN_EMAIL.to_string()] },
+        password_policy: PasswordPolicyConfig::default(),
+        event_stream: EventStreamConfig {
+            nats_url: None,
+            subject_prefix: "fintrack".to_string(),
+        },
+        http: HttpConfig {
+            trusted_proxies: vec![std::net::IpAddr::from(PROXY_ADDR).to_string()],
+            ..HttpConfig::from_env()
+        },
+        pool_monitor: PoolMonitorConfig::from_env(),
+        encryption: EncryptionConfig {
+            key: Some("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f".to_string()),
+            previous_keys: Vec::new(),
+        },
+        anomaly_alerts: false,
+        account_deletion_grace_days: 30,
+        categorization_provider: "embedding".to_string(),
+        transaction_archive_after_years: Some(5),
+    }
+}
\ No newline at end of file
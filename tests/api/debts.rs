@@ -0,0 +1,50 @@
+use axum::http::StatusCode;
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn payoff_plan_amortizes_the_balance() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app
+        .post(
+            "/debts",
+            &token,
+            json!({ "name": "Car loan", "debt_type": "loan", "principal": "1200", "apr": "12", "minimum_payment": "100" }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let id = created.body["data"]["id"].as_i64().expect("debt id");
+
+    let plan = app.get(&format!("/debts/{}/payoff-plan", id), &token).await;
+    assert_eq!(plan.status, StatusCode::OK, "{}", plan.body);
+    assert_eq!(plan.body["data"]["months"], json!(13));
+    assert_eq!(plan.body["data"]["schedule"][0]["interest"], json!("12.00"));
+
+    // Paying more each month finishes sooner
+    let faster = app.get(&format!("/debts/{}/payoff-plan?extra_payment=100", id), &token).await;
+    assert_eq!(faster.status, StatusCode::OK, "{}", faster.body);
+    assert_eq!(faster.body["data"]["months"], json!(7));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn payoff_plan_rejects_payments_below_interest() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app
+        .post(
+            "/debts",
+            &token,
+            json!({ "name": "Card", "debt_type": "credit_card", "principal": "10000", "apr": "24", "minimum_payment": "150" }),
+        )
+        .await;
+    let id = created.body["data"]["id"].as_i64().expect("debt id");
+
+    let plan = app.get(&format!("/debts/{}/payoff-plan", id), &token).await;
+    assert_eq!(plan.status, StatusCode::BAD_REQUEST, "{}", plan.body);
+}
\ No newline at end of file
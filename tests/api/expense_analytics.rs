@@ -0,0 +1,267 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn heatmap_groups_expenses_by_day_weekday_and_hour() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    // 2024-03-04 was a Monday
+    for (amount, date, transaction_type) in [
+        ("10.00", "2024-03-04", "expense"),
+        ("15.50", "2024-03-04", "expense"),
+        ("7.25", "2024-03-09", "expense"),
+        ("500.00", "2024-03-04", "income"),
+        ("99.00", "2023-12-31", "expense"),
+    ] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": "Groceries",
+                    "amount": amount,
+                    "category": "Food",
+                    "transaction_type": transaction_type,
+                    "transaction_date": date,
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    let response = app.get("/analytics/expenses/heatmap?year=2024", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let data = &response.body["data"];
+
+    let days = data["days"].as_array().expect("day list");
+    assert_eq!(days.len(), 2, "{}", response.body);
+    assert_eq!(days[0]["date"], json!("2024-03-04"));
+    assert_eq!(days[0]["transaction_count"], json!(2));
+
+    let weekdays = data["weekdays"].as_array().expect("weekday list");
+    assert_eq!(weekdays.len(), 7);
+    assert_eq!(weekdays[0]["weekday"], json!("monday"));
+    assert_eq!(weekdays[0]["transaction_count"], json!(2));
+    assert_eq!(weekdays[5]["weekday"], json!("saturday"));
+    assert_eq!(weekdays[5]["transaction_count"], json!(1));
+
+    let hours = data["hours"].as_array().expect("hour list");
+    assert_eq!(hours.len(), 24);
+    let hourly_count: i64 = hours.iter().map(|hour| hour["transaction_count"].as_i64().unwrap_or(0)).sum();
+    assert_eq!(hourly_count, 3);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn summaries_split_income_and_expenses_by_type() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    for (amount, transaction_type) in [("40.00", "expense"), ("2.50", "expense"), ("900.00", "income")] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": "Entry",
+                    "amount": amount,
+                    "category": "General",
+                    "transaction_type": transaction_type,
+                    "transaction_date": "2025-02-10",
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    let range = "from_date=2025-02-01&to_date=2025-02-28";
+    let expenses = app.get(&format!("/expense-analytics/summary?{}", range), &token).await;
+    assert_eq!(expenses.status, StatusCode::OK, "{}", expenses.body);
+    assert_eq!(expenses.body["data"]["total_expenses"], json!("42.50"));
+    assert_eq!(expenses.body["data"]["total_transactions"], json!(2));
+
+    let income = app.get(&format!("/income-analytics/summary?{}", range), &token).await;
+    assert_eq!(income.status, StatusCode::OK, "{}", income.body);
+    assert_eq!(income.body["data"]["total_transactions"], json!(1), "{}", income.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn summary_reflects_transactions_written_after_it_was_cached() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+    let summary_path = "/expense-analytics/summary?from_date=2025-02-01&to_date=2025-02-28";
+
+    let before = app.get(summary_path, &token).await;
+    assert_eq!(before.status, StatusCode::OK, "{}", before.body);
+    assert_eq!(before.body["data"]["total_transactions"], json!(0));
+
+    let created = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Lunch",
+                "amount": "12.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": "2025-02-10",
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let transaction_id = created.body["data"]["id"].as_i64().expect("transaction id");
+
+    let after_create = app.get(summary_path, &token).await;
+    assert_eq!(after_create.body["data"]["total_transactions"], json!(1), "{}", after_create.body);
+    assert_eq!(after_create.body["data"]["total_expenses"], json!("12.00"));
+
+    let deleted = app
+        .request(Method::DELETE, &format!("/transactions/{}", transaction_id), Some(&token), None)
+        .await;
+    assert_eq!(deleted.status, StatusCode::NO_CONTENT, "{}", deleted.body);
+
+    let after_delete = app.get(summary_path, &token).await;
+    assert_eq!(after_delete.body["data"]["total_transactions"], json!(0), "{}", after_delete.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn transaction_list_reflects_a_cancelled_pending_transaction() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+    let next_week = (chrono::Utc::now().date_naive() + chrono::Days::new(7)).to_string();
+
+    let created = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Rent",
+                "amount": "500.00",
+                "category": "Housing",
+                "transaction_type": "expense",
+                "transaction_date": next_week,
+                "pending": true,
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let transaction_id = created.body["data"]["id"].as_i64().expect("transaction id");
+
+    let before = app.get("/transactions", &token).await;
+    assert_eq!(before.body["data"]["data"][0]["status"], json!("pending"), "{}", before.body);
+
+    let cancelled = app
+        .request(Method::POST, &format!("/transactions/{}/cancel", transaction_id), Some(&token), None)
+        .await;
+    assert_eq!(cancelled.status, StatusCode::OK, "{}", cancelled.body);
+
+    let after = app.get("/transactions", &token).await;
+    assert_eq!(after.body["data"]["data"][0]["status"], json!("cancelled"), "{}", after.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn category_and_monthly_totals_span_partial_months_and_follow_edits() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    let mut ids = Vec::new();
+    for (amount, category, date) in [
+        ("99.00", "Food", "2025-01-10"),
+        ("10.00", "Food", "2025-01-20"),
+        ("20.00", "Food", "2025-02-05"),
+        ("50.00", "Travel", "2025-02-20"),
+        ("5.00", "Food", "2025-03-10"),
+    ] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": "Entry",
+                    "amount": amount,
+                    "category": category,
+                    "transaction_type": "expense",
+                    "transaction_date": date,
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+        ids.push(created.body["data"]["id"].as_i64().expect("transaction id"));
+    }
+
+    // February is read whole from the aggregates, January and March only in part
+    let range = "from_date=2025-01-15&to_date=2025-03-15";
+    let trend = app.get(&format!("/expense-analytics/monthly-trend?{}", range), &token).await;
+    assert_eq!(trend.status, StatusCode::OK, "{}", trend.body);
+    assert_eq!(
+        trend.body["data"]["trends"],
+        json!([
+            { "period": "2025-01", "total_amount": "10.00", "transaction_count": 1 },
+            { "period": "2025-02", "total_amount": "70.00", "transaction_count": 2 },
+            { "period": "2025-03", "total_amount": "5.00", "transaction_count": 1 },
+        ])
+    );
+
+    let categories = app.get(&format!("/expense-analytics/category-summary?{}", range), &token).await;
+    assert_eq!(categories.status, StatusCode::OK, "{}", categories.body);
+    let items = categories.body["data"]["categories"].as_array().expect("category list");
+    assert_eq!(items.len(), 2, "{}", categories.body);
+    assert_eq!(items[0]["category"], json!("Travel"));
+    assert_eq!(items[1]["category"], json!("Food"));
+    assert_eq!(items[1]["total_amount"], json!("35.00"));
+    assert_eq!(items[1]["transaction_count"], json!(3));
+
+    // Moving the trip out of the range refreshes the month it left
+    let updated = app
+        .request(
+            Method::PUT,
+            &format!("/transactions/{}", ids[3]),
+            Some(&token),
+            Some(json!({
+                "account_id": pocket_id,
+                "description": "Entry",
+                "amount": "50.00",
+                "category": "Travel",
+                "transaction_type": "expense",
+                "transaction_date": "2025-04-01",
+            })),
+        )
+        .await;
+    assert_eq!(updated.status, StatusCode::OK, "{}", updated.body);
+
+    let trend = app.get(&format!("/expense-analytics/monthly-trend?{}", range), &token).await;
+    assert_eq!(trend.body["data"]["trends"][1]["total_amount"], json!("20.00"), "{}", trend.body);
+    assert_eq!(trend.body["data"]["trends"][1]["transaction_count"], json!(1));
+
+    let categories = app.get(&format!("/expense-analytics/category-summary?{}", range), &token).await;
+    let items = categories.body["data"]["categories"].as_array().expect("category list");
+    assert_eq!(items.len(), 1, "{}", categories.body);
+    assert_eq!(items[0]["category"], json!("Food"));
+}
\ No newline at end of file
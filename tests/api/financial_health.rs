@@ -0,0 +1,107 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn health_scores_savings_spending_and_emergency_fund() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Main", "emoji": "🏦" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    for (amount, category, transaction_type) in [("1000.00", "Salary", "income"), ("600.00", "Rent", "expense")] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": category,
+                    "amount": amount,
+                    "category": category,
+                    "transaction_type": transaction_type,
+                    "transaction_date": today,
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    let response = app.get("/analytics/health?months=3", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let data = &response.body["data"];
+
+    assert_eq!(data["savings_rate"]["value"], json!("40.00"), "{}", response.body);
+    assert_eq!(data["savings_rate"]["score"], json!(100));
+    assert_eq!(data["expense_to_income_ratio"]["value"], json!("60.00"));
+    assert_eq!(data["expense_to_income_ratio"]["score"], json!(80));
+    // 400 left against 200 a month of spending
+    assert_eq!(data["emergency_fund_months"]["value"], json!("2"));
+    assert_eq!(data["emergency_fund_months"]["score"], json!(33));
+    // No budgets, so adherence is left out of the overall score
+    assert_eq!(data["budget_adherence"]["score"], json!(null));
+    assert_eq!(data["score"], json!(71));
+    assert_eq!(data["rating"], json!("good"));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn health_without_activity_has_no_score() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app.get("/analytics/health", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    assert_eq!(response.body["data"]["score"], json!(null));
+    assert_eq!(response.body["data"]["rating"], json!(null));
+
+    let response = app.get("/analytics/health?months=0", &token).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST, "{}", response.body);
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn liquid_balance_is_converted_to_the_base_currency_and_hidden_on_request() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive();
+
+    sqlx::query("INSERT INTO exchange_rates (currency, rate_date, usd_rate) VALUES ('IDR', $1, 16000)")
+        .bind(today)
+        .execute(app.pool())
+        .await
+        .expect("seed exchange rate");
+
+    for (currency, amount) in [("USD", "100.00"), ("IDR", "160000.00")] {
+        let pocket = app.post("/pockets", &token, json!({ "name": currency, "emoji": "🏦", "currency": currency })).await;
+        assert_eq!(pocket.status, StatusCode::CREATED, "{}", pocket.body);
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket.body["data"]["id"],
+                    "description": "Deposit",
+                    "amount": amount,
+                    "category": "Salary",
+                    "transaction_type": "income",
+                    "transaction_date": today.format("%Y-%m-%d").to_string(),
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    let response = app.get("/analytics/health", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    // 100 USD at 16000 IDR each, plus the IDR pocket, in the default IDR base currency
+    assert_eq!(response.body["data"]["liquid_balance"], json!("1760000.00"), "{}", response.body);
+
+    app.hide_balances(&token).await;
+    let response = app.get("/analytics/health?months=1", &token).await;
+    assert_eq!(response.body["data"]["liquid_balance"], json!("***"), "{}", response.body);
+}
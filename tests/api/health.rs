@@ -0,0 +1,28 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn liveness_and_readiness_probes_need_no_token() {
+    let app = TestApp::spawn().await;
+
+    let live = app.request(Method::GET, "/health/live", None, None).await;
+    assert_eq!(live.status, StatusCode::OK, "{}", live.body);
+    assert_eq!(live.body["status"], json!("ok"));
+
+    let ready = app.request(Method::GET, "/health/ready", None, None).await;
+    assert_eq!(ready.status, StatusCode::OK, "{}", ready.body);
+    assert_eq!(ready.body["status"], json!("ready"));
+
+    let checks = ready.body["checks"].as_array().expect("checks array");
+    let database = checks.iter().find(|check| check["name"] == json!("database")).expect("database check");
+    assert_eq!(database["status"], json!("up"));
+    assert_eq!(database["required"], json!(true));
+    assert!(database["latency_ms"].is_number(), "{}", database);
+
+    // Redis is optional, so a missing cache never fails readiness
+    let cache = checks.iter().find(|check| check["name"] == json!("cache")).expect("cache check");
+    assert_eq!(cache["required"], json!(false));
+}
\ No newline at end of file
@@ -0,0 +1,195 @@
+use axum::http::{header, HeaderName, StatusCode};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::common::{Response, TestApp, INBOUND_EMAIL_DOMAIN, MAILGUN_SIGNING_KEY, SES_WEBHOOK_SECRET};
+
+const MAILGUN_PATH: &str = "/webhooks/inbound-email/mailgun";
+const SES_PATH: &str = "/webhooks/inbound-email/ses";
+const RECEIPT: &str = "Thanks for your order!\nSubtotal: Rp 40.000\nTax: Rp 5.000\nTotal: Rp 45.000\n";
+// Test users are registered at example.com
+const ALIGNED_DKIM: &[(&str, &str)] = &[
+    ("X-Mailgun-Dkim-Check-Result", "Pass"),
+    ("DKIM-Signature", "v=1; a=rsa-sha256; d=example.com; s=mail; bh=abc; b=def"),
+];
+
+fn form_encode(fields: &[(&str, &str)]) -> String {
+    let encode = |value: &str| {
+        value
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+                _ => format!("%{:02X}", byte),
+            })
+            .collect::<String>()
+    };
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+async fn post_mailgun(
+    app: &TestApp,
+    recipient: &str,
+    from: &str,
+    message_id: &str,
+    signing_key: &str,
+    authentication: &[(&str, &str)],
+) -> Response {
+    let timestamp = Utc::now().timestamp().to_string();
+    let token = format!("token-{}", message_id);
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key.as_bytes()).expect("hmac key");
+    mac.update(timestamp.as_bytes());
+    mac.update(token.as_bytes());
+    let signature = format!("{:x}", mac.finalize().into_bytes());
+    let mut headers: Vec<(&str, &str)> = authentication.to_vec();
+    headers.push(("Subject", "Fwd: Kopi Kenangan receipt"));
+    let headers = json!(headers).to_string();
+
+    let body = form_encode(&[
+        ("recipient", recipient),
+        ("from", from),
+        ("subject", "Fwd: Kopi Kenangan receipt"),
+        ("body-plain", RECEIPT),
+        ("Message-Id", message_id),
+        ("message-headers", &headers),
+        ("timestamp", &timestamp),
+        ("token", &token),
+        ("signature", &signature),
+    ]);
+    app.post_raw(MAILGUN_PATH, "application/x-www-form-urlencoded", &[], body).await
+}
+
+async fn setup(app: &TestApp) -> (String, String, String) {
+    let token = app.register().await;
+    let me = app.get("/users/me", &token).await;
+    let email = me.body["data"]["email"].as_str().expect("email").to_string();
+
+    let generated = app.post("/users/me/inbound-address", &token, json!({})).await;
+    assert_eq!(generated.status, StatusCode::CREATED, "{}", generated.body);
+    let address = generated.body["data"]["address"].as_str().expect("address").to_string();
+    assert!(address.ends_with(&format!("@{}", INBOUND_EMAIL_DOMAIN)), "{}", address);
+
+    (token, email, address)
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn forwarded_receipts_become_pending_expenses_once() {
+    let app = TestApp::spawn().await;
+    let (token, email, address) = setup(&app).await;
+
+    let fetched = app.get("/users/me/inbound-address", &token).await;
+    assert_eq!(fetched.status, StatusCode::OK, "{}", fetched.body);
+    assert_eq!(fetched.body["data"]["address"], address.as_str());
+
+    let from = format!("Test User <{}>", email);
+    let received = post_mailgun(&app, &address, &from, "<receipt-1@mail.example.com>", MAILGUN_SIGNING_KEY, ALIGNED_DKIM).await;
+    assert_eq!(received.status, StatusCode::OK, "{}", received.body);
+    assert_eq!(received.body["data"]["status"], "created", "{}", received.body);
+    let transaction_id = received.body["data"]["transaction_id"].as_i64().expect("transaction id");
+
+    let transaction = app.get(&format!("/transactions/{}", transaction_id), &token).await;
+    assert_eq!(transaction.status, StatusCode::OK, "{}", transaction.body);
+    let transaction: &Value = &transaction.body["data"];
+    assert_eq!(transaction["status"], "pending");
+    assert_eq!(transaction["transaction_type"], "expense");
+    assert_eq!(transaction["description"], "Kopi Kenangan receipt");
+    assert_eq!(transaction["amount"], json!("45000.00"));
+
+    // Providers redeliver, so the same message must not create a second transaction
+    let again = post_mailgun(&app, &address, &from, "<receipt-1@mail.example.com>", MAILGUN_SIGNING_KEY, ALIGNED_DKIM).await;
+    assert_eq!(again.status, StatusCode::OK, "{}", again.body);
+    assert_eq!(again.body["data"]["status"], "duplicate", "{}", again.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn inbound_email_checks_the_signature_and_sender() {
+    let app = TestApp::spawn().await;
+    let (_, email, address) = setup(&app).await;
+
+    let forged = post_mailgun(&app, &address, &email, "<forged@mail.example.com>", "wrong-key", ALIGNED_DKIM).await;
+    assert_eq!(forged.status, StatusCode::UNAUTHORIZED, "{}", forged.body);
+
+    let stranger = post_mailgun(&app, &address, "someone@elsewhere.example", "<stranger@mail.example.com>", MAILGUN_SIGNING_KEY, ALIGNED_DKIM).await;
+    assert_eq!(stranger.status, StatusCode::OK, "{}", stranger.body);
+    assert_eq!(stranger.body["data"]["status"], "rejected", "{}", stranger.body);
+
+    // An SES notification whose sender failed DMARC is refused too
+    let notification = json!({
+        "notificationType": "Received",
+        "mail": {
+            "messageId": "ses-message-1",
+            "commonHeaders": { "from": [email], "subject": "Receipt" },
+        },
+        "receipt": {
+            "recipients": [address],
+            "spfVerdict": { "status": "FAIL" },
+            "dkimVerdict": { "status": "FAIL" },
+        },
+        "content": format!("Content-Type: text/plain\r\n\r\n{}", RECEIPT),
+    });
+    let envelope = json!({ "Type": "Notification", "Message": notification.to_string() }).to_string();
+
+    let unauthenticated = app.post_raw(SES_PATH, "text/plain", &[], envelope.clone()).await;
+    assert_eq!(unauthenticated.status, StatusCode::UNAUTHORIZED, "{}", unauthenticated.body);
+
+    let credentials = format!("Basic {}", STANDARD.encode(format!("ses:{}", SES_WEBHOOK_SECRET)));
+    let headers: [(HeaderName, &str); 1] = [(header::AUTHORIZATION, &credentials)];
+    let unverified = app.post_raw(SES_PATH, "text/plain", &headers, envelope).await;
+    assert_eq!(unverified.status, StatusCode::OK, "{}", unverified.body);
+    assert_eq!(unverified.body["data"]["status"], "rejected", "{}", unverified.body);
+    assert_eq!(unverified.body["data"]["reason"], "Sender failed DMARC and aligned DKIM checks");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn inbound_email_needs_dmarc_or_dkim_aligned_with_the_sender() {
+    let app = TestApp::spawn().await;
+    let (_, email, address) = setup(&app).await;
+
+    // SPF covers the envelope sender, which a forger picks freely
+    let spf_only = [("X-Mailgun-Spf", "Pass")];
+    let received = post_mailgun(&app, &address, &email, "<spf@mail.example.com>", MAILGUN_SIGNING_KEY, &spf_only).await;
+    assert_eq!(received.body["data"]["status"], "rejected", "{}", received.body);
+
+    let foreign_dkim = [
+        ("X-Mailgun-Dkim-Check-Result", "Pass"),
+        ("DKIM-Signature", "v=1; a=rsa-sha256; d=elsewhere.example; s=mail; bh=abc; b=def"),
+    ];
+    let received = post_mailgun(&app, &address, &email, "<foreign@mail.example.com>", MAILGUN_SIGNING_KEY, &foreign_dkim).await;
+    assert_eq!(received.body["data"]["status"], "rejected", "{}", received.body);
+
+    let ses = |message_id: &str, spf: &str, dmarc: &str| {
+        let notification = json!({
+            "notificationType": "Received",
+            "mail": {
+                "messageId": message_id,
+                "commonHeaders": { "from": [email], "subject": "Receipt" },
+            },
+            "receipt": {
+                "recipients": [address],
+                "spfVerdict": { "status": spf },
+                "dkimVerdict": { "status": "PASS" },
+                "dmarcVerdict": { "status": dmarc },
+            },
+            "content": format!("Content-Type: text/plain\r\n\r\n{}", RECEIPT),
+        });
+        json!({ "Type": "Notification", "Message": notification.to_string() }).to_string()
+    };
+    let credentials = format!("Basic {}", STANDARD.encode(format!("ses:{}", SES_WEBHOOK_SECRET)));
+    let headers: [(HeaderName, &str); 1] = [(header::AUTHORIZATION, &credentials)];
+
+    let unaligned = app.post_raw(SES_PATH, "text/plain", &headers, ses("ses-unaligned", "PASS", "FAIL")).await;
+    assert_eq!(unaligned.body["data"]["status"], "rejected", "{}", unaligned.body);
+
+    let aligned = app.post_raw(SES_PATH, "text/plain", &headers, ses("ses-aligned", "FAIL", "PASS")).await;
+    assert_eq!(aligned.status, StatusCode::OK, "{}", aligned.body);
+    assert_eq!(aligned.body["data"]["status"], "created", "{}", aligned.body);
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn devices_are_registered_once_per_token() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let other = app.register().await;
+
+    let device = json!({ "provider": "fcm", "token": "fcm-token-1", "name": "Pixel" });
+    let registered = app.post("/devices", &token, device.clone()).await;
+    assert_eq!(registered.status, StatusCode::CREATED, "{}", registered.body);
+    assert_eq!(registered.body["data"]["provider"], "fcm");
+    assert!(registered.body["data"].get("token").is_none(), "{}", registered.body);
+
+    // The token is a credential, so the row only holds it sealed
+    let sealed: Vec<u8> = sqlx::query_scalar("SELECT sealed_token FROM devices")
+        .fetch_one(app.pool())
+        .await
+        .expect("device row");
+    assert!(!sealed.windows(b"fcm-token-1".len()).any(|window| window == b"fcm-token-1"));
+
+    // Apps register again on every launch
+    let again = app.post("/devices", &token, device.clone()).await;
+    assert_eq!(again.status, StatusCode::CREATED, "{}", again.body);
+    assert_eq!(again.body["data"]["id"], registered.body["data"]["id"]);
+
+    let apns = app.post("/devices", &token, json!({ "provider": "apns", "token": "apns-token-1" })).await;
+    assert_eq!(apns.status, StatusCode::CREATED, "{}", apns.body);
+    let listed = app.get("/devices", &token).await;
+    assert_eq!(listed.body["data"].as_array().map(Vec::len), Some(2), "{}", listed.body);
+
+    // A phone that signs in to another account moves with it
+    let moved = app.post("/devices", &other, device).await;
+    assert_eq!(moved.status, StatusCode::CREATED, "{}", moved.body);
+    let listed = app.get("/devices", &token).await;
+    assert_eq!(listed.body["data"].as_array().map(Vec::len), Some(1), "{}", listed.body);
+
+    let path = format!("/devices/{}", apns.body["data"]["id"]);
+    let not_owner = app.request(Method::DELETE, &path, Some(&other), None).await;
+    assert_eq!(not_owner.status, StatusCode::NOT_FOUND);
+    let deleted = app.request(Method::DELETE, &path, Some(&token), None).await;
+    assert_eq!(deleted.status, StatusCode::NO_CONTENT);
+    let listed = app.get("/devices", &token).await;
+    assert_eq!(listed.body["data"].as_array().map(Vec::len), Some(0), "{}", listed.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn devices_need_a_known_provider_and_a_session() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let unknown = app.post("/devices", &token, json!({ "provider": "webpush", "token": "abc" })).await;
+    assert_eq!(unknown.status, StatusCode::BAD_REQUEST, "{}", unknown.body);
+    assert_eq!(unknown.body["error"]["code"], json!("VALIDATION_FAILED"));
+
+    let empty = app.post("/devices", &token, json!({ "provider": "fcm", "token": "" })).await;
+    assert_eq!(empty.status, StatusCode::BAD_REQUEST, "{}", empty.body);
+
+    let anonymous = app.request(Method::POST, "/devices", None, Some(json!({ "provider": "fcm", "token": "abc" }))).await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn notification_preferences_default_to_push() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let defaults = app.get("/notifications/preferences", &token).await;
+    assert_eq!(defaults.status, StatusCode::OK, "{}", defaults.body);
+    assert_eq!(
+        defaults.body["data"],
+        json!([{ "kind": "budget_alert", "push": true }, { "kind": "large_transaction", "push": true }])
+    );
+
+    let updated = app
+        .request(
+            Method::PUT,
+            "/notifications/preferences",
+            Some(&token),
+            Some(json!({ "preferences": [{ "kind": "large_transaction", "push": false }] })),
+        )
+        .await;
+    assert_eq!(updated.status, StatusCode::OK, "{}", updated.body);
+    assert_eq!(
+        updated.body["data"],
+        json!([{ "kind": "budget_alert", "push": true }, { "kind": "large_transaction", "push": false }])
+    );
+
+    let unknown = app
+        .request(
+            Method::PUT,
+            "/notifications/preferences",
+            Some(&token),
+            Some(json!({ "preferences": [{ "kind": "newsletter", "push": false }] })),
+        )
+        .await;
+    assert_eq!(unknown.status, StatusCode::BAD_REQUEST, "{}", unknown.body);
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn devices_are_refused_without_an_encryption_key() {
+    let app = TestApp::spawn_with(|config| config.encryption.key = None).await;
+    let token = app.register().await;
+
+    let registered = app.post("/devices", &token, json!({ "provider": "fcm", "token": "fcm-token-1" })).await;
+    assert_eq!(registered.status, StatusCode::BAD_REQUEST, "{}", registered.body);
+}
@@ -0,0 +1,317 @@
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::common::{TestApp, PASSWORD};
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn members_see_shared_pockets_that_stay_out_of_personal_lists() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let member = app.register().await;
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    assert_eq!(created.body["data"]["role"], json!("owner"));
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+
+    let me = app.get("/users/me", &member).await;
+    let email = me.body["data"]["email"].as_str().expect("member email").to_string();
+    let added = app
+        .post(&format!("/organizations/{}/members", org_id), &owner, json!({ "email": email, "role": "member" }))
+        .await;
+    assert_eq!(added.status, StatusCode::CREATED, "{}", added.body);
+
+    let pocket = app
+        .post(&format!("/organizations/{}/pockets", org_id), &owner, json!({ "name": "Payroll", "emoji": "🏢" }))
+        .await;
+    assert_eq!(pocket.status, StatusCode::CREATED, "{}", pocket.body);
+    let pocket_id = pocket.body["data"]["id"].clone();
+
+    let shared = app.get(&format!("/organizations/{}/pockets", org_id), &member).await;
+    assert_eq!(shared.status, StatusCode::OK, "{}", shared.body);
+    assert!(shared.body["data"].as_array().expect("pocket list").iter().any(|p| p["id"] == pocket_id));
+
+    let personal = app.get("/pockets", &owner).await;
+    assert!(!personal.body["data"].as_array().expect("pocket list").iter().any(|p| p["id"] == pocket_id));
+
+    // Plain members read shared records but can't create them
+    let denied = app
+        .post(&format!("/organizations/{}/pockets", org_id), &member, json!({ "name": "Mine", "emoji": "🙂" }))
+        .await;
+    assert_eq!(denied.status, StatusCode::FORBIDDEN, "{}", denied.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn organizations_are_hidden_from_non_members() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let stranger = app.register().await;
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+
+    let fetched = app.get(&format!("/organizations/{}", org_id), &stranger).await;
+    assert_eq!(fetched.status, StatusCode::NOT_FOUND, "{}", fetched.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn roles_decide_who_changes_shared_pockets_and_budgets() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let member = app.register().await;
+    let stranger = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+    let me = app.get("/users/me", &member).await;
+    let email = me.body["data"]["email"].as_str().expect("member email").to_string();
+    app.post(&format!("/organizations/{}/members", org_id), &owner, json!({ "email": email, "role": "member" }))
+        .await;
+
+    let pocket = app
+        .post(&format!("/organizations/{}/pockets", org_id), &owner, json!({ "name": "Payroll", "emoji": "🏢" }))
+        .await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+    let pocket_path = format!("/organizations/{}/pockets/{}", org_id, pocket_id);
+    let rename = json!({ "name": "Salaries" });
+
+    let refused = app.request(Method::PUT, &pocket_path, Some(&member), Some(rename.clone())).await;
+    assert_eq!(refused.status, StatusCode::FORBIDDEN, "{}", refused.body);
+    let hidden = app.request(Method::PUT, &pocket_path, Some(&stranger), Some(rename.clone())).await;
+    assert_eq!(hidden.status, StatusCode::NOT_FOUND, "{}", hidden.body);
+    let renamed = app.request(Method::PUT, &pocket_path, Some(&owner), Some(rename)).await;
+    assert_eq!(renamed.status, StatusCode::OK, "{}", renamed.body);
+    assert_eq!(renamed.body["data"]["name"], json!("Salaries"));
+
+    // Members record their spending in shared pockets, outsiders can't
+    let expense = json!({
+        "account_id": pocket_id,
+        "description": "Team lunch",
+        "amount": "40.00",
+        "category": "Food",
+        "transaction_type": "expense",
+        "transaction_date": today,
+    });
+    let spent = app.post("/transactions", &member, expense.clone()).await;
+    assert_eq!(spent.status, StatusCode::CREATED, "{}", spent.body);
+    let outsider = app.post("/transactions", &stranger, expense).await;
+    assert_eq!(outsider.status, StatusCode::NOT_FOUND, "{}", outsider.body);
+
+    let shared = app.get(&format!("/organizations/{}/pockets", org_id), &owner).await;
+    let balance = shared.body["data"]
+        .as_array()
+        .expect("pocket list")
+        .iter()
+        .find(|p| p["id"] == json!(pocket_id))
+        .map(|p| p["balance"].clone());
+    assert_eq!(balance, Some(json!("-40.00")), "{}", shared.body);
+
+    let budget = app
+        .post(
+            &format!("/organizations/{}/budgets", org_id),
+            &owner,
+            json!({
+                "category": "Food",
+                "target_amount": "500.00",
+                "period_type": "monthly",
+                "period_start": "2025-01-01",
+                "period_end": "2025-01-31",
+            }),
+        )
+        .await;
+    assert_eq!(budget.status, StatusCode::CREATED, "{}", budget.body);
+    let budget_path = format!("/organizations/{}/budgets/{}", org_id, budget.body["data"]["id"]);
+    let raise = json!({ "target_amount": "750.00" });
+
+    let refused = app.request(Method::PUT, &budget_path, Some(&member), Some(raise.clone())).await;
+    assert_eq!(refused.status, StatusCode::FORBIDDEN, "{}", refused.body);
+    let raised = app.request(Method::PUT, &budget_path, Some(&owner), Some(raise)).await;
+    assert_eq!(raised.status, StatusCode::OK, "{}", raised.body);
+    assert_eq!(raised.body["data"]["target_amount"], json!("750.00"));
+
+    let refused = app.request(Method::DELETE, &budget_path, Some(&member), None).await;
+    assert_eq!(refused.status, StatusCode::FORBIDDEN, "{}", refused.body);
+    let deleted = app.request(Method::DELETE, &budget_path, Some(&owner), None).await;
+    assert_eq!(deleted.status, StatusCode::NO_CONTENT, "{}", deleted.body);
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn shared_pockets_outlive_the_account_that_created_them() {
+    let app = TestApp::spawn_with(|config| config.account_deletion_grace_days = 0).await;
+    let owner = app.register().await;
+    let admin = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+    let me = app.get("/users/me", &admin).await;
+    let email = me.body["data"]["email"].as_str().expect("admin email").to_string();
+    app.post(&format!("/organizations/{}/members", org_id), &owner, json!({ "email": email, "role": "admin" }))
+        .await;
+
+    let pocket = app
+        .post(&format!("/organizations/{}/pockets", org_id), &admin, json!({ "name": "Payroll", "emoji": "🏢" }))
+        .await;
+    assert_eq!(pocket.status, StatusCode::CREATED, "{}", pocket.body);
+    let pocket_id = pocket.body["data"]["id"].clone();
+    let personal = app.post("/pockets", &admin, json!({ "name": "Mine", "emoji": "🙂" })).await;
+    assert_eq!(personal.status, StatusCode::CREATED, "{}", personal.body);
+
+    let spent = app
+        .post(
+            "/transactions",
+            &owner,
+            json!({
+                "account_id": pocket_id,
+                "description": "Team lunch",
+                "amount": "40.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": today,
+            }),
+        )
+        .await;
+    assert_eq!(spent.status, StatusCode::CREATED, "{}", spent.body);
+
+    let scheduled = app.request(Method::DELETE, "/users/me", Some(&admin), Some(json!({ "password": PASSWORD }))).await;
+    assert!(scheduled.status.is_success(), "{}", scheduled.body);
+    app.run_task("account_purge").await;
+
+    // The creator's personal pocket goes with their account
+    let personal_id = personal.body["data"]["id"].as_str().expect("pocket id").parse::<Uuid>().expect("uuid");
+    let personal_left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pockets WHERE id = $1")
+        .bind(personal_id)
+        .fetch_one(app.pool())
+        .await
+        .expect("count pockets");
+    assert_eq!(personal_left, 0);
+
+    let shared = app.get(&format!("/organizations/{}/pockets", org_id), &owner).await;
+    let balance = shared.body["data"]
+        .as_array()
+        .expect("pocket list")
+        .iter()
+        .find(|p| p["id"] == pocket_id)
+        .map(|p| p["balance"].clone());
+    assert_eq!(balance, Some(json!("-40.00")), "{}", shared.body);
+
+    let transaction = app.get(&format!("/transactions/{}", spent.body["data"]["id"]), &owner).await;
+    assert_eq!(transaction.body["data"]["account_id"], pocket_id, "{}", transaction.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn former_members_can_no_longer_change_shared_pocket_transactions() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let member = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+    let me = app.get("/users/me", &member).await;
+    let email = me.body["data"]["email"].as_str().expect("member email").to_string();
+    let member_id = me.body["data"]["id"].as_str().expect("member id").to_string();
+    app.post(&format!("/organizations/{}/members", org_id), &owner, json!({ "email": email, "role": "member" }))
+        .await;
+
+    let pocket = app
+        .post(&format!("/organizations/{}/pockets", org_id), &owner, json!({ "name": "Payroll", "emoji": "🏢" }))
+        .await;
+    let pocket_id = pocket.body["data"]["id"].clone();
+    let spent = app
+        .post(
+            "/transactions",
+            &member,
+            json!({
+                "account_id": pocket_id,
+                "description": "Team lunch",
+                "amount": "40.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": today,
+            }),
+        )
+        .await;
+    assert_eq!(spent.status, StatusCode::CREATED, "{}", spent.body);
+    let transaction_path = format!("/transactions/{}", spent.body["data"]["id"]);
+
+    let removed = app
+        .request(Method::DELETE, &format!("/organizations/{}/members/{}", org_id, member_id), Some(&owner), None)
+        .await;
+    assert!(removed.status.is_success(), "{}", removed.body);
+
+    let unassigned = app
+        .request(
+            Method::PUT,
+            &transaction_path,
+            Some(&member),
+            Some(json!({
+                "account_id": null,
+                "description": "Team lunch",
+                "amount": "40.00",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": today,
+            })),
+        )
+        .await;
+    assert_eq!(unassigned.status, StatusCode::NOT_FOUND, "{}", unassigned.body);
+    let deleted = app.request(Method::DELETE, &transaction_path, Some(&member), None).await;
+    assert_eq!(deleted.status, StatusCode::NOT_FOUND, "{}", deleted.body);
+
+    let shared = app.get(&format!("/organizations/{}/pockets", org_id), &owner).await;
+    let balance = shared.body["data"]
+        .as_array()
+        .expect("pocket list")
+        .iter()
+        .find(|p| p["id"] == pocket_id)
+        .map(|p| p["balance"].clone());
+    assert_eq!(balance, Some(json!("-40.00")), "{}", shared.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn the_last_owner_can_neither_step_down_nor_leave() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+    let me = app.get("/users/me", &owner).await;
+    let owner_id = me.body["data"]["id"].as_str().expect("owner id").to_string();
+    let member_path = format!("/organizations/{}/members/{}", org_id, owner_id);
+
+    let demoted = app
+        .request(Method::PUT, &member_path, Some(&owner), Some(json!({ "role": "admin" })))
+        .await;
+    assert_eq!(demoted.status, StatusCode::CONFLICT, "{}", demoted.body);
+    let left = app.request(Method::DELETE, &member_path, Some(&owner), None).await;
+    assert_eq!(left.status, StatusCode::CONFLICT, "{}", left.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn adding_a_member_does_not_reveal_whether_an_email_is_registered() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+
+    let created = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = created.body["data"]["id"].as_str().expect("organization id").to_string();
+    let me = app.get("/users/me", &owner).await;
+    let existing = me.body["data"]["email"].as_str().expect("owner email").to_string();
+    let unknown = format!("nobody-{}@example.com", Uuid::new_v4().simple());
+
+    let members_path = format!("/organizations/{}/members", org_id);
+    let already = app.post(&members_path, &owner, json!({ "email": existing, "role": "member" })).await;
+    let missing = app.post(&members_path, &owner, json!({ "email": unknown, "role": "member" })).await;
+    assert_eq!(already.status, StatusCode::CONFLICT, "{}", already.body);
+    assert_eq!(missing.status, already.status, "{}", missing.body);
+    assert_eq!(missing.body, already.body);
+}
@@ -0,0 +1,161 @@
+use axum::http::{header, Method, StatusCode};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn create_list_and_fetch_pocket() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app.post("/pockets", &token, json!({ "name": "Groceries", "emoji": "🛒" })).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let id = created.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    let list = app.get("/pockets", &token).await;
+    assert_eq!(list.status, StatusCode::OK, "{}", list.body);
+    let pockets = list.body["data"].as_array().expect("pocket list");
+    assert!(pockets.iter().any(|pocket| pocket["id"] == json!(id)));
+
+    let fetched = app.get(&format!("/pockets/{}", id), &token).await;
+    assert_eq!(fetched.status, StatusCode::OK, "{}", fetched.body);
+    assert_eq!(fetched.body["data"]["name"], json!("Groceries"));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn pockets_are_private_to_their_owner() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let stranger = app.register().await;
+
+    let created = app.post("/pockets", &owner, json!({ "name": "Savings", "emoji": "💰" })).await;
+    let id = created.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    let fetched = app.get(&format!("/pockets/{}", id), &stranger).await;
+    assert_eq!(fetched.status, StatusCode::NOT_FOUND, "{}", fetched.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn pocket_without_name_is_rejected() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app.post("/pockets", &token, json!({ "name": "", "emoji": "🛒" })).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST, "{}", response.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn concurrent_adjustments_all_apply() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let id = created.body["data"]["id"].as_str().expect("pocket id").to_string();
+    let path = format!("/pockets/{}/adjustments", id);
+    let adjustment = || json!({ "mode": "delta", "amount": "10.00", "reason": "Found cash" });
+
+    let (first, second, third) = tokio::join!(
+        app.post(&path, &token, adjustment()),
+        app.post(&path, &token, adjustment()),
+        app.post(&path, &token, adjustment()),
+    );
+    for response in [first, second, third] {
+        assert_eq!(response.status, StatusCode::CREATED, "{}", response.body);
+    }
+
+    let fetched = app.get(&format!("/pockets/{}", id), &token).await;
+    assert_eq!(fetched.body["data"]["balance"], json!("30.00"), "{}", fetched.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn stale_edits_are_rejected_instead_of_overwriting() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let id = created.body["data"]["id"].as_str().expect("pocket id").to_string();
+    let path = format!("/pockets/{}", id);
+    let first_read = created.body["data"]["updated_at"].clone();
+
+    let renamed = app
+        .request(Method::PUT, &path, Some(&token), Some(json!({ "name": "Cash", "version": first_read })))
+        .await;
+    assert_eq!(renamed.status, StatusCode::OK, "{}", renamed.body);
+
+    // A second device still holding the first read
+    let stale = app
+        .request(Method::PUT, &path, Some(&token), Some(json!({ "name": "Spending", "version": first_read })))
+        .await;
+    assert_eq!(stale.status, StatusCode::CONFLICT, "{}", stale.body);
+    assert_eq!(stale.body["error"]["code"], json!("STALE_VERSION"));
+
+    let modified_since = app
+        .request_with_headers(
+            Method::PUT,
+            &path,
+            Some(&token),
+            &[(header::IF_UNMODIFIED_SINCE, "Sat, 01 Jan 2000 00:00:00 GMT")],
+            Some(json!({ "name": "Spending" })),
+        )
+        .await;
+    assert_eq!(modified_since.status, StatusCode::CONFLICT, "{}", modified_since.body);
+
+    let unmodified = app
+        .request_with_headers(
+            Method::PUT,
+            &path,
+            Some(&token),
+            &[(header::IF_UNMODIFIED_SINCE, "Fri, 01 Jan 2100 00:00:00 GMT")],
+            Some(json!({ "emoji": "💵" })),
+        )
+        .await;
+    assert_eq!(unmodified.status, StatusCode::OK, "{}", unmodified.body);
+
+    let fetched = app.get(&path, &token).await;
+    assert_eq!(fetched.body["data"]["name"], json!("Cash"), "{}", fetched.body);
+    assert_eq!(fetched.body["data"]["emoji"], json!("💵"));
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn transactions_are_only_reassigned_to_a_pocket_in_the_same_currency() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let mut pockets = Vec::new();
+    for (name, currency) in [("Rupiah", "IDR"), ("Dollars", "USD"), ("More rupiah", "IDR")] {
+        let pocket = app.post("/pockets", &token, json!({ "name": name, "emoji": "👛", "currency": currency })).await;
+        assert_eq!(pocket.status, StatusCode::CREATED, "{}", pocket.body);
+        pockets.push(pocket.body["data"]["id"].as_str().expect("pocket id").to_string());
+    }
+    let income = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pockets[0],
+                "description": "Salary",
+                "amount": "50000.00",
+                "category": "Salary",
+                "transaction_type": "income",
+                "transaction_date": "2025-01-15",
+            }),
+        )
+        .await;
+    assert_eq!(income.status, StatusCode::CREATED, "{}", income.body);
+
+    let path = |target: &str| format!("/pockets/{}?reassign_to={}", pockets[0], target);
+    let refused = app.request(Method::DELETE, &path(&pockets[1]), Some(&token), None).await;
+    assert_eq!(refused.status, StatusCode::BAD_REQUEST, "{}", refused.body);
+    let dollars = app.get(&format!("/pockets/{}", pockets[1]), &token).await;
+    assert_eq!(dollars.body["data"]["balance"], json!("0"), "{}", dollars.body);
+
+    let moved = app.request(Method::DELETE, &path(&pockets[2]), Some(&token), None).await;
+    assert!(moved.status.is_success(), "{}", moved.body);
+    let rupiah = app.get(&format!("/pockets/{}", pockets[2]), &token).await;
+    assert_eq!(rupiah.body["data"]["balance"], json!("50000.00"), "{}", rupiah.body);
+}
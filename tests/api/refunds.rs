@@ -0,0 +1,39 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn refunds_are_received_as_a_valid_income_transaction() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+    let transaction = |transaction_type: &str, amount: &str| {
+        json!({
+            "description": "Headphones",
+            "amount": amount,
+            "category": "Shopping",
+            "transaction_type": transaction_type,
+            "transaction_date": today,
+        })
+    };
+
+    let expense = app.post("/transactions", &token, transaction("expense", "80.00")).await;
+    let expense_id = expense.body["data"]["id"].as_i64().expect("transaction id");
+    let refund = app
+        .post(&format!("/transactions/{}/refunds", expense_id), &token, json!({ "note": "Returned" }))
+        .await;
+    assert_eq!(refund.status, StatusCode::CREATED, "{}", refund.body);
+    let path = format!("/refunds/{}/receive", refund.body["data"]["id"]);
+
+    let invalid = app.post(&path, &token, json!({ "transaction_id": 0 })).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST, "{}", invalid.body);
+    assert_eq!(invalid.body["error"]["code"], json!("VALIDATION_FAILED"));
+
+    let credit = app.post("/transactions", &token, transaction("income", "80.00")).await;
+    let received = app.post(&path, &token, json!({ "transaction_id": credit.body["data"]["id"] })).await;
+    assert_eq!(received.status, StatusCode::OK, "{}", received.body);
+    assert_eq!(received.body["data"]["status"], json!("received"));
+}
\ No newline at end of file
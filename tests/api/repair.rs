@@ -0,0 +1,131 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn drifted_balances_are_reported_then_corrected() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let admin = app.register_admin().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let pocket = app.post("/pockets", &token, json!({ "name": "Wallet", "emoji": "👛" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+    let income = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Salary",
+                "amount": "100.00",
+                "category": "Salary",
+                "transaction_type": "income",
+                "transaction_date": today,
+            }),
+        )
+        .await;
+    assert_eq!(income.status, StatusCode::CREATED, "{}", income.body);
+
+    // A write that bypassed the balance bookkeeping
+    sqlx::query("UPDATE pockets SET balance = balance + 50 WHERE id = $1")
+        .bind(Uuid::parse_str(&pocket_id).expect("uuid"))
+        .execute(app.pool())
+        .await
+        .expect("inject drift");
+
+    let dry_run = app.post("/admin/repair/balances?dry_run=true", &admin, Value::Null).await;
+    assert_eq!(dry_run.status, StatusCode::OK, "{}", dry_run.body);
+    let corrections = dry_run.body["data"]["corrections"].as_array().expect("corrections");
+    assert_eq!(corrections.len(), 1, "{}", dry_run.body);
+    assert_eq!(corrections[0]["pocket_id"], json!(pocket_id));
+    assert_eq!(corrections[0]["drift"], json!("50.00"));
+
+    let fetched = app.get(&format!("/pockets/{}", pocket_id), &token).await;
+    assert_eq!(fetched.body["data"]["balance"], json!("150.00"), "{}", fetched.body);
+
+    let repaired = app.post("/admin/repair/balances", &admin, Value::Null).await;
+    assert_eq!(repaired.status, StatusCode::OK, "{}", repaired.body);
+    assert_eq!(repaired.body["data"]["corrections"][0]["balance"], json!("100.00"), "{}", repaired.body);
+
+    let fetched = app.get(&format!("/pockets/{}", pocket_id), &token).await;
+    assert_eq!(fetched.body["data"]["balance"], json!("100.00"), "{}", fetched.body);
+
+    let again = app.post("/admin/repair/balances", &admin, Value::Null).await;
+    assert_eq!(again.body["data"]["corrections"], json!([]), "{}", again.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn balance_repair_requires_an_admin() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app.post("/admin/repair/balances", &token, Value::Null).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN, "{}", response.body);
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn shared_pockets_are_replayed_from_every_members_transactions() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let member = app.register().await;
+    let admin = app.register_admin().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let org = app.post("/organizations", &owner, json!({ "name": "Acme" })).await;
+    let org_id = org.body["data"]["id"].as_str().expect("organization id").to_string();
+    let me = app.get("/users/me", &member).await;
+    let email = me.body["data"]["email"].as_str().expect("member email").to_string();
+    let added = app
+        .post(&format!("/organizations/{}/members", org_id), &owner, json!({ "email": email, "role": "member" }))
+        .await;
+    assert_eq!(added.status, StatusCode::CREATED, "{}", added.body);
+
+    let pocket = app
+        .post(&format!("/organizations/{}/pockets", org_id), &owner, json!({ "name": "Payroll", "emoji": "🏢" }))
+        .await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+    for (token, amount) in [(&owner, "100.00"), (&member, "40.00")] {
+        let income = app
+            .post(
+                "/transactions",
+                token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": "Deposit",
+                    "amount": amount,
+                    "category": "Salary",
+                    "transaction_type": "income",
+                    "transaction_date": today,
+                }),
+            )
+            .await;
+        assert_eq!(income.status, StatusCode::CREATED, "{}", income.body);
+    }
+
+    // The member's deposit counts, so an untouched shared pocket has nothing to correct
+    let clean = app.post("/admin/repair/balances?dry_run=true", &admin, Value::Null).await;
+    assert_eq!(clean.body["data"]["corrections"], json!([]), "{}", clean.body);
+
+    sqlx::query("UPDATE pockets SET balance = balance - 25 WHERE id = $1")
+        .bind(Uuid::parse_str(&pocket_id).expect("uuid"))
+        .execute(app.pool())
+        .await
+        .expect("inject drift");
+
+    let repaired = app.post("/admin/repair/balances", &admin, Value::Null).await;
+    assert_eq!(repaired.status, StatusCode::OK, "{}", repaired.body);
+    let corrections = repaired.body["data"]["corrections"].as_array().expect("corrections");
+    assert_eq!(corrections.len(), 1, "{}", repaired.body);
+    assert_eq!(corrections[0]["organization_id"], json!(org_id));
+    assert_eq!(corrections[0]["drift"], json!("-25.00"));
+    assert_eq!(corrections[0]["balance"], json!("140.00"));
+
+    let again = app.post("/admin/repair/balances", &admin, Value::Null).await;
+    assert_eq!(again.body["data"]["corrections"], json!([]), "{}", again.body);
+}
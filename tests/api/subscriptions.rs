@@ -0,0 +1,68 @@
+use axum::http::StatusCode;
+use chrono::{Months, Utc};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+// Four monthly charges from the same merchant, the latest a month ago
+async fn seed_monthly_charges(app: &TestApp, token: &str) {
+    let today = Utc::now().date_naive();
+
+    let pocket = app.post("/pockets", token, json!({ "name": "Card", "emoji": "💳" })).await;
+    let pocket_id = pocket.body["data"]["id"].as_str().expect("pocket id").to_string();
+
+    for months_ago in 1..=4 {
+        let date = today.checked_sub_months(Months::new(months_ago)).expect("date");
+        let charge = app
+            .post(
+                "/transactions",
+                token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": format!("STREAMFLIX *{}", months_ago * 1111),
+                    "amount": "12.99",
+                    "category": "Entertainment",
+                    "transaction_type": "expense",
+                    "transaction_date": date.format("%Y-%m-%d").to_string(),
+                }),
+            )
+            .await;
+        assert_eq!(charge.status, StatusCode::CREATED, "{}", charge.body);
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn monthly_charges_are_listed_as_a_subscription() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    seed_monthly_charges(&app, &token).await;
+
+    let response = app.get("/analytics/subscriptions", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let subscriptions = response.body["data"]["subscriptions"].as_array().expect("subscription list");
+    assert_eq!(subscriptions.len(), 1, "{}", response.body);
+    assert_eq!(subscriptions[0]["cadence"], json!("monthly"));
+    assert_eq!(subscriptions[0]["monthly_cost"], json!("12.99"));
+    assert_eq!(subscriptions[0]["charge_count"], json!(4));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn subscription_costs_are_masked_when_balances_are_hidden() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    seed_monthly_charges(&app, &token).await;
+    app.hide_balances(&token).await;
+
+    let response = app.get("/analytics/subscriptions", &token).await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let data = &response.body["data"];
+    assert_eq!(data["total_monthly_cost"], json!("***"), "{}", response.body);
+    assert_eq!(data["total_yearly_cost"], json!("***"));
+    let subscription = &data["subscriptions"][0];
+    for field in ["amount", "monthly_cost", "yearly_total", "previous_yearly_total"] {
+        assert_eq!(subscription[field], json!("***"), "{}: {}", field, response.body);
+    }
+    assert_eq!(subscription["charge_count"], json!(4));
+}
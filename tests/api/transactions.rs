@@ -0,0 +1,745 @@
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::{Value, json};
+
+use crate::common::TestApp;
+
+fn amount(value: &Value) -> f64 {
+    match value {
+        Value::String(s) => s.parse().expect("numeric string"),
+        other => other.as_f64().expect("numeric amount"),
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn transactions_move_the_pocket_balance() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let pocket = app
+        .post(
+            "/pockets",
+            &token,
+            json!({ "name": "Wallet", "emoji": "👛" }),
+        )
+        .await;
+    assert_eq!(pocket.status, StatusCode::CREATED, "{}", pocket.body);
+    let pocket_id = pocket.body["data"]["id"]
+        .as_str()
+        .expect("pocket id")
+        .to_string();
+
+    let income = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Salary",
+                "amount": "1000.00",
+                "category": "Salary",
+                "transaction_type": "income",
+                "transaction_date": today,
+            }),
+        )
+        .await;
+    assert_eq!(income.status, StatusCode::CREATED, "{}", income.body);
+
+    let expense = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "account_id": pocket_id,
+                "description": "Groceries",
+                "amount": "250.50",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": today,
+            }),
+        )
+        .await;
+    assert_eq!(expense.status, StatusCode::CREATED, "{}", expense.body);
+    assert_eq!(amount(&expense.body["data"]["amount"]), 250.5);
+
+    let fetched = app.get(&format!("/pockets/{}", pocket_id), &token).await;
+    assert_eq!(fetched.status, StatusCode::OK, "{}", fetched.body);
+    assert_eq!(amount(&fetched.body["data"]["balance"]), 749.5);
+
+    let list = app.get("/transactions", &token).await;
+    assert_eq!(list.status, StatusCode::OK, "{}", list.body);
+    assert_eq!(list.body["data"]["total_items"], json!(2));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn transaction_with_unknown_type_is_rejected() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "description": "Mystery",
+                "amount": "10.00",
+                "category": "Other",
+                "transaction_type": "transfer-ish",
+                "transaction_date": "2025-01-15",
+            }),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.body
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn negative_amount_contradicting_the_type_is_rejected() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let response = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "description": "Coffee",
+                "amount": "-4.50",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": "2025-01-15",
+            }),
+        )
+        .await;
+    assert_eq!(
+        response.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        response.body
+    );
+    assert_eq!(response.body["error"]["code"], json!("INVALID_AMOUNT"));
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn amount_must_fit_the_pocket_currency() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let pocket = app
+        .post(
+            "/pockets",
+            &token,
+            json!({ "name": "Tokyo", "emoji": "💴", "currency": "JPY" }),
+        )
+        .await;
+    assert_eq!(pocket.status, StatusCode::CREATED, "{}", pocket.body);
+    let pocket_id = pocket.body["data"]["id"]
+        .as_str()
+        .expect("pocket id")
+        .to_string();
+
+    let body = |amount: &str| {
+        json!({
+            "account_id": pocket_id,
+            "description": "Ramen",
+            "amount": amount,
+            "category": "Food",
+            "transaction_type": "expense",
+            "transaction_date": "2025-01-15",
+        })
+    };
+
+    let fractional = app.post("/transactions", &token, body("980.50")).await;
+    assert_eq!(
+        fractional.status,
+        StatusCode::BAD_REQUEST,
+        "{}",
+        fractional.body
+    );
+    assert_eq!(fractional.body["error"]["code"], json!("INVALID_AMOUNT"));
+
+    let whole = app.post("/transactions", &token, body("980")).await;
+    assert_eq!(whole.status, StatusCode::CREATED, "{}", whole.body);
+    assert_eq!(whole.body["data"]["amount"], json!("980.00"));
+}
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn amounts_are_formatted_for_the_user_locale() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let created = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "description": "Laptop",
+                "amount": "1234567.5",
+                "category": "Electronics",
+                "transaction_type": "expense",
+                "transaction_date": "2025-01-15",
+            }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    // New accounts default to en-US and their IDR base currency
+    assert_eq!(
+        created.body["data"]["formatted_amount"],
+        json!("Rp 1,234,567.50")
+    );
+
+    let preferences = app
+        .request(
+            Method::PUT,
+            "/users/me/preferences",
+            Some(&token),
+            Some(json!({ "currency": "USD", "locale": "id-ID", "week_start": "monday", "timezone": "Asia/Jakarta" })),
+        )
+        .await;
+    assert_eq!(preferences.status, StatusCode::OK, "{}", preferences.body);
+
+    let list = app.get("/transactions", &token).await;
+    assert_eq!(list.status, StatusCode::OK, "{}", list.body);
+    assert_eq!(
+        list.body["data"]["data"][0]["formatted_amount"],
+        json!("$ 1.234.567,50")
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn oversized_json_body_is_rejected() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    // Default JSON limit is 1 MiB
+    let created = app
+        .post(
+            "/transactions",
+            &token,
+            json!({
+                "description": "x".repeat(2 * 1024 * 1024),
+                "amount": "10",
+                "category": "Food",
+                "transaction_type": "expense",
+                "transaction_date": "2025-01-15",
+            }),
+        )
+        .await;
+    assert_eq!(
+        created.status,
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "{}",
+        created.body
+    );
+    assert_eq!(created.body["error"]["code"], json!("PAYLOAD_TOO_LARGE"));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn other_users_records_are_not_found() {
+    let app = TestApp::spawn().await;
+    let owner = app.register().await;
+    let stranger = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let pocket = app
+        .post(
+            "/pockets",
+            &owner,
+            json!({ "name": "Wallet", "emoji": "👛" }),
+        )
+        .await;
+    let pocket_id = pocket.body["data"]["id"]
+        .as_str()
+        .expect("pocket id")
+        .to_string();
+    let body = json!({
+        "account_id": pocket_id,
+        "description": "Coffee",
+        "amount": "5.00",
+        "category": "Food",
+        "transaction_type": "expense",
+        "transaction_date": today,
+    });
+    let created = app.post("/transactions", &owner, body.clone()).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let id = created.body["data"]["id"].as_i64().expect("transaction id");
+
+    // Reported the same as a record that doesn't exist, so ids can't be probed
+    let fetched = app.get(&format!("/transactions/{}", id), &stranger).await;
+    assert_eq!(fetched.status, StatusCode::NOT_FOUND, "{}", fetched.body);
+
+    let filtered = app
+        .get(
+            &format!("/transactions?account_id={}", pocket_id),
+            &stranger,
+        )
+        .await;
+    assert_eq!(filtered.status, StatusCode::NOT_FOUND, "{}", filtered.body);
+
+    let spent = app.post("/transactions", &stranger, body).await;
+    assert_eq!(spent.status, StatusCode::NOT_FOUND, "{}", spent.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn list_pages_carry_the_filtered_total() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    for (description, category) in [("Rent", "Housing"), ("Lunch", "Food"), ("Dinner", "Food")] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "description": description,
+                    "amount": "20.00",
+                    "category": category,
+                    "transaction_type": "expense",
+                    "transaction_date": "2025-01-15",
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    let last_page = app.get("/transactions?limit=2&page=2", &token).await;
+    assert_eq!(last_page.status, StatusCode::OK, "{}", last_page.body);
+    assert_eq!(
+        last_page.body["data"]["data"].as_array().map(Vec::len),
+        Some(1)
+    );
+    assert_eq!(last_page.body["data"]["total_items"], json!(3));
+
+    // Nothing comes back past the last page, yet the total is still reported
+    let past_the_end = app.get("/transactions?limit=2&page=5", &token).await;
+    assert_eq!(past_the_end.status, StatusCode::OK, "{}", past_the_end.body);
+    assert_eq!(
+        past_the_end.body["data"]["data"].as_array().map(Vec::len),
+        Some(0)
+    );
+    assert_eq!(past_the_end.body["data"]["total_items"], json!(3));
+
+    let filtered = app.get("/transactions?category=Food&limit=1", &token).await;
+    assert_eq!(filtered.status, StatusCode::OK, "{}", filtered.body);
+    assert_eq!(filtered.body["data"]["total_items"], json!(2));
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn archived_transactions_stay_readable_and_keep_balances_and_monthly_totals() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let admin = app.register_admin().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let pocket = app
+        .post(
+            "/pockets",
+            &token,
+            json!({ "name": "Wallet", "emoji": "👛" }),
+        )
+        .await;
+    let pocket_id = pocket.body["data"]["id"]
+        .as_str()
+        .expect("pocket id")
+        .to_string();
+
+    let mut ids = Vec::new();
+    for (amount, transaction_type, date) in [
+        ("100.00", "income", "2015-03-10"),
+        ("30.00", "expense", "2015-03-20"),
+        ("10.00", "expense", today.as_str()),
+    ] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "account_id": pocket_id,
+                    "description": "Entry",
+                    "amount": amount,
+                    "category": "General",
+                    "transaction_type": transaction_type,
+                    "transaction_date": date,
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+        ids.push(created.body["data"]["id"].clone());
+    }
+
+    // The test config archives anything older than five years
+    app.run_task("transaction_archive").await;
+
+    let listed = app.get("/transactions", &token).await;
+    assert_eq!(listed.status, StatusCode::OK, "{}", listed.body);
+    assert_eq!(
+        listed.body["data"]["total_items"],
+        json!(3),
+        "{}",
+        listed.body
+    );
+    let old = app
+        .get("/transactions?from_date=2015-01-01&to_date=2015-12-31", &token)
+        .await;
+    assert_eq!(old.body["data"]["total_items"], json!(2), "{}", old.body);
+
+    let fetched = app.get(&format!("/transactions/{}", ids[1]), &token).await;
+    assert_eq!(fetched.status, StatusCode::OK, "{}", fetched.body);
+    assert_eq!(fetched.body["data"]["transaction_date"], json!("2015-03-20"));
+
+    let archived: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transactions_archive_2015_03")
+        .fetch_one(app.pool())
+        .await
+        .expect("count archive partition");
+    assert_eq!(archived, 2);
+
+    let fetched = app.get(&format!("/pockets/{}", pocket_id), &token).await;
+    assert_eq!(
+        fetched.body["data"]["balance"],
+        json!("60.00"),
+        "{}",
+        fetched.body
+    );
+    let dry_run = app
+        .post("/admin/repair/balances?dry_run=true", &admin, Value::Null)
+        .await;
+    assert_eq!(
+        dry_run.body["data"]["corrections"],
+        json!([]),
+        "{}",
+        dry_run.body
+    );
+
+    let trend = app
+        .get(
+            "/expense-analytics/monthly-trend?from_date=2015-01-01&to_date=2015-12-31",
+            &token,
+        )
+        .await;
+    assert_eq!(trend.status, StatusCode::OK, "{}", trend.body);
+    assert_eq!(
+        trend.body["data"]["trends"],
+        json!([{ "period": "2015-03", "total_amount": "30.00", "transaction_count": 1 }])
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn exports_include_archived_months() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    for (description, date) in [
+        ("Old rent", "2015-03-01"),
+        ("Old groceries", "2015-03-20"),
+        ("Later groceries", "2015-04-02"),
+    ] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "description": description,
+                    "amount": "25.00",
+                    "category": "General",
+                    "transaction_type": "expense",
+                    "transaction_date": date,
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    app.run_task("transaction_archive").await;
+
+    let exported = app
+        .get(
+            "/exports/transactions?format=csv&from_date=2015-03-01&to_date=2015-03-31",
+            &token,
+        )
+        .await;
+    assert_eq!(exported.status, StatusCode::OK, "{}", exported.body);
+    let csv = exported.body.as_str().expect("csv body");
+    let rows: Vec<&str> = csv.lines().skip(1).collect();
+    assert_eq!(rows.len(), 2, "{}", csv);
+    assert!(rows[0].contains("2015-03-01,Old rent,"), "{}", csv);
+    assert!(rows[1].contains("2015-03-20,Old groceries,"), "{}", csv);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn analytics_include_archived_months() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    for (amount, transaction_type) in [
+        ("40.00", "expense"),
+        ("2.50", "expense"),
+        ("900.00", "income"),
+    ] {
+        let created = app
+            .post(
+                "/transactions",
+                &token,
+                json!({
+                    "description": "Entry",
+                    "amount": amount,
+                    "category": "General",
+                    "transaction_type": transaction_type,
+                    "transaction_date": "2015-03-10",
+                }),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    }
+
+    app.run_task("transaction_archive").await;
+    let hot: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transactions")
+        .fetch_one(app.pool())
+        .await
+        .expect("count hot transactions");
+    assert_eq!(hot, 0);
+
+    let range = "from_date=2015-03-01&to_date=2015-03-31";
+    let expenses = app
+        .get(&format!("/expense-analytics/summary?{}", range), &token)
+        .await;
+    assert_eq!(expenses.status, StatusCode::OK, "{}", expenses.body);
+    assert_eq!(
+        expenses.body["data"]["total_expenses"],
+        json!("42.50"),
+        "{}",
+        expenses.body
+    );
+    assert_eq!(
+        expenses.body["data"]["total_transactions"],
+        json!(2),
+        "{}",
+        expenses.body
+    );
+
+    let income = app
+        .get(&format!("/income-analytics/summary?{}", range), &token)
+        .await;
+    assert_eq!(income.status, StatusCode::OK, "{}", income.body);
+    assert_eq!(
+        income.body["data"]["total_transactions"],
+        json!(1),
+        "{}",
+        income.body
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn update_with_a_stale_version_leaves_the_transaction_alone() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    let edit = |description: &str, version: &Value| {
+        json!({
+            "description": description,
+            "amount": "12.00",
+            "category": "Food",
+            "transaction_type": "expense",
+            "transaction_date": "2025-01-15",
+            "version": version,
+        })
+    };
+    let created = app
+        .post("/transactions", &token, edit("Lunch", &Value::Null))
+        .await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let path = format!("/transactions/{}", created.body["data"]["id"]);
+    let first_read = created.body["data"]["updated_at"].clone();
+
+    let updated = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(&token),
+            Some(edit("Team lunch", &first_read)),
+        )
+        .await;
+    assert_eq!(updated.status, StatusCode::OK, "{}", updated.body);
+    assert_ne!(updated.body["data"]["updated_at"], first_read);
+
+    let stale = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(&token),
+            Some(edit("Dinner", &first_read)),
+        )
+        .await;
+    assert_eq!(stale.status, StatusCode::CONFLICT, "{}", stale.body);
+
+    let fetched = app.get(&path, &token).await;
+    assert_eq!(
+        fetched.body["data"]["description"],
+        json!("Team lunch"),
+        "{}",
+        fetched.body
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn foreign_currency_amounts_are_converted_at_the_rate_of_the_day() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+
+    sqlx::query(
+        "INSERT INTO exchange_rates (currency, rate_date, usd_rate)
+         VALUES ('EUR', '2025-01-01', 0.8), ('IDR', '2025-01-01', 16000)",
+    )
+    .execute(app.pool())
+    .await
+    .expect("seed exchange rates");
+
+    let pocket = app
+        .post(
+            "/pockets",
+            &token,
+            json!({ "name": "Wallet", "emoji": "👛", "currency": "IDR" }),
+        )
+        .await;
+    let pocket_id = pocket.body["data"]["id"]
+        .as_str()
+        .expect("pocket id")
+        .to_string();
+
+    let body = |currency: &str| {
+        json!({
+            "account_id": pocket_id,
+            "description": "Museum tickets",
+            "amount": "12.50",
+            "currency": currency,
+            "category": "Travel",
+            "transaction_type": "expense",
+            "transaction_date": "2025-01-15",
+        })
+    };
+
+    let created = app.post("/transactions", &token, body("EUR")).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.body);
+    let transaction = &created.body["data"];
+    assert_eq!(transaction["amount"], json!("250000.00"));
+    assert_eq!(transaction["original_amount"], json!("12.50"));
+    assert_eq!(transaction["original_currency"], json!("EUR"));
+    assert_eq!(transaction["exchange_rate"], json!("20000"));
+
+    // A rate published afterwards doesn't restate what was already recorded
+    sqlx::query("INSERT INTO exchange_rates (currency, rate_date, usd_rate) VALUES ('EUR', '2025-01-10', 0.5)")
+        .execute(app.pool())
+        .await
+        .expect("seed later rate");
+
+    let path = format!("/transactions/{}", transaction["id"]);
+    let fetched = app.get(&path, &token).await;
+    assert_eq!(
+        fetched.body["data"]["amount"],
+        json!("250000.00"),
+        "{}",
+        fetched.body
+    );
+
+    let pocket = app.get(&format!("/pockets/{}", pocket_id), &token).await;
+    assert_eq!(
+        amount(&pocket.body["data"]["balance"]),
+        -250000.0,
+        "{}",
+        pocket.body
+    );
+
+    let no_rate = app.post("/transactions", &token, body("GBP")).await;
+    assert_eq!(no_rate.status, StatusCode::BAD_REQUEST, "{}", no_rate.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn hard_limits_hold_for_concurrent_expenses_and_edits() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let today = Utc::now().date_naive().format("%Y-%m-%d").to_string();
+
+    let limit = app
+        .post("/spending-limits", &token, json!({ "category": "Food", "monthly_limit": "100.00", "enforcement": "hard" }))
+        .await;
+    assert_eq!(limit.status, StatusCode::CREATED, "{}", limit.body);
+
+    let expense = |amount: &str| {
+        json!({
+            "description": "Groceries",
+            "amount": amount,
+            "category": "Food",
+            "transaction_type": "expense",
+            "transaction_date": today,
+        })
+    };
+
+    // Each fits the limit alone, together they don't
+    let (first, second) = tokio::join!(
+        app.post("/transactions", &token, expense("60.00")),
+        app.post("/transactions", &token, expense("60.00")),
+    );
+    let mut statuses = [first.status, second.status];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::CREATED, StatusCode::CONFLICT], "{} / {}", first.body, second.body);
+
+    let created = if first.status == StatusCode::CREATED { first } else { second };
+    let path = format!("/transactions/{}", created.body["data"]["id"]);
+
+    let raised = app.request(Method::PUT, &path, Some(&token), Some(expense("120.00"))).await;
+    assert_eq!(raised.status, StatusCode::CONFLICT, "{}", raised.body);
+
+    // Edits that don't add spending pass, even up to the limit
+    let lowered = app.request(Method::PUT, &path, Some(&token), Some(expense("100.00"))).await;
+    assert_eq!(lowered.status, StatusCode::OK, "{}", lowered.body);
+    let mut rename = expense("100.00");
+    rename["description"] = json!("Market");
+    let renamed = app.request(Method::PUT, &path, Some(&token), Some(rename)).await;
+    assert_eq!(renamed.status, StatusCode::OK, "{}", renamed.body);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker or TEST_DATABASE_URL, see tests/api/main.rs"]
+async fn future_dated_expenses_count_toward_hard_limits() {
+    let app = TestApp::spawn().await;
+    let token = app.register().await;
+    let later = (Utc::now().date_naive() + chrono::Days::new(40)).format("%Y-%m-%d").to_string();
+
+    let limit = app
+        .post("/spending-limits", &token, json!({ "category": "Food", "monthly_limit": "100.00", "enforcement": "hard" }))
+        .await;
+    assert_eq!(limit.status, StatusCode::CREATED, "{}", limit.body);
+
+    let expense = json!({
+        "description": "Groceries",
+        "amount": "60.00",
+        "category": "Food",
+        "transaction_type": "expense",
+        "transaction_date": later,
+        "pending": true,
+    });
+
+    let first = app.post("/transactions", &token, expense.clone()).await;
+    assert_eq!(first.status, StatusCode::CREATED, "{}", first.body);
+    assert_eq!(first.body["data"]["status"], "pending");
+
+    // Otherwise both would post on the day, over the limit
+    let second = app.post("/transactions", &token, expense).await;
+    assert_eq!(second.status, StatusCode::CONFLICT, "{}", second.body);
+}